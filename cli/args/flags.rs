@@ -5,6 +5,7 @@ use clap::builder::FalseyValueParser;
 use clap::value_parser;
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgGroup;
 use clap::ArgMatches;
 use clap::ColorChoice;
 use clap::Command;
@@ -115,6 +116,14 @@ pub struct CheckFlags {
   pub files: Vec<String>,
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CleanFlags {
+  /// Prune the lockfile instead of removing the cache directory.
+  pub lock: bool,
+  pub dry_run: bool,
+  pub files: Vec<String>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CompileFlags {
   pub source_file: String,
@@ -194,6 +203,8 @@ pub struct DocFlags {
 pub struct EvalFlags {
   pub print: bool,
   pub code: String,
+  /// Start a REPL sharing the scope of the evaluated code once it finished.
+  pub repl: bool,
 }
 
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
@@ -319,6 +330,36 @@ pub struct RunFlags {
   pub script: String,
   pub watch: Option<WatchFlagsWithPaths>,
   pub bare: bool,
+  /// Modules that are executed in order before the main module
+  /// (`--preload`).
+  pub preload: Vec<String>,
+  /// Scripts that are run one after another in fresh workers once the main
+  /// module exited successfully (`--then`).
+  pub extra_scripts: Vec<String>,
+  /// Don't check for a newer version of Deno in the background. There is no
+  /// command line flag for this, it's meant for embedders of the cli crate.
+  pub skip_upgrade_check: bool,
+  /// Directory to write the V8 coverage of the run to (`--coverage`).
+  pub coverage_dir: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PrintConfigFlags {
+  pub json: bool,
+  /// Exit after printing instead of running the program.
+  pub only: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DumpAstFlags {
+  /// Dump every module of the graph instead of only the main module.
+  pub all: bool,
+  pub json: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DryRunFlags {
+  pub json: bool,
 }
 
 impl RunFlags {
@@ -328,6 +369,10 @@ impl RunFlags {
       script,
       watch: None,
       bare: false,
+      preload: vec![],
+      extra_scripts: vec![],
+      skip_upgrade_check: false,
+      coverage_dir: None,
     }
   }
 
@@ -368,9 +413,19 @@ pub struct WatchFlags {
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
 pub struct WatchFlagsWithPaths {
   pub hmr: bool,
+  /// Instead of patching changed modules in place, re-evaluate them and
+  /// their dependents so that top-level side effects run again. Only
+  /// meaningful together with `hmr`.
+  pub reevaluate: bool,
   pub paths: Vec<String>,
   pub no_clear_screen: bool,
   pub exclude: Vec<String>,
+  /// Stop watching once the program fails or exits with a nonzero code,
+  /// returning that code instead of waiting for a file change.
+  pub exit_on_error: bool,
+  /// Poll the watched files for changes every this many milliseconds
+  /// instead of relying on file system events (`--watch-poll`).
+  pub poll_interval: Option<u64>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -448,7 +503,7 @@ pub enum DenoSubcommand {
   Bundle(BundleFlags),
   Cache(CacheFlags),
   Check(CheckFlags),
-  Clean,
+  Clean(CleanFlags),
   Compile(CompileFlags),
   Completions(CompletionsFlags),
   Coverage(CoverageFlags),
@@ -540,6 +595,34 @@ impl Default for TypeCheckMode {
   }
 }
 
+/// How errors that make `deno run` fail are reported on stderr.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ErrorFormat {
+  #[default]
+  Pretty,
+  Json,
+}
+
+/// Whether Deno's own output is colored, set with `--color`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorMode {
+  /// Colored unless the `NO_COLOR` environment variable is set.
+  #[default]
+  Auto,
+  Always,
+  Never,
+}
+
+/// How the use of deprecated Deno APIs is handled, set with `--deprecation`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeprecationMode {
+  /// Log a warning on the first use of each API.
+  Warn,
+  /// Throw on every use.
+  Error,
+  Silent,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CaData {
   /// The string is a file path
@@ -565,6 +648,31 @@ pub enum PackagesAllowedScripts {
   None,
 }
 
+/// How the packages in a local `node_modules` directory are laid out.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum NodeModulesLayout {
+  #[default]
+  Default,
+  /// Names the folders of packages with peer dependencies after those peer
+  /// dependencies instead of the order the packages were resolved in, so the
+  /// same resolution always produces the same directory tree.
+  Deterministic,
+}
+
+/// What `deno run` does when a package.json dependency is missing from the
+/// `node_modules` directory managed by another package manager, or the
+/// package.json changed after that directory was last installed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum ByonmOnStale {
+  /// Fail before running the program.
+  #[default]
+  Error,
+  /// Print a warning and run the program anyway.
+  Warn,
+  /// Run the install command of the project's package manager first.
+  AutoInstall,
+}
+
 fn parse_packages_allowed_scripts(s: &str) -> Result<String, AnyError> {
   if !s.starts_with("npm:") {
     bail!("Invalid package for --allow-scripts: '{}'. An 'npm:' specifier is required", s);
@@ -599,34 +707,168 @@ pub struct Flags {
   /// the language server is configured with an explicit cache option.
   pub cache_path: Option<PathBuf>,
   pub cached_only: bool,
+  /// Fail with the list of resources and timers that are still open once the
+  /// program finishes.
+  pub check_resource_leaks: bool,
+  /// Report whether hrtime is granted and how often the program read the
+  /// high resolution clock once it finishes.
+  pub report_hrtime_usage: bool,
+  /// Load the main module and fill the caches without running it.
+  pub warmup: bool,
+  /// Log the exports of local modules that no module in the graph imports
+  /// before running the program.
+  pub report_unused_exports: bool,
+  /// Fail before running the program when its module graph has more than
+  /// this many modules.
+  pub max_graph_modules: Option<usize>,
+  /// Validate the config file against the config file schema before
+  /// running the program.
+  pub config_schema_validate: bool,
+  /// Record the random numbers, clock reads and `fetch()` responses of the
+  /// program to this file.
+  pub record_nondeterminism: Option<String>,
+  /// Feed the program the random numbers, clock reads and `fetch()`
+  /// responses recorded in this file instead of real ones.
+  pub replay_nondeterminism: Option<String>,
+  /// Deny permission prompts that aren't answered within this many seconds.
+  pub permissions_prompt_timeout: Option<u64>,
+  /// Resolve npm packages from the registry metadata and tarballs in this
+  /// file instead of the npm registry.
+  pub npm_snapshot: Option<String>,
+  /// Log the npm packages that have install scripts before running the
+  /// program.
+  pub detect_install_scripts: bool,
+  /// Fail when an npm package of the program has install scripts.
+  pub fail_on_install_scripts: bool,
+  /// Print the path of the bin script an `npm:` specifier resolves to
+  /// instead of running it.
+  pub print_bin: bool,
+  /// Print the fully resolved main module instead of running it.
+  pub print_entrypoint: bool,
+  /// Write the remote modules and npm packages the program loaded into this
+  /// directory, along with an import map that points at them.
+  pub vendor_out: Option<String>,
+  /// Time zone the program runs in, overriding the `TZ` environment variable.
+  /// It's applied by setting `TZ`, so `Deno.env` and subprocesses see it too.
+  pub tz: Option<String>,
+  /// Default locale of `Intl` and date formatting, overriding the one the
+  /// environment configures.
+  pub locale: Option<String>,
+  /// Only use HTTP/1.1 when downloading modules and packages.
+  pub module_http1_only: bool,
+  /// Fail module downloads and `fetch()` calls that read more than this many
+  /// bytes from the responses of a redirect chain.
+  pub max_redirect_bytes: Option<u64>,
+  /// Retry module downloads that fail with a 5xx status this many times.
+  pub module_fetch_retries: Option<u32>,
+  /// How many modules may be downloaded at once.
+  pub module_fetch_concurrency: Option<NonZeroUsize>,
+  /// How many modules may be downloaded at once from a single host.
+  pub module_fetch_concurrency_per_host: Option<NonZeroUsize>,
+  /// Also write what the program prints to stdout to this file.
+  pub tee_stdout: Option<String>,
+  /// Also write what the program prints to stderr to this file.
+  pub tee_stderr: Option<String>,
+  /// When set, `Deno.exit()` lets pending work run for up to this many
+  /// milliseconds before the process exits.
+  pub exit_drain: Option<u64>,
+  /// Path to a manifest of npm packages to download into the cache before
+  /// running.
+  pub warm_npm: Option<String>,
+  /// Print the configuration resolved from `deno.json`, `package.json` and
+  /// the flags before running.
+  pub print_config: Option<PrintConfigFlags>,
+  /// Print the AST of the main module, or its whole graph, and exit
+  /// (`--dump-ast`, `--dump-ast-all`).
+  pub dump_ast: Option<DumpAstFlags>,
+  /// Resolve the module graph and report it instead of running (`--dry-run`).
+  pub dry_run: Option<DryRunFlags>,
   pub type_check_mode: TypeCheckMode,
   pub config_flag: ConfigFlag,
+  /// Milliseconds after which a dynamic import that is still loading fails.
+  pub dynamic_import_timeout: Option<u64>,
   pub node_modules_dir: Option<bool>,
   pub vendor: Option<bool>,
+  pub nm_layout: Option<NodeModulesLayout>,
+  /// Install the platform specific npm packages of this operating system
+  /// instead of the host's (`--npm-target-os`).
+  pub npm_target_os: Option<String>,
+  /// Install the platform specific npm packages of this cpu architecture
+  /// instead of the host's (`--npm-target-cpu`).
+  pub npm_target_cpu: Option<String>,
   pub enable_op_summary_metrics: bool,
   pub enable_testing_features: bool,
   pub ext: Option<String>,
   pub ignore: Vec<String>,
   pub import_map_path: Option<String>,
-  pub env_file: Option<String>,
+  /// Environment files to load, later ones overriding earlier ones.
+  pub env_file: Option<Vec<String>>,
+  /// Let environment files override variables of the process environment
+  /// (`--env-file-override`).
+  pub env_file_override: bool,
+  pub error_format: ErrorFormat,
+  pub color: ColorMode,
+  /// How the use of deprecated Deno APIs is handled (`--deprecation`).
+  pub deprecation: Option<DeprecationMode>,
+  /// Exit when a warning matching this regular expression is logged
+  /// (`--abort-on-warning`).
+  pub abort_on_warning: Option<String>,
+  /// URL of an HTTP server that shares emitted modules between machines
+  /// (`--cache-backend`).
+  pub cache_backend: Option<String>,
+  /// Number of threads to type check the modules of the program on
+  /// (`--check-parallel`).
+  pub check_parallel: Option<NonZeroUsize>,
+  /// Write a V8 CPU profile of the main module to this file when it exits
+  /// (`--cpu-prof`).
+  pub cpu_prof: Option<String>,
+  /// URL scheme of the in-memory main module of `deno eval` and
+  /// `deno run -` (`--synthetic-scheme`).
+  pub synthetic_scheme: Option<String>,
+  /// Evaluate CommonJS modules in strict mode (`--force-strict`).
+  pub force_strict: bool,
+  /// How to handle an outdated `node_modules` directory when it is managed
+  /// by another package manager (`--byonm-on-stale`).
+  pub byonm_on_stale: Option<ByonmOnStale>,
+  /// With `--import-policy=deny`, the only specifiers modules may import,
+  /// as given with `--allow-import-spec`.
+  pub import_allow_list: Option<Vec<String>>,
   pub inspect_brk: Option<SocketAddr>,
   pub inspect_wait: Option<SocketAddr>,
   pub inspect: Option<SocketAddr>,
   pub location: Option<Url>,
   // TODO(bartlomieju): deprecated, to be removed in Deno 2.
   pub lock_write: bool,
+  /// Always write the lockfile with sorted keys and normalized whitespace
+  /// (`--lock-normalize`).
+  pub lock_normalize: bool,
   pub lock: Option<String>,
   pub log_level: Option<Level>,
+  /// Hide progress output like "Download" and "Initialize" lines without
+  /// lowering the log level (`--quiet=progress`).
+  pub quiet_progress: bool,
   pub no_remote: bool,
   pub no_lock: bool,
   pub no_npm: bool,
   pub reload: bool,
+  /// Authorization header values to send when fetching remote modules, as
+  /// `<host>=<value>` pairs.
+  pub remote_auth: Vec<String>,
   pub seed: Option<u64>,
   pub strace_ops: Option<Vec<String>>,
+  /// Path to write a Chrome trace format timeline of the run to.
+  pub trace_file: Option<String>,
   pub unstable_config: UnstableConfig,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub v8_flags: Vec<String>,
+  pub verify_npm_contents: bool,
+  /// Path to a JSON file mapping virtual paths to in-memory file contents
+  /// that are read before the real file system.
+  pub vfs_overlay: Option<String>,
   pub code_cache_enabled: bool,
+  /// Path of the V8 code cache database to use instead of the one in
+  /// `DENO_DIR`.
+  pub code_cache_path: Option<String>,
   pub permissions: PermissionFlags,
   pub allow_scripts: PackagesAllowedScripts,
 }
@@ -1011,25 +1253,7 @@ impl Flags {
   }
 
   pub fn has_permission_in_argv(&self) -> bool {
-    self.argv.iter().any(|arg| {
-      arg == "--allow-all"
-        || arg == "--allow-hrtime"
-        || arg == "--deny-hrtime"
-        || arg.starts_with("--allow-env")
-        || arg.starts_with("--deny-env")
-        || arg.starts_with("--allow-ffi")
-        || arg.starts_with("--deny-ffi")
-        || arg.starts_with("--allow-net")
-        || arg.starts_with("--deny-net")
-        || arg.starts_with("--allow-read")
-        || arg.starts_with("--deny-read")
-        || arg.starts_with("--allow-run")
-        || arg.starts_with("--deny-run")
-        || arg.starts_with("--allow-sys")
-        || arg.starts_with("--deny-sys")
-        || arg.starts_with("--allow-write")
-        || arg.starts_with("--deny-write")
-    })
+    self.argv.iter().any(|arg| is_permission_arg(arg))
   }
 
   #[inline(always)]
@@ -1045,6 +1269,22 @@ impl Flags {
     self.permissions.allow_hrtime = true;
   }
 
+  /// The interval to poll watched files at, when polling was requested
+  /// instead of file system events.
+  pub fn watch_poll_interval(&self) -> Option<std::time::Duration> {
+    match &self.subcommand {
+      DenoSubcommand::Run(RunFlags {
+        watch:
+          Some(WatchFlagsWithPaths {
+            poll_interval: Some(millis),
+            ..
+          }),
+        ..
+      }) => Some(std::time::Duration::from_millis(*millis)),
+      _ => None,
+    }
+  }
+
   pub fn resolve_watch_exclude_set(
     &self,
   ) -> Result<PathOrPatternSet, AnyError> {
@@ -1109,6 +1349,27 @@ impl Flags {
   }
 }
 
+/// Returns whether `arg` is a permission flag, like `--allow-read=.`.
+pub fn is_permission_arg(arg: &str) -> bool {
+  arg == "--allow-all"
+    || arg == "--allow-hrtime"
+    || arg == "--deny-hrtime"
+    || arg.starts_with("--allow-env")
+    || arg.starts_with("--deny-env")
+    || arg.starts_with("--allow-ffi")
+    || arg.starts_with("--deny-ffi")
+    || arg.starts_with("--allow-net")
+    || arg.starts_with("--deny-net")
+    || arg.starts_with("--allow-read")
+    || arg.starts_with("--deny-read")
+    || arg.starts_with("--allow-run")
+    || arg.starts_with("--deny-run")
+    || arg.starts_with("--allow-sys")
+    || arg.starts_with("--deny-sys")
+    || arg.starts_with("--allow-write")
+    || arg.starts_with("--deny-write")
+}
+
 static ENV_VARIABLES_HELP: &str = cstr!(
   r#"<y>Environment variables:</>
   <g>DENO_AUTH_TOKENS</>      A semi-colon separated list of bearer tokens and hostnames
@@ -1193,7 +1454,9 @@ pub fn flags_from_vec(args: Vec<OsString>) -> clap::error::Result<Flags> {
 
   let mut flags = Flags::default();
 
-  if matches.get_flag("quiet") {
+  let quiet = matches.get_one::<String>("quiet").map(String::as_str);
+  flags.quiet_progress = quiet == Some("progress");
+  if quiet == Some("all") {
     flags.log_level = Some(Level::Error);
   } else if let Some(log_level) = matches.get_one::<String>("log-level") {
     flags.log_level = match log_level.as_str() {
@@ -1485,8 +1748,15 @@ pub fn clap_root() -> Command {
       Arg::new("quiet")
         .short('q')
         .long("quiet")
-        .help("Suppress diagnostic output")
-        .action(ArgAction::SetTrue)
+        .num_args(0..=1)
+        .require_equals(true)
+        .value_name("LEVEL")
+        .value_parser(["all", "progress"])
+        .default_missing_value("all")
+        .help(cstr!(
+          "Suppress diagnostic output
+  <p(245)>--quiet=progress only hides download, initialization and other progress output</>"
+        ))
         .global(true),
     )
     .subcommand(run_subcommand())
@@ -1659,7 +1929,7 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
       .arg(watch_exclude_arg())
       .arg(no_clear_screen_arg())
       .arg(script_arg().last(true))
-      .arg(env_file_arg())
+      .args(env_file_args())
   })
 }
 
@@ -1720,9 +1990,37 @@ Future runs of this module will trigger no downloads or compilation unless
 fn clean_subcommand() -> Command {
   command(
     "clean",
-    "Remove the cache directory ($DENO_DIR)",
+    cstr!("Remove the cache directory ($DENO_DIR)
+
+With <c>--lock</>, removes the npm packages, jsr packages and remote modules from the lockfile
+that aren't reachable from the given files instead:
+  <p(245)>deno clean --lock main.ts</>
+  <p(245)>deno clean --lock --dry-run main.ts</>"),
     UnstableArgsConfig::None,
   )
+  .defer(|cmd| {
+    cmd
+      .arg(
+        Arg::new("lock")
+          .long("lock")
+          .help("Remove the lockfile entries that the given files don't use")
+          .action(ArgAction::SetTrue)
+          .requires("file"),
+      )
+      .arg(
+        Arg::new("dry-run")
+          .long("dry-run")
+          .help("Print the lockfile entries that would be removed without removing them")
+          .action(ArgAction::SetTrue)
+          .requires("lock"),
+      )
+      .arg(
+        Arg::new("file")
+          .num_args(1..)
+          .requires("lock")
+          .value_hint(ValueHint::FilePath),
+      )
+  })
 }
 
 fn check_subcommand() -> Command {
@@ -1752,6 +2050,7 @@ Unless --reload is specified, this command will not re-download already cached d
             .conflicts_with("no-remote")
             .hide(true)
         )
+        .arg(check_parallel_arg())
         .arg(
           Arg::new("file")
             .num_args(1..)
@@ -1840,7 +2139,7 @@ supported in canary.
           .help_heading(COMPILE_HEADING),
       )
       .arg(executable_ext_arg())
-      .arg(env_file_arg())
+      .args(env_file_args())
       .arg(
         script_arg()
           .required_unless_present("help")
@@ -2110,6 +2409,9 @@ fn eval_subcommand() -> Command {
 To evaluate as TypeScript:
   deno eval --ext=ts \"const v: string = 'hello'; console.log(v)\"
 
+To continue in a REPL with the evaluated bindings in scope:
+  deno eval --repl \"const answer = 6 * 7\"
+
 This command has implicit access to all permissions (--allow-all).",
           UnstableArgsConfig::ResolutionAndRuntime,
     )
@@ -2127,6 +2429,7 @@ This command has implicit access to all permissions (--allow-all).",
             .hide(true),
         )
         .arg(executable_ext_arg())
+        .arg(synthetic_scheme_arg())
         .arg(
           Arg::new("print")
             .long("print")
@@ -2134,6 +2437,13 @@ This command has implicit access to all permissions (--allow-all).",
             .help("print result to stdout")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("repl")
+            .long("repl")
+            .help("Start a REPL with the bindings of the evaluated code in scope")
+            .conflicts_with("print")
+            .action(ArgAction::SetTrue),
+        )
         .arg(
           Arg::new("code_arg")
             .num_args(1..)
@@ -2142,7 +2452,7 @@ This command has implicit access to all permissions (--allow-all).",
             .value_name("CODE_ARG")
             .required_unless_present("help"),
         )
-        .arg(env_file_arg())
+        .args(env_file_args())
     })
 }
 
@@ -2363,6 +2673,7 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
       .arg(no_npm_arg())
       .arg(lock_arg())
       .arg(lock_write_arg())
+      .arg(lock_normalize_arg())
       .arg(no_lock_arg())
       .arg(config_arg())
       .arg(import_map_arg())
@@ -2419,7 +2730,7 @@ fn install_args(cmd: Command, deno_future: bool) -> Command {
         .help("Install a package or script as a globally available executable")
         .action(ArgAction::SetTrue),
     )
-    .arg(env_file_arg())
+    .args(env_file_args())
 }
 
 fn future_install_subcommand() -> Command {
@@ -2730,7 +3041,7 @@ fn repl_subcommand() -> Command {
   <g>DENO_REPL_HISTORY</>  Set REPL history file path. History file is disabled when the value is empty.
                        <p(245)>[default: $DENO_DIR/deno_history.txt]</>"))
     )
-    .arg(env_file_arg())
+    .args(env_file_args())
 }
 
 fn run_args(command: Command, top_level: bool) -> Command {
@@ -2738,16 +3049,76 @@ fn run_args(command: Command, top_level: bool) -> Command {
     .arg(check_arg(false))
     .arg(watch_arg(true))
     .arg(hmr_arg(true))
+    .arg(watch_reevaluate_arg())
+    .arg(watch_exit_on_error_arg())
+    .arg(watch_poll_arg())
     .arg(watch_exclude_arg())
     .arg(no_clear_screen_arg())
     .arg(executable_ext_arg())
+    .arg(trace_arg())
+    .arg(verify_npm_contents_arg())
+    .arg(warm_npm_arg())
+    .arg(module_http1_only_arg())
+    .arg(max_redirect_bytes_arg())
+    .arg(module_fetch_retries_arg())
+    .args(module_fetch_concurrency_args())
+    .arg(vfs_overlay_arg())
+    .arg(error_format_arg())
+    .arg(color_arg())
+    .arg(deprecation_arg())
+    .arg(check_resource_leaks_arg())
+    .arg(exit_drain_arg())
+    .args(tee_args())
+    .arg(report_hrtime_usage_arg())
+    .arg(warmup_arg())
+    .arg(report_unused_exports_arg())
+    .arg(max_graph_modules_arg())
+    .arg(config_schema_validate_arg())
+    .args(nondeterminism_args())
+    .arg(permissions_prompt_timeout_arg())
+    .arg(npm_snapshot_arg())
+    .args(install_scripts_args())
+    .arg(print_bin_arg())
+    .arg(print_entrypoint_arg())
+    .arg(vendor_out_arg())
+    .arg(preload_arg())
+    .arg(then_arg())
+    .arg(cache_backend_arg())
+    .arg(abort_on_warning_arg())
+    .arg(check_parallel_arg())
+    .arg(cpu_prof_arg())
+    .arg(run_coverage_arg())
+    .arg(synthetic_scheme_arg())
+    .arg(force_strict_arg())
+    .arg(byonm_on_stale_arg())
+    .args(import_policy_args())
+    .args(tz_and_locale_args())
+    .args(print_config_args())
+    .group(
+      ArgGroup::new("print-config-group")
+        .args(["print-config", "print-config-only"]),
+    )
+    .args(dump_ast_args())
+    .arg(dry_run_arg())
+    .group(
+      ArgGroup::new("json-output-group")
+        .args([
+          "print-config",
+          "print-config-only",
+          "dump-ast",
+          "dump-ast-all",
+          "dry-run",
+        ])
+        .multiple(true),
+    )
     .arg(if top_level {
       script_arg().trailing_var_arg(true).hide(true)
     } else {
       script_arg().trailing_var_arg(true)
     })
-    .arg(env_file_arg())
+    .args(env_file_args())
     .arg(no_code_cache_arg())
+    .arg(code_cache_arg())
 }
 
 fn run_subcommand() -> Command {
@@ -2805,8 +3176,9 @@ fn serve_subcommand() -> Command {
         .required_unless_present_any(["help", "v8-flags"])
         .trailing_var_arg(true),
     )
-    .arg(env_file_arg())
+    .args(env_file_args())
     .arg(no_code_cache_arg())
+    .arg(code_cache_arg())
     .about("Run a server defined in a main module
 
 The serve command uses the default exports of the main module to determine which
@@ -3014,7 +3386,7 @@ Directory arguments are expanded to all contained files matching the glob
           .help("Hide stack traces for errors in failure test results.")
           .action(ArgAction::SetTrue)
       )
-      .arg(env_file_arg())
+      .args(env_file_args())
     )
 }
 
@@ -3244,12 +3616,15 @@ fn compile_args_without_check_args(app: Command) -> Command {
     .arg(no_remote_arg())
     .arg(no_npm_arg())
     .arg(node_modules_dir_arg())
+    .arg(nm_layout_arg())
+    .args(npm_target_args())
     .arg(vendor_arg())
     .arg(config_arg())
     .arg(no_config_arg())
     .arg(reload_arg())
     .arg(lock_arg())
     .arg(lock_write_arg())
+    .arg(lock_normalize_arg())
     .arg(no_lock_arg())
     .arg(ca_file_arg())
     .arg(unsafely_ignore_certificate_errors_arg())
@@ -3263,18 +3638,19 @@ Docs: <c>https://docs.deno.com/go/permissions</>
   <g>-A, --allow-all</>                        Allow all permissions.
   <g>--no-prompt</>                        Always throw if required permission wasn't passed.
                                            <p(245)>Can also be set via the DENO_NO_PROMPT environment variable.</>
-  <g>-R, --allow-read[=<<PATH>...]</>           Allow file system read access. Optionally specify allowed paths.
-                                           <p(245)>--allow-read  |  --allow-read="/etc,/var/log.txt"</>
+  <g>-R, --allow-read[=<<PATH>...]</>           Allow file system read access. Optionally specify allowed paths or globs.
+                                           <p(245)>--allow-read  |  --allow-read="/etc,/var/log.txt"  |  --allow-read="**/*.json"</>
   <g>-W, --allow-write[=<<PATH>...]</>          Allow file system write access. Optionally specify allowed paths.
-                                           <p(245)>--allow-write  |  --allow-write="/etc,/var/log.txt"</>
+                                           <p(245)>--allow-write  |  --allow-write="/etc,/var/log.txt"  |  --allow-write="/tmp:quota=50MB"</>
   <g>-N, --allow-net[=<<IP_OR_HOSTNAME>...]</>  Allow network access. Optionally specify allowed IP addresses and host names, with ports as necessary.
-                                           <p(245)>--allow-net  |  --allow-net="localhost:8080,deno.land"</>
+                                           <p(245)>--allow-net  |  --allow-net="localhost:8080,deno.land"  |  --allow-net="api.example.com:bytes=10MB"</>
   <g>-E, --allow-env[=<<VARIABLE_NAME>...]</>   Allow access to environment variables. Optionally specify accessible environment variables.
                                            <p(245)>--allow-env  |  --allow-env="PORT,HOME,PATH"</>
   <g>-S, --allow-sys[=<<API_NAME>...]</>        Allow access to OS information. Optionally allow specific APIs by function name.
                                            <p(245)>--allow-sys  |  --allow-sys="systemMemoryInfo,osRelease"</>
-      <g>--allow-run[=<<PROGRAM_NAME>...]</>    Allow running subprocesses. Optionally specify allowed runnable program names.
-                                           <p(245)>--allow-run  |  --allow-run="whoami,ps"</>
+      <g>--allow-run[=<<PROGRAM_NAME>...]</>    Allow running subprocesses. Optionally specify allowed runnable program names,
+                                           restricted to leading arguments with <<PROGRAM_NAME>:<<ARGS>.
+                                           <p(245)>--allow-run  |  --allow-run="whoami,ps"  |  --allow-run="git:status,git:log"</>
       <g>--allow-ffi[=<<PATH>...]</>            (Unstable) Allow loading dynamic libraries. Optionally specify allowed directories or files.
                                            <p(245)>--allow-ffi  |  --allow-ffi="./libfoo.so"</>
       <g>--allow-hrtime</>                     Allow high-resolution time measurement. Note: this can enable timing attacks and fingerprinting.
@@ -3312,7 +3688,7 @@ Docs: <c>https://docs.deno.com/go/permissions</>
         .use_value_delimiter(true)
         .require_equals(true)
         .value_name("PATH")
-        .help("Allow file system read access. Optionally specify allowed paths")
+        .help("Allow file system read access. Optionally specify allowed paths or globs")
         .value_parser(value_parser!(String))
         .value_hint(ValueHint::AnyPath)
         .hide(true),
@@ -3529,6 +3905,8 @@ fn runtime_args(
   app
     .arg(frozen_lockfile_arg())
     .arg(cached_only_arg())
+    .arg(remote_auth_arg())
+    .arg(dynamic_import_timeout_arg())
     .arg(location_arg())
     .arg(v8_flags_arg())
     .arg(seed_arg())
@@ -3590,20 +3968,31 @@ fn import_map_arg() -> Arg {
     .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
 }
 
-fn env_file_arg() -> Arg {
-  Arg::new("env-file")
-    .long("env-file")
-    .alias("env")
-    .value_name("FILE")
-    .help(cstr!(
-      "Load environment variables from local file
-  <p(245)>Only the first environment variable with a given key is used.
+fn env_file_args() -> [Arg; 2] {
+  [
+    Arg::new("env-file")
+      .long("env-file")
+      .alias("env")
+      .value_name("FILE")
+      .help(cstr!(
+        "Load environment variables from local file, can be repeated
+  <p(245)>Later files override variables of earlier ones. {NAME} in a file name is replaced
+  with the value of the environment variable NAME, e.g. --env-file=.env.{NODE_ENV}.
+  A directory loads the .env files it contains and a glob the files it matches,
+  alphabetically with the files ending in .local last.
   Existing process environment variables are not overwritten.</>"
-    ))
-    .value_hint(ValueHint::FilePath)
-    .default_missing_value(".env")
-    .require_equals(true)
-    .num_args(0..=1)
+      ))
+      .value_hint(ValueHint::FilePath)
+      .default_missing_value(".env")
+      .require_equals(true)
+      .num_args(0..=1)
+      .action(ArgAction::Append),
+    Arg::new("env-file-override")
+      .long("env-file-override")
+      .requires("env-file")
+      .action(ArgAction::SetTrue)
+      .help("Let environment files override existing process environment variables"),
+  ]
 }
 
 fn reload_arg() -> Arg {
@@ -3642,6 +4031,37 @@ fn cached_only_arg() -> Arg {
     .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
 }
 
+fn remote_auth_arg() -> Arg {
+  Arg::new("remote-auth")
+    .long("remote-auth")
+    .value_name("HOST=AUTHORIZATION")
+    .action(ArgAction::Append)
+    .value_parser(|value: &str| match value.split_once('=') {
+      Some((host, auth)) if !host.is_empty() && !auth.is_empty() => {
+        Ok(value.to_string())
+      }
+      _ => Err(format!(
+        "Expected a value in the form <host>=<authorization>, got '{value}'"
+      )),
+    })
+    .help(cstr!(
+      "Send an Authorization header when fetching remote modules from a host
+  <p(245)>Can be repeated. Takes precedence over DENO_AUTH_TOKENS.
+  Example: --remote-auth='deno.land=Bearer abc123'</>"
+    ))
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
+
+fn dynamic_import_timeout_arg() -> Arg {
+  Arg::new("dynamic-import-timeout")
+    .long("dynamic-import-timeout")
+    .value_name("MILLISECONDS")
+    .require_equals(true)
+    .value_parser(value_parser!(u64).range(1..))
+    .help("Reject dynamic imports that take longer than this to load")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
+
 fn frozen_lockfile_arg() -> Arg {
   Arg::new("frozen")
     .long("frozen")
@@ -3718,2457 +4138,4845 @@ fn v8_flags_arg() -> Arg {
   Any flags set with this flag are appended after the DENO_V8_FLAGS environment variable</>"))
 }
 
-fn seed_arg() -> Arg {
-  Arg::new("seed")
-    .long("seed")
-    .value_name("NUMBER")
-    .help("Set the random number generator seed")
-    .value_parser(value_parser!(u64))
+fn verify_npm_contents_arg() -> Arg {
+  Arg::new("verify-npm-contents")
+    .long("verify-npm-contents")
+    .action(ArgAction::SetTrue)
+    .help("Before running, verify that the extracted files of npm packages were not modified and no files were added")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
 }
 
-fn hmr_arg(takes_files: bool) -> Arg {
-  let arg = Arg::new("hmr")
-    .long("watch-hmr")
-    // NOTE(bartlomieju): compatibility with Deno pre-1.46
-    .alias("unstable-hmr")
-    .help("Watch for file changes and hot replace modules")
-    .conflicts_with("watch")
-    .help_heading(FILE_WATCHING_HEADING);
+fn module_http1_only_arg() -> Arg {
+  Arg::new("module-http-h1-only")
+    .long("module-http-h1-only")
+    .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "Only use HTTP/1.1 when downloading modules and packages
+  <p(245)>fetch() in the program can still use HTTP/2</>"
+    ))
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-  if takes_files {
-    arg
-      .value_name("FILES")
-      .num_args(0..)
-      .value_parser(value_parser!(String))
-      .use_value_delimiter(true)
-      .require_equals(true)
-      .help(
-        cstr!(
-        "Watch for file changes and restart process automatically.
-  <p(245)>Local files from entry point module graph are watched by default.
-  Additional paths might be watched by passing them as arguments to this flag.</>"),
-      )
-      .value_hint(ValueHint::AnyPath)
-  } else {
-    arg.action(ArgAction::SetTrue).help(cstr!(
-      "Watch for file changes and restart process automatically.
-  <p(245)>Only local files from entry point module graph are watched.</>"
+fn max_redirect_bytes_arg() -> Arg {
+  Arg::new("max-redirect-bytes")
+    .long("max-redirect-bytes")
+    .value_name("BYTES")
+    .value_parser(value_parser!(u64))
+    .help(cstr!(
+      "Fail module downloads and <c>fetch()</> calls that read more than this many bytes from the responses of a redirect chain
+  <p(245)>Responses that were not redirected to are not limited</>"
     ))
-  }
 }
 
-fn watch_arg(takes_files: bool) -> Arg {
-  let arg = Arg::new("watch")
-    .long("watch")
-    .help_heading(FILE_WATCHING_HEADING);
+fn module_fetch_retries_arg() -> Arg {
+  Arg::new("module-fetch-retries")
+    .long("module-fetch-retries")
+    .value_name("N")
+    .value_parser(value_parser!(u32))
+    .help(cstr!(
+      "Retry module downloads that fail with a 5xx status up to this many times
+  <p(245)>Waits longer before every retry. Doesn't affect fetch() calls</>"
+    ))
+}
 
-  if takes_files {
-    arg
-      .value_name("FILES")
-      .num_args(0..)
-      .value_parser(value_parser!(String))
-      .use_value_delimiter(true)
-      .require_equals(true)
-      .help(
-        cstr!(
-        "Watch for file changes and restart process automatically.
-  <p(245)>Local files from entry point module graph are watched by default.
-  Additional paths might be watched by passing them as arguments to this flag.</>"),
-      )
-      .value_hint(ValueHint::AnyPath)
-  } else {
-    arg.action(ArgAction::SetTrue).help(cstr!(
-      "Watch for file changes and restart process automatically.
-  <p(245)>Only local files from entry point module graph are watched.</>"
+fn module_fetch_concurrency_args() -> [Arg; 2] {
+  [
+    Arg::new("module-fetch-concurrency")
+      .long("module-fetch-concurrency")
+      .value_name("N")
+      .value_parser(value_parser!(NonZeroUsize))
+      .help(cstr!(
+        "Download at most this many modules at once
+  <p(245)>Doesn't affect fetch() calls</>"
+      )),
+    Arg::new("module-fetch-concurrency-per-host")
+      .long("module-fetch-concurrency-per-host")
+      .value_name("N")
+      .value_parser(value_parser!(NonZeroUsize))
+      .help("Download at most this many modules at once from a single host"),
+  ]
+}
+
+fn warm_npm_arg() -> Arg {
+  Arg::new("warm-npm")
+    .long("warm-npm")
+    .value_name("MANIFEST")
+    .help(cstr!(
+      "Download, verify and extract the npm packages listed in a manifest into the cache, without resolving any modules
+  <p(245)>The script may be omitted, in which case Deno exits after filling the cache</>"
     ))
-  }
+    .value_hint(ValueHint::FilePath)
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
 }
 
-fn no_clear_screen_arg() -> Arg {
-  Arg::new("no-clear-screen")
-    .requires("watch")
-    .long("no-clear-screen")
+fn check_resource_leaks_arg() -> Arg {
+  Arg::new("check-resource-leaks")
+    .long("check-resource-leaks")
     .action(ArgAction::SetTrue)
-    .help("Do not clear terminal screen when under watch mode")
-    .help_heading(FILE_WATCHING_HEADING)
+    .help(cstr!(
+      "Exit with an error listing the resources that are still open when the program finishes
+  <p(245)>Reports open files, sockets and timers, like the sanitizers of deno test</>"
+    ))
 }
 
-fn no_code_cache_arg() -> Arg {
-  Arg::new("no-code-cache")
-    .long("no-code-cache")
-    .help("Disable V8 code cache feature")
+fn print_bin_arg() -> Arg {
+  Arg::new("print-bin")
+    .long("print-bin")
     .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "Install the package of an npm: specifier and print the absolute path of its bin script instead of running it
+  <p(245)>deno run --print-bin npm:cowsay</>"
+    ))
 }
 
-fn watch_exclude_arg() -> Arg {
-  Arg::new("watch-exclude")
-    .long("watch-exclude")
-    .help("Exclude provided files/patterns from watch mode")
-    .value_name("FILES")
-    .num_args(0..)
-    .value_parser(value_parser!(String))
-    .use_value_delimiter(true)
-    .require_equals(true)
-    .value_hint(ValueHint::AnyPath)
-    .help_heading(FILE_WATCHING_HEADING)
+fn print_entrypoint_arg() -> Arg {
+  Arg::new("print-entrypoint")
+    .long("print-entrypoint")
+    .action(ArgAction::SetTrue)
+    .conflicts_with("print-bin")
+    .help(cstr!(
+      "Print the absolute specifier of the module that would be executed instead of running it
+  <p(245)>npm: specifiers print the url of their bin script</>"
+    ))
 }
 
-fn no_check_arg() -> Arg {
-  Arg::new("no-check")
-    .num_args(0..=1)
+fn abort_on_warning_arg() -> Arg {
+  Arg::new("abort-on-warning")
+    .long("abort-on-warning")
+    .value_name("PATTERN")
     .require_equals(true)
-    .value_name("NO_CHECK_TYPE")
-    .long("no-check")
-    .help("Skip type-checking. If the value of \"remote\" is supplied, diagnostic errors from remote modules will be ignored")
-    .help_heading(TYPE_CHECKING_HEADING)
+    .value_parser(|pattern: &str| {
+      regex::Regex::new(pattern)
+        .map(|_| pattern.to_string())
+        .map_err(|err| format!("Invalid pattern: {err}"))
+    })
+    .help(cstr!(
+      "Exit with an error when a warning matching a regular expression is logged
+  <p(245)>For example --abort-on-warning=deprecated fails on deprecated API warnings only</>"
+    ))
 }
 
-fn check_arg(checks_local_by_default: bool) -> Arg {
-  let arg = Arg::new("check")
-    .conflicts_with("no-check")
-    .long("check")
+fn check_parallel_arg() -> Arg {
+  Arg::new("check-parallel")
+    .long("check-parallel")
     .num_args(0..=1)
     .require_equals(true)
-    .value_name("CHECK_TYPE")
-    .help_heading(TYPE_CHECKING_HEADING);
-
-  if checks_local_by_default {
-    arg.help(
-      cstr!("Set type-checking behavior. This subcommand type-checks local modules by default, so adding --check is redundant
-  <p(245)>If the value of \"all\" is supplied, remote modules will be included.
-  Alternatively, the 'deno check' subcommand can be used</>",
-    ))
-  } else {
-    arg.help(cstr!(
-      "Enable type-checking. This subcommand does not type-check by default
-  <p(245)>If the value of \"all\" is supplied, remote modules will be included.
-  Alternatively, the 'deno check' subcommand can be used</>"
+    .value_name("THREADS")
+    .value_parser(value_parser!(NonZeroUsize))
+    .help(cstr!(
+      "Split type checking of the program across several threads
+  <p(245)>Modules that import each other are checked on the same thread. Defaults to one thread per available core</>"
     ))
-  }
 }
 
-fn script_arg() -> Arg {
-  Arg::new("script_arg")
-    .num_args(0..)
-    .action(ArgAction::Append)
-    // NOTE: these defaults are provided
-    // so `deno run --v8-flags=--help` works
-    // without specifying file to run.
-    .default_value_ifs([
-      ("v8-flags", "--help", Some("_")),
-      ("v8-flags", "-help", Some("_")),
-    ])
-    .help("Script arg")
-    .value_name("SCRIPT_ARG")
+fn cpu_prof_arg() -> Arg {
+  Arg::new("cpu-prof")
+    .long("cpu-prof")
+    .value_name("FILE")
+    .num_args(0..=1)
+    .require_equals(true)
+    .help(cstr!(
+      "Write a V8 CPU profile of the program to a file when it exits
+  <p(245)>Defaults to CPU.<<timestamp>>.<<pid>>.cpuprofile. The file can be loaded in Chrome DevTools</>"
+    ))
     .value_hint(ValueHint::FilePath)
 }
 
-fn lock_arg() -> Arg {
-  Arg::new("lock")
-    .long("lock")
-    .value_name("FILE")
-    .default_missing_value("./deno.lock")
-    .help("Check the specified lock file. (If value is not provided, defaults to \"./deno.lock\")")
+fn run_coverage_arg() -> Arg {
+  Arg::new("coverage")
+    .long("coverage")
+    .value_name("DIR")
     .num_args(0..=1)
-    .value_parser(value_parser!(String))
-    .value_hint(ValueHint::FilePath)
-    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+    .require_equals(true)
+    .default_missing_value("coverage")
+    .conflicts_with("inspect")
+    .conflicts_with("inspect-wait")
+    .conflicts_with("inspect-brk")
+    .help(cstr!(
+      "Collect coverage profile data of the run into DIR. If DIR is not specified, it uses 'coverage/'
+  <p(245)>The data is written when the program exits, also through an error or Deno.exit(). Use `deno coverage DIR` to report it</>"
+    ))
+    .value_hint(ValueHint::DirPath)
 }
 
-// TODO(bartlomieju): deprecated, to be removed in Deno 2.
-fn lock_write_arg() -> Arg {
-  Arg::new("lock-write")
-    .action(ArgAction::SetTrue)
-    .long("lock-write")
-    .help("Force overwriting the lock file")
-    .conflicts_with("no-lock")
-    .hide(true)
+fn default_cpu_prof_file_name() -> String {
+  let timestamp = std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|d| d.as_millis())
+    .unwrap_or(0);
+  format!("CPU.{}.{}.cpuprofile", timestamp, std::process::id())
 }
 
-fn no_lock_arg() -> Arg {
-  Arg::new("no-lock")
-    .long("no-lock")
+fn force_strict_arg() -> Arg {
+  Arg::new("force-strict")
+    .long("force-strict")
     .action(ArgAction::SetTrue)
-    .help("Disable auto discovery of the lock file")
-    .conflicts_with("lock")
-    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+    .help(cstr!(
+      "Evaluate CommonJS modules in strict mode, like ES modules always are
+  <p(245)>Modules that can't be parsed in strict mode, e.g. because of a with statement, fall back to sloppy mode with a warning</>"
+    ))
 }
 
-fn config_arg() -> Arg {
-  Arg::new("config")
-    .short('c')
-    .long("config")
-    .value_name("FILE")
-    .help(cstr!("Configure different aspects of deno including TypeScript, linting, and code formatting
-  <p(245)>Typically the configuration file will be called `deno.json` or `deno.jsonc` and
-  automatically detected; in that case this flag is not necessary.
-  Docs: https://docs.deno.com/go/config</>"))
-    .value_hint(ValueHint::FilePath)
+fn byonm_on_stale_arg() -> Arg {
+  Arg::new("byonm-on-stale")
+    .long("byonm-on-stale")
+    .value_name("BEHAVIOR")
+    .require_equals(true)
+    .value_parser(["error", "warn", "auto-install"])
+    .help(cstr!(
+      "What to do when a node_modules directory managed by another package manager is out of date
+  <p(245)>It is out of date when a package.json dependency isn't installed or the package.json changed after the last install</>"
+    ))
 }
 
-fn no_config_arg() -> Arg {
-  Arg::new("no-config")
-    .long("no-config")
-    .action(ArgAction::SetTrue)
-    .help("Disable automatic loading of the configuration file")
-    .conflicts_with("config")
+fn import_policy_args() -> [Arg; 2] {
+  [
+    Arg::new("import-policy")
+      .long("import-policy")
+      .value_name("POLICY")
+      .require_equals(true)
+      .value_parser(["allow", "deny"])
+      .help(cstr!(
+        "Whether modules may import any specifier (allow, the default) or only the ones given with --allow-import-spec (deny)
+  <p(245)>Applies to every module of the program except the main module and the modules inside an allowed npm package</>"
+      )),
+    Arg::new("allow-import-spec")
+      .long("allow-import-spec")
+      .value_name("SPECIFIER")
+      .action(ArgAction::Append)
+      .require_equals(true)
+      .requires("import-policy")
+      .help(cstr!(
+        "Allow importing this specifier with --import-policy=deny
+  <p(245)>Relative paths are resolved against the current directory. npm: and jsr: specifiers match by package name and, if given, the exact version requirement. Modules of an allowed package may import its own modules, and npm packages may import the versions of other npm packages that an allowed requirement matches</>
+  <p(245)>--allow-import-spec=./a.ts  |  --allow-import-spec=npm:chalk@5  |  --allow-import-spec=node:fs</>"
+      )),
+  ]
+}
+
+fn synthetic_scheme_arg() -> Arg {
+  Arg::new("synthetic-scheme")
+    .long("synthetic-scheme")
+    .value_name("SCHEME")
+    .require_equals(true)
+    .value_parser(|scheme: &str| -> Result<String, String> {
+      let scheme = scheme.strip_suffix(':').unwrap_or(scheme);
+      let mut chars = scheme.chars();
+      let is_valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+      if is_valid {
+        Ok(scheme.to_ascii_lowercase())
+      } else {
+        Err(format!("\"{scheme}\" is not a valid URL scheme"))
+      }
+    })
+    .help(cstr!(
+      "URL scheme of the main module when the code is passed on the command line or stdin
+  <p(245)>For example, --synthetic-scheme=eval labels it eval:$deno$eval in diagnostics and stack traces.
+  Relative imports can't be resolved against such a module</>"
+    ))
 }
 
-fn no_remote_arg() -> Arg {
-  Arg::new("no-remote")
-    .long("no-remote")
-    .action(ArgAction::SetTrue)
-    .help("Do not resolve remote modules")
-    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+fn cache_backend_arg() -> Arg {
+  Arg::new("cache-backend")
+    .long("cache-backend")
+    .value_name("URL")
+    .require_equals(true)
+    .help(cstr!(
+      "Share transpiled modules through an HTTP cache server
+  <p(245)>Entries are loaded with GET and saved with PUT requests to paths below the URL</>"
+    ))
+    .value_hint(ValueHint::Url)
 }
 
-fn no_npm_arg() -> Arg {
-  Arg::new("no-npm")
-    .long("no-npm")
-    .action(ArgAction::SetTrue)
-    .help("Do not resolve npm modules")
-    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+fn preload_arg() -> Arg {
+  Arg::new("preload")
+    .long("preload")
+    .value_name("MODULE")
+    .action(ArgAction::Append)
+    .require_equals(true)
+    .help(cstr!(
+      "Execute a module before the main module, can be repeated
+  <p(245)>Useful to set up instrumentation, polyfills or patches before the program starts</>"
+    ))
+    .value_hint(ValueHint::FilePath)
 }
 
-fn node_modules_dir_arg() -> Arg {
-  Arg::new("node-modules-dir")
-    .long("node-modules-dir")
-    .num_args(0..=1)
-    .value_parser(value_parser!(bool))
-    .value_name("DIRECTORY")
-    .default_missing_value("true")
+fn then_arg() -> Arg {
+  Arg::new("then")
+    .long("then")
+    .value_name("SCRIPT")
+    .action(ArgAction::Append)
     .require_equals(true)
-    .help("Enables or disables the use of a local node_modules folder for npm packages")
-    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+    .help(cstr!(
+      "Run another script in a fresh worker after the main module exits successfully, can be repeated
+  <p(245)>Each script loads its modules again. Deno.exit(0) continues with the next script; stops at the first script exiting with a non-zero code</>"
+    ))
+    .value_hint(ValueHint::FilePath)
 }
 
-fn vendor_arg() -> Arg {
-  Arg::new("vendor")
-    .long("vendor")
-    .num_args(0..=1)
-    .value_parser(value_parser!(bool))
-    .default_missing_value("true")
+fn vendor_out_arg() -> Arg {
+  Arg::new("vendor-out")
+    .long("vendor-out")
+    .value_name("DIR")
+    .value_hint(ValueHint::DirPath)
     .require_equals(true)
-    .help("Toggles local vendor folder usage for remote modules and a node_modules folder for npm packages")
+    .help(cstr!(
+      "After the program exits, write the remote modules and npm packages it loaded into a directory with an import map for them
+  <p(245)>The npm packages are written to a node_modules folder in the directory, which needs --unstable-byonm to be used</>
+  <p(245)>deno run --vendor-out=vendor/ main.ts && deno run --no-remote --import-map=vendor/import_map.json main.ts</>"
+    ))
     .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
 }
 
-fn unsafely_ignore_certificate_errors_arg() -> Arg {
-  Arg::new("unsafely-ignore-certificate-errors")
-    .hide(true)
-    .long("unsafely-ignore-certificate-errors")
-    .num_args(0..)
-    .use_value_delimiter(true)
-    .require_equals(true)
-    .value_name("HOSTNAMES")
-    .help("DANGER: Disables verification of TLS certificates")
-    .value_parser(flags_net::validator)
+fn report_unused_exports_arg() -> Arg {
+  Arg::new("report-unused-exports")
+    .long("report-unused-exports")
+    .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "Report the exports of local modules that no module of the program imports before running it
+  <p(245)>Only static imports are analyzed. Modules imported dynamically count as fully used</>"
+    ))
 }
 
-fn allow_scripts_arg() -> Arg {
-  Arg::new("allow-scripts")
-    .long("allow-scripts")
-    .num_args(0..)
-    .use_value_delimiter(true)
-    .require_equals(true)
-    .value_name("PACKAGE")
-    .value_parser(parse_packages_allowed_scripts)
-    .help(cstr!("Allow running npm lifecycle scripts for the given packages
-  <p(245)>Note: Scripts will only be executed when using a node_modules directory (`--node-modules-dir`)</>"))
+fn max_graph_modules_arg() -> Arg {
+  Arg::new("max-graph-modules")
+    .long("max-graph-modules")
+    .value_name("N")
+    .value_parser(value_parser!(usize))
+    .help(cstr!(
+      "Fail before running when the module graph has more than this many modules
+  <p(245)>Lists the dependencies of the main module that load the most modules</>"
+    ))
 }
 
-enum UnstableArgsConfig {
-  // for backwards-compatability
-  None,
-  ResolutionOnly,
-  ResolutionAndRuntime,
+fn config_schema_validate_arg() -> Arg {
+  Arg::new("config-schema-validate")
+    .long("config-schema-validate")
+    .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "Validate the configuration file against its schema before doing anything else
+  <p(245)>Fails on unknown keys and values of the wrong type</>"
+    ))
 }
 
-struct UnstableArgsIter {
-  idx: usize,
-  cfg: UnstableArgsConfig,
+fn nondeterminism_args() -> [Arg; 2] {
+  [
+    Arg::new("record-nondeterminism")
+      .long("record-nondeterminism")
+      .value_name("FILE")
+      .value_hint(ValueHint::FilePath)
+      .conflicts_with_all(["watch", "replay-nondeterminism"])
+      .help(cstr!(
+        "Record the random numbers, clock reads and fetch responses of the program to a file
+  <p(245)>Replay them with --replay-nondeterminism</>"
+      )),
+    Arg::new("replay-nondeterminism")
+      .long("replay-nondeterminism")
+      .value_name("FILE")
+      .value_hint(ValueHint::FilePath)
+      .conflicts_with("watch")
+      .help(cstr!(
+        "Replay the random numbers, clock reads and fetch responses recorded with --record-nondeterminism
+  <p(245)>Fetch requests are answered from the recording without touching the network</>"
+      )),
+  ]
+}
+
+fn permissions_prompt_timeout_arg() -> Arg {
+  Arg::new("permissions-prompt-timeout")
+    .long("permissions-prompt-timeout")
+    .require_equals(true)
+    .value_name("SECONDS")
+    .value_parser(value_parser!(u64).range(1..))
+    .help(cstr!(
+      "Deny permission prompts that are not answered within the given number of seconds
+  <p(245)>Keeps the program from hanging on a prompt nobody answers</>"
+    ))
 }
 
-impl Iterator for UnstableArgsIter {
-  type Item = Arg;
+fn npm_snapshot_arg() -> Arg {
+  Arg::new("npm-snapshot")
+    .long("npm-snapshot")
+    .value_name("FILE")
+    .value_hint(ValueHint::FilePath)
+    .help(cstr!(
+      "Resolve npm packages from a snapshot of the registry instead of the npm registry
+  <p(245)>The snapshot is a JSON file with the registry metadata of every needed package and optionally their tarballs</>"
+    ))
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-  fn next(&mut self) -> Option<Self::Item> {
-    let arg = if self.idx == 0 {
-      Arg::new("unstable")
-        .long("unstable")
-        .help(cstr!("Enable all unstable features and APIs. Instead of using this flag, consider enabling individual unstable features
-  <p(245)>To view the list of individual unstable feature flags, run this command again with --help=unstable</>"))
-        .action(ArgAction::SetTrue)
-        .hide(matches!(self.cfg, UnstableArgsConfig::None))
-    } else if self.idx == 1 {
-      Arg::new("unstable-bare-node-builtins")
-        .long("unstable-bare-node-builtins")
-        .help("Enable unstable bare node builtins feature")
-        .env("DENO_UNSTABLE_BARE_NODE_BUILTINS")
-        .value_parser(FalseyValueParser::new())
-        .action(ArgAction::SetTrue)
-        .hide(true)
-        .long_help(match self.cfg {
-          UnstableArgsConfig::None => None,
-          UnstableArgsConfig::ResolutionOnly
-          | UnstableArgsConfig::ResolutionAndRuntime => Some("true"),
-        })
-        .help_heading(UNSTABLE_HEADING)
-    } else if self.idx == 2 {
-      Arg::new("unstable-byonm")
-        .long("unstable-byonm")
-        .help("Enable unstable 'bring your own node_modules' feature")
-        .env("DENO_UNSTABLE_BYONM")
-        .value_parser(FalseyValueParser::new())
-        .action(ArgAction::SetTrue)
-        .hide(true)
-        .long_help(match self.cfg {
-          UnstableArgsConfig::None => None,
-          UnstableArgsConfig::ResolutionOnly
-          | UnstableArgsConfig::ResolutionAndRuntime => Some("true"),
-        })
-        .help_heading(UNSTABLE_HEADING)
-    } else if self.idx == 3 {
-      Arg::new("unstable-sloppy-imports")
-      .long("unstable-sloppy-imports")
-      .help("Enable unstable resolving of specifiers by extension probing, .js to .ts, and directory probing")
-      .env("DENO_UNSTABLE_SLOPPY_IMPORTS")
-      .value_parser(FalseyValueParser::new())
+fn install_scripts_args() -> [Arg; 2] {
+  [
+    Arg::new("detect-install-scripts")
+      .long("detect-install-scripts")
       .action(ArgAction::SetTrue)
-      .hide(true)
-      .long_help(match self.cfg {
-        UnstableArgsConfig::None => None,
-        UnstableArgsConfig::ResolutionOnly | UnstableArgsConfig::ResolutionAndRuntime => Some("true")
-      })
-      .help_heading(UNSTABLE_HEADING)
-    } else if self.idx > 3 {
-      let (flag_name, help, _) =
-        crate::UNSTABLE_GRANULAR_FLAGS.get(self.idx - 4)?;
-      Arg::new(format!("unstable-{}", flag_name))
-        .long(format!("unstable-{}", flag_name))
-        .help(help)
-        .action(ArgAction::SetTrue)
-        .hide(true)
-        .help_heading(UNSTABLE_HEADING)
-        // we don't render long help, so using it here as a sort of metadata
-        .long_help(match self.cfg {
-          UnstableArgsConfig::None | UnstableArgsConfig::ResolutionOnly => None,
-          UnstableArgsConfig::ResolutionAndRuntime => Some("true"),
-        })
-    } else {
-      return None;
-    };
-    self.idx += 1;
-    Some(arg.display_order(self.idx + 1000))
-  }
+      .help(cstr!(
+        "List the npm packages with preinstall, install or postinstall scripts before running
+  <p(245)>Useful to vet dependencies before enabling their scripts with --allow-scripts</>"
+      ))
+      .help_heading(DEPENDENCY_MANAGEMENT_HEADING),
+    Arg::new("fail-on-install-scripts")
+      .long("fail-on-install-scripts")
+      .action(ArgAction::SetTrue)
+      .help("Like --detect-install-scripts, but fail if any npm package has install scripts")
+      .help_heading(DEPENDENCY_MANAGEMENT_HEADING),
+  ]
 }
 
-fn unstable_args(cfg: UnstableArgsConfig) -> impl IntoIterator<Item = Arg> {
-  UnstableArgsIter { idx: 0, cfg }
+fn warmup_arg() -> Arg {
+  Arg::new("warmup")
+    .long("warmup")
+    .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "Load the program and populate the npm, transpile and V8 code caches, then exit without running it
+  <p(245)>Type checks first when combined with --check, so that the next run starts warm</>"
+    ))
 }
 
-fn allow_scripts_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  let Some(parts) = matches.remove_many::<String>("allow-scripts") else {
-    return;
-  };
-  if parts.len() == 0 {
-    flags.allow_scripts = PackagesAllowedScripts::All;
-  } else {
-    flags.allow_scripts = PackagesAllowedScripts::Some(parts.collect());
-  }
+fn tz_and_locale_args() -> [Arg; 2] {
+  [
+    Arg::new("tz")
+      .long("tz")
+      .value_name("TIME_ZONE")
+      .help(cstr!(
+        "Run the program in the given IANA time zone instead of the one set by the <c>TZ</> environment variable.
+  Sets <c>TZ</>, so <c>Deno.env</> and subprocesses see it too
+  <p(245)>deno run --tz=UTC main.ts</>"
+      )),
+    Arg::new("locale")
+      .long("locale")
+      .value_name("LOCALE")
+      .help(cstr!(
+        "Use the given BCP 47 language tag as the default locale of <c>Intl</> instead of the one set by the environment
+  <p(245)>deno run --locale=en-US main.ts</>"
+      )),
+  ]
+}
+
+fn tee_args() -> [Arg; 2] {
+  [
+    Arg::new("tee-stdout")
+      .long("tee-stdout")
+      .value_name("FILE")
+      .value_hint(ValueHint::FilePath)
+      .conflicts_with_all(["watch", "hmr"])
+      .help("Write the stdout of the program to a file while still printing it"),
+    Arg::new("tee-stderr")
+      .long("tee-stderr")
+      .value_name("FILE")
+      .value_hint(ValueHint::FilePath)
+      .conflicts_with_all(["watch", "hmr"])
+      .help(
+        "Write the stderr of the program, including warnings and the error it fails with, to a file while still printing it",
+      ),
+  ]
 }
 
-fn add_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.subcommand = DenoSubcommand::Add(add_parse_inner(matches, None));
+fn exit_drain_arg() -> Arg {
+  Arg::new("exit-drain")
+    .long("exit-drain")
+    .num_args(0..=1)
+    .require_equals(true)
+    .default_missing_value("5000")
+    .value_name("MILLISECONDS")
+    .value_parser(value_parser!(u64))
+    .help(cstr!(
+      "Let cleanup started by <c>Deno.exit()</>, for example in an \"unload\" listener, finish before the process exits
+  <p(245)>Waits for pending work for at most the given time. Defaults to 5000 milliseconds</>"
+    ))
 }
 
-fn add_parse_inner(
-  matches: &mut ArgMatches,
-  packages: Option<clap::parser::Values<String>>,
-) -> AddFlags {
-  let packages = packages
-    .unwrap_or_else(|| matches.remove_many::<String>("packages").unwrap())
-    .collect();
-  AddFlags { packages }
+fn report_hrtime_usage_arg() -> Arg {
+  Arg::new("report-hrtime-usage")
+    .long("report-hrtime-usage")
+    .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "When the program finishes, report whether high-resolution time was granted and how often it was read
+  <p(245)>Helps to spot code that measures time precisely, which can enable timing attacks</>"
+    ))
 }
 
-fn remove_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.subcommand = DenoSubcommand::Remove(RemoveFlags {
-    packages: matches.remove_many::<String>("packages").unwrap().collect(),
-  });
+fn print_config_args() -> [Arg; 3] {
+  [
+    Arg::new("print-config")
+      .long("print-config")
+      .action(ArgAction::SetTrue)
+      .help(cstr!(
+        "Print the configuration resolved from the config file, package.json and flags, then run
+  <p(245)>Includes the config file, import map, node_modules setting, lockfile and compiler options</>"
+      ))
+      .help_heading(DEBUGGING_HEADING),
+    Arg::new("print-config-only")
+      .long("print-config-only")
+      .action(ArgAction::SetTrue)
+      .conflicts_with("print-config")
+      .help("Print the resolved configuration and exit without running")
+      .help_heading(DEBUGGING_HEADING),
+    Arg::new("json")
+      .long("json")
+      .action(ArgAction::SetTrue)
+      .requires("json-output-group")
+      .help("Print the resolved configuration, the AST or the dry run report as JSON")
+      .help_heading(DEBUGGING_HEADING),
+  ]
 }
 
-fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.type_check_mode = TypeCheckMode::Local;
-
-  runtime_args_parse(flags, matches, true, false);
-
-  // NOTE: `deno bench` always uses `--no-prompt`, tests shouldn't ever do
-  // interactive prompts, unless done by user code
-  flags.permissions.no_prompt = true;
+fn dump_ast_args() -> [Arg; 2] {
+  [
+    Arg::new("dump-ast")
+      .long("dump-ast")
+      .action(ArgAction::SetTrue)
+      .help(cstr!(
+        "Print the AST of the main module as JSON and exit without running
+  <p(245)>The AST is the one produced by the parser that loads modules</>"
+      ))
+      .help_heading(DEBUGGING_HEADING),
+    Arg::new("dump-ast-all")
+      .long("dump-ast-all")
+      .action(ArgAction::SetTrue)
+      .conflicts_with("dump-ast")
+      .help(
+        "Print the AST of every module in the graph and exit without running",
+      )
+      .help_heading(DEBUGGING_HEADING),
+  ]
+}
 
-  let json = matches.get_flag("json");
+fn dry_run_arg() -> Arg {
+  Arg::new("dry-run")
+    .long("dry-run")
+    .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "Resolve the module graph, install npm packages and type check, then report what would run and exit
+  <p(245)>Lists the modules, which are remote or need transpiling, and the npm packages</>"
+    ))
+    .help_heading(DEBUGGING_HEADING)
+}
 
-  let ignore = match matches.remove_many::<String>("ignore") {
-    Some(f) => f.collect(),
-    None => vec![],
-  };
-
-  let filter = matches.remove_one::<String>("filter");
-
-  if matches.contains_id("script_arg") {
-    flags
-      .argv
-      .extend(matches.remove_many::<String>("script_arg").unwrap());
-  }
+fn error_format_arg() -> Arg {
+  Arg::new("error-format")
+    .long("error-format")
+    .value_name("FORMAT")
+    .require_equals(true)
+    .value_parser(["pretty", "json"])
+    .default_value("pretty")
+    .help(cstr!(
+      "Format of the error reported when the program fails
+  <p(245)>json prints an object with kind, message, specifier, stack and exitCode fields</>"
+    ))
+}
 
-  let include = if let Some(files) = matches.remove_many::<String>("files") {
-    files.collect()
-  } else {
-    Vec::new()
-  };
+fn color_arg() -> Arg {
+  Arg::new("color")
+    .long("color")
+    .value_name("WHEN")
+    .require_equals(true)
+    .value_parser(["auto", "always", "never"])
+    .default_value("auto")
+    .help(cstr!(
+      "Control colors in Deno's own output, like warnings, download logs and errors
+  <p(245)>always and never override the NO_COLOR environment variable</>"
+    ))
+}
 
-  let no_run = matches.get_flag("no-run");
+fn deprecation_arg() -> Arg {
+  Arg::new("deprecation")
+    .long("deprecation")
+    .value_name("MODE")
+    .require_equals(true)
+    .value_parser(["warn", "error", "silent"])
+    .help(cstr!(
+      "How to handle the use of deprecated Deno APIs
+  <p(245)>warn logs a warning on first use, error throws on every use and silent ignores them</>"
+    ))
+}
 
-  flags.subcommand = DenoSubcommand::Bench(BenchFlags {
-    files: FileFlags { include, ignore },
-    filter,
-    json,
-    no_run,
-    watch: watch_arg_parse(matches),
-  });
+fn vfs_overlay_arg() -> Arg {
+  Arg::new("vfs-overlay")
+    .long("vfs-overlay")
+    .value_name("FILE")
+    .require_equals(true)
+    .help(cstr!(
+      "Run with an in-memory file system overlay loaded from a JSON file mapping paths to file contents
+  <p(245)>Inside the directory of the overlay file, reads and imports consult the overlay first and changes are only made to the overlay</>"
+    ))
+    .value_hint(ValueHint::FilePath)
 }
 
-fn bundle_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.type_check_mode = TypeCheckMode::Local;
+fn trace_arg() -> Arg {
+  Arg::new("trace")
+    .long("trace")
+    .value_name("FILE")
+    .require_equals(true)
+    .help(cstr!(
+      "Write a timeline of module loads, ops and GC in Chrome trace format to a file
+  <p(245)>Open the file in chrome://tracing or https://ui.perfetto.dev</>"
+    ))
+    .value_hint(ValueHint::FilePath)
+    .help_heading(DEBUGGING_HEADING)
+}
 
-  compile_args_parse(flags, matches);
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
+fn seed_arg() -> Arg {
+  Arg::new("seed")
+    .long("seed")
+    .value_name("NUMBER")
+    .help("Set the random number generator seed")
+    .value_parser(value_parser!(u64))
+}
 
-  let source_file = matches.remove_one::<String>("source_file").unwrap();
+fn hmr_arg(takes_files: bool) -> Arg {
+  let arg = Arg::new("hmr")
+    .long("watch-hmr")
+    // NOTE(bartlomieju): compatibility with Deno pre-1.46
+    .alias("unstable-hmr")
+    .help("Watch for file changes and hot replace modules")
+    .conflicts_with("watch")
+    .help_heading(FILE_WATCHING_HEADING);
 
-  let out_file =
-    if let Some(out_file) = matches.remove_one::<String>("out_file") {
-      flags.permissions.allow_write = Some(vec![]);
-      Some(out_file)
-    } else {
-      None
-    };
+  if takes_files {
+    arg
+      .value_name("FILES")
+      .num_args(0..)
+      .value_parser(value_parser!(String))
+      .use_value_delimiter(true)
+      .require_equals(true)
+      .help(
+        cstr!(
+        "Watch for file changes and restart process automatically.
+  <p(245)>Local files from entry point module graph are watched by default.
+  Additional paths might be watched by passing them as arguments to this flag.</>"),
+      )
+      .value_hint(ValueHint::AnyPath)
+  } else {
+    arg.action(ArgAction::SetTrue).help(cstr!(
+      "Watch for file changes and restart process automatically.
+  <p(245)>Only local files from entry point module graph are watched.</>"
+    ))
+  }
+}
 
-  ext_arg_parse(flags, matches);
+fn watch_reevaluate_arg() -> Arg {
+  Arg::new("watch-reevaluate")
+    .long("watch-reevaluate")
+    .value_name("FILES")
+    .num_args(0..)
+    .value_parser(value_parser!(String))
+    .use_value_delimiter(true)
+    .require_equals(true)
+    .help(cstr!(
+      "Watch for file changes and re-evaluate changed modules in place.
+  <p(245)>The changed module and the modules that depend on it are evaluated again,
+  re-running their top-level side effects, without restarting the process.
+  The entry point module is never re-evaluated.</>"
+    ))
+    .conflicts_with_all(["watch", "hmr"])
+    .value_hint(ValueHint::AnyPath)
+    .help_heading(FILE_WATCHING_HEADING)
+}
 
-  flags.subcommand = DenoSubcommand::Bundle(BundleFlags {
-    source_file,
-    out_file,
-    watch: watch_arg_parse(matches),
-  });
+fn watch_exit_on_error_arg() -> Arg {
+  Arg::new("watch-exit-on-error")
+    .long("watch-exit-on-error")
+    .action(ArgAction::SetTrue)
+    .help(cstr!(
+      "Stop watching when the program fails or exits with a nonzero code
+  <p(245)>Deno exits with the code of the program instead of waiting for a file change.</>"
+    ))
+    .help_heading(FILE_WATCHING_HEADING)
 }
 
-fn cache_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  compile_args_parse(flags, matches);
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
-  frozen_lockfile_arg_parse(flags, matches);
-  allow_scripts_arg_parse(flags, matches);
-  let files = matches.remove_many::<String>("file").unwrap().collect();
-  flags.subcommand = DenoSubcommand::Cache(CacheFlags { files });
+fn watch_poll_arg() -> Arg {
+  Arg::new("watch-poll")
+    .long("watch-poll")
+    .value_name("MILLISECONDS")
+    .num_args(0..=1)
+    .require_equals(true)
+    .default_missing_value("1000")
+    .value_parser(value_parser!(u64).range(1..))
+    .help(cstr!(
+      "Detect file changes by checking the watched files at an interval instead of using file system events
+  <p(245)>For network file systems and containers that don't deliver file system events. Defaults to 1000 milliseconds.</>"
+    ))
+    .help_heading(FILE_WATCHING_HEADING)
 }
 
-fn check_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.type_check_mode = TypeCheckMode::Local;
-  compile_args_without_check_parse(flags, matches);
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionAndRuntime);
-  let files = matches.remove_many::<String>("file").unwrap().collect();
-  if matches.get_flag("all") || matches.get_flag("remote") {
-    flags.type_check_mode = TypeCheckMode::All;
+fn watch_arg(takes_files: bool) -> Arg {
+  let arg = Arg::new("watch")
+    .long("watch")
+    .help_heading(FILE_WATCHING_HEADING);
+
+  if takes_files {
+    arg
+      .value_name("FILES")
+      .num_args(0..)
+      .value_parser(value_parser!(String))
+      .use_value_delimiter(true)
+      .require_equals(true)
+      .help(
+        cstr!(
+        "Watch for file changes and restart process automatically.
+  <p(245)>Local files from entry point module graph are watched by default.
+  Additional paths might be watched by passing them as arguments to this flag.</>"),
+      )
+      .value_hint(ValueHint::AnyPath)
+  } else {
+    arg.action(ArgAction::SetTrue).help(cstr!(
+      "Watch for file changes and restart process automatically.
+  <p(245)>Only local files from entry point module graph are watched.</>"
+    ))
   }
-  flags.subcommand = DenoSubcommand::Check(CheckFlags { files });
 }
 
-fn clean_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
-  flags.subcommand = DenoSubcommand::Clean;
+fn no_clear_screen_arg() -> Arg {
+  Arg::new("no-clear-screen")
+    .requires("watch")
+    .long("no-clear-screen")
+    .action(ArgAction::SetTrue)
+    .help("Do not clear terminal screen when under watch mode")
+    .help_heading(FILE_WATCHING_HEADING)
 }
 
-fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.type_check_mode = TypeCheckMode::Local;
-  runtime_args_parse(flags, matches, true, false);
-
-  let mut script = matches.remove_many::<String>("script_arg").unwrap();
-  let source_file = script.next().unwrap();
-  let args = script.collect();
-  let output = matches.remove_one::<String>("output");
-  let target = matches.remove_one::<String>("target");
-  let icon = matches.remove_one::<String>("icon");
-  let no_terminal = matches.get_flag("no-terminal");
-  let include = match matches.remove_many::<String>("include") {
-    Some(f) => f.collect(),
-    None => vec![],
-  };
-  ext_arg_parse(flags, matches);
-
-  flags.subcommand = DenoSubcommand::Compile(CompileFlags {
-    source_file,
-    output,
-    args,
-    target,
-    no_terminal,
-    icon,
-    include,
-  });
+fn no_code_cache_arg() -> Arg {
+  Arg::new("no-code-cache")
+    .long("no-code-cache")
+    .help("Disable V8 code cache feature")
+    .action(ArgAction::SetTrue)
 }
 
-fn completions_parse(
-  flags: &mut Flags,
-  matches: &mut ArgMatches,
-  mut app: Command,
-) {
-  use clap_complete::generate;
-  use clap_complete::shells::Bash;
-  use clap_complete::shells::Fish;
-  use clap_complete::shells::PowerShell;
-  use clap_complete::shells::Zsh;
-  use clap_complete_fig::Fig;
+fn code_cache_arg() -> Arg {
+  Arg::new("code-cache")
+    .long("code-cache")
+    .value_name("PATH")
+    .require_equals(true)
+    .help("Store the V8 code cache in the given file instead of DENO_DIR")
+    .value_hint(ValueHint::FilePath)
+    .conflicts_with("no-code-cache")
+}
 
-  let mut buf: Vec<u8> = vec![];
-  let name = "deno";
+fn code_cache_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.code_cache_enabled = !matches.get_flag("no-code-cache");
+  flags.code_cache_path = matches.remove_one::<String>("code-cache");
+}
 
-  match matches.get_one::<String>("shell").unwrap().as_str() {
-    "bash" => generate(Bash, &mut app, name, &mut buf),
-    "fish" => generate(Fish, &mut app, name, &mut buf),
-    "powershell" => generate(PowerShell, &mut app, name, &mut buf),
-    "zsh" => generate(Zsh, &mut app, name, &mut buf),
-    "fig" => generate(Fig, &mut app, name, &mut buf),
-    _ => unreachable!(),
-  }
+fn watch_exclude_arg() -> Arg {
+  Arg::new("watch-exclude")
+    .long("watch-exclude")
+    .help("Exclude provided files/patterns from watch mode")
+    .value_name("FILES")
+    .num_args(0..)
+    .value_parser(value_parser!(String))
+    .use_value_delimiter(true)
+    .require_equals(true)
+    .value_hint(ValueHint::AnyPath)
+    .help_heading(FILE_WATCHING_HEADING)
+}
 
-  flags.subcommand = DenoSubcommand::Completions(CompletionsFlags {
-    buf: buf.into_boxed_slice(),
-  });
+fn no_check_arg() -> Arg {
+  Arg::new("no-check")
+    .num_args(0..=1)
+    .require_equals(true)
+    .value_name("NO_CHECK_TYPE")
+    .long("no-check")
+    .help("Skip type-checking. If the value of \"remote\" is supplied, diagnostic errors from remote modules will be ignored")
+    .help_heading(TYPE_CHECKING_HEADING)
 }
 
-fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  let files = match matches.remove_many::<String>("files") {
-    Some(f) => f.collect(),
-    None => vec!["coverage".to_string()], // default
-  };
-  let ignore = match matches.remove_many::<String>("ignore") {
-    Some(f) => f.collect(),
-    None => vec![],
-  };
-  let include = match matches.remove_many::<String>("include") {
-    Some(f) => f.collect(),
-    None => vec![],
-  };
-  let exclude = match matches.remove_many::<String>("exclude") {
-    Some(f) => f.collect(),
-    None => vec![],
-  };
-  let r#type = if matches.get_flag("lcov") {
-    CoverageType::Lcov
-  } else if matches.get_flag("html") {
-    CoverageType::Html
-  } else if matches.get_flag("detailed") {
-    CoverageType::Detailed
+fn check_arg(checks_local_by_default: bool) -> Arg {
+  let arg = Arg::new("check")
+    .conflicts_with("no-check")
+    .long("check")
+    .num_args(0..=1)
+    .require_equals(true)
+    .value_name("CHECK_TYPE")
+    .help_heading(TYPE_CHECKING_HEADING);
+
+  if checks_local_by_default {
+    arg.help(
+      cstr!("Set type-checking behavior. This subcommand type-checks local modules by default, so adding --check is redundant
+  <p(245)>If the value of \"all\" is supplied, remote modules will be included.
+  Alternatively, the 'deno check' subcommand can be used</>",
+    ))
   } else {
-    CoverageType::Summary
-  };
-  let output = matches.remove_one::<String>("output");
-  flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
-    files: FileFlags {
-      include: files,
-      ignore,
-    },
-    output,
-    include,
-    exclude,
-    r#type,
-  });
+    arg.help(cstr!(
+      "Enable type-checking. This subcommand does not type-check by default
+  <p(245)>If the value of \"all\" is supplied, remote modules will be included.
+  Alternatively, the 'deno check' subcommand can be used</>"
+    ))
+  }
 }
 
-fn doc_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
-  import_map_arg_parse(flags, matches);
-  reload_arg_parse(flags, matches);
-  lock_arg_parse(flags, matches);
-  no_lock_arg_parse(flags, matches);
-  no_npm_arg_parse(flags, matches);
-  no_remote_arg_parse(flags, matches);
+fn script_arg() -> Arg {
+  Arg::new("script_arg")
+    .num_args(0..)
+    .action(ArgAction::Append)
+    // NOTE: these defaults are provided
+    // so `deno run --v8-flags=--help` works
+    // without specifying file to run.
+    .default_value_ifs([
+      ("v8-flags", "--help", Some("_")),
+      ("v8-flags", "-help", Some("_")),
+    ])
+    .help("Script arg")
+    .value_name("SCRIPT_ARG")
+    .value_hint(ValueHint::FilePath)
+}
 
-  let source_files_val = matches.remove_many::<String>("source_file");
-  let source_files = if let Some(val) = source_files_val {
-    let vals: Vec<String> = val.collect();
+fn lock_arg() -> Arg {
+  Arg::new("lock")
+    .long("lock")
+    .value_name("FILE")
+    .default_missing_value("./deno.lock")
+    .help("Check the specified lock file. (If value is not provided, defaults to \"./deno.lock\")")
+    .num_args(0..=1)
+    .value_parser(value_parser!(String))
+    .value_hint(ValueHint::FilePath)
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-    if vals.len() == 1 {
-      if vals[0] == "--builtin" {
-        DocSourceFileFlag::Builtin
-      } else {
-        DocSourceFileFlag::Paths(vec![vals[0].to_string()])
-      }
-    } else {
-      DocSourceFileFlag::Paths(
-        vals.into_iter().filter(|v| v != "--builtin").collect(),
-      )
-    }
-  } else {
-    DocSourceFileFlag::Builtin
-  };
-  let private = matches.get_flag("private");
-  let lint = matches.get_flag("lint");
-  let json = matches.get_flag("json");
-  let filter = matches.remove_one::<String>("filter");
-  let html = if matches.get_flag("html") {
-    let name = matches.remove_one::<String>("name");
-    let category_docs_path = matches.remove_one::<String>("category-docs");
-    let symbol_redirect_map_path =
-      matches.remove_one::<String>("symbol-redirect-map");
-    let strip_trailing_html = matches.get_flag("strip-trailing-html");
-    let default_symbol_map_path =
-      matches.remove_one::<String>("default-symbol-map");
-    let output = matches
-      .remove_one::<String>("output")
-      .unwrap_or(String::from("./docs/"));
-    Some(DocHtmlFlag {
-      name,
-      category_docs_path,
-      symbol_redirect_map_path,
-      default_symbol_map_path,
-      strip_trailing_html,
-      output,
-    })
-  } else {
-    None
-  };
+// TODO(bartlomieju): deprecated, to be removed in Deno 2.
+fn lock_write_arg() -> Arg {
+  Arg::new("lock-write")
+    .action(ArgAction::SetTrue)
+    .long("lock-write")
+    .help("Force overwriting the lock file")
+    .conflicts_with("no-lock")
+    .hide(true)
+}
 
-  flags.subcommand = DenoSubcommand::Doc(DocFlags {
-    source_files,
-    json,
-    lint,
-    html,
-    filter,
-    private,
-  });
+fn lock_normalize_arg() -> Arg {
+  Arg::new("lock-normalize")
+    .long("lock-normalize")
+    .action(ArgAction::SetTrue)
+    .help(
+      "Always write the lock file with sorted keys and normalized whitespace",
+    )
+    .conflicts_with("no-lock")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
 }
 
-fn eval_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  runtime_args_parse(flags, matches, false, true);
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionAndRuntime);
-  flags.allow_all();
+fn no_lock_arg() -> Arg {
+  Arg::new("no-lock")
+    .long("no-lock")
+    .action(ArgAction::SetTrue)
+    .help("Disable auto discovery of the lock file")
+    .conflicts_with("lock")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-  ext_arg_parse(flags, matches);
+fn config_arg() -> Arg {
+  Arg::new("config")
+    .short('c')
+    .long("config")
+    .value_name("FILE")
+    .help(cstr!("Configure different aspects of deno including TypeScript, linting, and code formatting
+  <p(245)>Typically the configuration file will be called `deno.json` or `deno.jsonc` and
+  automatically detected; in that case this flag is not necessary.
+  Docs: https://docs.deno.com/go/config</>"))
+    .value_hint(ValueHint::FilePath)
+}
 
-  // TODO(@satyarohith): remove this flag in 2.0.
-  let as_typescript = matches.get_flag("ts");
+fn no_config_arg() -> Arg {
+  Arg::new("no-config")
+    .long("no-config")
+    .action(ArgAction::SetTrue)
+    .help("Disable automatic loading of the configuration file")
+    .conflicts_with("config")
+}
 
-  #[allow(clippy::print_stderr)]
-  if as_typescript {
-    eprintln!(
-      "⚠️ {}",
-      crate::colors::yellow(
-        "Use `--ext=ts` instead. The `--ts` and `-T` flags are deprecated and will be removed in Deno 2.0."
-      ),
-    );
+fn no_remote_arg() -> Arg {
+  Arg::new("no-remote")
+    .long("no-remote")
+    .action(ArgAction::SetTrue)
+    .help("Do not resolve remote modules")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-    flags.ext = Some("ts".to_string());
-  }
+fn no_npm_arg() -> Arg {
+  Arg::new("no-npm")
+    .long("no-npm")
+    .action(ArgAction::SetTrue)
+    .help("Do not resolve npm modules")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-  let print = matches.get_flag("print");
-  let mut code_args = matches.remove_many::<String>("code_arg").unwrap();
-  let code = code_args.next().unwrap();
-  flags.argv.extend(code_args);
+fn node_modules_dir_arg() -> Arg {
+  Arg::new("node-modules-dir")
+    .long("node-modules-dir")
+    .num_args(0..=1)
+    .value_parser(value_parser!(bool))
+    .value_name("DIRECTORY")
+    .default_missing_value("true")
+    .require_equals(true)
+    .help("Enables or disables the use of a local node_modules folder for npm packages")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-  flags.subcommand = DenoSubcommand::Eval(EvalFlags { print, code });
+fn nm_layout_arg() -> Arg {
+  Arg::new("nm-layout")
+    .long("nm-layout")
+    .value_parser(["default", "deterministic"])
+    .value_name("LAYOUT")
+    .require_equals(true)
+    .help(cstr!(
+      "Layout of the local node_modules folder
+  <p(245)>deterministic: folder names and symlink targets only depend on the resolved packages</>
+  <p(245)>The layout is kept for later runs; changing it sets up the folder again</>"
+    ))
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
 }
 
-fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  config_args_parse(flags, matches);
-  ext_arg_parse(flags, matches);
+fn npm_target_args() -> [Arg; 2] {
+  // the values of Node.js' `process.platform` and `process.arch`, which npm
+  // matches against the `os` and `cpu` fields of a package
+  [
+    Arg::new("npm-target-os")
+      .long("npm-target-os")
+      .value_parser([
+        "aix", "android", "darwin", "freebsd", "linux", "openbsd", "sunos",
+        "win32",
+      ])
+      .value_name("OS")
+      .require_equals(true)
+      .help(cstr!(
+        "Operating system to install platform specific npm packages for
+  <p(245)>Defaults to the current operating system</>"
+      ))
+      .help_heading(DEPENDENCY_MANAGEMENT_HEADING),
+    Arg::new("npm-target-cpu")
+      .long("npm-target-cpu")
+      .value_parser([
+        "arm", "arm64", "ia32", "loong64", "mips", "mipsel", "ppc", "ppc64",
+        "riscv64", "s390", "s390x", "x64",
+      ])
+      .value_name("CPU")
+      .require_equals(true)
+      .help(cstr!(
+        "CPU architecture to install platform specific npm packages for
+  <p(245)>Defaults to the current architecture</>"
+      ))
+      .help_heading(DEPENDENCY_MANAGEMENT_HEADING),
+  ]
+}
 
-  let include = match matches.remove_many::<String>("files") {
-    Some(f) => f.collect(),
-    None => vec![],
-  };
-  let ignore = match matches.remove_many::<String>("ignore") {
-    Some(f) => f.collect(),
-    None => vec![],
-  };
+fn vendor_arg() -> Arg {
+  Arg::new("vendor")
+    .long("vendor")
+    .num_args(0..=1)
+    .value_parser(value_parser!(bool))
+    .default_missing_value("true")
+    .require_equals(true)
+    .help("Toggles local vendor folder usage for remote modules and a node_modules folder for npm packages")
+    .help_heading(DEPENDENCY_MANAGEMENT_HEADING)
+}
 
-  let use_tabs = matches.remove_one::<bool>("use-tabs");
-  let line_width = matches.remove_one::<NonZeroU32>("line-width");
-  let indent_width = matches.remove_one::<NonZeroU8>("indent-width");
-  let single_quote = matches.remove_one::<bool>("single-quote");
-  let prose_wrap = matches.remove_one::<String>("prose-wrap");
-  let no_semicolons = matches.remove_one::<bool>("no-semicolons");
-  let unstable_css = matches.get_flag("unstable-css");
-  let unstable_html = matches.get_flag("unstable-html");
-  let unstable_component = matches.get_flag("unstable-component");
-  let unstable_yaml = matches.get_flag("unstable-yaml");
+fn unsafely_ignore_certificate_errors_arg() -> Arg {
+  Arg::new("unsafely-ignore-certificate-errors")
+    .hide(true)
+    .long("unsafely-ignore-certificate-errors")
+    .num_args(0..)
+    .use_value_delimiter(true)
+    .require_equals(true)
+    .value_name("HOSTNAMES")
+    .help("DANGER: Disables verification of TLS certificates")
+    .value_parser(flags_net::validator)
+}
 
-  flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
-    check: matches.get_flag("check"),
-    files: FileFlags { include, ignore },
-    use_tabs,
-    line_width,
-    indent_width,
-    single_quote,
-    prose_wrap,
-    no_semicolons,
-    watch: watch_arg_parse(matches),
-    unstable_css,
-    unstable_html,
-    unstable_component,
-    unstable_yaml,
-  });
+fn allow_scripts_arg() -> Arg {
+  Arg::new("allow-scripts")
+    .long("allow-scripts")
+    .num_args(0..)
+    .use_value_delimiter(true)
+    .require_equals(true)
+    .value_name("PACKAGE")
+    .value_parser(parse_packages_allowed_scripts)
+    .help(cstr!("Allow running npm lifecycle scripts for the given packages
+  <p(245)>Note: Scripts will only be executed when using a node_modules directory (`--node-modules-dir`)</>"))
 }
 
-fn init_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.subcommand = DenoSubcommand::Init(InitFlags {
-    dir: matches.remove_one::<String>("dir"),
-    lib: matches.get_flag("lib"),
-    serve: matches.get_flag("serve"),
-  });
+enum UnstableArgsConfig {
+  // for backwards-compatability
+  None,
+  ResolutionOnly,
+  ResolutionAndRuntime,
 }
 
-fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
-  reload_arg_parse(flags, matches);
-  config_args_parse(flags, matches);
-  import_map_arg_parse(flags, matches);
-  location_arg_parse(flags, matches);
-  ca_file_arg_parse(flags, matches);
-  unsafely_ignore_certificate_errors_parse(flags, matches);
-  node_modules_and_vendor_dir_arg_parse(flags, matches);
-  lock_args_parse(flags, matches);
-  no_remote_arg_parse(flags, matches);
-  no_npm_arg_parse(flags, matches);
-  let json = matches.get_flag("json");
-  flags.subcommand = DenoSubcommand::Info(InfoFlags {
-    file: matches.remove_one::<String>("file"),
-    json,
-  });
+struct UnstableArgsIter {
+  idx: usize,
+  cfg: UnstableArgsConfig,
 }
 
-fn install_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  runtime_args_parse(flags, matches, true, true);
+impl Iterator for UnstableArgsIter {
+  type Item = Arg;
 
-  let global = matches.get_flag("global");
-  if global || !*DENO_FUTURE {
-    let root = matches.remove_one::<String>("root");
-    let force = matches.get_flag("force");
-    let name = matches.remove_one::<String>("name");
-    let mut cmd_values =
-      matches.remove_many::<String>("cmd").unwrap_or_default();
+  fn next(&mut self) -> Option<Self::Item> {
+    let arg = if self.idx == 0 {
+      Arg::new("unstable")
+        .long("unstable")
+        .help(cstr!("Enable all unstable features and APIs. Instead of using this flag, consider enabling individual unstable features
+  <p(245)>To view the list of individual unstable feature flags, run this command again with --help=unstable</>"))
+        .action(ArgAction::SetTrue)
+        .hide(matches!(self.cfg, UnstableArgsConfig::None))
+    } else if self.idx == 1 {
+      Arg::new("unstable-bare-node-builtins")
+        .long("unstable-bare-node-builtins")
+        .help("Enable unstable bare node builtins feature")
+        .env("DENO_UNSTABLE_BARE_NODE_BUILTINS")
+        .value_parser(FalseyValueParser::new())
+        .action(ArgAction::SetTrue)
+        .hide(true)
+        .long_help(match self.cfg {
+          UnstableArgsConfig::None => None,
+          UnstableArgsConfig::ResolutionOnly
+          | UnstableArgsConfig::ResolutionAndRuntime => Some("true"),
+        })
+        .help_heading(UNSTABLE_HEADING)
+    } else if self.idx == 2 {
+      Arg::new("unstable-byonm")
+        .long("unstable-byonm")
+        .help("Enable unstable 'bring your own node_modules' feature")
+        .env("DENO_UNSTABLE_BYONM")
+        .value_parser(FalseyValueParser::new())
+        .action(ArgAction::SetTrue)
+        .hide(true)
+        .long_help(match self.cfg {
+          UnstableArgsConfig::None => None,
+          UnstableArgsConfig::ResolutionOnly
+          | UnstableArgsConfig::ResolutionAndRuntime => Some("true"),
+        })
+        .help_heading(UNSTABLE_HEADING)
+    } else if self.idx == 3 {
+      Arg::new("unstable-sloppy-imports")
+      .long("unstable-sloppy-imports")
+      .help("Enable unstable resolving of specifiers by extension probing, .js to .ts, and directory probing")
+      .env("DENO_UNSTABLE_SLOPPY_IMPORTS")
+      .value_parser(FalseyValueParser::new())
+      .action(ArgAction::SetTrue)
+      .hide(true)
+      .long_help(match self.cfg {
+        UnstableArgsConfig::None => None,
+        UnstableArgsConfig::ResolutionOnly | UnstableArgsConfig::ResolutionAndRuntime => Some("true")
+      })
+      .help_heading(UNSTABLE_HEADING)
+    } else if self.idx > 3 {
+      let (flag_name, help, _) =
+        crate::UNSTABLE_GRANULAR_FLAGS.get(self.idx - 4)?;
+      Arg::new(format!("unstable-{}", flag_name))
+        .long(format!("unstable-{}", flag_name))
+        .help(help)
+        .action(ArgAction::SetTrue)
+        .hide(true)
+        .help_heading(UNSTABLE_HEADING)
+        // we don't render long help, so using it here as a sort of metadata
+        .long_help(match self.cfg {
+          UnstableArgsConfig::None | UnstableArgsConfig::ResolutionOnly => None,
+          UnstableArgsConfig::ResolutionAndRuntime => Some("true"),
+        })
+    } else {
+      return None;
+    };
+    self.idx += 1;
+    Some(arg.display_order(self.idx + 1000))
+  }
+}
 
-    let module_url = cmd_values.next().unwrap();
-    let args = cmd_values.collect();
+fn unstable_args(cfg: UnstableArgsConfig) -> impl IntoIterator<Item = Arg> {
+  UnstableArgsIter { idx: 0, cfg }
+}
 
-    flags.subcommand = DenoSubcommand::Install(InstallFlags {
-      // TODO(bartlomieju): remove for 2.0
-      global,
-      kind: InstallKind::Global(InstallFlagsGlobal {
-        name,
-        module_url,
-        args,
-        root,
-        force,
-      }),
-    });
+fn allow_scripts_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let Some(parts) = matches.remove_many::<String>("allow-scripts") else {
+    return;
+  };
+  if parts.len() == 0 {
+    flags.allow_scripts = PackagesAllowedScripts::All;
   } else {
-    let local_flags = matches
-      .remove_many("cmd")
-      .map(|packages| add_parse_inner(matches, Some(packages)));
-    allow_scripts_arg_parse(flags, matches);
-    flags.subcommand = DenoSubcommand::Install(InstallFlags {
-      global,
-      kind: InstallKind::Local(local_flags),
-    })
+    flags.allow_scripts = PackagesAllowedScripts::Some(parts.collect());
   }
 }
 
-fn json_reference_parse(
-  flags: &mut Flags,
-  _matches: &mut ArgMatches,
-  mut app: Command,
-) {
-  use deno_core::serde_json::json;
-
-  app.build();
+fn add_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.subcommand = DenoSubcommand::Add(add_parse_inner(matches, None));
+}
 
-  fn serialize_command(
-    command: &mut Command,
-    top_level: bool,
-  ) -> deno_core::serde_json::Value {
-    let args = command
-      .get_arguments()
-      .filter(|arg| {
-        !arg.is_hide_set()
-          && if top_level {
-            true
-          } else {
-            !arg.is_global_set()
-          }
-      })
-      .map(|arg| {
-        let name = arg.get_id().as_str();
-        let short = arg.get_short();
-        let long = arg.get_long();
-        let aliases = arg.get_visible_aliases();
-        let required = arg.is_required_set();
-        let help = arg.get_help().map(|help| help.to_string());
-        let usage = arg.to_string();
+fn add_parse_inner(
+  matches: &mut ArgMatches,
+  packages: Option<clap::parser::Values<String>>,
+) -> AddFlags {
+  let packages = packages
+    .unwrap_or_else(|| matches.remove_many::<String>("packages").unwrap())
+    .collect();
+  AddFlags { packages }
+}
 
-        json!({
-          "name": name,
-          "short": short,
-          "long": long,
-          "aliases": aliases,
-          "required": required,
-          "help": help,
-          "usage": usage,
-        })
-      })
-      .collect::<Vec<_>>();
+fn remove_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.subcommand = DenoSubcommand::Remove(RemoveFlags {
+    packages: matches.remove_many::<String>("packages").unwrap().collect(),
+  });
+}
 
-    let name = command.get_name().to_string();
-    let about = command.get_about().map(|about| about.to_string());
-    let visible_aliases = command
-      .get_visible_aliases()
-      .map(|s| s.to_string())
-      .collect::<Vec<_>>();
-    let usage = command.render_usage().to_string();
+fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.type_check_mode = TypeCheckMode::Local;
 
-    let subcommands = command
-      .get_subcommands_mut()
-      .map(|command| serialize_command(command, false))
-      .collect::<Vec<_>>();
+  runtime_args_parse(flags, matches, true, false);
 
-    json!({
-      "name": name,
-      "about": about,
-      "visible_aliases": visible_aliases,
-      "args": args,
-      "subcommands": subcommands,
-      "usage": usage,
-    })
+  // NOTE: `deno bench` always uses `--no-prompt`, tests shouldn't ever do
+  // interactive prompts, unless done by user code
+  flags.permissions.no_prompt = true;
+
+  let json = matches.get_flag("json");
+
+  let ignore = match matches.remove_many::<String>("ignore") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+
+  let filter = matches.remove_one::<String>("filter");
+
+  if matches.contains_id("script_arg") {
+    flags
+      .argv
+      .extend(matches.remove_many::<String>("script_arg").unwrap());
   }
 
-  flags.subcommand = DenoSubcommand::JSONReference(JSONReferenceFlags {
-    json: serialize_command(&mut app, true),
-  })
-}
+  let include = if let Some(files) = matches.remove_many::<String>("files") {
+    files.collect()
+  } else {
+    Vec::new()
+  };
 
-fn jupyter_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  let conn_file = matches.remove_one::<String>("conn");
-  let kernel = matches.get_flag("kernel");
-  let install = matches.get_flag("install");
+  let no_run = matches.get_flag("no-run");
 
-  flags.subcommand = DenoSubcommand::Jupyter(JupyterFlags {
-    install,
-    kernel,
-    conn_file,
+  flags.subcommand = DenoSubcommand::Bench(BenchFlags {
+    files: FileFlags { include, ignore },
+    filter,
+    json,
+    no_run,
+    watch: watch_arg_parse(matches),
   });
 }
 
-fn uninstall_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  let root = matches.remove_one::<String>("root");
-  let global = matches.get_flag("global");
-  let name = matches.remove_one::<String>("name").unwrap();
-  flags.subcommand = DenoSubcommand::Uninstall(UninstallFlags {
-    // TODO(bartlomieju): remove once `deno uninstall` supports both local and
-    // global installs
-    global,
-    kind: UninstallKind::Global(UninstallFlagsGlobal { name, root }),
+fn bundle_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.type_check_mode = TypeCheckMode::Local;
+
+  compile_args_parse(flags, matches);
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
+
+  let source_file = matches.remove_one::<String>("source_file").unwrap();
+
+  let out_file =
+    if let Some(out_file) = matches.remove_one::<String>("out_file") {
+      flags.permissions.allow_write = Some(vec![]);
+      Some(out_file)
+    } else {
+      None
+    };
+
+  ext_arg_parse(flags, matches);
+
+  flags.subcommand = DenoSubcommand::Bundle(BundleFlags {
+    source_file,
+    out_file,
+    watch: watch_arg_parse(matches),
   });
 }
 
-fn lsp_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
-  flags.subcommand = DenoSubcommand::Lsp;
+fn cache_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  compile_args_parse(flags, matches);
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
+  frozen_lockfile_arg_parse(flags, matches);
+  allow_scripts_arg_parse(flags, matches);
+  let files = matches.remove_many::<String>("file").unwrap().collect();
+  flags.subcommand = DenoSubcommand::Cache(CacheFlags { files });
 }
 
-fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
+fn check_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.type_check_mode = TypeCheckMode::Local;
+  compile_args_without_check_parse(flags, matches);
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionAndRuntime);
+  let files = matches.remove_many::<String>("file").unwrap().collect();
+  if matches.get_flag("all") || matches.get_flag("remote") {
+    flags.type_check_mode = TypeCheckMode::All;
+  }
+  check_parallel_arg_parse(flags, matches);
+  flags.subcommand = DenoSubcommand::Check(CheckFlags { files });
+}
 
-  config_args_parse(flags, matches);
-  let files = match matches.remove_many::<String>("files") {
-    Some(f) => f.collect(),
-    None => vec![],
+fn clean_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let files = match matches.remove_many::<String>("file") {
+    Some(files) => files.collect(),
+    None => Vec::new(),
   };
-  let ignore = match matches.remove_many::<String>("ignore") {
+  flags.subcommand = DenoSubcommand::Clean(CleanFlags {
+    lock: matches.get_flag("lock"),
+    dry_run: matches.get_flag("dry-run"),
+    files,
+  });
+}
+
+fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.type_check_mode = TypeCheckMode::Local;
+  runtime_args_parse(flags, matches, true, false);
+
+  let mut script = matches.remove_many::<String>("script_arg").unwrap();
+  let source_file = script.next().unwrap();
+  let args = script.collect();
+  let output = matches.remove_one::<String>("output");
+  let target = matches.remove_one::<String>("target");
+  let icon = matches.remove_one::<String>("icon");
+  let no_terminal = matches.get_flag("no-terminal");
+  let include = match matches.remove_many::<String>("include") {
     Some(f) => f.collect(),
     None => vec![],
   };
-  let fix = matches.get_flag("fix");
-  let rules = matches.get_flag("rules");
-  let maybe_rules_tags = matches
-    .remove_many::<String>("rules-tags")
-    .map(|f| f.collect());
+  ext_arg_parse(flags, matches);
 
-  let maybe_rules_include = matches
-    .remove_many::<String>("rules-include")
-    .map(|f| f.collect());
+  flags.subcommand = DenoSubcommand::Compile(CompileFlags {
+    source_file,
+    output,
+    args,
+    target,
+    no_terminal,
+    icon,
+    include,
+  });
+}
 
-  let maybe_rules_exclude = matches
-    .remove_many::<String>("rules-exclude")
-    .map(|f| f.collect());
+fn completions_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+  mut app: Command,
+) {
+  use clap_complete::generate;
+  use clap_complete::shells::Bash;
+  use clap_complete::shells::Fish;
+  use clap_complete::shells::PowerShell;
+  use clap_complete::shells::Zsh;
+  use clap_complete_fig::Fig;
 
-  let json = matches.get_flag("json");
-  let compact = matches.get_flag("compact");
-  let ext = matches.remove_one::<String>("ext");
+  let mut buf: Vec<u8> = vec![];
+  let name = "deno";
 
-  flags.subcommand = DenoSubcommand::Lint(LintFlags {
+  match matches.get_one::<String>("shell").unwrap().as_str() {
+    "bash" => generate(Bash, &mut app, name, &mut buf),
+    "fish" => generate(Fish, &mut app, name, &mut buf),
+    "powershell" => generate(PowerShell, &mut app, name, &mut buf),
+    "zsh" => generate(Zsh, &mut app, name, &mut buf),
+    "fig" => generate(Fig, &mut app, name, &mut buf),
+    _ => unreachable!(),
+  }
+
+  flags.subcommand = DenoSubcommand::Completions(CompletionsFlags {
+    buf: buf.into_boxed_slice(),
+  });
+}
+
+fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let files = match matches.remove_many::<String>("files") {
+    Some(f) => f.collect(),
+    None => vec!["coverage".to_string()], // default
+  };
+  let ignore = match matches.remove_many::<String>("ignore") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  let include = match matches.remove_many::<String>("include") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  let exclude = match matches.remove_many::<String>("exclude") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  let r#type = if matches.get_flag("lcov") {
+    CoverageType::Lcov
+  } else if matches.get_flag("html") {
+    CoverageType::Html
+  } else if matches.get_flag("detailed") {
+    CoverageType::Detailed
+  } else {
+    CoverageType::Summary
+  };
+  let output = matches.remove_one::<String>("output");
+  flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
       ignore,
     },
-    fix,
-    rules,
-    maybe_rules_tags,
-    maybe_rules_include,
-    maybe_rules_exclude,
-    json,
-    compact,
-    watch: watch_arg_parse(matches),
-    ext,
+    output,
+    include,
+    exclude,
+    r#type,
   });
 }
 
-fn repl_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  runtime_args_parse(flags, matches, true, true);
-  unsafely_ignore_certificate_errors_parse(flags, matches);
-
-  let eval_files = matches
-    .remove_many::<String>("eval-file")
-    .map(|values| values.collect());
+fn doc_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
+  import_map_arg_parse(flags, matches);
+  reload_arg_parse(flags, matches);
+  lock_arg_parse(flags, matches);
+  no_lock_arg_parse(flags, matches);
+  no_npm_arg_parse(flags, matches);
+  no_remote_arg_parse(flags, matches);
 
-  handle_repl_flags(
-    flags,
-    ReplFlags {
-      eval_files,
-      eval: matches.remove_one::<String>("eval"),
-      is_default_command: false,
-    },
-  );
-}
+  let source_files_val = matches.remove_many::<String>("source_file");
+  let source_files = if let Some(val) = source_files_val {
+    let vals: Vec<String> = val.collect();
 
-fn run_parse(
-  flags: &mut Flags,
-  matches: &mut ArgMatches,
-  app: Command,
-  bare: bool,
-) -> clap::error::Result<()> {
-  // todo(dsherret): remove this in Deno 2.0
-  // This is a hack to make https://github.com/netlify/build/pull/5767 work
-  // for old versions of @netlify/edge-bundler with new versions of Deno
-  // where Deno has gotten smarter at resolving config files.
-  //
-  // It's an unfortunate scenario, but Netlify has the version at least
-  // pinned to 1.x in old versions so we can remove this in Deno 2.0 in
-  // a few months.
-  fn temp_netlify_deno_1_hack(flags: &mut Flags, script_arg: &str) {
-    fn is_netlify_edge_bundler_entrypoint(
-      flags: &Flags,
-      script_arg: &str,
-    ) -> bool {
-      // based on diff here: https://github.com/netlify/edge-bundler/blame/f1d33b74ca7aeec19a7c2149316d4547a94e43fb/node/config.ts#L85
-      if flags.permissions.allow_read.is_none()
-        || flags.permissions.allow_write.is_none()
-        || flags.config_flag != ConfigFlag::Discover
-      {
-        return false;
-      }
-      if !script_arg.contains("@netlify") {
-        return false;
-      }
-      let path = PathBuf::from(script_arg);
-      if !path.ends_with("deno/config.ts") {
-        return false;
-      }
-      let mut found_node_modules = false;
-      for component in path.components().filter_map(|c| c.as_os_str().to_str())
-      {
-        if !found_node_modules {
-          found_node_modules = component == "node_modules";
-        } else {
-          // make this work with pnpm and other package managers
-          if component.contains("@netlify") {
-            return true;
-          }
-        }
+    if vals.len() == 1 {
+      if vals[0] == "--builtin" {
+        DocSourceFileFlag::Builtin
+      } else {
+        DocSourceFileFlag::Paths(vec![vals[0].to_string()])
       }
-      false
-    }
-
-    if is_netlify_edge_bundler_entrypoint(flags, script_arg) {
-      flags.config_flag = ConfigFlag::Disabled;
+    } else {
+      DocSourceFileFlag::Paths(
+        vals.into_iter().filter(|v| v != "--builtin").collect(),
+      )
     }
-  }
-
-  runtime_args_parse(flags, matches, true, true);
-  ext_arg_parse(flags, matches);
-
-  flags.code_cache_enabled = !matches.get_flag("no-code-cache");
-
-  if let Some(mut script_arg) = matches.remove_many::<String>("script_arg") {
-    let script = script_arg.next().unwrap();
-    flags.argv.extend(script_arg);
-    temp_netlify_deno_1_hack(flags, &script);
-    flags.subcommand = DenoSubcommand::Run(RunFlags {
-      script,
-      watch: watch_arg_parse_with_paths(matches),
-      bare,
-    });
-  } else if bare {
-    return Err(app.override_usage("deno [OPTIONS] [COMMAND] [SCRIPT_ARG]...").error(
-      clap::error::ErrorKind::MissingRequiredArgument,
-      "[SCRIPT_ARG] may only be omitted with --v8-flags=--help, else to use the repl with arguments, please use the `deno repl` subcommand",
-    ));
   } else {
-    flags.subcommand = DenoSubcommand::Task(TaskFlags {
-      cwd: None,
-      task: None,
-      is_run: true,
-    });
-  }
-
-  Ok(())
-}
-
-fn serve_parse(
-  flags: &mut Flags,
-  matches: &mut ArgMatches,
-  app: Command,
-) -> clap::error::Result<()> {
-  // deno serve implies --allow-net=host:port
-  let port = matches.remove_one::<u16>("port").unwrap_or(8000);
-  let host = matches
-    .remove_one::<String>("host")
-    .unwrap_or_else(|| "0.0.0.0".to_owned());
-
-  let worker_count = parallel_arg_parse(matches, false).map(|v| v.get());
-
-  runtime_args_parse(flags, matches, true, true);
-  // If the user didn't pass --allow-net, add this port to the network
-  // allowlist. If the host is 0.0.0.0, we add :{port} and allow the same network perms
-  // as if it was passed to --allow-net directly.
-  let allowed = flags_net::parse(vec![if host == "0.0.0.0" {
-    format!(":{port}")
+    DocSourceFileFlag::Builtin
+  };
+  let private = matches.get_flag("private");
+  let lint = matches.get_flag("lint");
+  let json = matches.get_flag("json");
+  let filter = matches.remove_one::<String>("filter");
+  let html = if matches.get_flag("html") {
+    let name = matches.remove_one::<String>("name");
+    let category_docs_path = matches.remove_one::<String>("category-docs");
+    let symbol_redirect_map_path =
+      matches.remove_one::<String>("symbol-redirect-map");
+    let strip_trailing_html = matches.get_flag("strip-trailing-html");
+    let default_symbol_map_path =
+      matches.remove_one::<String>("default-symbol-map");
+    let output = matches
+      .remove_one::<String>("output")
+      .unwrap_or(String::from("./docs/"));
+    Some(DocHtmlFlag {
+      name,
+      category_docs_path,
+      symbol_redirect_map_path,
+      default_symbol_map_path,
+      strip_trailing_html,
+      output,
+    })
   } else {
-    format!("{host}:{port}")
-  }])?;
-  match &mut flags.permissions.allow_net {
-    None => flags.permissions.allow_net = Some(allowed),
-    Some(v) => {
-      if !v.is_empty() {
-        v.extend(allowed);
-      }
-    }
-  }
-  flags.code_cache_enabled = !matches.get_flag("no-code-cache");
-
-  let mut script_arg =
-    matches.remove_many::<String>("script_arg").ok_or_else(|| {
-      let mut app = app;
-      let subcommand = &mut app.find_subcommand_mut("serve").unwrap();
-      subcommand.error(
-        clap::error::ErrorKind::MissingRequiredArgument,
-        "[SCRIPT_ARG] may only be omitted with --v8-flags=--help",
-      )
-    })?;
-
-  let script = script_arg.next().unwrap();
-  flags.argv.extend(script_arg);
-
-  ext_arg_parse(flags, matches);
+    None
+  };
 
-  flags.subcommand = DenoSubcommand::Serve(ServeFlags {
-    script,
-    watch: watch_arg_parse_with_paths(matches),
-    port,
-    host,
-    worker_count,
+  flags.subcommand = DenoSubcommand::Doc(DocFlags {
+    source_files,
+    json,
+    lint,
+    html,
+    filter,
+    private,
   });
-
-  Ok(())
 }
 
-fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.config_flag = matches
-    .remove_one::<String>("config")
-    .map(ConfigFlag::Path)
-    .unwrap_or(ConfigFlag::Discover);
-
+fn eval_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  runtime_args_parse(flags, matches, false, true);
   unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionAndRuntime);
+  flags.allow_all();
 
-  let mut task_flags = TaskFlags {
-    cwd: matches.remove_one::<String>("cwd"),
-    task: None,
-    is_run: false,
-  };
+  ext_arg_parse(flags, matches);
+  flags.synthetic_scheme = matches.remove_one::<String>("synthetic-scheme");
 
-  if let Some((task, mut matches)) = matches.remove_subcommand() {
-    task_flags.task = Some(task);
+  // TODO(@satyarohith): remove this flag in 2.0.
+  let as_typescript = matches.get_flag("ts");
 
-    flags.argv.extend(
-      matches
-        .remove_many::<std::ffi::OsString>("")
-        .into_iter()
-        .flatten()
-        .filter_map(|arg| arg.into_string().ok()),
+  #[allow(clippy::print_stderr)]
+  if as_typescript {
+    eprintln!(
+      "⚠️ {}",
+      crate::colors::yellow(
+        "Use `--ext=ts` instead. The `--ts` and `-T` flags are deprecated and will be removed in Deno 2.0."
+      ),
     );
+
+    flags.ext = Some("ts".to_string());
   }
 
-  flags.subcommand = DenoSubcommand::Task(task_flags);
-}
+  let print = matches.get_flag("print");
+  let repl = matches.get_flag("repl");
+  let mut code_args = matches.remove_many::<String>("code_arg").unwrap();
+  let code = code_args.next().unwrap();
+  flags.argv.extend(code_args);
 
-fn parallel_arg_parse(
-  matches: &mut ArgMatches,
-  fallback_to_jobs: bool,
-) -> Option<NonZeroUsize> {
-  if matches.get_flag("parallel") {
-    if let Ok(value) = env::var("DENO_JOBS") {
-      value.parse::<NonZeroUsize>().ok()
-    } else {
-      std::thread::available_parallelism().ok()
-    }
-  } else if fallback_to_jobs && matches.contains_id("jobs") {
-    // We can't change this to use the log crate because its not configured
-    // yet at this point since the flags haven't been parsed. This flag is
-    // deprecated though so it's not worth changing the code to use the log
-    // crate here and this is only done for testing anyway.
-    #[allow(clippy::print_stderr)]
-    {
-      eprintln!(
-        "⚠️ {}",
-        crate::colors::yellow(concat!(
-          "The `--jobs` flag is deprecated and will be removed in Deno 2.0.\n",
-          "Use the `--parallel` flag with possibly the `DENO_JOBS` environment variable instead.\n",
-          "Learn more at: https://docs.deno.com/runtime/manual/basics/env_variables"
-        )),
-      );
-    }
-    if let Some(value) = matches.remove_one::<NonZeroUsize>("jobs") {
-      Some(value)
-    } else {
-      std::thread::available_parallelism().ok()
-    }
-  } else {
-    None
-  }
+  flags.subcommand = DenoSubcommand::Eval(EvalFlags { print, code, repl });
 }
 
-fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.type_check_mode = TypeCheckMode::Local;
-  runtime_args_parse(flags, matches, true, true);
-  // NOTE: `deno test` always uses `--no-prompt`, tests shouldn't ever do
-  // interactive prompts, unless done by user code
-  flags.permissions.no_prompt = true;
+fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  config_args_parse(flags, matches);
+  ext_arg_parse(flags, matches);
 
+  let include = match matches.remove_many::<String>("files") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
   let ignore = match matches.remove_many::<String>("ignore") {
     Some(f) => f.collect(),
     None => vec![],
   };
 
-  let no_run = matches.get_flag("no-run");
-  let trace_leaks =
-    matches.get_flag("trace-ops") || matches.get_flag("trace-leaks");
-
-  #[allow(clippy::print_stderr)]
-  if trace_leaks && matches.get_flag("trace-ops") {
-    // We can't change this to use the log crate because its not configured
-    // yet at this point since the flags haven't been parsed. This flag is
-    // deprecated though so it's not worth changing the code to use the log
-    // crate here and this is only done for testing anyway.
-    eprintln!(
-      "⚠️ {}",
-      crate::colors::yellow("The `--trace-ops` flag is deprecated and will be removed in Deno 2.0.\nUse the `--trace-leaks` flag instead."),
-    );
-  }
-  let doc = matches.get_flag("doc");
-  #[allow(clippy::print_stderr)]
-  let allow_none = matches.get_flag("permit-no-files")
-    || if matches.get_flag("allow-none") {
-      eprintln!(
-      "⚠️ {}",
-      crate::colors::yellow("The `--allow-none` flag is deprecated and will be removed in Deno 2.0.\nUse the `--permit-no-files` flag instead."),
-    );
-      true
-    } else {
-      false
-    };
-  let filter = matches.remove_one::<String>("filter");
-  let clean = matches.get_flag("clean");
-
-  let fail_fast = if matches.contains_id("fail-fast") {
-    Some(
-      matches
-        .remove_one::<NonZeroUsize>("fail-fast")
-        .unwrap_or_else(|| NonZeroUsize::new(1).unwrap()),
-    )
-  } else {
-    None
-  };
-
-  let shuffle = if matches.contains_id("shuffle") {
-    Some(
-      matches
-        .remove_one::<u64>("shuffle")
-        .unwrap_or_else(rand::random),
-    )
-  } else {
-    None
-  };
-
-  if let Some(script_arg) = matches.remove_many::<String>("script_arg") {
-    flags.argv.extend(script_arg);
-  }
-
-  let concurrent_jobs = parallel_arg_parse(matches, true);
-
-  let include = if let Some(files) = matches.remove_many::<String>("files") {
-    files.collect()
-  } else {
-    Vec::new()
-  };
-
-  let junit_path = matches.remove_one::<String>("junit-path");
-
-  let reporter =
-    if let Some(reporter) = matches.remove_one::<String>("reporter") {
-      match reporter.as_str() {
-        "pretty" => TestReporterConfig::Pretty,
-        "junit" => TestReporterConfig::Junit,
-        "dot" => TestReporterConfig::Dot,
-        "tap" => TestReporterConfig::Tap,
-        _ => unreachable!(),
-      }
-    } else {
-      TestReporterConfig::Pretty
-    };
-
-  if matches!(reporter, TestReporterConfig::Dot | TestReporterConfig::Tap) {
-    flags.log_level = Some(Level::Error);
-  }
-
-  let hide_stacktraces = matches.get_flag("hide-stacktraces");
+  let use_tabs = matches.remove_one::<bool>("use-tabs");
+  let line_width = matches.remove_one::<NonZeroU32>("line-width");
+  let indent_width = matches.remove_one::<NonZeroU8>("indent-width");
+  let single_quote = matches.remove_one::<bool>("single-quote");
+  let prose_wrap = matches.remove_one::<String>("prose-wrap");
+  let no_semicolons = matches.remove_one::<bool>("no-semicolons");
+  let unstable_css = matches.get_flag("unstable-css");
+  let unstable_html = matches.get_flag("unstable-html");
+  let unstable_component = matches.get_flag("unstable-component");
+  let unstable_yaml = matches.get_flag("unstable-yaml");
 
-  flags.subcommand = DenoSubcommand::Test(TestFlags {
-    no_run,
-    doc,
-    coverage_dir: matches.remove_one::<String>("coverage"),
-    clean,
-    fail_fast,
+  flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
+    check: matches.get_flag("check"),
     files: FileFlags { include, ignore },
-    filter,
-    shuffle,
-    allow_none,
-    concurrent_jobs,
-    trace_leaks,
-    watch: watch_arg_parse_with_paths(matches),
-    reporter,
-    junit_path,
-    hide_stacktraces,
+    use_tabs,
+    line_width,
+    indent_width,
+    single_quote,
+    prose_wrap,
+    no_semicolons,
+    watch: watch_arg_parse(matches),
+    unstable_css,
+    unstable_html,
+    unstable_component,
+    unstable_yaml,
   });
 }
 
-fn types_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
-  flags.subcommand = DenoSubcommand::Types;
-}
-
-fn upgrade_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  ca_file_arg_parse(flags, matches);
-  unsafely_ignore_certificate_errors_parse(flags, matches);
-
-  let dry_run = matches.get_flag("dry-run");
-  let force = matches.get_flag("force");
-  let canary = matches.get_flag("canary");
-  let release_candidate = matches.get_flag("release-candidate");
-  let version = matches.remove_one::<String>("version");
-  let output = matches.remove_one::<String>("output");
-  let version_or_hash_or_channel =
-    matches.remove_one::<String>("version-or-hash-or-channel");
-  flags.subcommand = DenoSubcommand::Upgrade(UpgradeFlags {
-    dry_run,
-    force,
-    release_candidate,
-    canary,
-    version,
-    output,
-    version_or_hash_or_channel,
+fn init_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.subcommand = DenoSubcommand::Init(InitFlags {
+    dir: matches.remove_one::<String>("dir"),
+    lib: matches.get_flag("lib"),
+    serve: matches.get_flag("serve"),
   });
 }
 
-fn vendor_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
-  ca_file_arg_parse(flags, matches);
-  unsafely_ignore_certificate_errors_parse(flags, matches);
+  reload_arg_parse(flags, matches);
   config_args_parse(flags, matches);
   import_map_arg_parse(flags, matches);
-  lock_arg_parse(flags, matches);
+  location_arg_parse(flags, matches);
+  ca_file_arg_parse(flags, matches);
+  unsafely_ignore_certificate_errors_parse(flags, matches);
   node_modules_and_vendor_dir_arg_parse(flags, matches);
-  reload_arg_parse(flags, matches);
-
-  flags.subcommand = DenoSubcommand::Vendor(VendorFlags {
-    specifiers: matches
-      .remove_many::<String>("specifiers")
-      .map(|p| p.collect())
-      .unwrap_or_default(),
-    output_path: matches.remove_one::<String>("output"),
-    force: matches.get_flag("force"),
+  lock_args_parse(flags, matches);
+  no_remote_arg_parse(flags, matches);
+  no_npm_arg_parse(flags, matches);
+  let json = matches.get_flag("json");
+  flags.subcommand = DenoSubcommand::Info(InfoFlags {
+    file: matches.remove_one::<String>("file"),
+    json,
   });
 }
 
-fn publish_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.type_check_mode = TypeCheckMode::Local; // local by default
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
-  no_check_arg_parse(flags, matches);
-  check_arg_parse(flags, matches);
-  config_args_parse(flags, matches);
+fn install_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  runtime_args_parse(flags, matches, true, true);
 
-  flags.subcommand = DenoSubcommand::Publish(PublishFlags {
-    token: matches.remove_one("token"),
-    dry_run: matches.get_flag("dry-run"),
-    allow_slow_types: matches.get_flag("allow-slow-types"),
-    allow_dirty: matches.get_flag("allow-dirty"),
-    no_provenance: matches.get_flag("no-provenance"),
-  });
-}
+  let global = matches.get_flag("global");
+  if global || !*DENO_FUTURE {
+    let root = matches.remove_one::<String>("root");
+    let force = matches.get_flag("force");
+    let name = matches.remove_one::<String>("name");
+    let mut cmd_values =
+      matches.remove_many::<String>("cmd").unwrap_or_default();
 
-fn compile_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  compile_args_without_check_parse(flags, matches);
-  no_check_arg_parse(flags, matches);
-  check_arg_parse(flags, matches);
+    let module_url = cmd_values.next().unwrap();
+    let args = cmd_values.collect();
+
+    flags.subcommand = DenoSubcommand::Install(InstallFlags {
+      // TODO(bartlomieju): remove for 2.0
+      global,
+      kind: InstallKind::Global(InstallFlagsGlobal {
+        name,
+        module_url,
+        args,
+        root,
+        force,
+      }),
+    });
+  } else {
+    let local_flags = matches
+      .remove_many("cmd")
+      .map(|packages| add_parse_inner(matches, Some(packages)));
+    allow_scripts_arg_parse(flags, matches);
+    flags.subcommand = DenoSubcommand::Install(InstallFlags {
+      global,
+      kind: InstallKind::Local(local_flags),
+    })
+  }
 }
 
-fn compile_args_without_check_parse(
+fn json_reference_parse(
   flags: &mut Flags,
-  matches: &mut ArgMatches,
+  _matches: &mut ArgMatches,
+  mut app: Command,
 ) {
-  import_map_arg_parse(flags, matches);
-  no_remote_arg_parse(flags, matches);
-  no_npm_arg_parse(flags, matches);
-  node_modules_and_vendor_dir_arg_parse(flags, matches);
-  config_args_parse(flags, matches);
-  reload_arg_parse(flags, matches);
-  lock_args_parse(flags, matches);
-  ca_file_arg_parse(flags, matches);
-  unsafely_ignore_certificate_errors_parse(flags, matches);
-}
-
-fn permission_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(read_wl) = matches.remove_many::<String>("allow-read") {
-    flags.permissions.allow_read = Some(read_wl.collect());
-  }
-
-  if let Some(read_wl) = matches.remove_many::<String>("deny-read") {
-    flags.permissions.deny_read = Some(read_wl.collect());
-  }
-
-  if let Some(write_wl) = matches.remove_many::<String>("allow-write") {
-    flags.permissions.allow_write = Some(write_wl.collect());
-  }
+  use deno_core::serde_json::json;
 
-  if let Some(write_wl) = matches.remove_many::<String>("deny-write") {
-    flags.permissions.deny_write = Some(write_wl.collect());
-  }
+  app.build();
 
-  if let Some(net_wl) = matches.remove_many::<String>("allow-net") {
-    let net_allowlist = flags_net::parse(net_wl.collect()).unwrap();
-    flags.permissions.allow_net = Some(net_allowlist);
-  }
-
-  if let Some(net_wl) = matches.remove_many::<String>("deny-net") {
-    let net_denylist = flags_net::parse(net_wl.collect()).unwrap();
-    flags.permissions.deny_net = Some(net_denylist);
-  }
-
-  if let Some(env_wl) = matches.remove_many::<String>("allow-env") {
-    flags.permissions.allow_env = Some(env_wl.collect());
-    debug!("env allowlist: {:#?}", &flags.permissions.allow_env);
-  }
+  fn serialize_command(
+    command: &mut Command,
+    top_level: bool,
+  ) -> deno_core::serde_json::Value {
+    let args = command
+      .get_arguments()
+      .filter(|arg| {
+        !arg.is_hide_set()
+          && if top_level {
+            true
+          } else {
+            !arg.is_global_set()
+          }
+      })
+      .map(|arg| {
+        let name = arg.get_id().as_str();
+        let short = arg.get_short();
+        let long = arg.get_long();
+        let aliases = arg.get_visible_aliases();
+        let required = arg.is_required_set();
+        let help = arg.get_help().map(|help| help.to_string());
+        let usage = arg.to_string();
 
-  if let Some(env_wl) = matches.remove_many::<String>("deny-env") {
-    flags.permissions.deny_env = Some(env_wl.collect());
-    debug!("env denylist: {:#?}", &flags.permissions.deny_env);
-  }
+        json!({
+          "name": name,
+          "short": short,
+          "long": long,
+          "aliases": aliases,
+          "required": required,
+          "help": help,
+          "usage": usage,
+        })
+      })
+      .collect::<Vec<_>>();
 
-  if let Some(run_wl) = matches.remove_many::<String>("allow-run") {
-    flags.permissions.allow_run = Some(run_wl.collect());
-    debug!("run allowlist: {:#?}", &flags.permissions.allow_run);
-  }
+    let name = command.get_name().to_string();
+    let about = command.get_about().map(|about| about.to_string());
+    let visible_aliases = command
+      .get_visible_aliases()
+      .map(|s| s.to_string())
+      .collect::<Vec<_>>();
+    let usage = command.render_usage().to_string();
 
-  if let Some(run_wl) = matches.remove_many::<String>("deny-run") {
-    flags.permissions.deny_run = Some(run_wl.collect());
-    debug!("run denylist: {:#?}", &flags.permissions.deny_run);
-  }
+    let subcommands = command
+      .get_subcommands_mut()
+      .map(|command| serialize_command(command, false))
+      .collect::<Vec<_>>();
 
-  if let Some(sys_wl) = matches.remove_many::<String>("allow-sys") {
-    flags.permissions.allow_sys = Some(sys_wl.collect());
-    debug!("sys info allowlist: {:#?}", &flags.permissions.allow_sys);
+    json!({
+      "name": name,
+      "about": about,
+      "visible_aliases": visible_aliases,
+      "args": args,
+      "subcommands": subcommands,
+      "usage": usage,
+    })
   }
 
-  if let Some(sys_wl) = matches.remove_many::<String>("deny-sys") {
-    flags.permissions.deny_sys = Some(sys_wl.collect());
-    debug!("sys info denylist: {:#?}", &flags.permissions.deny_sys);
-  }
+  flags.subcommand = DenoSubcommand::JSONReference(JSONReferenceFlags {
+    json: serialize_command(&mut app, true),
+  })
+}
 
-  if let Some(ffi_wl) = matches.remove_many::<String>("allow-ffi") {
-    flags.permissions.allow_ffi = Some(ffi_wl.collect());
-    debug!("ffi allowlist: {:#?}", &flags.permissions.allow_ffi);
-  }
+fn jupyter_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let conn_file = matches.remove_one::<String>("conn");
+  let kernel = matches.get_flag("kernel");
+  let install = matches.get_flag("install");
 
-  if let Some(ffi_wl) = matches.remove_many::<String>("deny-ffi") {
-    flags.permissions.deny_ffi = Some(ffi_wl.collect());
-    debug!("ffi denylist: {:#?}", &flags.permissions.deny_ffi);
-  }
+  flags.subcommand = DenoSubcommand::Jupyter(JupyterFlags {
+    install,
+    kernel,
+    conn_file,
+  });
+}
 
-  if matches.get_flag("allow-hrtime") {
-    flags.permissions.allow_hrtime = true;
-  }
+fn uninstall_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let root = matches.remove_one::<String>("root");
+  let global = matches.get_flag("global");
+  let name = matches.remove_one::<String>("name").unwrap();
+  flags.subcommand = DenoSubcommand::Uninstall(UninstallFlags {
+    // TODO(bartlomieju): remove once `deno uninstall` supports both local and
+    // global installs
+    global,
+    kind: UninstallKind::Global(UninstallFlagsGlobal { name, root }),
+  });
+}
 
-  if matches.get_flag("deny-hrtime") {
-    flags.permissions.deny_hrtime = true;
-  }
+fn lsp_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
+  flags.subcommand = DenoSubcommand::Lsp;
+}
 
-  if matches.get_flag("allow-all") {
-    flags.allow_all();
-  }
+fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
 
-  if matches.get_flag("no-prompt") {
-    flags.permissions.no_prompt = true;
-  }
-}
+  config_args_parse(flags, matches);
+  let files = match matches.remove_many::<String>("files") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  let ignore = match matches.remove_many::<String>("ignore") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  let fix = matches.get_flag("fix");
+  let rules = matches.get_flag("rules");
+  let maybe_rules_tags = matches
+    .remove_many::<String>("rules-tags")
+    .map(|f| f.collect());
 
-fn unsafely_ignore_certificate_errors_parse(
-  flags: &mut Flags,
-  matches: &mut ArgMatches,
-) {
-  if let Some(ic_wl) =
-    matches.remove_many::<String>("unsafely-ignore-certificate-errors")
-  {
-    let ic_allowlist = flags_net::parse(ic_wl.collect()).unwrap();
-    flags.unsafely_ignore_certificate_errors = Some(ic_allowlist);
-  }
-}
+  let maybe_rules_include = matches
+    .remove_many::<String>("rules-include")
+    .map(|f| f.collect());
 
-fn runtime_args_parse(
-  flags: &mut Flags,
-  matches: &mut ArgMatches,
-  include_perms: bool,
-  include_inspector: bool,
-) {
-  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionAndRuntime);
-  compile_args_parse(flags, matches);
-  cached_only_arg_parse(flags, matches);
-  frozen_lockfile_arg_parse(flags, matches);
-  if include_perms {
-    permission_args_parse(flags, matches);
-  }
-  if include_inspector {
-    inspect_arg_parse(flags, matches);
-  }
-  location_arg_parse(flags, matches);
-  v8_flags_arg_parse(flags, matches);
-  seed_arg_parse(flags, matches);
-  enable_testing_features_arg_parse(flags, matches);
-  env_file_arg_parse(flags, matches);
-  strace_ops_parse(flags, matches);
-}
+  let maybe_rules_exclude = matches
+    .remove_many::<String>("rules-exclude")
+    .map(|f| f.collect());
 
-fn inspect_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.inspect = matches.remove_one::<SocketAddr>("inspect");
-  flags.inspect_brk = matches.remove_one::<SocketAddr>("inspect-brk");
-  flags.inspect_wait = matches.remove_one::<SocketAddr>("inspect-wait");
-}
+  let json = matches.get_flag("json");
+  let compact = matches.get_flag("compact");
+  let ext = matches.remove_one::<String>("ext");
 
-fn import_map_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.import_map_path = matches.remove_one::<String>("import-map");
+  flags.subcommand = DenoSubcommand::Lint(LintFlags {
+    files: FileFlags {
+      include: files,
+      ignore,
+    },
+    fix,
+    rules,
+    maybe_rules_tags,
+    maybe_rules_include,
+    maybe_rules_exclude,
+    json,
+    compact,
+    watch: watch_arg_parse(matches),
+    ext,
+  });
 }
 
-fn env_file_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.env_file = matches.remove_one::<String>("env-file");
-}
+fn repl_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  runtime_args_parse(flags, matches, true, true);
+  unsafely_ignore_certificate_errors_parse(flags, matches);
 
-fn reload_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(cache_bl) = matches.remove_many::<String>("reload") {
-    let raw_cache_blocklist: Vec<String> = cache_bl.collect();
-    if raw_cache_blocklist.is_empty() {
-      flags.reload = true;
-    } else {
-      flags.cache_blocklist = resolve_urls(raw_cache_blocklist);
-      debug!("cache blocklist: {:#?}", &flags.cache_blocklist);
-      flags.reload = false;
-    }
-  }
-}
+  let eval_files = matches
+    .remove_many::<String>("eval-file")
+    .map(|values| values.collect());
 
-fn ca_file_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.ca_data = matches.remove_one::<String>("cert").map(CaData::File);
+  handle_repl_flags(
+    flags,
+    ReplFlags {
+      eval_files,
+      eval: matches.remove_one::<String>("eval"),
+      is_default_command: false,
+    },
+  );
 }
 
-fn enable_testing_features_arg_parse(
+fn run_parse(
   flags: &mut Flags,
   matches: &mut ArgMatches,
-) {
-  if matches.get_flag("enable-testing-features-do-not-use") {
-    flags.enable_testing_features = true
-  }
-}
-
-fn strace_ops_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(patterns) = matches.remove_many::<String>("strace-ops") {
-    flags.strace_ops = Some(patterns.collect());
-  }
-}
-
-fn cached_only_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if matches.get_flag("cached-only") {
-    flags.cached_only = true;
-  }
-}
+  app: Command,
+  bare: bool,
+) -> clap::error::Result<()> {
+  // todo(dsherret): remove this in Deno 2.0
+  // This is a hack to make https://github.com/netlify/build/pull/5767 work
+  // for old versions of @netlify/edge-bundler with new versions of Deno
+  // where Deno has gotten smarter at resolving config files.
+  //
+  // It's an unfortunate scenario, but Netlify has the version at least
+  // pinned to 1.x in old versions so we can remove this in Deno 2.0 in
+  // a few months.
+  fn temp_netlify_deno_1_hack(flags: &mut Flags, script_arg: &str) {
+    fn is_netlify_edge_bundler_entrypoint(
+      flags: &Flags,
+      script_arg: &str,
+    ) -> bool {
+      // based on diff here: https://github.com/netlify/edge-bundler/blame/f1d33b74ca7aeec19a7c2149316d4547a94e43fb/node/config.ts#L85
+      if flags.permissions.allow_read.is_none()
+        || flags.permissions.allow_write.is_none()
+        || flags.config_flag != ConfigFlag::Discover
+      {
+        return false;
+      }
+      if !script_arg.contains("@netlify") {
+        return false;
+      }
+      let path = PathBuf::from(script_arg);
+      if !path.ends_with("deno/config.ts") {
+        return false;
+      }
+      let mut found_node_modules = false;
+      for component in path.components().filter_map(|c| c.as_os_str().to_str())
+      {
+        if !found_node_modules {
+          found_node_modules = component == "node_modules";
+        } else {
+          // make this work with pnpm and other package managers
+          if component.contains("@netlify") {
+            return true;
+          }
+        }
+      }
+      false
+    }
 
-fn frozen_lockfile_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(&v) = matches.get_one::<bool>("frozen") {
-    flags.frozen_lockfile = Some(v);
+    if is_netlify_edge_bundler_entrypoint(flags, script_arg) {
+      flags.config_flag = ConfigFlag::Disabled;
+    }
   }
-}
 
-fn ext_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.ext = matches.remove_one::<String>("ext");
-}
+  runtime_args_parse(flags, matches, true, true);
+  ext_arg_parse(flags, matches);
 
-fn location_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.location = matches.remove_one::<Url>("location");
-}
+  code_cache_arg_parse(flags, matches);
+  flags.trace_file = matches.remove_one::<String>("trace");
+  flags.verify_npm_contents = matches.get_flag("verify-npm-contents");
+  flags.warm_npm = matches.remove_one::<String>("warm-npm");
+  flags.module_http1_only = matches.get_flag("module-http-h1-only");
+  flags.max_redirect_bytes = matches.remove_one::<u64>("max-redirect-bytes");
+  flags.module_fetch_retries =
+    matches.remove_one::<u32>("module-fetch-retries");
+  flags.module_fetch_concurrency =
+    matches.remove_one::<NonZeroUsize>("module-fetch-concurrency");
+  flags.module_fetch_concurrency_per_host =
+    matches.remove_one::<NonZeroUsize>("module-fetch-concurrency-per-host");
+  flags.vfs_overlay = matches.remove_one::<String>("vfs-overlay");
+  flags.error_format = match matches.remove_one::<String>("error-format") {
+    Some(format) if format == "json" => ErrorFormat::Json,
+    _ => ErrorFormat::Pretty,
+  };
+  flags.color = match matches.remove_one::<String>("color").as_deref() {
+    Some("always") => ColorMode::Always,
+    Some("never") => ColorMode::Never,
+    _ => ColorMode::Auto,
+  };
+  flags.deprecation =
+    match matches.remove_one::<String>("deprecation").as_deref() {
+      Some("warn") => Some(DeprecationMode::Warn),
+      Some("error") => Some(DeprecationMode::Error),
+      Some("silent") => Some(DeprecationMode::Silent),
+      _ => None,
+    };
+  flags.check_resource_leaks = matches.get_flag("check-resource-leaks");
+  flags.exit_drain = matches.remove_one::<u64>("exit-drain");
+  flags.tee_stdout = matches.remove_one::<String>("tee-stdout");
+  flags.tee_stderr = matches.remove_one::<String>("tee-stderr");
+  flags.report_hrtime_usage = matches.get_flag("report-hrtime-usage");
+  flags.warmup = matches.get_flag("warmup");
+  flags.report_unused_exports = matches.get_flag("report-unused-exports");
+  flags.max_graph_modules = matches.remove_one::<usize>("max-graph-modules");
+  flags.config_schema_validate = matches.get_flag("config-schema-validate");
+  flags.record_nondeterminism =
+    matches.remove_one::<String>("record-nondeterminism");
+  flags.replay_nondeterminism =
+    matches.remove_one::<String>("replay-nondeterminism");
+  flags.permissions_prompt_timeout =
+    matches.remove_one::<u64>("permissions-prompt-timeout");
+  flags.npm_snapshot = matches.remove_one::<String>("npm-snapshot");
+  flags.fail_on_install_scripts = matches.get_flag("fail-on-install-scripts");
+  flags.detect_install_scripts =
+    flags.fail_on_install_scripts || matches.get_flag("detect-install-scripts");
+  flags.print_bin = matches.get_flag("print-bin");
+  flags.print_entrypoint = matches.get_flag("print-entrypoint");
+  flags.vendor_out = matches.remove_one::<String>("vendor-out");
+  flags.cache_backend = matches.remove_one::<String>("cache-backend");
+  flags.abort_on_warning = matches.remove_one::<String>("abort-on-warning");
+  check_parallel_arg_parse(flags, matches);
+  flags.force_strict = matches.get_flag("force-strict");
+  if let Some(on_stale) = matches.remove_one::<String>("byonm-on-stale") {
+    flags.byonm_on_stale = Some(match on_stale.as_str() {
+      "warn" => ByonmOnStale::Warn,
+      "auto-install" => ByonmOnStale::AutoInstall,
+      _ => ByonmOnStale::Error,
+    });
+  }
+  if matches.remove_one::<String>("import-policy").as_deref() == Some("deny") {
+    flags.import_allow_list = Some(
+      matches
+        .remove_many::<String>("allow-import-spec")
+        .map(|specs| specs.collect())
+        .unwrap_or_default(),
+    );
+  }
+  flags.synthetic_scheme = matches.remove_one::<String>("synthetic-scheme");
+  if matches.contains_id("cpu-prof") {
+    flags.cpu_prof = Some(
+      matches
+        .remove_one::<String>("cpu-prof")
+        .unwrap_or_else(default_cpu_prof_file_name),
+    );
+  }
+  flags.tz = matches.remove_one::<String>("tz");
+  flags.locale = matches.remove_one::<String>("locale");
+  let preload = matches
+    .remove_many::<String>("preload")
+    .map(|modules| modules.collect())
+    .unwrap_or_default();
+  let extra_scripts = matches
+    .remove_many::<String>("then")
+    .map(|scripts| scripts.collect())
+    .unwrap_or_default();
+  let coverage_dir = matches.remove_one::<String>("coverage");
+  let print_config_only = matches.get_flag("print-config-only");
+  if print_config_only || matches.get_flag("print-config") {
+    flags.print_config = Some(PrintConfigFlags {
+      json: matches.get_flag("json"),
+      only: print_config_only,
+    });
+  }
+  let dump_ast_all = matches.get_flag("dump-ast-all");
+  if dump_ast_all || matches.get_flag("dump-ast") {
+    flags.dump_ast = Some(DumpAstFlags {
+      all: dump_ast_all,
+      json: matches.get_flag("json"),
+    });
+  }
+  if matches.get_flag("dry-run") {
+    flags.dry_run = Some(DryRunFlags {
+      json: matches.get_flag("json"),
+    });
+  }
 
-fn v8_flags_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(v8_flags) = matches.remove_many::<String>("v8-flags") {
-    flags.v8_flags = v8_flags.collect();
+  if let Some(mut script_arg) = matches.remove_many::<String>("script_arg") {
+    let script = script_arg.next().unwrap();
+    flags.argv.extend(script_arg);
+    temp_netlify_deno_1_hack(flags, &script);
+    flags.subcommand = DenoSubcommand::Run(RunFlags {
+      script,
+      watch: watch_arg_parse_with_paths(matches),
+      bare,
+      preload,
+      extra_scripts,
+      skip_upgrade_check: false,
+      coverage_dir,
+    });
+  } else if flags.warm_npm.is_some() {
+    // only fill the npm cache
+    flags.subcommand = DenoSubcommand::Run(RunFlags {
+      script: String::new(),
+      watch: None,
+      bare,
+      preload,
+      extra_scripts,
+      skip_upgrade_check: false,
+      coverage_dir: None,
+    });
+  } else if bare {
+    return Err(app.override_usage("deno [OPTIONS] [COMMAND] [SCRIPT_ARG]...").error(
+      clap::error::ErrorKind::MissingRequiredArgument,
+      "[SCRIPT_ARG] may only be omitted with --v8-flags=--help, else to use the repl with arguments, please use the `deno repl` subcommand",
+    ));
+  } else {
+    flags.subcommand = DenoSubcommand::Task(TaskFlags {
+      cwd: None,
+      task: None,
+      is_run: true,
+    });
   }
+
+  Ok(())
 }
 
-fn seed_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(seed) = matches.remove_one::<u64>("seed") {
-    flags.seed = Some(seed);
+fn serve_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+  app: Command,
+) -> clap::error::Result<()> {
+  // deno serve implies --allow-net=host:port
+  let port = matches.remove_one::<u16>("port").unwrap_or(8000);
+  let host = matches
+    .remove_one::<String>("host")
+    .unwrap_or_else(|| "0.0.0.0".to_owned());
 
-    flags.v8_flags.push(format!("--random-seed={seed}"));
-  }
-}
+  let worker_count = parallel_arg_parse(matches, false).map(|v| v.get());
 
-fn no_check_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(cache_type) = matches.get_one::<String>("no-check") {
-    match cache_type.as_str() {
-      "remote" => flags.type_check_mode = TypeCheckMode::Local,
-      _ => debug!(
-        "invalid value for 'no-check' of '{}' using default",
-        cache_type
-      ),
+  runtime_args_parse(flags, matches, true, true);
+  // If the user didn't pass --allow-net, add this port to the network
+  // allowlist. If the host is 0.0.0.0, we add :{port} and allow the same network perms
+  // as if it was passed to --allow-net directly.
+  let allowed = flags_net::parse(vec![if host == "0.0.0.0" {
+    format!(":{port}")
+  } else {
+    format!("{host}:{port}")
+  }])?;
+  match &mut flags.permissions.allow_net {
+    None => flags.permissions.allow_net = Some(allowed),
+    Some(v) => {
+      if !v.is_empty() {
+        v.extend(allowed);
+      }
     }
-  } else if matches.contains_id("no-check") {
-    flags.type_check_mode = TypeCheckMode::None;
   }
-}
+  code_cache_arg_parse(flags, matches);
 
-fn check_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if let Some(cache_type) = matches.get_one::<String>("check") {
-    match cache_type.as_str() {
-      "all" => flags.type_check_mode = TypeCheckMode::All,
-      _ => debug!(
-        "invalid value for 'check' of '{}' using default",
-        cache_type
-      ),
-    }
-  } else if matches.contains_id("check") {
-    flags.type_check_mode = TypeCheckMode::Local;
-  }
-}
+  let mut script_arg =
+    matches.remove_many::<String>("script_arg").ok_or_else(|| {
+      let mut app = app;
+      let subcommand = &mut app.find_subcommand_mut("serve").unwrap();
+      subcommand.error(
+        clap::error::ErrorKind::MissingRequiredArgument,
+        "[SCRIPT_ARG] may only be omitted with --v8-flags=--help",
+      )
+    })?;
 
-fn lock_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  lock_arg_parse(flags, matches);
-  no_lock_arg_parse(flags, matches);
-  // TODO(bartlomieju): deprecated, to be removed in Deno 2.
-  if matches.get_flag("lock-write") {
-    flags.lock_write = true;
-  }
-}
+  let script = script_arg.next().unwrap();
+  flags.argv.extend(script_arg);
 
-fn lock_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if matches.contains_id("lock") {
-    let lockfile = matches.remove_one::<String>("lock").unwrap();
-    flags.lock = Some(lockfile);
-  }
-}
+  ext_arg_parse(flags, matches);
 
-fn no_lock_arg_parse(flags: &mut Flags, matches: &ArgMatches) {
-  if matches.get_flag("no-lock") {
-    flags.no_lock = true;
-  }
+  flags.subcommand = DenoSubcommand::Serve(ServeFlags {
+    script,
+    watch: watch_arg_parse_with_paths(matches),
+    port,
+    host,
+    worker_count,
+  });
+
+  Ok(())
 }
 
-fn config_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.config_flag = if matches.get_flag("no-config") {
-    ConfigFlag::Disabled
-  } else if let Some(config) = matches.remove_one::<String>("config") {
-    ConfigFlag::Path(config)
-  } else {
-    ConfigFlag::Discover
+fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.config_flag = matches
+    .remove_one::<String>("config")
+    .map(ConfigFlag::Path)
+    .unwrap_or(ConfigFlag::Discover);
+
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionAndRuntime);
+
+  let mut task_flags = TaskFlags {
+    cwd: matches.remove_one::<String>("cwd"),
+    task: None,
+    is_run: false,
   };
-}
 
-fn no_remote_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if matches.get_flag("no-remote") {
-    flags.no_remote = true;
-  }
-}
+  if let Some((task, mut matches)) = matches.remove_subcommand() {
+    task_flags.task = Some(task);
 
-fn no_npm_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  if matches.get_flag("no-npm") {
-    flags.no_npm = true;
+    flags.argv.extend(
+      matches
+        .remove_many::<std::ffi::OsString>("")
+        .into_iter()
+        .flatten()
+        .filter_map(|arg| arg.into_string().ok()),
+    );
   }
-}
 
-fn node_modules_and_vendor_dir_arg_parse(
-  flags: &mut Flags,
-  matches: &mut ArgMatches,
-) {
-  flags.node_modules_dir = matches.remove_one::<bool>("node-modules-dir");
-  flags.vendor = matches.remove_one::<bool>("vendor");
+  flags.subcommand = DenoSubcommand::Task(task_flags);
 }
 
-fn reload_arg_validate(urlstr: &str) -> Result<String, String> {
-  if urlstr.is_empty() {
-    return Err(String::from("Missing url. Check for extra commas."));
-  }
-  match Url::from_str(urlstr) {
-    Ok(_) => Ok(urlstr.to_string()),
-    Err(e) => Err(e.to_string()),
-  }
-}
-
-fn watch_arg_parse(matches: &mut ArgMatches) -> Option<WatchFlags> {
-  if matches.get_flag("watch") {
-    Some(WatchFlags {
-      hmr: false,
-      no_clear_screen: matches.get_flag("no-clear-screen"),
-      exclude: matches
-        .remove_many::<String>("watch-exclude")
-        .map(|f| f.collect::<Vec<String>>())
-        .unwrap_or_default(),
-    })
+fn parallel_arg_parse(
+  matches: &mut ArgMatches,
+  fallback_to_jobs: bool,
+) -> Option<NonZeroUsize> {
+  if matches.get_flag("parallel") {
+    if let Ok(value) = env::var("DENO_JOBS") {
+      value.parse::<NonZeroUsize>().ok()
+    } else {
+      std::thread::available_parallelism().ok()
+    }
+  } else if fallback_to_jobs && matches.contains_id("jobs") {
+    // We can't change this to use the log crate because its not configured
+    // yet at this point since the flags haven't been parsed. This flag is
+    // deprecated though so it's not worth changing the code to use the log
+    // crate here and this is only done for testing anyway.
+    #[allow(clippy::print_stderr)]
+    {
+      eprintln!(
+        "⚠️ {}",
+        crate::colors::yellow(concat!(
+          "The `--jobs` flag is deprecated and will be removed in Deno 2.0.\n",
+          "Use the `--parallel` flag with possibly the `DENO_JOBS` environment variable instead.\n",
+          "Learn more at: https://docs.deno.com/runtime/manual/basics/env_variables"
+        )),
+      );
+    }
+    if let Some(value) = matches.remove_one::<NonZeroUsize>("jobs") {
+      Some(value)
+    } else {
+      std::thread::available_parallelism().ok()
+    }
   } else {
     None
   }
 }
 
-fn watch_arg_parse_with_paths(
-  matches: &mut ArgMatches,
-) -> Option<WatchFlagsWithPaths> {
-  if let Some(paths) = matches.remove_many::<String>("watch") {
-    return Some(WatchFlagsWithPaths {
-      paths: paths.collect(),
-      hmr: false,
-      no_clear_screen: matches.get_flag("no-clear-screen"),
-      exclude: matches
-        .remove_many::<String>("watch-exclude")
-        .map(|f| f.collect::<Vec<String>>())
-        .unwrap_or_default(),
-    });
-  }
+fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.type_check_mode = TypeCheckMode::Local;
+  runtime_args_parse(flags, matches, true, true);
+  // NOTE: `deno test` always uses `--no-prompt`, tests shouldn't ever do
+  // interactive prompts, unless done by user code
+  flags.permissions.no_prompt = true;
 
-  if matches.try_contains_id("hmr").is_ok() {
-    return matches.remove_many::<String>("hmr").map(|paths| {
-      WatchFlagsWithPaths {
-        paths: paths.collect(),
-        hmr: true,
-        no_clear_screen: matches.get_flag("no-clear-screen"),
-        exclude: matches
-          .remove_many::<String>("watch-exclude")
-          .map(|f| f.collect::<Vec<String>>())
-          .unwrap_or_default(),
-      }
-    });
-  }
+  let ignore = match matches.remove_many::<String>("ignore") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
 
-  None
-}
+  let no_run = matches.get_flag("no-run");
+  let trace_leaks =
+    matches.get_flag("trace-ops") || matches.get_flag("trace-leaks");
 
-fn unstable_args_parse(
-  flags: &mut Flags,
-  matches: &mut ArgMatches,
-  cfg: UnstableArgsConfig,
-) {
-  if matches.get_flag("unstable") {
-    flags.unstable_config.legacy_flag_enabled = true;
+  #[allow(clippy::print_stderr)]
+  if trace_leaks && matches.get_flag("trace-ops") {
+    // We can't change this to use the log crate because its not configured
+    // yet at this point since the flags haven't been parsed. This flag is
+    // deprecated though so it's not worth changing the code to use the log
+    // crate here and this is only done for testing anyway.
+    eprintln!(
+      "⚠️ {}",
+      crate::colors::yellow("The `--trace-ops` flag is deprecated and will be removed in Deno 2.0.\nUse the `--trace-leaks` flag instead."),
+    );
   }
+  let doc = matches.get_flag("doc");
+  #[allow(clippy::print_stderr)]
+  let allow_none = matches.get_flag("permit-no-files")
+    || if matches.get_flag("allow-none") {
+      eprintln!(
+      "⚠️ {}",
+      crate::colors::yellow("The `--allow-none` flag is deprecated and will be removed in Deno 2.0.\nUse the `--permit-no-files` flag instead."),
+    );
+      true
+    } else {
+      false
+    };
+  let filter = matches.remove_one::<String>("filter");
+  let clean = matches.get_flag("clean");
 
-  flags.unstable_config.bare_node_builtins =
-    matches.get_flag("unstable-bare-node-builtins");
-  flags.unstable_config.byonm = matches.get_flag("unstable-byonm");
-  flags.unstable_config.sloppy_imports =
-    matches.get_flag("unstable-sloppy-imports");
+  let fail_fast = if matches.contains_id("fail-fast") {
+    Some(
+      matches
+        .remove_one::<NonZeroUsize>("fail-fast")
+        .unwrap_or_else(|| NonZeroUsize::new(1).unwrap()),
+    )
+  } else {
+    None
+  };
 
-  if matches!(cfg, UnstableArgsConfig::ResolutionAndRuntime) {
-    for (name, _, _) in crate::UNSTABLE_GRANULAR_FLAGS {
-      if matches.get_flag(&format!("unstable-{}", name)) {
-        flags.unstable_config.features.push(name.to_string());
-      }
-    }
+  let shuffle = if matches.contains_id("shuffle") {
+    Some(
+      matches
+        .remove_one::<u64>("shuffle")
+        .unwrap_or_else(rand::random),
+    )
+  } else {
+    None
+  };
+
+  if let Some(script_arg) = matches.remove_many::<String>("script_arg") {
+    flags.argv.extend(script_arg);
   }
-}
 
-// TODO(ry) move this to utility module and add test.
-/// Strips fragment part of URL. Panics on bad URL.
-pub fn resolve_urls(urls: Vec<String>) -> Vec<String> {
-  let mut out: Vec<String> = vec![];
-  for urlstr in urls.iter() {
-    if let Ok(mut url) = Url::from_str(urlstr) {
-      url.set_fragment(None);
-      let mut full_url = String::from(url.as_str());
-      if full_url.len() > 1 && full_url.ends_with('/') {
-        full_url.pop();
+  let concurrent_jobs = parallel_arg_parse(matches, true);
+
+  let include = if let Some(files) = matches.remove_many::<String>("files") {
+    files.collect()
+  } else {
+    Vec::new()
+  };
+
+  let junit_path = matches.remove_one::<String>("junit-path");
+
+  let reporter =
+    if let Some(reporter) = matches.remove_one::<String>("reporter") {
+      match reporter.as_str() {
+        "pretty" => TestReporterConfig::Pretty,
+        "junit" => TestReporterConfig::Junit,
+        "dot" => TestReporterConfig::Dot,
+        "tap" => TestReporterConfig::Tap,
+        _ => unreachable!(),
       }
-      out.push(full_url);
     } else {
-      panic!("Bad Url: {urlstr}");
-    }
+      TestReporterConfig::Pretty
+    };
+
+  if matches!(reporter, TestReporterConfig::Dot | TestReporterConfig::Tap) {
+    flags.log_level = Some(Level::Error);
   }
-  out
+
+  let hide_stacktraces = matches.get_flag("hide-stacktraces");
+
+  flags.subcommand = DenoSubcommand::Test(TestFlags {
+    no_run,
+    doc,
+    coverage_dir: matches.remove_one::<String>("coverage"),
+    clean,
+    fail_fast,
+    files: FileFlags { include, ignore },
+    filter,
+    shuffle,
+    allow_none,
+    concurrent_jobs,
+    trace_leaks,
+    watch: watch_arg_parse_with_paths(matches),
+    reporter,
+    junit_path,
+    hide_stacktraces,
+  });
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use pretty_assertions::assert_eq;
+fn types_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
+  flags.subcommand = DenoSubcommand::Types;
+}
 
-  /// Creates vector of strings, Vec<String>
-  macro_rules! svec {
-    ($($x:expr),* $(,)?) => (vec![$($x.to_string().into()),*]);
+fn upgrade_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  ca_file_arg_parse(flags, matches);
+  unsafely_ignore_certificate_errors_parse(flags, matches);
+
+  let dry_run = matches.get_flag("dry-run");
+  let force = matches.get_flag("force");
+  let canary = matches.get_flag("canary");
+  let release_candidate = matches.get_flag("release-candidate");
+  let version = matches.remove_one::<String>("version");
+  let output = matches.remove_one::<String>("output");
+  let version_or_hash_or_channel =
+    matches.remove_one::<String>("version-or-hash-or-channel");
+  flags.subcommand = DenoSubcommand::Upgrade(UpgradeFlags {
+    dry_run,
+    force,
+    release_candidate,
+    canary,
+    version,
+    output,
+    version_or_hash_or_channel,
+  });
+}
+
+fn vendor_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
+  ca_file_arg_parse(flags, matches);
+  unsafely_ignore_certificate_errors_parse(flags, matches);
+  config_args_parse(flags, matches);
+  import_map_arg_parse(flags, matches);
+  lock_arg_parse(flags, matches);
+  node_modules_and_vendor_dir_arg_parse(flags, matches);
+  reload_arg_parse(flags, matches);
+
+  flags.subcommand = DenoSubcommand::Vendor(VendorFlags {
+    specifiers: matches
+      .remove_many::<String>("specifiers")
+      .map(|p| p.collect())
+      .unwrap_or_default(),
+    output_path: matches.remove_one::<String>("output"),
+    force: matches.get_flag("force"),
+  });
+}
+
+fn publish_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.type_check_mode = TypeCheckMode::Local; // local by default
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionOnly);
+  no_check_arg_parse(flags, matches);
+  check_arg_parse(flags, matches);
+  config_args_parse(flags, matches);
+
+  flags.subcommand = DenoSubcommand::Publish(PublishFlags {
+    token: matches.remove_one("token"),
+    dry_run: matches.get_flag("dry-run"),
+    allow_slow_types: matches.get_flag("allow-slow-types"),
+    allow_dirty: matches.get_flag("allow-dirty"),
+    no_provenance: matches.get_flag("no-provenance"),
+  });
+}
+
+fn compile_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  compile_args_without_check_parse(flags, matches);
+  no_check_arg_parse(flags, matches);
+  check_arg_parse(flags, matches);
+}
+
+fn compile_args_without_check_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+) {
+  import_map_arg_parse(flags, matches);
+  no_remote_arg_parse(flags, matches);
+  no_npm_arg_parse(flags, matches);
+  node_modules_and_vendor_dir_arg_parse(flags, matches);
+  nm_layout_arg_parse(flags, matches);
+  flags.npm_target_os = matches.remove_one::<String>("npm-target-os");
+  flags.npm_target_cpu = matches.remove_one::<String>("npm-target-cpu");
+  config_args_parse(flags, matches);
+  reload_arg_parse(flags, matches);
+  lock_args_parse(flags, matches);
+  ca_file_arg_parse(flags, matches);
+  unsafely_ignore_certificate_errors_parse(flags, matches);
+}
+
+fn permission_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(read_wl) = matches.remove_many::<String>("allow-read") {
+    flags.permissions.allow_read = Some(read_wl.collect());
+  }
+
+  if let Some(read_wl) = matches.remove_many::<String>("deny-read") {
+    flags.permissions.deny_read = Some(read_wl.collect());
+  }
+
+  if let Some(write_wl) = matches.remove_many::<String>("allow-write") {
+    flags.permissions.allow_write = Some(write_wl.collect());
+  }
+
+  if let Some(write_wl) = matches.remove_many::<String>("deny-write") {
+    flags.permissions.deny_write = Some(write_wl.collect());
+  }
+
+  if let Some(net_wl) = matches.remove_many::<String>("allow-net") {
+    let net_allowlist = flags_net::parse(net_wl.collect()).unwrap();
+    flags.permissions.allow_net = Some(net_allowlist);
+  }
+
+  if let Some(net_wl) = matches.remove_many::<String>("deny-net") {
+    let net_denylist = flags_net::parse(net_wl.collect()).unwrap();
+    flags.permissions.deny_net = Some(net_denylist);
+  }
+
+  if let Some(env_wl) = matches.remove_many::<String>("allow-env") {
+    flags.permissions.allow_env = Some(env_wl.collect());
+    debug!("env allowlist: {:#?}", &flags.permissions.allow_env);
+  }
+
+  if let Some(env_wl) = matches.remove_many::<String>("deny-env") {
+    flags.permissions.deny_env = Some(env_wl.collect());
+    debug!("env denylist: {:#?}", &flags.permissions.deny_env);
+  }
+
+  if let Some(run_wl) = matches.remove_many::<String>("allow-run") {
+    flags.permissions.allow_run = Some(run_wl.collect());
+    debug!("run allowlist: {:#?}", &flags.permissions.allow_run);
+  }
+
+  if let Some(run_wl) = matches.remove_many::<String>("deny-run") {
+    flags.permissions.deny_run = Some(run_wl.collect());
+    debug!("run denylist: {:#?}", &flags.permissions.deny_run);
+  }
+
+  if let Some(sys_wl) = matches.remove_many::<String>("allow-sys") {
+    flags.permissions.allow_sys = Some(sys_wl.collect());
+    debug!("sys info allowlist: {:#?}", &flags.permissions.allow_sys);
+  }
+
+  if let Some(sys_wl) = matches.remove_many::<String>("deny-sys") {
+    flags.permissions.deny_sys = Some(sys_wl.collect());
+    debug!("sys info denylist: {:#?}", &flags.permissions.deny_sys);
+  }
+
+  if let Some(ffi_wl) = matches.remove_many::<String>("allow-ffi") {
+    flags.permissions.allow_ffi = Some(ffi_wl.collect());
+    debug!("ffi allowlist: {:#?}", &flags.permissions.allow_ffi);
+  }
+
+  if let Some(ffi_wl) = matches.remove_many::<String>("deny-ffi") {
+    flags.permissions.deny_ffi = Some(ffi_wl.collect());
+    debug!("ffi denylist: {:#?}", &flags.permissions.deny_ffi);
+  }
+
+  if matches.get_flag("allow-hrtime") {
+    flags.permissions.allow_hrtime = true;
+  }
+
+  if matches.get_flag("deny-hrtime") {
+    flags.permissions.deny_hrtime = true;
+  }
+
+  if matches.get_flag("allow-all") {
+    flags.allow_all();
+  }
+
+  if matches.get_flag("no-prompt") {
+    flags.permissions.no_prompt = true;
+  }
+}
+
+fn unsafely_ignore_certificate_errors_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+) {
+  if let Some(ic_wl) =
+    matches.remove_many::<String>("unsafely-ignore-certificate-errors")
+  {
+    let ic_allowlist = flags_net::parse(ic_wl.collect()).unwrap();
+    flags.unsafely_ignore_certificate_errors = Some(ic_allowlist);
+  }
+}
+
+fn runtime_args_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+  include_perms: bool,
+  include_inspector: bool,
+) {
+  unstable_args_parse(flags, matches, UnstableArgsConfig::ResolutionAndRuntime);
+  compile_args_parse(flags, matches);
+  cached_only_arg_parse(flags, matches);
+  remote_auth_arg_parse(flags, matches);
+  flags.dynamic_import_timeout =
+    matches.remove_one::<u64>("dynamic-import-timeout");
+  frozen_lockfile_arg_parse(flags, matches);
+  if include_perms {
+    permission_args_parse(flags, matches);
+  }
+  if include_inspector {
+    inspect_arg_parse(flags, matches);
+  }
+  location_arg_parse(flags, matches);
+  v8_flags_arg_parse(flags, matches);
+  seed_arg_parse(flags, matches);
+  enable_testing_features_arg_parse(flags, matches);
+  env_file_arg_parse(flags, matches);
+  strace_ops_parse(flags, matches);
+}
+
+fn check_parallel_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if matches.contains_id("check-parallel") {
+    flags.check_parallel = Some(
+      matches
+        .remove_one::<NonZeroUsize>("check-parallel")
+        .unwrap_or_else(|| {
+          std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)
+        }),
+    );
+  }
+}
+
+fn inspect_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.inspect = matches.remove_one::<SocketAddr>("inspect");
+  flags.inspect_brk = matches.remove_one::<SocketAddr>("inspect-brk");
+  flags.inspect_wait = matches.remove_one::<SocketAddr>("inspect-wait");
+}
+
+fn import_map_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.import_map_path = matches.remove_one::<String>("import-map");
+}
+
+fn env_file_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.env_file = matches
+    .remove_many::<String>("env-file")
+    .map(|files| files.collect());
+  flags.env_file_override = matches.get_flag("env-file-override");
+}
+
+fn reload_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(cache_bl) = matches.remove_many::<String>("reload") {
+    let raw_cache_blocklist: Vec<String> = cache_bl.collect();
+    if raw_cache_blocklist.is_empty() {
+      flags.reload = true;
+    } else {
+      flags.cache_blocklist = resolve_urls(raw_cache_blocklist);
+      debug!("cache blocklist: {:#?}", &flags.cache_blocklist);
+      flags.reload = false;
+    }
+  }
+}
+
+fn ca_file_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.ca_data = matches.remove_one::<String>("cert").map(CaData::File);
+}
+
+fn enable_testing_features_arg_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+) {
+  if matches.get_flag("enable-testing-features-do-not-use") {
+    flags.enable_testing_features = true
+  }
+}
+
+fn strace_ops_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(patterns) = matches.remove_many::<String>("strace-ops") {
+    flags.strace_ops = Some(patterns.collect());
+  }
+}
+
+fn cached_only_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if matches.get_flag("cached-only") {
+    flags.cached_only = true;
+  }
+}
+
+fn remote_auth_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(remote_auth) = matches.remove_many::<String>("remote-auth") {
+    flags.remote_auth = remote_auth.collect();
+  }
+}
+
+fn frozen_lockfile_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(&v) = matches.get_one::<bool>("frozen") {
+    flags.frozen_lockfile = Some(v);
+  }
+}
+
+fn ext_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.ext = matches.remove_one::<String>("ext");
+}
+
+fn location_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.location = matches.remove_one::<Url>("location");
+}
+
+fn v8_flags_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(v8_flags) = matches.remove_many::<String>("v8-flags") {
+    flags.v8_flags = v8_flags.collect();
+  }
+}
+
+fn seed_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(seed) = matches.remove_one::<u64>("seed") {
+    flags.seed = Some(seed);
+
+    flags.v8_flags.push(format!("--random-seed={seed}"));
+  }
+}
+
+fn no_check_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(cache_type) = matches.get_one::<String>("no-check") {
+    match cache_type.as_str() {
+      "remote" => flags.type_check_mode = TypeCheckMode::Local,
+      _ => debug!(
+        "invalid value for 'no-check' of '{}' using default",
+        cache_type
+      ),
+    }
+  } else if matches.contains_id("no-check") {
+    flags.type_check_mode = TypeCheckMode::None;
+  }
+}
+
+fn check_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(cache_type) = matches.get_one::<String>("check") {
+    match cache_type.as_str() {
+      "all" => flags.type_check_mode = TypeCheckMode::All,
+      _ => debug!(
+        "invalid value for 'check' of '{}' using default",
+        cache_type
+      ),
+    }
+  } else if matches.contains_id("check") {
+    flags.type_check_mode = TypeCheckMode::Local;
+  }
+}
+
+fn lock_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  lock_arg_parse(flags, matches);
+  no_lock_arg_parse(flags, matches);
+  if matches.get_flag("lock-normalize") {
+    flags.lock_normalize = true;
+  }
+  // TODO(bartlomieju): deprecated, to be removed in Deno 2.
+  if matches.get_flag("lock-write") {
+    flags.lock_write = true;
+  }
+}
+
+fn lock_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if matches.contains_id("lock") {
+    let lockfile = matches.remove_one::<String>("lock").unwrap();
+    flags.lock = Some(lockfile);
+  }
+}
+
+fn no_lock_arg_parse(flags: &mut Flags, matches: &ArgMatches) {
+  if matches.get_flag("no-lock") {
+    flags.no_lock = true;
+  }
+}
+
+fn config_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.config_flag = if matches.get_flag("no-config") {
+    ConfigFlag::Disabled
+  } else if let Some(config) = matches.remove_one::<String>("config") {
+    ConfigFlag::Path(config)
+  } else {
+    ConfigFlag::Discover
+  };
+}
+
+fn no_remote_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if matches.get_flag("no-remote") {
+    flags.no_remote = true;
+  }
+}
+
+fn no_npm_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if matches.get_flag("no-npm") {
+    flags.no_npm = true;
+  }
+}
+
+fn node_modules_and_vendor_dir_arg_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+) {
+  flags.node_modules_dir = matches.remove_one::<bool>("node-modules-dir");
+  flags.vendor = matches.remove_one::<bool>("vendor");
+}
+
+fn nm_layout_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(layout) = matches.remove_one::<String>("nm-layout") {
+    flags.nm_layout = Some(match layout.as_str() {
+      "deterministic" => NodeModulesLayout::Deterministic,
+      _ => NodeModulesLayout::Default,
+    });
+  }
+}
+
+fn reload_arg_validate(urlstr: &str) -> Result<String, String> {
+  if urlstr.is_empty() {
+    return Err(String::from("Missing url. Check for extra commas."));
+  }
+  match Url::from_str(urlstr) {
+    Ok(_) => Ok(urlstr.to_string()),
+    Err(e) => Err(e.to_string()),
+  }
+}
+
+fn watch_arg_parse(matches: &mut ArgMatches) -> Option<WatchFlags> {
+  if matches.get_flag("watch") {
+    Some(WatchFlags {
+      hmr: false,
+      no_clear_screen: matches.get_flag("no-clear-screen"),
+      exclude: matches
+        .remove_many::<String>("watch-exclude")
+        .map(|f| f.collect::<Vec<String>>())
+        .unwrap_or_default(),
+    })
+  } else {
+    None
+  }
+}
+
+fn watch_arg_parse_with_paths(
+  matches: &mut ArgMatches,
+) -> Option<WatchFlagsWithPaths> {
+  let exit_on_error = matches.try_contains_id("watch-exit-on-error").is_ok()
+    && matches.get_flag("watch-exit-on-error");
+  let poll_interval = if matches.try_contains_id("watch-poll").is_ok() {
+    matches.remove_one::<u64>("watch-poll")
+  } else {
+    None
+  };
+  if let Some(paths) = matches.remove_many::<String>("watch") {
+    return Some(WatchFlagsWithPaths {
+      paths: paths.collect(),
+      hmr: false,
+      reevaluate: false,
+      no_clear_screen: matches.get_flag("no-clear-screen"),
+      exclude: matches
+        .remove_many::<String>("watch-exclude")
+        .map(|f| f.collect::<Vec<String>>())
+        .unwrap_or_default(),
+      exit_on_error,
+      poll_interval,
+    });
+  }
+
+  if matches.try_contains_id("hmr").is_ok() {
+    return matches.remove_many::<String>("hmr").map(|paths| {
+      WatchFlagsWithPaths {
+        paths: paths.collect(),
+        hmr: true,
+        reevaluate: false,
+        no_clear_screen: matches.get_flag("no-clear-screen"),
+        exclude: matches
+          .remove_many::<String>("watch-exclude")
+          .map(|f| f.collect::<Vec<String>>())
+          .unwrap_or_default(),
+        exit_on_error,
+        poll_interval,
+      }
+    });
+  }
+
+  if matches.try_contains_id("watch-reevaluate").is_ok() {
+    return matches
+      .remove_many::<String>("watch-reevaluate")
+      .map(|paths| WatchFlagsWithPaths {
+        paths: paths.collect(),
+        hmr: true,
+        reevaluate: true,
+        no_clear_screen: matches.get_flag("no-clear-screen"),
+        exclude: matches
+          .remove_many::<String>("watch-exclude")
+          .map(|f| f.collect::<Vec<String>>())
+          .unwrap_or_default(),
+        exit_on_error,
+        poll_interval,
+      });
+  }
+
+  None
+}
+
+fn unstable_args_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+  cfg: UnstableArgsConfig,
+) {
+  if matches.get_flag("unstable") {
+    flags.unstable_config.legacy_flag_enabled = true;
+  }
+
+  flags.unstable_config.bare_node_builtins =
+    matches.get_flag("unstable-bare-node-builtins");
+  flags.unstable_config.byonm = matches.get_flag("unstable-byonm");
+  flags.unstable_config.sloppy_imports =
+    matches.get_flag("unstable-sloppy-imports");
+
+  if matches!(cfg, UnstableArgsConfig::ResolutionAndRuntime) {
+    for (name, _, _) in crate::UNSTABLE_GRANULAR_FLAGS {
+      if matches.get_flag(&format!("unstable-{}", name)) {
+        flags.unstable_config.features.push(name.to_string());
+      }
+    }
+  }
+}
+
+// TODO(ry) move this to utility module and add test.
+/// Strips fragment part of URL. Panics on bad URL.
+pub fn resolve_urls(urls: Vec<String>) -> Vec<String> {
+  let mut out: Vec<String> = vec![];
+  for urlstr in urls.iter() {
+    if let Ok(mut url) = Url::from_str(urlstr) {
+      url.set_fragment(None);
+      let mut full_url = String::from(url.as_str());
+      if full_url.len() > 1 && full_url.ends_with('/') {
+        full_url.pop();
+      }
+      out.push(full_url);
+    } else {
+      panic!("Bad Url: {urlstr}");
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  /// Creates vector of strings, Vec<String>
+  macro_rules! svec {
+    ($($x:expr),* $(,)?) => (vec![$($x.to_string().into()),*]);
+  }
+
+  #[test]
+  fn global_flags() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "--log-level", "debug", "--quiet", "run", "script.ts"]);
+
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string()
+        )),
+        log_level: Some(Level::Error),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+    #[rustfmt::skip]
+    let r2 = flags_from_vec(svec!["deno", "run", "--log-level", "debug", "--quiet", "script.ts"]);
+    let flags2 = r2.unwrap();
+    assert_eq!(flags2, flags);
+  }
+
+  #[test]
+  fn upgrade() {
+    let r = flags_from_vec(svec!["deno", "upgrade", "--dry-run", "--force"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: true,
+          dry_run: true,
+          canary: false,
+          release_candidate: false,
+          version: None,
+          output: None,
+          version_or_hash_or_channel: None,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn upgrade_with_output_flag() {
+    let r = flags_from_vec(svec!["deno", "upgrade", "--output", "example.txt"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: false,
+          dry_run: false,
+          canary: false,
+          release_candidate: false,
+          version: None,
+          output: Some(String::from("example.txt")),
+          version_or_hash_or_channel: None,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn version() {
+    let r = flags_from_vec(svec!["deno", "--version"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::DisplayVersion
+    );
+    let r = flags_from_vec(svec!["deno", "-V"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::DisplayVersion
+    );
+  }
+
+  #[test]
+  fn run_reload() {
+    let r = flags_from_vec(svec!["deno", "run", "-r", "script.ts"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string()
+        )),
+        reload: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_watch() {
+    let r = flags_from_vec(svec!["deno", "run", "--watch", "script.ts"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "--watch",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: true,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-hmr",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: true,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: true,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--unstable-hmr",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: true,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: true,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-hmr=foo.txt",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: true,
+            reevaluate: false,
+            paths: vec![String::from("foo.txt")],
+            no_clear_screen: true,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--hmr", "--watch", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn run_watch_reevaluate() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-reevaluate=foo.txt",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: true,
+            reevaluate: true,
+            paths: vec![String::from("foo.txt")],
+            no_clear_screen: false,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-reevaluate",
+      "--watch-hmr",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn run_watch_exit_on_error() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-hmr",
+      "--watch-exit-on-error",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: true,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![],
+            exit_on_error: true,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_watch_poll() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-hmr",
+      "--watch-poll=250",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: true,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: Some(250),
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_watch_with_external() {
+    let r = flags_from_vec(svec!["deno", "--watch=file1,file2", "script.ts"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![String::from("file1"), String::from("file2")],
+            no_clear_screen: false,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_watch_with_no_clear_screen() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: true,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_watch_with_excluded_paths() {
+    let r = flags_from_vec(svec!(
+      "deno",
+      "--watch",
+      "--watch-exclude=foo",
+      "script.ts"
+    ));
+
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![String::from("foo")],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!(
+      "deno",
+      "run",
+      "--watch=foo",
+      "--watch-exclude=bar",
+      "script.ts"
+    ));
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![String::from("foo")],
+            no_clear_screen: false,
+            exclude: vec![String::from("bar")],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--watch-exclude=foo,bar",
+      "script.ts"
+    ]);
+
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![String::from("foo"), String::from("bar")],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: false,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "--watch=foo,bar",
+      "--watch-exclude=baz,qux",
+      "script.ts"
+    ]);
+
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![String::from("foo"), String::from("bar")],
+            no_clear_screen: false,
+            exclude: vec![String::from("baz"), String::from("qux"),],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_reload_allow_write() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "-r", "--allow-write", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        reload: true,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string()
+        )),
+        permissions: PermissionFlags {
+          allow_write: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_v8_flags() {
+    let r = flags_from_vec(svec!["deno", "run", "--v8-flags=--help"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default("_".to_string())),
+        v8_flags: svec!["--help"],
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--v8-flags=--expose-gc,--gc-stats=1",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        v8_flags: svec!["--expose-gc", "--gc-stats=1"],
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "run", "--v8-flags=--expose-gc"]);
+    assert!(r.is_ok());
+  }
+
+  #[test]
+  fn serve_flags() {
+    let r = flags_from_vec(svec!["deno", "serve", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          8000,
+          "0.0.0.0"
+        )),
+        permissions: PermissionFlags {
+          allow_net: Some(vec![
+            "0.0.0.0:8000".to_string(),
+            "127.0.0.1:8000".to_string(),
+            "localhost:8000".to_string()
+          ]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+    let r = flags_from_vec(svec!["deno", "serve", "--port", "5000", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          5000,
+          "0.0.0.0"
+        )),
+        permissions: PermissionFlags {
+          allow_net: Some(vec![
+            "0.0.0.0:5000".to_string(),
+            "127.0.0.1:5000".to_string(),
+            "localhost:5000".to_string()
+          ]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--port",
+      "5000",
+      "--allow-net=example.com",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          5000,
+          "0.0.0.0"
+        )),
+        permissions: PermissionFlags {
+          allow_net: Some(vec![
+            "example.com".to_string(),
+            "0.0.0.0:5000".to_string(),
+            "127.0.0.1:5000".to_string(),
+            "localhost:5000".to_string()
+          ]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--port",
+      "5000",
+      "--allow-net",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          5000,
+          "0.0.0.0"
+        )),
+        permissions: PermissionFlags {
+          allow_net: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--port",
+      "5000",
+      "--host",
+      "example.com",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          5000,
+          "example.com"
+        )),
+        permissions: PermissionFlags {
+          allow_net: Some(vec!["example.com:5000".to_owned()]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--port",
+      "0",
+      "--host",
+      "example.com",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          0,
+          "example.com"
+        )),
+        permissions: PermissionFlags {
+          allow_net: Some(vec!["example.com:0".to_owned()]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn has_permission() {
+    let r = flags_from_vec(svec!["deno", "--allow-read", "x.ts"]);
+    assert_eq!(r.unwrap().has_permission(), true);
+
+    let r = flags_from_vec(svec!["deno", "run", "--deny-read", "x.ts"]);
+    assert_eq!(r.unwrap().has_permission(), true);
+
+    let r = flags_from_vec(svec!["deno", "run", "x.ts"]);
+    assert_eq!(r.unwrap().has_permission(), false);
+  }
+
+  #[test]
+  fn has_permission_in_argv() {
+    let r = flags_from_vec(svec!["deno", "run", "x.ts", "--allow-read"]);
+    assert_eq!(r.unwrap().has_permission_in_argv(), true);
+
+    let r = flags_from_vec(svec!["deno", "x.ts", "--deny-read"]);
+    assert_eq!(r.unwrap().has_permission_in_argv(), true);
+
+    let r = flags_from_vec(svec!["deno", "run", "x.ts"]);
+    assert_eq!(r.unwrap().has_permission_in_argv(), false);
+  }
+
+  #[test]
+  fn script_args() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-net",
+      "gist.ts",
+      "--title",
+      "X"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string()
+        )),
+        argv: svec!["--title", "X"],
+        permissions: PermissionFlags {
+          allow_net: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn allow_all() {
+    let r = flags_from_vec(svec!["deno", "run", "--allow-all", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string()
+        )),
+        permissions: PermissionFlags {
+          allow_all: true,
+          allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn allow_read() {
+    let r = flags_from_vec(svec!["deno", "run", "--allow-read", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string()
+        )),
+        permissions: PermissionFlags {
+          allow_read: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn short_permission_flags() {
+    let r = flags_from_vec(svec!["deno", "run", "-RNESW", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string()
+        )),
+        permissions: PermissionFlags {
+          allow_read: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_net: Some(vec![]),
+          allow_sys: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn deny_read() {
+    let r = flags_from_vec(svec!["deno", "--deny-read", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "gist.ts".to_string(),
+          watch: None,
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        permissions: PermissionFlags {
+          deny_read: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn allow_hrtime() {
+    let r = flags_from_vec(svec!["deno", "run", "--allow-hrtime", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_hrtime: true,
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn deny_hrtime() {
+    let r = flags_from_vec(svec!["deno", "run", "--deny-hrtime", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          deny_hrtime: true,
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn double_hyphen() {
+    // notice that flags passed after double dash will not
+    // be parsed to Flags but instead forwarded to
+    // script args as Deno.args
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-write",
+      "script.ts",
+      "--",
+      "-D",
+      "--allow-net"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        argv: svec!["--", "-D", "--allow-net"],
+        permissions: PermissionFlags {
+          allow_write: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn fmt() {
+    let r = flags_from_vec(svec!["deno", "fmt", "script_1.ts", "script_2.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--check"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--watch"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Some(Default::default()),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--watch",
+      "--no-clear-screen",
+      "--unstable-css",
+      "--unstable-html",
+      "--unstable-component",
+      "--unstable-yaml"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: true,
+          unstable_html: true,
+          unstable_component: true,
+          unstable_yaml: true,
+          watch: Some(WatchFlags {
+            hmr: false,
+            no_clear_screen: true,
+            exclude: vec![],
+          })
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--check",
+      "--watch",
+      "foo.ts",
+      "--ignore=bar.js"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          files: FileFlags {
+            include: vec!["foo.ts".to_string()],
+            ignore: vec!["bar.js".to_string()],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Some(Default::default()),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--config", "deno.jsonc"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--config",
+      "deno.jsonc",
+      "--watch",
+      "foo.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec!["foo.ts".to_string()],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Some(Default::default()),
+        }),
+        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--use-tabs",
+      "--line-width",
+      "60",
+      "--indent-width",
+      "4",
+      "--single-quote",
+      "--prose-wrap",
+      "never",
+      "--no-semicolons",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: Some(true),
+          line_width: Some(NonZeroU32::new(60).unwrap()),
+          indent_width: Some(NonZeroU8::new(4).unwrap()),
+          single_quote: Some(true),
+          prose_wrap: Some("never".to_string()),
+          no_semicolons: Some(true),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    // try providing =false to the booleans
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--use-tabs=false",
+      "--single-quote=false",
+      "--no-semicolons=false",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: Some(false),
+          line_width: None,
+          indent_width: None,
+          single_quote: Some(false),
+          prose_wrap: None,
+          no_semicolons: Some(false),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
+          unstable_yaml: false,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
-  fn global_flags() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "--log-level", "debug", "--quiet", "run", "script.ts"]);
-
-    let flags = r.unwrap();
+  fn lint() {
+    let r = flags_from_vec(svec!["deno", "lint", "script_1.ts", "script_2.ts"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string()
-        )),
-        log_level: Some(Level::Error),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string(),],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          watch: Default::default(),
+          ext: None,
+        }),
         ..Flags::default()
       }
     );
-    #[rustfmt::skip]
-    let r2 = flags_from_vec(svec!["deno", "run", "--log-level", "debug", "--quiet", "script.ts"]);
-    let flags2 = r2.unwrap();
-    assert_eq!(flags2, flags);
-  }
 
-  #[test]
-  fn upgrade() {
-    let r = flags_from_vec(svec!["deno", "upgrade", "--dry-run", "--force"]);
-    let flags = r.unwrap();
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--watch",
+      "script_1.ts",
+      "script_2.ts"
+    ]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
-          force: true,
-          dry_run: true,
-          canary: false,
-          release_candidate: false,
-          version: None,
-          output: None,
-          version_or_hash_or_channel: None,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          watch: Some(Default::default()),
+          ext: None,
         }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn upgrade_with_output_flag() {
-    let r = flags_from_vec(svec!["deno", "upgrade", "--output", "example.txt"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--watch",
+      "--no-clear-screen",
+      "script_1.ts",
+      "script_2.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
-          force: false,
-          dry_run: false,
-          canary: false,
-          release_candidate: false,
-          version: None,
-          output: Some(String::from("example.txt")),
-          version_or_hash_or_channel: None,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          watch: Some(WatchFlags {
+            hmr: false,
+            no_clear_screen: true,
+            exclude: vec![],
+          }),
+          ext: None,
         }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn version() {
-    let r = flags_from_vec(svec!["deno", "--version"]);
-    assert_eq!(
-      r.unwrap_err().kind(),
-      clap::error::ErrorKind::DisplayVersion
-    );
-    let r = flags_from_vec(svec!["deno", "-V"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--fix",
+      "--ignore=script_1.ts,script_2.ts"
+    ]);
     assert_eq!(
-      r.unwrap_err().kind(),
-      clap::error::ErrorKind::DisplayVersion
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
+          },
+          fix: true,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          watch: Default::default(),
+          ext: None,
+        }),
+        ..Flags::default()
+      }
     );
-  }
 
-  #[test]
-  fn run_reload() {
-    let r = flags_from_vec(svec!["deno", "run", "-r", "script.ts"]);
-    let flags = r.unwrap();
+    let r = flags_from_vec(svec!["deno", "lint", "--rules"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string()
-        )),
-        reload: true,
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: true,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          watch: Default::default(),
+          ext: None,
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn run_watch() {
-    let r = flags_from_vec(svec!["deno", "run", "--watch", "script.ts"]);
-    let flags = r.unwrap();
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--rules",
+      "--rules-tags=recommended"
+    ]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![],
-            no_clear_screen: false,
-            exclude: vec![],
-          }),
-          bare: false,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: true,
+          maybe_rules_tags: Some(svec!["recommended"]),
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          watch: Default::default(),
+          ext: None,
         }),
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
     let r = flags_from_vec(svec![
       "deno",
-      "--watch",
-      "--no-clear-screen",
-      "script.ts"
+      "lint",
+      "--rules-tags=",
+      "--rules-include=ban-untagged-todo,no-undef",
+      "--rules-exclude=no-const-assign"
     ]);
-    let flags = r.unwrap();
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![],
-            no_clear_screen: true,
-            exclude: vec![],
-          }),
-          bare: true,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: Some(svec![""]),
+          maybe_rules_include: Some(svec!["ban-untagged-todo", "no-undef"]),
+          maybe_rules_exclude: Some(svec!["no-const-assign"]),
+          json: false,
+          compact: false,
+          watch: Default::default(),
+          ext: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--json", "script_1.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string()],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: true,
+          compact: false,
+          watch: Default::default(),
+          ext: None,
         }),
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--watch-hmr",
-      "--no-clear-screen",
-      "script.ts"
+      "lint",
+      "--config",
+      "Deno.jsonc",
+      "--json",
+      "script_1.ts"
     ]);
-    let flags = r.unwrap();
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: true,
-            paths: vec![],
-            no_clear_screen: true,
-            exclude: vec![],
-          }),
-          bare: false,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string()],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: true,
+          compact: false,
+          watch: Default::default(),
+          ext: None,
         }),
-        code_cache_enabled: true,
+        config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
 
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--unstable-hmr",
-      "--no-clear-screen",
-      "script.ts"
+      "lint",
+      "--config",
+      "Deno.jsonc",
+      "--compact",
+      "script_1.ts"
     ]);
-    let flags = r.unwrap();
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: true,
-            paths: vec![],
-            no_clear_screen: true,
-            exclude: vec![],
-          }),
-          bare: false,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string()],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: true,
+          watch: Default::default(),
+          ext: None,
         }),
-        code_cache_enabled: true,
+        config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--watch-hmr=foo.txt",
-      "--no-clear-screen",
-      "script.ts"
-    ]);
-    let flags = r.unwrap();
+  #[test]
+  fn types() {
+    let r = flags_from_vec(svec!["deno", "types"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: true,
-            paths: vec![String::from("foo.txt")],
-            no_clear_screen: true,
-            exclude: vec![],
-          }),
-          bare: false,
-        }),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Types,
         ..Flags::default()
       }
     );
-
-    let r =
-      flags_from_vec(svec!["deno", "run", "--hmr", "--watch", "script.ts"]);
-    assert!(r.is_err());
   }
 
   #[test]
-  fn run_watch_with_external() {
-    let r = flags_from_vec(svec!["deno", "--watch=file1,file2", "script.ts"]);
-    let flags = r.unwrap();
+  fn cache() {
+    let r = flags_from_vec(svec!["deno", "cache", "script.ts"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![String::from("file1"), String::from("file2")],
-            no_clear_screen: false,
-            exclude: vec![],
-          }),
-          bare: true,
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["script.ts"],
         }),
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn run_watch_with_no_clear_screen() {
+  fn clean() {
+    let r = flags_from_vec(svec!["deno", "clean"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Clean(CleanFlags::default()),
+        ..Flags::default()
+      }
+    );
+
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--watch",
-      "--no-clear-screen",
-      "script.ts"
+      "clean",
+      "--lock",
+      "--dry-run",
+      "main.ts",
+      "other.ts"
     ]);
-
-    let flags = r.unwrap();
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![],
-            no_clear_screen: true,
-            exclude: vec![],
-          }),
-          bare: false,
+        subcommand: DenoSubcommand::Clean(CleanFlags {
+          lock: true,
+          dry_run: true,
+          files: svec!["main.ts", "other.ts"],
         }),
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    // the entrypoints decide which entries are used
+    let r = flags_from_vec(svec!["deno", "clean", "--lock"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "clean", "main.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn run_watch_with_excluded_paths() {
-    let r = flags_from_vec(svec!(
-      "deno",
-      "--watch",
-      "--watch-exclude=foo",
-      "script.ts"
-    ));
-
-    let flags = r.unwrap();
+  fn check() {
+    let r = flags_from_vec(svec!["deno", "check", "script.ts"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![],
-            no_clear_screen: false,
-            exclude: vec![String::from("foo")],
-          }),
-          bare: true,
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
         }),
-        code_cache_enabled: true,
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!(
-      "deno",
-      "run",
-      "--watch=foo",
-      "--watch-exclude=bar",
-      "script.ts"
-    ));
-    let flags = r.unwrap();
+    for all_flag in ["--remote", "--all"] {
+      let r = flags_from_vec(svec!["deno", "check", all_flag, "script.ts"]);
+      assert_eq!(
+        r.unwrap(),
+        Flags {
+          subcommand: DenoSubcommand::Check(CheckFlags {
+            files: svec!["script.ts"],
+          }),
+          type_check_mode: TypeCheckMode::All,
+          ..Flags::default()
+        }
+      );
+
+      let r = flags_from_vec(svec![
+        "deno",
+        "check",
+        all_flag,
+        "--no-remote",
+        "script.ts"
+      ]);
+      assert_eq!(
+        r.unwrap_err().kind(),
+        clap::error::ErrorKind::ArgumentConflict
+      );
+    }
+  }
+
+  #[test]
+  fn info() {
+    let r = flags_from_vec(svec!["deno", "info", "script.ts"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![String::from("foo")],
-            no_clear_screen: false,
-            exclude: vec![String::from("bar")],
-          }),
-          bare: false,
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("script.ts".to_string()),
         }),
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--watch",
-      "--watch-exclude=foo,bar",
-      "script.ts"
-    ]);
-
-    let flags = r.unwrap();
+    let r = flags_from_vec(svec!["deno", "info", "--reload", "script.ts"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![],
-            no_clear_screen: false,
-            exclude: vec![String::from("foo"), String::from("bar")],
-          }),
-          bare: false,
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("script.ts".to_string()),
         }),
-        code_cache_enabled: true,
+        reload: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "--watch=foo,bar",
-      "--watch-exclude=baz,qux",
-      "script.ts"
-    ]);
-
-    let flags = r.unwrap();
+    let r = flags_from_vec(svec!["deno", "info", "--json", "script.ts"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![String::from("foo"), String::from("bar")],
-            no_clear_screen: false,
-            exclude: vec![String::from("baz"), String::from("qux"),],
-          }),
-          bare: true,
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          file: Some("script.ts".to_string()),
         }),
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn run_reload_allow_write() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "-r", "--allow-write", "script.ts"]);
+    let r = flags_from_vec(svec!["deno", "info"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        reload: true,
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string()
-        )),
-        permissions: PermissionFlags {
-          allow_write: Some(vec![]),
-          ..Default::default()
-        },
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: None
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn run_v8_flags() {
-    let r = flags_from_vec(svec!["deno", "run", "--v8-flags=--help"]);
+    let r = flags_from_vec(svec!["deno", "info", "--json"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default("_".to_string())),
-        v8_flags: svec!["--help"],
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          file: None
+        }),
         ..Flags::default()
       }
     );
 
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--v8-flags=--expose-gc,--gc-stats=1",
-      "script.ts"
+      "info",
+      "--no-npm",
+      "--no-remote",
+      "--config",
+      "tsconfig.json"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        v8_flags: svec!["--expose-gc", "--gc-stats=1"],
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: None
+        }),
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
+        no_npm: true,
+        no_remote: true,
         ..Flags::default()
       }
     );
-
-    let r = flags_from_vec(svec!["deno", "run", "--v8-flags=--expose-gc"]);
-    assert!(r.is_ok());
   }
 
   #[test]
-  fn serve_flags() {
-    let r = flags_from_vec(svec!["deno", "serve", "main.ts"]);
+  fn tsconfig() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "-c", "tsconfig.json", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
-          "main.ts".to_string(),
-          8000,
-          "0.0.0.0"
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          allow_net: Some(vec![
-            "0.0.0.0:8000".to_string(),
-            "127.0.0.1:8000".to_string(),
-            "localhost:8000".to_string()
-          ]),
-          ..Default::default()
-        },
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
-    let r = flags_from_vec(svec!["deno", "serve", "--port", "5000", "main.ts"]);
+  }
+
+  #[test]
+  fn eval() {
+    let r = flags_from_vec(svec!["deno", "eval", "'console.log(\"hello\")'"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
-          "main.ts".to_string(),
-          5000,
-          "0.0.0.0"
-        )),
+        subcommand: DenoSubcommand::Eval(EvalFlags {
+          print: false,
+          code: "'console.log(\"hello\")'".to_string(),
+          repl: false,
+        }),
         permissions: PermissionFlags {
-          allow_net: Some(vec![
-            "0.0.0.0:5000".to_string(),
-            "127.0.0.1:5000".to_string(),
-            "localhost:5000".to_string()
-          ]),
+          allow_all: true,
+          allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-    let r = flags_from_vec(svec![
-      "deno",
-      "serve",
-      "--port",
-      "5000",
-      "--allow-net=example.com",
-      "main.ts"
-    ]);
+  }
+
+  #[test]
+  fn eval_p() {
+    let r = flags_from_vec(svec!["deno", "eval", "-p", "1+2"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
-          "main.ts".to_string(),
-          5000,
-          "0.0.0.0"
-        )),
+        subcommand: DenoSubcommand::Eval(EvalFlags {
+          print: true,
+          code: "1+2".to_string(),
+          repl: false,
+        }),
         permissions: PermissionFlags {
-          allow_net: Some(vec![
-            "example.com".to_string(),
-            "0.0.0.0:5000".to_string(),
-            "127.0.0.1:5000".to_string(),
-            "localhost:5000".to_string()
-          ]),
+          allow_all: true,
+          allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
+
+  #[test]
+  fn eval_synthetic_scheme() {
+    let flags = flags_from_vec(svec![
+      "deno",
+      "eval",
+      "--synthetic-scheme=eval:",
+      "throw 1"
+    ])
+    .unwrap();
+    assert_eq!(flags.synthetic_scheme, Some("eval".to_string()));
+
+    let flags =
+      flags_from_vec(svec!["deno", "run", "--synthetic-scheme=stdin", "-"])
+        .unwrap();
+    assert_eq!(flags.synthetic_scheme, Some("stdin".to_string()));
+
     let r = flags_from_vec(svec![
       "deno",
-      "serve",
-      "--port",
-      "5000",
-      "--allow-net",
-      "main.ts"
+      "eval",
+      "--synthetic-scheme=1nvalid",
+      "throw 1"
     ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn eval_repl() {
+    let r = flags_from_vec(svec!["deno", "eval", "--repl", "const a = 1"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
-          "main.ts".to_string(),
-          5000,
-          "0.0.0.0"
-        )),
+        subcommand: DenoSubcommand::Eval(EvalFlags {
+          print: false,
+          code: "const a = 1".to_string(),
+          repl: true,
+        }),
         permissions: PermissionFlags {
+          allow_all: true,
           allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-    let r = flags_from_vec(svec![
-      "deno",
-      "serve",
-      "--port",
-      "5000",
-      "--host",
-      "example.com",
-      "main.ts"
-    ]);
+
+    let r = flags_from_vec(svec!["deno", "eval", "--repl", "-p", "1+2"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn eval_typescript() {
+    let r =
+      flags_from_vec(svec!["deno", "eval", "-T", "'console.log(\"hello\")'"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
-          "main.ts".to_string(),
-          5000,
-          "example.com"
-        )),
+        subcommand: DenoSubcommand::Eval(EvalFlags {
+          print: false,
+          code: "'console.log(\"hello\")'".to_string(),
+          repl: false,
+        }),
         permissions: PermissionFlags {
-          allow_net: Some(vec!["example.com:5000".to_owned()]),
+          allow_all: true,
+          allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
+        ext: Some("ts".to_string()),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "serve",
-      "--port",
-      "0",
-      "--host",
-      "example.com",
-      "main.ts"
-    ]);
+  #[test]
+  fn eval_with_flags() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "eval", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--env=.example.env", "42"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
-          "main.ts".to_string(),
-          0,
-          "example.com"
-        )),
+        subcommand: DenoSubcommand::Eval(EvalFlags {
+          print: false,
+          code: "42".to_string(),
+          repl: false,
+        }),
+        import_map_path: Some("import_map.json".to_string()),
+        no_remote: true,
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
+        type_check_mode: TypeCheckMode::None,
+        reload: true,
+        lock: Some(String::from("lock.json")),
+        lock_write: true,
+        ca_data: Some(CaData::File("example.crt".to_string())),
+        cached_only: true,
+        location: Some(Url::parse("https://foo/").unwrap()),
+        v8_flags: svec!["--help", "--random-seed=1"],
+        seed: Some(1),
+        inspect: Some("127.0.0.1:9229".parse().unwrap()),
         permissions: PermissionFlags {
-          allow_net: Some(vec!["example.com:0".to_owned()]),
+          allow_all: true,
+          allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
+        env_file: Some(vec![".example.env".to_owned()]),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn has_permission() {
-    let r = flags_from_vec(svec!["deno", "--allow-read", "x.ts"]);
-    assert_eq!(r.unwrap().has_permission(), true);
-
-    let r = flags_from_vec(svec!["deno", "run", "--deny-read", "x.ts"]);
-    assert_eq!(r.unwrap().has_permission(), true);
-
-    let r = flags_from_vec(svec!["deno", "run", "x.ts"]);
-    assert_eq!(r.unwrap().has_permission(), false);
-  }
-
-  #[test]
-  fn has_permission_in_argv() {
-    let r = flags_from_vec(svec!["deno", "run", "x.ts", "--allow-read"]);
-    assert_eq!(r.unwrap().has_permission_in_argv(), true);
-
-    let r = flags_from_vec(svec!["deno", "x.ts", "--deny-read"]);
-    assert_eq!(r.unwrap().has_permission_in_argv(), true);
-
-    let r = flags_from_vec(svec!["deno", "run", "x.ts"]);
-    assert_eq!(r.unwrap().has_permission_in_argv(), false);
-  }
-
-  #[test]
-  fn script_args() {
+  fn eval_args() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--allow-net",
-      "gist.ts",
-      "--title",
-      "X"
+      "eval",
+      "console.log(Deno.args)",
+      "arg1",
+      "arg2"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string()
-        )),
-        argv: svec!["--title", "X"],
+        subcommand: DenoSubcommand::Eval(EvalFlags {
+          print: false,
+          code: "console.log(Deno.args)".to_string(),
+          repl: false,
+        }),
+        argv: svec!["arg1", "arg2"],
         permissions: PermissionFlags {
+          allow_all: true,
           allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn allow_all() {
-    let r = flags_from_vec(svec!["deno", "run", "--allow-all", "gist.ts"]);
+  fn repl() {
+    let r = flags_from_vec(svec!["deno"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string()
-        )),
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: None,
+          is_default_command: true,
+        }),
+        unsafely_ignore_certificate_errors: None,
         permissions: PermissionFlags {
-          allow_all: true,
           allow_net: Some(vec![]),
           allow_env: Some(vec![]),
+          deny_env: None,
           allow_run: Some(vec![]),
+          deny_run: None,
           allow_read: Some(vec![]),
+          deny_read: None,
           allow_sys: Some(vec![]),
+          deny_sys: None,
           allow_write: Some(vec![]),
+          deny_write: None,
           allow_ffi: Some(vec![]),
+          deny_ffi: None,
           allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn allow_read() {
-    let r = flags_from_vec(svec!["deno", "run", "--allow-read", "gist.ts"]);
+  fn repl_strace_ops() {
+    // Lightly test this undocumented flag
+    let r = flags_from_vec(svec!["deno", "repl", "--strace-ops"]);
+    assert_eq!(r.unwrap().strace_ops, Some(vec![]));
+    let r =
+      flags_from_vec(svec!["deno", "repl", "--strace-ops=http,websocket"]);
+    assert_eq!(
+      r.unwrap().strace_ops,
+      Some(vec!["http".to_string(), "websocket".to_string()])
+    );
+  }
+
+  #[test]
+  fn repl_with_flags() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "repl", "-A", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--unsafely-ignore-certificate-errors", "--env=.example.env"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string()
-        )),
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: None,
+          is_default_command: false,
+        }),
+        import_map_path: Some("import_map.json".to_string()),
+        no_remote: true,
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
+        type_check_mode: TypeCheckMode::None,
+        reload: true,
+        lock: Some(String::from("lock.json")),
+        lock_write: true,
+        ca_data: Some(CaData::File("example.crt".to_string())),
+        cached_only: true,
+        location: Some(Url::parse("https://foo/").unwrap()),
+        v8_flags: svec!["--help", "--random-seed=1"],
+        seed: Some(1),
+        inspect: Some("127.0.0.1:9229".parse().unwrap()),
         permissions: PermissionFlags {
+          allow_all: true,
+          allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
           allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
           ..Default::default()
         },
-        code_cache_enabled: true,
+        env_file: Some(vec![".example.env".to_owned()]),
+        unsafely_ignore_certificate_errors: Some(vec![]),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn short_permission_flags() {
-    let r = flags_from_vec(svec!["deno", "run", "-RNESW", "gist.ts"]);
+  fn repl_with_eval_flag() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "repl", "--allow-write", "--eval", "console.log('hello');"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string()
-        )),
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: Some("console.log('hello');".to_string()),
+          is_default_command: false,
+        }),
         permissions: PermissionFlags {
-          allow_read: Some(vec![]),
           allow_write: Some(vec![]),
-          allow_env: Some(vec![]),
-          allow_net: Some(vec![]),
-          allow_sys: Some(vec![]),
           ..Default::default()
         },
-        code_cache_enabled: true,
+        type_check_mode: TypeCheckMode::None,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn deny_read() {
-    let r = flags_from_vec(svec!["deno", "--deny-read", "gist.ts"]);
+  fn repl_with_eval_file_flag() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "repl", "--eval-file=./a.js,./b.ts,https://examples.deno.land/hello-world.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "gist.ts".to_string(),
-          watch: None,
-          bare: true,
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: Some(vec![
+            "./a.js".to_string(),
+            "./b.ts".to_string(),
+            "https://examples.deno.land/hello-world.ts".to_string()
+          ]),
+          eval: None,
+          is_default_command: false,
         }),
+        type_check_mode: TypeCheckMode::None,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn allow_read_allowlist() {
+    use test_util::TempDir;
+    let temp_dir_guard = TempDir::new();
+    let temp_dir = temp_dir_guard.path().to_string();
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      format!("--allow-read=.,{}", temp_dir),
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        permissions: PermissionFlags {
+          allow_read: Some(vec![String::from("."), temp_dir]),
+          ..Default::default()
+        },
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn deny_read_denylist() {
+    use test_util::TempDir;
+    let temp_dir_guard = TempDir::new();
+    let temp_dir = temp_dir_guard.path().to_string();
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      format!("--deny-read=.,{}", temp_dir),
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
         permissions: PermissionFlags {
-          deny_read: Some(vec![]),
+          deny_read: Some(vec![String::from("."), temp_dir]),
           ..Default::default()
         },
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -6176,18 +8984,27 @@ mod tests {
   }
 
   #[test]
-  fn allow_hrtime() {
-    let r = flags_from_vec(svec!["deno", "run", "--allow-hrtime", "gist.ts"]);
+  fn allow_write_allowlist() {
+    use test_util::TempDir;
+    let temp_dir_guard = TempDir::new();
+    let temp_dir = temp_dir_guard.path().to_string();
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      format!("--allow-write=.,{}", temp_dir),
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string(),
-        )),
         permissions: PermissionFlags {
-          allow_hrtime: true,
+          allow_write: Some(vec![String::from("."), temp_dir]),
           ..Default::default()
         },
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -6195,18 +9012,27 @@ mod tests {
   }
 
   #[test]
-  fn deny_hrtime() {
-    let r = flags_from_vec(svec!["deno", "run", "--deny-hrtime", "gist.ts"]);
+  fn deny_write_denylist() {
+    use test_util::TempDir;
+    let temp_dir_guard = TempDir::new();
+    let temp_dir = temp_dir_guard.path().to_string();
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      format!("--deny-write=.,{}", temp_dir),
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string(),
-        )),
         permissions: PermissionFlags {
-          deny_hrtime: true,
+          deny_write: Some(vec![String::from("."), temp_dir]),
           ..Default::default()
         },
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -6214,18 +9040,12 @@ mod tests {
   }
 
   #[test]
-  fn double_hyphen() {
-    // notice that flags passed after double dash will not
-    // be parsed to Flags but instead forwarded to
-    // script args as Deno.args
+  fn allow_net_allowlist() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--allow-write",
-      "script.ts",
-      "--",
-      "-D",
-      "--allow-net"
+      "--allow-net=127.0.0.1",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
@@ -6233,9 +9053,8 @@ mod tests {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        argv: svec!["--", "-D", "--allow-net"],
         permissions: PermissionFlags {
-          allow_write: Some(vec![]),
+          allow_net: Some(svec!["127.0.0.1"]),
           ..Default::default()
         },
         code_cache_enabled: true,
@@ -6245,995 +9064,880 @@ mod tests {
   }
 
   #[test]
-  fn fmt() {
-    let r = flags_from_vec(svec!["deno", "fmt", "script_1.ts", "script_2.ts"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Default::default(),
-        }),
-        ext: Some("ts".to_string()),
-        ..Flags::default()
-      }
-    );
-
-    let r = flags_from_vec(svec!["deno", "fmt", "--check"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: true,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Default::default(),
-        }),
-        ext: Some("ts".to_string()),
-        ..Flags::default()
-      }
-    );
-
-    let r = flags_from_vec(svec!["deno", "fmt"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Default::default(),
-        }),
-        ext: Some("ts".to_string()),
-        ..Flags::default()
-      }
-    );
-
-    let r = flags_from_vec(svec!["deno", "fmt", "--watch"]);
+  fn deny_net_denylist() {
+    let r = flags_from_vec(svec!["deno", "--deny-net=127.0.0.1", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Some(Default::default()),
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        ext: Some("ts".to_string()),
+        permissions: PermissionFlags {
+          deny_net: Some(svec!["127.0.0.1"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "fmt",
-      "--watch",
-      "--no-clear-screen",
-      "--unstable-css",
-      "--unstable-html",
-      "--unstable-component",
-      "--unstable-yaml"
-    ]);
+  #[test]
+  fn allow_env_allowlist() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-env=HOME", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: true,
-          unstable_html: true,
-          unstable_component: true,
-          unstable_yaml: true,
-          watch: Some(WatchFlags {
-            hmr: false,
-            no_clear_screen: true,
-            exclude: vec![],
-          })
-        }),
-        ext: Some("ts".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_env: Some(svec!["HOME"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "fmt",
-      "--check",
-      "--watch",
-      "foo.ts",
-      "--ignore=bar.js"
-    ]);
+  #[test]
+  fn deny_env_denylist() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deny-env=HOME", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: true,
-          files: FileFlags {
-            include: vec!["foo.ts".to_string()],
-            ignore: vec!["bar.js".to_string()],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Some(Default::default()),
-        }),
-        ext: Some("ts".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          deny_env: Some(svec!["HOME"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "fmt", "--config", "deno.jsonc"]);
+  #[test]
+  fn allow_env_allowlist_multiple() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-env=HOME,PATH",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Default::default(),
-        }),
-        ext: Some("ts".to_string()),
-        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_env: Some(svec!["HOME", "PATH"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "fmt",
-      "--config",
-      "deno.jsonc",
-      "--watch",
-      "foo.ts"
-    ]);
+  #[test]
+  fn deny_env_denylist_multiple() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deny-env=HOME,PATH", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec!["foo.ts".to_string()],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Some(Default::default()),
-        }),
-        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
-        ext: Some("ts".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          deny_env: Some(svec!["HOME", "PATH"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "fmt",
-      "--use-tabs",
-      "--line-width",
-      "60",
-      "--indent-width",
-      "4",
-      "--single-quote",
-      "--prose-wrap",
-      "never",
-      "--no-semicolons",
-    ]);
+  #[test]
+  fn allow_env_allowlist_validator() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-env=HOME", "script.ts"]);
+    assert!(r.is_ok());
+    let r = flags_from_vec(svec!["deno", "--allow-env=H=ME", "script.ts"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-env=H\0ME", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn deny_env_denylist_validator() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deny-env=HOME", "script.ts"]);
+    assert!(r.is_ok());
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deny-env=H=ME", "script.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "--deny-env=H\0ME", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn allow_sys() {
+    let r = flags_from_vec(svec!["deno", "run", "--allow-sys", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          use_tabs: Some(true),
-          line_width: Some(NonZeroU32::new(60).unwrap()),
-          indent_width: Some(NonZeroU8::new(4).unwrap()),
-          single_quote: Some(true),
-          prose_wrap: Some("never".to_string()),
-          no_semicolons: Some(true),
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Default::default(),
-        }),
-        ext: Some("ts".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_sys: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    // try providing =false to the booleans
-    let r = flags_from_vec(svec![
-      "deno",
-      "fmt",
-      "--use-tabs=false",
-      "--single-quote=false",
-      "--no-semicolons=false",
-    ]);
+  #[test]
+  fn deny_sys() {
+    let r = flags_from_vec(svec!["deno", "run", "--deny-sys", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          use_tabs: Some(false),
-          line_width: None,
-          indent_width: None,
-          single_quote: Some(false),
-          prose_wrap: None,
-          no_semicolons: Some(false),
-          unstable_css: false,
-          unstable_html: false,
-          unstable_component: false,
-          unstable_yaml: false,
-          watch: Default::default(),
-        }),
-        ext: Some("ts".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          deny_sys: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn lint() {
-    let r = flags_from_vec(svec!["deno", "lint", "script_1.ts", "script_2.ts"]);
+  fn allow_sys_allowlist() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-sys=hostname", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string(),],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: false,
-          watch: Default::default(),
-          ext: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_sys: Some(svec!["hostname"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "lint",
-      "--watch",
-      "script_1.ts",
-      "script_2.ts"
-    ]);
+  #[test]
+  fn deny_sys_denylist() {
+    let r = flags_from_vec(svec!["deno", "--deny-sys=hostname", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: false,
-          watch: Some(Default::default()),
-          ext: None,
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
+        permissions: PermissionFlags {
+          deny_sys: Some(svec!["hostname"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn allow_sys_allowlist_multiple() {
     let r = flags_from_vec(svec![
       "deno",
-      "lint",
-      "--watch",
-      "--no-clear-screen",
-      "script_1.ts",
-      "script_2.ts"
+      "run",
+      "--allow-sys=hostname,osRelease",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: false,
-          watch: Some(WatchFlags {
-            hmr: false,
-            no_clear_screen: true,
-            exclude: vec![],
-          }),
-          ext: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_sys: Some(svec!["hostname", "osRelease"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn deny_sys_denylist_multiple() {
     let r = flags_from_vec(svec![
       "deno",
-      "lint",
-      "--fix",
-      "--ignore=script_1.ts,script_2.ts"
+      "run",
+      "--deny-sys=hostname,osRelease",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec![],
-            ignore: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
-          },
-          fix: true,
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: false,
-          watch: Default::default(),
-          ext: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          deny_sys: Some(svec!["hostname", "osRelease"]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "lint", "--rules"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: true,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: false,
-          watch: Default::default(),
-          ext: None,
-        }),
-        ..Flags::default()
-      }
-    );
+  #[test]
+  fn allow_sys_allowlist_validator() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-sys=hostname", "script.ts"]);
+    assert!(r.is_ok());
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-sys=hostname,osRelease",
+      "script.ts"
+    ]);
+    assert!(r.is_ok());
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-sys=foo", "script.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-sys=hostname,foo",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
 
+  #[test]
+  fn deny_sys_denylist_validator() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deny-sys=hostname", "script.ts"]);
+    assert!(r.is_ok());
     let r = flags_from_vec(svec![
       "deno",
-      "lint",
-      "--rules",
-      "--rules-tags=recommended"
+      "run",
+      "--deny-sys=hostname,osRelease",
+      "script.ts"
     ]);
+    assert!(r.is_ok());
+    let r = flags_from_vec(svec!["deno", "run", "--deny-sys=foo", "script.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--deny-sys=hostname,foo",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn reload_validator() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=http://deno.land/",
+      "script.ts"
+    ]);
+    assert!(r.is_ok(), "should accept valid urls");
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=http://deno.land/a,http://deno.land/b",
+      "script.ts"
+    ]);
+    assert!(r.is_ok(), "should accept accept multiple valid urls");
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=./relativeurl/",
+      "script.ts"
+    ]);
+    assert!(r.is_err(), "Should reject relative urls that start with ./");
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=relativeurl/",
+      "script.ts"
+    ]);
+    assert!(r.is_err(), "Should reject relative urls");
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--reload=/absolute", "script.ts"]);
+    assert!(r.is_err(), "Should reject absolute urls");
+
+    let r = flags_from_vec(svec!["deno", "--reload=/", "script.ts"]);
+    assert!(r.is_err(), "Should reject absolute root url");
+
+    let r = flags_from_vec(svec!["deno", "run", "--reload=", "script.ts"]);
+    assert!(r.is_err(), "Should reject when nothing is provided");
+
+    let r = flags_from_vec(svec!["deno", "run", "--reload=,", "script.ts"]);
+    assert!(r.is_err(), "Should reject when a single comma is provided");
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=,http://deno.land/a",
+      "script.ts"
+    ]);
+    assert!(r.is_err(), "Should reject a leading comma");
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=http://deno.land/a,",
+      "script.ts"
+    ]);
+    assert!(r.is_err(), "Should reject a trailing comma");
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=http://deno.land/a,,http://deno.land/b",
+      "script.ts"
+    ]);
+    assert!(r.is_err(), "Should reject adjacent commas");
+  }
+
+  #[test]
+  fn bundle() {
+    let r = flags_from_vec(svec!["deno", "bundle", "source.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: true,
-          maybe_rules_tags: Some(svec!["recommended"]),
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: false,
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: None,
           watch: Default::default(),
-          ext: None,
         }),
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn bundle_with_config() {
     let r = flags_from_vec(svec![
-      "deno",
-      "lint",
-      "--rules-tags=",
-      "--rules-include=ban-untagged-todo,no-undef",
-      "--rules-exclude=no-const-assign"
-    ]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: false,
-          maybe_rules_tags: Some(svec![""]),
-          maybe_rules_include: Some(svec!["ban-untagged-todo", "no-undef"]),
-          maybe_rules_exclude: Some(svec!["no-const-assign"]),
-          json: false,
-          compact: false,
+      "deno",
+      "bundle",
+      "--no-remote",
+      "--config",
+      "tsconfig.json",
+      "source.ts",
+      "bundle.js"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: Some("bundle.js".to_string()),
           watch: Default::default(),
-          ext: None,
         }),
+        permissions: PermissionFlags {
+          allow_write: Some(vec![]),
+          ..Default::default()
+        },
+        no_remote: true,
+        type_check_mode: TypeCheckMode::Local,
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "lint", "--json", "script_1.ts"]);
+  #[test]
+  fn bundle_with_output() {
+    let r = flags_from_vec(svec!["deno", "bundle", "source.ts", "bundle.js"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec!["script_1.ts".to_string()],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: true,
-          compact: false,
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: Some("bundle.js".to_string()),
           watch: Default::default(),
-          ext: None,
         }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          allow_write: Some(vec![]),
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn bundle_with_lock() {
     let r = flags_from_vec(svec![
       "deno",
-      "lint",
-      "--config",
-      "Deno.jsonc",
-      "--json",
-      "script_1.ts"
+      "bundle",
+      "--lock-write",
+      "--lock=lock.json",
+      "source.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec!["script_1.ts".to_string()],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: true,
-          compact: false,
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: None,
           watch: Default::default(),
-          ext: None,
         }),
-        config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
+        type_check_mode: TypeCheckMode::Local,
+        lock_write: true,
+        lock: Some(String::from("lock.json")),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "lint",
-      "--config",
-      "Deno.jsonc",
-      "--compact",
-      "script_1.ts"
-    ]);
+  #[test]
+  fn bundle_with_reload() {
+    let r = flags_from_vec(svec!["deno", "bundle", "--reload", "source.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
-          files: FileFlags {
-            include: vec!["script_1.ts".to_string()],
-            ignore: vec![],
-          },
-          fix: false,
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: true,
+        reload: true,
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: None,
           watch: Default::default(),
-          ext: None,
         }),
-        config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn types() {
-    let r = flags_from_vec(svec!["deno", "types"]);
+  fn bundle_nocheck() {
+    let r = flags_from_vec(svec!["deno", "bundle", "--no-check", "script.ts"])
+      .unwrap();
     assert_eq!(
-      r.unwrap(),
+      r,
       Flags {
-        subcommand: DenoSubcommand::Types,
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "script.ts".to_string(),
+          out_file: None,
+          watch: Default::default(),
+        }),
+        type_check_mode: TypeCheckMode::None,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn cache() {
-    let r = flags_from_vec(svec!["deno", "cache", "script.ts"]);
+  fn bundle_watch() {
+    let r = flags_from_vec(svec!["deno", "bundle", "--watch", "source.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Cache(CacheFlags {
-          files: svec!["script.ts"],
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: None,
+          watch: Some(Default::default()),
         }),
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
-    );
+    )
   }
 
   #[test]
-  fn check() {
-    let r = flags_from_vec(svec!["deno", "check", "script.ts"]);
+  fn bundle_watch_with_no_clear_screen() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bundle",
+      "--watch",
+      "--no-clear-screen",
+      "source.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Check(CheckFlags {
-          files: svec!["script.ts"],
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: None,
+          watch: Some(WatchFlags {
+            hmr: false,
+            no_clear_screen: true,
+            exclude: vec![],
+          }),
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
-    );
-
-    for all_flag in ["--remote", "--all"] {
-      let r = flags_from_vec(svec!["deno", "check", all_flag, "script.ts"]);
-      assert_eq!(
-        r.unwrap(),
-        Flags {
-          subcommand: DenoSubcommand::Check(CheckFlags {
-            files: svec!["script.ts"],
-          }),
-          type_check_mode: TypeCheckMode::All,
-          ..Flags::default()
-        }
-      );
+    )
+  }
 
-      let r = flags_from_vec(svec![
-        "deno",
-        "check",
-        all_flag,
-        "--no-remote",
-        "script.ts"
-      ]);
-      assert_eq!(
-        r.unwrap_err().kind(),
-        clap::error::ErrorKind::ArgumentConflict
-      );
-    }
+  #[test]
+  fn run_import_map() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--import-map=import_map.json",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        import_map_path: Some("import_map.json".to_owned()),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
-  fn info() {
-    let r = flags_from_vec(svec!["deno", "info", "script.ts"]);
+  fn info_import_map() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--import-map=import_map.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
-          json: false,
           file: Some("script.ts".to_string()),
+          json: false,
         }),
+        import_map_path: Some("import_map.json".to_owned()),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "info", "--reload", "script.ts"]);
+  #[test]
+  fn cache_import_map() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "cache",
+      "--import-map=import_map.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Info(InfoFlags {
-          json: false,
-          file: Some("script.ts".to_string()),
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["script.ts"],
         }),
-        reload: true,
+        import_map_path: Some("import_map.json".to_owned()),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "info", "--json", "script.ts"]);
+  #[test]
+  fn doc_import_map() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--import-map=import_map.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Info(InfoFlags {
-          json: true,
-          file: Some("script.ts".to_string()),
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          source_files: DocSourceFileFlag::Paths(vec!["script.ts".to_owned()]),
+          private: false,
+          json: false,
+          html: None,
+          lint: false,
+          filter: None,
         }),
+        import_map_path: Some("import_map.json".to_owned()),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "info"]);
+  #[test]
+  fn run_env_default() {
+    let r = flags_from_vec(svec!["deno", "run", "--env", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Info(InfoFlags {
-          json: false,
-          file: None
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(vec![".env".to_owned()]),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "info", "--json"]);
+  #[test]
+  fn run_env_file_default() {
+    let r = flags_from_vec(svec!["deno", "run", "--env-file", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Info(InfoFlags {
-          json: true,
-          file: None
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(vec![".env".to_owned()]),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn run_env_file_layered() {
     let r = flags_from_vec(svec![
       "deno",
-      "info",
-      "--no-npm",
-      "--no-remote",
-      "--config",
-      "tsconfig.json"
+      "run",
+      "--env-file",
+      "--env-file=.env.{NODE_ENV}",
+      "--env-file-override",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Info(InfoFlags {
-          json: false,
-          file: None
-        }),
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
-        no_npm: true,
-        no_remote: true,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(svec![".env", ".env.{NODE_ENV}"]),
+        env_file_override: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn tsconfig() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "-c", "tsconfig.json", "script.ts"]);
+  fn run_no_code_cache() {
+    let r = flags_from_vec(svec!["deno", "--no-code-cache", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn eval() {
-    let r = flags_from_vec(svec!["deno", "eval", "'console.log(\"hello\")'"]);
+  fn run_env_defined() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--env=.another_env", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Eval(EvalFlags {
-          print: false,
-          code: "'console.log(\"hello\")'".to_string(),
-        }),
-        permissions: PermissionFlags {
-          allow_all: true,
-          allow_net: Some(vec![]),
-          allow_env: Some(vec![]),
-          allow_run: Some(vec![]),
-          allow_read: Some(vec![]),
-          allow_sys: Some(vec![]),
-          allow_write: Some(vec![]),
-          allow_ffi: Some(vec![]),
-          allow_hrtime: true,
-          ..Default::default()
-        },
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(vec![".another_env".to_owned()]),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn eval_p() {
-    let r = flags_from_vec(svec!["deno", "eval", "-p", "1+2"]);
+  fn run_env_file_defined() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--env-file=.another_env",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Eval(EvalFlags {
-          print: true,
-          code: "1+2".to_string(),
-        }),
-        permissions: PermissionFlags {
-          allow_all: true,
-          allow_net: Some(vec![]),
-          allow_env: Some(vec![]),
-          allow_run: Some(vec![]),
-          allow_read: Some(vec![]),
-          allow_sys: Some(vec![]),
-          allow_write: Some(vec![]),
-          allow_ffi: Some(vec![]),
-          allow_hrtime: true,
-          ..Default::default()
-        },
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(vec![".another_env".to_owned()]),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn eval_typescript() {
+  fn cache_multiple() {
     let r =
-      flags_from_vec(svec!["deno", "eval", "-T", "'console.log(\"hello\")'"]);
+      flags_from_vec(svec!["deno", "cache", "script.ts", "script_two.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Eval(EvalFlags {
-          print: false,
-          code: "'console.log(\"hello\")'".to_string(),
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["script.ts", "script_two.ts"],
         }),
-        permissions: PermissionFlags {
-          allow_all: true,
-          allow_net: Some(vec![]),
-          allow_env: Some(vec![]),
-          allow_run: Some(vec![]),
-          allow_read: Some(vec![]),
-          allow_sys: Some(vec![]),
-          allow_write: Some(vec![]),
-          allow_ffi: Some(vec![]),
-          allow_hrtime: true,
-          ..Default::default()
-        },
-        ext: Some("ts".to_string()),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn eval_with_flags() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "eval", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--env=.example.env", "42"]);
+  fn run_seed() {
+    let r = flags_from_vec(svec!["deno", "run", "--seed", "250", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Eval(EvalFlags {
-          print: false,
-          code: "42".to_string(),
-        }),
-        import_map_path: Some("import_map.json".to_string()),
-        no_remote: true,
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
-        type_check_mode: TypeCheckMode::None,
-        reload: true,
-        lock: Some(String::from("lock.json")),
-        lock_write: true,
-        ca_data: Some(CaData::File("example.crt".to_string())),
-        cached_only: true,
-        location: Some(Url::parse("https://foo/").unwrap()),
-        v8_flags: svec!["--help", "--random-seed=1"],
-        seed: Some(1),
-        inspect: Some("127.0.0.1:9229".parse().unwrap()),
-        permissions: PermissionFlags {
-          allow_all: true,
-          allow_net: Some(vec![]),
-          allow_env: Some(vec![]),
-          allow_run: Some(vec![]),
-          allow_read: Some(vec![]),
-          allow_sys: Some(vec![]),
-          allow_write: Some(vec![]),
-          allow_ffi: Some(vec![]),
-          allow_hrtime: true,
-          ..Default::default()
-        },
-        env_file: Some(".example.env".to_owned()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        seed: Some(250_u64),
+        v8_flags: svec!["--random-seed=250"],
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn eval_args() {
+  fn run_seed_with_v8_flags() {
     let r = flags_from_vec(svec![
       "deno",
-      "eval",
-      "console.log(Deno.args)",
-      "arg1",
-      "arg2"
+      "run",
+      "--seed",
+      "250",
+      "--v8-flags=--expose-gc",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Eval(EvalFlags {
-          print: false,
-          code: "console.log(Deno.args)".to_string(),
-        }),
-        argv: svec!["arg1", "arg2"],
-        permissions: PermissionFlags {
-          allow_all: true,
-          allow_net: Some(vec![]),
-          allow_env: Some(vec![]),
-          allow_run: Some(vec![]),
-          allow_read: Some(vec![]),
-          allow_sys: Some(vec![]),
-          allow_write: Some(vec![]),
-          allow_ffi: Some(vec![]),
-          allow_hrtime: true,
-          ..Default::default()
-        },
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        seed: Some(250_u64),
+        v8_flags: svec!["--expose-gc", "--random-seed=250"],
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn repl() {
-    let r = flags_from_vec(svec!["deno"]);
+  fn install() {
+    let r =
+      flags_from_vec(svec!["deno", "install", "jsr:@std/http/file-server"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Repl(ReplFlags {
-          eval_files: None,
-          eval: None,
-          is_default_command: true,
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::Global(InstallFlagsGlobal {
+            name: None,
+            module_url: "jsr:@std/http/file-server".to_string(),
+            args: vec![],
+            root: None,
+            force: false,
+          }),
+          global: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "-g",
+      "jsr:@std/http/file-server"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::Global(InstallFlagsGlobal {
+            name: None,
+            module_url: "jsr:@std/http/file-server".to_string(),
+            args: vec![],
+            root: None,
+            force: false,
+          }),
+          global: true,
         }),
-        unsafely_ignore_certificate_errors: None,
-        permissions: PermissionFlags {
-          allow_net: Some(vec![]),
-          allow_env: Some(vec![]),
-          deny_env: None,
-          allow_run: Some(vec![]),
-          deny_run: None,
-          allow_read: Some(vec![]),
-          deny_read: None,
-          allow_sys: Some(vec![]),
-          deny_sys: None,
-          allow_write: Some(vec![]),
-          deny_write: None,
-          allow_ffi: Some(vec![]),
-          deny_ffi: None,
-          allow_hrtime: true,
-          ..Default::default()
-        },
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn repl_strace_ops() {
-    // Lightly test this undocumented flag
-    let r = flags_from_vec(svec!["deno", "repl", "--strace-ops"]);
-    assert_eq!(r.unwrap().strace_ops, Some(vec![]));
-    let r =
-      flags_from_vec(svec!["deno", "repl", "--strace-ops=http,websocket"]);
-    assert_eq!(
-      r.unwrap().strace_ops,
-      Some(vec!["http".to_string(), "websocket".to_string()])
-    );
-  }
-
-  #[test]
-  fn repl_with_flags() {
+  fn install_with_flags() {
     #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "repl", "-A", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--unsafely-ignore-certificate-errors", "--env=.example.env"]);
+    let r = flags_from_vec(svec!["deno", "install", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--name", "file_server", "--root", "/foo", "--force", "--env=.example.env", "jsr:@std/http/file-server", "foo", "bar"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Repl(ReplFlags {
-          eval_files: None,
-          eval: None,
-          is_default_command: false,
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::Global(InstallFlagsGlobal {
+            name: Some("file_server".to_string()),
+            module_url: "jsr:@std/http/file-server".to_string(),
+            args: svec!["foo", "bar"],
+            root: Some("/foo".to_string()),
+            force: true,
+          }),
+          global: false,
         }),
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -7244,95 +9948,71 @@ mod tests {
         lock_write: true,
         ca_data: Some(CaData::File("example.crt".to_string())),
         cached_only: true,
-        location: Some(Url::parse("https://foo/").unwrap()),
         v8_flags: svec!["--help", "--random-seed=1"],
         seed: Some(1),
         inspect: Some("127.0.0.1:9229".parse().unwrap()),
+        unsafely_ignore_certificate_errors: Some(vec![]),
         permissions: PermissionFlags {
-          allow_all: true,
           allow_net: Some(vec![]),
-          allow_env: Some(vec![]),
-          allow_run: Some(vec![]),
           allow_read: Some(vec![]),
-          allow_sys: Some(vec![]),
-          allow_write: Some(vec![]),
-          allow_ffi: Some(vec![]),
-          allow_hrtime: true,
           ..Default::default()
         },
-        env_file: Some(".example.env".to_owned()),
-        unsafely_ignore_certificate_errors: Some(vec![]),
+        env_file: Some(vec![".example.env".to_owned()]),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn repl_with_eval_flag() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "repl", "--allow-write", "--eval", "console.log('hello');"]);
+  fn uninstall() {
+    let r = flags_from_vec(svec!["deno", "uninstall", "file_server"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Repl(ReplFlags {
-          eval_files: None,
-          eval: Some("console.log('hello');".to_string()),
-          is_default_command: false,
+        subcommand: DenoSubcommand::Uninstall(UninstallFlags {
+          kind: UninstallKind::Global(UninstallFlagsGlobal {
+            name: "file_server".to_string(),
+            root: None,
+          }),
+          global: false,
         }),
-        permissions: PermissionFlags {
-          allow_write: Some(vec![]),
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::None,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn repl_with_eval_file_flag() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "repl", "--eval-file=./a.js,./b.ts,https://examples.deno.land/hello-world.ts"]);
+    let r = flags_from_vec(svec!["deno", "uninstall", "-g", "file_server"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Repl(ReplFlags {
-          eval_files: Some(vec![
-            "./a.js".to_string(),
-            "./b.ts".to_string(),
-            "https://examples.deno.land/hello-world.ts".to_string()
-          ]),
-          eval: None,
-          is_default_command: false,
+        subcommand: DenoSubcommand::Uninstall(UninstallFlags {
+          kind: UninstallKind::Global(UninstallFlagsGlobal {
+            name: "file_server".to_string(),
+            root: None,
+          }),
+          global: true,
         }),
-        type_check_mode: TypeCheckMode::None,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn allow_read_allowlist() {
-    use test_util::TempDir;
-    let temp_dir_guard = TempDir::new();
-    let temp_dir = temp_dir_guard.path().to_string();
+  fn uninstall_with_help_flag() {
+    let r = flags_from_vec(svec!["deno", "uninstall", "--help"]);
+    assert!(r.is_ok());
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      format!("--allow-read=.,{}", temp_dir),
-      "script.ts"
-    ]);
+  #[test]
+  fn log_level() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--log-level=debug", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        permissions: PermissionFlags {
-          allow_read: Some(vec![String::from("."), temp_dir]),
-          ..Default::default()
-        },
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
+        log_level: Some(Level::Debug),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7340,107 +10020,143 @@ mod tests {
   }
 
   #[test]
-  fn deny_read_denylist() {
-    use test_util::TempDir;
-    let temp_dir_guard = TempDir::new();
-    let temp_dir = temp_dir_guard.path().to_string();
+  fn quiet() {
+    let r = flags_from_vec(svec!["deno", "-q", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
+        }),
+        log_level: Some(Level::Error),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      format!("--deny-read=.,{}", temp_dir),
-      "script.ts"
-    ]);
+    let r = flags_from_vec(svec!["deno", "run", "--quiet=all", "script.ts"]);
+    assert_eq!(r.unwrap().log_level, Some(Level::Error));
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--quiet=progress", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        permissions: PermissionFlags {
-          deny_read: Some(vec![String::from("."), temp_dir]),
-          ..Default::default()
-        },
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
+        quiet_progress: true,
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "run", "--quiet=some", "script.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn allow_write_allowlist() {
-    use test_util::TempDir;
-    let temp_dir_guard = TempDir::new();
-    let temp_dir = temp_dir_guard.path().to_string();
+  fn completions() {
+    let r = flags_from_vec(svec!["deno", "completions", "zsh"]).unwrap();
+
+    match r.subcommand {
+      DenoSubcommand::Completions(CompletionsFlags { buf }) => {
+        assert!(!buf.is_empty())
+      }
+      _ => unreachable!(),
+    }
+  }
 
+  #[test]
+  fn run_with_args() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      format!("--allow-write=.,{}", temp_dir),
-      "script.ts"
+      "script.ts",
+      "--allow-read",
+      "--allow-net"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        permissions: PermissionFlags {
-          allow_write: Some(vec![String::from("."), temp_dir]),
-          ..Default::default()
-        },
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
+        argv: svec!["--allow-read", "--allow-net"],
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
-
-  #[test]
-  fn deny_write_denylist() {
-    use test_util::TempDir;
-    let temp_dir_guard = TempDir::new();
-    let temp_dir = temp_dir_guard.path().to_string();
-
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      format!("--deny-write=.,{}", temp_dir),
-      "script.ts"
+      "--location",
+      "https:foo",
+      "--allow-read",
+      "script.ts",
+      "--allow-net",
+      "-r",
+      "--help",
+      "--foo",
+      "bar"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        location: Some(Url::parse("https://foo/").unwrap()),
         permissions: PermissionFlags {
-          deny_write: Some(vec![String::from("."), temp_dir]),
+          allow_read: Some(vec![]),
           ..Default::default()
         },
+        argv: svec!["--allow-net", "-r", "--help", "--foo", "bar"],
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "run", "script.ts", "foo", "bar"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
+        argv: svec!["foo", "bar"],
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+    let r = flags_from_vec(svec!["deno", "run", "script.ts", "-"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        argv: svec!["-"],
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn allow_net_allowlist() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--allow-net=127.0.0.1",
-      "script.ts"
-    ]);
+    let r =
+      flags_from_vec(svec!["deno", "run", "script.ts", "-", "foo", "bar"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          allow_net: Some(svec!["127.0.0.1"]),
-          ..Default::default()
-        },
+        argv: svec!["-", "foo", "bar"],
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7448,8 +10164,8 @@ mod tests {
   }
 
   #[test]
-  fn deny_net_denylist() {
-    let r = flags_from_vec(svec!["deno", "--deny-net=127.0.0.1", "script.ts"]);
+  fn no_check() {
+    let r = flags_from_vec(svec!["deno", "--no-check", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
@@ -7457,11 +10173,12 @@ mod tests {
           script: "script.ts".to_string(),
           watch: None,
           bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        permissions: PermissionFlags {
-          deny_net: Some(svec!["127.0.0.1"]),
-          ..Default::default()
-        },
+        type_check_mode: TypeCheckMode::None,
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7469,19 +10186,16 @@ mod tests {
   }
 
   #[test]
-  fn allow_env_allowlist() {
+  fn no_check_remote() {
     let r =
-      flags_from_vec(svec!["deno", "run", "--allow-env=HOME", "script.ts"]);
+      flags_from_vec(svec!["deno", "run", "--no-check=remote", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          allow_env: Some(svec!["HOME"]),
-          ..Default::default()
-        },
+        type_check_mode: TypeCheckMode::Local,
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7489,31 +10203,35 @@ mod tests {
   }
 
   #[test]
-  fn deny_env_denylist() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--deny-env=HOME", "script.ts"]);
+  fn repl_with_unsafely_ignore_certificate_errors() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "repl",
+      "--eval",
+      "console.log('hello');",
+      "--unsafely-ignore-certificate-errors"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        permissions: PermissionFlags {
-          deny_env: Some(svec!["HOME"]),
-          ..Default::default()
-        },
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: Some("console.log('hello');".to_string()),
+          is_default_command: false,
+        }),
+        unsafely_ignore_certificate_errors: Some(vec![]),
+        type_check_mode: TypeCheckMode::None,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn allow_env_allowlist_multiple() {
+  fn run_with_unsafely_ignore_certificate_errors() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--allow-env=HOME,PATH",
+      "--unsafely-ignore-certificate-errors",
       "script.ts"
     ]);
     assert_eq!(
@@ -7522,10 +10240,7 @@ mod tests {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          allow_env: Some(svec!["HOME", "PATH"]),
-          ..Default::default()
-        },
+        unsafely_ignore_certificate_errors: Some(vec![]),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7533,19 +10248,27 @@ mod tests {
   }
 
   #[test]
-  fn deny_env_denylist_multiple() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--deny-env=HOME,PATH", "script.ts"]);
+  fn run_with_unsafely_treat_insecure_origin_as_secure_with_ipv6_address() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--unsafely-ignore-certificate-errors=deno.land,localhost,[::],127.0.0.1,[::1],1.2.3.4",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          deny_env: Some(svec!["HOME", "PATH"]),
-          ..Default::default()
-        },
+        unsafely_ignore_certificate_errors: Some(svec![
+          "deno.land",
+          "localhost",
+          "[::]",
+          "127.0.0.1",
+          "[::1]",
+          "1.2.3.4"
+        ]),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7553,61 +10276,43 @@ mod tests {
   }
 
   #[test]
-  fn allow_env_allowlist_validator() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--allow-env=HOME", "script.ts"]);
-    assert!(r.is_ok());
-    let r = flags_from_vec(svec!["deno", "--allow-env=H=ME", "script.ts"]);
-    assert!(r.is_err());
-    let r =
-      flags_from_vec(svec!["deno", "run", "--allow-env=H\0ME", "script.ts"]);
-    assert!(r.is_err());
-  }
-
-  #[test]
-  fn deny_env_denylist_validator() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--deny-env=HOME", "script.ts"]);
-    assert!(r.is_ok());
-    let r =
-      flags_from_vec(svec!["deno", "run", "--deny-env=H=ME", "script.ts"]);
-    assert!(r.is_err());
-    let r = flags_from_vec(svec!["deno", "--deny-env=H\0ME", "script.ts"]);
-    assert!(r.is_err());
-  }
-
-  #[test]
-  fn allow_sys() {
-    let r = flags_from_vec(svec!["deno", "run", "--allow-sys", "script.ts"]);
+  fn repl_with_unsafely_treat_insecure_origin_as_secure_with_ipv6_address() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "repl",
+      "--unsafely-ignore-certificate-errors=deno.land,localhost,[::],127.0.0.1,[::1],1.2.3.4"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        permissions: PermissionFlags {
-          allow_sys: Some(vec![]),
-          ..Default::default()
-        },
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: None,
+          is_default_command: false,
+        }),
+        unsafely_ignore_certificate_errors: Some(svec![
+          "deno.land",
+          "localhost",
+          "[::]",
+          "127.0.0.1",
+          "[::1]",
+          "1.2.3.4"
+        ]),
+        type_check_mode: TypeCheckMode::None,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn deny_sys() {
-    let r = flags_from_vec(svec!["deno", "run", "--deny-sys", "script.ts"]);
+  fn no_remote() {
+    let r = flags_from_vec(svec!["deno", "run", "--no-remote", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          deny_sys: Some(vec![]),
-          ..Default::default()
-        },
+        no_remote: true,
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7615,19 +10320,15 @@ mod tests {
   }
 
   #[test]
-  fn allow_sys_allowlist() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--allow-sys=hostname", "script.ts"]);
+  fn no_npm() {
+    let r = flags_from_vec(svec!["deno", "run", "--no-npm", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          allow_sys: Some(svec!["hostname"]),
-          ..Default::default()
-        },
+        no_npm: true,
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7635,8 +10336,8 @@ mod tests {
   }
 
   #[test]
-  fn deny_sys_denylist() {
-    let r = flags_from_vec(svec!["deno", "--deny-sys=hostname", "script.ts"]);
+  fn local_npm() {
+    let r = flags_from_vec(svec!["deno", "--node-modules-dir", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
@@ -7644,23 +10345,21 @@ mod tests {
           script: "script.ts".to_string(),
           watch: None,
           bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        permissions: PermissionFlags {
-          deny_sys: Some(svec!["hostname"]),
-          ..Default::default()
-        },
+        node_modules_dir: Some(true),
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn allow_sys_allowlist_multiple() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--allow-sys=hostname,osRelease",
+      "--node-modules-dir=false",
       "script.ts"
     ]);
     assert_eq!(
@@ -7669,10 +10368,7 @@ mod tests {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          allow_sys: Some(svec!["hostname", "osRelease"]),
-          ..Default::default()
-        },
+        node_modules_dir: Some(false),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -7680,11 +10376,12 @@ mod tests {
   }
 
   #[test]
-  fn deny_sys_denylist_multiple() {
+  fn nm_layout_flag() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--deny-sys=hostname,osRelease",
+      "--node-modules-dir",
+      "--nm-layout=deterministic",
       "script.ts"
     ]);
     assert_eq!(
@@ -7693,315 +10390,326 @@ mod tests {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        permissions: PermissionFlags {
-          deny_sys: Some(svec!["hostname", "osRelease"]),
-          ..Default::default()
-        },
+        node_modules_dir: Some(true),
+        nm_layout: Some(NodeModulesLayout::Deterministic),
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
-
-  #[test]
-  fn allow_sys_allowlist_validator() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--allow-sys=hostname", "script.ts"]);
-    assert!(r.is_ok());
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--allow-sys=hostname,osRelease",
-      "script.ts"
-    ]);
-    assert!(r.is_ok());
-    let r =
-      flags_from_vec(svec!["deno", "run", "--allow-sys=foo", "script.ts"]);
-    assert!(r.is_err());
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--allow-sys=hostname,foo",
-      "script.ts"
-    ]);
-    assert!(r.is_err());
-  }
-
-  #[test]
-  fn deny_sys_denylist_validator() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--deny-sys=hostname", "script.ts"]);
-    assert!(r.is_ok());
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--deny-sys=hostname,osRelease",
-      "script.ts"
-    ]);
-    assert!(r.is_ok());
-    let r = flags_from_vec(svec!["deno", "run", "--deny-sys=foo", "script.ts"]);
-    assert!(r.is_err());
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--deny-sys=hostname,foo",
-      "script.ts"
-    ]);
-    assert!(r.is_err());
-  }
-
-  #[test]
-  fn reload_validator() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--reload=http://deno.land/",
-      "script.ts"
-    ]);
-    assert!(r.is_ok(), "should accept valid urls");
-
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--reload=http://deno.land/a,http://deno.land/b",
-      "script.ts"
-    ]);
-    assert!(r.is_ok(), "should accept accept multiple valid urls");
-
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--reload=./relativeurl/",
-      "script.ts"
-    ]);
-    assert!(r.is_err(), "Should reject relative urls that start with ./");
-
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--reload=relativeurl/",
-      "script.ts"
-    ]);
-    assert!(r.is_err(), "Should reject relative urls");
-
-    let r =
-      flags_from_vec(svec!["deno", "run", "--reload=/absolute", "script.ts"]);
-    assert!(r.is_err(), "Should reject absolute urls");
-
-    let r = flags_from_vec(svec!["deno", "--reload=/", "script.ts"]);
-    assert!(r.is_err(), "Should reject absolute root url");
-
-    let r = flags_from_vec(svec!["deno", "run", "--reload=", "script.ts"]);
-    assert!(r.is_err(), "Should reject when nothing is provided");
-
-    let r = flags_from_vec(svec!["deno", "run", "--reload=,", "script.ts"]);
-    assert!(r.is_err(), "Should reject when a single comma is provided");
 
+    let r =
+      flags_from_vec(svec!["deno", "run", "--nm-layout=flat", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn npm_target_flags() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--reload=,http://deno.land/a",
+      "cache",
+      "--npm-target-os=linux",
+      "--npm-target-cpu=arm64",
       "script.ts"
     ]);
-    assert!(r.is_err(), "Should reject a leading comma");
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["script.ts"],
+        }),
+        npm_target_os: Some("linux".to_string()),
+        npm_target_cpu: Some("arm64".to_string()),
+        ..Flags::default()
+      }
+    );
 
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--reload=http://deno.land/a,",
+      "cache",
+      "--npm-target-os=haiku",
       "script.ts"
     ]);
-    assert!(r.is_err(), "Should reject a trailing comma");
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn vendor_flag() {
+    let r = flags_from_vec(svec!["deno", "run", "--vendor", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        vendor: Some(true),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "run", "--vendor=false", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        vendor: Some(false),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn cached_only() {
+    let r = flags_from_vec(svec!["deno", "run", "--cached-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        cached_only: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
 
+  #[test]
+  fn allow_net_allowlist_with_ports() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--reload=http://deno.land/a,,http://deno.land/b",
+      "--allow-net=deno.land,:8000,:4545",
       "script.ts"
     ]);
-    assert!(r.is_err(), "Should reject adjacent commas");
-  }
-
-  #[test]
-  fn bundle() {
-    let r = flags_from_vec(svec!["deno", "bundle", "source.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: None,
-          watch: Default::default(),
-        }),
-        type_check_mode: TypeCheckMode::Local,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_net: Some(svec![
+            "deno.land",
+            "0.0.0.0:8000",
+            "127.0.0.1:8000",
+            "localhost:8000",
+            "0.0.0.0:4545",
+            "127.0.0.1:4545",
+            "localhost:4545"
+          ]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn bundle_with_config() {
+  fn deny_net_denylist_with_ports() {
     let r = flags_from_vec(svec![
       "deno",
-      "bundle",
-      "--no-remote",
-      "--config",
-      "tsconfig.json",
-      "source.ts",
-      "bundle.js"
+      "run",
+      "--deny-net=deno.land,:8000,:4545",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: Some("bundle.js".to_string()),
-          watch: Default::default(),
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
         permissions: PermissionFlags {
-          allow_write: Some(vec![]),
+          deny_net: Some(svec![
+            "deno.land",
+            "0.0.0.0:8000",
+            "127.0.0.1:8000",
+            "localhost:8000",
+            "0.0.0.0:4545",
+            "127.0.0.1:4545",
+            "localhost:4545"
+          ]),
           ..Default::default()
         },
-        no_remote: true,
-        type_check_mode: TypeCheckMode::Local,
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn bundle_with_output() {
-    let r = flags_from_vec(svec!["deno", "bundle", "source.ts", "bundle.js"]);
+  fn allow_net_allowlist_with_ipv6_address() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-net=deno.land,deno.land:80,[::],127.0.0.1,[::1],1.2.3.4:5678,:5678,[::1]:8080",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: Some("bundle.js".to_string()),
-          watch: Default::default(),
-        }),
-        type_check_mode: TypeCheckMode::Local,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
         permissions: PermissionFlags {
-          allow_write: Some(vec![]),
+          allow_net: Some(svec![
+            "deno.land",
+            "deno.land:80",
+            "[::]",
+            "127.0.0.1",
+            "[::1]",
+            "1.2.3.4:5678",
+            "0.0.0.0:5678",
+            "127.0.0.1:5678",
+            "localhost:5678",
+            "[::1]:8080"
+          ]),
           ..Default::default()
         },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn bundle_with_lock() {
+  fn deny_net_denylist_with_ipv6_address() {
     let r = flags_from_vec(svec![
       "deno",
-      "bundle",
-      "--lock-write",
-      "--lock=lock.json",
-      "source.ts"
+      "run",
+      "--deny-net=deno.land,deno.land:80,[::],127.0.0.1,[::1],1.2.3.4:5678,:5678,[::1]:8080",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: None,
-          watch: Default::default(),
-        }),
-        type_check_mode: TypeCheckMode::Local,
-        lock_write: true,
-        lock: Some(String::from("lock.json")),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          deny_net: Some(svec![
+            "deno.land",
+            "deno.land:80",
+            "[::]",
+            "127.0.0.1",
+            "[::1]",
+            "1.2.3.4:5678",
+            "0.0.0.0:5678",
+            "127.0.0.1:5678",
+            "localhost:5678",
+            "[::1]:8080"
+          ]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn bundle_with_reload() {
-    let r = flags_from_vec(svec!["deno", "bundle", "--reload", "source.ts"]);
+  fn lock_normalize() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--lock-normalize",
+      "--lock=lock.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        reload: true,
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: None,
-          watch: Default::default(),
-        }),
-        type_check_mode: TypeCheckMode::Local,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        lock_normalize: true,
+        lock: Some(String::from("lock.json")),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--lock-normalize",
+      "--no-lock",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn bundle_nocheck() {
-    let r = flags_from_vec(svec!["deno", "bundle", "--no-check", "script.ts"])
-      .unwrap();
+  fn lock_write() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--lock-write",
+      "--lock=lock.json",
+      "script.ts"
+    ]);
     assert_eq!(
-      r,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "script.ts".to_string(),
-          out_file: None,
-          watch: Default::default(),
-        }),
-        type_check_mode: TypeCheckMode::None,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        lock_write: true,
+        lock: Some(String::from("lock.json")),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn bundle_watch() {
-    let r = flags_from_vec(svec!["deno", "bundle", "--watch", "source.ts"]);
+    let r = flags_from_vec(svec!["deno", "--no-lock", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: None,
-          watch: Some(Default::default()),
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        type_check_mode: TypeCheckMode::Local,
+        no_lock: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
-    )
-  }
+    );
 
-  #[test]
-  fn bundle_watch_with_no_clear_screen() {
     let r = flags_from_vec(svec![
       "deno",
-      "bundle",
-      "--watch",
-      "--no-clear-screen",
-      "source.ts"
+      "run",
+      "--lock",
+      "--lock-write",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: None,
-          watch: Some(WatchFlags {
-            hmr: false,
-            no_clear_screen: true,
-            exclude: vec![],
-          }),
-        }),
-        type_check_mode: TypeCheckMode::Local,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        lock_write: true,
+        lock: Some(String::from("./deno.lock")),
+        code_cache_enabled: true,
         ..Flags::default()
       }
-    )
-  }
+    );
 
-  #[test]
-  fn run_import_map() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--import-map=import_map.json",
+      "--lock-write",
+      "--lock",
+      "lock.json",
       "script.ts"
     ]);
     assert_eq!(
@@ -8010,898 +10718,1137 @@ mod tests {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        import_map_path: Some("import_map.json".to_owned()),
+        lock_write: true,
+        lock: Some(String::from("lock.json")),
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn info_import_map() {
+    let r = flags_from_vec(svec!["deno", "run", "--lock-write", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        lock_write: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--lock", "--no-lock", "script.ts"]);
+    assert!(r.is_err(),);
+
     let r = flags_from_vec(svec![
       "deno",
-      "info",
-      "--import-map=import_map.json",
+      "run",
+      "--lock-write",
+      "--no-lock",
       "script.ts"
     ]);
+    assert!(r.is_err(),);
+  }
+
+  #[test]
+  fn test_no_colon_in_value_name() {
+    let app =
+      runtime_args(Command::new("test_inspect_completion_value"), true, true);
+    let inspect_args = app
+      .get_arguments()
+      .filter(|arg| arg.get_id() == "inspect")
+      .collect::<Vec<_>>();
+    // The value_name cannot have a : otherwise it breaks shell completions for zsh.
+    let value_name = "HOST_AND_PORT";
+    let arg = inspect_args
+      .iter()
+      .any(|v| v.get_value_names().unwrap() == [value_name]);
+
+    assert_eq!(arg, true);
+  }
+
+  #[test]
+  fn test_with_flags() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "test", "--unstable", "--no-npm", "--no-remote", "--trace-leaks", "--no-run", "--filter", "- foo", "--coverage=cov", "--clean", "--location", "https:foo", "--allow-net", "--permit-no-files", "dir1/", "dir2/", "--", "arg1", "arg2"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Info(InfoFlags {
-          file: Some("script.ts".to_string()),
-          json: false,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: true,
+          doc: false,
+          fail_fast: None,
+          filter: Some("- foo".to_string()),
+          allow_none: true,
+          files: FileFlags {
+            include: vec!["dir1/".to_string(), "dir2/".to_string()],
+            ignore: vec![],
+          },
+          shuffle: None,
+          concurrent_jobs: None,
+          trace_leaks: true,
+          coverage_dir: Some("cov".to_string()),
+          clean: true,
+          watch: Default::default(),
+          reporter: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
         }),
-        import_map_path: Some("import_map.json".to_owned()),
+        unstable_config: UnstableConfig {
+          legacy_flag_enabled: true,
+          ..Default::default()
+        },
+        no_npm: true,
+        no_remote: true,
+        location: Some(Url::parse("https://foo/").unwrap()),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          allow_net: Some(vec![]),
+          ..Default::default()
+        },
+        argv: svec!["arg1", "arg2"],
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn cache_import_map() {
+  fn run_with_cafile() {
     let r = flags_from_vec(svec![
       "deno",
-      "cache",
-      "--import-map=import_map.json",
+      "run",
+      "--cert",
+      "example.crt",
       "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Cache(CacheFlags {
-          files: svec!["script.ts"],
-        }),
-        import_map_path: Some("import_map.json".to_owned()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        ca_data: Some(CaData::File("example.crt".to_owned())),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn doc_import_map() {
+  fn run_with_enable_testing_features() {
     let r = flags_from_vec(svec![
       "deno",
-      "doc",
-      "--import-map=import_map.json",
+      "run",
+      "--enable-testing-features-do-not-use",
       "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          source_files: DocSourceFileFlag::Paths(vec!["script.ts".to_owned()]),
-          private: false,
-          json: false,
-          html: None,
-          lint: false,
-          filter: None,
-        }),
-        import_map_path: Some("import_map.json".to_owned()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        enable_testing_features: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn run_env_default() {
-    let r = flags_from_vec(svec!["deno", "run", "--env", "script.ts"]);
+  fn test_with_concurrent_jobs() {
+    let r = flags_from_vec(svec!["deno", "test", "--jobs=4"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        env_file: Some(".env".to_owned()),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          reporter: Default::default(),
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          shuffle: None,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          concurrent_jobs: Some(NonZeroUsize::new(4).unwrap()),
+          trace_leaks: false,
+          coverage_dir: None,
+          clean: false,
+          watch: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "test", "--jobs=0"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn run_env_file_default() {
-    let r = flags_from_vec(svec!["deno", "run", "--env-file", "script.ts"]);
+  fn test_with_fail_fast() {
+    let r = flags_from_vec(svec!["deno", "test", "--fail-fast=3"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        env_file: Some(".env".to_owned()),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          doc: false,
+          fail_fast: Some(NonZeroUsize::new(3).unwrap()),
+          filter: None,
+          allow_none: false,
+          shuffle: None,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          concurrent_jobs: None,
+          trace_leaks: false,
+          coverage_dir: None,
+          clean: false,
+          watch: Default::default(),
+          reporter: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "test", "--fail-fast=0"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn run_no_code_cache() {
-    let r = flags_from_vec(svec!["deno", "--no-code-cache", "script.ts"]);
+  fn test_with_enable_testing_features() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--enable-testing-features-do-not-use"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: None,
-          bare: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          shuffle: None,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          concurrent_jobs: None,
+          trace_leaks: false,
+          coverage_dir: None,
+          clean: false,
+          watch: Default::default(),
+          reporter: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
         }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        enable_testing_features: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn run_env_defined() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--env=.another_env", "script.ts"]);
+  fn test_reporter() {
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=pretty"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        env_file: Some(".another_env".to_owned()),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Pretty,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn run_env_file_defined() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--env-file=.another_env",
-      "script.ts"
-    ]);
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=dot"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        env_file: Some(".another_env".to_owned()),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Dot,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn cache_multiple() {
-    let r =
-      flags_from_vec(svec!["deno", "cache", "script.ts", "script_two.ts"]);
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=junit"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Cache(CacheFlags {
-          files: svec!["script.ts", "script_two.ts"],
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Junit,
+          ..Default::default()
         }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn run_seed() {
-    let r = flags_from_vec(svec!["deno", "run", "--seed", "250", "script.ts"]);
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=tap"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        seed: Some(250_u64),
-        v8_flags: svec!["--random-seed=250"],
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Tap,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn run_seed_with_v8_flags() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--seed",
-      "250",
-      "--v8-flags=--expose-gc",
-      "script.ts"
+      "test",
+      "--reporter=dot",
+      "--junit-path=report.xml"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        seed: Some(250_u64),
-        v8_flags: svec!["--expose-gc", "--random-seed=250"],
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Dot,
+          junit_path: Some("report.xml".to_string()),
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "test", "--junit-path"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn install() {
-    let r =
-      flags_from_vec(svec!["deno", "install", "jsr:@std/http/file-server"]);
+  fn test_shuffle() {
+    let r = flags_from_vec(svec!["deno", "test", "--shuffle=1"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Install(InstallFlags {
-          kind: InstallKind::Global(InstallFlagsGlobal {
-            name: None,
-            module_url: "jsr:@std/http/file-server".to_string(),
-            args: vec![],
-            root: None,
-            force: false,
-          }),
-          global: false,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          shuffle: Some(1),
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          concurrent_jobs: None,
+          trace_leaks: false,
+          coverage_dir: None,
+          clean: false,
+          watch: Default::default(),
+          reporter: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
         }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "install",
-      "-g",
-      "jsr:@std/http/file-server"
-    ]);
+  #[test]
+  fn test_watch() {
+    let r = flags_from_vec(svec!["deno", "test", "--watch"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Install(InstallFlags {
-          kind: InstallKind::Global(InstallFlagsGlobal {
-            name: None,
-            module_url: "jsr:@std/http/file-server".to_string(),
-            args: vec![],
-            root: None,
-            force: false,
-          }),
-          global: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          shuffle: None,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          concurrent_jobs: None,
+          trace_leaks: false,
+          coverage_dir: None,
+          clean: false,
+          watch: Some(Default::default()),
+          reporter: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
         }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
   }
-
   #[test]
-  fn install_with_flags() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "install", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--name", "file_server", "--root", "/foo", "--force", "--env=.example.env", "jsr:@std/http/file-server", "foo", "bar"]);
+  fn test_watch_explicit_cwd() {
+    let r = flags_from_vec(svec!["deno", "test", "--watch", "./"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Install(InstallFlags {
-          kind: InstallKind::Global(InstallFlagsGlobal {
-            name: Some("file_server".to_string()),
-            module_url: "jsr:@std/http/file-server".to_string(),
-            args: svec!["foo", "bar"],
-            root: Some("/foo".to_string()),
-            force: true,
-          }),
-          global: false,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          shuffle: None,
+          files: FileFlags {
+            include: vec!["./".to_string()],
+            ignore: vec![],
+          },
+          concurrent_jobs: None,
+          trace_leaks: false,
+          coverage_dir: None,
+          clean: false,
+          watch: Some(Default::default()),
+          reporter: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
         }),
-        import_map_path: Some("import_map.json".to_string()),
-        no_remote: true,
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
-        type_check_mode: TypeCheckMode::None,
-        reload: true,
-        lock: Some(String::from("lock.json")),
-        lock_write: true,
-        ca_data: Some(CaData::File("example.crt".to_string())),
-        cached_only: true,
-        v8_flags: svec!["--help", "--random-seed=1"],
-        seed: Some(1),
-        inspect: Some("127.0.0.1:9229".parse().unwrap()),
-        unsafely_ignore_certificate_errors: Some(vec![]),
         permissions: PermissionFlags {
-          allow_net: Some(vec![]),
-          allow_read: Some(vec![]),
+          no_prompt: true,
           ..Default::default()
         },
-        env_file: Some(".example.env".to_owned()),
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn uninstall() {
-    let r = flags_from_vec(svec!["deno", "uninstall", "file_server"]);
+  fn test_watch_with_no_clear_screen() {
+    let r =
+      flags_from_vec(svec!["deno", "test", "--watch", "--no-clear-screen"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Uninstall(UninstallFlags {
-          kind: UninstallKind::Global(UninstallFlagsGlobal {
-            name: "file_server".to_string(),
-            root: None,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          shuffle: None,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          concurrent_jobs: None,
+          trace_leaks: false,
+          coverage_dir: None,
+          clean: false,
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            no_clear_screen: true,
+            exclude: vec![],
+            paths: vec![],
+            exit_on_error: false,
+            poll_interval: None,
           }),
-          global: false,
+          reporter: Default::default(),
+          junit_path: None,
+          hide_stacktraces: false,
         }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "uninstall", "-g", "file_server"]);
+  #[test]
+  fn test_watch_with_paths() {
+    let r = flags_from_vec(svec!("deno", "test", "--watch=foo"));
+
+    let flags = r.unwrap();
     assert_eq!(
-      r.unwrap(),
+      flags,
       Flags {
-        subcommand: DenoSubcommand::Uninstall(UninstallFlags {
-          kind: UninstallKind::Global(UninstallFlagsGlobal {
-            name: "file_server".to_string(),
-            root: None,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![String::from("foo")],
+            no_clear_screen: false,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
           }),
-          global: true,
+          ..TestFlags::default()
         }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn uninstall_with_help_flag() {
-    let r = flags_from_vec(svec!["deno", "uninstall", "--help"]);
-    assert!(r.is_ok());
-  }
+    let r = flags_from_vec(svec!["deno", "test", "--watch=foo,bar"]);
 
-  #[test]
-  fn log_level() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--log-level=debug", "script.ts"]);
+    let flags = r.unwrap();
     assert_eq!(
-      r.unwrap(),
+      flags,
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        log_level: Some(Level::Debug),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![String::from("foo"), String::from("bar")],
+            no_clear_screen: false,
+            exclude: vec![],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn quiet() {
-    let r = flags_from_vec(svec!["deno", "-q", "script.ts"]);
+  fn test_watch_with_excluded_paths() {
+    let r =
+      flags_from_vec(svec!("deno", "test", "--watch", "--watch-exclude=foo",));
+
+    let flags = r.unwrap();
     assert_eq!(
-      r.unwrap(),
+      flags,
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: None,
-          bare: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![String::from("foo")],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          ..TestFlags::default()
         }),
-        log_level: Some(Level::Error),
-        code_cache_enabled: true,
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn completions() {
-    let r = flags_from_vec(svec!["deno", "completions", "zsh"]).unwrap();
-
-    match r.subcommand {
-      DenoSubcommand::Completions(CompletionsFlags { buf }) => {
-        assert!(!buf.is_empty())
-      }
-      _ => unreachable!(),
-    }
-  }
-
-  #[test]
-  fn run_with_args() {
-    let r = flags_from_vec(svec![
+    let r = flags_from_vec(svec!(
       "deno",
-      "run",
-      "script.ts",
-      "--allow-read",
-      "--allow-net"
-    ]);
+      "test",
+      "--watch=foo",
+      "--watch-exclude=bar",
+    ));
+    let flags = r.unwrap();
     assert_eq!(
-      r.unwrap(),
+      flags,
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        argv: svec!["--allow-read", "--allow-net"],
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![String::from("foo")],
+            no_clear_screen: false,
+            exclude: vec![String::from("bar")],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
+
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--location",
-      "https:foo",
-      "--allow-read",
-      "script.ts",
-      "--allow-net",
-      "-r",
-      "--help",
-      "--foo",
-      "bar"
+      "test",
+      "--watch",
+      "--watch-exclude=foo,bar",
     ]);
+
+    let flags = r.unwrap();
     assert_eq!(
-      r.unwrap(),
+      flags,
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        location: Some(Url::parse("https://foo/").unwrap()),
+        subcommand: DenoSubcommand::Test(TestFlags {
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![String::from("foo"), String::from("bar")],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
-          allow_read: Some(vec![]),
+          no_prompt: true,
           ..Default::default()
         },
-        argv: svec!["--allow-net", "-r", "--help", "--foo", "bar"],
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "run", "script.ts", "foo", "bar"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        argv: svec!["foo", "bar"],
-        code_cache_enabled: true,
-        ..Flags::default()
-      }
-    );
-    let r = flags_from_vec(svec!["deno", "run", "script.ts", "-"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--watch=foo,bar",
+      "--watch-exclude=baz,qux",
+    ]);
+
+    let flags = r.unwrap();
     assert_eq!(
-      r.unwrap(),
+      flags,
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        argv: svec!["-"],
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            reevaluate: false,
+            paths: vec![String::from("foo"), String::from("bar")],
+            no_clear_screen: false,
+            exclude: vec![String::from("baz"), String::from("qux"),],
+            exit_on_error: false,
+            poll_interval: None,
+          }),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
+  }
 
-    let r =
-      flags_from_vec(svec!["deno", "run", "script.ts", "-", "foo", "bar"]);
+  #[test]
+  fn test_coverage_default_dir() {
+    let r = flags_from_vec(svec!["deno", "test", "--coverage"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        argv: svec!["-", "foo", "bar"],
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          coverage_dir: Some("coverage".to_string()),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn no_check() {
-    let r = flags_from_vec(svec!["deno", "--no-check", "script.ts"]);
+  fn test_hide_stacktraces() {
+    let r = flags_from_vec(svec!["deno", "test", "--hide-stacktraces"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: None,
-          bare: true,
+        subcommand: DenoSubcommand::Test(TestFlags {
+          hide_stacktraces: true,
+          ..TestFlags::default()
         }),
-        type_check_mode: TypeCheckMode::None,
-        code_cache_enabled: true,
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn no_check_remote() {
-    let r =
-      flags_from_vec(svec!["deno", "run", "--no-check=remote", "script.ts"]);
+  fn bundle_with_cafile() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bundle",
+      "--cert",
+      "example.crt",
+      "source.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: None,
+          watch: Default::default(),
+        }),
         type_check_mode: TypeCheckMode::Local,
-        code_cache_enabled: true,
+        ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn repl_with_unsafely_ignore_certificate_errors() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "repl",
-      "--eval",
-      "console.log('hello');",
-      "--unsafely-ignore-certificate-errors"
-    ]);
+  fn upgrade_with_ca_file() {
+    let r = flags_from_vec(svec!["deno", "upgrade", "--cert", "example.crt"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Repl(ReplFlags {
-          eval_files: None,
-          eval: Some("console.log('hello');".to_string()),
-          is_default_command: false,
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: false,
+          dry_run: false,
+          canary: false,
+          release_candidate: false,
+          version: None,
+          output: None,
+          version_or_hash_or_channel: None,
         }),
-        unsafely_ignore_certificate_errors: Some(vec![]),
-        type_check_mode: TypeCheckMode::None,
+        ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn run_with_unsafely_ignore_certificate_errors() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--unsafely-ignore-certificate-errors",
-      "script.ts"
-    ]);
+  fn upgrade_release_candidate() {
+    let r = flags_from_vec(svec!["deno", "upgrade", "--rc"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        unsafely_ignore_certificate_errors: Some(vec![]),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: false,
+          dry_run: false,
+          canary: false,
+          release_candidate: true,
+          version: None,
+          output: None,
+          version_or_hash_or_channel: None,
+        }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "upgrade", "--rc", "--canary"]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "upgrade", "--rc", "--version"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn run_with_unsafely_treat_insecure_origin_as_secure_with_ipv6_address() {
+  fn cache_with_cafile() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--unsafely-ignore-certificate-errors=deno.land,localhost,[::],127.0.0.1,[::1],1.2.3.4",
-      "script.ts"
+      "cache",
+      "--cert",
+      "example.crt",
+      "script.ts",
+      "script_two.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        unsafely_ignore_certificate_errors: Some(svec![
-          "deno.land",
-          "localhost",
-          "[::]",
-          "127.0.0.1",
-          "[::1]",
-          "1.2.3.4"
-        ]),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["script.ts", "script_two.ts"],
+        }),
+        ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn repl_with_unsafely_treat_insecure_origin_as_secure_with_ipv6_address() {
+  fn info_with_cafile() {
     let r = flags_from_vec(svec![
       "deno",
-      "repl",
-      "--unsafely-ignore-certificate-errors=deno.land,localhost,[::],127.0.0.1,[::1],1.2.3.4"]);
+      "info",
+      "--cert",
+      "example.crt",
+      "https://example.com"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Repl(ReplFlags {
-          eval_files: None,
-          eval: None,
-          is_default_command: false,
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("https://example.com".to_string()),
         }),
-        unsafely_ignore_certificate_errors: Some(svec![
-          "deno.land",
-          "localhost",
-          "[::]",
-          "127.0.0.1",
-          "[::1]",
-          "1.2.3.4"
-        ]),
-        type_check_mode: TypeCheckMode::None,
+        ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn no_remote() {
-    let r = flags_from_vec(svec!["deno", "run", "--no-remote", "script.ts"]);
+  fn doc() {
+    let r = flags_from_vec(svec!["deno", "doc", "--json", "path/to/module.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        no_remote: true,
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          json: true,
+          html: None,
+          lint: false,
+          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+          filter: None,
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn no_npm() {
-    let r = flags_from_vec(svec!["deno", "run", "--no-npm", "script.ts"]);
+    let r = flags_from_vec(svec!["deno", "doc", "--html", "path/to/module.ts"]);
+    assert!(r.is_ok());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--html",
+      "--name=My library",
+      "path/to/module.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        no_npm: true,
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          json: false,
+          lint: false,
+          html: Some(DocHtmlFlag {
+            name: Some("My library".to_string()),
+            category_docs_path: None,
+            symbol_redirect_map_path: None,
+            default_symbol_map_path: None,
+            strip_trailing_html: false,
+            output: String::from("./docs/"),
+          }),
+          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+          filter: None,
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn local_npm() {
-    let r = flags_from_vec(svec!["deno", "--node-modules-dir", "script.ts"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--html",
+      "--name=My library",
+      "--lint",
+      "--output=./foo",
+      "path/to/module.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: None,
-          bare: true,
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          json: false,
+          html: Some(DocHtmlFlag {
+            name: Some("My library".to_string()),
+            category_docs_path: None,
+            symbol_redirect_map_path: None,
+            default_symbol_map_path: None,
+            strip_trailing_html: false,
+            output: String::from("./foo"),
+          }),
+          lint: true,
+          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+          filter: None,
         }),
-        node_modules_dir: Some(true),
-        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
+    let r =
+      flags_from_vec(svec!["deno", "doc", "--html", "--name=My library",]);
+    assert!(r.is_err());
+
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--node-modules-dir=false",
-      "script.ts"
+      "doc",
+      "--filter",
+      "SomeClass.someField",
+      "path/to/module.ts",
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        node_modules_dir: Some(false),
-        code_cache_enabled: true,
-        ..Flags::default()
-      }
-    );
-  }
-
-  #[test]
-  fn vendor_flag() {
-    let r = flags_from_vec(svec!["deno", "run", "--vendor", "script.ts"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        vendor: Some(true),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          json: false,
+          html: None,
+          lint: false,
+          source_files: DocSourceFileFlag::Paths(vec![
+            "path/to/module.ts".to_string()
+          ]),
+          filter: Some("SomeClass.someField".to_string()),
+        }),
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "run", "--vendor=false", "script.ts"]);
+    let r = flags_from_vec(svec!["deno", "doc"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        vendor: Some(false),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          json: false,
+          html: None,
+          lint: false,
+          source_files: Default::default(),
+          filter: None,
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn cached_only() {
-    let r = flags_from_vec(svec!["deno", "run", "--cached-only", "script.ts"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--filter",
+      "Deno.Listener",
+      "--builtin"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        cached_only: true,
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          lint: false,
+          json: false,
+          html: None,
+          source_files: DocSourceFileFlag::Builtin,
+          filter: Some("Deno.Listener".to_string()),
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn allow_net_allowlist_with_ports() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--allow-net=deno.land,:8000,:4545",
-      "script.ts"
+      "doc",
+      "--no-npm",
+      "--no-remote",
+      "--private",
+      "path/to/module.js"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        permissions: PermissionFlags {
-          allow_net: Some(svec![
-            "deno.land",
-            "0.0.0.0:8000",
-            "127.0.0.1:8000",
-            "localhost:8000",
-            "0.0.0.0:4545",
-            "127.0.0.1:4545",
-            "localhost:4545"
-          ]),
-          ..Default::default()
-        },
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: true,
+          lint: false,
+          json: false,
+          html: None,
+          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.js"]),
+          filter: None,
+        }),
+        no_npm: true,
+        no_remote: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn deny_net_denylist_with_ports() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--deny-net=deno.land,:8000,:4545",
-      "script.ts"
+      "doc",
+      "path/to/module.js",
+      "path/to/module2.js"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        permissions: PermissionFlags {
-          deny_net: Some(svec![
-            "deno.land",
-            "0.0.0.0:8000",
-            "127.0.0.1:8000",
-            "localhost:8000",
-            "0.0.0.0:4545",
-            "127.0.0.1:4545",
-            "localhost:4545"
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          lint: false,
+          json: false,
+          html: None,
+          source_files: DocSourceFileFlag::Paths(vec![
+            "path/to/module.js".to_string(),
+            "path/to/module2.js".to_string()
           ]),
-          ..Default::default()
-        },
-        code_cache_enabled: true,
+          filter: None,
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn allow_net_allowlist_with_ipv6_address() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--allow-net=deno.land,deno.land:80,[::],127.0.0.1,[::1],1.2.3.4:5678,:5678,[::1]:8080",
-      "script.ts"
+      "doc",
+      "path/to/module.js",
+      "--builtin",
+      "path/to/module2.js"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        permissions: PermissionFlags {
-          allow_net: Some(svec![
-            "deno.land",
-            "deno.land:80",
-            "[::]",
-            "127.0.0.1",
-            "[::1]",
-            "1.2.3.4:5678",
-            "0.0.0.0:5678",
-            "127.0.0.1:5678",
-            "localhost:5678",
-            "[::1]:8080"
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          json: false,
+          html: None,
+          lint: false,
+          source_files: DocSourceFileFlag::Paths(vec![
+            "path/to/module.js".to_string(),
+            "path/to/module2.js".to_string()
           ]),
-          ..Default::default()
-        },
-        code_cache_enabled: true,
+          filter: None,
+        }),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn deny_net_denylist_with_ipv6_address() {
+    let r = flags_from_vec(svec!["deno", "doc", "--lint",]);
+    assert!(r.is_err());
+
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--deny-net=deno.land,deno.land:80,[::],127.0.0.1,[::1],1.2.3.4:5678,:5678,[::1]:8080",
-      "script.ts"
+      "doc",
+      "--lint",
+      "path/to/module.js",
+      "path/to/module2.js"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        permissions: PermissionFlags {
-          deny_net: Some(svec![
-            "deno.land",
-            "deno.land:80",
-            "[::]",
-            "127.0.0.1",
-            "[::1]",
-            "1.2.3.4:5678",
-            "0.0.0.0:5678",
-            "127.0.0.1:5678",
-            "localhost:5678",
-            "[::1]:8080"
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          lint: true,
+          json: false,
+          html: None,
+          source_files: DocSourceFileFlag::Paths(vec![
+            "path/to/module.js".to_string(),
+            "path/to/module2.js".to_string()
           ]),
-          ..Default::default()
-        },
-        code_cache_enabled: true,
+          filter: None,
+        }),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn lock_write() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--lock-write",
-      "--lock=lock.json",
-      "script.ts"
-    ]);
+  fn inspect_default_host() {
+    let r = flags_from_vec(svec!["deno", "run", "--inspect", "foo.js"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
+          "foo.js".to_string(),
         )),
-        lock_write: true,
-        lock: Some(String::from("lock.json")),
+        inspect: Some("127.0.0.1:9229".parse().unwrap()),
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "--no-lock", "script.ts"]);
+  #[test]
+  fn inspect_wait() {
+    let r = flags_from_vec(svec!["deno", "--inspect-wait", "foo.js"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
+          script: "foo.js".to_string(),
           watch: None,
           bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        no_lock: true,
+        inspect_wait: Some("127.0.0.1:9229".parse().unwrap()),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -8910,910 +11857,757 @@ mod tests {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--lock",
-      "--lock-write",
-      "script.ts"
+      "--inspect-wait=127.0.0.1:3567",
+      "foo.js"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
+          "foo.js".to_string(),
         )),
-        lock_write: true,
-        lock: Some(String::from("./deno.lock")),
+        inspect_wait: Some("127.0.0.1:3567".parse().unwrap()),
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn compile() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--lock-write",
-      "--lock",
-      "lock.json",
-      "script.ts"
+      "compile",
+      "https://examples.deno.land/color-logging.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        lock_write: true,
-        lock: Some(String::from("lock.json")),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "https://examples.deno.land/color-logging.ts"
+            .to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          icon: None,
+          include: vec![]
+        }),
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "run", "--lock-write", "script.ts"]);
+  #[test]
+  fn compile_with_flags() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "compile", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--no-terminal", "--icon", "favicon.ico", "--output", "colors", "--env=.example.env", "https://examples.deno.land/color-logging.ts", "foo", "bar", "-p", "8080"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "https://examples.deno.land/color-logging.ts"
+            .to_string(),
+          output: Some(String::from("colors")),
+          args: svec!["foo", "bar", "-p", "8080"],
+          target: None,
+          no_terminal: true,
+          icon: Some(String::from("favicon.ico")),
+          include: vec![]
+        }),
+        import_map_path: Some("import_map.json".to_string()),
+        no_remote: true,
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
+        type_check_mode: TypeCheckMode::None,
+        reload: true,
+        lock: Some(String::from("lock.json")),
         lock_write: true,
-        code_cache_enabled: true,
+        ca_data: Some(CaData::File("example.crt".to_string())),
+        cached_only: true,
+        location: Some(Url::parse("https://foo/").unwrap()),
+        permissions: PermissionFlags {
+          allow_read: Some(vec![]),
+          allow_net: Some(vec![]),
+          ..Default::default()
+        },
+        unsafely_ignore_certificate_errors: Some(vec![]),
+        v8_flags: svec!["--help", "--random-seed=1"],
+        seed: Some(1),
+        env_file: Some(vec![".example.env".to_owned()]),
         ..Flags::default()
       }
     );
-
-    let r =
-      flags_from_vec(svec!["deno", "run", "--lock", "--no-lock", "script.ts"]);
-    assert!(r.is_err(),);
-
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--lock-write",
-      "--no-lock",
-      "script.ts"
-    ]);
-    assert!(r.is_err(),);
-  }
-
-  #[test]
-  fn test_no_colon_in_value_name() {
-    let app =
-      runtime_args(Command::new("test_inspect_completion_value"), true, true);
-    let inspect_args = app
-      .get_arguments()
-      .filter(|arg| arg.get_id() == "inspect")
-      .collect::<Vec<_>>();
-    // The value_name cannot have a : otherwise it breaks shell completions for zsh.
-    let value_name = "HOST_AND_PORT";
-    let arg = inspect_args
-      .iter()
-      .any(|v| v.get_value_names().unwrap() == [value_name]);
-
-    assert_eq!(arg, true);
   }
 
   #[test]
-  fn test_with_flags() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "test", "--unstable", "--no-npm", "--no-remote", "--trace-leaks", "--no-run", "--filter", "- foo", "--coverage=cov", "--clean", "--location", "https:foo", "--allow-net", "--permit-no-files", "dir1/", "dir2/", "--", "arg1", "arg2"]);
+  fn coverage() {
+    let r = flags_from_vec(svec!["deno", "coverage", "foo.json"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: true,
-          doc: false,
-          fail_fast: None,
-          filter: Some("- foo".to_string()),
-          allow_none: true,
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
           files: FileFlags {
-            include: vec!["dir1/".to_string(), "dir2/".to_string()],
+            include: vec!["foo.json".to_string()],
             ignore: vec![],
           },
-          shuffle: None,
-          concurrent_jobs: None,
-          trace_leaks: true,
-          coverage_dir: Some("cov".to_string()),
-          clean: true,
-          watch: Default::default(),
-          reporter: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          ..CoverageFlags::default()
         }),
-        unstable_config: UnstableConfig {
-          legacy_flag_enabled: true,
-          ..Default::default()
-        },
-        no_npm: true,
-        no_remote: true,
-        location: Some(Url::parse("https://foo/").unwrap()),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          allow_net: Some(vec![]),
-          ..Default::default()
-        },
-        argv: svec!["arg1", "arg2"],
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn run_with_cafile() {
+  fn coverage_with_lcov_and_out_file() {
     let r = flags_from_vec(svec![
       "deno",
-      "run",
-      "--cert",
-      "example.crt",
-      "script.ts"
+      "coverage",
+      "--lcov",
+      "--output=foo.lcov",
+      "foo.json"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        ca_data: Some(CaData::File("example.crt".to_owned())),
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Lcov,
+          output: Some(String::from("foo.lcov")),
+        }),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn run_with_enable_testing_features() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "run",
-      "--enable-testing-features-do-not-use",
-      "script.ts"
-    ]);
+  fn coverage_with_default_files() {
+    let r = flags_from_vec(svec!["deno", "coverage",]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
-        )),
-        enable_testing_features: true,
-        code_cache_enabled: true,
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          ..CoverageFlags::default()
+        }),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn test_with_concurrent_jobs() {
-    let r = flags_from_vec(svec!["deno", "test", "--jobs=4"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: false,
-          reporter: Default::default(),
-          doc: false,
-          fail_fast: None,
-          filter: None,
-          allow_none: false,
-          shuffle: None,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          concurrent_jobs: Some(NonZeroUsize::new(4).unwrap()),
-          trace_leaks: false,
-          coverage_dir: None,
-          clean: false,
-          watch: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
-        }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        ..Flags::default()
-      }
-    );
-
-    let r = flags_from_vec(svec!["deno", "test", "--jobs=0"]);
+  fn location_with_bad_scheme() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "run", "--location", "foo:", "mod.ts"]);
+    assert!(r.is_err());
+    assert!(r
+      .unwrap_err()
+      .to_string()
+      .contains("Expected protocol \"http\" or \"https\""));
+  }
+
+  #[test]
+  fn test_config_path_args() {
+    let flags = flags_from_vec(svec!["deno", "run", "foo.js"]).unwrap();
+    let cwd = std::env::current_dir().unwrap();
+
+    assert_eq!(flags.config_path_args(&cwd), Some(vec![cwd.clone()]));
+
+    let flags = flags_from_vec(svec!["deno", "run", "sub_dir/foo.js"]).unwrap();
+    let cwd = std::env::current_dir().unwrap();
+    assert_eq!(
+      flags.config_path_args(&cwd),
+      Some(vec![cwd.join("sub_dir").clone()])
+    );
+
+    let flags =
+      flags_from_vec(svec!["deno", "https://example.com/foo.js"]).unwrap();
+    assert_eq!(flags.config_path_args(&cwd), None);
+
+    let flags =
+      flags_from_vec(svec!["deno", "lint", "dir/a/a.js", "dir/b/b.js"])
+        .unwrap();
+    assert_eq!(
+      flags.config_path_args(&cwd),
+      Some(vec![cwd.join("dir/a/a.js"), cwd.join("dir/b/b.js")])
+    );
+
+    let flags = flags_from_vec(svec!["deno", "lint"]).unwrap();
+    assert_eq!(flags.config_path_args(&cwd), Some(vec![cwd.clone()]));
+
+    let flags = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "dir/a/a.js",
+      "dir/a/a2.js",
+      "dir/b.js"
+    ])
+    .unwrap();
+    assert_eq!(
+      flags.config_path_args(&cwd),
+      Some(vec![
+        cwd.join("dir/a/a.js"),
+        cwd.join("dir/a/a2.js"),
+        cwd.join("dir/b.js")
+      ])
+    );
+  }
+
+  #[test]
+  fn test_no_clear_watch_flag_without_watch_flag() {
+    let r = flags_from_vec(svec!["deno", "run", "--no-clear-screen", "foo.js"]);
     assert!(r.is_err());
+    let error_message = r.unwrap_err().to_string();
+    assert!(&error_message
+      .contains("error: the following required arguments were not provided:"));
+    assert!(&error_message.contains("--watch[=<FILES>...]"));
   }
 
   #[test]
-  fn test_with_fail_fast() {
-    let r = flags_from_vec(svec!["deno", "test", "--fail-fast=3"]);
+  fn vendor_minimal() {
+    let r = flags_from_vec(svec!["deno", "vendor", "mod.ts",]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: false,
-          doc: false,
-          fail_fast: Some(NonZeroUsize::new(3).unwrap()),
-          filter: None,
-          allow_none: false,
-          shuffle: None,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          concurrent_jobs: None,
-          trace_leaks: false,
-          coverage_dir: None,
-          clean: false,
-          watch: Default::default(),
-          reporter: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
+        subcommand: DenoSubcommand::Vendor(VendorFlags {
+          specifiers: svec!["mod.ts"],
+          force: false,
+          output_path: None,
         }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
         ..Flags::default()
       }
     );
-
-    let r = flags_from_vec(svec!["deno", "test", "--fail-fast=0"]);
-    assert!(r.is_err());
   }
 
   #[test]
-  fn test_with_enable_testing_features() {
+  fn vendor_all() {
     let r = flags_from_vec(svec![
       "deno",
-      "test",
-      "--enable-testing-features-do-not-use"
+      "vendor",
+      "--config",
+      "deno.json",
+      "--import-map",
+      "import_map.json",
+      "--lock",
+      "lock.json",
+      "--force",
+      "--output",
+      "out_dir",
+      "--reload",
+      "mod.ts",
+      "deps.test.ts",
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: false,
-          doc: false,
-          fail_fast: None,
-          filter: None,
-          allow_none: false,
-          shuffle: None,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          concurrent_jobs: None,
-          trace_leaks: false,
-          coverage_dir: None,
-          clean: false,
-          watch: Default::default(),
-          reporter: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
+        subcommand: DenoSubcommand::Vendor(VendorFlags {
+          specifiers: svec!["mod.ts", "deps.test.ts"],
+          force: true,
+          output_path: Some(String::from("out_dir")),
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
-        enable_testing_features: true,
+        config_flag: ConfigFlag::Path("deno.json".to_owned()),
+        import_map_path: Some("import_map.json".to_string()),
+        lock: Some(String::from("lock.json")),
+        reload: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn test_reporter() {
-    let r = flags_from_vec(svec!["deno", "test", "--reporter=pretty"]);
+  fn task_subcommand() {
+    let r = flags_from_vec(svec!["deno", "task", "build", "hello", "world",]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          reporter: TestReporterConfig::Pretty,
-          ..Default::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
+        argv: svec!["hello", "world"],
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "test", "--reporter=dot"]);
+    let r = flags_from_vec(svec!["deno", "task", "build"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          reporter: TestReporterConfig::Dot,
-          ..Default::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
-        log_level: Some(Level::Error),
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "test", "--reporter=junit"]);
+    let r = flags_from_vec(svec!["deno", "task", "--cwd", "foo", "build"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          reporter: TestReporterConfig::Junit,
-          ..Default::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: Some("foo".to_string()),
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "test", "--reporter=tap"]);
+  #[test]
+  fn task_subcommand_double_hyphen() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "task",
+      "-c",
+      "deno.json",
+      "build",
+      "--",
+      "hello",
+      "world",
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          reporter: TestReporterConfig::Tap,
-          ..Default::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
-        log_level: Some(Level::Error),
+        argv: svec!["--", "hello", "world"],
+        config_flag: ConfigFlag::Path("deno.json".to_owned()),
         ..Flags::default()
       }
     );
 
     let r = flags_from_vec(svec![
-      "deno",
-      "test",
-      "--reporter=dot",
-      "--junit-path=report.xml"
+      "deno", "task", "--cwd", "foo", "build", "--", "hello", "world"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          reporter: TestReporterConfig::Dot,
-          junit_path: Some("report.xml".to_string()),
-          ..Default::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: Some("foo".to_string()),
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
-        log_level: Some(Level::Error),
+        argv: svec!["--", "hello", "world"],
         ..Flags::default()
       }
     );
-
-    let r = flags_from_vec(svec!["deno", "test", "--junit-path"]);
-    assert!(r.is_err());
   }
 
   #[test]
-  fn test_shuffle() {
-    let r = flags_from_vec(svec!["deno", "test", "--shuffle=1"]);
+  fn task_subcommand_double_hyphen_only() {
+    // edge case, but it should forward
+    let r = flags_from_vec(svec!["deno", "task", "build", "--"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: false,
-          doc: false,
-          fail_fast: None,
-          filter: None,
-          allow_none: false,
-          shuffle: Some(1),
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          concurrent_jobs: None,
-          trace_leaks: false,
-          coverage_dir: None,
-          clean: false,
-          watch: Default::default(),
-          reporter: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
-        }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          is_run: false,
+        }),
+        argv: svec!["--"],
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn test_watch() {
-    let r = flags_from_vec(svec!["deno", "test", "--watch"]);
+  fn task_following_arg() {
+    let r = flags_from_vec(svec!["deno", "task", "build", "-1", "--test"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: false,
-          doc: false,
-          fail_fast: None,
-          filter: None,
-          allow_none: false,
-          shuffle: None,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          concurrent_jobs: None,
-          trace_leaks: false,
-          coverage_dir: None,
-          clean: false,
-          watch: Some(Default::default()),
-          reporter: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
+        argv: svec!["-1", "--test"],
         ..Flags::default()
       }
     );
   }
+
   #[test]
-  fn test_watch_explicit_cwd() {
-    let r = flags_from_vec(svec!["deno", "test", "--watch", "./"]);
+  fn task_following_double_hyphen_arg() {
+    let r = flags_from_vec(svec!["deno", "task", "build", "--test"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: false,
-          doc: false,
-          fail_fast: None,
-          filter: None,
-          allow_none: false,
-          shuffle: None,
-          files: FileFlags {
-            include: vec!["./".to_string()],
-            ignore: vec![],
-          },
-          concurrent_jobs: None,
-          trace_leaks: false,
-          coverage_dir: None,
-          clean: false,
-          watch: Some(Default::default()),
-          reporter: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
+        argv: svec!["--test"],
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn test_watch_with_no_clear_screen() {
-    let r =
-      flags_from_vec(svec!["deno", "test", "--watch", "--no-clear-screen"]);
+  fn task_with_global_flags() {
+    // can fail if the custom parser in task_parse() starts at the wrong index
+    let r = flags_from_vec(svec!["deno", "--quiet", "task", "build"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          no_run: false,
-          doc: false,
-          fail_fast: None,
-          filter: None,
-          allow_none: false,
-          shuffle: None,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          concurrent_jobs: None,
-          trace_leaks: false,
-          coverage_dir: None,
-          clean: false,
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            no_clear_screen: true,
-            exclude: vec![],
-            paths: vec![],
-          }),
-          reporter: Default::default(),
-          junit_path: None,
-          hide_stacktraces: false,
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          is_run: false,
         }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
+        log_level: Some(log::Level::Error),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn test_watch_with_paths() {
-    let r = flags_from_vec(svec!("deno", "test", "--watch=foo"));
-
-    let flags = r.unwrap();
+  fn task_subcommand_empty() {
+    let r = flags_from_vec(svec!["deno", "task"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![String::from("foo")],
-            no_clear_screen: false,
-            exclude: vec![],
-          }),
-          ..TestFlags::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: None,
+          is_run: false,
         }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "test", "--watch=foo,bar"]);
-
-    let flags = r.unwrap();
+  #[test]
+  fn task_subcommand_config() {
+    let r = flags_from_vec(svec!["deno", "task", "--config", "deno.jsonc"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![String::from("foo"), String::from("bar")],
-            no_clear_screen: false,
-            exclude: vec![],
-          }),
-          ..TestFlags::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: None,
+          is_run: false,
         }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
+        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn test_watch_with_excluded_paths() {
-    let r =
-      flags_from_vec(svec!("deno", "test", "--watch", "--watch-exclude=foo",));
-
-    let flags = r.unwrap();
+  fn task_subcommand_config_short() {
+    let r = flags_from_vec(svec!["deno", "task", "-c", "deno.jsonc"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![],
-            no_clear_screen: false,
-            exclude: vec![String::from("foo")],
-          }),
-          ..TestFlags::default()
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: None,
+          is_run: false,
         }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
+        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!(
-      "deno",
-      "test",
-      "--watch=foo",
-      "--watch-exclude=bar",
-    ));
-    let flags = r.unwrap();
+  #[test]
+  fn task_subcommand_noconfig_invalid() {
+    let r = flags_from_vec(svec!["deno", "task", "--no-config"]);
     assert_eq!(
-      flags,
-      Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![String::from("foo")],
-            no_clear_screen: false,
-            exclude: vec![String::from("bar")],
-          }),
-          ..TestFlags::default()
-        }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        ..Flags::default()
-      }
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::UnknownArgument
     );
+  }
 
+  #[test]
+  fn bench_with_flags() {
     let r = flags_from_vec(svec![
       "deno",
-      "test",
-      "--watch",
-      "--watch-exclude=foo,bar",
+      "bench",
+      "--json",
+      "--unstable",
+      "--no-npm",
+      "--no-remote",
+      "--no-run",
+      "--filter",
+      "- foo",
+      "--location",
+      "https:foo",
+      "--allow-net",
+      "dir1/",
+      "dir2/",
+      "--",
+      "arg1",
+      "arg2"
     ]);
-
-    let flags = r.unwrap();
     assert_eq!(
-      flags,
-      Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![],
-            no_clear_screen: false,
-            exclude: vec![String::from("foo"), String::from("bar")],
-          }),
-          ..TestFlags::default()
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bench(BenchFlags {
+          filter: Some("- foo".to_string()),
+          json: true,
+          no_run: true,
+          files: FileFlags {
+            include: vec!["dir1/".to_string(), "dir2/".to_string()],
+            ignore: vec![],
+          },
+          watch: Default::default(),
         }),
+        unstable_config: UnstableConfig {
+          legacy_flag_enabled: true,
+          ..Default::default()
+        },
+        no_npm: true,
+        no_remote: true,
         type_check_mode: TypeCheckMode::Local,
+        location: Some(Url::parse("https://foo/").unwrap()),
         permissions: PermissionFlags {
+          allow_net: Some(vec![]),
           no_prompt: true,
           ..Default::default()
         },
+        argv: svec!["arg1", "arg2"],
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "test",
-      "--watch=foo,bar",
-      "--watch-exclude=baz,qux",
-    ]);
-
-    let flags = r.unwrap();
+  #[test]
+  fn bench_watch() {
+    let r = flags_from_vec(svec!["deno", "bench", "--watch"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          watch: Some(WatchFlagsWithPaths {
-            hmr: false,
-            paths: vec![String::from("foo"), String::from("bar")],
-            no_clear_screen: false,
-            exclude: vec![String::from("baz"), String::from("qux"),],
-          }),
-          ..TestFlags::default()
+        subcommand: DenoSubcommand::Bench(BenchFlags {
+          filter: None,
+          json: false,
+          no_run: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          watch: Some(Default::default()),
         }),
-        type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
           no_prompt: true,
           ..Default::default()
         },
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn test_coverage_default_dir() {
-    let r = flags_from_vec(svec!["deno", "test", "--coverage"]);
+  fn run_with_check() {
+    let r = flags_from_vec(svec!["deno", "run", "--check", "script.ts",]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          coverage_dir: Some("coverage".to_string()),
-          ..TestFlags::default()
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
         type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn test_hide_stacktraces() {
-    let r = flags_from_vec(svec!["deno", "test", "--hide-stacktraces"]);
+    let r = flags_from_vec(svec!["deno", "run", "--check=all", "script.ts",]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Test(TestFlags {
-          hide_stacktraces: true,
-          ..TestFlags::default()
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        type_check_mode: TypeCheckMode::All,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "--check=foo", "script.ts",]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        type_check_mode: TypeCheckMode::Local,
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
+        type_check_mode: TypeCheckMode::None,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn bundle_with_cafile() {
     let r = flags_from_vec(svec![
       "deno",
-      "bundle",
-      "--cert",
-      "example.crt",
-      "source.ts"
+      "run",
+      "--no-check",
+      "--check",
+      "script.ts",
     ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn no_config() {
+    let r = flags_from_vec(svec!["deno", "run", "--no-config", "script.ts",]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bundle(BundleFlags {
-          source_file: "source.ts".to_string(),
-          out_file: None,
-          watch: Default::default(),
-        }),
-        type_check_mode: TypeCheckMode::Local,
-        ca_data: Some(CaData::File("example.crt".to_owned())),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        config_flag: ConfigFlag::Disabled,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--config",
+      "deno.json",
+      "--no-config",
+      "script.ts",
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn upgrade_with_ca_file() {
-    let r = flags_from_vec(svec!["deno", "upgrade", "--cert", "example.crt"]);
+  fn init() {
+    let r = flags_from_vec(svec!["deno", "init"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
-          force: false,
-          dry_run: false,
-          canary: false,
-          release_candidate: false,
-          version: None,
-          output: None,
-          version_or_hash_or_channel: None,
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: None,
+          lib: false,
+          serve: false,
         }),
-        ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn upgrade_release_candidate() {
-    let r = flags_from_vec(svec!["deno", "upgrade", "--rc"]);
+    let r = flags_from_vec(svec!["deno", "init", "foo"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
-          force: false,
-          dry_run: false,
-          canary: false,
-          release_candidate: true,
-          version: None,
-          output: None,
-          version_or_hash_or_channel: None,
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: Some(String::from("foo")),
+          lib: false,
+          serve: false,
         }),
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "upgrade", "--rc", "--canary"]);
-    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "init", "--quiet"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: None,
+          lib: false,
+          serve: false,
+        }),
+        log_level: Some(Level::Error),
+        ..Flags::default()
+      }
+    );
 
-    let r = flags_from_vec(svec!["deno", "upgrade", "--rc", "--version"]);
-    assert!(r.is_err());
-  }
+    let r = flags_from_vec(svec!["deno", "init", "--lib"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: None,
+          lib: true,
+          serve: false,
+        }),
+        ..Flags::default()
+      }
+    );
 
-  #[test]
-  fn cache_with_cafile() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "cache",
-      "--cert",
-      "example.crt",
-      "script.ts",
-      "script_two.ts"
-    ]);
+    let r = flags_from_vec(svec!["deno", "init", "--serve"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Cache(CacheFlags {
-          files: svec!["script.ts", "script_two.ts"],
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: None,
+          lib: false,
+          serve: true,
         }),
-        ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn info_with_cafile() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "info",
-      "--cert",
-      "example.crt",
-      "https://example.com"
-    ]);
+    let r = flags_from_vec(svec!["deno", "init", "foo", "--lib"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Info(InfoFlags {
-          json: false,
-          file: Some("https://example.com".to_string()),
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: Some(String::from("foo")),
+          lib: true,
+          serve: false,
         }),
-        ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn doc() {
-    let r = flags_from_vec(svec!["deno", "doc", "--json", "path/to/module.ts"]);
+  fn jupyter() {
+    let r = flags_from_vec(svec!["deno", "jupyter"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          json: true,
-          html: None,
-          lint: false,
-          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
-          filter: None,
+        subcommand: DenoSubcommand::Jupyter(JupyterFlags {
+          install: false,
+          kernel: false,
+          conn_file: None,
         }),
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "doc", "--html", "path/to/module.ts"]);
-    assert!(r.is_ok());
-
-    let r = flags_from_vec(svec![
-      "deno",
-      "doc",
-      "--html",
-      "--name=My library",
-      "path/to/module.ts"
-    ]);
+    let r = flags_from_vec(svec!["deno", "jupyter", "--install"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          json: false,
-          lint: false,
-          html: Some(DocHtmlFlag {
-            name: Some("My library".to_string()),
-            category_docs_path: None,
-            symbol_redirect_map_path: None,
-            default_symbol_map_path: None,
-            strip_trailing_html: false,
-            output: String::from("./docs/"),
-          }),
-          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
-          filter: None,
+        subcommand: DenoSubcommand::Jupyter(JupyterFlags {
+          install: true,
+          kernel: false,
+          conn_file: None,
         }),
         ..Flags::default()
       }
@@ -9821,214 +12615,223 @@ mod tests {
 
     let r = flags_from_vec(svec![
       "deno",
-      "doc",
-      "--html",
-      "--name=My library",
-      "--lint",
-      "--output=./foo",
-      "path/to/module.ts"
+      "jupyter",
+      "--kernel",
+      "--conn",
+      "path/to/conn/file"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          json: false,
-          html: Some(DocHtmlFlag {
-            name: Some("My library".to_string()),
-            category_docs_path: None,
-            symbol_redirect_map_path: None,
-            default_symbol_map_path: None,
-            strip_trailing_html: false,
-            output: String::from("./foo"),
-          }),
-          lint: true,
-          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
-          filter: None,
+        subcommand: DenoSubcommand::Jupyter(JupyterFlags {
+          install: false,
+          kernel: true,
+          conn_file: Some(String::from("path/to/conn/file")),
         }),
         ..Flags::default()
       }
     );
 
-    let r =
-      flags_from_vec(svec!["deno", "doc", "--html", "--name=My library",]);
-    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "jupyter",
+      "--install",
+      "--conn",
+      "path/to/conn/file"
+    ]);
+    r.unwrap_err();
+    let r = flags_from_vec(svec!["deno", "jupyter", "--kernel",]);
+    r.unwrap_err();
+    let r = flags_from_vec(svec!["deno", "jupyter", "--install", "--kernel",]);
+    r.unwrap_err();
+  }
 
+  #[test]
+  fn publish_args() {
     let r = flags_from_vec(svec![
       "deno",
-      "doc",
-      "--filter",
-      "SomeClass.someField",
-      "path/to/module.ts",
+      "publish",
+      "--no-provenance",
+      "--dry-run",
+      "--allow-slow-types",
+      "--allow-dirty",
+      "--token=asdf",
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          json: false,
-          html: None,
-          lint: false,
-          source_files: DocSourceFileFlag::Paths(vec![
-            "path/to/module.ts".to_string()
-          ]),
-          filter: Some("SomeClass.someField".to_string()),
+        subcommand: DenoSubcommand::Publish(PublishFlags {
+          token: Some("asdf".to_string()),
+          dry_run: true,
+          allow_slow_types: true,
+          allow_dirty: true,
+          no_provenance: true,
         }),
+        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "doc"]);
+  #[test]
+  fn add_subcommand() {
+    let r = flags_from_vec(svec!["deno", "add"]);
+    r.unwrap_err();
+
+    let r = flags_from_vec(svec!["deno", "add", "@david/which"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          json: false,
-          html: None,
-          lint: false,
-          source_files: Default::default(),
-          filter: None,
+        subcommand: DenoSubcommand::Add(AddFlags {
+          packages: svec!["@david/which"],
         }),
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "doc",
-      "--filter",
-      "Deno.Listener",
-      "--builtin"
-    ]);
+    let r = flags_from_vec(svec!["deno", "add", "@david/which", "@luca/hello"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          lint: false,
-          json: false,
-          html: None,
-          source_files: DocSourceFileFlag::Builtin,
-          filter: Some("Deno.Listener".to_string()),
+        subcommand: DenoSubcommand::Add(AddFlags {
+          packages: svec!["@david/which", "@luca/hello"],
         }),
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "doc",
-      "--no-npm",
-      "--no-remote",
-      "--private",
-      "path/to/module.js"
-    ]);
+  #[test]
+  fn remove_subcommand() {
+    let r = flags_from_vec(svec!["deno", "remove"]);
+    r.unwrap_err();
+
+    let r = flags_from_vec(svec!["deno", "remove", "@david/which"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: true,
-          lint: false,
-          json: false,
-          html: None,
-          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.js"]),
-          filter: None,
+        subcommand: DenoSubcommand::Remove(RemoveFlags {
+          packages: svec!["@david/which"],
         }),
-        no_npm: true,
-        no_remote: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec![
-      "deno",
-      "doc",
-      "path/to/module.js",
-      "path/to/module2.js"
-    ]);
+    let r =
+      flags_from_vec(svec!["deno", "remove", "@david/which", "@luca/hello"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          lint: false,
-          json: false,
-          html: None,
-          source_files: DocSourceFileFlag::Paths(vec![
-            "path/to/module.js".to_string(),
-            "path/to/module2.js".to_string()
-          ]),
-          filter: None,
+        subcommand: DenoSubcommand::Remove(RemoveFlags {
+          packages: svec!["@david/which", "@luca/hello"],
         }),
         ..Flags::default()
       }
     );
+  }
+
+  #[test]
+  fn run_with_frozen_lockfile() {
+    let cases = [
+      (Some("--frozen"), Some(true)),
+      (Some("--frozen=true"), Some(true)),
+      (Some("--frozen=false"), Some(false)),
+      (None, None),
+    ];
+    for (flag, frozen) in cases {
+      let mut args = svec!["deno", "run"];
+      if let Some(f) = flag {
+        args.push(f.into());
+      }
+      args.push("script.ts".into());
+      let r = flags_from_vec(args);
+      assert_eq!(
+        r.unwrap(),
+        Flags {
+          subcommand: DenoSubcommand::Run(RunFlags::new_default(
+            "script.ts".to_string(),
+          )),
+          frozen_lockfile: frozen,
+          code_cache_enabled: true,
+          ..Flags::default()
+        }
+      );
+    }
+  }
 
+  #[test]
+  fn run_with_remote_auth() {
     let r = flags_from_vec(svec![
       "deno",
-      "doc",
-      "path/to/module.js",
-      "--builtin",
-      "path/to/module2.js"
+      "run",
+      "--remote-auth=deno.land=Bearer abc123",
+      "--remote-auth=example.com:8080=Basic dXNlcjpwYXNz",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          json: false,
-          html: None,
-          lint: false,
-          source_files: DocSourceFileFlag::Paths(vec![
-            "path/to/module.js".to_string(),
-            "path/to/module2.js".to_string()
-          ]),
-          filter: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        remote_auth: svec![
+          "deno.land=Bearer abc123",
+          "example.com:8080=Basic dXNlcjpwYXNz"
+        ],
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "doc", "--lint",]);
+    let r =
+      flags_from_vec(svec!["deno", "run", "--remote-auth=deno.land", "a.ts"]);
     assert!(r.is_err());
+  }
 
+  #[test]
+  fn run_with_dynamic_import_timeout() {
     let r = flags_from_vec(svec![
       "deno",
-      "doc",
-      "--lint",
-      "path/to/module.js",
-      "path/to/module2.js"
+      "run",
+      "--dynamic-import-timeout=500",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Doc(DocFlags {
-          private: false,
-          lint: true,
-          json: false,
-          html: None,
-          source_files: DocSourceFileFlag::Paths(vec![
-            "path/to/module.js".to_string(),
-            "path/to/module2.js".to_string()
-          ]),
-          filter: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        dynamic_import_timeout: Some(500),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--dynamic-import-timeout=0",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn inspect_default_host() {
-    let r = flags_from_vec(svec!["deno", "run", "--inspect", "foo.js"]);
+  fn run_with_verify_npm_contents() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--verify-npm-contents",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "foo.js".to_string(),
+          "script.ts".to_string(),
         )),
-        inspect: Some("127.0.0.1:9229".parse().unwrap()),
+        verify_npm_contents: true,
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -10036,18 +12839,21 @@ mod tests {
   }
 
   #[test]
-  fn inspect_wait() {
-    let r = flags_from_vec(svec!["deno", "--inspect-wait", "foo.js"]);
+  fn run_with_code_cache_path() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--code-cache=cache/code.db",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "foo.js".to_string(),
-          watch: None,
-          bare: true,
-        }),
-        inspect_wait: Some("127.0.0.1:9229".parse().unwrap()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
         code_cache_enabled: true,
+        code_cache_path: Some("cache/code.db".to_string()),
         ..Flags::default()
       }
     );
@@ -10055,607 +12861,710 @@ mod tests {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--inspect-wait=127.0.0.1:3567",
-      "foo.js"
+      "--no-code-cache",
+      "--code-cache=cache/code.db",
+      "script.ts"
     ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn run_with_error_format() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--error-format=json", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "foo.js".to_string(),
+          "script.ts".to_string(),
         )),
-        inspect_wait: Some("127.0.0.1:3567".parse().unwrap()),
+        error_format: ErrorFormat::Json,
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--error-format=xml", "script.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn compile() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "compile",
-      "https://examples.deno.land/color-logging.ts"
-    ]);
+  fn run_with_color() {
+    let r = flags_from_vec(svec!["deno", "run", "--color=always", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Compile(CompileFlags {
-          source_file: "https://examples.deno.land/color-logging.ts"
-            .to_string(),
-          output: None,
-          args: vec![],
-          target: None,
-          no_terminal: false,
-          icon: None,
-          include: vec![]
-        }),
-        type_check_mode: TypeCheckMode::Local,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        color: ColorMode::Always,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "run", "--color=never", "script.ts"]);
+    assert_eq!(r.unwrap().color, ColorMode::Never);
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--color=sometimes", "script.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn compile_with_flags() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "compile", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--no-terminal", "--icon", "favicon.ico", "--output", "colors", "--env=.example.env", "https://examples.deno.land/color-logging.ts", "foo", "bar", "-p", "8080"]);
+  fn run_with_deprecation() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deprecation=error", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Compile(CompileFlags {
-          source_file: "https://examples.deno.land/color-logging.ts"
-            .to_string(),
-          output: Some(String::from("colors")),
-          args: svec!["foo", "bar", "-p", "8080"],
-          target: None,
-          no_terminal: true,
-          icon: Some(String::from("favicon.ico")),
-          include: vec![]
-        }),
-        import_map_path: Some("import_map.json".to_string()),
-        no_remote: true,
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
-        type_check_mode: TypeCheckMode::None,
-        reload: true,
-        lock: Some(String::from("lock.json")),
-        lock_write: true,
-        ca_data: Some(CaData::File("example.crt".to_string())),
-        cached_only: true,
-        location: Some(Url::parse("https://foo/").unwrap()),
-        permissions: PermissionFlags {
-          allow_read: Some(vec![]),
-          allow_net: Some(vec![]),
-          ..Default::default()
-        },
-        unsafely_ignore_certificate_errors: Some(vec![]),
-        v8_flags: svec!["--help", "--random-seed=1"],
-        seed: Some(1),
-        env_file: Some(".example.env".to_owned()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        deprecation: Some(DeprecationMode::Error),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deprecation=warn", "script.ts"]);
+    assert_eq!(r.unwrap().deprecation, Some(DeprecationMode::Warn));
+
+    let r = flags_from_vec(svec!["deno", "run", "script.ts"]);
+    assert_eq!(r.unwrap().deprecation, None);
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--deprecation=loud", "script.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn coverage() {
-    let r = flags_from_vec(svec!["deno", "coverage", "foo.json"]);
+  fn run_with_check_resource_leaks() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--check-resource-leaks",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Coverage(CoverageFlags {
-          files: FileFlags {
-            include: vec!["foo.json".to_string()],
-            ignore: vec![],
-          },
-          include: vec![r"^file:".to_string()],
-          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
-          ..CoverageFlags::default()
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        check_resource_leaks: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn coverage_with_lcov_and_out_file() {
+  fn run_with_module_http_h1_only() {
     let r = flags_from_vec(svec![
       "deno",
-      "coverage",
-      "--lcov",
-      "--output=foo.lcov",
-      "foo.json"
+      "run",
+      "--module-http-h1-only",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Coverage(CoverageFlags {
-          files: FileFlags {
-            include: vec!["foo.json".to_string()],
-            ignore: vec![],
-          },
-          include: vec![r"^file:".to_string()],
-          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
-          r#type: CoverageType::Lcov,
-          output: Some(String::from("foo.lcov")),
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        module_http1_only: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn coverage_with_default_files() {
-    let r = flags_from_vec(svec!["deno", "coverage",]);
+  fn run_with_module_fetch_concurrency() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--module-fetch-concurrency=8",
+      "--module-fetch-concurrency-per-host=2",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Coverage(CoverageFlags {
-          files: FileFlags {
-            include: vec!["coverage".to_string()],
-            ignore: vec![],
-          },
-          include: vec![r"^file:".to_string()],
-          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
-          ..CoverageFlags::default()
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        module_fetch_concurrency: NonZeroUsize::new(8),
+        module_fetch_concurrency_per_host: NonZeroUsize::new(2),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn location_with_bad_scheme() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "run", "--location", "foo:", "mod.ts"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--module-fetch-concurrency=0",
+      "script.ts"
+    ]);
     assert!(r.is_err());
-    assert!(r
-      .unwrap_err()
-      .to_string()
-      .contains("Expected protocol \"http\" or \"https\""));
   }
 
   #[test]
-  fn test_config_path_args() {
-    let flags = flags_from_vec(svec!["deno", "run", "foo.js"]).unwrap();
-    let cwd = std::env::current_dir().unwrap();
-
-    assert_eq!(flags.config_path_args(&cwd), Some(vec![cwd.clone()]));
-
-    let flags = flags_from_vec(svec!["deno", "run", "sub_dir/foo.js"]).unwrap();
-    let cwd = std::env::current_dir().unwrap();
-    assert_eq!(
-      flags.config_path_args(&cwd),
-      Some(vec![cwd.join("sub_dir").clone()])
-    );
-
-    let flags =
-      flags_from_vec(svec!["deno", "https://example.com/foo.js"]).unwrap();
-    assert_eq!(flags.config_path_args(&cwd), None);
-
-    let flags =
-      flags_from_vec(svec!["deno", "lint", "dir/a/a.js", "dir/b/b.js"])
-        .unwrap();
+  fn run_with_module_fetch_retries() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--module-fetch-retries=2",
+      "script.ts"
+    ]);
     assert_eq!(
-      flags.config_path_args(&cwd),
-      Some(vec![cwd.join("dir/a/a.js"), cwd.join("dir/b/b.js")])
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        module_fetch_retries: Some(2),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
     );
 
-    let flags = flags_from_vec(svec!["deno", "lint"]).unwrap();
-    assert_eq!(flags.config_path_args(&cwd), Some(vec![cwd.clone()]));
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--module-fetch-retries=-1",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
 
-    let flags = flags_from_vec(svec![
+  #[test]
+  fn run_with_max_redirect_bytes() {
+    let r = flags_from_vec(svec![
       "deno",
-      "fmt",
-      "dir/a/a.js",
-      "dir/a/a2.js",
-      "dir/b.js"
-    ])
-    .unwrap();
+      "run",
+      "--max-redirect-bytes=1024",
+      "script.ts"
+    ]);
     assert_eq!(
-      flags.config_path_args(&cwd),
-      Some(vec![
-        cwd.join("dir/a/a.js"),
-        cwd.join("dir/a/a2.js"),
-        cwd.join("dir/b.js")
-      ])
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        max_redirect_bytes: Some(1024),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
     );
-  }
 
-  #[test]
-  fn test_no_clear_watch_flag_without_watch_flag() {
-    let r = flags_from_vec(svec!["deno", "run", "--no-clear-screen", "foo.js"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--max-redirect-bytes=lots",
+      "script.ts"
+    ]);
     assert!(r.is_err());
-    let error_message = r.unwrap_err().to_string();
-    assert!(&error_message
-      .contains("error: the following required arguments were not provided:"));
-    assert!(&error_message.contains("--watch[=<FILES>...]"));
   }
 
   #[test]
-  fn vendor_minimal() {
-    let r = flags_from_vec(svec!["deno", "vendor", "mod.ts",]);
+  fn run_with_warm_npm() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--warm-npm=manifest.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Vendor(VendorFlags {
-          specifiers: svec!["mod.ts"],
-          force: false,
-          output_path: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        warm_npm: Some("manifest.json".to_string()),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "run", "--warm-npm=manifest.json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(String::new())),
+        warm_npm: Some("manifest.json".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn vendor_all() {
+  fn run_with_tee() {
     let r = flags_from_vec(svec![
       "deno",
-      "vendor",
-      "--config",
-      "deno.json",
-      "--import-map",
-      "import_map.json",
-      "--lock",
-      "lock.json",
-      "--force",
-      "--output",
-      "out_dir",
-      "--reload",
-      "mod.ts",
-      "deps.test.ts",
+      "run",
+      "--tee-stdout=out.log",
+      "--tee-stderr=err.log",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Vendor(VendorFlags {
-          specifiers: svec!["mod.ts", "deps.test.ts"],
-          force: true,
-          output_path: Some(String::from("out_dir")),
-        }),
-        config_flag: ConfigFlag::Path("deno.json".to_owned()),
-        import_map_path: Some("import_map.json".to_string()),
-        lock: Some(String::from("lock.json")),
-        reload: true,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        tee_stdout: Some("out.log".to_string()),
+        tee_stderr: Some("err.log".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--tee-stdout=out.log",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-hmr",
+      "--tee-stderr=err.log",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn task_subcommand() {
-    let r = flags_from_vec(svec!["deno", "task", "build", "hello", "world",]);
+  fn run_with_exit_drain() {
+    let r = flags_from_vec(svec!["deno", "run", "--exit-drain", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
-        argv: svec!["hello", "world"],
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        exit_drain: Some(5000),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "task", "build"]);
+    let r =
+      flags_from_vec(svec!["deno", "run", "--exit-drain=200", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        exit_drain: Some(200),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "task", "--cwd", "foo", "build"]);
+  #[test]
+  fn run_with_print_bin() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--print-bin",
+      "npm:@denotest/bin/cli-esm"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: Some("foo".to_string()),
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "npm:@denotest/bin/cli-esm".to_string(),
+        )),
+        print_bin: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn task_subcommand_double_hyphen() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "task",
-      "-c",
-      "deno.json",
-      "build",
-      "--",
-      "hello",
-      "world",
-    ]);
+  fn run_with_vendor_out() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--vendor-out=vendor/", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
-        argv: svec!["--", "hello", "world"],
-        config_flag: ConfigFlag::Path("deno.json".to_owned()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        vendor_out: Some("vendor/".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn run_with_abort_on_warning() {
     let r = flags_from_vec(svec![
-      "deno", "task", "--cwd", "foo", "build", "--", "hello", "world"
+      "deno",
+      "run",
+      "--abort-on-warning=deprecated",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: Some("foo".to_string()),
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
-        argv: svec!["--", "hello", "world"],
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        abort_on_warning: Some("deprecated".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--abort-on-warning=(", "script.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn task_subcommand_double_hyphen_only() {
-    // edge case, but it should forward
-    let r = flags_from_vec(svec!["deno", "task", "build", "--"]);
+  fn run_with_check_parallel() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--check",
+      "--check-parallel=2",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
-        argv: svec!["--"],
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        type_check_mode: TypeCheckMode::Local,
+        check_parallel: Some(NonZeroUsize::new(2).unwrap()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--check",
+      "--check-parallel",
+      "script.ts"
+    ]);
+    assert!(r.unwrap().check_parallel.is_some());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--check",
+      "--check-parallel=0",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "check",
+      "--check-parallel=2",
+      "a.ts",
+      "b.ts"
+    ]);
+    assert_eq!(r.unwrap().check_parallel, NonZeroUsize::new(2));
   }
 
   #[test]
-  fn task_following_arg() {
-    let r = flags_from_vec(svec!["deno", "task", "build", "-1", "--test"]);
+  fn run_with_cache_backend() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--cache-backend=http://cache.internal:8080/deno",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
-        argv: svec!["-1", "--test"],
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        cache_backend: Some("http://cache.internal:8080/deno".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn task_following_double_hyphen_arg() {
-    let r = flags_from_vec(svec!["deno", "task", "build", "--test"]);
+  fn run_with_preload() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--preload=otel.ts",
+      "--preload=npm:polyfill",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: Some("build".to_string()),
-          is_run: false,
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          bare: false,
+          preload: svec!["otel.ts", "npm:polyfill"],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        argv: svec!["--test"],
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn task_with_global_flags() {
-    // can fail if the custom parser in task_parse() starts at the wrong index
-    let r = flags_from_vec(svec!["deno", "--quiet", "task", "build"]);
+  fn run_with_cpu_prof() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--cpu-prof=out.cpuprofile", "a.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: Some("build".to_string()),
-          is_run: false,
-        }),
-        log_level: Some(log::Level::Error),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "a.ts".to_string(),
+        )),
+        cpu_prof: Some("out.cpuprofile".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let flags =
+      flags_from_vec(svec!["deno", "run", "--cpu-prof", "a.ts"]).unwrap();
+    let file_name = flags.cpu_prof.unwrap();
+    assert!(file_name.starts_with("CPU."));
+    assert!(file_name.ends_with(&format!(".{}.cpuprofile", std::process::id())));
   }
 
   #[test]
-  fn task_subcommand_empty() {
-    let r = flags_from_vec(svec!["deno", "task"]);
+  fn run_with_coverage() {
+    let r = flags_from_vec(svec!["deno", "run", "--coverage=cov", "a.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: None,
-          is_run: false,
+        subcommand: DenoSubcommand::Run(RunFlags {
+          coverage_dir: Some("cov".to_string()),
+          ..RunFlags::new_default("a.ts".to_string())
         }),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "run", "--coverage", "a.ts"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Run(RunFlags {
+        coverage_dir: Some("coverage".to_string()),
+        ..RunFlags::new_default("a.ts".to_string())
+      })
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--coverage", "--inspect", "a.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn task_subcommand_config() {
-    let r = flags_from_vec(svec!["deno", "task", "--config", "deno.jsonc"]);
+  fn run_with_force_strict() {
+    let r = flags_from_vec(svec!["deno", "run", "--force-strict", "main.cjs"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: None,
-          is_run: false,
-        }),
-        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "main.cjs".to_string(),
+        )),
+        force_strict: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn task_subcommand_config_short() {
-    let r = flags_from_vec(svec!["deno", "task", "-c", "deno.jsonc"]);
+  fn run_with_import_policy() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--import-policy=deny",
+      "--allow-import-spec=./a.ts",
+      "--allow-import-spec=npm:chalk@5",
+      "main.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Task(TaskFlags {
-          cwd: None,
-          task: None,
-          is_run: false,
-        }),
-        config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "main.ts".to_string(),
+        )),
+        import_allow_list: Some(svec!["./a.ts", "npm:chalk@5"]),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn task_subcommand_noconfig_invalid() {
-    let r = flags_from_vec(svec!["deno", "task", "--no-config"]);
-    assert_eq!(
-      r.unwrap_err().kind(),
-      clap::error::ErrorKind::UnknownArgument
-    );
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--import-policy=allow",
+      "--allow-import-spec=./a.ts",
+      "main.ts"
+    ]);
+    assert_eq!(r.unwrap().import_allow_list, None);
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-import-spec=./a.ts",
+      "main.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn bench_with_flags() {
+  fn run_with_byonm_on_stale() {
     let r = flags_from_vec(svec![
       "deno",
-      "bench",
-      "--json",
-      "--unstable",
-      "--no-npm",
-      "--no-remote",
-      "--no-run",
-      "--filter",
-      "- foo",
-      "--location",
-      "https:foo",
-      "--allow-net",
-      "dir1/",
-      "dir2/",
-      "--",
-      "arg1",
-      "arg2"
+      "run",
+      "--byonm-on-stale=auto-install",
+      "main.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bench(BenchFlags {
-          filter: Some("- foo".to_string()),
-          json: true,
-          no_run: true,
-          files: FileFlags {
-            include: vec!["dir1/".to_string(), "dir2/".to_string()],
-            ignore: vec![],
-          },
-          watch: Default::default(),
-        }),
-        unstable_config: UnstableConfig {
-          legacy_flag_enabled: true,
-          ..Default::default()
-        },
-        no_npm: true,
-        no_remote: true,
-        type_check_mode: TypeCheckMode::Local,
-        location: Some(Url::parse("https://foo/").unwrap()),
-        permissions: PermissionFlags {
-          allow_net: Some(vec![]),
-          no_prompt: true,
-          ..Default::default()
-        },
-        argv: svec!["arg1", "arg2"],
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "main.ts".to_string(),
+        )),
+        byonm_on_stale: Some(ByonmOnStale::AutoInstall),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--byonm-on-stale=ignore",
+      "main.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn bench_watch() {
-    let r = flags_from_vec(svec!["deno", "bench", "--watch"]);
+  fn run_with_then() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--then=b.ts",
+      "--then=c.ts",
+      "a.ts",
+      "arg"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Bench(BenchFlags {
-          filter: None,
-          json: false,
-          no_run: false,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
-          },
-          watch: Some(Default::default()),
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "a.ts".to_string(),
+          watch: None,
+          bare: false,
+          preload: vec![],
+          extra_scripts: svec!["b.ts", "c.ts"],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
-        permissions: PermissionFlags {
-          no_prompt: true,
-          ..Default::default()
-        },
-        type_check_mode: TypeCheckMode::Local,
+        argv: svec!["arg"],
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn run_with_check() {
-    let r = flags_from_vec(svec!["deno", "run", "--check", "script.ts",]);
+  fn run_with_print_entrypoint() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--print-entrypoint", "main.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
+          "main.ts".to_string(),
         )),
-        type_check_mode: TypeCheckMode::Local,
+        print_entrypoint: true,
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "run", "--check=all", "script.ts",]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--print-entrypoint",
+      "--print-bin",
+      "npm:@denotest/bin/cli-esm"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn run_with_tz_and_locale() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--tz=UTC",
+      "--locale=en-US",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        type_check_mode: TypeCheckMode::All,
+        tz: Some("UTC".to_string()),
+        locale: Some("en-US".to_string()),
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "--check=foo", "script.ts",]);
+  #[test]
+  fn run_with_max_graph_modules() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--max-graph-modules=100",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "script.ts".to_string(),
-          watch: None,
-          bare: true,
-        }),
-        type_check_mode: TypeCheckMode::None,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        max_graph_modules: Some(100),
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -10664,295 +13573,391 @@ mod tests {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--no-check",
-      "--check",
-      "script.ts",
+      "--max-graph-modules=lots",
+      "script.ts"
     ]);
     assert!(r.is_err());
   }
 
   #[test]
-  fn no_config() {
-    let r = flags_from_vec(svec!["deno", "run", "--no-config", "script.ts",]);
+  fn run_with_report_unused_exports() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--report-unused-exports",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string(),
         )),
-        config_flag: ConfigFlag::Disabled,
+        report_unused_exports: true,
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn run_with_config_schema_validate() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--config",
-      "deno.json",
-      "--no-config",
-      "script.ts",
+      "--config-schema-validate",
+      "script.ts"
     ]);
-    assert!(r.is_err());
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        config_schema_validate: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
-  fn init() {
-    let r = flags_from_vec(svec!["deno", "init"]);
+  fn run_with_install_scripts_detection() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--detect-install-scripts",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags {
-          dir: None,
-          lib: false,
-          serve: false,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        detect_install_scripts: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "init", "foo"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--fail-on-install-scripts",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags {
-          dir: Some(String::from("foo")),
-          lib: false,
-          serve: false,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        detect_install_scripts: true,
+        fail_on_install_scripts: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "init", "--quiet"]);
+  #[test]
+  fn run_with_npm_snapshot() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--npm-snapshot=snapshot.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags {
-          dir: None,
-          lib: false,
-          serve: false,
-        }),
-        log_level: Some(Level::Error),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        npm_snapshot: Some("snapshot.json".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "init", "--lib"]);
+  #[test]
+  fn run_with_permissions_prompt_timeout() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permissions-prompt-timeout=30",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags {
-          dir: None,
-          lib: true,
-          serve: false,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        permissions_prompt_timeout: Some(30),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "init", "--serve"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permissions-prompt-timeout=0",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn run_with_nondeterminism_trace() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--record-nondeterminism=trace.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags {
-          dir: None,
-          lib: false,
-          serve: true,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        record_nondeterminism: Some("trace.json".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "init", "foo", "--lib"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--replay-nondeterminism=trace.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags {
-          dir: Some(String::from("foo")),
-          lib: true,
-          serve: false,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        replay_nondeterminism: Some("trace.json".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--record-nondeterminism=a.json",
+      "--replay-nondeterminism=b.json",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn jupyter() {
-    let r = flags_from_vec(svec!["deno", "jupyter"]);
+  fn run_with_warmup() {
+    let r = flags_from_vec(svec!["deno", "run", "--warmup", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Jupyter(JupyterFlags {
-          install: false,
-          kernel: false,
-          conn_file: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        warmup: true,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
-    let r = flags_from_vec(svec!["deno", "jupyter", "--install"]);
+  #[test]
+  fn run_with_report_hrtime_usage() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "-A",
+      "--deny-hrtime",
+      "--report-hrtime-usage",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert!(flags.report_hrtime_usage);
+    assert!(flags.permissions.allow_all);
+    assert!(flags.permissions.deny_hrtime);
+  }
+
+  #[test]
+  fn run_with_print_config() {
+    let r = flags_from_vec(svec!["deno", "run", "--print-config", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Jupyter(JupyterFlags {
-          install: true,
-          kernel: false,
-          conn_file: None,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        print_config: Some(PrintConfigFlags {
+          json: false,
+          only: false,
         }),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
     let r = flags_from_vec(svec![
       "deno",
-      "jupyter",
-      "--kernel",
-      "--conn",
-      "path/to/conn/file"
+      "run",
+      "--print-config-only",
+      "--json",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Jupyter(JupyterFlags {
-          install: false,
-          kernel: true,
-          conn_file: Some(String::from("path/to/conn/file")),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        print_config: Some(PrintConfigFlags {
+          json: true,
+          only: true,
         }),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "run", "--json", "script.ts"]);
+    assert!(r.is_err());
     let r = flags_from_vec(svec![
       "deno",
-      "jupyter",
-      "--install",
-      "--conn",
-      "path/to/conn/file"
+      "run",
+      "--print-config",
+      "--print-config-only",
+      "script.ts"
     ]);
-    r.unwrap_err();
-    let r = flags_from_vec(svec!["deno", "jupyter", "--kernel",]);
-    r.unwrap_err();
-    let r = flags_from_vec(svec!["deno", "jupyter", "--install", "--kernel",]);
-    r.unwrap_err();
+    assert!(r.is_err());
   }
 
   #[test]
-  fn publish_args() {
-    let r = flags_from_vec(svec![
-      "deno",
-      "publish",
-      "--no-provenance",
-      "--dry-run",
-      "--allow-slow-types",
-      "--allow-dirty",
-      "--token=asdf",
-    ]);
+  fn run_with_dump_ast() {
+    let r = flags_from_vec(svec!["deno", "run", "--dump-ast", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Publish(PublishFlags {
-          token: Some("asdf".to_string()),
-          dry_run: true,
-          allow_slow_types: true,
-          allow_dirty: true,
-          no_provenance: true,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        dump_ast: Some(DumpAstFlags {
+          all: false,
+          json: false,
         }),
-        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
-
-  #[test]
-  fn add_subcommand() {
-    let r = flags_from_vec(svec!["deno", "add"]);
-    r.unwrap_err();
 
-    let r = flags_from_vec(svec!["deno", "add", "@david/which"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--dump-ast-all",
+      "--json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Add(AddFlags {
-          packages: svec!["@david/which"],
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        dump_ast: Some(DumpAstFlags {
+          all: true,
+          json: true,
         }),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "add", "@david/which", "@luca/hello"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--dump-ast",
+      "--dump-ast-all",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn run_with_dry_run() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--dry-run",
+      "--json",
+      "--allow-net",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Add(AddFlags {
-          packages: svec!["@david/which", "@luca/hello"],
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        dry_run: Some(DryRunFlags { json: true }),
+        permissions: PermissionFlags {
+          allow_net: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn remove_subcommand() {
-    let r = flags_from_vec(svec!["deno", "remove"]);
-    r.unwrap_err();
-
-    let r = flags_from_vec(svec!["deno", "remove", "@david/which"]);
+  fn run_with_vfs_overlay() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--vfs-overlay=overlay.json",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Remove(RemoveFlags {
-          packages: svec!["@david/which"],
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        vfs_overlay: Some("overlay.json".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn run_with_trace() {
     let r =
-      flags_from_vec(svec!["deno", "remove", "@david/which", "@luca/hello"]);
+      flags_from_vec(svec!["deno", "run", "--trace=trace.json", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Remove(RemoveFlags {
-          packages: svec!["@david/which", "@luca/hello"],
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        trace_file: Some("trace.json".to_string()),
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
-  #[test]
-  fn run_with_frozen_lockfile() {
-    let cases = [
-      (Some("--frozen"), Some(true)),
-      (Some("--frozen=true"), Some(true)),
-      (Some("--frozen=false"), Some(false)),
-      (None, None),
-    ];
-    for (flag, frozen) in cases {
-      let mut args = svec!["deno", "run"];
-      if let Some(f) = flag {
-        args.push(f.into());
-      }
-      args.push("script.ts".into());
-      let r = flags_from_vec(args);
-      assert_eq!(
-        r.unwrap(),
-        Flags {
-          subcommand: DenoSubcommand::Run(RunFlags::new_default(
-            "script.ts".to_string(),
-          )),
-          frozen_lockfile: frozen,
-          code_cache_enabled: true,
-          ..Flags::default()
-        }
-      );
-    }
-  }
-
   #[test]
   fn allow_scripts() {
     let cases = [
@@ -11009,6 +14014,10 @@ mod tests {
           script: "script.ts".to_string(),
           watch: None,
           bare: true,
+          preload: vec![],
+          extra_scripts: vec![],
+          skip_upgrade_check: false,
+          coverage_dir: None,
         }),
         config_flag: ConfigFlag::Disabled,
         code_cache_enabled: true,
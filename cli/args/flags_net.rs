@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use deno_core::url::Url;
+use deno_runtime::deno_permissions::split_net_byte_cap;
 use deno_runtime::deno_permissions::NetDescriptor;
 use std::net::IpAddr;
 use std::str::FromStr;
@@ -27,12 +28,15 @@ impl FromStr for BarePort {
   }
 }
 
-pub fn validator(host_and_port: &str) -> Result<String, String> {
-  if Url::parse(&format!("internal://{host_and_port}")).is_ok()
+pub fn validator(entry: &str) -> Result<String, String> {
+  let (host_and_port, _) =
+    split_net_byte_cap(entry).map_err(|err| err.to_string())?;
+  if host_and_port.starts_with("unix:")
+    || Url::parse(&format!("internal://{host_and_port}")).is_ok()
     || host_and_port.parse::<IpAddr>().is_ok()
     || host_and_port.parse::<BarePort>().is_ok()
   {
-    Ok(host_and_port.to_string())
+    Ok(entry.to_string())
   } else {
     Err(format!("Bad host:port pair: {host_and_port}"))
   }
@@ -43,17 +47,25 @@ pub fn validator(host_and_port: &str) -> Result<String, String> {
 /// `127.0.0.1:port` and `localhost:port`.
 pub fn parse(paths: Vec<String>) -> clap::error::Result<Vec<String>> {
   let mut out: Vec<String> = vec![];
-  for host_and_port in paths.into_iter() {
+  for entry in paths.into_iter() {
+    let (host_and_port, maybe_cap) =
+      split_net_byte_cap(&entry).map_err(|e| {
+        clap::Error::raw(clap::error::ErrorKind::InvalidValue, format!("{e:?}"))
+      })?;
     if let Ok(port) = host_and_port.parse::<BarePort>() {
       // we got bare port, let's add default hosts
+      let cap_suffix = match maybe_cap {
+        Some(_) => &entry[host_and_port.len()..],
+        None => "",
+      };
       for host in ["0.0.0.0", "127.0.0.1", "localhost"].iter() {
-        out.push(format!("{}:{}", host, port.0));
+        out.push(format!("{}:{}{}", host, port.0, cap_suffix));
       }
     } else {
       host_and_port.parse::<NetDescriptor>().map_err(|e| {
         clap::Error::raw(clap::error::ErrorKind::InvalidValue, format!("{e:?}"))
       })?;
-      out.push(host_and_port)
+      out.push(entry)
     }
   }
   Ok(out)
@@ -169,6 +181,29 @@ mod tests {
     assert_eq!(actual, expected);
   }
 
+  #[test]
+  fn parse_net_args_byte_cap() {
+    let entries = svec!["api.example.com:bytes=10MB", ":8080:bytes=1KB"];
+    let expected = svec![
+      "api.example.com:bytes=10MB",
+      "0.0.0.0:8080:bytes=1KB",
+      "127.0.0.1:8080:bytes=1KB",
+      "localhost:8080:bytes=1KB"
+    ];
+    let actual = parse(entries).unwrap();
+    assert_eq!(actual, expected);
+    assert!(parse(svec!["api.example.com:bytes=ten"]).is_err());
+  }
+
+  #[test]
+  fn parse_net_args_unix() {
+    let entries = svec!["unix:/var/run/app.sock", "deno.land"];
+    let expected = svec!["unix:/var/run/app.sock", "deno.land"];
+    let actual = parse(entries).unwrap();
+    assert_eq!(actual, expected);
+    assert!(parse(svec!["unix:"]).is_err());
+  }
+
   #[test]
   fn parse_net_args_ipv6() {
     let entries = svec!["[::1]", "[::]:5678", "[::1]:5678"];
@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::collections::BTreeSet;
+use std::path::Path;
 use std::path::PathBuf;
 
 use deno_config::deno_json::ConfigFile;
@@ -9,9 +10,12 @@ use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use deno_core::parking_lot::MutexGuard;
+use deno_core::serde_json;
+use deno_core::serde_json::Value;
 use deno_lockfile::WorkspaceMemberConfig;
 use deno_package_json::PackageJsonDepValue;
 use deno_runtime::deno_node::PackageJson;
+use deno_semver::package::PackageReq;
 
 use crate::cache;
 use crate::util::fs::atomic_write_file_with_retries;
@@ -28,6 +32,11 @@ pub struct CliLockfile {
   lockfile: Mutex<Lockfile>,
   pub filename: PathBuf,
   pub frozen: bool,
+  /// Always write the lockfile with sorted keys and normalized whitespace.
+  pub normalize: bool,
+  /// The command that recreates the lockfile for the entrypoints of the
+  /// current command, suggested when it can't be read.
+  pub recreate_command: String,
 }
 
 pub struct Guard<'a, T> {
@@ -51,10 +60,13 @@ impl<'a, T> std::ops::DerefMut for Guard<'a, T> {
 impl CliLockfile {
   pub fn new(lockfile: Lockfile, frozen: bool) -> Self {
     let filename = lockfile.filename.clone();
+    let recreate_command = lock_write_command(&filename, &[]);
     Self {
       lockfile: Mutex::new(lockfile),
       filename,
       frozen,
+      normalize: false,
+      recreate_command,
     }
   }
 
@@ -79,8 +91,23 @@ impl CliLockfile {
   pub fn write_if_changed(&self) -> Result<(), AnyError> {
     self.error_if_changed()?;
     let mut lockfile = self.lockfile.lock();
-    let Some(bytes) = lockfile.resolve_write_bytes() else {
-      return Ok(()); // nothing to do
+    let bytes = match lockfile.resolve_write_bytes() {
+      Some(bytes) if self.normalize => normalize_lockfile_bytes(&bytes)?,
+      Some(bytes) => bytes,
+      None if self.normalize => {
+        // the content is up to date, but the file on disk might not be
+        // normalized yet
+        let Ok(current) = std::fs::read(&lockfile.filename) else {
+          return Ok(()); // nothing to do
+        };
+        let bytes =
+          normalize_lockfile_bytes(lockfile.as_json_string().as_bytes())?;
+        if bytes == current {
+          return Ok(()); // nothing to do
+        }
+        bytes
+      }
+      None => return Ok(()), // nothing to do
     };
     // do an atomic write to reduce the chance of multiple deno
     // processes corrupting the file
@@ -94,6 +121,21 @@ impl CliLockfile {
     Ok(())
   }
 
+  /// Overwrites the lockfile with `content`, for commands that edit its json
+  /// directly instead of going through `deno_lockfile`.
+  pub fn write_json(&self, content: &Value) -> Result<(), AnyError> {
+    let mut text = serde_json::to_string_pretty(content)?;
+    text.push('\n');
+    let bytes = if self.normalize {
+      normalize_lockfile_bytes(text.as_bytes())?
+    } else {
+      text.into_bytes()
+    };
+    atomic_write_file_with_retries(&self.filename, bytes, cache::CACHE_PERM)
+      .context("Failed writing lockfile.")?;
+    Ok(())
+  }
+
   pub fn discover(
     flags: &Flags,
     workspace: &Workspace,
@@ -157,7 +199,7 @@ impl CliLockfile {
         .unwrap_or(false)
     });
 
-    let lockfile = if flags.lock_write {
+    let mut lockfile = if flags.lock_write {
       log::warn!(
         "{} \"--lock-write\" flag is deprecated and will be removed in Deno 2.",
         crate::colors::yellow("Warning")
@@ -166,6 +208,15 @@ impl CliLockfile {
     } else {
       Self::read_from_path(filename, frozen)?
     };
+    lockfile.normalize = flags.lock_normalize;
+    let entrypoints = match &flags.subcommand {
+      DenoSubcommand::Run(run_flags) => vec![run_flags.script.clone()],
+      DenoSubcommand::Cache(cache_flags) => cache_flags.files.clone(),
+      DenoSubcommand::Check(check_flags) => check_flags.files.clone(),
+      _ => Vec::new(),
+    };
+    lockfile.recreate_command =
+      lock_write_command(&lockfile.filename, &entrypoints);
 
     // initialize the lockfile with the workspace's configuration
     let root_url = workspace.root_dir();
@@ -257,11 +308,7 @@ impl CliLockfile {
     }
     let lockfile = self.lockfile.lock();
     if lockfile.has_content_changed {
-      let suggested = if *super::DENO_FUTURE {
-        "`deno cache --frozen=false`, `deno install --frozen=false`,"
-      } else {
-        "`deno cache --frozen=false`"
-      };
+      let suggested = frozen_update_suggestion();
 
       let contents =
         std::fs::read_to_string(&lockfile.filename).unwrap_or_default();
@@ -276,4 +323,186 @@ impl CliLockfile {
       Ok(())
     }
   }
+
+  /// Errors when the lockfile is frozen and installing the provided npm
+  /// package requirements would add or change packages in it.
+  pub fn error_if_npm_reqs_changed<'a>(
+    &self,
+    reqs: impl Iterator<Item = &'a PackageReq>,
+  ) -> Result<(), AnyError> {
+    if !self.frozen {
+      return Ok(());
+    }
+    let lockfile = self.lockfile.lock();
+    let packages = &lockfile.content.packages;
+    let mut changes = Vec::new();
+    for req in reqs {
+      if packages.specifiers.contains_key(&format!("npm:{}", req)) {
+        continue;
+      }
+      let name_prefix = format!("{}@", req.name);
+      let locked = packages
+        .npm
+        .keys()
+        .filter_map(|key| key.strip_prefix(&name_prefix))
+        // strip the peer dependency suffix
+        .map(|version| version.split('_').next().unwrap_or(version))
+        .collect::<BTreeSet<_>>();
+      if locked.is_empty() {
+        changes.push(format!("  + npm:{}", req));
+      } else {
+        changes.push(format!(
+          "  ~ npm:{} (locked: {})",
+          req,
+          locked
+            .iter()
+            .map(|version| format!("{}{}", name_prefix, version))
+            .collect::<Vec<_>>()
+            .join(", ")
+        ));
+      }
+    }
+    if changes.is_empty() {
+      return Ok(());
+    }
+    Err(deno_core::anyhow::anyhow!(
+      "The lockfile is out of date with package.json. Run {} or rerun with `--frozen=false` to update it.\npackages:\n{}",
+      frozen_update_suggestion(),
+      changes.join("\n")
+    ))
+  }
+}
+
+/// Returns the `deno cache` invocation that rewrites the lockfile at `path`
+/// from the modules of `entrypoints`.
+fn lock_write_command(path: &Path, entrypoints: &[String]) -> String {
+  let mut command =
+    format!("deno cache --lock={} --lock-write", path.display());
+  for entrypoint in entrypoints {
+    command.push(' ');
+    command.push_str(entrypoint);
+  }
+  command
+}
+
+fn frozen_update_suggestion() -> &'static str {
+  if *super::DENO_FUTURE {
+    "`deno cache --frozen=false`, `deno install --frozen=false`,"
+  } else {
+    "`deno cache --frozen=false`"
+  }
+}
+
+/// Re-serializes the lockfile with its object keys sorted recursively and
+/// two space indentation, so the output doesn't depend on the order the
+/// entries were inserted in. The top level `"version"` stays first, like
+/// `deno_lockfile` writes it.
+fn normalize_lockfile_bytes(bytes: &[u8]) -> Result<Vec<u8>, AnyError> {
+  fn sort_keys(value: Value) -> Value {
+    match value {
+      Value::Object(object) => {
+        let mut entries = object.into_iter().collect::<Vec<_>>();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Value::Object(
+          entries
+            .into_iter()
+            .map(|(key, value)| (key, sort_keys(value)))
+            .collect(),
+        )
+      }
+      Value::Array(items) => {
+        Value::Array(items.into_iter().map(sort_keys).collect())
+      }
+      value => value,
+    }
+  }
+
+  let value: Value =
+    serde_json::from_slice(bytes).context("Failed parsing lockfile.")?;
+  let value = match sort_keys(value) {
+    Value::Object(mut object) => {
+      let mut sorted = serde_json::Map::new();
+      if let Some(version) = object.shift_remove("version") {
+        sorted.insert("version".to_string(), version);
+      }
+      sorted.extend(object);
+      Value::Object(sorted)
+    }
+    value => value,
+  };
+  let mut text = serde_json::to_string_pretty(&value)?;
+  text.push('\n');
+  Ok(text.into_bytes())
+}
+
+#[cfg(test)]
+mod test {
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn normalize_lockfile_sorts_keys() {
+    let unsorted = json!({
+      "version": "3",
+      "remote": {
+        "https://deno.land/std/path/mod.ts": "b",
+        "https://deno.land/std/fs/mod.ts": "a"
+      },
+      "packages": {
+        "specifiers": { "npm:chalk@5": "npm:chalk@5.0.1" },
+        "npm": {
+          "chalk@5.0.1": { "integrity": "sha512", "dependencies": {} },
+          "ansi-styles@4.3.0": { "integrity": "sha512", "dependencies": {} }
+        }
+      }
+    });
+    let bytes = normalize_lockfile_bytes(
+      serde_json::to_string(&unsorted).unwrap().as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(
+      String::from_utf8(bytes.clone()).unwrap(),
+      r#"{
+  "version": "3",
+  "packages": {
+    "npm": {
+      "ansi-styles@4.3.0": {
+        "dependencies": {},
+        "integrity": "sha512"
+      },
+      "chalk@5.0.1": {
+        "dependencies": {},
+        "integrity": "sha512"
+      }
+    },
+    "specifiers": {
+      "npm:chalk@5": "npm:chalk@5.0.1"
+    }
+  },
+  "remote": {
+    "https://deno.land/std/fs/mod.ts": "a",
+    "https://deno.land/std/path/mod.ts": "b"
+  }
+}
+"#
+    );
+    // normalizing again is stable
+    assert_eq!(normalize_lockfile_bytes(&bytes).unwrap(), bytes);
+  }
+
+  #[test]
+  fn lock_write_command_lists_entrypoints() {
+    assert_eq!(
+      lock_write_command(
+        Path::new("npm/lock_file/lock.json"),
+        &["main.ts".to_string(), "other.ts".to_string()]
+      ),
+      "deno cache --lock=npm/lock_file/lock.json --lock-write main.ts other.ts"
+    );
+    assert_eq!(
+      lock_write_command(Path::new("deno.lock"), &[]),
+      "deno cache --lock=deno.lock --lock-write"
+    );
+  }
 }
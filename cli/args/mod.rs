@@ -25,6 +25,7 @@ use deno_npm::npm_rc::NpmRc;
 use deno_npm::npm_rc::ResolvedNpmRc;
 use deno_npm::resolution::ValidSerializedNpmResolutionSnapshot;
 use deno_npm::NpmSystemInfo;
+use deno_package_json::PackageJsonDepValue;
 use deno_runtime::deno_permissions::PermissionsContainer;
 use deno_semver::npm::NpmPackageReqReference;
 use import_map::resolve_import_map_value_from_specifier;
@@ -59,11 +60,11 @@ use deno_runtime::deno_tls::rustls_pemfile;
 use deno_runtime::deno_tls::webpki_roots;
 use deno_runtime::inspector_server::InspectorServer;
 use deno_terminal::colors;
-use dotenvy::from_filename;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::env;
 use std::io::BufReader;
 use std::io::Cursor;
@@ -72,12 +73,15 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 use crate::cache;
 use crate::cache::DenoDirProvider;
 use crate::file_fetcher::FileFetcher;
+use crate::resolver::ImportAllowList;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
+use crate::util::warning;
 use crate::version;
 
 use deno_config::deno_json::FmtConfig;
@@ -825,7 +829,10 @@ impl CliOptions {
     )
     .with_context(|| "Resolving node_modules folder.")?;
 
-    load_env_variables_from_env_file(flags.env_file.as_ref());
+    load_env_variables_from_env_files(
+      flags.env_file.as_deref(),
+      flags.env_file_override,
+    );
 
     let disable_deprecated_api_warning = flags.log_level
       == Some(log::Level::Error)
@@ -917,7 +924,10 @@ impl CliOptions {
     };
 
     for diagnostic in start_dir.workspace.diagnostics() {
-      log::warn!("{} {}", colors::yellow("Warning"), diagnostic);
+      warning::warn(
+        format!("{} {}", colors::yellow("Warning"), diagnostic),
+        flags.abort_on_warning.as_deref(),
+      )?;
     }
 
     let (npmrc, _) = discover_npmrc_from_workspace(&start_dir.workspace)?;
@@ -985,6 +995,17 @@ impl CliOptions {
   }
 
   pub fn npm_system_info(&self) -> NpmSystemInfo {
+    let mut system_info = self.target_npm_system_info();
+    if let Some(os) = &self.flags.npm_target_os {
+      system_info.os = os.clone();
+    }
+    if let Some(cpu) = &self.flags.npm_target_cpu {
+      system_info.cpu = cpu.clone();
+    }
+    system_info
+  }
+
+  fn target_npm_system_info(&self) -> NpmSystemInfo {
     match self.sub_command() {
       DenoSubcommand::Compile(CompileFlags {
         target: Some(target),
@@ -1133,8 +1154,8 @@ impl CliOptions {
     }
   }
 
-  pub fn env_file_name(&self) -> Option<&String> {
-    self.flags.env_file.as_ref()
+  pub fn env_file_names(&self) -> Option<&[String]> {
+    self.flags.env_file.as_deref()
   }
 
   pub fn enable_future_features(&self) -> bool {
@@ -1150,21 +1171,20 @@ impl CliOptions {
         resolve_url_or_path(&compile_flags.source_file, self.initial_cwd())?
       }
       DenoSubcommand::Eval(_) => {
-        resolve_url_or_path("./$deno$eval", self.initial_cwd())?
+        self.resolve_synthetic_main_module("./$deno$eval")?
       }
       DenoSubcommand::Repl(_) => {
         resolve_url_or_path("./$deno$repl.ts", self.initial_cwd())?
       }
       DenoSubcommand::Run(run_flags) => {
         if run_flags.is_stdin() {
-          std::env::current_dir()
-            .context("Unable to get CWD")
-            .and_then(|cwd| {
-              resolve_url_or_path("./$deno$stdin.ts", &cwd)
-                .map_err(AnyError::from)
-            })?
+          self.resolve_synthetic_main_module("./$deno$stdin.ts")?
         } else if NpmPackageReqReference::from_str(&run_flags.script).is_ok() {
           ModuleSpecifier::parse(&run_flags.script)?
+        } else if let Some(specifier) =
+          self.resolve_package_json_dep_main_module(&run_flags.script)
+        {
+          specifier
         } else {
           resolve_url_or_path(&run_flags.script, self.initial_cwd())?
         }
@@ -1180,19 +1200,79 @@ impl CliOptions {
     Ok(main_module)
   }
 
+  /// Resolves the specifier of a main module that only exists in memory,
+  /// like the code of `deno eval`. It's a file in the current directory unless
+  /// another scheme was chosen with `--synthetic-scheme`.
+  pub fn resolve_synthetic_main_module(
+    &self,
+    name: &str,
+  ) -> Result<ModuleSpecifier, AnyError> {
+    match &self.flags.synthetic_scheme {
+      Some(scheme) => Ok(ModuleSpecifier::parse(&format!(
+        "{}:{}",
+        scheme,
+        name.trim_start_matches("./")
+      ))?),
+      None => Ok(resolve_url_or_path(name, self.initial_cwd())?),
+    }
+  }
+
+  /// Resolves a bare specifier like `cowsay` or `@scope/pkg/bin-name` to an
+  /// `npm:` specifier when its package is a dependency in the package.json.
+  /// Local files and tasks of the same name take precedence.
+  fn resolve_package_json_dep_main_module(
+    &self,
+    script: &str,
+  ) -> Option<ModuleSpecifier> {
+    if script.starts_with('.')
+      || Path::new(script).is_absolute()
+      || Url::parse(script).is_ok()
+      || self.initial_cwd().join(script).exists()
+    {
+      return None;
+    }
+    let pkg_json = self.start_dir.maybe_pkg_json()?;
+    let is_task = self
+      .start_dir
+      .to_tasks_config()
+      .map(|tasks_config| tasks_config.task(script).is_some())
+      .unwrap_or(false);
+    if is_task {
+      return None;
+    }
+    let name_end = if script.starts_with('@') {
+      script.match_indices('/').nth(1).map(|(index, _)| index)
+    } else {
+      script.find('/')
+    }
+    .unwrap_or(script.len());
+    let (name, sub_path) = script.split_at(name_end);
+    let deps = pkg_json.resolve_local_package_json_deps();
+    match deps.get(name) {
+      Some(Ok(PackageJsonDepValue::Req(req))) => {
+        ModuleSpecifier::parse(&format!("npm:{}{}", req, sub_path)).ok()
+      }
+      _ => None,
+    }
+  }
+
+  /// The `Content-Type` matching the `--ext` flag, if one was passed.
+  pub fn ext_content_type(&self) -> Option<&'static str> {
+    // TODO(Cre3per): This mapping moved to deno_ast with https://github.com/denoland/deno_ast/issues/133 and should be available in deno_ast >= 0.25.0 via `MediaType::from_path(...).as_media_type()`
+    self.flags.ext.as_ref().and_then(|el| match el.as_str() {
+      "ts" => Some("text/typescript"),
+      "tsx" => Some("text/tsx"),
+      "js" => Some("text/javascript"),
+      "jsx" => Some("text/jsx"),
+      _ => None,
+    })
+  }
+
   pub fn resolve_file_header_overrides(
     &self,
   ) -> HashMap<ModuleSpecifier, HashMap<String, String>> {
     let maybe_main_specifier = self.resolve_main_module().ok();
-    // TODO(Cre3per): This mapping moved to deno_ast with https://github.com/denoland/deno_ast/issues/133 and should be available in deno_ast >= 0.25.0 via `MediaType::from_path(...).as_media_type()`
-    let maybe_content_type =
-      self.flags.ext.as_ref().and_then(|el| match el.as_str() {
-        "ts" => Some("text/typescript"),
-        "tsx" => Some("text/tsx"),
-        "js" => Some("text/javascript"),
-        "jsx" => Some("text/jsx"),
-        _ => None,
-      });
+    let maybe_content_type = self.ext_content_type();
 
     if let (Some(main_specifier), Some(content_type)) =
       (maybe_main_specifier, maybe_content_type)
@@ -1240,6 +1320,10 @@ impl CliOptions {
     self.maybe_node_modules_folder.as_ref()
   }
 
+  pub fn node_modules_layout(&self) -> Option<NodeModulesLayout> {
+    self.flags.nm_layout
+  }
+
   pub fn with_node_modules_dir_path(&self, path: PathBuf) -> Self {
     Self {
       flags: self.flags.clone(),
@@ -1479,6 +1563,7 @@ impl CliOptions {
         .as_ref()
         .map(ToOwned::to_owned)
         .or_else(|| env::var("DENO_UNSTABLE_COVERAGE_DIR").ok()),
+      DenoSubcommand::Run(run) => run.coverage_dir.clone(),
       _ => None,
     }
   }
@@ -1513,6 +1598,19 @@ impl CliOptions {
     }
   }
 
+  /// If the --watch-reevaluate flag is used.
+  pub fn has_hmr_reevaluate(&self) -> bool {
+    if let DenoSubcommand::Run(RunFlags {
+      watch: Some(WatchFlagsWithPaths { reevaluate, .. }),
+      ..
+    }) = &self.flags.subcommand
+    {
+      *reevaluate
+    } else {
+      false
+    }
+  }
+
   /// If the --inspect or --inspect-brk flags are used.
   pub fn is_inspecting(&self) -> bool {
     self.flags.inspect.is_some()
@@ -1598,6 +1696,240 @@ impl CliOptions {
     self.flags.type_check_mode
   }
 
+  pub fn dynamic_import_timeout(&self) -> Option<Duration> {
+    self.flags.dynamic_import_timeout.map(Duration::from_millis)
+  }
+
+  pub fn print_config(&self) -> Option<&PrintConfigFlags> {
+    self.flags.print_config.as_ref()
+  }
+
+  pub fn dump_ast(&self) -> Option<&DumpAstFlags> {
+    self.flags.dump_ast.as_ref()
+  }
+
+  pub fn dry_run(&self) -> Option<&DryRunFlags> {
+    self.flags.dry_run.as_ref()
+  }
+
+  pub fn warm_npm(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .warm_npm
+      .as_ref()
+      .map(|manifest| self.initial_cwd().join(manifest))
+  }
+
+  pub fn print_bin(&self) -> bool {
+    self.flags.print_bin
+  }
+
+  pub fn print_entrypoint(&self) -> bool {
+    self.flags.print_entrypoint
+  }
+
+  pub fn cache_backend(&self) -> Result<Option<Url>, AnyError> {
+    self
+      .flags
+      .cache_backend
+      .as_ref()
+      .map(|url| {
+        Url::parse(url)
+          .with_context(|| format!("Invalid cache backend URL '{}'.", url))
+      })
+      .transpose()
+  }
+
+  pub fn check_parallel(&self) -> Option<NonZeroUsize> {
+    self.flags.check_parallel
+  }
+
+  pub fn force_strict(&self) -> bool {
+    self.flags.force_strict
+  }
+
+  pub fn deprecation(&self) -> Option<DeprecationMode> {
+    self.flags.deprecation
+  }
+
+  pub fn abort_on_warning(&self) -> Option<String> {
+    self.flags.abort_on_warning.clone()
+  }
+
+  pub fn byonm_on_stale(&self) -> Option<ByonmOnStale> {
+    self.flags.byonm_on_stale
+  }
+
+  pub fn import_allow_list(&self) -> Result<Option<ImportAllowList>, AnyError> {
+    self
+      .flags
+      .import_allow_list
+      .as_ref()
+      .map(|specs| ImportAllowList::parse(specs, self.initial_cwd()))
+      .transpose()
+  }
+
+  pub fn cpu_prof(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .cpu_prof
+      .as_ref()
+      .map(|path| self.initial_cwd().join(path))
+  }
+
+  /// Resolves the modules passed to `--preload`, in order.
+  pub fn preload_modules(&self) -> Result<Vec<ModuleSpecifier>, AnyError> {
+    let DenoSubcommand::Run(run_flags) = &self.flags.subcommand else {
+      return Ok(Vec::new());
+    };
+    run_flags
+      .preload
+      .iter()
+      .map(|specifier| {
+        resolve_url_or_path(specifier, self.initial_cwd()).with_context(|| {
+          format!("Failed resolving preload module '{}'.", specifier)
+        })
+      })
+      .collect()
+  }
+
+  pub fn skip_upgrade_check(&self) -> bool {
+    matches!(
+      &self.flags.subcommand,
+      DenoSubcommand::Run(run_flags) if run_flags.skip_upgrade_check
+    )
+  }
+
+  pub fn extra_script_modules(&self) -> Result<Vec<ModuleSpecifier>, AnyError> {
+    let DenoSubcommand::Run(run_flags) = &self.flags.subcommand else {
+      return Ok(Vec::new());
+    };
+    run_flags
+      .extra_scripts
+      .iter()
+      .map(|specifier| {
+        resolve_url_or_path(specifier, self.initial_cwd())
+          .with_context(|| format!("Failed resolving script '{}'.", specifier))
+      })
+      .collect()
+  }
+
+  pub fn vendor_out(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .vendor_out
+      .as_ref()
+      .map(|path| self.initial_cwd().join(path))
+  }
+
+  pub fn warmup(&self) -> bool {
+    self.flags.warmup
+  }
+
+  pub fn report_unused_exports(&self) -> bool {
+    self.flags.report_unused_exports
+  }
+
+  pub fn max_graph_modules(&self) -> Option<usize> {
+    self.flags.max_graph_modules
+  }
+
+  pub fn config_schema_validate(&self) -> bool {
+    self.flags.config_schema_validate
+  }
+
+  /// Whether to report npm packages with install scripts, and if so
+  /// whether finding any is an error.
+  pub fn detect_install_scripts(&self) -> Option<bool> {
+    self
+      .flags
+      .detect_install_scripts
+      .then_some(self.flags.fail_on_install_scripts)
+  }
+
+  pub fn npm_snapshot(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .npm_snapshot
+      .as_ref()
+      .map(|path| self.initial_cwd().join(path))
+  }
+
+  pub fn record_nondeterminism(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .record_nondeterminism
+      .as_ref()
+      .map(|path| self.initial_cwd().join(path))
+  }
+
+  pub fn replay_nondeterminism(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .replay_nondeterminism
+      .as_ref()
+      .map(|path| self.initial_cwd().join(path))
+  }
+
+  pub fn report_hrtime_usage(&self) -> bool {
+    self.flags.report_hrtime_usage
+  }
+
+  pub fn max_redirect_bytes(&self) -> Option<u64> {
+    self.flags.max_redirect_bytes
+  }
+
+  pub fn exit_drain(&self) -> Option<Duration> {
+    match self.flags.exit_drain {
+      Some(millis) => Some(Duration::from_millis(millis)),
+      // `Deno.exit()` has to return to `run_script` so that the output
+      // copied to the --tee-* files is complete, the coverage of
+      // `deno run --coverage` and the `--vendor-out` directory are written
+      // and the `--then` scripts run
+      None
+        if self.flags.tee_stdout.is_some()
+          || self.flags.tee_stderr.is_some()
+          || self.flags.vendor_out.is_some()
+          || matches!(
+            &self.flags.subcommand,
+            DenoSubcommand::Run(run)
+              if run.coverage_dir.is_some() || !run.extra_scripts.is_empty()
+          ) =>
+      {
+        Some(Duration::ZERO)
+      }
+      None => None,
+    }
+  }
+
+  pub fn check_resource_leaks(&self) -> bool {
+    self.flags.check_resource_leaks
+  }
+
+  pub fn verify_npm_contents(&self) -> bool {
+    self.flags.verify_npm_contents
+  }
+
+  pub fn vfs_overlay(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .vfs_overlay
+      .as_ref()
+      .map(|path| self.initial_cwd.join(path))
+  }
+
+  pub fn trace_file(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .trace_file
+      .as_ref()
+      .map(|path| self.initial_cwd.join(path))
+  }
+
+  pub fn remote_auth(&self) -> &[String] {
+    &self.flags.remote_auth
+  }
+
   pub fn unsafely_ignore_certificate_errors(&self) -> &Option<Vec<String>> {
     &self.flags.unsafely_ignore_certificate_errors
   }
@@ -1710,6 +2042,14 @@ impl CliOptions {
     self.flags.code_cache_enabled
   }
 
+  pub fn code_cache_path(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .code_cache_path
+      .as_ref()
+      .map(|path| self.initial_cwd.join(path))
+  }
+
   pub fn watch_paths(&self) -> Vec<PathBuf> {
     let mut full_paths = Vec::new();
     if let DenoSubcommand::Run(RunFlags {
@@ -1903,23 +2243,154 @@ pub fn config_to_deno_graph_workspace_member(
   })
 }
 
-fn load_env_variables_from_env_file(filename: Option<&String>) {
-  let Some(env_file_name) = filename else {
+/// Loads the environment files in order, with later files overriding the
+/// variables of earlier ones. Variables of the process environment are kept
+/// unless `override_existing` is set.
+fn load_env_variables_from_env_files(
+  filenames: Option<&[String]>,
+  override_existing: bool,
+) {
+  let Some(env_file_names) = filenames else {
     return;
   };
-  match from_filename(env_file_name) {
-    Ok(_) => (),
+  let existing_keys: HashSet<std::ffi::OsString> = if override_existing {
+    HashSet::new()
+  } else {
+    std::env::vars_os().map(|(key, _)| key).collect()
+  };
+  for env_file_name in env_file_names {
+    // earlier files can choose the later ones, like `.env.{NODE_ENV}`
+    let env_file_name =
+      interpolate_env_file_name(env_file_name, |name| std::env::var(name).ok());
+    let Some(env_file_names) = expand_env_file_name(&env_file_name) else {
+      warn_env_file_error(
+        &env_file_name,
+        dotenvy::Error::Io(std::io::ErrorKind::NotFound.into()),
+      );
+      continue;
+    };
+    for env_file_name in env_file_names {
+      load_env_file(&env_file_name, &existing_keys);
+    }
+  }
+}
+
+fn load_env_file(
+  env_file_name: &str,
+  existing_keys: &HashSet<std::ffi::OsString>,
+) {
+  let iter = match dotenvy::from_filename_iter(env_file_name) {
+    Ok(iter) => iter,
     Err(error) => {
-      match error {
-          dotenvy::Error::LineParse(line, index)=> log::info!("{} Parsing failed within the specified environment file: {} at index: {} of the value: {}",colors::yellow("Warning"), env_file_name, index, line),
-          dotenvy::Error::Io(_)=> log::info!("{} The `--env-file` flag was used, but the environment file specified '{}' was not found.",colors::yellow("Warning"),env_file_name),
-          dotenvy::Error::EnvVar(_)=> log::info!("{} One or more of the environment variables isn't present or not unicode within the specified environment file: {}",colors::yellow("Warning"),env_file_name),
-          _ => log::info!("{} Unknown failure occurred with the specified environment file: {}", colors::yellow("Warning"), env_file_name),
+      warn_env_file_error(env_file_name, error);
+      return;
+    }
+  };
+  for item in iter {
+    match item {
+      Ok((key, value)) => {
+        if !existing_keys.contains(std::ffi::OsStr::new(&key)) {
+          std::env::set_var(key, value);
         }
+      }
+      Err(error) => {
+        warn_env_file_error(env_file_name, error);
+        break;
+      }
     }
   }
 }
 
+/// Expands an `--env-file` value that names a directory into the `.env`
+/// files it contains, and a glob into the files it matches. Other values
+/// are returned as is. Returns `None` when a directory or glob doesn't
+/// contain any file.
+fn expand_env_file_name(name: &str) -> Option<Vec<String>> {
+  let paths = if Path::new(name).is_dir() {
+    std::fs::read_dir(name)
+      .ok()?
+      .filter_map(|entry| entry.ok())
+      .filter(|entry| {
+        entry.file_name().to_string_lossy().starts_with(".env")
+          && entry.file_type().is_ok_and(|file_type| file_type.is_file())
+      })
+      .map(|entry| entry.path())
+      .collect::<Vec<_>>()
+  } else if name.contains(['*', '?', '[']) {
+    glob::glob(name)
+      .ok()?
+      .filter_map(|path| path.ok())
+      .filter(|path| path.is_file())
+      .collect::<Vec<_>>()
+  } else {
+    return Some(vec![name.to_string()]);
+  };
+  if paths.is_empty() {
+    return None;
+  }
+  Some(
+    sort_env_file_paths(paths)
+      .into_iter()
+      .map(|path| path.to_string_lossy().into_owned())
+      .collect(),
+  )
+}
+
+/// Orders the environment files of a directory or glob alphabetically, with
+/// the `.local` files last so that their variables win.
+fn sort_env_file_paths(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+  paths.sort_by_cached_key(|path| {
+    let file_name = path
+      .file_name()
+      .map(|name| name.to_string_lossy().into_owned())
+      .unwrap_or_default();
+    (file_name.ends_with(".local"), path.clone())
+  });
+  paths
+}
+
+fn warn_env_file_error(env_file_name: &str, error: dotenvy::Error) {
+  match error {
+    dotenvy::Error::LineParse(line, index)=> log::info!("{} Parsing failed within the specified environment file: {} at index: {} of the value: {}",colors::yellow("Warning"), env_file_name, index, line),
+    dotenvy::Error::Io(_)=> log::info!("{} The `--env-file` flag was used, but the environment file specified '{}' was not found.",colors::yellow("Warning"),env_file_name),
+    dotenvy::Error::EnvVar(_)=> log::info!("{} One or more of the environment variables isn't present or not unicode within the specified environment file: {}",colors::yellow("Warning"),env_file_name),
+    _ => log::info!("{} Unknown failure occurred with the specified environment file: {}", colors::yellow("Warning"), env_file_name),
+  }
+}
+
+/// Replaces each `{NAME}` in an environment file name with the value of the
+/// variable `NAME`, or with nothing when it isn't set.
+pub fn interpolate_env_file_name(
+  name: &str,
+  get_var: impl Fn(&str) -> Option<String>,
+) -> String {
+  let mut result = String::with_capacity(name.len());
+  let mut rest = name;
+  while let Some(start) = rest.find('{') {
+    result.push_str(&rest[..start]);
+    let after = &rest[start + 1..];
+    let var_name =
+      after.find('}').map(|end| &after[..end]).filter(|var_name| {
+        !var_name.is_empty()
+          && var_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+      });
+    match var_name {
+      Some(var_name) => {
+        result.push_str(&get_var(var_name).unwrap_or_default());
+        rest = &after[var_name.len() + 1..];
+      }
+      None => {
+        result.push('{');
+        rest = after;
+      }
+    }
+  }
+  result.push_str(rest);
+  result
+}
+
 #[cfg(test)]
 mod test {
   use pretty_assertions::assert_eq;
@@ -2010,4 +2481,41 @@ mod test {
     let reg_api_url = jsr_api_url();
     assert!(reg_api_url.as_str().ends_with('/'));
   }
+
+  #[test]
+  fn test_interpolate_env_file_name() {
+    let get_var = |name: &str| match name {
+      "NODE_ENV" => Some("production".to_string()),
+      _ => None,
+    };
+    assert_eq!(
+      interpolate_env_file_name(".env.{NODE_ENV}", get_var),
+      ".env.production"
+    );
+    assert_eq!(interpolate_env_file_name(".env.{UNSET}", get_var), ".env.");
+    assert_eq!(interpolate_env_file_name(".env.{}", get_var), ".env.{}");
+    assert_eq!(
+      interpolate_env_file_name("{not a var}/{NODE_ENV", get_var),
+      "{not a var}/{NODE_ENV"
+    );
+  }
+
+  #[test]
+  fn test_sort_env_file_paths() {
+    let paths = vec![
+      PathBuf::from("config/.env.production"),
+      PathBuf::from("config/.env.local"),
+      PathBuf::from("config/.env"),
+      PathBuf::from("config/.env.production.local"),
+    ];
+    assert_eq!(
+      sort_env_file_paths(paths),
+      vec![
+        PathBuf::from("config/.env"),
+        PathBuf::from("config/.env.production"),
+        PathBuf::from("config/.env.local"),
+        PathBuf::from("config/.env.production.local"),
+      ]
+    );
+  }
 }
@@ -17,6 +17,7 @@ use std::str::FromStr;
 pub enum AuthTokenData {
   Bearer(String),
   Basic { username: String, password: String },
+  Header(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,6 +34,7 @@ impl fmt::Display for AuthToken {
         let credentials = format!("{username}:{password}");
         write!(f, "Basic {}", BASE64_STANDARD.encode(credentials))
       }
+      AuthTokenData::Header(value) => write!(f, "{value}"),
     }
   }
 }
@@ -152,6 +154,23 @@ impl AuthTokens {
     Self(tokens)
   }
 
+  /// Add tokens from `--remote-auth` flags, where each value is
+  /// `{hostname}={authorization}`. These are matched before any tokens from
+  /// `DENO_AUTH_TOKENS`, and among themselves in the order provided.
+  pub fn prepend_remote_auth(&mut self, remote_auth: &[String]) {
+    let tokens = remote_auth.iter().filter_map(|value| {
+      let Some((host, auth)) = value.split_once('=') else {
+        error!("Badly formed remote auth discarded.");
+        return None;
+      };
+      Some(AuthToken {
+        host: AuthDomain::from(host.trim()),
+        token: AuthTokenData::Header(auth.trim().to_string()),
+      })
+    });
+    self.0.splice(0..0, tokens);
+  }
+
   /// Attempt to match the provided specifier to the tokens in the set.  The
   /// matching occurs from the right of the hostname plus port, irrespective of
   /// scheme.  For example `https://www.deno.land:8080/` would match a token
@@ -290,6 +309,25 @@ mod tests {
     assert_eq!(auth_tokens.get(&fixture), None);
   }
 
+  #[test]
+  fn test_remote_auth_precedence() {
+    let mut auth_tokens =
+      AuthTokens::new(Some("abc123@deno.land;def456@example.com".to_string()));
+    auth_tokens.prepend_remote_auth(&[
+      "deno.land=Token xyz".to_string(),
+      "www.deno.land=Bearer first".to_string(),
+    ]);
+    let fixture = resolve_url("https://deno.land/x/mod.ts").unwrap();
+    assert_eq!(auth_tokens.get(&fixture).unwrap().to_string(), "Token xyz");
+    let fixture = resolve_url("https://www.deno.land/x/mod.ts").unwrap();
+    assert_eq!(auth_tokens.get(&fixture).unwrap().to_string(), "Token xyz");
+    let fixture = resolve_url("http://example.com/a/file.ts").unwrap();
+    assert_eq!(
+      auth_tokens.get(&fixture).unwrap().to_string(),
+      "Bearer def456"
+    );
+  }
+
   #[test]
   fn test_parse_ip() {
     let ip = AuthDomain::from("[2001:db8:a::123]");
@@ -0,0 +1,123 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::url::Url;
+use http_body_util::BodyExt;
+
+use crate::http_util::HttpClientProvider;
+
+/// A store for emitted modules that is shared beyond the local `DENO_DIR`,
+/// like a distributed build cache.
+///
+/// Entries are addressed by a SHA-256 key of the specifier, the source and
+/// the CLI version. Errors should be treated as cache misses by
+/// implementations. What a backend hands back is verified by the emit cache
+/// before it's used, so backends don't need to.
+#[async_trait::async_trait(?Send)]
+pub trait ModuleCacheBackend: std::fmt::Debug + Send + Sync {
+  async fn get(&self, key: &str) -> Option<Vec<u8>>;
+  async fn put(&self, key: &str, data: Vec<u8>);
+}
+
+/// The built-in backend for `--cache-backend`, which stores entries on an
+/// HTTP server with `GET` and `PUT` requests to `<url>/<key>`. Requests go
+/// through the CLI's HTTP client, so they use its proxy and TLS settings.
+#[derive(Debug)]
+pub struct HttpModuleCacheBackend {
+  url: Url,
+  http_client_provider: Arc<HttpClientProvider>,
+}
+
+/// A slow backend shouldn't hold up loading modules for longer than emitting
+/// them locally would take.
+const HTTP_BACKEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl HttpModuleCacheBackend {
+  pub fn new(
+    url: Url,
+    http_client_provider: Arc<HttpClientProvider>,
+  ) -> Result<Self, AnyError> {
+    if !matches!(url.scheme(), "http" | "https") {
+      bail!(
+        "Unsupported cache backend '{}'. Only http: and https: URLs are supported.",
+        url
+      );
+    }
+    Ok(Self {
+      url,
+      http_client_provider,
+    })
+  }
+
+  fn entry_url(&self, key: &str) -> Url {
+    let mut url = self.url.clone();
+    url.set_path(&format!(
+      "{}/{}",
+      self.url.path().trim_end_matches('/'),
+      key
+    ));
+    url
+  }
+
+  async fn get_inner(&self, key: &str) -> Result<Option<Vec<u8>>, AnyError> {
+    let client = self.http_client_provider.get_or_create()?;
+    let response = client.get(self.entry_url(key))?.send().await?;
+    if !response.status().is_success() {
+      return Ok(None);
+    }
+    Ok(Some(
+      response.into_body().collect().await?.to_bytes().into(),
+    ))
+  }
+
+  async fn put_inner(&self, key: &str, data: Vec<u8>) -> Result<(), AnyError> {
+    let client = self.http_client_provider.get_or_create()?;
+    let body = http_body_util::Full::new(data.into())
+      .map_err(|never| match never {})
+      .boxed();
+    let response = client.put(self.entry_url(key), body)?.send().await?;
+    if !response.status().is_success() {
+      bail!(
+        "The cache backend rejected an entry ({}).",
+        response.status()
+      );
+    }
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ModuleCacheBackend for HttpModuleCacheBackend {
+  async fn get(&self, key: &str) -> Option<Vec<u8>> {
+    match tokio::time::timeout(HTTP_BACKEND_TIMEOUT, self.get_inner(key)).await
+    {
+      Ok(Ok(maybe_data)) => maybe_data,
+      Ok(Err(err)) => {
+        log::debug!("Error loading from the cache backend: {:#}", err);
+        None
+      }
+      Err(_) => {
+        log::debug!("Timed out loading from the cache backend.");
+        None
+      }
+    }
+  }
+
+  async fn put(&self, key: &str, data: Vec<u8>) {
+    match tokio::time::timeout(HTTP_BACKEND_TIMEOUT, self.put_inner(key, data))
+      .await
+    {
+      Ok(Ok(())) => {}
+      Ok(Err(err)) => {
+        log::debug!("Error saving to the cache backend: {:#}", err);
+      }
+      Err(_) => {
+        log::debug!("Timed out saving to the cache backend.");
+      }
+    }
+  }
+}
@@ -143,4 +143,10 @@ impl Caches {
         .map(|dir| dir.code_cache_db_file_path()),
     )
   }
+
+  /// The V8 code cache database stored at a custom location instead of in
+  /// `DENO_DIR`.
+  pub fn code_cache_db_at(&self, path: PathBuf) -> CacheDB {
+    Self::make_db(&self.code_cache_db, &CODE_CACHE_DB, Some(path))
+  }
 }
@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::anyhow;
@@ -10,8 +11,11 @@ use deno_core::unsync::sync::AtomicFlag;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::util::checksum;
+
 use super::DiskCache;
 use super::FastInsecureHasher;
+use super::ModuleCacheBackend;
 
 #[derive(Debug, Deserialize, Serialize)]
 struct EmitMetadata {
@@ -24,6 +28,7 @@ pub struct EmitCache {
   disk_cache: DiskCache,
   cli_version: &'static str,
   emit_failed_flag: AtomicFlag,
+  maybe_backend: Option<Arc<dyn ModuleCacheBackend>>,
 }
 
 impl EmitCache {
@@ -32,9 +37,22 @@ impl EmitCache {
       disk_cache,
       cli_version: crate::version::DENO_VERSION_INFO.deno,
       emit_failed_flag: Default::default(),
+      maybe_backend: None,
     }
   }
 
+  /// Also loads emits from and saves them to `backend` with
+  /// [`EmitCache::get_backend_emit_code`] and
+  /// [`EmitCache::set_backend_emit_code`].
+  pub fn with_backend(mut self, backend: Arc<dyn ModuleCacheBackend>) -> Self {
+    self.maybe_backend = Some(backend);
+    self
+  }
+
+  pub fn has_backend(&self) -> bool {
+    self.maybe_backend.is_some()
+  }
+
   /// Gets the emitted code with embedded sourcemap from the cache.
   ///
   /// The expected source hash is used in order to verify
@@ -129,6 +147,63 @@ impl EmitCache {
     Ok(())
   }
 
+  /// Gets the emitted code from the backend, if there is one, and copies it
+  /// into the local cache.
+  ///
+  /// The `source_checksum` is a SHA-256 checksum of the source and emit
+  /// options, which the backend is keyed by instead of the source hash. An
+  /// entry that doesn't match the checksum of the emit stored with it is
+  /// treated as a cache miss.
+  pub async fn get_backend_emit_code(
+    &self,
+    specifier: &ModuleSpecifier,
+    source_hash: u64,
+    source_checksum: &str,
+  ) -> Option<Vec<u8>> {
+    let backend = self.maybe_backend.as_ref()?;
+    let entry = backend
+      .get(&self.backend_key(specifier, source_checksum))
+      .await?;
+    let Some(emit_bytes) = verify_backend_entry(&entry) else {
+      log::debug!("Invalid cache backend entry for {}.", specifier);
+      return None;
+    };
+    if let Err(err) =
+      self.set_emit_code_result(specifier, source_hash, emit_bytes)
+    {
+      log::debug!("Error saving emit data ({}): {}", specifier, err);
+    }
+    Some(emit_bytes.to_vec())
+  }
+
+  /// Saves the emitted code to the backend, if there is one.
+  pub async fn set_backend_emit_code(
+    &self,
+    specifier: &ModuleSpecifier,
+    source_checksum: &str,
+    code: &[u8],
+  ) {
+    let Some(backend) = &self.maybe_backend else {
+      return;
+    };
+    let key = self.backend_key(specifier, source_checksum);
+    let mut entry = checksum::gen(&[code]).into_bytes();
+    entry.push(b'\n');
+    entry.extend_from_slice(code);
+    backend.put(&key, entry).await;
+  }
+
+  /// The key of an emit in the backend. It covers the source and the CLI
+  /// version, because unlike the local cache the backend doesn't store the
+  /// metadata that verifies them.
+  fn backend_key(
+    &self,
+    specifier: &ModuleSpecifier,
+    source_checksum: &str,
+  ) -> String {
+    checksum::gen(&[specifier.as_str(), source_checksum, self.cli_version])
+  }
+
   fn get_meta_filename(&self, specifier: &ModuleSpecifier) -> Option<PathBuf> {
     self
       .disk_cache
@@ -142,6 +217,18 @@ impl EmitCache {
   }
 }
 
+/// Returns the emit of a backend entry, which is the SHA-256 checksum of the
+/// emit on the first line followed by the emit, if the checksum matches.
+fn verify_backend_entry(entry: &[u8]) -> Option<&[u8]> {
+  let newline_index = entry.iter().position(|b| *b == b'\n')?;
+  let (checksum, emit_bytes) =
+    (&entry[..newline_index], &entry[newline_index + 1..]);
+  if checksum != checksum::gen(&[emit_bytes]).as_bytes() {
+    return None;
+  }
+  Some(emit_bytes)
+}
+
 fn compute_emit_hash(bytes: &[u8], cli_version: &str) -> u64 {
   // it's ok to use an insecure hash here because
   // if someone can change the emit source then they
@@ -155,6 +242,9 @@ fn compute_emit_hash(bytes: &[u8], cli_version: &str) -> u64 {
 
 #[cfg(test)]
 mod test {
+  use std::collections::HashMap;
+
+  use deno_core::parking_lot::Mutex;
   use test_util::TempDir;
 
   use super::*;
@@ -167,6 +257,7 @@ mod test {
       disk_cache: disk_cache.clone(),
       cli_version: "1.0.0",
       emit_failed_flag: Default::default(),
+      maybe_backend: None,
     };
     let to_string =
       |bytes: Vec<u8>| -> String { String::from_utf8(bytes).unwrap() };
@@ -199,6 +290,7 @@ mod test {
       disk_cache: disk_cache.clone(),
       cli_version: "2.0.0",
       emit_failed_flag: Default::default(),
+      maybe_backend: None,
     };
     assert_eq!(cache.get_emit_code(&specifier1, 10), None);
     cache.set_emit_code(&specifier1, 5, emit_code1.as_bytes());
@@ -208,6 +300,7 @@ mod test {
       disk_cache,
       cli_version: "2.0.0",
       emit_failed_flag: Default::default(),
+      maybe_backend: None,
     };
     assert_eq!(
       cache.get_emit_code(&specifier1, 5).map(to_string),
@@ -223,4 +316,70 @@ mod test {
       Some(emit_code3)
     );
   }
+
+  #[derive(Debug, Default)]
+  struct InMemoryBackend {
+    entries: Mutex<HashMap<String, Vec<u8>>>,
+  }
+
+  #[async_trait::async_trait(?Send)]
+  impl ModuleCacheBackend for InMemoryBackend {
+    async fn get(&self, key: &str) -> Option<Vec<u8>> {
+      self.entries.lock().get(key).cloned()
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>) {
+      self.entries.lock().insert(key.to_string(), data);
+    }
+  }
+
+  #[tokio::test]
+  async fn emit_cache_with_backend() {
+    let backend = Arc::new(InMemoryBackend::default());
+    let specifier = ModuleSpecifier::parse("file:///mod.ts").unwrap();
+
+    // saving an emit stores it in the backend
+    let temp_dir = TempDir::new();
+    let cache = EmitCache::new(DiskCache::new(temp_dir.path().as_path()))
+      .with_backend(backend.clone());
+    cache
+      .set_backend_emit_code(&specifier, "source", b"emitted")
+      .await;
+    assert_eq!(backend.entries.lock().len(), 1);
+
+    // another machine with an empty local cache loads it from the backend
+    let other_temp_dir = TempDir::new();
+    let disk_cache = DiskCache::new(other_temp_dir.path().as_path());
+    let cache =
+      EmitCache::new(disk_cache.clone()).with_backend(backend.clone());
+    assert_eq!(
+      cache
+        .get_backend_emit_code(&specifier, 10, "other source")
+        .await,
+      None
+    );
+    assert_eq!(
+      cache.get_backend_emit_code(&specifier, 10, "source").await,
+      Some(b"emitted".to_vec())
+    );
+
+    // and copies it into the local cache
+    let cache = EmitCache::new(disk_cache);
+    assert_eq!(
+      cache.get_emit_code(&specifier, 10),
+      Some(b"emitted".to_vec())
+    );
+
+    // entries that don't match their checksum aren't used
+    for entry in backend.entries.lock().values_mut() {
+      *entry.last_mut().unwrap() = b'?';
+    }
+    let temp_dir = TempDir::new();
+    let cache = EmitCache::new(DiskCache::new(temp_dir.path().as_path()))
+      .with_backend(backend.clone());
+    assert_eq!(
+      cache.get_backend_emit_code(&specifier, 10, "source").await,
+      None
+    );
+  }
 }
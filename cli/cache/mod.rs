@@ -24,6 +24,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::SystemTime;
 
+mod backend;
 mod cache_db;
 mod caches;
 mod check;
@@ -38,6 +39,8 @@ mod module_info;
 mod node;
 mod parsed_source;
 
+pub use backend::HttpModuleCacheBackend;
+pub use backend::ModuleCacheBackend;
 pub use cache_db::CacheDBHash;
 pub use caches::Caches;
 pub use check::TypeCheckCache;
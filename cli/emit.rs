@@ -3,6 +3,7 @@
 use crate::cache::EmitCache;
 use crate::cache::FastInsecureHasher;
 use crate::cache::ParsedSourceCache;
+use crate::util::checksum;
 
 use deno_ast::SourceMapOption;
 use deno_ast::SourceRange;
@@ -109,6 +110,20 @@ impl Emitter {
     match helper.pre_emit_parsed_source(specifier, source) {
       PreEmitResult::Cached(emitted_text) => Ok(emitted_text),
       PreEmitResult::NotCached { source_hash } => {
+        // only the async version uses the backend, which goes over the network
+        let maybe_source_checksum = self
+          .emit_cache
+          .has_backend()
+          .then(|| self.get_source_checksum(source));
+        if let Some(source_checksum) = &maybe_source_checksum {
+          if let Some(emit_code) = self
+            .emit_cache
+            .get_backend_emit_code(specifier, source_hash, source_checksum)
+            .await
+          {
+            return Ok(emit_code.into_boxed_slice().into());
+          }
+        }
         let parsed_source_cache = self.parsed_source_cache.clone();
         let transpile_and_emit_options =
           self.transpile_and_emit_options.clone();
@@ -129,12 +144,21 @@ impl Emitter {
           })
           .await
           .unwrap()?;
-        Ok(helper.post_emit_parsed_source(
+        let emit_code = helper.post_emit_parsed_source(
           specifier,
           transpile_result,
           source_hash,
           should_cache,
-        ))
+        );
+        if let (true, Some(source_checksum)) =
+          (should_cache, &maybe_source_checksum)
+        {
+          self
+            .emit_cache
+            .set_backend_emit_code(specifier, source_checksum, &emit_code)
+            .await;
+        }
+        Ok(emit_code.into())
       }
     }
   }
@@ -159,12 +183,16 @@ impl Emitter {
             &self.transpile_and_emit_options.0,
             &self.transpile_and_emit_options.1,
           )?;
-        Ok(helper.post_emit_parsed_source(
-          specifier,
-          transpile_result,
-          source_hash,
-          should_cache,
-        ))
+        Ok(
+          helper
+            .post_emit_parsed_source(
+              specifier,
+              transpile_result,
+              source_hash,
+              should_cache,
+            )
+            .into(),
+        )
       }
     }
   }
@@ -227,6 +255,15 @@ impl Emitter {
       .write_u64(self.transpile_and_emit_options_hash)
       .finish()
   }
+
+  /// A SHA-256 checksum of the source code and the global emit options,
+  /// which keys emits in a cache backend shared with other machines.
+  fn get_source_checksum(&self, source_text: &str) -> String {
+    checksum::gen(&[
+      source_text.as_bytes(),
+      &self.transpile_and_emit_options_hash.to_le_bytes(),
+    ])
+  }
 }
 
 enum PreEmitResult {
@@ -280,7 +317,7 @@ impl<'a> EmitParsedSourceHelper<'a> {
     source_hash: u64,
     // todo(dsherret): remove after Deno 2.0
     should_cache: bool,
-  ) -> ModuleCodeBytes {
+  ) -> Box<[u8]> {
     let transpiled_source = match transpile_result {
       TranspileResult::Owned(source) => source,
       TranspileResult::Cloned(source) => {
@@ -296,7 +333,7 @@ impl<'a> EmitParsedSourceHelper<'a> {
         &transpiled_source.source,
       );
     }
-    transpiled_source.source.into_boxed_slice().into()
+    transpiled_source.source.into_boxed_slice()
   }
 }
 
@@ -4,6 +4,7 @@ use crate::args::get_root_cert_store;
 use crate::args::CaData;
 use crate::args::CliOptions;
 use crate::args::DenoSubcommand;
+use crate::args::DeprecationMode;
 use crate::args::Flags;
 use crate::args::PackageJsonInstallDepsProvider;
 use crate::args::StorageKeyResolver;
@@ -15,11 +16,14 @@ use crate::cache::DenoDirProvider;
 use crate::cache::EmitCache;
 use crate::cache::GlobalHttpCache;
 use crate::cache::HttpCache;
+use crate::cache::HttpModuleCacheBackend;
 use crate::cache::LocalHttpCache;
+use crate::cache::ModuleCacheBackend;
 use crate::cache::ModuleInfoCache;
 use crate::cache::NodeAnalysisCache;
 use crate::cache::ParsedSourceCache;
 use crate::emit::Emitter;
+use crate::file_fetcher::File;
 use crate::file_fetcher::FileFetcher;
 use crate::graph_container::MainModuleGraphContainer;
 use crate::graph_util::FileWatcherReporter;
@@ -36,6 +40,8 @@ use crate::npm::CliNpmResolverByonmCreateOptions;
 use crate::npm::CliNpmResolverCreateOptions;
 use crate::npm::CliNpmResolverManagedCreateOptions;
 use crate::npm::CliNpmResolverManagedSnapshotOption;
+use crate::npm::NpmOverrides;
+use crate::npm::NpmRegistrySnapshot;
 use crate::resolver::CjsResolutionStore;
 use crate::resolver::CliGraphResolver;
 use crate::resolver::CliGraphResolverOptions;
@@ -43,12 +49,14 @@ use crate::resolver::CliNodeResolver;
 use crate::resolver::NpmModuleLoader;
 use crate::resolver::SloppyImportsResolver;
 use crate::standalone::DenoCompileBinaryWriter;
+use crate::tools::check::IncrementalCheckState;
 use crate::tools::check::TypeChecker;
 use crate::tools::coverage::CoverageCollector;
 use crate::tools::lint::LintRuleProvider;
 use crate::tools::run::hmr::HmrRunner;
 use crate::util::file_watcher::WatcherCommunicator;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
+use crate::util::overlay_fs::OverlayFileSystem;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 use crate::worker::CliMainWorkerFactory;
@@ -60,6 +68,7 @@ use deno_config::workspace::WorkspaceResolver;
 use deno_core::error::AnyError;
 use deno_core::futures::FutureExt;
 use deno_core::FeatureChecker;
+use deno_core::ModuleSpecifier;
 
 use deno_runtime::deno_fs;
 use deno_runtime::deno_node::DenoFsNodeResolverEnv;
@@ -166,6 +175,7 @@ struct CliFactoryServices {
   emit_cache: Deferred<Arc<EmitCache>>,
   emitter: Deferred<Arc<Emitter>>,
   fs: Deferred<Arc<dyn deno_fs::FileSystem>>,
+  vfs_overlay: Deferred<Option<Arc<OverlayFileSystem>>>,
   main_graph_container: Deferred<Arc<MainModuleGraphContainer>>,
   maybe_inspector_server: Deferred<Option<Arc<InspectorServer>>>,
   root_cert_store_provider: Deferred<Arc<dyn RootCertStoreProvider>>,
@@ -192,6 +202,8 @@ struct CliFactoryServices {
 
 pub struct CliFactory {
   watcher_communicator: Option<Arc<WatcherCommunicator>>,
+  maybe_incremental_check_state: Option<Arc<IncrementalCheckState>>,
+  maybe_module_cache_backend: Option<Arc<dyn ModuleCacheBackend>>,
   flags: Arc<Flags>,
   services: CliFactoryServices,
 }
@@ -201,6 +213,8 @@ impl CliFactory {
     Self {
       flags,
       watcher_communicator: None,
+      maybe_incremental_check_state: None,
+      maybe_module_cache_backend: None,
       services: Default::default(),
     }
   }
@@ -209,6 +223,8 @@ impl CliFactory {
     let (cli_options, flags) = cli_options.into_self_and_flags();
     CliFactory {
       watcher_communicator: None,
+      maybe_incremental_check_state: None,
+      maybe_module_cache_backend: None,
       flags,
       services: CliFactoryServices {
         cli_options: Deferred::from_value(cli_options),
@@ -223,11 +239,32 @@ impl CliFactory {
   ) -> Self {
     CliFactory {
       watcher_communicator: Some(watcher_communicator),
+      maybe_incremental_check_state: None,
+      maybe_module_cache_backend: None,
       flags,
       services: Default::default(),
     }
   }
 
+  /// Reuses the type checking results of previous factories that were given
+  /// the same state, like the ones of earlier watcher iterations.
+  pub fn set_incremental_check_state(
+    &mut self,
+    state: Arc<IncrementalCheckState>,
+  ) {
+    self.maybe_incremental_check_state = Some(state);
+  }
+
+  /// Shares emitted modules through `backend`, in addition to the local
+  /// cache. Takes precedence over `--cache-backend`.
+  #[allow(dead_code)]
+  pub fn set_module_cache_backend(
+    &mut self,
+    backend: Arc<dyn ModuleCacheBackend>,
+  ) {
+    self.maybe_module_cache_backend = Some(backend);
+  }
+
   pub fn cli_options(&self) -> Result<&Arc<CliOptions>, AnyError> {
     self.services.cli_options.get_or_try_init(|| {
       CliOptions::from_flags(self.flags.clone()).map(Arc::new)
@@ -259,7 +296,9 @@ impl CliFactory {
             _ = caches.fast_check_db();
             _ = caches.type_checking_cache_db();
           }
-          if cli_options.code_cache_enabled() {
+          if cli_options.code_cache_enabled()
+            && cli_options.code_cache_path().is_none()
+          {
             _ = caches.code_cache_db();
           }
         }
@@ -315,24 +354,52 @@ impl CliFactory {
 
   pub fn http_client_provider(&self) -> &Arc<HttpClientProvider> {
     self.services.http_client_provider.get_or_init(|| {
-      Arc::new(HttpClientProvider::new(
+      let provider = HttpClientProvider::new(
         Some(self.root_cert_store_provider().clone()),
         self.flags.unsafely_ignore_certificate_errors.clone(),
-      ))
+      );
+      let provider = if self.flags.module_http1_only {
+        provider.with_http1_only()
+      } else {
+        provider
+      };
+      let provider = match self.flags.max_redirect_bytes {
+        Some(max_bytes) => provider.with_max_redirect_bytes(max_bytes),
+        None => provider,
+      };
+      Arc::new(match self.flags.module_fetch_retries {
+        Some(retries) => provider.with_server_error_retries(retries),
+        None => provider,
+      })
     })
   }
 
   pub fn file_fetcher(&self) -> Result<&Arc<FileFetcher>, AnyError> {
     self.services.file_fetcher.get_or_try_init(|| {
       let cli_options = self.cli_options()?;
-      Ok(Arc::new(FileFetcher::new(
+      let mut file_fetcher = FileFetcher::new(
         self.http_cache()?.clone(),
         cli_options.cache_setting(),
         !cli_options.no_remote(),
         self.http_client_provider().clone(),
         self.blob_store().clone(),
         Some(self.text_only_progress_bar().clone()),
-      )))
+      );
+      file_fetcher.add_remote_auth(cli_options.remote_auth());
+      file_fetcher.set_download_concurrency(
+        self.flags.module_fetch_concurrency,
+        self.flags.module_fetch_concurrency_per_host,
+      );
+      if let Some(overlay) = self.vfs_overlay()? {
+        for (path, data) in overlay.files() {
+          file_fetcher.insert_memory_files(File {
+            specifier: ModuleSpecifier::from_file_path(&path).unwrap(),
+            maybe_headers: None,
+            source: data.into(),
+          });
+        }
+      }
+      Ok(Arc::new(file_fetcher))
     })
   }
 
@@ -340,6 +407,18 @@ impl CliFactory {
     self.services.fs.get_or_init(|| Arc::new(deno_fs::RealFs))
   }
 
+  pub fn vfs_overlay(
+    &self,
+  ) -> Result<&Option<Arc<OverlayFileSystem>>, AnyError> {
+    self.services.vfs_overlay.get_or_try_init(|| {
+      let cli_options = self.cli_options()?;
+      Ok(match cli_options.vfs_overlay() {
+        Some(path) => Some(Arc::new(OverlayFileSystem::load(&path)?)),
+        None => None,
+      })
+    })
+  }
+
   pub async fn npm_resolver(
     &self,
   ) -> Result<&Arc<dyn CliNpmResolver>, AnyError> {
@@ -389,6 +468,13 @@ impl CliFactory {
             npm_system_info: cli_options.npm_system_info(),
             npmrc: cli_options.npmrc().clone(),
             lifecycle_scripts: cli_options.lifecycle_scripts_config(),
+            node_modules_layout: cli_options.node_modules_layout(),
+            maybe_registry_snapshot: cli_options
+              .npm_snapshot()
+              .map(|path| NpmRegistrySnapshot::from_file(&path).map(Arc::new))
+              .transpose()?,
+            overrides: Arc::new(NpmOverrides::from_workspace(cli_options.workspace())?),
+            record_contents: cli_options.verify_npm_contents(),
           })
         }).await
       }.boxed_local())
@@ -469,6 +555,7 @@ impl CliFactory {
               .workspace()
               .to_maybe_jsx_import_source_config()?,
             maybe_vendor_dir: cli_options.vendor_dir_path(),
+            maybe_import_allow_list: cli_options.import_allow_list()?,
           })))
         }
         .boxed_local(),
@@ -489,7 +576,21 @@ impl CliFactory {
 
   pub fn emit_cache(&self) -> Result<&Arc<EmitCache>, AnyError> {
     self.services.emit_cache.get_or_try_init(|| {
-      Ok(Arc::new(EmitCache::new(self.deno_dir()?.gen_cache.clone())))
+      let emit_cache = EmitCache::new(self.deno_dir()?.gen_cache.clone());
+      let maybe_backend = match &self.maybe_module_cache_backend {
+        Some(backend) => Some(backend.clone()),
+        None => match self.cli_options()?.cache_backend()? {
+          Some(url) => Some(Arc::new(HttpModuleCacheBackend::new(
+            url,
+            self.http_client_provider().clone(),
+          )?) as Arc<dyn ModuleCacheBackend>),
+          None => None,
+        },
+      };
+      Ok(Arc::new(match maybe_backend {
+        Some(backend) => emit_cache.with_backend(backend),
+        None => emit_cache,
+      }))
     })
   }
 
@@ -503,7 +604,12 @@ impl CliFactory {
 
   pub fn code_cache(&self) -> Result<&Arc<CodeCache>, AnyError> {
     self.services.code_cache.get_or_try_init(|| {
-      Ok(Arc::new(CodeCache::new(self.caches()?.code_cache_db())))
+      let caches = self.caches()?;
+      let db = match self.cli_options()?.code_cache_path() {
+        Some(path) => caches.code_cache_db_at(path),
+        None => caches.code_cache_db(),
+      };
+      Ok(Arc::new(CodeCache::new(db)))
     })
   }
 
@@ -590,6 +696,7 @@ impl CliFactory {
         Ok(Arc::new(TypeChecker::new(
           self.caches()?.clone(),
           cli_options.clone(),
+          self.maybe_incremental_check_state.clone(),
           self.module_graph_builder().await?.clone(),
           self.node_resolver().await?.clone(),
           self.npm_resolver().await?.clone(),
@@ -785,19 +892,26 @@ impl CliFactory {
         self.resolver().await?.clone(),
       )),
       self.root_cert_store_provider().clone(),
-      self.fs().clone(),
+      match self.vfs_overlay()? {
+        Some(overlay) => overlay.clone() as Arc<dyn deno_fs::FileSystem>,
+        None => self.fs().clone(),
+      },
       maybe_file_watcher_communicator,
       self.maybe_inspector_server()?.clone(),
       cli_options.maybe_lockfile().cloned(),
       self.feature_checker()?.clone(),
-      self.create_cli_main_worker_options()?,
+      self.create_cli_main_worker_options().await?,
       cli_options.node_ipc_fd(),
       cli_options.serve_port(),
       cli_options.serve_host(),
       cli_options.enable_future_features(),
-      // TODO(bartlomieju): temporarily disabled
-      // cli_options.disable_deprecated_api_warning,
-      true,
+      match cli_options.deprecation() {
+        Some(DeprecationMode::Warn | DeprecationMode::Error) => false,
+        Some(DeprecationMode::Silent) => true,
+        // TODO(bartlomieju): temporarily disabled
+        // cli_options.disable_deprecated_api_warning,
+        None => true,
+      },
       cli_options.verbose_deprecated_api_warning,
       if cli_options.code_cache_enabled() {
         Some(self.code_cache()?.clone())
@@ -807,18 +921,24 @@ impl CliFactory {
     ))
   }
 
-  fn create_cli_main_worker_options(
+  async fn create_cli_main_worker_options(
     &self,
   ) -> Result<CliMainWorkerOptions, AnyError> {
     let cli_options = self.cli_options()?;
     let create_hmr_runner = if cli_options.has_hmr() {
       let watcher_communicator = self.watcher_communicator.clone().unwrap();
       let emitter = self.emitter()?.clone();
+      let maybe_reevaluate_graph = if cli_options.has_hmr_reevaluate() {
+        Some(self.main_module_graph_container().await?.clone())
+      } else {
+        None
+      };
       let fn_: crate::worker::CreateHmrRunnerCb = Box::new(move |session| {
         Box::new(HmrRunner::new(
           emitter.clone(),
           session,
           watcher_communicator.clone(),
+          maybe_reevaluate_graph.clone(),
         ))
       });
       Some(fn_)
@@ -844,6 +964,9 @@ impl CliFactory {
       // integration.
       skip_op_registration: cli_options.sub_command().is_run(),
       log_level: cli_options.log_level().unwrap_or(log::Level::Info).into(),
+      check_resource_leaks: cli_options.check_resource_leaks(),
+      exit_drain: cli_options.exit_drain(),
+      max_redirect_bytes: cli_options.max_redirect_bytes(),
       enable_op_summary_metrics: cli_options.enable_op_summary_metrics(),
       enable_testing_features: cli_options.enable_testing_features(),
       has_node_modules_dir: cli_options.has_node_modules_dir(),
@@ -868,6 +991,11 @@ impl CliFactory {
       unstable: cli_options.legacy_unstable_flag(),
       create_hmr_runner,
       create_coverage_collector,
+      cpu_prof: cli_options.cpu_prof(),
+      force_strict: cli_options.force_strict(),
+      deprecated_api_error: cli_options.deprecation()
+        == Some(DeprecationMode::Error),
+      abort_on_warning: cli_options.abort_on_warning(),
     })
   }
 }
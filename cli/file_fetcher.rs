@@ -4,11 +4,13 @@ use crate::args::CacheSetting;
 use crate::auth_tokens::AuthTokens;
 use crate::cache::HttpCache;
 use crate::colors;
+use crate::http_util::status_with_body_snippet;
 use crate::http_util::CacheSemantics;
 use crate::http_util::FetchOnceArgs;
 use crate::http_util::FetchOnceResult;
 use crate::http_util::HttpClientProvider;
 use crate::util::progress_bar::ProgressBar;
+use crate::util::progress_bar::PROGRESS_LOG_TARGET;
 
 use deno_ast::MediaType;
 use deno_core::anyhow::bail;
@@ -22,6 +24,7 @@ use deno_core::url::Url;
 use deno_core::ModuleSpecifier;
 use deno_graph::source::LoaderChecksum;
 
+use deno_runtime::deno_fetch::RedirectChain;
 use deno_runtime::deno_permissions::PermissionsContainer;
 use deno_runtime::deno_web::BlobStore;
 use log::debug;
@@ -29,9 +32,12 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::env;
 use std::fs;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::SystemTime;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
 
 pub const SUPPORTED_SCHEMES: [&str; 5] =
   ["data", "blob", "file", "http", "https"];
@@ -169,6 +175,14 @@ pub struct FileFetcher {
   blob_store: Arc<BlobStore>,
   download_log_level: log::Level,
   progress_bar: Option<ProgressBar>,
+  /// Redirect chains of the specifiers that were redirected to, so that the
+  /// bytes read across a chain can be limited.
+  redirect_chains: Mutex<HashMap<ModuleSpecifier, RedirectChain>>,
+  /// Bounds the number of remote files downloaded at once.
+  download_permits: Option<Arc<Semaphore>>,
+  /// How many remote files may be downloaded at once from a single host.
+  max_downloads_per_host: Option<NonZeroUsize>,
+  download_permits_by_host: Mutex<HashMap<String, Arc<Semaphore>>>,
 }
 
 impl FileFetcher {
@@ -190,6 +204,10 @@ impl FileFetcher {
       blob_store,
       download_log_level: log::Level::Info,
       progress_bar,
+      redirect_chains: Default::default(),
+      download_permits: None,
+      max_downloads_per_host: None,
+      download_permits_by_host: Default::default(),
     }
   }
 
@@ -197,11 +215,64 @@ impl FileFetcher {
     &self.cache_setting
   }
 
+  /// Adds `--remote-auth` authorization values, which take precedence over
+  /// `DENO_AUTH_TOKENS`.
+  pub fn add_remote_auth(&mut self, remote_auth: &[String]) {
+    self.auth_tokens.prepend_remote_auth(remote_auth);
+  }
+
   /// Sets the log level to use when outputting the download message.
   pub fn set_download_log_level(&mut self, level: log::Level) {
     self.download_log_level = level;
   }
 
+  /// Limits how many remote files are downloaded at once, in total and from
+  /// a single host. Other downloads wait for one of them to finish.
+  pub fn set_download_concurrency(
+    &mut self,
+    max_downloads: Option<NonZeroUsize>,
+    max_downloads_per_host: Option<NonZeroUsize>,
+  ) {
+    self.download_permits =
+      max_downloads.map(|max| Arc::new(Semaphore::new(max.get())));
+    self.max_downloads_per_host = max_downloads_per_host;
+  }
+
+  /// Waits until `specifier` may be downloaded without exceeding the
+  /// download concurrency. The returned permits are held for the download.
+  async fn acquire_download_permits(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Vec<OwnedSemaphorePermit> {
+    let mut semaphores = Vec::with_capacity(2);
+    // wait for the host first, so that a download waiting for a busy host
+    // doesn't hold up downloads from other hosts
+    if let Some(max) = self.max_downloads_per_host {
+      let host = match (specifier.host_str(), specifier.port()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (host, None) => host.unwrap_or_default().to_string(),
+        (None, Some(_)) => String::new(),
+      };
+      semaphores.push(
+        self
+          .download_permits_by_host
+          .lock()
+          .entry(host)
+          .or_insert_with(|| Arc::new(Semaphore::new(max.get())))
+          .clone(),
+      );
+    }
+    if let Some(semaphore) = &self.download_permits {
+      semaphores.push(semaphore.clone());
+    }
+    let mut permits = Vec::with_capacity(semaphores.len());
+    for semaphore in semaphores {
+      // the semaphores are never closed
+      permits.push(semaphore.acquire_owned().await.unwrap());
+    }
+    permits
+  }
+
   /// Fetch cached remote file.
   ///
   /// This is a recursive operation if source file has redirections.
@@ -351,11 +422,13 @@ impl FileFetcher {
       ));
     }
 
+    let _download_permits = self.acquire_download_permits(specifier).await;
     let mut maybe_progress_guard = None;
     if let Some(pb) = self.progress_bar.as_ref() {
       maybe_progress_guard = Some(pb.update(specifier.as_str()));
     } else {
       log::log!(
+        target: PROGRESS_LOG_TARGET,
         self.download_log_level,
         "{} {}",
         colors::green("Download"),
@@ -370,6 +443,8 @@ impl FileFetcher {
       .and_then(|key| self.http_cache.read_headers(&key).ok().flatten())
       .and_then(|headers| headers.get("etag").cloned());
     let maybe_auth_token = self.auth_tokens.get(specifier);
+    let maybe_redirect_chain =
+      self.redirect_chains.lock().get(specifier).cloned();
 
     async fn handle_request_or_server_error(
       retried: &mut bool,
@@ -391,7 +466,8 @@ impl FileFetcher {
     }
 
     let mut maybe_etag = maybe_etag;
-    let mut retried = false; // retry intermittent failures
+    // retry intermittent failures, unless the client already retried them
+    let mut retried = self.http_client_provider.retries_requests();
     let result = loop {
       let result = match self
         .http_client_provider
@@ -402,6 +478,7 @@ impl FileFetcher {
           maybe_etag: maybe_etag.clone(),
           maybe_auth_token: maybe_auth_token.clone(),
           maybe_progress_guard: maybe_progress_guard.as_ref(),
+          maybe_redirect_chain: maybe_redirect_chain.clone(),
         })
         .await?
       {
@@ -427,9 +504,14 @@ impl FileFetcher {
         }
         FetchOnceResult::Redirect(redirect_url, headers) => {
           self.http_cache.set(specifier, headers, &[])?;
+          let mut redirect_chains = self.redirect_chains.lock();
+          let redirect_chain =
+            redirect_chains.remove(specifier).unwrap_or_default();
+          redirect_chains.insert(redirect_url.clone(), redirect_chain);
           Ok(FileOrRedirect::Redirect(redirect_url))
         }
         FetchOnceResult::Code(bytes, headers) => {
+          self.redirect_chains.lock().remove(specifier);
           self.http_cache.set(specifier, headers.clone(), &bytes)?;
           if let Some(checksum) = &maybe_checksum {
             checksum.check_source(&bytes)?;
@@ -444,11 +526,11 @@ impl FileFetcher {
           handle_request_or_server_error(&mut retried, specifier, err).await?;
           continue;
         }
-        FetchOnceResult::ServerError(status) => {
+        FetchOnceResult::ServerError(status, snippet) => {
           handle_request_or_server_error(
             &mut retried,
             specifier,
-            status.to_string(),
+            status_with_body_snippet(status, snippet.as_deref()),
           )
           .await?;
           continue;
@@ -574,6 +656,13 @@ impl FileFetcher {
       "FileFetcher::fetch_no_follow_with_options - specifier: {}",
       specifier
     );
+    if let Some(file) = self.memory_files.get(specifier) {
+      // the cli's own in memory modules may use a synthetic scheme
+      // (`--synthetic-scheme`)
+      if !SUPPORTED_SCHEMES.contains(&specifier.scheme()) {
+        return Ok(FileOrRedirect::File(file));
+      }
+    }
     let scheme = get_validated_scheme(specifier)?;
     options.permissions.check_specifier(specifier)?;
     if let Some(file) = self.memory_files.get(specifier) {
@@ -1430,6 +1519,68 @@ mod tests {
     assert_eq!(first, second);
   }
 
+  #[tokio::test]
+  async fn test_fetch_download_concurrency() {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    tokio::spawn({
+      let in_flight = in_flight.clone();
+      let max_in_flight = max_in_flight.clone();
+      async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+          let in_flight = in_flight.clone();
+          let max_in_flight = max_in_flight.clone();
+          tokio::spawn(async move {
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+              let n = socket.read(&mut buf).await.unwrap();
+              if n == 0 {
+                return;
+              }
+              request.extend_from_slice(&buf[..n]);
+            }
+            let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            max_in_flight.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            let body = "export default 1;";
+            let response = format!(
+              "HTTP/1.1 200 OK\r\ncontent-type: application/javascript\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+              body.len(),
+              body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+          });
+        }
+      }
+    });
+
+    let (mut file_fetcher, _) = setup(CacheSetting::ReloadAll, None);
+    file_fetcher.set_download_concurrency(None, NonZeroUsize::new(2));
+    let specifiers = (0..8)
+      .map(|i| resolve_url(&format!("http://127.0.0.1:{port}/mod{i}.js")))
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    let results = deno_core::futures::future::join_all(specifiers.iter().map(
+      |specifier| {
+        file_fetcher.fetch(specifier, &PermissionsContainer::allow_all())
+      },
+    ))
+    .await;
+    for result in results {
+      assert_eq!(&*result.unwrap().source, b"export default 1;");
+    }
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+  }
+
   #[tokio::test]
   async fn test_fetch_local_utf_16be() {
     let expected =
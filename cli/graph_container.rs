@@ -1,15 +1,18 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use deno_ast::ModuleSpecifier;
 use deno_config::glob::FilePatterns;
 use deno_config::glob::PathOrPatternSet;
 use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
 use deno_core::parking_lot::RwLock;
 use deno_graph::ModuleGraph;
 use deno_runtime::colors;
 use deno_runtime::deno_permissions::PermissionsContainer;
+use indexmap::IndexSet;
 
 use crate::args::CliOptions;
 use crate::module_loader::ModuleLoadPreparer;
@@ -43,6 +46,11 @@ pub struct MainModuleGraphContainer {
   // while another request is updating the data.
   update_queue: Arc<crate::util::sync::TaskQueue>,
   inner: Arc<RwLock<Arc<ModuleGraph>>>,
+  /// Fresh instances of modules that `--watch-reevaluate` imported again,
+  /// like `file:///mod.ts?reevaluate=1`.
+  reevaluated: Arc<Mutex<HashSet<ModuleSpecifier>>>,
+  /// Roots of the graphs dropped by [`Self::reset`].
+  previous_roots: Arc<Mutex<IndexSet<ModuleSpecifier>>>,
   cli_options: Arc<CliOptions>,
   module_load_preparer: Arc<ModuleLoadPreparer>,
 }
@@ -57,11 +65,60 @@ impl MainModuleGraphContainer {
       inner: Arc::new(RwLock::new(Arc::new(ModuleGraph::new(
         cli_options.graph_kind(),
       )))),
+      reevaluated: Default::default(),
+      previous_roots: Default::default(),
       cli_options,
       module_load_preparer,
     }
   }
 
+  /// Starts over with an empty graph, so that the next `--then` script
+  /// doesn't share modules with the previous one.
+  pub fn reset(&self) {
+    let mut inner = self.inner.write();
+    self
+      .previous_roots
+      .lock()
+      .extend(inner.roots.iter().cloned());
+    *inner = Arc::new(ModuleGraph::new(self.cli_options.graph_kind()));
+    self.reevaluated.lock().clear();
+  }
+
+  /// The roots of every graph loaded so far, including the ones dropped by
+  /// [`Self::reset`].
+  pub fn loaded_roots(&self) -> Vec<ModuleSpecifier> {
+    let mut roots = self.previous_roots.lock().clone();
+    roots.extend(self.inner.read().roots.iter().cloned());
+    roots.into_iter().collect()
+  }
+
+  /// Records fresh instances of modules that are about to be imported again
+  /// with a `reevaluate=<generation>` query.
+  pub fn add_reevaluated_modules(
+    &self,
+    specifiers: impl IntoIterator<Item = ModuleSpecifier>,
+  ) {
+    self.reevaluated.lock().extend(specifiers);
+  }
+
+  /// Resolves an import of a re-evaluated module to the fresh instance of
+  /// the same generation, so that it doesn't see the stale exports of the
+  /// original module.
+  pub fn resolve_reevaluated(
+    &self,
+    specifier: &ModuleSpecifier,
+    referrer: &ModuleSpecifier,
+  ) -> Option<ModuleSpecifier> {
+    let query = referrer.query().filter(|q| q.starts_with("reevaluate="))?;
+    let mut fresh_specifier = specifier.clone();
+    fresh_specifier.set_query(Some(query));
+    self
+      .reevaluated
+      .lock()
+      .contains(&fresh_specifier)
+      .then_some(fresh_specifier)
+  }
+
   pub async fn check_specifiers(
     &self,
     specifiers: &[ModuleSpecifier],
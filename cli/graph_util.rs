@@ -115,7 +115,7 @@ pub fn graph_valid(
       };
 
       if let Some(range) = error.maybe_range() {
-        if !is_root && !range.specifier.as_str().contains("/$deno$eval") {
+        if !is_root && !range.specifier.as_str().contains("$deno$eval") {
           message.push_str("\n    at ");
           message.push_str(&format_range_with_colors(range));
         }
@@ -18,6 +18,7 @@ use deno_core::url::Url;
 use deno_runtime::deno_fetch;
 use deno_runtime::deno_fetch::create_http_client;
 use deno_runtime::deno_fetch::CreateHttpClientOptions;
+use deno_runtime::deno_fetch::RedirectChain;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use http::header::HeaderName;
 use http::header::HeaderValue;
@@ -195,7 +196,9 @@ pub enum FetchOnceResult {
   NotModified,
   Redirect(Url, HeadersMap),
   RequestError(String),
-  ServerError(StatusCode),
+  /// The server responded with a 5xx status, with the start of the body it
+  /// sent if it wasn't empty.
+  ServerError(StatusCode, Option<String>),
 }
 
 #[derive(Debug)]
@@ -205,6 +208,8 @@ pub struct FetchOnceArgs<'a> {
   pub maybe_etag: Option<String>,
   pub maybe_auth_token: Option<AuthToken>,
   pub maybe_progress_guard: Option<&'a UpdateGuard>,
+  /// The redirect chain this request is part of, if it was redirected to.
+  pub maybe_redirect_chain: Option<RedirectChain>,
 }
 
 pub struct HttpClientProvider {
@@ -239,6 +244,38 @@ impl HttpClientProvider {
     }
   }
 
+  /// Only negotiate HTTP/1.1, for module hosts that misbehave over HTTP/2.
+  /// This doesn't affect the client used by `fetch()` in user code.
+  pub fn with_http1_only(mut self) -> Self {
+    self.options.http2 = false;
+    self
+  }
+
+  /// Fails downloads that read more than `max_bytes` from the responses of
+  /// a redirect chain.
+  pub fn with_max_redirect_bytes(mut self, max_bytes: u64) -> Self {
+    self.options.max_redirect_bytes = Some(max_bytes);
+    self
+  }
+
+  /// Retries module downloads that fail with a 5xx status up to `retries`
+  /// times, backing off between attempts.
+  pub fn with_server_error_retries(mut self, retries: u32) -> Self {
+    self.options.retry = Some(deno_fetch::RetryConfig {
+      max_attempts: retries.saturating_add(1),
+      retry_statuses: (500..600)
+        .filter_map(|code| StatusCode::from_u16(code).ok())
+        .collect(),
+      ..Default::default()
+    });
+    self
+  }
+
+  /// Whether the clients already retry failed requests themselves.
+  pub fn retries_requests(&self) -> bool {
+    self.options.retry.is_some()
+  }
+
   pub fn get_or_create(&self) -> Result<HttpClient, AnyError> {
     use std::collections::hash_map::Entry;
     let thread_id = std::thread::current().id();
@@ -337,6 +374,20 @@ impl HttpClient {
     })
   }
 
+  pub fn put(
+    &self,
+    url: Url,
+    body: deno_fetch::ReqBody,
+  ) -> Result<RequestBuilder, http::Error> {
+    let mut req = http::Request::new(body);
+    *req.method_mut() = http::Method::PUT;
+    *req.uri_mut() = url.as_str().parse()?;
+    Ok(RequestBuilder {
+      client: self.client.clone(),
+      req,
+    })
+  }
+
   pub fn post_json<S>(
     &self,
     url: Url,
@@ -370,6 +421,9 @@ impl HttpClient {
       .boxed();
     let mut request = http::Request::new(body);
     *request.uri_mut() = args.url.as_str().parse()?;
+    if let Some(redirect_chain) = args.maybe_redirect_chain {
+      request.extensions_mut().insert(redirect_chain);
+    }
 
     if let Some(etag) = args.maybe_etag {
       let if_none_match_val = HeaderValue::from_str(&etag)?;
@@ -431,20 +485,22 @@ impl HttpClient {
     let status = response.status();
 
     if status.is_server_error() {
-      return Ok(FetchOnceResult::ServerError(status));
+      let snippet = response_body_snippet(response).await;
+      return Ok(FetchOnceResult::ServerError(status, snippet));
     }
 
     if status.is_client_error() {
-      let err = if response.status() == StatusCode::NOT_FOUND {
+      let err = if status == StatusCode::NOT_FOUND {
         custom_error(
           "NotFound",
           format!("Import '{}' failed, not found.", args.url),
         )
       } else {
+        let snippet = response_body_snippet(response).await;
         generic_error(format!(
           "Import '{}' failed: {}",
           args.url,
-          response.status()
+          status_with_body_snippet(status, snippet.as_deref())
         ))
       };
       return Err(err);
@@ -534,9 +590,11 @@ impl HttpClient {
       .map_err(|e| DownloadError::Fetch(e.into()))?;
     let status = response.status();
     if status.is_redirection() {
+      let redirect_chain = RedirectChain::default();
       for _ in 0..5 {
         let new_url = resolve_redirect_from_response(&url, &response)?;
         let mut req = self.get(new_url.clone())?.build();
+        req.extensions_mut().insert(redirect_chain.clone());
 
         if new_url.origin() == url.origin() {
           if let Some((header_name, header_value)) = maybe_header.as_ref() {
@@ -567,6 +625,52 @@ impl HttpClient {
   }
 }
 
+/// How many characters of an error response's body are shown in the error.
+const BODY_SNIPPET_MAX_CHARS: usize = 200;
+
+/// Reads the start of an error response's body for the error message, with
+/// runs of whitespace collapsed into a single space. Returns `None` when the
+/// body is empty or can't be read.
+async fn response_body_snippet(
+  response: http::Response<deno_fetch::ResBody>,
+) -> Option<String> {
+  let mut stream = response.into_body().into_data_stream();
+  let mut bytes = Vec::new();
+  // four bytes per character is enough for any UTF-8 text
+  while bytes.len() < BODY_SNIPPET_MAX_CHARS * 4 {
+    match stream.next().await {
+      Some(Ok(chunk)) => bytes.extend_from_slice(&chunk),
+      _ => break,
+    }
+  }
+  let text = String::from_utf8_lossy(&bytes);
+  let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+  if text.is_empty() {
+    return None;
+  }
+  let mut chars = text.chars();
+  let mut snippet = chars
+    .by_ref()
+    .take(BODY_SNIPPET_MAX_CHARS)
+    .collect::<String>();
+  if chars.next().is_some() {
+    snippet.push('…');
+  }
+  Some(snippet)
+}
+
+/// Formats an error status for an import error, followed by the start of the
+/// response body when there is one.
+pub fn status_with_body_snippet(
+  status: StatusCode,
+  snippet: Option<&str>,
+) -> String {
+  match snippet {
+    Some(snippet) => format!("{status}\n    Response body: {snippet}"),
+    None => status.to_string(),
+  }
+}
+
 async fn get_response_body_with_progress(
   response: http::Response<deno_fetch::ResBody>,
   progress_guard: Option<&UpdateGuard>,
@@ -784,6 +888,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -810,6 +915,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -837,6 +943,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -858,6 +965,7 @@ mod test {
         maybe_etag: Some("33a64df551425fcc55e".to_string()),
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     assert_eq!(res.unwrap(), FetchOnceResult::NotModified);
@@ -877,6 +985,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -906,6 +1015,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, _)) = result {
@@ -931,6 +1041,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Redirect(url, _)) = result {
@@ -966,6 +1077,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -1013,6 +1125,7 @@ mod test {
           maybe_etag: None,
           maybe_auth_token: None,
           maybe_progress_guard: None,
+          maybe_redirect_chain: None,
         })
         .await;
 
@@ -1027,7 +1140,7 @@ mod test {
           | FetchOnceResult::Redirect(..),
         ) => return,
         Ok(
-          FetchOnceResult::RequestError(_) | FetchOnceResult::ServerError(_),
+          FetchOnceResult::RequestError(_) | FetchOnceResult::ServerError(..),
         ) => {
           eprintln!("HTTP error: {result:?}");
           continue;
@@ -1075,6 +1188,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
 
@@ -1091,7 +1205,7 @@ mod test {
         panic!("Should not have successfully fetched a URL");
       }
       Ok(
-        FetchOnceResult::RequestError(_) | FetchOnceResult::ServerError(_),
+        FetchOnceResult::RequestError(_) | FetchOnceResult::ServerError(..),
       ) => {
         eprintln!("HTTP error (expected): {result:?}");
         return;
@@ -1128,6 +1242,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -1169,6 +1284,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -1191,6 +1307,7 @@ mod test {
         maybe_etag: Some("33a64df551425fcc55e".to_string()),
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     assert_eq!(res.unwrap(), FetchOnceResult::NotModified);
@@ -1225,6 +1342,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     if let Ok(FetchOnceResult::Code(body, headers)) = result {
@@ -1254,6 +1372,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
     assert!(result.is_err());
@@ -1275,11 +1394,45 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
 
-    if let Ok(FetchOnceResult::ServerError(status)) = result {
+    if let Ok(FetchOnceResult::ServerError(status, snippet)) = result {
       assert_eq!(status, 500);
+      assert_eq!(snippet, None);
+    } else {
+      panic!();
+    }
+  }
+
+  #[tokio::test]
+  async fn server_error_with_body() {
+    let _g = test_util::http_server();
+    let url_str = "http://127.0.0.1:4545/server_error_with_body.js";
+    let url = Url::parse(url_str).unwrap();
+    let client = create_test_client();
+    let result = client
+      .fetch_no_follow(FetchOnceArgs {
+        url,
+        maybe_accept: None,
+        maybe_etag: None,
+        maybe_auth_token: None,
+        maybe_progress_guard: None,
+        maybe_redirect_chain: None,
+      })
+      .await;
+
+    if let Ok(FetchOnceResult::ServerError(status, snippet)) = result {
+      assert_eq!(status, 500);
+      assert_eq!(
+        snippet.as_deref(),
+        Some("<html> <body>Database unavailable</body> </html>")
+      );
+      assert_eq!(
+        status_with_body_snippet(status, snippet.as_deref()),
+        "500 Internal Server Error\n    Response body: <html> <body>Database unavailable</body> </html>"
+      );
     } else {
       panic!();
     }
@@ -1298,6 +1451,7 @@ mod test {
         maybe_etag: None,
         maybe_auth_token: None,
         maybe_progress_guard: None,
+        maybe_redirect_chain: None,
       })
       .await;
 
@@ -0,0 +1,148 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Hooks for `--record-nondeterminism` and `--replay-nondeterminism`. Every
+// source of randomness, every clock read and every `fetch()` response goes
+// through the trace, so that a replayed run sees exactly the inputs of the
+// recorded one.
+
+import { core, primordials } from "ext:core/mod.js";
+import {
+  forgivingBase64Decode,
+  forgivingBase64Encode,
+} from "ext:deno_web/00_infra.js";
+import { Response } from "ext:deno_fetch/23_response.js";
+
+// TODO(mmastrac): We cannot import these from "ext:core/ops" yet
+const {
+  op_nondeterminism_fill,
+  op_nondeterminism_is_replay,
+  op_nondeterminism_record,
+  op_nondeterminism_replay,
+} = core.ops;
+const {
+  ArrayFrom,
+  ArrayPrototypeIncludes,
+  ObjectDefineProperty,
+  ReflectConstruct,
+  TypedArrayPrototypeGetBuffer,
+  TypedArrayPrototypeGetByteLength,
+  TypedArrayPrototypeGetByteOffset,
+  TypedArrayPrototypeSet,
+  Uint8Array,
+} = primordials;
+
+const isReplay = op_nondeterminism_is_replay();
+
+/**
+ * Returns the next recorded value of `kind` when replaying, otherwise calls
+ * `fn` and records what it returned.
+ * @template T
+ * @param {"random" | "time"} kind
+ * @param {() => T} fn
+ * @returns {T}
+ */
+function traced(kind, fn) {
+  if (isReplay) {
+    return op_nondeterminism_replay(kind);
+  }
+  const value = fn();
+  op_nondeterminism_record(kind, value);
+  return value;
+}
+
+function bytesOf(typedArray) {
+  return new Uint8Array(
+    TypedArrayPrototypeGetBuffer(typedArray),
+    TypedArrayPrototypeGetByteOffset(typedArray),
+    TypedArrayPrototypeGetByteLength(typedArray),
+  );
+}
+
+// randomness
+const MathRandom = Math.random;
+Math.random = function random() {
+  return traced("random", () => MathRandom());
+};
+
+const crypto = globalThis.crypto;
+const getRandomValues = crypto.getRandomValues;
+crypto.getRandomValues = function (typedArray) {
+  const bytes = traced(
+    "random",
+    () => {
+      getRandomValues.call(crypto, typedArray);
+      return forgivingBase64Encode(bytesOf(typedArray));
+    },
+  );
+  if (isReplay) {
+    TypedArrayPrototypeSet(bytesOf(typedArray), forgivingBase64Decode(bytes));
+  }
+  return typedArray;
+};
+const randomUUID = crypto.randomUUID;
+crypto.randomUUID = function () {
+  return traced("random", () => randomUUID.call(crypto));
+};
+
+// time
+const OriginalDate = globalThis.Date;
+const DateNow = OriginalDate.now;
+function now() {
+  return traced("time", () => DateNow());
+}
+
+function Date(...args) {
+  if (new.target === undefined) {
+    // `Date()` returns the current time as a string
+    return new OriginalDate(now()).toString();
+  }
+  if (args.length === 0) {
+    return ReflectConstruct(OriginalDate, [now()], new.target);
+  }
+  return ReflectConstruct(OriginalDate, args, new.target);
+}
+Date.prototype = OriginalDate.prototype;
+Date.now = now;
+Date.parse = OriginalDate.parse;
+Date.UTC = OriginalDate.UTC;
+ObjectDefineProperty(OriginalDate.prototype, "constructor", {
+  __proto__: null,
+  value: Date,
+  writable: true,
+  configurable: true,
+  enumerable: false,
+});
+globalThis.Date = Date;
+
+// network
+const originalFetch = globalThis.fetch;
+const NULL_BODY_STATUSES = [204, 205, 304];
+
+function responseFromRecord(record) {
+  const body = ArrayPrototypeIncludes(NULL_BODY_STATUSES, record.status)
+    ? null
+    : forgivingBase64Decode(record.body);
+  return new Response(body, {
+    status: record.status,
+    statusText: record.statusText,
+    headers: record.headers,
+  });
+}
+
+globalThis.fetch = async function fetch(input, init) {
+  if (isReplay) {
+    return responseFromRecord(op_nondeterminism_replay("fetch"));
+  }
+  // reserve the slot now so that concurrent requests replay in call order
+  const index = op_nondeterminism_record("fetch", null);
+  const response = await originalFetch(input, init);
+  const body = new Uint8Array(await response.arrayBuffer());
+  const record = {
+    status: response.status,
+    statusText: response.statusText,
+    headers: ArrayFrom(response.headers),
+    body: forgivingBase64Encode(body),
+  };
+  op_nondeterminism_fill("fetch", index, record);
+  return responseFromRecord(record);
+};
@@ -16,6 +16,7 @@ use crate::npm::CliNpmResolverCreateOptions;
 use crate::npm::CliNpmResolverManagedCreateOptions;
 use crate::npm::CliNpmResolverManagedSnapshotOption;
 use crate::npm::ManagedCliNpmResolver;
+use crate::npm::NpmOverrides;
 use crate::resolver::CjsResolutionStore;
 use crate::resolver::CliGraphResolver;
 use crate::resolver::CliGraphResolverOptions;
@@ -482,6 +483,19 @@ async fn create_npm_resolver(
         .unwrap_or_else(create_default_npmrc),
       npm_system_info: NpmSystemInfo::default(),
       lifecycle_scripts: Default::default(),
+      // follow the layout recorded in the node_modules directory
+      node_modules_layout: None,
+      maybe_registry_snapshot: None,
+      overrides: Arc::new(
+        config_data
+          .and_then(|d| {
+            NpmOverrides::from_workspace(&d.member_dir.workspace)
+              .inspect_err(|err| lsp_warn!("{:#}", err))
+              .ok()
+          })
+          .unwrap_or_default(),
+      ),
+      record_contents: false,
     })
   };
   Some(create_cli_npm_resolver_for_lsp(options).await)
@@ -536,6 +550,7 @@ fn create_graph_resolver(
       workspace.to_maybe_jsx_import_source_config().ok().flatten()
     }),
     maybe_vendor_dir: config_data.and_then(|d| d.vendor_dir.as_ref()),
+    maybe_import_allow_list: None,
     bare_node_builtins_enabled: workspace
       .is_some_and(|workspace| workspace.has_unstable("bare-node-builtins")),
     sloppy_imports_resolver: config_data
@@ -38,6 +38,8 @@ use crate::util::display;
 use crate::util::v8::get_v8_flags_from_env;
 use crate::util::v8::init_v8_flags;
 
+use args::ColorMode;
+use args::ErrorFormat;
 use args::TaskFlags;
 use deno_runtime::WorkerExecutionMode;
 pub use deno_runtime::UNSTABLE_GRANULAR_FLAGS;
@@ -46,12 +48,15 @@ use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::error::JsError;
 use deno_core::futures::FutureExt;
+use deno_core::resolve_url_or_path;
+use deno_core::serde_json;
 use deno_core::unsync::JoinHandle;
 use deno_npm::resolution::SnapshotFromLockfileError;
 use deno_runtime::fmt_errors::format_js_error;
 use deno_runtime::tokio_util::create_and_run_current_thread_with_maybe_metrics;
 use deno_terminal::colors;
 use factory::CliFactory;
+use serde::Serialize;
 use standalone::MODULE_NOT_FOUND;
 use std::env;
 use std::future::Future;
@@ -137,8 +142,12 @@ async fn run_subcommand(flags: Arc<Flags>) -> Result<i32, AnyError> {
         .load_and_type_check_files(&check_flags.files)
         .await
     }),
-    DenoSubcommand::Clean => spawn_subcommand(async move {
-      tools::clean::clean()
+    DenoSubcommand::Clean(clean_flags) => spawn_subcommand(async move {
+      if clean_flags.lock {
+        tools::clean::prune_lockfile(flags, clean_flags).await
+      } else {
+        tools::clean::clean()
+      }
     }),
     DenoSubcommand::Compile(compile_flags) => spawn_subcommand(async {
       tools::compile::compile(flags, compile_flags).await
@@ -189,7 +198,8 @@ async fn run_subcommand(flags: Arc<Flags>) -> Result<i32, AnyError> {
       spawn_subcommand(async move { tools::repl::run(flags, repl_flags).await })
     }
     DenoSubcommand::Run(run_flags) => spawn_subcommand(async move {
-      if run_flags.is_stdin() {
+      let error_format = flags.error_format;
+      let result = if run_flags.is_stdin() {
         tools::run::run_from_stdin(flags.clone()).await
       } else {
         let result = tools::run::run_script(WorkerExecutionMode::Run, flags.clone(), run_flags.watch).await;
@@ -235,6 +245,10 @@ async fn run_subcommand(flags: Arc<Flags>) -> Result<i32, AnyError> {
             }
           }
         }
+      };
+      match result {
+        Err(err) if error_format == ErrorFormat::Json => Ok(print_json_error(&err, &run_flags.script)),
+        result => result,
       }
     }),
     DenoSubcommand::Serve(serve_flags) => spawn_subcommand(async move {
@@ -349,7 +363,6 @@ fn exit_with_message(message: &str, code: i32) -> ! {
 
 fn exit_for_error(error: AnyError) -> ! {
   let mut error_string = format!("{error:?}");
-  let mut error_code = 1;
 
   if let Some(e) = error.downcast_ref::<JsError>() {
     error_string = format_js_error(e);
@@ -357,10 +370,62 @@ fn exit_for_error(error: AnyError) -> ! {
     error.downcast_ref::<SnapshotFromLockfileError>()
   {
     error_string = e.to_string();
-    error_code = 10;
   }
 
-  exit_with_message(&error_string, error_code);
+  exit_with_message(&error_string, exit_code_for_error(&error));
+}
+
+fn exit_code_for_error(error: &AnyError) -> i32 {
+  match error.downcast_ref::<SnapshotFromLockfileError>() {
+    Some(SnapshotFromLockfileError::IntegrityCheckFailed(_)) => 10,
+    _ => 1,
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonErrorReport<'a> {
+  kind: &'a str,
+  message: String,
+  specifier: Option<String>,
+  stack: Option<&'a str>,
+  exit_code: i32,
+}
+
+/// Reports `error` on stderr as a single line JSON object for
+/// `--error-format=json` and returns the exit code to use.
+#[allow(clippy::print_stderr)]
+fn print_json_error(error: &AnyError, script: &str) -> i32 {
+  let exit_code = exit_code_for_error(error);
+  let report = match error.downcast_ref::<JsError>() {
+    Some(js_error) => JsonErrorReport {
+      kind: js_error.name.as_deref().unwrap_or("Error"),
+      message: js_error
+        .message
+        .clone()
+        .unwrap_or_else(|| js_error.exception_message.clone()),
+      specifier: js_error
+        .frames
+        .iter()
+        .find_map(|frame| frame.file_name.clone()),
+      stack: js_error.stack.as_deref(),
+      exit_code,
+    },
+    // errors raised before the program runs, like a module that can't be
+    // found, are attributed to the main module
+    None => JsonErrorReport {
+      kind: errors::get_error_class_name(error),
+      message: format!("{error:#}"),
+      specifier: env::current_dir()
+        .ok()
+        .and_then(|cwd| resolve_url_or_path(script, &cwd).ok())
+        .map(|specifier| specifier.to_string()),
+      stack: None,
+      exit_code,
+    },
+  };
+  eprintln!("{}", serde_json::to_string(&report).unwrap());
+  exit_code
 }
 
 #[allow(clippy::print_stderr)]
@@ -397,11 +462,15 @@ pub fn main() {
   );
 
   let args: Vec<_> = env::args_os().collect();
+  let flags = parse_flags(args);
+  // the environment isn't safe to modify once other threads may read it, so
+  // this has to happen before the tokio runtime starts
+  tools::run::set_tz(&flags);
   let future = async move {
     // NOTE(lucacasonato): due to new PKU feature introduced in V8 11.6 we need to
     // initialize the V8 platform on a parent thread of all threads that will spawn
     // V8 isolates.
-    let flags = resolve_flags_and_init(args)?;
+    let flags = resolve_flags_and_init(flags)?;
     run_subcommand(Arc::new(flags)).await
   };
 
@@ -411,10 +480,8 @@ pub fn main() {
   }
 }
 
-fn resolve_flags_and_init(
-  args: Vec<std::ffi::OsString>,
-) -> Result<Flags, AnyError> {
-  let flags = match flags_from_vec(args) {
+fn parse_flags(args: Vec<std::ffi::OsString>) -> Flags {
+  match flags_from_vec(args) {
     Ok(flags) => flags,
     Err(err @ clap::Error { .. })
       if err.kind() == clap::error::ErrorKind::DisplayVersion =>
@@ -424,7 +491,17 @@ fn resolve_flags_and_init(
       std::process::exit(0);
     }
     Err(err) => exit_for_error(AnyError::from(err)),
-  };
+  }
+}
+
+fn resolve_flags_and_init(flags: Flags) -> Result<Flags, AnyError> {
+  // colors are detected from `NO_COLOR` on first use, so this has to happen
+  // before anything is printed
+  match flags.color {
+    ColorMode::Always => env::remove_var("NO_COLOR"),
+    ColorMode::Never => env::set_var("NO_COLOR", "1"),
+    ColorMode::Auto => {}
+  }
 
   // TODO(bartlomieju): remove when `--unstable` flag is removed.
   if flags.unstable_config.legacy_flag_enabled {
@@ -476,7 +553,7 @@ fn resolve_flags_and_init(
   init_v8_flags(&default_v8_flags, &flags.v8_flags, get_v8_flags_from_env());
   // TODO(bartlomieju): remove last argument in Deno 2.
   deno_core::JsRuntime::init_platform(None, !*DENO_FUTURE);
-  util::logger::init(flags.log_level);
+  util::logger::init(flags.log_level, flags.quiet_progress);
 
   Ok(flags)
 }
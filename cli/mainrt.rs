@@ -90,7 +90,7 @@ fn main() {
     match standalone {
       Ok(Some(future)) => {
         let (metadata, eszip) = future.await?;
-        util::logger::init(metadata.log_level);
+        util::logger::init(metadata.log_level, false);
         load_env_vars(&metadata.env_vars_from_env_file);
         let exit_code = standalone::run(eszip, metadata).await?;
         std::process::exit(exit_code);
@@ -8,6 +8,7 @@ use std::pin::Pin;
 use std::rc::Rc;
 use std::str;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::args::jsr_url;
 use crate::args::CliLockfile;
@@ -33,6 +34,7 @@ use crate::tools::check::TypeChecker;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::text_encoding::code_without_source_map;
 use crate::util::text_encoding::source_map_from_code;
+use crate::util::trace;
 use crate::worker::ModuleLoaderAndSourceMapGetter;
 use crate::worker::ModuleLoaderFactory;
 use deno_ast::MediaType;
@@ -179,6 +181,7 @@ struct SharedCliModuleLoaderState {
   initial_cwd: PathBuf,
   is_inspecting: bool,
   is_repl: bool,
+  dynamic_import_timeout: Option<Duration>,
   code_cache: Option<Arc<CodeCache>>,
   emitter: Arc<Emitter>,
   main_module_graph_container: Arc<MainModuleGraphContainer>,
@@ -217,6 +220,7 @@ impl CliModuleLoaderFactory {
           options.sub_command(),
           DenoSubcommand::Repl(_) | DenoSubcommand::Jupyter(_)
         ),
+        dynamic_import_timeout: options.dynamic_import_timeout(),
         code_cache,
         emitter,
         main_module_graph_container,
@@ -311,6 +315,8 @@ impl<TGraphContainer: ModuleGraphContainer>
     maybe_referrer: Option<&ModuleSpecifier>,
     requested_module_type: RequestedModuleType,
   ) -> Result<ModuleSource, AnyError> {
+    let _trace_span =
+      trace::span("module", "load_module", || specifier.to_string());
     let code_source = if let Some(result) = self
       .shared
       .npm_module_loader
@@ -405,13 +411,17 @@ impl<TGraphContainer: ModuleGraphContainer>
     referrer: &ModuleSpecifier,
   ) -> Result<ModuleSpecifier, AnyError> {
     if self.shared.node_resolver.in_npm_package(referrer) {
-      return Ok(
-        self
-          .shared
-          .node_resolver
-          .resolve(specifier, referrer, NodeResolutionMode::Execution)?
-          .into_url(),
-      );
+      let specifier = self
+        .shared
+        .node_resolver
+        .resolve(specifier, referrer, NodeResolutionMode::Execution)?
+        .into_url();
+      self
+        .shared
+        .resolver
+        .check_npm_package_import(&specifier, referrer)
+        .map_err(|err| anyhow!("{:#}\n    at {}", err, referrer))?;
+      return Ok(specifier);
     }
 
     let graph = self.graph_container.graph();
@@ -510,6 +520,8 @@ impl<TGraphContainer: ModuleGraphContainer>
         media_type,
         source,
       }) => {
+        let _trace_span =
+          trace::span("compile", "emit", || specifier.to_string());
         let transpile_result = self
           .emitter
           .emit_parsed_source(specifier, media_type, source)
@@ -546,6 +558,8 @@ impl<TGraphContainer: ModuleGraphContainer>
         media_type,
         source,
       }) => {
+        let _trace_span =
+          trace::span("compile", "emit", || specifier.to_string());
         let transpile_result = self
           .emitter
           .emit_parsed_source_sync(specifier, media_type, source)?;
@@ -680,6 +694,14 @@ impl<TGraphContainer: ModuleGraphContainer> ModuleLoader
     let referrer = self.0.resolve_referrer(referrer)?;
     let specifier = self.0.inner_resolve(specifier, &referrer)?;
     ensure_not_jsr_non_jsr_remote_import(&specifier, &referrer)?;
+    if let Some(fresh_specifier) = self
+      .0
+      .shared
+      .main_module_graph_container
+      .resolve_reevaluated(&specifier, &referrer)
+    {
+      return Ok(fresh_specifier);
+    }
     Ok(specifier)
   }
 
@@ -761,19 +783,44 @@ impl<TGraphContainer: ModuleGraphContainer> ModuleLoader
         inner.root_permissions.clone()
       };
       let lib = inner.lib;
-      let mut update_permit = graph_container.acquire_update_permit().await;
-      let graph = update_permit.graph_mut();
-      module_load_preparer
-        .prepare_module_load(
-          graph,
-          &[specifier],
-          is_dynamic,
-          lib,
-          root_permissions,
-        )
-        .await?;
-      update_permit.commit();
-      Ok(())
+      let _trace_span =
+        trace::span("module", "prepare_module_load", || specifier.to_string());
+      let maybe_timeout = if is_dynamic {
+        inner.shared.dynamic_import_timeout
+      } else {
+        None
+      };
+      let timeout_specifier = specifier.clone();
+      let prepare_fut = async move {
+        let mut update_permit = graph_container.acquire_update_permit().await;
+        let graph = update_permit.graph_mut();
+        module_load_preparer
+          .prepare_module_load(
+            graph,
+            &[specifier],
+            is_dynamic,
+            lib,
+            root_permissions,
+          )
+          .await?;
+        update_permit.commit();
+        Ok::<_, AnyError>(())
+      };
+      match maybe_timeout {
+        Some(timeout) => tokio::time::timeout(timeout, prepare_fut)
+          .await
+          .unwrap_or_else(|_| {
+            Err(custom_error(
+              "TimedOut",
+              format!(
+                "Dynamic import of \"{}\" timed out after {}ms",
+                timeout_specifier,
+                timeout.as_millis()
+              ),
+            ))
+          }),
+        None => prepare_fut.await,
+      }
     }
     .boxed_local()
   }
@@ -31,6 +31,8 @@ mod tarball_extract;
 
 pub use registry_info::RegistryInfoDownloader;
 pub use tarball::TarballCache;
+pub use tarball_extract::verify_extracted_contents;
+pub use tarball_extract::ContentsManifest;
 
 /// Stores a single copy of npm packages in a cache.
 #[derive(Debug)]
@@ -40,6 +42,7 @@ pub struct NpmCache {
   npmrc: Arc<ResolvedNpmRc>,
   /// ensures a package is only downloaded once per run
   previously_reloaded_packages: Mutex<HashSet<PackageNv>>,
+  record_contents: bool,
 }
 
 impl NpmCache {
@@ -47,12 +50,14 @@ impl NpmCache {
     cache_dir: NpmCacheDir,
     cache_setting: CacheSetting,
     npmrc: Arc<ResolvedNpmRc>,
+    record_contents: bool,
   ) -> Self {
     Self {
       cache_dir,
       cache_setting,
       previously_reloaded_packages: Default::default(),
       npmrc,
+      record_contents,
     }
   }
 
@@ -64,6 +69,12 @@ impl NpmCache {
     self.cache_dir.root_dir_url()
   }
 
+  /// Whether extracted packages get a [`ContentsManifest`] for
+  /// `--verify-npm-contents`.
+  pub fn records_contents(&self) -> bool {
+    self.record_contents
+  }
+
   /// Checks if the cache should be used for the provided name and version.
   /// NOTE: Subsequent calls for the same package will always return `true`
   /// to ensure a package is only downloaded once per run of the CLI. This
@@ -173,6 +184,40 @@ impl NpmCache {
     Ok(())
   }
 
+  pub fn load_contents_manifest(
+    &self,
+    package: &PackageNv,
+  ) -> Result<Option<ContentsManifest>, AnyError> {
+    let file_cache_path = self.get_contents_manifest_file_cache_path(package);
+    let file_text = match fs::read_to_string(file_cache_path) {
+      Ok(file_text) => file_text,
+      Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+    Ok(serde_json::from_str(&file_text)?)
+  }
+
+  pub fn save_contents_manifest(
+    &self,
+    package: &PackageNv,
+    manifest: &ContentsManifest,
+  ) -> Result<(), AnyError> {
+    let file_cache_path = self.get_contents_manifest_file_cache_path(package);
+    let file_text = serde_json::to_string(manifest)?;
+    atomic_write_file_with_retries(&file_cache_path, file_text, CACHE_PERM)?;
+    Ok(())
+  }
+
+  /// The manifest is stored with the registry info rather than in the
+  /// package folder, so that it isn't part of what it verifies.
+  fn get_contents_manifest_file_cache_path(
+    &self,
+    package: &PackageNv,
+  ) -> PathBuf {
+    let name_folder_path = self.package_name_folder(&package.name);
+    name_folder_path.join(format!("{}.contents.json", package.version))
+  }
+
   fn get_registry_package_info_file_cache_path(&self, name: &str) -> PathBuf {
     let name_folder_path = self.package_name_folder(name);
     name_folder_path.join("registry.json")
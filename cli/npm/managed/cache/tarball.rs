@@ -22,6 +22,7 @@ use crate::args::CacheSetting;
 use crate::http_util::DownloadError;
 use crate::http_util::HttpClientProvider;
 use crate::npm::common::maybe_auth_header_for_npm_registry;
+use crate::npm::NpmRegistrySnapshot;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::sync::MultiRuntimeAsyncValueCreator;
 
@@ -55,6 +56,7 @@ pub struct TarballCache {
   http_client_provider: Arc<HttpClientProvider>,
   npmrc: Arc<ResolvedNpmRc>,
   progress_bar: ProgressBar,
+  maybe_registry_snapshot: Option<Arc<NpmRegistrySnapshot>>,
   memory_cache: Mutex<HashMap<PackageNv, MemoryCacheItem>>,
 }
 
@@ -65,6 +67,7 @@ impl TarballCache {
     http_client_provider: Arc<HttpClientProvider>,
     npmrc: Arc<ResolvedNpmRc>,
     progress_bar: ProgressBar,
+    maybe_registry_snapshot: Option<Arc<NpmRegistrySnapshot>>,
   ) -> Self {
     Self {
       cache,
@@ -72,6 +75,7 @@ impl TarballCache {
       http_client_provider,
       npmrc,
       progress_bar,
+      maybe_registry_snapshot,
       memory_cache: Default::default(),
     }
   }
@@ -133,6 +137,57 @@ impl TarballCache {
     }
   }
 
+  async fn download_tarball(
+    &self,
+    package_nv: &PackageNv,
+    dist: &NpmPackageVersionDistInfo,
+    registry_url: &Url,
+  ) -> Result<Option<Vec<u8>>, AnyError> {
+    if dist.tarball.is_empty() {
+      bail!("Tarball URL was empty.");
+    }
+
+    // IMPORTANT: npm registries may specify tarball URLs at different URLS than the
+    // registry, so we MUST get the auth for the tarball URL and not the registry URL.
+    let tarball_uri = Url::parse(&dist.tarball)?;
+    let maybe_registry_config = self.npmrc.tarball_config(&tarball_uri);
+    let maybe_auth_header = maybe_registry_config
+      .and_then(|c| maybe_auth_header_for_npm_registry(c).ok()?);
+
+    let guard = self.progress_bar.update(&dist.tarball);
+    let result = self
+      .http_client_provider
+      .get_or_create()?
+      .download_with_progress(tarball_uri, maybe_auth_header, &guard)
+      .await;
+    match result {
+      Ok(maybe_bytes) => Ok(maybe_bytes),
+      Err(DownloadError::BadResponse(err)) => {
+        if err.status_code == StatusCode::UNAUTHORIZED
+          && maybe_registry_config.is_none()
+          && self
+            .npmrc
+            .get_registry_config(&package_nv.name)
+            .auth_token
+            .is_some()
+        {
+          bail!(
+            concat!(
+              "No auth for tarball URI, but present for scoped registry.\n\n",
+              "Tarball URI: {}\n",
+              "Scope URI: {}\n\n",
+              "More info here: https://github.com/npm/cli/wiki/%22No-auth-for-URI,-but-auth-present-for-scoped-registry%22"
+            ),
+            dist.tarball,
+            registry_url,
+          )
+        }
+        Err(err.into())
+      }
+      Err(err) => Err(err.into()),
+    }
+  }
+
   fn create_setup_future(
     self: &Arc<Self>,
     package_nv: PackageNv,
@@ -145,7 +200,15 @@ impl TarballCache {
         tarball_cache.cache.package_folder_for_nv_and_url(&package_nv, registry_url);
       let should_use_cache = tarball_cache.cache.should_use_cache_for_package(&package_nv);
       let package_folder_exists = tarball_cache.fs.exists_sync(&package_folder);
-      if should_use_cache && package_folder_exists {
+      // a package extracted without recording its contents is extracted
+      // again for --verify-npm-contents
+      let needs_contents_manifest = tarball_cache.cache.records_contents()
+        && tarball_cache.cache.cache_setting() != &CacheSetting::Only
+        && !matches!(
+          tarball_cache.cache.load_contents_manifest(&package_nv),
+          Ok(Some(_))
+        );
+      if should_use_cache && package_folder_exists && !needs_contents_manifest {
         return Ok(());
       } else if tarball_cache.cache.cache_setting() == &CacheSetting::Only {
         return Err(custom_error(
@@ -158,50 +221,23 @@ impl TarballCache {
         );
       }
 
-      if dist.tarball.is_empty() {
-        bail!("Tarball URL was empty.");
-      }
-
-      // IMPORTANT: npm registries may specify tarball URLs at different URLS than the
-      // registry, so we MUST get the auth for the tarball URL and not the registry URL.
-      let tarball_uri = Url::parse(&dist.tarball)?;
-      let maybe_registry_config =
-        tarball_cache.npmrc.tarball_config(&tarball_uri);
-      let maybe_auth_header = maybe_registry_config.and_then(|c| maybe_auth_header_for_npm_registry(c).ok()?);
-
-      let guard = tarball_cache.progress_bar.update(&dist.tarball);
-      let result = tarball_cache.http_client_provider
-        .get_or_create()?
-        .download_with_progress(tarball_uri, maybe_auth_header, &guard)
-        .await;
-      let maybe_bytes = match result {
-        Ok(maybe_bytes) => maybe_bytes,
-        Err(DownloadError::BadResponse(err)) => {
-          if err.status_code == StatusCode::UNAUTHORIZED
-            && maybe_registry_config.is_none()
-            && tarball_cache.npmrc.get_registry_config(&package_nv.name).auth_token.is_some()
-          {
-            bail!(
-              concat!(
-                "No auth for tarball URI, but present for scoped registry.\n\n",
-                "Tarball URI: {}\n",
-                "Scope URI: {}\n\n",
-                "More info here: https://github.com/npm/cli/wiki/%22No-auth-for-URI,-but-auth-present-for-scoped-registry%22"
-              ),
-              dist.tarball,
-              registry_url,
-            )
-          }
-          return Err(err.into())
-        },
-        Err(err) => return Err(err.into()),
+      let maybe_snapshot_tarball = tarball_cache
+        .maybe_registry_snapshot
+        .as_ref()
+        .and_then(|snapshot| snapshot.tarball(&package_nv));
+      let maybe_bytes = if let Some(bytes) = maybe_snapshot_tarball {
+        Some(bytes.to_vec())
+      } else {
+        tarball_cache.download_tarball(&package_nv, &dist, registry_url).await?
       };
+
       match maybe_bytes {
         Some(bytes) => {
-          let extraction_mode = if should_use_cache || !package_folder_exists {
+          let extraction_mode = if !package_folder_exists {
             TarballExtractionMode::SiblingTempDir
           } else {
-            // The user ran with `--reload`, so overwrite the package instead of
+            // The user ran with `--reload` or the contents of the package
+            // need recording, so overwrite the package instead of
             // deleting it since the package might get corrupted if a user kills
             // their deno process while it's deleting a package directory
             //
@@ -212,17 +248,27 @@ impl TarballCache {
             TarballExtractionMode::Overwrite
           };
           let dist = dist.clone();
-          let package_nv = package_nv.clone();
-          deno_core::unsync::spawn_blocking(move || {
-            verify_and_extract_tarball(
-              &package_nv,
-              &bytes,
-              &dist,
-              &package_folder,
-              extraction_mode,
-            )
+          let record_contents = tarball_cache.cache.records_contents();
+          let maybe_manifest = deno_core::unsync::spawn_blocking({
+            let package_nv = package_nv.clone();
+            move || {
+              verify_and_extract_tarball(
+                &package_nv,
+                &bytes,
+                &dist,
+                &package_folder,
+                extraction_mode,
+                record_contents,
+              )
+            }
           })
-          .await?
+          .await??;
+          match maybe_manifest {
+            Some(manifest) => tarball_cache
+              .cache
+              .save_contents_manifest(&package_nv, &manifest),
+            None => Ok(()),
+          }
         }
         None => {
           bail!("Could not find npm package tarball at: {}", dist.tarball);
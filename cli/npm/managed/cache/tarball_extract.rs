@@ -1,8 +1,10 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::fs;
 use std::io::ErrorKind;
+use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -15,6 +17,8 @@ use deno_npm::registry::NpmPackageVersionDistInfo;
 use deno_npm::registry::NpmPackageVersionDistInfoIntegrity;
 use deno_semver::package::PackageNv;
 use flate2::read::GzDecoder;
+use serde::Deserialize;
+use serde::Serialize;
 use tar::Archive;
 use tar::EntryType;
 
@@ -32,25 +36,43 @@ pub enum TarballExtractionMode {
   SiblingTempDir,
 }
 
+/// The tarball integrity and a checksum of every file extracted from it,
+/// which `--verify-npm-contents` records when extracting a package and
+/// later verifies the package folder against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentsManifest {
+  integrity: String,
+  /// Relative path (with forward slashes) to sha256 hex checksum.
+  files: BTreeMap<String, String>,
+}
+
 pub fn verify_and_extract_tarball(
   package_nv: &PackageNv,
   data: &[u8],
   dist_info: &NpmPackageVersionDistInfo,
   output_folder: &Path,
   extraction_mode: TarballExtractionMode,
-) -> Result<(), AnyError> {
+  record_contents: bool,
+) -> Result<Option<ContentsManifest>, AnyError> {
   verify_tarball_integrity(package_nv, data, &dist_info.integrity())?;
 
-  match extraction_mode {
-    TarballExtractionMode::Overwrite => extract_tarball(data, output_folder),
+  let maybe_files = match extraction_mode {
+    TarballExtractionMode::Overwrite => {
+      extract_tarball(data, output_folder, record_contents)?
+    }
     TarballExtractionMode::SiblingTempDir => {
       let temp_dir = get_atomic_dir_path(output_folder);
-      extract_tarball(data, &temp_dir)?;
+      let maybe_files = extract_tarball(data, &temp_dir, record_contents)?;
       rename_with_retries(&temp_dir, output_folder)
         .map_err(AnyError::from)
-        .context("Failed moving extracted tarball to final destination.")
+        .context("Failed moving extracted tarball to final destination.")?;
+      maybe_files
     }
-  }
+  };
+  Ok(maybe_files.map(|files| ContentsManifest {
+    integrity: dist_info.integrity().for_lockfile(),
+    files,
+  }))
 }
 
 fn rename_with_retries(
@@ -143,7 +165,86 @@ fn verify_tarball_integrity(
   Ok(())
 }
 
-fn extract_tarball(data: &[u8], output_folder: &Path) -> Result<(), AnyError> {
+/// Verifies that the files in `package_folder` are exactly the ones that
+/// were extracted from the tarball with the provided lockfile integrity.
+pub fn verify_extracted_contents(
+  package_nv: &PackageNv,
+  integrity: &str,
+  package_folder: &Path,
+  maybe_manifest: Option<ContentsManifest>,
+) -> Result<(), AnyError> {
+  let Some(manifest) = maybe_manifest else {
+    bail!(
+      "Cannot verify the contents of npm package {}: no checksums were recorded when it was extracted to '{}'. Run again with --reload to extract it again.",
+      package_nv,
+      package_folder.display(),
+    );
+  };
+  if manifest.integrity != integrity {
+    bail!(
+      "Contents of npm package {} were not extracted from the tarball in the lockfile.\n\nExpected: {}\nActual: {}",
+      package_nv,
+      integrity,
+      manifest.integrity,
+    );
+  }
+  for (relative_path, expected_checksum) in &manifest.files {
+    let file_path = package_folder.join(relative_path);
+    let data = match fs::read(&file_path) {
+      Ok(data) => data,
+      Err(err) if err.kind() == ErrorKind::NotFound => bail!(
+        "Contents of npm package {} have been modified: '{}' is missing.",
+        package_nv,
+        relative_path,
+      ),
+      Err(err) => return Err(err.into()),
+    };
+    if checksum_file_data(&data) != *expected_checksum {
+      bail!(
+        "Contents of npm package {} have been modified: '{}' differs from the extracted tarball.",
+        package_nv,
+        relative_path,
+      );
+    }
+  }
+  // files added to the package folder, like a module patched in next to
+  // the original, weren't extracted from the tarball either
+  for entry in walkdir::WalkDir::new(package_folder).min_depth(1) {
+    let entry = entry?;
+    if entry.file_type().is_dir() {
+      continue;
+    }
+    let relative_path = entry
+      .path()
+      .strip_prefix(package_folder)?
+      .components()
+      .map(|c| c.as_os_str().to_string_lossy())
+      .collect::<Vec<_>>()
+      .join("/");
+    if !manifest.files.contains_key(&relative_path) {
+      bail!(
+        "Contents of npm package {} have been modified: '{}' was not extracted from the tarball.",
+        package_nv,
+        relative_path,
+      );
+    }
+  }
+  Ok(())
+}
+
+fn checksum_file_data(data: &[u8]) -> String {
+  let digest = ring::digest::digest(&ring::digest::SHA256, data);
+  faster_hex::hex_string(digest.as_ref())
+}
+
+/// Extracts the tarball to `output_folder`. When `record_contents` is set,
+/// returns the checksum of every extracted file, computed from the data as
+/// it's written.
+fn extract_tarball(
+  data: &[u8],
+  output_folder: &Path,
+  record_contents: bool,
+) -> Result<Option<BTreeMap<String, String>>, AnyError> {
   fs::create_dir_all(output_folder)?;
   let output_folder = fs::canonicalize(output_folder)?;
   let tar = GzDecoder::new(data);
@@ -151,6 +252,7 @@ fn extract_tarball(data: &[u8], output_folder: &Path) -> Result<(), AnyError> {
   archive.set_overwrite(true);
   archive.set_preserve_permissions(true);
   let mut created_dirs = HashSet::new();
+  let mut files = BTreeMap::new();
 
   for entry in archive.entries()? {
     let mut entry = entry?;
@@ -165,7 +267,7 @@ fn extract_tarball(data: &[u8], output_folder: &Path) -> Result<(), AnyError> {
 
     // skip the first component which will be either "package" or the name of the package
     let relative_path = path.components().skip(1).collect::<PathBuf>();
-    let absolute_path = output_folder.join(relative_path);
+    let absolute_path = output_folder.join(&relative_path);
     let dir_path = if entry_type == EntryType::Directory {
       absolute_path.as_path()
     } else {
@@ -184,6 +286,15 @@ fn extract_tarball(data: &[u8], output_folder: &Path) -> Result<(), AnyError> {
 
     let entry_type = entry.header().entry_type();
     match entry_type {
+      EntryType::Regular if record_contents => {
+        let checksum = unpack_with_checksum(&mut entry, &absolute_path)?;
+        let relative_path = relative_path
+          .components()
+          .map(|c| c.as_os_str().to_string_lossy())
+          .collect::<Vec<_>>()
+          .join("/");
+        files.insert(relative_path, checksum);
+      }
       EntryType::Regular => {
         entry.unpack(&absolute_path)?;
       }
@@ -203,7 +314,26 @@ fn extract_tarball(data: &[u8], output_folder: &Path) -> Result<(), AnyError> {
       }
     }
   }
-  Ok(())
+
+  Ok(record_contents.then_some(files))
+}
+
+/// Unpacks a regular file like [`tar::Entry::unpack`] and returns the
+/// checksum of its data.
+fn unpack_with_checksum(
+  entry: &mut tar::Entry<impl Read>,
+  path: &Path,
+) -> Result<String, AnyError> {
+  let mut data = Vec::with_capacity(entry.size() as usize);
+  entry.read_to_end(&mut data)?;
+  fs::write(path, &data)?;
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = entry.header().mode()?;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+  }
+  Ok(checksum_file_data(&data))
 }
 
 #[cfg(test)]
@@ -301,6 +431,85 @@ mod test {
     .is_ok());
   }
 
+  #[test]
+  fn verify_extracted_contents_detects_changes() {
+    let package = PackageNv {
+      name: "package".to_string(),
+      version: Version::parse_from_npm("1.0.0").unwrap(),
+    };
+    let temp_dir = TempDir::new();
+    let package_folder = temp_dir.path().join("package");
+    package_folder.create_dir_all();
+    package_folder.join("lib").create_dir_all();
+    package_folder.join("index.js").write("export default 1;");
+    package_folder
+      .join("lib/util.js")
+      .write("export const a = 2;");
+    let manifest = ContentsManifest {
+      integrity: "sha512-abc".to_string(),
+      files: BTreeMap::from([
+        (
+          "index.js".to_string(),
+          checksum_file_data(b"export default 1;"),
+        ),
+        (
+          "lib/util.js".to_string(),
+          checksum_file_data(b"export const a = 2;"),
+        ),
+      ]),
+    };
+    let manifest = || Some(manifest.clone());
+
+    let folder = package_folder.as_path();
+    assert!(verify_extracted_contents(
+      &package,
+      "sha512-abc",
+      folder,
+      manifest()
+    )
+    .is_ok());
+    assert!(
+      verify_extracted_contents(&package, "sha512-abc", folder, None).is_err()
+    );
+    assert_eq!(
+      verify_extracted_contents(&package, "sha512-other", folder, manifest())
+        .unwrap_err()
+        .to_string(),
+      "Contents of npm package package@1.0.0 were not extracted from the tarball in the lockfile.\n\nExpected: sha512-other\nActual: sha512-abc",
+    );
+
+    package_folder
+      .join("lib/util.js")
+      .write("export const a = 3;");
+    assert_eq!(
+      verify_extracted_contents(&package, "sha512-abc", folder, manifest())
+        .unwrap_err()
+        .to_string(),
+      "Contents of npm package package@1.0.0 have been modified: 'lib/util.js' differs from the extracted tarball.",
+    );
+
+    std::fs::remove_file(folder.join("lib/util.js")).unwrap();
+    assert_eq!(
+      verify_extracted_contents(&package, "sha512-abc", folder, manifest())
+        .unwrap_err()
+        .to_string(),
+      "Contents of npm package package@1.0.0 have been modified: 'lib/util.js' is missing.",
+    );
+
+    package_folder
+      .join("lib/util.js")
+      .write("export const a = 2;");
+    package_folder
+      .join("lib/extra.js")
+      .write("export const b = 3;");
+    assert_eq!(
+      verify_extracted_contents(&package, "sha512-abc", folder, manifest())
+        .unwrap_err()
+        .to_string(),
+      "Contents of npm package package@1.0.0 have been modified: 'lib/extra.js' was not extracted from the tarball.",
+    );
+  }
+
   #[test]
   fn rename_with_retries_succeeds_exists() {
     let temp_dir = TempDir::new();
@@ -9,9 +9,12 @@ use cache::TarballCache;
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::StreamExt;
 use deno_core::serde_json;
 use deno_npm::npm_rc::ResolvedNpmRc;
 use deno_npm::registry::NpmPackageInfo;
+use deno_npm::registry::NpmPackageVersionDistInfo;
 use deno_npm::registry::NpmRegistryApi;
 use deno_npm::resolution::NpmResolutionSnapshot;
 use deno_npm::resolution::PackageReqNotFoundError;
@@ -32,6 +35,7 @@ use resolution::AddPkgReqsResult;
 
 use crate::args::CliLockfile;
 use crate::args::LifecycleScriptsConfig;
+use crate::args::NodeModulesLayout;
 use crate::args::NpmProcessState;
 use crate::args::NpmProcessStateKind;
 use crate::args::PackageJsonInstallDepsProvider;
@@ -45,6 +49,7 @@ use self::cache::NpmCache;
 use self::registry::CliNpmRegistryApi;
 use self::resolution::NpmResolution;
 use self::resolvers::create_npm_fs_resolver;
+use self::resolvers::lifecycle_script_names;
 use self::resolvers::NpmPackageFsResolver;
 
 use super::CliNpmResolver;
@@ -52,10 +57,15 @@ use super::InnerCliNpmResolverRef;
 use super::NpmCacheDir;
 
 mod cache;
+mod overrides;
 mod registry;
+mod registry_snapshot;
 mod resolution;
 mod resolvers;
 
+pub use self::overrides::NpmOverrides;
+pub use self::registry_snapshot::NpmRegistrySnapshot;
+
 pub enum CliNpmResolverManagedSnapshotOption {
   ResolveFromLockfile(Arc<CliLockfile>),
   Specified(Option<ValidSerializedNpmResolutionSnapshot>),
@@ -74,6 +84,11 @@ pub struct CliNpmResolverManagedCreateOptions {
   pub package_json_deps_provider: Arc<PackageJsonInstallDepsProvider>,
   pub npmrc: Arc<ResolvedNpmRc>,
   pub lifecycle_scripts: LifecycleScriptsConfig,
+  pub node_modules_layout: Option<NodeModulesLayout>,
+  pub maybe_registry_snapshot: Option<Arc<NpmRegistrySnapshot>>,
+  pub overrides: Arc<NpmOverrides>,
+  /// Records the contents of extracted packages for `--verify-npm-contents`.
+  pub record_contents: bool,
 }
 
 pub async fn create_managed_npm_resolver_for_lsp(
@@ -103,6 +118,8 @@ pub async fn create_managed_npm_resolver_for_lsp(
       options.npm_system_info,
       snapshot,
       options.lifecycle_scripts,
+      options.node_modules_layout,
+      options.maybe_registry_snapshot,
     )
   })
   .await
@@ -128,6 +145,8 @@ pub async fn create_managed_npm_resolver(
     options.npm_system_info,
     snapshot,
     options.lifecycle_scripts,
+    options.node_modules_layout,
+    options.maybe_registry_snapshot,
   ))
 }
 
@@ -145,6 +164,8 @@ fn create_inner(
   npm_system_info: NpmSystemInfo,
   snapshot: Option<ValidSerializedNpmResolutionSnapshot>,
   lifecycle_scripts: LifecycleScriptsConfig,
+  node_modules_layout: Option<NodeModulesLayout>,
+  maybe_registry_snapshot: Option<Arc<NpmRegistrySnapshot>>,
 ) -> Arc<dyn CliNpmResolver> {
   let resolution = Arc::new(NpmResolution::from_serialized(
     npm_api.clone(),
@@ -157,6 +178,7 @@ fn create_inner(
     http_client_provider.clone(),
     npm_rc.clone(),
     text_only_progress_bar.clone(),
+    maybe_registry_snapshot,
   ));
   let fs_resolver = create_npm_fs_resolver(
     fs.clone(),
@@ -168,6 +190,7 @@ fn create_inner(
     node_modules_dir_path,
     npm_system_info.clone(),
     lifecycle_scripts.clone(),
+    node_modules_layout,
   );
   Arc::new(ManagedCliNpmResolver::new(
     fs,
@@ -181,6 +204,7 @@ fn create_inner(
     text_only_progress_bar,
     npm_system_info,
     lifecycle_scripts,
+    node_modules_layout,
   ))
 }
 
@@ -192,6 +216,7 @@ fn create_cache(options: &CliNpmResolverManagedCreateOptions) -> Arc<NpmCache> {
     ),
     options.cache_setting.clone(),
     options.npmrc.clone(),
+    options.record_contents,
   ))
 }
 
@@ -207,6 +232,8 @@ fn create_api(
       options.npmrc.clone(),
       options.text_only_progress_bar.clone(),
     )),
+    options.maybe_registry_snapshot.clone(),
+    options.overrides.clone(),
   ))
 }
 
@@ -217,11 +244,7 @@ async fn resolve_snapshot(
   match snapshot {
     CliNpmResolverManagedSnapshotOption::ResolveFromLockfile(lockfile) => {
       if !lockfile.overwrite() {
-        let snapshot = snapshot_from_lockfile(lockfile.clone(), api)
-          .await
-          .with_context(|| {
-            format!("failed reading lockfile '{}'", lockfile.filename.display())
-          })?;
+        let snapshot = snapshot_from_lockfile(lockfile.clone(), api).await?;
         Ok(Some(snapshot))
       } else {
         Ok(None)
@@ -238,7 +261,14 @@ async fn snapshot_from_lockfile(
   let (incomplete_snapshot, skip_integrity_check) = {
     let lock = lockfile.lock();
     (
-      deno_npm::resolution::incomplete_snapshot_from_lockfile(&lock)?,
+      deno_npm::resolution::incomplete_snapshot_from_lockfile(&lock)
+        .with_context(|| {
+          format!(
+            "failed reading lockfile '{}'. You can recreate it with `{}`",
+            lockfile.filename.display(),
+            lockfile.recreate_command
+          )
+        })?,
       lock.overwrite,
     )
   };
@@ -249,7 +279,10 @@ async fn snapshot_from_lockfile(
       skip_integrity_check,
     },
   )
-  .await?;
+  .await
+  .with_context(|| {
+    format!("failed reading lockfile '{}'", lockfile.filename.display())
+  })?;
   Ok(snapshot)
 }
 
@@ -268,6 +301,7 @@ pub struct ManagedCliNpmResolver {
   npm_system_info: NpmSystemInfo,
   top_level_install_flag: AtomicFlag,
   lifecycle_scripts: LifecycleScriptsConfig,
+  node_modules_layout: Option<NodeModulesLayout>,
 }
 
 impl std::fmt::Debug for ManagedCliNpmResolver {
@@ -292,6 +326,7 @@ impl ManagedCliNpmResolver {
     text_only_progress_bar: ProgressBar,
     npm_system_info: NpmSystemInfo,
     lifecycle_scripts: LifecycleScriptsConfig,
+    node_modules_layout: Option<NodeModulesLayout>,
   ) -> Self {
     Self {
       fs,
@@ -306,6 +341,7 @@ impl ManagedCliNpmResolver {
       npm_system_info,
       top_level_install_flag: Default::default(),
       lifecycle_scripts,
+      node_modules_layout,
     }
   }
 
@@ -451,6 +487,57 @@ impl ManagedCliNpmResolver {
     self.fs_resolver.cache_packages().await
   }
 
+  /// Checks that the extracted files of every package in the snapshot still
+  /// match the tarball with the integrity stored in the lockfile.
+  pub fn verify_package_contents(&self) -> Result<(), AnyError> {
+    for package in self.all_system_packages(&self.npm_system_info) {
+      let package_folder = self.resolve_pkg_folder_from_pkg_id(&package.id)?;
+      cache::verify_extracted_contents(
+        &package.id.nv,
+        &package.dist.integrity().for_lockfile(),
+        &package_folder,
+        self.npm_cache.load_contents_manifest(&package.id.nv)?,
+      )?;
+    }
+    Ok(())
+  }
+
+  /// Lists the packages in the snapshot that have `preinstall`, `install`
+  /// or `postinstall` scripts, along with the names of those scripts.
+  ///
+  /// The packages need to be cached.
+  pub fn packages_with_lifecycle_scripts(
+    &self,
+  ) -> Result<Vec<(PackageNv, Vec<&'static str>)>, AnyError> {
+    let mut packages = Vec::new();
+    for package in self.all_system_packages(&self.npm_system_info) {
+      let package_folder = self.resolve_pkg_folder_from_pkg_id(&package.id)?;
+      let script_names = lifecycle_script_names(&package, &package_folder);
+      if !script_names.is_empty() {
+        packages.push((package.id.nv, script_names));
+      }
+    }
+    packages.sort_by(|(a, _), (b, _)| a.cmp(b));
+    packages.dedup_by(|(a, _), (b, _)| a == b);
+    Ok(packages)
+  }
+
+  /// Downloads, verifies and extracts the given package tarballs into the
+  /// global cache without resolving them.
+  pub async fn cache_tarballs(
+    &self,
+    packages: &[(PackageNv, NpmPackageVersionDistInfo)],
+  ) -> Result<(), AnyError> {
+    let mut futures = packages
+      .iter()
+      .map(|(nv, dist)| self.tarball_cache.ensure_package(nv, dist))
+      .collect::<FuturesUnordered<_>>();
+    while let Some(result) = futures.next().await {
+      result?;
+    }
+    Ok(())
+  }
+
   pub fn resolve_pkg_folder_from_deno_module(
     &self,
     nv: &PackageNv,
@@ -459,13 +546,17 @@ impl ManagedCliNpmResolver {
     self.resolve_pkg_folder_from_pkg_id(&pkg_id)
   }
 
-  fn resolve_pkg_id_from_pkg_req(
+  pub fn resolve_pkg_id_from_pkg_req(
     &self,
     req: &PackageReq,
   ) -> Result<NpmPackageId, PackageReqNotFoundError> {
     self.resolution.resolve_pkg_id_from_pkg_req(req)
   }
 
+  pub fn package_json_deps_provider(&self) -> &PackageJsonInstallDepsProvider {
+    &self.package_json_deps_provider
+  }
+
   /// Ensures that the top level `package.json` dependencies are installed.
   /// This may set up the `node_modules` directory.
   ///
@@ -613,6 +704,7 @@ impl CliNpmResolver for ManagedCliNpmResolver {
         self.root_node_modules_path().map(ToOwned::to_owned),
         self.npm_system_info.clone(),
         self.lifecycle_scripts.clone(),
+        self.node_modules_layout,
       ),
       self.maybe_lockfile.clone(),
       self.npm_api.clone(),
@@ -623,6 +715,7 @@ impl CliNpmResolver for ManagedCliNpmResolver {
       self.text_only_progress_bar.clone(),
       self.npm_system_info.clone(),
       self.lifecycle_scripts.clone(),
+      self.node_modules_layout,
     ))
   }
 
@@ -0,0 +1,508 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use deno_config::workspace::Workspace;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::serde_json::Map;
+use deno_core::serde_json::Value;
+use deno_npm::registry::NpmPackageInfo;
+use deno_semver::Version;
+use deno_semver::VersionReq;
+
+/// A package name with an optional version range, like the keys of
+/// `overrides`: `foo`, `foo@^1` or `@scope/foo@1.2.3`.
+#[derive(Debug, Clone)]
+struct PackageSelector {
+  name: String,
+  range: Option<String>,
+}
+
+impl PackageSelector {
+  fn parse(text: &str) -> Result<Self, AnyError> {
+    // skip the first character so the `@` of a scope isn't the separator
+    let Some(index) = text.get(1..).and_then(|rest| rest.find('@')) else {
+      return Ok(Self {
+        name: text.to_string(),
+        range: None,
+      });
+    };
+    let (name, range) = text.split_at(index + 1);
+    let range = &range[1..];
+    VersionReq::parse_from_npm(range).map_err(|err| {
+      anyhow!(
+        "Invalid version range in npm override \"{}\": {:#}",
+        text,
+        err
+      )
+    })?;
+    Ok(Self {
+      name: name.to_string(),
+      range: Some(range.to_string()),
+    })
+  }
+
+  fn matches_package(&self, name: &str, version: &Version) -> bool {
+    self.name == name
+      && match &self.range {
+        Some(range) => VersionReq::parse_from_npm(range)
+          .is_ok_and(|req| req.matches(version)),
+        None => true,
+      }
+  }
+
+  /// Whether a dependency declared as `name` with the version requirement
+  /// `spec` is selected, which is when `spec` can resolve to a version in the
+  /// range of the selector.
+  fn matches_dependency(&self, name: &str, spec: &str) -> bool {
+    self.name == name
+      && match &self.range {
+        Some(range) => {
+          range == spec
+            || match (
+              VersionReq::parse_from_npm(range),
+              VersionReq::parse_from_npm(spec),
+            ) {
+              (Ok(range), Ok(spec)) => range.intersects(&spec),
+              _ => false,
+            }
+        }
+        None => true,
+      }
+  }
+}
+
+#[derive(Debug, Clone)]
+struct NpmOverride {
+  /// The package whose dependencies are overridden, or `None` for the
+  /// dependencies of every package.
+  parent: Option<PackageSelector>,
+  target: PackageSelector,
+  /// The version requirement that replaces the one of the dependency.
+  version: String,
+}
+
+/// The versions that the `overrides` (npm) and `resolutions` (yarn) fields of
+/// the root package.json force transitive dependencies to.
+///
+/// They're applied by rewriting the dependencies in the registry information
+/// of packages before resolving them. A nested override applies to the whole
+/// subtree of its parent package wherever that package is in the tree. As a
+/// package version is only resolved once, a package that was already resolved
+/// outside of the subtree keeps its dependencies.
+#[derive(Debug, Default)]
+pub struct NpmOverrides {
+  overrides: Vec<NpmOverride>,
+  /// The names of the packages found below the parent of each nested
+  /// override, by the index of the override.
+  subtrees: Mutex<HashMap<usize, HashSet<String>>>,
+}
+
+impl NpmOverrides {
+  pub fn from_workspace(workspace: &Workspace) -> Result<Self, AnyError> {
+    let Some(pkg_json) = &workspace.root_folder_configs().pkg_json else {
+      return Ok(Self::default());
+    };
+    let text = std::fs::read_to_string(&pkg_json.path).with_context(|| {
+      format!("Failed reading '{}'.", pkg_json.path.display())
+    })?;
+    let value: Value = serde_json::from_str(&text).with_context(|| {
+      format!("Failed parsing '{}'.", pkg_json.path.display())
+    })?;
+    let root_deps = pkg_json
+      .dependencies
+      .iter()
+      .chain(pkg_json.dev_dependencies.iter())
+      .flatten()
+      .map(|(name, spec)| (name.clone(), spec.clone()))
+      .collect();
+    Self::parse(&value, &root_deps).with_context(|| {
+      format!("Invalid npm overrides in '{}'.", pkg_json.path.display())
+    })
+  }
+
+  /// Parses the `overrides` and `resolutions` of a package.json. Versions
+  /// starting with `$` reference a dependency in `root_deps`.
+  fn parse(
+    pkg_json: &Value,
+    root_deps: &HashMap<String, String>,
+  ) -> Result<Self, AnyError> {
+    let mut overrides = Vec::new();
+    if let Some(value) = pkg_json.get("overrides") {
+      let Some(object) = value.as_object() else {
+        bail!("\"overrides\" must be an object.");
+      };
+      parse_overrides(object, None, root_deps, &mut overrides)?;
+    }
+    if let Some(value) = pkg_json.get("resolutions") {
+      let Some(object) = value.as_object() else {
+        bail!("\"resolutions\" must be an object.");
+      };
+      for (key, value) in object {
+        let Some(version) = value.as_str() else {
+          bail!("The resolution of \"{}\" must be a version.", key);
+        };
+        let (parent, target) = parse_resolution_key(key)?;
+        overrides.push(NpmOverride {
+          parent,
+          target,
+          version: resolve_version(version, root_deps)?,
+        });
+      }
+    }
+    Ok(Self {
+      overrides,
+      subtrees: Default::default(),
+    })
+  }
+
+  /// The indexes of the nested overrides that apply to the dependencies of
+  /// `name@version`: the ones below which the package was found and the ones
+  /// whose parent it is.
+  fn nested_overrides(
+    &self,
+    inherited: &[usize],
+    name: &str,
+    version: &Version,
+  ) -> Vec<usize> {
+    let mut indexes = inherited.to_vec();
+    for (index, o) in self.overrides.iter().enumerate() {
+      if !indexes.contains(&index)
+        && o
+          .parent
+          .as_ref()
+          .is_some_and(|p| p.matches_package(name, version))
+      {
+        indexes.push(index);
+      }
+    }
+    indexes
+  }
+
+  /// The version requirement that replaces `spec` for the dependency `name`
+  /// of a package that the `nested` overrides apply to. Nested overrides
+  /// take precedence over the ones that apply everywhere.
+  fn replacement(
+    &self,
+    nested: &[usize],
+    name: &str,
+    spec: &str,
+  ) -> Option<&str> {
+    nested
+      .iter()
+      .map(|index| &self.overrides[*index])
+      .find(|o| o.target.matches_dependency(name, spec))
+      .or_else(|| {
+        self.overrides.iter().find(|o| {
+          o.parent.is_none() && o.target.matches_dependency(name, spec)
+        })
+      })
+      .map(|o| o.version.as_str())
+  }
+
+  /// Rewrites the dependencies of every version in `info` that are
+  /// overridden, only cloning it when something changes.
+  ///
+  /// This also records the dependencies as being in the subtree of the nested
+  /// overrides that apply, so it's called each time the information of a
+  /// package is requested rather than once when it's loaded.
+  pub fn apply(&self, info: Arc<NpmPackageInfo>) -> Arc<NpmPackageInfo> {
+    if self.overrides.is_empty() {
+      return info;
+    }
+    let inherited = self
+      .subtrees
+      .lock()
+      .iter()
+      .filter(|(_, names)| names.contains(&info.name))
+      .map(|(index, _)| *index)
+      .collect::<Vec<_>>();
+    let mut replacements = Vec::new();
+    let mut found = Vec::new();
+    for version_info in info.versions.values() {
+      let nested =
+        self.nested_overrides(&inherited, &info.name, &version_info.version);
+      let deps = version_info
+        .dependencies
+        .iter()
+        .chain(version_info.optional_dependencies.iter());
+      for (name, spec) in deps {
+        for index in &nested {
+          found.push((*index, name));
+        }
+        if let Some(version) = self.replacement(&nested, name, spec) {
+          if version != spec {
+            replacements.push((
+              version_info.version.clone(),
+              name.clone(),
+              version.to_string(),
+            ));
+          }
+        }
+      }
+    }
+    if !found.is_empty() {
+      let mut subtrees = self.subtrees.lock();
+      for (index, name) in found {
+        let names = subtrees.entry(index).or_default();
+        if !names.contains(name) {
+          names.insert(name.clone());
+        }
+      }
+    }
+    if replacements.is_empty() {
+      return info;
+    }
+    let mut info = (*info).clone();
+    for (version, name, replacement) in replacements {
+      let Some(version_info) = info.versions.get_mut(&version) else {
+        continue;
+      };
+      for deps in [
+        &mut version_info.dependencies,
+        &mut version_info.optional_dependencies,
+      ] {
+        if let Some(spec) = deps.get_mut(&name) {
+          spec.clone_from(&replacement);
+        }
+      }
+    }
+    Arc::new(info)
+  }
+}
+
+fn parse_overrides(
+  object: &Map<String, Value>,
+  parent: Option<&PackageSelector>,
+  root_deps: &HashMap<String, String>,
+  overrides: &mut Vec<NpmOverride>,
+) -> Result<(), AnyError> {
+  for (key, value) in object {
+    if key == "." {
+      // the version of the parent itself, handled with the parent
+      continue;
+    }
+    let target = PackageSelector::parse(key)?;
+    let version = match value {
+      Value::String(version) => Some(version.as_str()),
+      Value::Object(nested) => {
+        parse_overrides(nested, Some(&target), root_deps, overrides)?;
+        match nested.get(".") {
+          Some(Value::String(version)) => Some(version.as_str()),
+          Some(_) => bail!("The override of \"{}\" must be a version.", key),
+          None => None,
+        }
+      }
+      _ => bail!(
+        "The override of \"{}\" must be a version or an object.",
+        key
+      ),
+    };
+    if let Some(version) = version {
+      overrides.push(NpmOverride {
+        parent: parent.cloned(),
+        target,
+        version: resolve_version(version, root_deps)?,
+      });
+    }
+  }
+  Ok(())
+}
+
+/// Splits a yarn resolution key like `**/foo`, `parent/foo` or
+/// `@scope/parent/@scope/foo` into the parent and the overridden package.
+fn parse_resolution_key(
+  key: &str,
+) -> Result<(Option<PackageSelector>, PackageSelector), AnyError> {
+  let mut names = Vec::new();
+  let mut segments = key.split('/').filter(|s| !s.is_empty() && *s != "**");
+  while let Some(segment) = segments.next() {
+    if segment.starts_with('@') && !segment[1..].contains('@') {
+      let Some(name) = segments.next() else {
+        bail!("Invalid resolution \"{}\".", key);
+      };
+      names.push(format!("{}/{}", segment, name));
+    } else {
+      names.push(segment.to_string());
+    }
+  }
+  let Some(target) = names.pop() else {
+    bail!("Invalid resolution \"{}\".", key);
+  };
+  let parent = names
+    .pop()
+    .map(|name| PackageSelector::parse(&name))
+    .transpose()?;
+  Ok((parent, PackageSelector::parse(&target)?))
+}
+
+fn resolve_version(
+  version: &str,
+  root_deps: &HashMap<String, String>,
+) -> Result<String, AnyError> {
+  match version.strip_prefix('$') {
+    Some(name) => root_deps.get(name).cloned().ok_or_else(|| {
+      anyhow!(
+        "The override \"{}\" references \"{}\", which is not a dependency of the root package.json.",
+        version,
+        name
+      )
+    }),
+    None => Ok(version.to_string()),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  fn version(text: &str) -> Version {
+    Version::parse_from_npm(text).unwrap()
+  }
+
+  #[test]
+  fn overrides_everywhere_and_nested() {
+    let overrides = NpmOverrides::parse(
+      &json!({
+        "overrides": {
+          "ansi-styles": "4.3.0",
+          "chalk@4": {
+            ".": "4.1.2",
+            "supports-color": "$supports-color"
+          },
+          "@scope/parent": {
+            "@scope/child@1.0.0": "1.0.1"
+          }
+        }
+      }),
+      &HashMap::from([("supports-color".to_string(), "7.2.0".to_string())]),
+    )
+    .unwrap();
+
+    let replacement = |parent: &str, parent_version: &str, name, spec| {
+      let nested =
+        overrides.nested_overrides(&[], parent, &version(parent_version));
+      overrides.replacement(&nested, name, spec)
+    };
+    assert_eq!(
+      replacement("a", "1.0.0", "ansi-styles", "^3"),
+      Some("4.3.0")
+    );
+    assert_eq!(replacement("a", "1.0.0", "chalk", "4.0.0"), Some("4.1.2"));
+    assert_eq!(replacement("a", "1.0.0", "chalk", "5.0.0"), None);
+    // ranges that can resolve to a selected version
+    assert_eq!(replacement("a", "1.0.0", "chalk", "^4.1.0"), Some("4.1.2"));
+    assert_eq!(replacement("a", "1.0.0", "chalk", ">=3"), Some("4.1.2"));
+    assert_eq!(replacement("a", "1.0.0", "chalk", "^5.0.0"), None);
+    assert_eq!(
+      replacement("chalk", "4.1.0", "supports-color", "^7"),
+      Some("7.2.0")
+    );
+    // the nested override only applies below matching parents
+    assert_eq!(replacement("chalk", "5.0.0", "supports-color", "^7"), None);
+    assert_eq!(replacement("a", "1.0.0", "supports-color", "^7"), None);
+    assert_eq!(
+      replacement("@scope/parent", "2.0.0", "@scope/child", "1.0.0"),
+      Some("1.0.1")
+    );
+    assert_eq!(
+      replacement("@scope/parent", "2.0.0", "@scope/child", "2.0.0"),
+      None
+    );
+  }
+
+  #[test]
+  fn yarn_resolutions() {
+    let overrides = NpmOverrides::parse(
+      &json!({
+        "resolutions": {
+          "**/lodash": "4.17.21",
+          "@scope/parent/@scope/child": "2.0.0"
+        }
+      }),
+      &HashMap::new(),
+    )
+    .unwrap();
+    let replacement = |parent: &str, name, spec| {
+      let nested = overrides.nested_overrides(&[], parent, &version("1.0.0"));
+      overrides.replacement(&nested, name, spec)
+    };
+    assert_eq!(replacement("a", "lodash", "^4.0.0"), Some("4.17.21"));
+    assert_eq!(
+      replacement("@scope/parent", "@scope/child", "^1"),
+      Some("2.0.0")
+    );
+    assert_eq!(replacement("other", "@scope/child", "^1"), None);
+  }
+
+  #[test]
+  fn nested_overrides_apply_to_subtree() {
+    let overrides = NpmOverrides::parse(
+      &json!({
+        "overrides": {
+          "chalk@4": {
+            "supports-color": "7.2.0"
+          }
+        }
+      }),
+      &HashMap::new(),
+    )
+    .unwrap();
+    let apply = |name: &str, package_version: &str| {
+      let info: NpmPackageInfo = serde_json::from_value(json!({
+        "name": name,
+        "versions": {
+          package_version: {
+            "version": package_version,
+            "dependencies": {
+              "has-flag": "^4.0.0",
+              "supports-color": "^7.1.0"
+            }
+          }
+        }
+      }))
+      .unwrap();
+      let info = overrides.apply(Arc::new(info));
+      info.versions[&version(package_version)].dependencies["supports-color"]
+        .clone()
+    };
+    // not below chalk yet
+    assert_eq!(apply("has-flag", "4.0.0"), "^7.1.0");
+    assert_eq!(apply("chalk", "4.1.0"), "7.2.0");
+    // a dependency of chalk
+    assert_eq!(apply("has-flag", "4.0.0"), "7.2.0");
+    assert_eq!(apply("other", "1.0.0"), "^7.1.0");
+    assert_eq!(apply("chalk", "5.0.0"), "^7.1.0");
+  }
+
+  #[test]
+  fn invalid_overrides() {
+    let err = NpmOverrides::parse(
+      &json!({ "overrides": { "a": "$b" } }),
+      &HashMap::new(),
+    )
+    .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "The override \"$b\" references \"b\", which is not a dependency of the root package.json."
+    );
+    assert!(NpmOverrides::parse(
+      &json!({ "overrides": { "a": 1 } }),
+      &HashMap::new()
+    )
+    .is_err());
+    assert!(
+      NpmOverrides::parse(&json!({ "overrides": [] }), &HashMap::new())
+        .is_err()
+    );
+  }
+}
@@ -20,6 +20,8 @@ use crate::util::sync::AtomicFlag;
 
 use super::cache::NpmCache;
 use super::cache::RegistryInfoDownloader;
+use super::overrides::NpmOverrides;
+use super::registry_snapshot::NpmRegistrySnapshot;
 
 #[derive(Debug)]
 pub struct CliNpmRegistryApi(Option<Arc<CliNpmRegistryApiInner>>);
@@ -28,6 +30,8 @@ impl CliNpmRegistryApi {
   pub fn new(
     cache: Arc<NpmCache>,
     registry_info_downloader: Arc<RegistryInfoDownloader>,
+    maybe_registry_snapshot: Option<Arc<NpmRegistrySnapshot>>,
+    overrides: Arc<NpmOverrides>,
   ) -> Self {
     Self(Some(Arc::new(CliNpmRegistryApiInner {
       cache,
@@ -35,6 +39,8 @@ impl CliNpmRegistryApi {
       mem_cache: Default::default(),
       previously_reloaded_packages: Default::default(),
       registry_info_downloader,
+      maybe_registry_snapshot,
+      overrides,
     })))
   }
 
@@ -57,7 +63,7 @@ impl NpmRegistryApi for CliNpmRegistryApi {
     name: &str,
   ) -> Result<Arc<NpmPackageInfo>, NpmRegistryPackageInfoLoadError> {
     match self.inner().maybe_package_info(name).await {
-      Ok(Some(info)) => Ok(info),
+      Ok(Some(info)) => Ok(self.inner().overrides.apply(info)),
       Ok(None) => Err(NpmRegistryPackageInfoLoadError::PackageNotExists {
         package_name: name.to_string(),
       }),
@@ -88,6 +94,10 @@ struct CliNpmRegistryApiInner {
   mem_cache: Mutex<HashMap<String, CacheItem>>,
   previously_reloaded_packages: Mutex<HashSet<String>>,
   registry_info_downloader: Arc<RegistryInfoDownloader>,
+  maybe_registry_snapshot: Option<Arc<NpmRegistrySnapshot>>,
+  /// Applied to the package information each time it's requested because
+  /// nested overrides depend on the packages found so far.
+  overrides: Arc<NpmOverrides>,
 }
 
 impl CliNpmRegistryApiInner {
@@ -95,6 +105,10 @@ impl CliNpmRegistryApiInner {
     self: &Arc<Self>,
     name: &str,
   ) -> Result<Option<Arc<NpmPackageInfo>>, AnyError> {
+    if let Some(registry_snapshot) = &self.maybe_registry_snapshot {
+      // never go to the registry when running from a snapshot
+      return registry_snapshot.package_info(name).map(Some);
+    }
     let (created, future) = {
       let mut mem_cache = self.mem_cache.lock();
       match mem_cache.get(name) {
@@ -157,10 +171,12 @@ impl CliNpmRegistryApiInner {
   fn mark_force_reload(&self) -> bool {
     // never force reload the registry information if reloading
     // is disabled or if we're already reloading
-    if matches!(
-      self.cache.cache_setting(),
-      CacheSetting::Only | CacheSetting::ReloadAll
-    ) {
+    if self.maybe_registry_snapshot.is_some()
+      || matches!(
+        self.cache.cache_setting(),
+        CacheSetting::Only | CacheSetting::ReloadAll
+      )
+    {
       return false;
     }
     if self.force_reload_flag.raise() {
@@ -0,0 +1,93 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_npm::registry::NpmPackageInfo;
+use deno_semver::package::PackageNv;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct SerializedNpmRegistrySnapshot {
+  #[serde(default)]
+  packages: HashMap<String, NpmPackageInfo>,
+  /// Base64 encoded tarballs keyed by `<name>@<version>`.
+  #[serde(default)]
+  tarballs: HashMap<String, String>,
+}
+
+/// Pre-baked npm registry metadata for `--npm-snapshot`, used instead of
+/// the registry so that npm packages resolve without network access.
+///
+/// The tarballs of packages can be embedded in the snapshot. Packages whose
+/// tarball isn't embedded are loaded from the `dist.tarball` url of their
+/// version info, unless they're already in the cache.
+#[derive(Debug)]
+pub struct NpmRegistrySnapshot {
+  path: PathBuf,
+  packages: HashMap<String, Arc<NpmPackageInfo>>,
+  tarballs: HashMap<String, Vec<u8>>,
+}
+
+impl NpmRegistrySnapshot {
+  pub fn from_file(path: &Path) -> Result<Self, AnyError> {
+    let text = std::fs::read_to_string(path).with_context(|| {
+      format!("Failed reading npm snapshot '{}'.", path.display())
+    })?;
+    let serialized: SerializedNpmRegistrySnapshot = serde_json::from_str(&text)
+      .with_context(|| {
+        format!("Failed parsing npm snapshot '{}'.", path.display())
+      })?;
+    let tarballs = serialized
+      .tarballs
+      .into_iter()
+      .map(|(nv, data)| {
+        let bytes = BASE64_STANDARD.decode(data).with_context(|| {
+          format!(
+            "Failed decoding the tarball of '{}' in npm snapshot '{}'.",
+            nv,
+            path.display()
+          )
+        })?;
+        Ok((nv, bytes))
+      })
+      .collect::<Result<_, AnyError>>()?;
+    Ok(Self {
+      path: path.to_path_buf(),
+      packages: serialized
+        .packages
+        .into_iter()
+        .map(|(name, info)| (name, Arc::new(info)))
+        .collect(),
+      tarballs,
+    })
+  }
+
+  pub fn package_info(
+    &self,
+    name: &str,
+  ) -> Result<Arc<NpmPackageInfo>, AnyError> {
+    self.packages.get(name).cloned().ok_or_else(|| {
+      anyhow!(
+        "npm package '{}' is not in the npm snapshot '{}'.",
+        name,
+        self.path.display()
+      )
+    })
+  }
+
+  pub fn tarball(&self, package_nv: &PackageNv) -> Option<&[u8]> {
+    self
+      .tarballs
+      .get(&package_nv.to_string())
+      .map(|bytes| bytes.as_slice())
+  }
+}
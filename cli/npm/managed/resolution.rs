@@ -216,6 +216,20 @@ impl NpmResolution {
       .map(|pkg| pkg.id.clone())
   }
 
+  /// Finds the cache folder id of the first package, for any system, that
+  /// matches the predicate.
+  pub fn find_pkg_cache_folder_id(
+    &self,
+    predicate: impl Fn(&NpmResolutionPackage) -> bool,
+  ) -> Option<NpmPackageCacheFolderId> {
+    self
+      .snapshot
+      .read()
+      .all_packages_for_every_system()
+      .find(|pkg| predicate(pkg))
+      .map(|pkg| pkg.get_package_cache_folder_id())
+  }
+
   pub fn package_reqs(&self) -> HashMap<PackageReq, PackageNv> {
     self.snapshot.read().package_reqs().clone()
   }
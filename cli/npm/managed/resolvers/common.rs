@@ -131,6 +131,42 @@ impl RegistryReadPermissionChecker {
   }
 }
 
+// npm defaults to running `node-gyp rebuild` if there is a `binding.gyp` file
+// but it always fails if the package excludes the `binding.gyp` file when they publish.
+// (for example, `fsevents` hits this)
+pub fn is_broken_default_install_script(
+  script: &str,
+  package_path: &Path,
+) -> bool {
+  script == "node-gyp rebuild" && !package_path.join("binding.gyp").exists()
+}
+
+pub fn has_lifecycle_scripts(
+  package: &NpmResolutionPackage,
+  package_path: &Path,
+) -> bool {
+  !lifecycle_script_names(package, package_path).is_empty()
+}
+
+/// The names of the `preinstall`, `install` and `postinstall` scripts that
+/// installing the package would run.
+pub fn lifecycle_script_names(
+  package: &NpmResolutionPackage,
+  package_path: &Path,
+) -> Vec<&'static str> {
+  ["preinstall", "install", "postinstall"]
+    .into_iter()
+    .filter(|name| match package.scripts.get(*name) {
+      // default script
+      Some(script) if *name == "install" => {
+        !is_broken_default_install_script(script, package_path)
+      }
+      Some(_) => true,
+      None => false,
+    })
+    .collect()
+}
+
 /// Caches all the packages in parallel.
 pub async fn cache_packages(
   packages: Vec<NpmResolutionPackage>,
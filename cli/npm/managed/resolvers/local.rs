@@ -17,6 +17,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use crate::args::LifecycleScriptsConfig;
+use crate::args::NodeModulesLayout;
 use crate::args::PackagesAllowedScripts;
 use async_trait::async_trait;
 use deno_ast::ModuleSpecifier;
@@ -42,6 +43,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::args::PackageJsonInstallDepsProvider;
+use crate::cache::FastInsecureHasher;
 use crate::cache::CACHE_PERM;
 use crate::npm::cache_dir::mixed_case_package_name_decode;
 use crate::npm::cache_dir::mixed_case_package_name_encode;
@@ -56,6 +58,8 @@ use crate::util::progress_bar::ProgressMessagePrompt;
 use super::super::cache::NpmCache;
 use super::super::cache::TarballCache;
 use super::super::resolution::NpmResolution;
+use super::common::has_lifecycle_scripts;
+use super::common::is_broken_default_install_script;
 use super::common::NpmPackageFsResolver;
 use super::common::RegistryReadPermissionChecker;
 
@@ -74,6 +78,7 @@ pub struct LocalNpmPackageResolver {
   system_info: NpmSystemInfo,
   registry_read_permission_checker: RegistryReadPermissionChecker,
   lifecycle_scripts: LifecycleScriptsConfig,
+  node_modules_layout: NodeModulesLayout,
 }
 
 impl LocalNpmPackageResolver {
@@ -88,7 +93,12 @@ impl LocalNpmPackageResolver {
     node_modules_folder: PathBuf,
     system_info: NpmSystemInfo,
     lifecycle_scripts: LifecycleScriptsConfig,
+    maybe_node_modules_layout: Option<NodeModulesLayout>,
   ) -> Self {
+    // without an explicit layout, use the one the directory was set up with
+    let node_modules_layout = maybe_node_modules_layout
+      .or_else(|| read_layout_file(fs.as_ref(), &node_modules_folder))
+      .unwrap_or_default();
     Self {
       cache,
       fs: fs.clone(),
@@ -105,6 +115,7 @@ impl LocalNpmPackageResolver {
       root_node_modules_path: node_modules_folder,
       system_info,
       lifecycle_scripts,
+      node_modules_layout,
     }
   }
 
@@ -174,7 +185,11 @@ impl NpmPackageFsResolver for LocalNpmPackageResolver {
       self
         .root_node_modules_path
         .join(".deno")
-        .join(get_package_folder_id_folder_name(&cache_folder_id))
+        .join(get_package_folder_name(
+          self.node_modules_layout,
+          id,
+          &cache_folder_id,
+        ))
         .join("node_modules")
         .join(&cache_folder_id.nv.name),
     )
@@ -241,7 +256,20 @@ impl NpmPackageFsResolver for LocalNpmPackageResolver {
       return Ok(None);
     };
     let folder_name = folder_path.parent().unwrap().to_string_lossy();
-    Ok(get_package_folder_id_from_folder_name(&folder_name))
+    match self.node_modules_layout {
+      NodeModulesLayout::Default => {
+        Ok(get_package_folder_id_from_folder_name(&folder_name))
+      }
+      // the folder name does not contain the copy index
+      NodeModulesLayout::Deterministic => {
+        Ok(self.resolution.find_pkg_cache_folder_id(|package| {
+          get_local_package_folder_name(
+            NodeModulesLayout::Deterministic,
+            package,
+          ) == folder_name
+        }))
+      }
+    }
   }
 
   async fn cache_packages(&self) -> Result<(), AnyError> {
@@ -254,6 +282,7 @@ impl NpmPackageFsResolver for LocalNpmPackageResolver {
       &self.root_node_modules_path,
       &self.system_info,
       &self.lifecycle_scripts,
+      self.node_modules_layout,
     )
     .await
   }
@@ -276,6 +305,7 @@ fn resolve_baseline_custom_commands(
   snapshot: &NpmResolutionSnapshot,
   packages: &[NpmResolutionPackage],
   local_registry_dir: &Path,
+  layout: NodeModulesLayout,
 ) -> Result<crate::task_runner::TaskCustomCommands, AnyError> {
   let mut custom_commands = crate::task_runner::TaskCustomCommands::new();
   custom_commands
@@ -301,6 +331,7 @@ fn resolve_baseline_custom_commands(
     snapshot,
     packages,
     local_registry_dir,
+    layout,
   )
 }
 
@@ -315,11 +346,12 @@ fn resolve_custom_commands_from_packages<
   snapshot: &'a NpmResolutionSnapshot,
   packages: P,
   local_registry_dir: &Path,
+  layout: NodeModulesLayout,
 ) -> Result<crate::task_runner::TaskCustomCommands, AnyError> {
   let mut bin_entries = bin_entries::BinEntries::new();
   for package in packages {
     let package_path =
-      local_node_modules_package_path(local_registry_dir, package);
+      local_node_modules_package_path(local_registry_dir, package, layout);
 
     if package.bin.is_some() {
       bin_entries.add(package.clone(), package_path);
@@ -342,11 +374,10 @@ fn resolve_custom_commands_from_packages<
 fn local_node_modules_package_path(
   local_registry_dir: &Path,
   package: &NpmResolutionPackage,
+  layout: NodeModulesLayout,
 ) -> PathBuf {
   local_registry_dir
-    .join(get_package_folder_id_folder_name(
-      &package.get_package_cache_folder_id(),
-    ))
+    .join(get_local_package_folder_name(layout, package))
     .join("node_modules")
     .join(&package.id.nv.name)
 }
@@ -359,6 +390,7 @@ fn resolve_custom_commands_from_deps(
   package: &NpmResolutionPackage,
   snapshot: &NpmResolutionSnapshot,
   local_registry_dir: &Path,
+  layout: NodeModulesLayout,
 ) -> Result<crate::task_runner::TaskCustomCommands, AnyError> {
   resolve_custom_commands_from_packages(
     baseline,
@@ -368,6 +400,7 @@ fn resolve_custom_commands_from_deps(
       .values()
       .map(|id| snapshot.package_from_id(id).unwrap()),
     local_registry_dir,
+    layout,
   )
 }
 
@@ -386,27 +419,6 @@ fn can_run_scripts(
   }
 }
 
-// npm defaults to running `node-gyp rebuild` if there is a `binding.gyp` file
-// but it always fails if the package excludes the `binding.gyp` file when they publish.
-// (for example, `fsevents` hits this)
-fn is_broken_default_install_script(script: &str, package_path: &Path) -> bool {
-  script == "node-gyp rebuild" && !package_path.join("binding.gyp").exists()
-}
-
-fn has_lifecycle_scripts(
-  package: &NpmResolutionPackage,
-  package_path: &Path,
-) -> bool {
-  if let Some(install) = package.scripts.get("install") {
-    // default script
-    if !is_broken_default_install_script(install, package_path) {
-      return true;
-    }
-  }
-  package.scripts.contains_key("preinstall")
-    || package.scripts.contains_key("postinstall")
-}
-
 /// Creates a pnpm style folder structure.
 #[allow(clippy::too_many_arguments)]
 async fn sync_resolution_with_fs(
@@ -418,6 +430,7 @@ async fn sync_resolution_with_fs(
   root_node_modules_dir_path: &Path,
   system_info: &NpmSystemInfo,
   lifecycle_scripts: &LifecycleScriptsConfig,
+  layout: NodeModulesLayout,
 ) -> Result<(), AnyError> {
   if snapshot.is_empty() && pkg_json_deps_provider.workspace_pkgs().is_empty() {
     return Ok(()); // don't create the directory
@@ -440,6 +453,39 @@ async fn sync_resolution_with_fs(
   )
   .await;
 
+  // the package folders are named differently in another layout, so a
+  // directory set up with one is cleared instead of growing a second tree
+  let layout_file_path = deno_local_registry_dir.join(LAYOUT_FILE_NAME);
+  let current_layout = fs::read_to_string(&layout_file_path)
+    .ok()
+    .and_then(|text| parse_layout(&text));
+  if current_layout.unwrap_or_default() != layout {
+    for entry in fs::read_dir(&deno_local_registry_dir)? {
+      let entry = entry?;
+      let file_name = entry.file_name();
+      if file_name == ".deno.lock" || file_name == ".deno.lock.poll" {
+        continue;
+      }
+      let path = entry.path();
+      if entry.file_type()?.is_dir() {
+        fs::remove_dir_all(&path)
+      } else {
+        fs::remove_file(&path)
+      }
+      .with_context(|| format!("Removing '{}'", path.display()))?;
+    }
+    fs::create_dir_all(&deno_node_modules_dir).with_context(|| {
+      format!("Creating '{}'", deno_node_modules_dir.display())
+    })?;
+  }
+  if current_layout != Some(layout) {
+    atomic_write_file_with_retries(
+      &layout_file_path,
+      layout_name(layout),
+      CACHE_PERM,
+    )?;
+  }
+
   // load this after we get the directory lock
   let mut setup_cache =
     SetupCache::load(deno_local_registry_dir.join(".setup-cache.bin"));
@@ -469,8 +515,7 @@ async fn sync_resolution_with_fs(
       newest_packages_by_name.insert(&package.id.nv.name, package);
     };
 
-    let package_folder_name =
-      get_package_folder_id_folder_name(&package.get_package_cache_folder_id());
+    let package_folder_name = get_local_package_folder_name(layout, package);
     let folder_path = deno_local_registry_dir.join(&package_folder_name);
     let initialized_file = folder_path.join(".initialized");
     if !cache
@@ -558,21 +603,27 @@ async fn sync_resolution_with_fs(
   for package in &package_partitions.copy_packages {
     let package_cache_folder_id = package.get_package_cache_folder_id();
     let destination_path = deno_local_registry_dir
-      .join(get_package_folder_id_folder_name(&package_cache_folder_id));
+      .join(get_local_package_folder_name(layout, package));
     let initialized_file = destination_path.join(".initialized");
     if !initialized_file.exists() {
       let sub_node_modules = destination_path.join("node_modules");
       let package_path =
         join_package_name(&sub_node_modules, &package.id.nv.name);
 
-      let source_path = join_package_name(
-        &deno_local_registry_dir
-          .join(get_package_folder_id_folder_name(
-            &package_cache_folder_id.with_no_count(),
-          ))
-          .join("node_modules"),
-        &package.id.nv.name,
-      );
+      let source_path = match layout {
+        NodeModulesLayout::Default => join_package_name(
+          &deno_local_registry_dir
+            .join(get_package_folder_id_folder_name(
+              &package_cache_folder_id.with_no_count(),
+            ))
+            .join("node_modules"),
+          &package.id.nv.name,
+        ),
+        // the folder of the first copy is not known by its copy index
+        NodeModulesLayout::Deterministic => {
+          cache.package_folder_for_nv(&package.id.nv)
+        }
+      };
 
       clone_dir_recursive(&source_path, &package_path)?;
       // write out a file that indicates this folder has been initialized
@@ -585,8 +636,7 @@ async fn sync_resolution_with_fs(
   // Symlink node_modules/.deno/<package_id>/node_modules/<dep_name> to
   // node_modules/.deno/<dep_id>/node_modules/<dep_package_name>
   for package in package_partitions.iter_all() {
-    let package_folder_name =
-      get_package_folder_id_folder_name(&package.get_package_cache_folder_id());
+    let package_folder_name = get_local_package_folder_name(layout, package);
     let sub_node_modules = deno_local_registry_dir
       .join(&package_folder_name)
       .join("node_modules");
@@ -598,9 +648,7 @@ async fn sync_resolution_with_fs(
       {
         continue; // this isn't a dependency for the current system
       }
-      let dep_cache_folder_id = dep.get_package_cache_folder_id();
-      let dep_folder_name =
-        get_package_folder_id_folder_name(&dep_cache_folder_id);
+      let dep_folder_name = get_local_package_folder_name(layout, dep);
       if dep_setup_cache.insert(name, &dep_folder_name) {
         let dep_folder_path = join_package_name(
           &deno_local_registry_dir
@@ -654,9 +702,8 @@ async fn sync_resolution_with_fs(
           }
         }
       };
-      let target_folder_name = get_package_folder_id_folder_name(
-        &remote_pkg.get_package_cache_folder_id(),
-      );
+      let target_folder_name =
+        get_local_package_folder_name(layout, remote_pkg);
       let local_registry_package_path = join_package_name(
         &deno_local_registry_dir
           .join(&target_folder_name)
@@ -702,8 +749,7 @@ async fn sync_resolution_with_fs(
       }
     }
     let package = snapshot.package_from_id(id).unwrap();
-    let target_folder_name =
-      get_package_folder_id_folder_name(&package.get_package_cache_folder_id());
+    let target_folder_name = get_local_package_folder_name(layout, package);
     if setup_cache.insert_root_symlink(&id.nv.name, &target_folder_name) {
       let local_registry_package_path = join_package_name(
         &deno_local_registry_dir
@@ -731,8 +777,7 @@ async fn sync_resolution_with_fs(
       }
     }
 
-    let target_folder_name =
-      get_package_folder_id_folder_name(&package.get_package_cache_folder_id());
+    let target_folder_name = get_local_package_folder_name(layout, package);
     if setup_cache.insert_deno_symlink(&package.id.nv.name, &target_folder_name)
     {
       let local_registry_package_path = join_package_name(
@@ -775,6 +820,7 @@ async fn sync_resolution_with_fs(
       snapshot,
       &package_partitions.packages,
       &deno_local_registry_dir,
+      layout,
     )?;
     let init_cwd = lifecycle_scripts.initial_cwd.as_deref().unwrap();
     let process_state = crate::npm::managed::npm_process_state(
@@ -796,6 +842,7 @@ async fn sync_resolution_with_fs(
         &package,
         snapshot,
         &deno_local_registry_dir,
+        layout,
       )?;
       for script_name in ["preinstall", "install", "postinstall"] {
         if let Some(script) = package.scripts.get(script_name) {
@@ -993,6 +1040,71 @@ impl SetupCache {
   }
 }
 
+/// Gets the name of the folder in `node_modules/.deno` for a package.
+/// File in `node_modules/.deno` recording the [`NodeModulesLayout`] the
+/// directory was set up with, so that later runs, the language server and
+/// compiled binaries find the package folders without being told.
+const LAYOUT_FILE_NAME: &str = ".layout";
+
+fn layout_name(layout: NodeModulesLayout) -> &'static str {
+  match layout {
+    NodeModulesLayout::Default => "default",
+    NodeModulesLayout::Deterministic => "deterministic",
+  }
+}
+
+fn parse_layout(text: &str) -> Option<NodeModulesLayout> {
+  match text.trim() {
+    "default" => Some(NodeModulesLayout::Default),
+    "deterministic" => Some(NodeModulesLayout::Deterministic),
+    _ => None,
+  }
+}
+
+fn read_layout_file(
+  fs: &dyn deno_fs::FileSystem,
+  root_node_modules_path: &Path,
+) -> Option<NodeModulesLayout> {
+  let path = root_node_modules_path.join(".deno").join(LAYOUT_FILE_NAME);
+  let text = fs.read_text_file_lossy_sync(&path, None).ok()?;
+  parse_layout(&text)
+}
+
+fn get_local_package_folder_name(
+  layout: NodeModulesLayout,
+  package: &NpmResolutionPackage,
+) -> String {
+  get_package_folder_name(
+    layout,
+    &package.id,
+    &package.get_package_cache_folder_id(),
+  )
+}
+
+fn get_package_folder_name(
+  layout: NodeModulesLayout,
+  id: &NpmPackageId,
+  folder_id: &NpmPackageCacheFolderId,
+) -> String {
+  match layout {
+    NodeModulesLayout::Default => get_package_folder_id_folder_name(folder_id),
+    NodeModulesLayout::Deterministic => {
+      let folder_name =
+        get_package_folder_id_folder_name(&folder_id.with_no_count());
+      if id.peer_dependencies.is_empty() {
+        folder_name
+      } else {
+        // the copy index depends on the order packages were resolved in,
+        // while the peer dependencies that caused the copy do not
+        let hash = FastInsecureHasher::new_without_deno_version()
+          .write_str(&id.as_serialized())
+          .finish();
+        format!("{}_{:016x}", folder_name, hash)
+      }
+    }
+  }
+}
+
 fn get_package_folder_id_folder_name(
   folder_id: &NpmPackageCacheFolderId,
 ) -> String {
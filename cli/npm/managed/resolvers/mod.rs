@@ -11,9 +11,11 @@ use deno_npm::NpmSystemInfo;
 use deno_runtime::deno_fs::FileSystem;
 
 use crate::args::LifecycleScriptsConfig;
+use crate::args::NodeModulesLayout;
 use crate::args::PackageJsonInstallDepsProvider;
 use crate::util::progress_bar::ProgressBar;
 
+pub use self::common::lifecycle_script_names;
 pub use self::common::NpmPackageFsResolver;
 
 use self::global::GlobalNpmPackageResolver;
@@ -34,6 +36,7 @@ pub fn create_npm_fs_resolver(
   maybe_node_modules_path: Option<PathBuf>,
   system_info: NpmSystemInfo,
   lifecycle_scripts: LifecycleScriptsConfig,
+  node_modules_layout: Option<NodeModulesLayout>,
 ) -> Arc<dyn NpmPackageFsResolver> {
   match maybe_node_modules_path {
     Some(node_modules_folder) => Arc::new(LocalNpmPackageResolver::new(
@@ -46,6 +49,7 @@ pub fn create_npm_fs_resolver(
       node_modules_folder,
       system_info,
       lifecycle_scripts,
+      node_modules_layout,
     )),
     None => Arc::new(GlobalNpmPackageResolver::new(
       npm_cache,
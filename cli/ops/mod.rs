@@ -2,4 +2,5 @@
 
 pub mod bench;
 pub mod jupyter;
+pub mod nondeterminism;
 pub mod testing;
@@ -0,0 +1,186 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use deno_core::anyhow::Context;
+use deno_core::error::generic_error;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::serde_json::Value;
+use deno_core::OpState;
+use serde::Deserialize;
+use serde::Serialize;
+
+deno_core::extension!(deno_nondeterminism,
+  ops = [
+    op_nondeterminism_is_replay,
+    op_nondeterminism_record,
+    op_nondeterminism_fill,
+    op_nondeterminism_replay,
+  ],
+  options = {
+    recorder: NondeterminismRecorder,
+  },
+  state = |state, options| {
+    state.put(options.recorder);
+  },
+);
+
+/// The nondeterministic inputs of a run, in the order the program read them.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NondeterminismTrace {
+  /// `Math.random()`, `crypto.getRandomValues()` and `crypto.randomUUID()`
+  /// results.
+  #[serde(default)]
+  pub random: Vec<Value>,
+  /// `Date.now()` and `new Date()` reads, in milliseconds.
+  #[serde(default)]
+  pub time: Vec<Value>,
+  /// `fetch()` responses, in the order the requests were made.
+  #[serde(default)]
+  pub fetch: Vec<Value>,
+}
+
+impl NondeterminismTrace {
+  fn entries_mut(&mut self, kind: &str) -> Result<&mut Vec<Value>, AnyError> {
+    match kind {
+      "random" => Ok(&mut self.random),
+      "time" => Ok(&mut self.time),
+      "fetch" => Ok(&mut self.fetch),
+      _ => Err(type_error(format!("Unknown nondeterminism kind '{kind}'"))),
+    }
+  }
+}
+
+#[derive(Debug)]
+struct ReplayState {
+  trace: NondeterminismTrace,
+  random_index: usize,
+  time_index: usize,
+  fetch_index: usize,
+}
+
+#[derive(Debug)]
+enum RecorderMode {
+  Record(NondeterminismTrace),
+  Replay(ReplayState),
+}
+
+/// Records the nondeterministic inputs of a run for
+/// `--record-nondeterminism`, or hands them back in the same order for
+/// `--replay-nondeterminism`.
+#[derive(Debug, Clone)]
+pub struct NondeterminismRecorder(Arc<Mutex<RecorderMode>>);
+
+impl NondeterminismRecorder {
+  pub fn record() -> Self {
+    Self(Arc::new(Mutex::new(RecorderMode::Record(
+      Default::default(),
+    ))))
+  }
+
+  pub fn replay_from_file(path: &Path) -> Result<Self, AnyError> {
+    let text = std::fs::read_to_string(path).with_context(|| {
+      format!("Failed reading replay trace '{}'.", path.display())
+    })?;
+    let trace = serde_json::from_str(&text).with_context(|| {
+      format!("Failed parsing replay trace '{}'.", path.display())
+    })?;
+    Ok(Self(Arc::new(Mutex::new(RecorderMode::Replay(
+      ReplayState {
+        trace,
+        random_index: 0,
+        time_index: 0,
+        fetch_index: 0,
+      },
+    )))))
+  }
+
+  /// Writes the recorded trace. Does nothing when replaying.
+  pub fn write_to_file(&self, path: &Path) -> Result<(), AnyError> {
+    let mode = self.0.lock();
+    let RecorderMode::Record(trace) = &*mode else {
+      return Ok(());
+    };
+    let text = serde_json::to_string_pretty(trace)?;
+    std::fs::write(path, text).with_context(|| {
+      format!("Failed writing replay trace '{}'.", path.display())
+    })?;
+    Ok(())
+  }
+}
+
+#[op2(fast)]
+fn op_nondeterminism_is_replay(state: &mut OpState) -> bool {
+  let recorder = state.borrow::<NondeterminismRecorder>();
+  matches!(&*recorder.0.lock(), RecorderMode::Replay(_))
+}
+
+/// Appends a value to the trace and returns its index, so that values which
+/// only become known later (like `fetch()` responses) keep the position of
+/// the call.
+#[op2]
+fn op_nondeterminism_record(
+  state: &mut OpState,
+  #[string] kind: &str,
+  #[serde] value: Value,
+) -> Result<u32, AnyError> {
+  let recorder = state.borrow::<NondeterminismRecorder>();
+  let mut mode = recorder.0.lock();
+  let RecorderMode::Record(trace) = &mut *mode else {
+    return Err(generic_error("Not recording nondeterministic inputs."));
+  };
+  let entries = trace.entries_mut(kind)?;
+  entries.push(value);
+  Ok(entries.len() as u32 - 1)
+}
+
+#[op2]
+fn op_nondeterminism_fill(
+  state: &mut OpState,
+  #[string] kind: &str,
+  index: u32,
+  #[serde] value: Value,
+) -> Result<(), AnyError> {
+  let recorder = state.borrow::<NondeterminismRecorder>();
+  let mut mode = recorder.0.lock();
+  let RecorderMode::Record(trace) = &mut *mode else {
+    return Err(generic_error("Not recording nondeterministic inputs."));
+  };
+  if let Some(entry) = trace.entries_mut(kind)?.get_mut(index as usize) {
+    *entry = value;
+  }
+  Ok(())
+}
+
+#[op2]
+#[serde]
+fn op_nondeterminism_replay(
+  state: &mut OpState,
+  #[string] kind: &str,
+) -> Result<Value, AnyError> {
+  let recorder = state.borrow::<NondeterminismRecorder>();
+  let mut mode = recorder.0.lock();
+  let RecorderMode::Replay(replay) = &mut *mode else {
+    return Err(generic_error("Not replaying nondeterministic inputs."));
+  };
+  let index = match kind {
+    "random" => &mut replay.random_index,
+    "time" => &mut replay.time_index,
+    "fetch" => &mut replay.fetch_index,
+    _ => {
+      return Err(type_error(format!("Unknown nondeterminism kind '{kind}'")))
+    }
+  };
+  let Some(value) = replay.trace.entries_mut(kind)?.get(*index).cloned() else {
+    return Err(generic_error(format!(
+      "The replay trace has no more '{kind}' inputs. The program diverged from the recorded run."
+    )));
+  };
+  *index += 1;
+  Ok(value)
+}
@@ -11,6 +11,7 @@ use deno_config::workspace::WorkspaceResolver;
 use deno_core::anyhow::anyhow;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
+use deno_core::resolve_url_or_path;
 use deno_core::ModuleSourceCode;
 use deno_core::ModuleSpecifier;
 use deno_graph::source::ResolutionMode;
@@ -28,8 +29,10 @@ use deno_runtime::deno_fs::FileSystem;
 use deno_runtime::deno_node::is_builtin_node_module;
 use deno_runtime::deno_node::NodeResolver;
 use deno_runtime::fs_util::specifier_to_file_path;
+use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
 use deno_semver::package::PackageReq;
+use deno_semver::Version;
 use node_resolver::errors::ClosestPkgJsonError;
 use node_resolver::errors::NodeResolveError;
 use node_resolver::errors::NodeResolveErrorKind;
@@ -43,10 +46,12 @@ use node_resolver::NodeResolution;
 use node_resolver::NodeResolutionMode;
 use node_resolver::PackageJson;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::args::jsr_url;
 use crate::args::JsxImportSourceConfig;
 use crate::args::DENO_DISABLE_PEDANTIC_NODE_WARNINGS;
 use crate::node::CliNodeCodeTranslator;
@@ -422,6 +427,94 @@ impl CjsResolutionStore {
   }
 }
 
+/// The specifiers modules may import with `--import-policy=deny`.
+#[derive(Debug, Default)]
+pub struct ImportAllowList {
+  specifiers: HashSet<ModuleSpecifier>,
+  npm_reqs: Vec<PackageReq>,
+  jsr_reqs: Vec<PackageReq>,
+}
+
+impl ImportAllowList {
+  /// Parses the `--allow-import-spec` values, resolving relative paths
+  /// against `cwd`.
+  pub fn parse(specs: &[String], cwd: &Path) -> Result<Self, AnyError> {
+    let mut allow_list = Self::default();
+    for spec in specs {
+      if let Ok(req_ref) = NpmPackageReqReference::from_str(spec) {
+        allow_list.npm_reqs.push(req_ref.into_inner().req);
+      } else if let Ok(req_ref) = JsrPackageReqReference::from_str(spec) {
+        allow_list.jsr_reqs.push(req_ref.into_inner().req);
+      } else {
+        let specifier = resolve_url_or_path(spec, cwd).with_context(|| {
+          format!("Invalid --allow-import-spec value: {spec}")
+        })?;
+        allow_list.specifiers.insert(specifier);
+      }
+    }
+    Ok(allow_list)
+  }
+
+  /// npm and jsr packages are allowed by name, and by version requirement
+  /// unless the allowed one has none. The modules of an allowed jsr package
+  /// on the registry are allowed with it.
+  fn allows(&self, specifier: &ModuleSpecifier) -> bool {
+    fn allows_req(allowed: &[PackageReq], req: &PackageReq) -> bool {
+      allowed.iter().any(|allowed| {
+        allowed.name == req.name
+          && (allowed.version_req.version_text() == "*"
+            || allowed.version_req == req.version_req)
+      })
+    }
+
+    if let Ok(req_ref) = NpmPackageReqReference::from_specifier(specifier) {
+      allows_req(&self.npm_reqs, req_ref.req())
+    } else if let Ok(req_ref) =
+      JsrPackageReqReference::from_specifier(specifier)
+    {
+      allows_req(&self.jsr_reqs, req_ref.req())
+    } else if let Some((name, version)) = jsr_url_to_name_and_version(specifier)
+    {
+      allows_version(&self.jsr_reqs, &name, &version)
+    } else {
+      self.specifiers.contains(specifier)
+    }
+  }
+
+  /// Whether the npm package `name@version` may be imported by modules of
+  /// other npm packages.
+  fn allows_npm_package(&self, name: &str, version: &Version) -> bool {
+    allows_version(&self.npm_reqs, name, version)
+  }
+}
+
+fn allows_version(
+  allowed: &[PackageReq],
+  name: &str,
+  version: &Version,
+) -> bool {
+  allowed.iter().any(|allowed| {
+    allowed.name == name
+      && (allowed.version_req.version_text() == "*"
+        || allowed.version_req.matches(version))
+  })
+}
+
+/// Gets the package name and version of a module on the jsr registry, like
+/// `https://jsr.io/@std/path/1.0.2/mod.ts`.
+fn jsr_url_to_name_and_version(
+  specifier: &ModuleSpecifier,
+) -> Option<(String, Version)> {
+  let path = specifier.as_str().strip_prefix(jsr_url().as_str())?;
+  let mut parts = path.split('/');
+  let scope = parts.next().filter(|scope| scope.starts_with('@'))?;
+  let name = parts.next()?;
+  let version = Version::parse_standard(parts.next()?).ok()?;
+  // a module of the package rather than its metadata
+  parts.next()?;
+  Some((format!("{}/{}", scope, name), version))
+}
+
 /// A resolver that takes care of resolution, taking into account loaded
 /// import map, JSX settings.
 #[derive(Debug)]
@@ -437,6 +530,7 @@ pub struct CliGraphResolver {
   found_package_json_dep_flag: AtomicFlag,
   bare_node_builtins_enabled: bool,
   warned_pkgs: DashSet<PackageReq>,
+  maybe_import_allow_list: Option<ImportAllowList>,
 }
 
 pub struct CliGraphResolverOptions<'a> {
@@ -447,6 +541,7 @@ pub struct CliGraphResolverOptions<'a> {
   pub bare_node_builtins_enabled: bool,
   pub maybe_jsx_import_source_config: Option<JsxImportSourceConfig>,
   pub maybe_vendor_dir: Option<&'a PathBuf>,
+  pub maybe_import_allow_list: Option<ImportAllowList>,
 }
 
 impl CliGraphResolver {
@@ -473,6 +568,7 @@ impl CliGraphResolver {
       found_package_json_dep_flag: Default::default(),
       bare_node_builtins_enabled: options.bare_node_builtins_enabled,
       warned_pkgs: Default::default(),
+      maybe_import_allow_list: options.maybe_import_allow_list,
     }
   }
 
@@ -487,6 +583,60 @@ impl CliGraphResolver {
       bare_node_builtins_enabled: self.bare_node_builtins_enabled,
     }
   }
+
+  /// Checks a module resolved with node resolution from `referrer`, a module
+  /// in an npm package, against `--import-policy=deny`. The modules of the
+  /// same package and of allowed npm packages may be imported.
+  pub fn check_npm_package_import(
+    &self,
+    specifier: &ModuleSpecifier,
+    referrer: &ModuleSpecifier,
+  ) -> Result<(), AnyError> {
+    let (Some(allow_list), Some(node_resolver)) =
+      (&self.maybe_import_allow_list, &self.node_resolver)
+    else {
+      return Ok(());
+    };
+    if specifier.scheme() != "file" {
+      // node built-ins
+      return if allow_list.allows(specifier) {
+        Ok(())
+      } else {
+        Err(import_not_allowed_error(specifier))
+      };
+    }
+    let Some(pkg_json) = node_resolver.get_closest_package_json(specifier)?
+    else {
+      return if allow_list.allows(specifier) {
+        Ok(())
+      } else {
+        Err(import_not_allowed_error(specifier))
+      };
+    };
+    let referrer_pkg_json = node_resolver.get_closest_package_json(referrer)?;
+    if referrer_pkg_json.is_some_and(|p| p.path == pkg_json.path) {
+      return Ok(());
+    }
+    let name = pkg_json.name.as_deref().unwrap_or_default();
+    let version = pkg_json.version.as_deref().unwrap_or_default();
+    let allowed = Version::parse_from_npm(version)
+      .is_ok_and(|version| allow_list.allows_npm_package(name, &version));
+    if allowed {
+      Ok(())
+    } else {
+      Err(import_not_allowed_error(format!(
+        "npm:{}@{}",
+        name, version
+      )))
+    }
+  }
+}
+
+fn import_not_allowed_error(specifier: impl std::fmt::Display) -> AnyError {
+  anyhow!(
+    "Importing \"{}\" is not allowed by --import-policy=deny. Allow it with --allow-import-spec.",
+    specifier
+  )
 }
 
 impl Resolver for CliGraphResolver {
@@ -523,10 +673,14 @@ impl Resolver for CliGraphResolver {
     // Use node resolution if we're in an npm package
     if let Some(node_resolver) = self.node_resolver.as_ref() {
       if referrer.scheme() == "file" && node_resolver.in_npm_package(referrer) {
-        return node_resolver
+        let specifier = node_resolver
           .resolve(specifier, referrer, to_node_mode(mode))
           .map(|res| res.into_url())
-          .map_err(|e| ResolveError::Other(e.into()));
+          .map_err(|e| ResolveError::Other(e.into()))?;
+        self
+          .check_npm_package_import(&specifier, referrer)
+          .map_err(ResolveError::Other)?;
+        return Ok(specifier);
       }
     }
 
@@ -655,6 +809,14 @@ impl Resolver for CliGraphResolver {
       }
     }
 
+    if let (Some(allow_list), Ok(specifier)) =
+      (&self.maybe_import_allow_list, &result)
+    {
+      if !allow_list.allows(specifier) {
+        return Err(ResolveError::Other(import_not_allowed_error(specifier)));
+      }
+    }
+
     let Some(node_resolver) = &self.node_resolver else {
       return result;
     };
@@ -42,6 +42,7 @@ use log::Level;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::args::interpolate_env_file_name;
 use crate::args::CaData;
 use crate::args::CliOptions;
 use crate::args::CompileFlags;
@@ -565,10 +566,13 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       }
     };
 
-    let env_vars_from_env_file = match cli_options.env_file_name() {
-      Some(env_filename) => {
-        log::info!("{} Environment variables from the file \"{}\" were embedded in the generated executable file", crate::colors::yellow("Warning"), env_filename);
-        get_file_env_vars(env_filename.to_string())?
+    let env_vars_from_env_file = match cli_options.env_file_names() {
+      Some(env_filenames) => {
+        let env_vars = get_file_env_vars(env_filenames)?;
+        for env_filename in env_filenames {
+          log::info!("{} Environment variables from the file \"{}\" were embedded in the generated executable file", crate::colors::yellow("Warning"), env_filename);
+        }
+        env_vars
       }
       None => Default::default(),
     };
@@ -759,15 +763,24 @@ impl<'a> DenoCompileBinaryWriter<'a> {
 
 /// This function returns the environment variables specified
 /// in the passed environment file.
+/// Merges the variables of the environment files, with later files overriding
+/// earlier ones.
 fn get_file_env_vars(
-  filename: String,
+  filenames: &[String],
 ) -> Result<IndexMap<String, String>, dotenvy::Error> {
   let mut file_env_vars = IndexMap::new();
-  for item in dotenvy::from_filename_iter(filename)? {
-    let Ok((key, val)) = item else {
-      continue; // this failure will be warned about on load
-    };
-    file_env_vars.insert(key, val);
+  for filename in filenames {
+    let filename = interpolate_env_file_name(filename, |name| {
+      std::env::var(name)
+        .ok()
+        .or_else(|| file_env_vars.get(name).cloned())
+    });
+    for item in dotenvy::from_filename_iter(filename)? {
+      let Ok((key, val)) = item else {
+        continue; // this failure will be warned about on load
+      };
+      file_env_vars.insert(key, val);
+    }
   }
   Ok(file_env_vars)
 }
@@ -515,6 +515,11 @@ pub async fn run(
               registry_configs: Default::default(),
             }),
             lifecycle_scripts: Default::default(),
+            // follow the layout recorded in the node_modules directory
+            node_modules_layout: None,
+            maybe_registry_snapshot: None,
+            overrides: Default::default(),
+            record_contents: false,
           },
         ))
         .await?;
@@ -561,6 +566,11 @@ pub async fn run(
             // so no need to create actual `.npmrc` configuration.
             npmrc: create_default_npmrc(),
             lifecycle_scripts: Default::default(),
+            // follow the layout recorded in the node_modules directory
+            node_modules_layout: None,
+            maybe_registry_snapshot: None,
+            overrides: Default::default(),
+            record_contents: false,
           },
         ))
         .await?;
@@ -708,6 +718,9 @@ pub async fn run(
     CliMainWorkerOptions {
       argv: metadata.argv,
       log_level: WorkerLogLevel::Info,
+      check_resource_leaks: false,
+      exit_drain: None,
+      max_redirect_bytes: None,
       enable_op_summary_metrics: false,
       enable_testing_features: false,
       has_node_modules_dir,
@@ -731,6 +744,10 @@ pub async fn run(
       unstable: metadata.unstable_config.legacy_flag_enabled,
       create_hmr_runner: None,
       create_coverage_collector: None,
+      cpu_prof: None,
+      force_strict: false,
+      deprecated_api_error: false,
+      abort_on_warning: None,
     },
     None,
     None,
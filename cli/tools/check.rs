@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -7,6 +8,7 @@ use std::sync::Arc;
 use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
 use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
 use deno_graph::Module;
 use deno_graph::ModuleGraph;
 use deno_runtime::deno_node::NodeResolver;
@@ -52,6 +54,7 @@ pub struct CheckOptions {
 pub struct TypeChecker {
   caches: Arc<Caches>,
   cli_options: Arc<CliOptions>,
+  maybe_incremental_check_state: Option<Arc<IncrementalCheckState>>,
   module_graph_builder: Arc<ModuleGraphBuilder>,
   node_resolver: Arc<NodeResolver>,
   npm_resolver: Arc<dyn CliNpmResolver>,
@@ -61,6 +64,7 @@ impl TypeChecker {
   pub fn new(
     caches: Arc<Caches>,
     cli_options: Arc<CliOptions>,
+    maybe_incremental_check_state: Option<Arc<IncrementalCheckState>>,
     module_graph_builder: Arc<ModuleGraphBuilder>,
     node_resolver: Arc<NodeResolver>,
     npm_resolver: Arc<dyn CliNpmResolver>,
@@ -68,6 +72,7 @@ impl TypeChecker {
     Self {
       caches,
       cli_options,
+      maybe_incremental_check_state,
       module_graph_builder,
       node_resolver,
       npm_resolver,
@@ -142,26 +147,34 @@ impl TypeChecker {
       None => None, // we can't determine a check hash
     };
 
+    let maybe_incremental = self
+      .maybe_incremental_check_state
+      .as_ref()
+      .filter(|_| !options.reload)
+      .and_then(|state| {
+        let npm_check_hash = self.npm_resolver.check_state_hash()?;
+        let config_hash = get_incremental_config_hash(
+          npm_check_hash,
+          type_check_mode,
+          &ts_config,
+        );
+        Some((state, config_hash))
+      });
+
     // do not type check if we know this is type checked
     let cache = TypeCheckCache::new(self.caches.type_checking_cache_db());
     if !options.reload {
       if let Some(check_hash) = maybe_check_hash {
         if cache.has_check_hash(check_hash) {
           log::debug!("Already type checked.");
+          if let Some((state, config_hash)) = maybe_incremental {
+            state.update(&graph, config_hash, None, &Default::default());
+          }
           return Ok((graph.into(), Default::default()));
         }
       }
     }
 
-    for root in &graph.roots {
-      let root_str = root.as_str();
-      log::info!(
-        "{} {}",
-        colors::green("Check"),
-        to_percent_decoded_str(root_str)
-      );
-    }
-
     let check_js = ts_config.get_check_js();
     // while there might be multiple roots, we can't "merge" the build info, so we
     // try to retrieve the build info for first root, which is the most common use
@@ -189,22 +202,112 @@ impl TypeChecker {
     }
 
     let root_names = get_tsc_roots(&graph, check_js);
+    let maybe_check_only =
+      maybe_incremental.and_then(|(state, config_hash)| {
+        state.modules_to_check(&graph, config_hash, &root_names)
+      });
+    match &maybe_check_only {
+      Some(check_only) => {
+        for specifier in check_only {
+          log::info!(
+            "{} {}",
+            colors::green("Check"),
+            to_percent_decoded_str(specifier.as_str())
+          );
+        }
+      }
+      None => {
+        for root in &graph.roots {
+          log::info!(
+            "{} {}",
+            colors::green("Check"),
+            to_percent_decoded_str(root.as_str())
+          );
+        }
+      }
+    }
+
     let graph = Arc::new(graph);
-    let response = tsc::exec(tsc::Request {
-      config: ts_config,
-      debug: self.cli_options.log_level() == Some(log::Level::Debug),
-      graph: graph.clone(),
-      hash_data,
-      maybe_npm: Some(tsc::RequestNpmState {
-        node_resolver: self.node_resolver.clone(),
-        npm_resolver: self.npm_resolver.clone(),
-      }),
-      maybe_tsbuildinfo,
-      root_names,
-      check_mode: type_check_mode,
-    })?;
-
-    let mut diagnostics = response.diagnostics.filter(|d| {
+    let create_request =
+      |check_only: Option<Vec<ModuleSpecifier>>,
+       maybe_tsbuildinfo: Option<String>| {
+        tsc::Request {
+          config: ts_config.clone(),
+          debug: self.cli_options.log_level() == Some(log::Level::Debug),
+          graph: graph.clone(),
+          hash_data,
+          maybe_npm: Some(tsc::RequestNpmState {
+            node_resolver: self.node_resolver.clone(),
+            npm_resolver: self.npm_resolver.clone(),
+          }),
+          maybe_tsbuildinfo,
+          root_names: root_names.clone(),
+          check_mode: type_check_mode,
+          check_only,
+        }
+      };
+    let check_groups = match self.cli_options.check_parallel() {
+      Some(threads) => partition_check_modules(
+        &graph,
+        &root_names,
+        maybe_check_only.as_deref(),
+        threads.get(),
+      ),
+      None => Vec::new(),
+    };
+    let (diagnostics, maybe_tsbuildinfo) = if check_groups.len() > 1 {
+      log::debug!(
+        "Type checking {} groups of modules in parallel",
+        check_groups.len()
+      );
+      // the build info can't be merged, so only the group with the first
+      // root uses it and updates it, and the modules of the other groups are
+      // checked again the next time
+      let first_root = graph.resolve(&graph.roots[0]);
+      let build_info_group = check_groups
+        .iter()
+        .position(|group| group.contains(first_root))
+        .unwrap_or(0);
+      let mut maybe_tsbuildinfo = maybe_tsbuildinfo;
+      let requests = check_groups
+        .into_iter()
+        .enumerate()
+        .map(|(index, group)| {
+          let maybe_tsbuildinfo = if index == build_info_group {
+            maybe_tsbuildinfo.take()
+          } else {
+            None
+          };
+          create_request(Some(group), maybe_tsbuildinfo)
+        })
+        .collect::<Vec<_>>();
+      let responses = exec_parallel(requests)?;
+      let mut all_diagnostics = Vec::with_capacity(responses.len());
+      let mut maybe_tsbuildinfo = None;
+      for (index, response) in responses.into_iter().enumerate() {
+        log::debug!("{}", response.stats);
+        all_diagnostics.push(response.diagnostics);
+        if index == build_info_group {
+          maybe_tsbuildinfo = response.maybe_tsbuildinfo;
+        }
+      }
+      (
+        tsc::Diagnostics::merge_sorted(all_diagnostics),
+        maybe_tsbuildinfo,
+      )
+    } else {
+      let response =
+        tsc::exec(create_request(maybe_check_only.clone(), maybe_tsbuildinfo))?;
+      log::debug!("{}", response.stats);
+      // sorted like the diagnostics of a parallel check, so that the output
+      // doesn't depend on how the modules were split up
+      (
+        tsc::Diagnostics::merge_sorted(vec![response.diagnostics]),
+        response.maybe_tsbuildinfo,
+      )
+    };
+
+    let mut diagnostics = diagnostics.filter(|d| {
       if self.is_remote_diagnostic(d) {
         type_check_mode == TypeCheckMode::All && d.include_when_remote()
       } else {
@@ -214,7 +317,7 @@ impl TypeChecker {
 
     diagnostics.apply_fast_check_source_maps(&graph);
 
-    if let Some(tsbuildinfo) = response.maybe_tsbuildinfo {
+    if let Some(tsbuildinfo) = maybe_tsbuildinfo {
       cache.set_tsbuildinfo(&graph.roots[0], &tsbuildinfo);
     }
 
@@ -224,7 +327,14 @@ impl TypeChecker {
       }
     }
 
-    log::debug!("{}", response.stats);
+    if let Some((state, config_hash)) = maybe_incremental {
+      state.update(
+        &graph,
+        config_hash,
+        maybe_check_only.as_deref(),
+        &diagnostics,
+      );
+    }
 
     Ok((graph, diagnostics))
   }
@@ -244,6 +354,183 @@ impl TypeChecker {
   }
 }
 
+/// Remembers the modules seen by the previous type check of a `--watch`
+/// session, so that the next check only needs to look at the modules that
+/// changed since then and the modules that depend on them.
+#[derive(Default)]
+pub struct IncrementalCheckState(Mutex<Option<IncrementalCheck>>);
+
+struct IncrementalCheck {
+  /// Hash of the inputs that affect every module, like the compiler options.
+  config_hash: u64,
+  module_hashes: HashMap<ModuleSpecifier, u64>,
+  /// Modules that need to be checked again because they had diagnostics.
+  modules_with_diagnostics: HashSet<ModuleSpecifier>,
+}
+
+impl IncrementalCheckState {
+  /// Gets the tsc roots that are affected by the changes since the previous
+  /// check or `None` when everything needs to be checked.
+  fn modules_to_check(
+    &self,
+    graph: &ModuleGraph,
+    config_hash: u64,
+    root_names: &[(ModuleSpecifier, MediaType)],
+  ) -> Option<Vec<ModuleSpecifier>> {
+    let previous = self.0.lock();
+    let previous = previous
+      .as_ref()
+      .filter(|previous| previous.config_hash == config_hash)?;
+
+    let mut pending = VecDeque::new();
+    for (specifier, hash) in get_module_hashes(graph) {
+      if previous.module_hashes.get(&specifier) == Some(&hash) {
+        continue;
+      }
+      let is_declaration_file = graph
+        .get(&specifier)
+        .and_then(|module| module.js())
+        .map(|module| {
+          matches!(
+            module.media_type,
+            MediaType::Dts | MediaType::Dmts | MediaType::Dcts
+          )
+        })
+        .unwrap_or(false);
+      if is_declaration_file {
+        // declaration files can change the global scope of any module
+        return None;
+      }
+      pending.push_back(specifier);
+    }
+
+    let dependents = get_dependents(graph);
+    let mut affected = pending.iter().cloned().collect::<HashSet<_>>();
+    while let Some(specifier) = pending.pop_front() {
+      for dependent in dependents.get(&specifier).into_iter().flatten() {
+        if affected.insert(dependent.clone()) {
+          pending.push_back(dependent.clone());
+        }
+      }
+    }
+
+    Some(
+      root_names
+        .iter()
+        .map(|(specifier, _)| specifier)
+        .filter(|specifier| {
+          affected.contains(*specifier)
+            || previous.modules_with_diagnostics.contains(*specifier)
+        })
+        .cloned()
+        .collect(),
+    )
+  }
+
+  /// Stores the result of a check. `checked` is the list of modules that
+  /// were checked or `None` if all of them were.
+  fn update(
+    &self,
+    graph: &ModuleGraph,
+    config_hash: u64,
+    checked: Option<&[ModuleSpecifier]>,
+    diagnostics: &Diagnostics,
+  ) {
+    let mut state = self.0.lock();
+    if diagnostics.iter().any(|d| d.file_name.is_none()) {
+      // diagnostics not tied to a module need a full check to go away
+      *state = None;
+      return;
+    }
+    let mut modules_with_diagnostics = match (checked, state.take()) {
+      (Some(checked), Some(previous))
+        if previous.config_hash == config_hash =>
+      {
+        let mut modules = previous.modules_with_diagnostics;
+        for specifier in checked {
+          modules.remove(specifier);
+        }
+        modules
+      }
+      _ => HashSet::new(),
+    };
+    modules_with_diagnostics.extend(
+      diagnostics
+        .iter()
+        .filter_map(|d| ModuleSpecifier::parse(d.file_name.as_ref()?).ok()),
+    );
+    *state = Some(IncrementalCheck {
+      config_hash,
+      module_hashes: get_module_hashes(graph),
+      modules_with_diagnostics,
+    });
+  }
+}
+
+fn get_incremental_config_hash(
+  npm_check_hash: u64,
+  type_check_mode: TypeCheckMode,
+  ts_config: &TsConfig,
+) -> u64 {
+  FastInsecureHasher::new_deno_versioned()
+    .write_u64(npm_check_hash)
+    .write_u8(type_check_mode as u8)
+    .write(&ts_config.as_bytes())
+    .finish()
+}
+
+fn get_module_hashes(graph: &ModuleGraph) -> HashMap<ModuleSpecifier, u64> {
+  graph
+    .modules()
+    .map(|module| {
+      let mut hasher = FastInsecureHasher::new_without_deno_version();
+      match module {
+        Module::Js(module) => {
+          hasher.write_str(&module.source);
+        }
+        Module::Json(module) => {
+          hasher.write_str(&module.source);
+        }
+        Module::Npm(_) | Module::Node(_) | Module::External(_) => {
+          // covered by the npm snapshot in the config hash
+        }
+      }
+      (module.specifier().clone(), hasher.finish())
+    })
+    .collect()
+}
+
+/// Maps each module to the modules that import it.
+fn get_dependents(
+  graph: &ModuleGraph,
+) -> HashMap<ModuleSpecifier, Vec<ModuleSpecifier>> {
+  let mut dependents: HashMap<ModuleSpecifier, Vec<ModuleSpecifier>> =
+    HashMap::new();
+  for module in graph.modules() {
+    let Some(module) = module.js() else {
+      continue;
+    };
+    let types_dependency = module
+      .maybe_types_dependency
+      .as_ref()
+      .and_then(|d| d.dependency.ok())
+      .map(|d| &d.specifier);
+    let dependencies = module
+      .dependencies
+      .values()
+      .flat_map(|dep| [dep.get_code(), dep.get_type()])
+      .flatten()
+      .chain(types_dependency);
+    for specifier in dependencies {
+      dependents
+        .entry(graph.resolve(specifier).clone())
+        .or_default()
+        .push(module.specifier.clone());
+    }
+  }
+  dependents
+}
+
 enum CheckHashResult {
   Hash(CacheDBHash),
   NoFiles,
@@ -343,6 +630,119 @@ fn get_check_hash(
   }
 }
 
+/// Splits the modules to type check into at most `max_groups` groups for
+/// `--check-parallel`. The import subtrees below the roots that don't share
+/// any modules are independent of each other, and each of them is kept in a
+/// single group so that it's only checked by one compiler. The subtrees are
+/// spread so that the groups are about the same size.
+fn partition_check_modules(
+  graph: &ModuleGraph,
+  root_names: &[(ModuleSpecifier, MediaType)],
+  maybe_check_only: Option<&[ModuleSpecifier]>,
+  max_groups: usize,
+) -> Vec<Vec<ModuleSpecifier>> {
+  let maybe_check_only = maybe_check_only
+    .map(|check_only| check_only.iter().collect::<HashSet<_>>());
+  let modules = root_names
+    .iter()
+    .map(|(specifier, _)| specifier)
+    .filter(|specifier| specifier.scheme() != "asset")
+    .filter(|specifier| {
+      maybe_check_only
+        .as_ref()
+        .map(|check_only| check_only.contains(specifier))
+        .unwrap_or(true)
+    })
+    .collect::<Vec<_>>();
+  if modules.is_empty() {
+    return Vec::new();
+  }
+
+  // the imports of the roots are left out, as every module hangs off a root
+  // and the whole graph would otherwise always be a single subtree
+  let roots = graph
+    .roots
+    .iter()
+    .map(|root| graph.resolve(root))
+    .collect::<HashSet<_>>();
+  let dependents = get_dependents(graph);
+  let mut subgraphs = DisjointSets::default();
+  for (dependency, dependents) in &dependents {
+    for dependent in dependents {
+      if !roots.contains(dependent) {
+        subgraphs.union(dependency, dependent);
+      }
+    }
+  }
+
+  // the modules of each subgraph, in the order they were first seen
+  let mut components: Vec<Vec<ModuleSpecifier>> = Vec::new();
+  let mut component_indexes = HashMap::new();
+  for specifier in modules {
+    let index = *component_indexes
+      .entry(subgraphs.find(specifier))
+      .or_insert_with(|| {
+        components.push(Vec::new());
+        components.len() - 1
+      });
+    components[index].push(specifier.clone());
+  }
+
+  // the largest subgraphs go first, each into the smallest group so far
+  components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+  let mut groups = vec![Vec::new(); max_groups.min(components.len())];
+  for component in components {
+    let group = groups.iter_mut().min_by_key(|group| group.len()).unwrap();
+    group.extend(component);
+  }
+  groups
+}
+
+/// Union-find over module specifiers, used to find the subgraphs of modules
+/// that are connected through imports.
+#[derive(Default)]
+struct DisjointSets<'a> {
+  indexes: HashMap<&'a ModuleSpecifier, usize>,
+  parents: Vec<usize>,
+}
+
+impl<'a> DisjointSets<'a> {
+  fn find(&mut self, specifier: &'a ModuleSpecifier) -> usize {
+    let mut index = *self.indexes.entry(specifier).or_insert_with(|| {
+      self.parents.push(self.parents.len());
+      self.parents.len() - 1
+    });
+    while self.parents[index] != index {
+      self.parents[index] = self.parents[self.parents[index]];
+      index = self.parents[index];
+    }
+    index
+  }
+
+  fn union(&mut self, a: &'a ModuleSpecifier, b: &'a ModuleSpecifier) {
+    let a = self.find(a);
+    let b = self.find(b);
+    self.parents[a] = b;
+  }
+}
+
+/// Runs the requests on their own threads, each with its own instance of
+/// the compiler, returning the responses in the order of the requests.
+fn exec_parallel(
+  requests: Vec<tsc::Request>,
+) -> Result<Vec<tsc::Response>, AnyError> {
+  std::thread::scope(|scope| {
+    let handles = requests
+      .into_iter()
+      .map(|request| scope.spawn(move || tsc::exec(request)))
+      .collect::<Vec<_>>();
+    handles
+      .into_iter()
+      .map(|handle| handle.join().unwrap())
+      .collect()
+  })
+}
+
 /// Transform the graph into root specifiers that we can feed `tsc`. We have to
 /// provide the media type for root modules because `tsc` does not "resolve" the
 /// media type like other modules, as well as a root specifier needs any
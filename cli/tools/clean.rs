@@ -1,12 +1,29 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
+use deno_core::resolve_url_or_path;
+use deno_core::serde_json;
+use deno_core::serde_json::Map;
+use deno_core::serde_json::Value;
+use deno_graph::GraphKind;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_semver::jsr::JsrPackageReqReference;
+use deno_semver::npm::NpmPackageReqReference;
+use deno_semver::package::PackageReq;
+use std::collections::HashSet;
 use std::path::Path;
+use std::sync::Arc;
 
+use crate::args::CleanFlags;
+use crate::args::Flags;
 use crate::cache::DenoDir;
 use crate::colors;
 use crate::display;
+use crate::factory::CliFactory;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 use crate::util::progress_bar::ProgressMessagePrompt;
@@ -95,3 +112,381 @@ fn remove_file(
     .with_context(|| format!("Failed to remove file: {}", path.display()))?;
   Ok(())
 }
+
+/// The lockfile entries used by a module graph.
+#[derive(Debug, Default)]
+struct UsedLockfileEntries {
+  /// `npm:` and `jsr:` package requirements, as they are keyed in
+  /// `packages.specifiers`.
+  specifiers: HashSet<String>,
+  /// The remote modules in the graph and the urls redirecting to them.
+  remote: HashSet<String>,
+}
+
+impl UsedLockfileEntries {
+  fn from_graph(graph: &ModuleGraph) -> Self {
+    let mut used = Self::default();
+    for root in &graph.roots {
+      used.add_package_specifier(root);
+    }
+    for module in graph.modules() {
+      let specifier = module.specifier();
+      if matches!(specifier.scheme(), "http" | "https") {
+        used.remote.insert(specifier.to_string());
+      }
+      used.add_package_specifier(specifier);
+      let Module::Js(module) = module else {
+        continue;
+      };
+      for dep in module.dependencies.values() {
+        for specifier in [dep.get_code(), dep.get_type()].into_iter().flatten()
+        {
+          used.add_package_specifier(specifier);
+        }
+      }
+      if let Some(specifier) = module
+        .maybe_types_dependency
+        .as_ref()
+        .and_then(|dep| dep.dependency.maybe_specifier())
+      {
+        used.add_package_specifier(specifier);
+      }
+    }
+    for from in graph.redirects.keys() {
+      if used.remote.contains(graph.resolve(from).as_str()) {
+        used.remote.insert(from.to_string());
+      }
+    }
+    used
+  }
+
+  fn add_package_specifier(&mut self, specifier: &ModuleSpecifier) {
+    if let Ok(reference) = NpmPackageReqReference::from_specifier(specifier) {
+      self.specifiers.insert(format!("npm:{}", reference.req()));
+    } else if let Ok(reference) =
+      JsrPackageReqReference::from_specifier(specifier)
+    {
+      self.specifiers.insert(format!("jsr:{}", reference.req()));
+    }
+  }
+}
+
+/// Removes the lockfile entries that the module graph of the given files
+/// doesn't use, along with the npm and jsr packages only they depend on.
+pub async fn prune_lockfile(
+  flags: Arc<Flags>,
+  clean_flags: CleanFlags,
+) -> Result<(), AnyError> {
+  let factory = CliFactory::from_flags(flags);
+  let cli_options = factory.cli_options()?;
+  let Some(lockfile) = cli_options.maybe_lockfile() else {
+    bail!("No lockfile was found for the current directory.");
+  };
+  let roots = clean_flags
+    .files
+    .iter()
+    .map(|file| resolve_url_or_path(file, cli_options.initial_cwd()))
+    .collect::<Result<Vec<_>, _>>()?;
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let graph = module_graph_creator
+    .create_graph(GraphKind::All, roots)
+    .await?;
+  // the entries of modules that failed to load would look unused
+  module_graph_creator.graph_valid(&graph)?;
+
+  let used = UsedLockfileEntries::from_graph(&graph);
+  let mut content: Value =
+    serde_json::from_str(&lockfile.lock().as_json_string())?;
+  let removed = prune_lockfile_content(&mut content, &used);
+  let filename = lockfile.filename.display();
+  if removed.is_empty() {
+    log::info!("No unused entries in {}", filename);
+    return Ok(());
+  }
+  if clean_flags.dry_run {
+    log::info!("Would remove {} entries from {}:", removed.len(), filename);
+    for entry in &removed {
+      log::info!("  {}", entry);
+    }
+    return Ok(());
+  }
+  lockfile.write_json(&content)?;
+  log::info!(
+    "{} {} entries from {}",
+    colors::green("Removed"),
+    removed.len(),
+    filename
+  );
+  Ok(())
+}
+
+/// Removes the entries of the lockfile's `content` that aren't `used`,
+/// returning a description of each removed entry.
+fn prune_lockfile_content(
+  content: &mut Value,
+  used: &UsedLockfileEntries,
+) -> Vec<String> {
+  let empty = Map::new();
+  let section = |value: &Value, key: &str| -> Map<String, Value> {
+    value
+      .get(key)
+      .and_then(|v| v.as_object())
+      .unwrap_or(&empty)
+      .clone()
+  };
+  let packages = content.get("packages").cloned().unwrap_or(Value::Null);
+  let specifiers = section(&packages, "specifiers");
+  let npm = section(&packages, "npm");
+  let jsr = section(&packages, "jsr");
+
+  // dependencies in package.json files are always installed
+  let mut pending_specifiers = used.specifiers.iter().cloned().collect();
+  if let Some(workspace) = content.get("workspace") {
+    collect_package_json_deps(workspace, &mut pending_specifiers);
+  }
+  let mut kept_specifiers = HashSet::new();
+  let mut kept_jsr = HashSet::new();
+  let mut pending_npm = Vec::new();
+  while let Some(specifier) = pending_specifiers.pop() {
+    let Some(value) = specifiers.get(&specifier).and_then(|v| v.as_str())
+    else {
+      continue;
+    };
+    if !kept_specifiers.insert(specifier.clone()) {
+      continue;
+    }
+    if let Some(req) = specifier.strip_prefix("npm:") {
+      pending_npm.extend(package_id(req, value, "npm:"));
+    } else if let Some(req) = specifier.strip_prefix("jsr:") {
+      let Some(nv) = package_id(req, value, "jsr:") else {
+        continue;
+      };
+      if let Some(deps) = jsr
+        .get(&nv)
+        .and_then(|info| info.get("dependencies"))
+        .and_then(|deps| deps.as_array())
+      {
+        pending_specifiers
+          .extend(deps.iter().filter_map(|dep| dep.as_str()).map(String::from));
+      }
+      kept_jsr.insert(nv);
+    }
+  }
+  let mut kept_npm = HashSet::new();
+  while let Some(id) = pending_npm.pop() {
+    let Some(info) = npm.get(&id) else {
+      continue;
+    };
+    if !kept_npm.insert(id) {
+      continue;
+    }
+    let deps: Vec<&str> = match info.get("dependencies") {
+      Some(Value::Object(deps)) => {
+        deps.values().filter_map(|dep| dep.as_str()).collect()
+      }
+      Some(Value::Array(deps)) => {
+        deps.iter().filter_map(|dep| dep.as_str()).collect()
+      }
+      _ => Vec::new(),
+    };
+    pending_npm
+      .extend(deps.into_iter().filter_map(|dep| npm_dep_id(&npm, dep)));
+  }
+
+  let mut removed = Vec::new();
+  if let Some(packages) = content.get_mut("packages") {
+    retain_entries(packages, "specifiers", "", &mut removed, |key| {
+      kept_specifiers.contains(key)
+    });
+    retain_entries(packages, "jsr", "jsr:", &mut removed, |key| {
+      kept_jsr.contains(key)
+    });
+    retain_entries(packages, "npm", "npm:", &mut removed, |key| {
+      kept_npm.contains(key)
+    });
+  }
+  for section in ["redirects", "remote"] {
+    retain_entries(content, section, "", &mut removed, |key| {
+      used.remote.contains(key)
+    });
+  }
+  removed
+}
+
+fn retain_entries(
+  value: &mut Value,
+  section: &str,
+  prefix: &str,
+  removed: &mut Vec<String>,
+  keep: impl Fn(&str) -> bool,
+) {
+  let Some(entries) = value.get_mut(section).and_then(|v| v.as_object_mut())
+  else {
+    return;
+  };
+  entries.retain(|key, _| {
+    let keep = keep(key);
+    if !keep {
+      removed.push(format!("{}{}", prefix, key));
+    }
+    keep
+  });
+}
+
+/// Resolves the id of the package that a `packages.specifiers` entry points
+/// to. Lockfiles before v4 prefix it with the registry, v4 only stores the
+/// version.
+fn package_id(req: &str, value: &str, prefix: &str) -> Option<String> {
+  match value.strip_prefix(prefix) {
+    Some(id) => Some(id.to_string()),
+    None => {
+      let req = PackageReq::from_str(req).ok()?;
+      Some(format!("{}@{}", req.name, value))
+    }
+  }
+}
+
+/// Resolves a dependency of an npm package, which is either a package id or,
+/// in v4 lockfiles, only the name when there is a single version of it.
+fn npm_dep_id(npm: &Map<String, Value>, dep: &str) -> Option<String> {
+  if npm.contains_key(dep) {
+    return Some(dep.to_string());
+  }
+  npm
+    .keys()
+    .find(|id| {
+      id.strip_prefix(dep)
+        .is_some_and(|rest| rest.starts_with('@'))
+    })
+    .cloned()
+}
+
+fn collect_package_json_deps(workspace: &Value, deps: &mut Vec<String>) {
+  let Some(workspace) = workspace.as_object() else {
+    return;
+  };
+  for (key, value) in workspace {
+    if key == "packageJson" {
+      if let Some(items) =
+        value.get("dependencies").and_then(|deps| deps.as_array())
+      {
+        deps.extend(
+          items
+            .iter()
+            .filter_map(|dep| dep.as_str())
+            .map(String::from),
+        );
+      }
+    } else {
+      collect_package_json_deps(value, deps);
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn prune_lockfile_content_removes_unused() {
+    let mut content = json!({
+      "version": "3",
+      "packages": {
+        "specifiers": {
+          "jsr:@std/path@1": "jsr:@std/path@1.0.0",
+          "jsr:@std/assert@1": "jsr:@std/assert@1.0.0",
+          "jsr:@std/fs@1": "jsr:@std/fs@1.0.0",
+          "npm:chalk@5": "npm:chalk@5.0.1",
+          "npm:chalk@4": "npm:chalk@4.1.2"
+        },
+        "jsr": {
+          "@std/assert@1.0.0": { "integrity": "a" },
+          "@std/fs@1.0.0": { "integrity": "b" },
+          "@std/path@1.0.0": {
+            "integrity": "c",
+            "dependencies": ["jsr:@std/assert@1"]
+          }
+        },
+        "npm": {
+          "ansi-styles@4.3.0": { "integrity": "d", "dependencies": {} },
+          "chalk@4.1.2": {
+            "integrity": "e",
+            "dependencies": { "ansi-styles": "ansi-styles@4.3.0" }
+          },
+          "chalk@5.0.1": { "integrity": "f", "dependencies": {} }
+        }
+      },
+      "redirects": {
+        "https://deno.land/x/mod.ts": "https://deno.land/x@1.0.0/mod.ts",
+        "https://deno.land/x/old.ts": "https://deno.land/x@1.0.0/old.ts"
+      },
+      "remote": {
+        "https://deno.land/x@1.0.0/mod.ts": "g",
+        "https://deno.land/x@1.0.0/old.ts": "h"
+      },
+      "workspace": {
+        "packageJson": { "dependencies": ["npm:chalk@5"] }
+      }
+    });
+    let used = UsedLockfileEntries {
+      specifiers: HashSet::from(["jsr:@std/path@1".to_string()]),
+      remote: HashSet::from([
+        "https://deno.land/x/mod.ts".to_string(),
+        "https://deno.land/x@1.0.0/mod.ts".to_string(),
+      ]),
+    };
+    let removed = prune_lockfile_content(&mut content, &used);
+    assert_eq!(
+      removed,
+      vec![
+        "jsr:@std/fs@1",
+        "npm:chalk@4",
+        "jsr:@std/fs@1.0.0",
+        "npm:ansi-styles@4.3.0",
+        "npm:chalk@4.1.2",
+        "https://deno.land/x/old.ts",
+        "https://deno.land/x@1.0.0/old.ts",
+      ]
+    );
+    assert_eq!(
+      content["packages"]["specifiers"],
+      json!({
+        "jsr:@std/path@1": "jsr:@std/path@1.0.0",
+        "jsr:@std/assert@1": "jsr:@std/assert@1.0.0",
+        "npm:chalk@5": "npm:chalk@5.0.1"
+      })
+    );
+
+    // pruning again doesn't remove anything more
+    assert!(prune_lockfile_content(&mut content, &used).is_empty());
+  }
+
+  #[test]
+  fn prune_lockfile_content_v4() {
+    let mut content = json!({
+      "version": "4",
+      "packages": {
+        "specifiers": {
+          "npm:chalk@4": "4.1.2",
+          "npm:@denotest/add@1": "1.0.0"
+        },
+        "npm": {
+          "@denotest/add@1.0.0": { "integrity": "a" },
+          "ansi-styles@4.3.0": { "integrity": "b" },
+          "chalk@4.1.2": { "integrity": "c", "dependencies": ["ansi-styles"] }
+        }
+      }
+    });
+    let used = UsedLockfileEntries {
+      specifiers: HashSet::from(["npm:chalk@4".to_string()]),
+      remote: HashSet::new(),
+    };
+    let removed = prune_lockfile_content(&mut content, &used);
+    assert_eq!(
+      removed,
+      vec!["npm:@denotest/add@1", "npm:@denotest/add@1.0.0"]
+    );
+  }
+}
@@ -0,0 +1,261 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+
+use crate::args::ByonmOnStale;
+use crate::args::CliOptions;
+use crate::args::PackageJsonInstallDepsProvider;
+
+/// Files a package manager writes into `node_modules` on every install.
+const INSTALL_MARKER_FILES: [&str; 4] = [
+  ".package-lock.json",
+  ".modules.yaml",
+  ".yarn-state.yml",
+  ".yarn-integrity",
+];
+
+/// Lockfiles that identify the package manager of a project, in the order
+/// they are looked for. npm is used when none is found.
+const PACKAGE_MANAGER_LOCKFILES: [(&str, &str); 4] = [
+  ("pnpm-lock.yaml", "pnpm"),
+  ("yarn.lock", "yarn"),
+  ("bun.lockb", "bun"),
+  ("package-lock.json", "npm"),
+];
+
+/// Why the `node_modules` directory of a package.json is out of date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StaleReason {
+  /// A dependency has no folder in any `node_modules` directory Node's
+  /// resolution would look in.
+  MissingDependency {
+    package_json: PathBuf,
+    alias: String,
+  },
+  /// The package.json was modified after the package manager last wrote
+  /// its install marker into `node_modules`.
+  ModifiedAfterInstall { package_json: PathBuf },
+}
+
+impl StaleReason {
+  fn package_json(&self) -> &Path {
+    match self {
+      StaleReason::MissingDependency { package_json, .. }
+      | StaleReason::ModifiedAfterInstall { package_json } => package_json,
+    }
+  }
+
+  fn describe(&self) -> String {
+    match self {
+      StaleReason::MissingDependency {
+        package_json,
+        alias,
+      } => format!(
+        "\"{}\" of {} is not installed",
+        alias,
+        package_json.display()
+      ),
+      StaleReason::ModifiedAfterInstall { package_json } => format!(
+        "{} was modified after the last install",
+        package_json.display()
+      ),
+    }
+  }
+}
+
+/// Checks the `node_modules` directories of the package.json files in the
+/// workspace before running, for `--byonm-on-stale`.
+pub fn check_node_modules(
+  cli_options: &CliOptions,
+  on_stale: ByonmOnStale,
+) -> Result<(), AnyError> {
+  let reasons = find_stale_reasons(cli_options);
+  if reasons.is_empty() {
+    return Ok(());
+  }
+  let description = reasons
+    .iter()
+    .map(|reason| format!("  {}", reason.describe()))
+    .collect::<Vec<_>>()
+    .join("\n");
+  match on_stale {
+    ByonmOnStale::Error => bail!(
+      "The node_modules directory is out of date:\n{}\nRun your package manager's install command or pass --byonm-on-stale=auto-install.",
+      description
+    ),
+    ByonmOnStale::Warn => {
+      log::warn!(
+        "{} The node_modules directory is out of date:\n{}",
+        crate::colors::yellow("Warning"),
+        description
+      );
+      Ok(())
+    }
+    ByonmOnStale::AutoInstall => {
+      let mut install_dirs = Vec::new();
+      for reason in &reasons {
+        let dir = install_dir(reason.package_json());
+        if !install_dirs.contains(&dir) {
+          install_dirs.push(dir);
+        }
+      }
+      for dir in install_dirs {
+        run_package_manager_install(&dir)?;
+      }
+      let reasons = find_stale_reasons(cli_options);
+      if let Some(reason) = reasons
+        .iter()
+        .find(|reason| matches!(reason, StaleReason::MissingDependency { .. }))
+      {
+        bail!(
+          "The node_modules directory is still out of date after installing: {}",
+          reason.describe()
+        );
+      }
+      Ok(())
+    }
+  }
+}
+
+fn find_stale_reasons(cli_options: &CliOptions) -> Vec<StaleReason> {
+  let deps =
+    PackageJsonInstallDepsProvider::from_workspace(cli_options.workspace());
+  let mut reasons = Vec::new();
+  for pkg in deps.remote_pkgs() {
+    let package_json = pkg.base_dir.join("package.json");
+    if find_installed_package(&pkg.base_dir, &pkg.alias).is_none() {
+      reasons.push(StaleReason::MissingDependency {
+        package_json,
+        alias: pkg.alias.clone(),
+      });
+    } else if is_modified_after_install(&package_json)
+      && !reasons
+        .iter()
+        .any(|reason| reason.package_json() == package_json)
+    {
+      reasons.push(StaleReason::ModifiedAfterInstall { package_json });
+    }
+  }
+  reasons
+}
+
+/// Resolves a package the way Node does, by looking in the `node_modules`
+/// directory of every ancestor.
+fn find_installed_package(base_dir: &Path, alias: &str) -> Option<PathBuf> {
+  base_dir.ancestors().find_map(|ancestor| {
+    let mut dir = ancestor.join("node_modules");
+    for part in alias.split('/') {
+      dir.push(part);
+    }
+    dir.is_dir().then_some(dir)
+  })
+}
+
+fn is_modified_after_install(package_json: &Path) -> bool {
+  let Some(dir) = package_json.parent() else {
+    return false;
+  };
+  let Some(package_json_mtime) = modified_time(package_json) else {
+    return false;
+  };
+  let last_install = dir.ancestors().find_map(|ancestor| {
+    INSTALL_MARKER_FILES.iter().find_map(|marker| {
+      modified_time(&ancestor.join("node_modules").join(marker))
+    })
+  });
+  match last_install {
+    Some(last_install) => package_json_mtime > last_install,
+    // without a marker there's nothing to compare against
+    None => false,
+  }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+  std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// The directory to install from: the closest one with a lockfile of a
+/// package manager, otherwise the one of the package.json.
+fn install_dir(package_json: &Path) -> PathBuf {
+  let dir = package_json.parent().unwrap_or(package_json);
+  dir
+    .ancestors()
+    .find(|ancestor| {
+      PACKAGE_MANAGER_LOCKFILES
+        .iter()
+        .any(|(lockfile, _)| ancestor.join(lockfile).is_file())
+    })
+    .unwrap_or(dir)
+    .to_path_buf()
+}
+
+fn package_manager_for_dir(dir: &Path) -> &'static str {
+  PACKAGE_MANAGER_LOCKFILES
+    .iter()
+    .find(|(lockfile, _)| dir.join(lockfile).is_file())
+    .map(|(_, package_manager)| *package_manager)
+    .unwrap_or("npm")
+}
+
+fn run_package_manager_install(dir: &Path) -> Result<(), AnyError> {
+  let package_manager = package_manager_for_dir(dir);
+  log::info!(
+    "{} {} install in {}",
+    crate::colors::green("Running"),
+    package_manager,
+    dir.display()
+  );
+  // package managers are batch scripts on windows
+  let mut command = if cfg!(windows) {
+    let mut command = std::process::Command::new("cmd");
+    command.args(["/C", package_manager, "install"]);
+    command
+  } else {
+    let mut command = std::process::Command::new(package_manager);
+    command.arg("install");
+    command
+  };
+  let status = command.current_dir(dir).status().with_context(|| {
+    format!("Failed running `{} install`.", package_manager)
+  })?;
+  if !status.success() {
+    bail!(
+      "`{} install` failed with {} in {}",
+      package_manager,
+      status,
+      dir.display()
+    );
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_find_installed_package() {
+    let temp_dir = test_util::TempDir::new();
+    let root = temp_dir.path().as_path().to_path_buf();
+    let nested = root.join("packages").join("a");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir_all(root.join("node_modules/@scope/pkg")).unwrap();
+    std::fs::create_dir_all(nested.join("node_modules/chalk")).unwrap();
+
+    assert_eq!(
+      find_installed_package(&nested, "@scope/pkg"),
+      Some(root.join("node_modules").join("@scope").join("pkg"))
+    );
+    assert_eq!(
+      find_installed_package(&nested, "chalk"),
+      Some(nested.join("node_modules").join("chalk"))
+    );
+    assert_eq!(find_installed_package(&root, "chalk"), None);
+  }
+}
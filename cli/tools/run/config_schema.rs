@@ -0,0 +1,349 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_config::deno_json::ConfigFile;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::Value;
+use once_cell::sync::Lazy;
+
+use crate::args::CliOptions;
+
+static CONFIG_SCHEMA: Lazy<Value> = Lazy::new(|| {
+  serde_json::from_str(include_str!("../../schemas/config-file.v1.json"))
+    .unwrap()
+});
+
+/// Validates the resolved `deno.json` files against the config file schema
+/// for `--config-schema-validate`, failing with every key that has an
+/// unknown name or the wrong type.
+pub fn validate_config_files(cli_options: &CliOptions) -> Result<(), AnyError> {
+  let mut config_files = Vec::with_capacity(2);
+  if let Some(config_file) = cli_options.start_dir.maybe_deno_json() {
+    config_files.push(config_file.clone());
+  }
+  if let Some(root_config_file) = cli_options.workspace().root_deno_json() {
+    if config_files
+      .iter()
+      .all(|config_file| config_file.specifier != root_config_file.specifier)
+    {
+      config_files.push(root_config_file.clone());
+    }
+  }
+  for config_file in config_files {
+    validate_config_file(&config_file)?;
+  }
+  Ok(())
+}
+
+fn validate_config_file(config_file: &ConfigFile) -> Result<(), AnyError> {
+  let Ok(path) = config_file.specifier.to_file_path() else {
+    return Ok(());
+  };
+  let text = std::fs::read_to_string(&path)
+    .with_context(|| format!("Failed reading '{}'.", path.display()))?;
+  let value = jsonc_parser::parse_to_serde_value(&text, &Default::default())
+    .with_context(|| format!("Failed parsing '{}'.", path.display()))?
+    .unwrap_or(Value::Null);
+
+  let mut errors = Vec::new();
+  validate_value(&CONFIG_SCHEMA, &value, "", &mut errors);
+  if errors.is_empty() {
+    return Ok(());
+  }
+  bail!(
+    "Invalid config file '{}':\n{}",
+    config_file.specifier,
+    errors
+      .iter()
+      .map(|error| format!("  {}", error))
+      .collect::<Vec<_>>()
+      .join("\n")
+  );
+}
+
+struct SchemaError {
+  path: String,
+  message: String,
+}
+
+impl SchemaError {
+  fn new(path: &str, message: String) -> Self {
+    Self {
+      path: path.to_string(),
+      message,
+    }
+  }
+}
+
+impl std::fmt::Display for SchemaError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.path.is_empty() {
+      write!(f, "{}", self.message)
+    } else {
+      write!(f, "{}: {}", self.path, self.message)
+    }
+  }
+}
+
+/// Checks `value` against the subset of JSON schema that the config file
+/// schema uses.
+fn validate_value(
+  schema: &Value,
+  value: &Value,
+  path: &str,
+  errors: &mut Vec<SchemaError>,
+) {
+  if let Some(variants) = schema.get("oneOf").and_then(|v| v.as_array()) {
+    validate_one_of(variants, value, path, errors);
+    return;
+  }
+
+  if let Some(expected) = schema.get("type") {
+    let types = match expected {
+      Value::String(ty) => vec![ty.as_str()],
+      Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+      _ => vec![],
+    };
+    if !types.is_empty() && !types.iter().any(|ty| matches_type(ty, value)) {
+      errors.push(SchemaError::new(
+        path,
+        format!(
+          "expected {}, found {}",
+          types.join(" or "),
+          type_name(value)
+        ),
+      ));
+      return;
+    }
+  }
+
+  if let Some(allowed) = schema.get("enum").and_then(|v| v.as_array()) {
+    if !allowed.contains(value) {
+      errors.push(SchemaError::new(
+        path,
+        format!(
+          "expected one of {}, found {}",
+          allowed
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+          value
+        ),
+      ));
+      return;
+    }
+  }
+
+  match value {
+    Value::String(text) => {
+      if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
+        if let Ok(regex) = regex::Regex::new(pattern) {
+          if !regex.is_match(text) {
+            errors.push(SchemaError::new(
+              path,
+              format!("\"{}\" does not match the pattern {}", text, pattern),
+            ));
+          }
+        }
+      }
+    }
+    Value::Array(items) => {
+      if let Some(min_items) = schema.get("minItems").and_then(|v| v.as_u64()) {
+        if (items.len() as u64) < min_items {
+          errors.push(SchemaError::new(
+            path,
+            format!("expected at least {} items", min_items),
+          ));
+        }
+      }
+      if let Some(item_schema) = schema.get("items") {
+        for (index, item) in items.iter().enumerate() {
+          let item_path = format!("{}[{}]", path, index);
+          validate_value(item_schema, item, &item_path, errors);
+        }
+      }
+    }
+    Value::Object(object) => {
+      validate_object(schema, object, path, errors);
+    }
+    Value::Null | Value::Bool(_) | Value::Number(_) => {}
+  }
+}
+
+fn validate_object(
+  schema: &Value,
+  object: &serde_json::Map<String, Value>,
+  path: &str,
+  errors: &mut Vec<SchemaError>,
+) {
+  let properties = schema.get("properties").and_then(|v| v.as_object());
+  let pattern_properties = schema
+    .get("patternProperties")
+    .and_then(|v| v.as_object())
+    .map(|patterns| {
+      patterns
+        .iter()
+        .filter_map(|(pattern, schema)| {
+          regex::Regex::new(pattern).ok().map(|regex| (regex, schema))
+        })
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  let additional_properties = schema.get("additionalProperties");
+  // objects that list their keys only allow those keys, unless they say
+  // otherwise
+  let is_closed = match additional_properties {
+    Some(Value::Bool(allowed)) => !allowed,
+    Some(_) => false,
+    None => properties.is_some() && pattern_properties.is_empty(),
+  };
+
+  if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
+    for key in required.iter().filter_map(|key| key.as_str()) {
+      if !object.contains_key(key) {
+        errors.push(SchemaError::new(
+          path,
+          format!("missing required key \"{}\"", key),
+        ));
+      }
+    }
+  }
+
+  for (key, value) in object {
+    let key_path = if path.is_empty() {
+      key.to_string()
+    } else {
+      format!("{}.{}", path, key)
+    };
+    if let Some(property_schema) = properties.and_then(|p| p.get(key)) {
+      validate_value(property_schema, value, &key_path, errors);
+      continue;
+    }
+    if let Some((_, property_schema)) = pattern_properties
+      .iter()
+      .find(|(regex, _)| regex.is_match(key))
+    {
+      validate_value(property_schema, value, &key_path, errors);
+      continue;
+    }
+    match additional_properties {
+      Some(schema @ Value::Object(_)) => {
+        validate_value(schema, value, &key_path, errors);
+      }
+      _ if is_closed => {
+        // editors add this key to get completions from the schema
+        if path.is_empty() && key == "$schema" {
+          continue;
+        }
+        let mut known_keys = properties
+          .map(|p| p.keys().map(|k| k.as_str()).collect::<Vec<_>>())
+          .unwrap_or_default();
+        known_keys.sort();
+        errors.push(SchemaError {
+          path: key_path,
+          message: unknown_key_message(key, &known_keys),
+        });
+      }
+      _ => {}
+    }
+  }
+}
+
+fn validate_one_of(
+  variants: &[Value],
+  value: &Value,
+  path: &str,
+  errors: &mut Vec<SchemaError>,
+) {
+  let mut variant_errors = Vec::with_capacity(variants.len());
+  for variant in variants {
+    let mut errors = Vec::new();
+    validate_value(variant, value, path, &mut errors);
+    if errors.is_empty() {
+      return;
+    }
+    variant_errors.push((variant, errors));
+  }
+  // report the errors of the variant for this kind of value, if there is one
+  let matching_type = variant_errors
+    .iter()
+    .enumerate()
+    .filter(|(_, (variant, _))| {
+      variant
+        .get("type")
+        .and_then(|ty| ty.as_str())
+        .map(|ty| matches_type(ty, value))
+        .unwrap_or(false)
+    })
+    .map(|(index, _)| index)
+    .collect::<Vec<_>>();
+  if let [index] = matching_type[..] {
+    errors.extend(variant_errors.swap_remove(index).1);
+    return;
+  }
+  let types = variants
+    .iter()
+    .filter_map(|variant| variant.get("type").and_then(|ty| ty.as_str()))
+    .collect::<Vec<_>>();
+  let message = if types.is_empty() {
+    "does not match any of the allowed values".to_string()
+  } else {
+    format!(
+      "expected {}, found {}",
+      types.join(" or "),
+      type_name(value)
+    )
+  };
+  errors.push(SchemaError::new(path, message));
+}
+
+fn unknown_key_message(key: &str, known_keys: &[&str]) -> String {
+  let mut message = format!("unknown key \"{}\"", key);
+  // GH #4660: using `jaro` because `jaro_winkler` implementation in `strsim-rs` is wrong
+  let suggestion = known_keys
+    .iter()
+    .map(|known_key| (strsim::jaro(key, known_key), *known_key))
+    .filter(|(confidence, _)| *confidence > 0.8)
+    .max_by(|(a, _), (b, _)| a.total_cmp(b));
+  if let Some((_, suggestion)) = suggestion {
+    message.push_str(&format!(", did you mean \"{}\"?", suggestion));
+  }
+  if !known_keys.is_empty() {
+    message.push_str(&format!(
+      " Expected one of: {}",
+      known_keys
+        .iter()
+        .map(|key| format!("\"{}\"", key))
+        .collect::<Vec<_>>()
+        .join(", ")
+    ));
+  }
+  message
+}
+
+fn matches_type(ty: &str, value: &Value) -> bool {
+  match ty {
+    "null" => value.is_null(),
+    "boolean" => value.is_boolean(),
+    "integer" => value.is_i64() || value.is_u64(),
+    "number" => value.is_number(),
+    "string" => value.is_string(),
+    "array" => value.is_array(),
+    "object" => value.is_object(),
+    _ => true,
+  }
+}
+
+fn type_name(value: &Value) -> &'static str {
+  match value {
+    Value::Null => "null",
+    Value::Bool(_) => "boolean",
+    Value::Number(_) => "number",
+    Value::String(_) => "string",
+    Value::Array(_) => "array",
+    Value::Object(_) => "object",
+  }
+}
@@ -0,0 +1,109 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_core::error::AnyError;
+use deno_graph::Module;
+use serde::Serialize;
+
+use crate::args::PermissionFlags;
+use crate::colors;
+use crate::factory::CliFactory;
+use crate::util::display;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DryRunModule {
+  specifier: String,
+  media_type: String,
+  /// Fetched from a remote server rather than read from disk.
+  remote: bool,
+  /// Transpiled to JavaScript before it runs.
+  emit: bool,
+}
+
+/// What `deno run` would load for the main module, without running it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DryRunReport<'a> {
+  main_module: String,
+  modules: Vec<DryRunModule>,
+  npm_packages: Vec<String>,
+  permissions: &'a PermissionFlags,
+}
+
+/// Resolves the module graph of `main_module` the same way running it would,
+/// including the type check and npm install, then reports what it contains
+/// instead of creating a worker.
+#[allow(clippy::print_stdout)]
+pub async fn dry_run(
+  factory: &CliFactory,
+  main_module: &ModuleSpecifier,
+  json: bool,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options()?;
+  let graph = factory
+    .module_graph_creator()
+    .await?
+    .create_graph_and_maybe_check(vec![main_module.clone()])
+    .await?;
+
+  let mut modules = Vec::new();
+  for module in graph.modules() {
+    let (specifier, media_type) = match module {
+      Module::Js(module) => (&module.specifier, module.media_type),
+      Module::Json(module) => (&module.specifier, module.media_type),
+      Module::Npm(_) | Module::Node(_) | Module::External(_) => continue,
+    };
+    modules.push(DryRunModule {
+      specifier: specifier.to_string(),
+      media_type: media_type.to_string(),
+      remote: matches!(specifier.scheme(), "http" | "https"),
+      emit: matches!(
+        media_type,
+        MediaType::TypeScript
+          | MediaType::Mts
+          | MediaType::Cts
+          | MediaType::Jsx
+          | MediaType::Tsx
+      ),
+    });
+  }
+  let npm_packages = graph
+    .npm_packages
+    .iter()
+    .map(|nv| nv.to_string())
+    .collect::<Vec<_>>();
+  let report = DryRunReport {
+    main_module: main_module.to_string(),
+    modules,
+    npm_packages,
+    permissions: cli_options.permission_flags(),
+  };
+
+  if json {
+    return display::write_json_to_stdout(&report);
+  }
+  let remote_count = report.modules.iter().filter(|m| m.remote).count();
+  let emit_count = report.modules.iter().filter(|m| m.emit).count();
+  println!("{} {}", colors::bold("Main module:"), report.main_module);
+  println!(
+    "{} {} ({} remote, {} to transpile)",
+    colors::bold("Modules:"),
+    report.modules.len(),
+    remote_count,
+    emit_count
+  );
+  for module in &report.modules {
+    println!("  {}", module.specifier);
+  }
+  println!(
+    "{} {}",
+    colors::bold("npm packages:"),
+    report.npm_packages.len()
+  );
+  for package in &report.npm_packages {
+    println!("  npm:{}", package);
+  }
+  Ok(())
+}
@@ -0,0 +1,82 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::anyhow;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_graph::GraphKind;
+use deno_graph::Module;
+use serde::Serialize;
+
+use crate::args::DumpAstFlags;
+use crate::colors;
+use crate::factory::CliFactory;
+use crate::util::display;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModuleAst {
+  specifier: String,
+  media_type: String,
+  ast: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct AstDump {
+  modules: Vec<ModuleAst>,
+}
+
+/// Prints the swc AST of `main_module`, or of every JavaScript and TypeScript
+/// module in its graph with `--dump-ast-all`, as parsed by the module loader.
+#[allow(clippy::print_stdout)]
+pub async fn dump_ast(
+  factory: &CliFactory,
+  main_module: &ModuleSpecifier,
+  flags: &DumpAstFlags,
+) -> Result<(), AnyError> {
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let graph = module_graph_creator
+    .create_graph(GraphKind::CodeOnly, vec![main_module.clone()])
+    .await?;
+  let parsed_source_cache = factory.parsed_source_cache();
+
+  let js_modules = if flags.all {
+    module_graph_creator.graph_valid(&graph)?;
+    graph.modules().filter_map(|module| module.js()).collect()
+  } else {
+    match graph.try_get(main_module)? {
+      Some(Module::Js(module)) => vec![module],
+      Some(_) => {
+        return Err(anyhow!(
+          "Cannot dump the AST of {}, it isn't a JavaScript or TypeScript module.",
+          main_module
+        ))
+      }
+      None => return Err(anyhow!("Module not found: {}", main_module)),
+    }
+  };
+
+  let mut modules = Vec::with_capacity(js_modules.len());
+  for module in js_modules {
+    let parsed_source =
+      parsed_source_cache.get_parsed_source_from_js_module(module)?;
+    modules.push(ModuleAst {
+      specifier: module.specifier.to_string(),
+      media_type: module.media_type.to_string(),
+      ast: serde_json::to_value(parsed_source.program_ref())?,
+    });
+  }
+
+  if flags.json {
+    return display::write_json_to_stdout(&AstDump { modules });
+  }
+  if let [module] = modules.as_slice() {
+    println!("{}", serde_json::to_string_pretty(&module.ast)?);
+    return Ok(());
+  }
+  for module in modules {
+    println!("{} {}", colors::bold("Module:"), module.specifier);
+    println!("{}", serde_json::to_string_pretty(&module.ast)?);
+  }
+  Ok(())
+}
@@ -0,0 +1,45 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::ast::Stmt;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParseParams;
+use deno_ast::SourceRangedForSpanned;
+
+/// Rewrites the code of `deno eval --print` so that it logs the completion
+/// value of its final expression statement, like the REPL does. Code that
+/// doesn't end with an expression logs `undefined`.
+pub fn print_completion_value(code: String, ext: Option<&str>) -> String {
+  let media_type = match ext {
+    Some("js") => MediaType::JavaScript,
+    Some("jsx") => MediaType::Jsx,
+    Some("tsx") => MediaType::Tsx,
+    _ => MediaType::TypeScript,
+  };
+  let Ok(parsed_source) = deno_ast::parse_module(ParseParams {
+    specifier: ModuleSpecifier::parse("file:///$deno$eval").unwrap(),
+    text: code.clone().into(),
+    media_type,
+    capture_tokens: false,
+    maybe_syntax: None,
+    scope_analysis: false,
+  }) else {
+    // leave reporting the syntax error to the runtime
+    return code;
+  };
+
+  let last_expr = match parsed_source.module().body.last() {
+    Some(ModuleItem::Stmt(Stmt::Expr(expr_stmt))) => expr_stmt.expr.range(),
+    _ => return format!("{}\nconsole.log(undefined);", code),
+  };
+  let text_start = parsed_source.text_info_lazy().range().start;
+  let start = last_expr.start.as_byte_index(text_start);
+  let end = last_expr.end.as_byte_index(text_start);
+  format!(
+    "{}console.log(({})){}",
+    &code[..start],
+    &code[start..end],
+    &code[end..]
+  )
+}
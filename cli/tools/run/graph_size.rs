@@ -0,0 +1,114 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashSet;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_graph::JsModule;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+
+use crate::factory::CliFactory;
+
+/// How many of the largest contributors to list when the graph is too big.
+const MAX_CONTRIBUTORS: usize = 5;
+
+/// Errors before the program runs when the module graph of `main_module` has
+/// more than `max` modules, for `--max-graph-modules`. The error lists the
+/// dependencies of the main module that pull in the most modules.
+pub async fn check_graph_size(
+  factory: &CliFactory,
+  main_module: &ModuleSpecifier,
+  max: usize,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options()?;
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let graph = module_graph_creator
+    .create_graph(cli_options.graph_kind(), vec![main_module.clone()])
+    .await?;
+  module_graph_creator.graph_valid(&graph)?;
+
+  let count = graph.modules().count();
+  if count <= max {
+    return Ok(());
+  }
+  let mut message = format!(
+    "The module graph has {} modules, more than the {} allowed by --max-graph-modules.",
+    count, max
+  );
+  let contributors = largest_contributors(&graph, main_module);
+  if !contributors.is_empty() {
+    message.push_str("\nLargest contributors:");
+    for (specifier, count) in contributors.iter().take(MAX_CONTRIBUTORS) {
+      message.push_str(&format!(
+        "\n  {} ({} module{})",
+        specifier,
+        count,
+        if *count == 1 { "" } else { "s" }
+      ));
+    }
+  }
+  bail!("{}", message)
+}
+
+/// The dependencies of `main_module` with the number of modules each one
+/// loads, largest first. Modules shared between dependencies count for
+/// each of them.
+fn largest_contributors(
+  graph: &ModuleGraph,
+  main_module: &ModuleSpecifier,
+) -> Vec<(ModuleSpecifier, usize)> {
+  let Some(Module::Js(module)) = graph.get(main_module) else {
+    return Vec::new();
+  };
+  let mut seen = HashSet::new();
+  let mut contributors = dependency_specifiers(graph, module)
+    .into_iter()
+    .filter(|specifier| seen.insert(specifier.clone()))
+    .map(|specifier| {
+      let count = module_count(graph, &specifier);
+      (specifier, count)
+    })
+    .collect::<Vec<_>>();
+  contributors.sort_by(|(a_specifier, a_count), (b_specifier, b_count)| {
+    b_count
+      .cmp(a_count)
+      .then_with(|| a_specifier.cmp(b_specifier))
+  });
+  contributors
+}
+
+/// The number of modules in the graph that loading `specifier` loads,
+/// including itself.
+fn module_count(graph: &ModuleGraph, specifier: &ModuleSpecifier) -> usize {
+  let mut seen = HashSet::new();
+  let mut pending = vec![specifier.clone()];
+  let mut count = 0;
+  while let Some(specifier) = pending.pop() {
+    if !seen.insert(specifier.clone()) {
+      continue;
+    }
+    let Some(module) = graph.get(&specifier) else {
+      continue;
+    };
+    count += 1;
+    if let Module::Js(module) = module {
+      pending.extend(dependency_specifiers(graph, module));
+    }
+  }
+  count
+}
+
+fn dependency_specifiers(
+  graph: &ModuleGraph,
+  module: &JsModule,
+) -> Vec<ModuleSpecifier> {
+  module
+    .dependencies
+    .values()
+    .flat_map(|dep| [dep.get_code(), dep.get_type()])
+    .flatten()
+    .map(|specifier| graph.resolve(specifier).clone())
+    .collect()
+}
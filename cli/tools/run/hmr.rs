@@ -2,6 +2,8 @@
 
 use crate::cdp;
 use crate::emit::Emitter;
+use crate::graph_container::MainModuleGraphContainer;
+use crate::graph_container::ModuleGraphContainer;
 use crate::util::file_watcher::WatcherCommunicator;
 use crate::util::file_watcher::WatcherRestartMode;
 use deno_core::error::generic_error;
@@ -11,8 +13,13 @@ use deno_core::serde_json::json;
 use deno_core::serde_json::{self};
 use deno_core::url::Url;
 use deno_core::LocalInspectorSession;
+use deno_core::ModuleSpecifier;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
 use deno_terminal::colors;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::select;
@@ -54,11 +61,20 @@ fn should_retry(status: &cdp::Status) -> bool {
 /// can refuse to perform hot replacement, eg. a top-level variable/function
 /// of an ES module cannot be hot-replaced. In such situation the runner will
 /// force a full restart of a program by notifying the `FileWatcher`.
+///
+/// When created with a module graph container (`--watch-reevaluate`), the
+/// runner doesn't patch function bodies. Instead it re-imports the changed
+/// module and all of its dependents (except the entry point) under a fresh
+/// specifier, so their top-level side effects run again in the same isolate.
+/// Modules that already imported the old version keep their old bindings,
+/// and any state held by the previous evaluation is not torn down.
 pub struct HmrRunner {
   session: LocalInspectorSession,
   watcher_communicator: Arc<WatcherCommunicator>,
   script_ids: HashMap<String, String>,
   emitter: Arc<Emitter>,
+  maybe_reevaluate_graph: Option<Arc<MainModuleGraphContainer>>,
+  reevaluate_generation: usize,
 }
 
 #[async_trait::async_trait(?Send)]
@@ -123,6 +139,11 @@ impl crate::worker::HmrRunner for HmrRunner {
             continue;
           }
 
+          if self.maybe_reevaluate_graph.is_some() {
+            self.reevaluate_changed_modules(filtered_paths).await?;
+            continue;
+          }
+
           for path in filtered_paths {
             let Some(path_str) = path.to_str() else {
               let _ = self.watcher_communicator.force_restart();
@@ -170,20 +191,162 @@ impl crate::worker::HmrRunner for HmrRunner {
   }
 }
 
+/// Appends `specifier` to `ordered` after those of its dependencies that
+/// are in `affected`.
+fn order_dependencies_first<'a>(
+  graph: &'a ModuleGraph,
+  specifier: &'a ModuleSpecifier,
+  affected: &HashSet<&'a ModuleSpecifier>,
+  visited: &mut HashSet<&'a ModuleSpecifier>,
+  ordered: &mut Vec<ModuleSpecifier>,
+) {
+  if !visited.insert(specifier) {
+    return;
+  }
+  if let Some(Module::Js(module)) = graph.get(specifier) {
+    for dep in module.dependencies.values() {
+      if let Some(code) = dep.get_code() {
+        let dependency = graph.resolve(code);
+        if let Some(dependency) = affected.get(dependency).copied() {
+          order_dependencies_first(
+            graph, dependency, affected, visited, ordered,
+          );
+        }
+      }
+    }
+  }
+  ordered.push(specifier.clone());
+}
+
 impl HmrRunner {
   pub fn new(
     emitter: Arc<Emitter>,
     session: LocalInspectorSession,
     watcher_communicator: Arc<WatcherCommunicator>,
+    maybe_reevaluate_graph: Option<Arc<MainModuleGraphContainer>>,
   ) -> Self {
     Self {
       session,
       emitter,
       watcher_communicator,
       script_ids: HashMap::new(),
+      maybe_reevaluate_graph,
+      reevaluate_generation: 0,
     }
   }
 
+  async fn reevaluate_changed_modules(
+    &mut self,
+    changed_paths: Vec<PathBuf>,
+  ) -> Result<(), AnyError> {
+    let graph = self.maybe_reevaluate_graph.as_ref().unwrap().graph();
+    let mut changed_specifiers = Vec::with_capacity(changed_paths.len());
+    for path in changed_paths {
+      let Ok(specifier) = ModuleSpecifier::from_file_path(&path) else {
+        let _ = self.watcher_communicator.force_restart();
+        return Ok(());
+      };
+      // a file that isn't part of the module graph can't be re-evaluated
+      if graph.get(&specifier).is_none() {
+        let _ = self.watcher_communicator.force_restart();
+        return Ok(());
+      }
+      changed_specifiers.push(graph.resolve(&specifier).clone());
+    }
+
+    let roots = graph
+      .roots
+      .iter()
+      .map(|root| graph.resolve(root))
+      .collect::<HashSet<_>>();
+    // re-evaluating the entry point is the same as restarting the program
+    if changed_specifiers.iter().any(|s| roots.contains(s)) {
+      let _ = self.watcher_communicator.force_restart();
+      return Ok(());
+    }
+
+    let mut dependents: HashMap<&ModuleSpecifier, Vec<&ModuleSpecifier>> =
+      HashMap::new();
+    for module in graph.modules() {
+      let Module::Js(module) = module else {
+        continue;
+      };
+      for dep in module.dependencies.values() {
+        if let Some(code) = dep.get_code() {
+          dependents
+            .entry(graph.resolve(code))
+            .or_default()
+            .push(&module.specifier);
+        }
+      }
+    }
+
+    // the changed modules and every module that depends on them
+    let mut affected = HashSet::new();
+    let mut pending = changed_specifiers.iter().collect::<VecDeque<_>>();
+    let mut found = Vec::new();
+    while let Some(specifier) = pending.pop_front() {
+      if roots.contains(specifier) || !affected.insert(specifier) {
+        continue;
+      }
+      found.push(specifier);
+      if let Some(specifiers) = dependents.get(specifier) {
+        pending.extend(specifiers.iter().copied());
+      }
+    }
+
+    // dependencies go before their dependents, so that the fresh instance of
+    // a module is already evaluated when a dependent imports it
+    let mut ordered = Vec::with_capacity(found.len());
+    let mut visited = HashSet::new();
+    for specifier in found {
+      order_dependencies_first(
+        &graph,
+        specifier,
+        &affected,
+        &mut visited,
+        &mut ordered,
+      );
+    }
+
+    self.reevaluate_generation += 1;
+    let query = format!("reevaluate={}", self.reevaluate_generation);
+    let fresh_specifiers = ordered
+      .iter()
+      .map(|specifier| {
+        let mut fresh_specifier = specifier.clone();
+        fresh_specifier.set_query(Some(&query));
+        fresh_specifier
+      })
+      .collect::<Vec<_>>();
+    // the imports of the fresh modules resolve to each other instead of to
+    // the original modules
+    self
+      .maybe_reevaluate_graph
+      .as_ref()
+      .unwrap()
+      .add_reevaluated_modules(fresh_specifiers.iter().cloned());
+
+    for (specifier, fresh_specifier) in ordered.iter().zip(&fresh_specifiers) {
+      let result = self.evaluate_import(fresh_specifier).await?;
+      if let Some(exception_details) = result.exception_details {
+        let (message, description) =
+          exception_details.get_message_and_description();
+        self.watcher_communicator.print(format!(
+          "Failed to re-evaluate module {}: {} {}",
+          specifier, message, description
+        ));
+        return Ok(());
+      }
+      self.dispatch_hmr_event(specifier.as_str()).await?;
+      self
+        .watcher_communicator
+        .print(format!("Re-evaluated module {}", specifier));
+    }
+
+    Ok(())
+  }
+
   // TODO(bartlomieju): this code is duplicated in `cli/tools/coverage/mod.rs`
   async fn enable_debugger(&mut self) -> Result<(), AnyError> {
     self
@@ -232,6 +395,25 @@ impl HmrRunner {
     )?)
   }
 
+  async fn evaluate_import(
+    &mut self,
+    specifier: &ModuleSpecifier,
+  ) -> Result<cdp::EvaluateResponse, AnyError> {
+    let result = self
+      .session
+      .post_message(
+        "Runtime.evaluate",
+        Some(json!({
+          "expression": format!("import({});", json!(specifier.as_str())),
+          "contextId": Some(1),
+          "awaitPromise": true,
+        })),
+      )
+      .await?;
+
+    Ok(serde_json::from_value::<cdp::EvaluateResponse>(result)?)
+  }
+
   async fn dispatch_hmr_event(
     &mut self,
     script_id: &str,
@@ -0,0 +1,54 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_graph::GraphKind;
+
+use crate::colors;
+use crate::factory::CliFactory;
+
+/// Resolves the npm packages of the program and logs the ones that ship
+/// `preinstall`, `install` or `postinstall` scripts, for
+/// `--detect-install-scripts`. Errors if there are any and `fail` is set.
+pub async fn report_install_scripts(
+  factory: &CliFactory,
+  main_module: &ModuleSpecifier,
+  fail: bool,
+) -> Result<(), AnyError> {
+  let Some(npm_resolver) = factory.npm_resolver().await?.as_managed() else {
+    // packages in a user managed node_modules folder were already installed
+    return Ok(());
+  };
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let graph = module_graph_creator
+    .create_graph(GraphKind::CodeOnly, vec![main_module.clone()])
+    .await?;
+  module_graph_creator.graph_valid(&graph)?;
+  npm_resolver.cache_packages().await?;
+
+  let packages = npm_resolver.packages_with_lifecycle_scripts()?;
+  if packages.is_empty() {
+    return Ok(());
+  }
+  let mut message = format!(
+    "{} The following npm packages have install scripts:",
+    colors::yellow("Warning")
+  );
+  for (package_nv, script_names) in &packages {
+    message.push_str(&format!(
+      "\n  npm:{} ({})",
+      package_nv,
+      script_names.join(", ")
+    ));
+  }
+  log::warn!("{}", message);
+  if fail {
+    bail!(
+      "Found {} npm package{} with install scripts. Review them before running with --allow-scripts.",
+      packages.len(),
+      if packages.len() == 1 { "" } else { "s" }
+    );
+  }
+  Ok(())
+}
@@ -1,33 +1,122 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::io::Read;
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
+use deno_core::anyhow::bail;
 use deno_core::error::AnyError;
+use deno_core::ModuleSpecifier;
+use deno_runtime::deno_io::Stdio;
 use deno_runtime::deno_permissions::Permissions;
 use deno_runtime::deno_permissions::PermissionsContainer;
 use deno_runtime::WorkerExecutionMode;
+use deno_semver::npm::NpmPackageReqReference;
 
+use crate::args::is_permission_arg;
+use crate::args::DenoSubcommand;
 use crate::args::EvalFlags;
 use crate::args::Flags;
+use crate::args::ReplFlags;
 use crate::args::WatchFlagsWithPaths;
 use crate::factory::CliFactory;
 use crate::file_fetcher::File;
+use crate::ops::nondeterminism::deno_nondeterminism;
+use crate::ops::nondeterminism::NondeterminismRecorder;
+use crate::tools::check::IncrementalCheckState;
 use crate::util;
 use crate::util::file_watcher::WatcherRestartMode;
+use crate::worker::CliMainWorkerFactory;
+use crate::worker::ModuleNamespace;
 
+use self::tee::RunTee;
+
+mod byonm_stale;
+mod config_schema;
+mod dry_run;
+mod dump_ast;
+mod eval_print;
+mod graph_size;
 pub mod hmr;
+mod install_scripts;
+mod print_config;
+mod tee;
+mod unused_exports;
+mod warm_npm;
+
+pub fn check_permission_before_script(flags: &Flags) -> Result<(), AnyError> {
+  if let Some(warning) = misplaced_permission_warning(flags) {
+    util::warning::warn(
+      crate::colors::yellow(warning),
+      flags.abort_on_warning.as_deref(),
+    )?;
+  }
+  Ok(())
+}
 
-pub fn check_permission_before_script(flags: &Flags) {
-  if !flags.has_permission() && flags.has_permission_in_argv() {
-    log::warn!(
-      "{}",
-      crate::colors::yellow(
-        r#"Permission flags have likely been incorrectly set after the script argument.
+fn misplaced_permission_warning(flags: &Flags) -> Option<String> {
+  if flags.has_permission() || !flags.has_permission_in_argv() {
+    return None;
+  }
+  let args = std::env::args_os()
+    .map(|arg| arg.to_string_lossy().into_owned())
+    .collect::<Vec<_>>();
+  let example = corrected_command(&args, &flags.argv)
+    .unwrap_or_else(|| "deno run --allow-read=. main.js".to_string());
+  Some(format!(
+    r#"Permission flags have likely been incorrectly set after the script argument.
 To grant permissions, set them before the script argument. For example:
-    deno run --allow-read=. main.js"#
-      )
-    );
+    {example}"#
+  ))
+}
+
+/// Rebuilds the command line in `args` with the permission flags found in
+/// the script arguments moved in front of the script.
+fn corrected_command(
+  args: &[String],
+  script_argv: &[String],
+) -> Option<String> {
+  let script_index = args.len().checked_sub(script_argv.len() + 1)?;
+  if script_index == 0 || args[script_index + 1..] != *script_argv {
+    return None;
+  }
+  let (permission_args, script_args): (Vec<_>, Vec<_>) =
+    script_argv.iter().partition(|arg| is_permission_arg(arg));
+  let command = std::iter::once("deno")
+    .chain(args[1..script_index].iter().map(|arg| arg.as_str()))
+    .chain(permission_args.into_iter().map(|arg| arg.as_str()))
+    .chain(std::iter::once(args[script_index].as_str()))
+    .chain(script_args.into_iter().map(|arg| arg.as_str()))
+    .map(|arg| {
+      if arg.contains(char::is_whitespace) {
+        format!("'{}'", arg)
+      } else {
+        arg.to_string()
+      }
+    })
+    .collect::<Vec<_>>();
+  Some(command.join(" "))
+}
+
+/// Applies `--tz` by setting the `TZ` environment variable, so the program
+/// also sees it in `Deno.env` and passes it on to subprocesses. Modifying the
+/// environment isn't thread safe, so this must be called before the runtime
+/// starts any threads.
+pub fn set_tz(flags: &Flags) {
+  if let Some(tz) = &flags.tz {
+    std::env::set_var("TZ", tz);
+  }
+}
+
+/// Applies `--locale`. This needs to happen before V8 reads the default
+/// locale for the first time.
+fn set_locale(flags: &Flags) {
+  if let Some(locale) = &flags.locale {
+    deno_core::v8::icu::set_default_locale(locale);
   }
 }
 
@@ -36,47 +125,289 @@ pub async fn run_script(
   flags: Arc<Flags>,
   watch: Option<WatchFlagsWithPaths>,
 ) -> Result<i32, AnyError> {
-  check_permission_before_script(&flags);
+  set_locale(&flags);
+  deno_runtime::deno_permissions::set_prompt_timeout(
+    flags.permissions_prompt_timeout.map(Duration::from_secs),
+  );
+  if watch.is_some()
+    && (flags.tee_stdout.is_some() || flags.tee_stderr.is_some())
+  {
+    // the files would only get the output of the first restart
+    bail!("--tee-stdout and --tee-stderr can't be used with --watch.");
+  }
+  let (tee, stdio) = RunTee::create(&flags)?;
+  if let Some(warning) = misplaced_permission_warning(&flags) {
+    tee.write_stderr_message(&warning);
+    util::warning::warn(
+      crate::colors::yellow(&warning),
+      flags.abort_on_warning.as_deref(),
+    )?;
+  }
 
   if let Some(watch_flags) = watch {
     return run_with_watch(mode, flags, watch_flags).await;
   }
 
+  let result = run_main_module(mode, flags, stdio).await;
+  tee.finish(&result)?;
+  result
+}
+
+async fn run_main_module(
+  mode: WorkerExecutionMode,
+  flags: Arc<Flags>,
+  stdio: Stdio,
+) -> Result<i32, AnyError> {
   // TODO(bartlomieju): actually I think it will also fail if there's an import
   // map specified and bare specifier is used on the command line
   let factory = CliFactory::from_flags(flags);
   let cli_options = factory.cli_options()?;
+  if cli_options.config_schema_validate() {
+    config_schema::validate_config_files(cli_options)?;
+  }
+  if let Some(trace_file) = cli_options.trace_file() {
+    util::trace::init(trace_file)?;
+  }
+  let trace_guard = util::trace::FlushGuard::default();
+  if let Some(print_config_flags) = cli_options.print_config() {
+    print_config::print_config(cli_options, print_config_flags.json)?;
+    if print_config_flags.only {
+      return Ok(0);
+    }
+  }
+  if let Some(manifest_path) = cli_options.warm_npm() {
+    warm_npm::warm_npm_cache(&factory, &manifest_path).await?;
+    if let DenoSubcommand::Run(run_flags) = cli_options.sub_command() {
+      if run_flags.script.is_empty() {
+        // there's nothing to run after filling the cache
+        return Ok(0);
+      }
+    }
+  }
   let deno_dir = factory.deno_dir()?;
   let http_client = factory.http_client_provider();
 
   // Run a background task that checks for available upgrades or output
   // if an earlier run of this background task found a new version of Deno.
   #[cfg(feature = "upgrade")]
-  super::upgrade::check_for_upgrades(
-    http_client.clone(),
-    deno_dir.upgrade_check_file_path(),
-  );
+  if !cli_options.skip_upgrade_check() {
+    super::upgrade::check_for_upgrades(
+      http_client.clone(),
+      deno_dir.upgrade_check_file_path(),
+    );
+  }
 
   let main_module = cli_options.resolve_main_module()?;
+  let preload_modules = cli_options.preload_modules()?;
+  let extra_script_modules = cli_options.extra_script_modules()?;
 
   maybe_npm_install(&factory).await?;
 
+  if let Some(dump_ast_flags) = cli_options.dump_ast() {
+    dump_ast::dump_ast(&factory, &main_module, dump_ast_flags).await?;
+    return Ok(0);
+  }
+  if let Some(dry_run_flags) = cli_options.dry_run() {
+    dry_run::dry_run(&factory, &main_module, dry_run_flags.json).await?;
+    return Ok(0);
+  }
+  if let Some(max) = cli_options.max_graph_modules() {
+    graph_size::check_graph_size(&factory, &main_module, max).await?;
+  }
+  if cli_options.report_unused_exports() {
+    unused_exports::report_unused_exports(&factory, &main_module).await?;
+  }
+  if let Some(fail) = cli_options.detect_install_scripts() {
+    install_scripts::report_install_scripts(&factory, &main_module, fail)
+      .await?;
+  }
+
   let permissions = PermissionsContainer::new(Permissions::from_options(
     &cli_options.permissions_options()?,
   )?);
+  let maybe_hrtime_report = cli_options
+    .report_hrtime_usage()
+    .then(|| permissions.clone());
   let worker_factory = factory.create_cli_main_worker_factory().await?;
-  let mut worker = worker_factory
-    .create_main_worker(mode, main_module, permissions)
-    .await?;
+  if cli_options.print_bin() {
+    print_bin(&worker_factory, &main_module).await?;
+    return Ok(0);
+  }
+  if cli_options.print_entrypoint() {
+    print_entrypoint(&worker_factory, &main_module).await?;
+    return Ok(0);
+  }
+  let maybe_recorder = match cli_options.replay_nondeterminism() {
+    Some(trace_path) => {
+      Some(NondeterminismRecorder::replay_from_file(&trace_path)?)
+    }
+    None => cli_options
+      .record_nondeterminism()
+      .map(|_| NondeterminismRecorder::record()),
+  };
+  let maybe_recording_writer = maybe_recorder
+    .as_ref()
+    .zip(cli_options.record_nondeterminism())
+    .map(|(recorder, path)| RecordingWriter {
+      recorder: recorder.clone(),
+      path,
+    });
+  let recording_guard = RecordingGuard(maybe_recording_writer.clone());
+  let main_graph_container = factory.main_module_graph_container().await?;
+  let mut exit_code = 0;
+  let modules = std::iter::once(main_module).chain(extra_script_modules);
+  for (index, module) in modules.enumerate() {
+    if index > 0 {
+      if exit_code != 0 {
+        break;
+      }
+      // Each `--then` script gets a fresh worker and module graph, so that
+      // no state leaks between them, while the factory and permissions are
+      // shared.
+      main_graph_container.reset();
+    }
+    let custom_extensions = maybe_recorder
+      .iter()
+      .map(|recorder| deno_nondeterminism::init_ops(recorder.clone()))
+      .collect();
+    let trace_span =
+      util::trace::span("worker", "create_main_worker", || module.to_string());
+    let mut worker = worker_factory
+      .create_custom_worker(
+        mode,
+        module,
+        permissions.clone(),
+        custom_extensions,
+        stdio.clone(),
+      )
+      .await?;
+    drop(trace_span);
+    if maybe_recorder.is_some() {
+      worker.install_nondeterminism_hooks()?;
+    }
+    if let Some(writer) = maybe_recording_writer.clone() {
+      worker.add_exit_hook(move || writer.write_or_log());
+    }
+    if cli_options.trace_file().is_some() {
+      worker.add_exit_hook(util::trace::flush_or_log);
+    }
 
-  let exit_code = worker.run().await?;
+    if cli_options.warmup() {
+      worker.warmup().await?;
+      return Ok(0);
+    }
+
+    worker.execute_preload_modules(&preload_modules).await?;
+    exit_code = worker.run().await?;
+  }
+  recording_guard.finish()?;
+  if let Some(vendor_dir) = cli_options.vendor_out() {
+    crate::tools::vendor::vendor_loaded_modules(&factory, &vendor_dir).await?;
+  }
+  if let Some(permissions) = maybe_hrtime_report {
+    report_hrtime_usage(&permissions);
+  }
+  trace_guard.finish()?;
   Ok(exit_code)
 }
 
+/// Writes the `--record-nondeterminism` recording.
+#[derive(Clone)]
+struct RecordingWriter {
+  recorder: NondeterminismRecorder,
+  path: PathBuf,
+}
+
+impl RecordingWriter {
+  fn write_or_log(&self) {
+    if let Err(err) = self.recorder.write_to_file(&self.path) {
+      log::error!("{:#}", err);
+    }
+  }
+}
+
+/// Writes the recording when dropped without [`RecordingGuard::finish`], so
+/// that it's saved when the script fails too. `Deno.exit()` skips this, so
+/// workers write it from an exit hook.
+struct RecordingGuard(Option<RecordingWriter>);
+
+impl RecordingGuard {
+  fn finish(mut self) -> Result<(), AnyError> {
+    match self.0.take() {
+      Some(writer) => writer.recorder.write_to_file(&writer.path),
+      None => Ok(()),
+    }
+  }
+}
+
+impl Drop for RecordingGuard {
+  fn drop(&mut self) {
+    if let Some(writer) = self.0.take() {
+      writer.write_or_log();
+    }
+  }
+}
+
+#[allow(clippy::print_stdout)]
+async fn print_bin(
+  worker_factory: &CliMainWorkerFactory,
+  main_module: &ModuleSpecifier,
+) -> Result<(), AnyError> {
+  let Ok(package_ref) = NpmPackageReqReference::from_specifier(main_module)
+  else {
+    bail!("--print-bin requires an npm: specifier, but got '{main_module}'.");
+  };
+  let resolution = worker_factory
+    .resolve_npm_binary_entrypoint(&package_ref)
+    .await?;
+  let path = resolution.into_url().to_file_path().unwrap();
+  println!("{}", path.display());
+  Ok(())
+}
+
+#[allow(clippy::print_stdout)]
+async fn print_entrypoint(
+  worker_factory: &CliMainWorkerFactory,
+  main_module: &ModuleSpecifier,
+) -> Result<(), AnyError> {
+  let entrypoint = match NpmPackageReqReference::from_specifier(main_module) {
+    Ok(package_ref) => worker_factory
+      .resolve_npm_binary_entrypoint(&package_ref)
+      .await?
+      .into_url(),
+    Err(_) => main_module.clone(),
+  };
+  println!("{}", entrypoint);
+  Ok(())
+}
+
+fn report_hrtime_usage(permissions: &PermissionsContainer) {
+  let usage = permissions.hrtime_usage();
+  log::info!(
+    "{} {}, read {} times at full precision and {} times rounded to 2ms",
+    crate::colors::bold("High resolution time:"),
+    permissions.hrtime_state(),
+    usage.precise_reads(),
+    usage.coarse_reads(),
+  );
+}
+
 pub async fn run_from_stdin(flags: Arc<Flags>) -> Result<i32, AnyError> {
+  run_from_reader(flags, std::io::stdin(), "./$deno$stdin.ts").await
+}
+
+/// Runs the program read from `reader` as the main module, which gets the
+/// specifier `specifier_name` resolved against the current directory, or
+/// with the scheme of `--synthetic-scheme`.
+pub async fn run_from_reader<R: Read>(
+  flags: Arc<Flags>,
+  mut reader: R,
+  specifier_name: &str,
+) -> Result<i32, AnyError> {
   let factory = CliFactory::from_flags(flags);
   let cli_options = factory.cli_options()?;
-  let main_module = cli_options.resolve_main_module()?;
+  let main_module =
+    cli_options.resolve_synthetic_main_module(specifier_name)?;
 
   maybe_npm_install(&factory).await?;
 
@@ -86,12 +417,16 @@ pub async fn run_from_stdin(flags: Arc<Flags>) -> Result<i32, AnyError> {
     &cli_options.permissions_options()?,
   )?);
   let mut source = Vec::new();
-  std::io::stdin().read_to_end(&mut source)?;
+  reader.read_to_end(&mut source)?;
+  // `--ext` overrides the media type of the specifier, which is TypeScript
+  let maybe_headers = cli_options.ext_content_type().map(|content_type| {
+    HashMap::from([("content-type".to_string(), content_type.to_string())])
+  });
   // Save a fake file into file fetcher cache
   // to allow module access by TS compiler
   file_fetcher.insert_memory_files(File {
     specifier: main_module.clone(),
-    maybe_headers: None,
+    maybe_headers,
     source: source.into(),
   });
 
@@ -102,62 +437,124 @@ pub async fn run_from_stdin(flags: Arc<Flags>) -> Result<i32, AnyError> {
   Ok(exit_code)
 }
 
-// TODO(bartlomieju): this function is not handling `exit_code` set by the runtime
-// code properly.
+/// Runs the main module for an embedder of the cli crate and returns its
+/// exports, whose functions can be called afterwards.
+#[allow(dead_code)]
+pub async fn run_for_namespace(
+  flags: Arc<Flags>,
+) -> Result<ModuleNamespace, AnyError> {
+  let factory = CliFactory::from_flags(flags);
+  let cli_options = factory.cli_options()?;
+  let main_module = cli_options.resolve_main_module()?;
+
+  maybe_npm_install(&factory).await?;
+
+  let worker_factory = factory.create_cli_main_worker_factory().await?;
+  let permissions = PermissionsContainer::new(Permissions::from_options(
+    &cli_options.permissions_options()?,
+  )?);
+  let worker = worker_factory
+    .create_main_worker(WorkerExecutionMode::Run, main_module, permissions)
+    .await?;
+  worker.run_for_namespace().await
+}
+
 async fn run_with_watch(
   mode: WorkerExecutionMode,
   flags: Arc<Flags>,
   watch_flags: WatchFlagsWithPaths,
 ) -> Result<i32, AnyError> {
+  // only re-check the modules affected by a change
+  let incremental_check_state = Arc::new(IncrementalCheckState::default());
+  let exit_code = Rc::new(Cell::new(0));
+  let exit_code_ = exit_code.clone();
   util::file_watcher::watch_recv(
     flags,
     util::file_watcher::PrintConfig::new_with_banner(
-      if watch_flags.hmr { "HMR" } else { "Watcher" },
+      if watch_flags.reevaluate {
+        "Reevaluate"
+      } else if watch_flags.hmr {
+        "HMR"
+      } else {
+        "Watcher"
+      },
       "Process",
       !watch_flags.no_clear_screen,
     ),
     WatcherRestartMode::Automatic,
     move |flags, watcher_communicator, _changed_paths| {
+      let incremental_check_state = incremental_check_state.clone();
+      let exit_code = exit_code_.clone();
       Ok(async move {
-        let factory = CliFactory::from_flags_for_watcher(
-          flags,
-          watcher_communicator.clone(),
-        );
-        let cli_options = factory.cli_options()?;
-        let main_module = cli_options.resolve_main_module()?;
-
-        maybe_npm_install(&factory).await?;
-
-        let _ = watcher_communicator.watch_paths(cli_options.watch_paths());
-
-        let permissions = PermissionsContainer::new(Permissions::from_options(
-          &cli_options.permissions_options()?,
-        )?);
-        let mut worker = factory
-          .create_cli_main_worker_factory()
-          .await?
-          .create_main_worker(mode, main_module, permissions)
-          .await?;
-
-        if watch_flags.hmr {
-          worker.run().await?;
-        } else {
-          worker.run_for_watcher().await?;
+        let result: Result<i32, AnyError> = async {
+          let mut factory = CliFactory::from_flags_for_watcher(
+            flags,
+            watcher_communicator.clone(),
+          );
+          factory.set_incremental_check_state(incremental_check_state);
+          let cli_options = factory.cli_options()?;
+          if cli_options.config_schema_validate() {
+            config_schema::validate_config_files(cli_options)?;
+          }
+          let main_module = cli_options.resolve_main_module()?;
+          let preload_modules = cli_options.preload_modules()?;
+
+          maybe_npm_install(&factory).await?;
+
+          let _ = watcher_communicator.watch_paths(cli_options.watch_paths());
+
+          let permissions = PermissionsContainer::new(
+            Permissions::from_options(&cli_options.permissions_options()?)?,
+          );
+          let mut worker = factory
+            .create_cli_main_worker_factory()
+            .await?
+            .create_main_worker(mode, main_module, permissions)
+            .await?;
+          worker.execute_preload_modules(&preload_modules).await?;
+
+          if watch_flags.hmr {
+            worker.run().await
+          } else {
+            worker.run_for_watcher().await
+          }
         }
+        .await;
 
-        Ok(())
+        exit_code.set(match &result {
+          Ok(code) => *code,
+          Err(_) => 1,
+        });
+        if watch_flags.exit_on_error && exit_code.get() != 0 {
+          watcher_communicator.stop();
+        }
+        result.map(|_| ())
       })
     },
   )
   .await?;
 
-  Ok(0)
+  Ok(exit_code.get())
 }
 
 pub async fn eval_command(
   flags: Arc<Flags>,
   eval_flags: EvalFlags,
 ) -> Result<i32, AnyError> {
+  if eval_flags.repl {
+    // Evaluate the code inside the REPL session rather than as a module, so
+    // that its top-level bindings stay in scope. If it throws, the error is
+    // printed and the REPL starts anyway.
+    let repl_flags = ReplFlags {
+      eval_files: None,
+      eval: Some(eval_flags.code),
+      is_default_command: false,
+    };
+    let mut flags = Arc::unwrap_or_clone(flags);
+    flags.subcommand = DenoSubcommand::Repl(repl_flags.clone());
+    return crate::tools::repl::run(Arc::new(flags), repl_flags).await;
+  }
+
   let factory = CliFactory::from_flags(flags);
   let cli_options = factory.cli_options()?;
   let file_fetcher = factory.file_fetcher()?;
@@ -167,7 +564,10 @@ pub async fn eval_command(
 
   // Create a dummy source file.
   let source_code = if eval_flags.print {
-    format!("console.log({})", eval_flags.code)
+    eval_print::print_completion_value(
+      eval_flags.code,
+      cli_options.ext_flag().as_deref(),
+    )
   } else {
     eval_flags.code
   };
@@ -192,12 +592,35 @@ pub async fn eval_command(
 }
 
 pub async fn maybe_npm_install(factory: &CliFactory) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options()?;
   // ensure an "npm install" is done if the user has explicitly
   // opted into using a managed node_modules directory
-  if factory.cli_options()?.node_modules_dir_enablement() == Some(true) {
+  if cli_options.node_modules_dir_enablement() == Some(true) {
     if let Some(npm_resolver) = factory.npm_resolver().await?.as_managed() {
+      if let Some(lockfile) = cli_options.maybe_lockfile() {
+        // with --frozen, report the package.json deps that aren't locked
+        // before resolving them
+        lockfile.error_if_npm_reqs_changed(
+          npm_resolver
+            .package_json_deps_provider()
+            .remote_pkgs()
+            .iter()
+            .map(|pkg| &pkg.req),
+        )?;
+      }
       npm_resolver.ensure_top_level_package_json_install().await?;
     }
   }
+  if cli_options.use_byonm() {
+    if let Some(on_stale) = cli_options.byonm_on_stale() {
+      byonm_stale::check_node_modules(cli_options, on_stale)?;
+    }
+  }
+  if cli_options.verify_npm_contents() {
+    if let Some(npm_resolver) = factory.npm_resolver().await?.as_managed() {
+      npm_resolver.cache_packages().await?;
+      npm_resolver.verify_package_contents()?;
+    }
+  }
   Ok(())
 }
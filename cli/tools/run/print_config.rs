@@ -0,0 +1,121 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_terminal::colors;
+use serde::Serialize;
+
+use crate::args::CliOptions;
+use crate::args::TsConfigType;
+use crate::display;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LockfileConfig {
+  path: String,
+  frozen: bool,
+}
+
+/// The configuration `deno run` ends up with after merging the config file,
+/// package.json and the flags.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolvedConfig {
+  config_file: Option<String>,
+  package_json: Option<String>,
+  import_map: Option<String>,
+  node_modules_dir: Option<bool>,
+  node_modules_path: Option<String>,
+  vendor_dir: Option<String>,
+  lockfile: Option<LockfileConfig>,
+  compiler_options: serde_json::Value,
+}
+
+impl ResolvedConfig {
+  fn resolve(cli_options: &CliOptions) -> Result<Self, AnyError> {
+    let maybe_deno_json = cli_options.start_dir.maybe_deno_json();
+    let import_map =
+      match cli_options.resolve_specified_import_map_specifier()? {
+        Some(specifier) => Some(specifier.to_string()),
+        // the config file doubles as the import map
+        None => maybe_deno_json
+          .filter(|c| c.is_an_import_map())
+          .map(|c| c.specifier.to_string()),
+      };
+    let ts_config =
+      cli_options.resolve_ts_config_for_emit(TsConfigType::Check {
+        lib: cli_options.ts_type_lib_window(),
+      })?;
+    Ok(Self {
+      config_file: maybe_deno_json.map(|c| c.specifier.to_string()),
+      package_json: cli_options
+        .start_dir
+        .maybe_pkg_json()
+        .map(|p| p.path.display().to_string()),
+      import_map,
+      node_modules_dir: cli_options.node_modules_dir_enablement(),
+      node_modules_path: cli_options
+        .node_modules_dir_path()
+        .map(|p| p.display().to_string()),
+      vendor_dir: cli_options
+        .vendor_dir_path()
+        .map(|p| p.display().to_string()),
+      lockfile: cli_options.maybe_lockfile().map(|lockfile| LockfileConfig {
+        path: lockfile.filename.display().to_string(),
+        frozen: lockfile.frozen,
+      }),
+      compiler_options: ts_config.ts_config.0,
+    })
+  }
+}
+
+#[allow(clippy::print_stdout)]
+pub fn print_config(
+  cli_options: &CliOptions,
+  json: bool,
+) -> Result<(), AnyError> {
+  let config = ResolvedConfig::resolve(cli_options)?;
+  if json {
+    return display::write_json_to_stdout(&config);
+  }
+
+  fn print_entry(name: &str, value: Option<&str>) {
+    match value {
+      Some(value) => println!("{} {}", colors::bold(name), value),
+      None => println!("{} {}", colors::bold(name), colors::gray("none")),
+    }
+  }
+
+  print_entry("Config file:", config.config_file.as_deref());
+  print_entry("package.json:", config.package_json.as_deref());
+  print_entry("Import map:", config.import_map.as_deref());
+  print_entry(
+    "node_modules dir:",
+    config
+      .node_modules_dir
+      .map(|enabled| enabled.to_string())
+      .as_deref(),
+  );
+  print_entry("node_modules path:", config.node_modules_path.as_deref());
+  print_entry("Vendor dir:", config.vendor_dir.as_deref());
+  print_entry(
+    "Lockfile:",
+    config
+      .lockfile
+      .as_ref()
+      .map(|lockfile| {
+        if lockfile.frozen {
+          format!("{} (frozen)", lockfile.path)
+        } else {
+          lockfile.path.clone()
+        }
+      })
+      .as_deref(),
+  );
+  println!(
+    "{} {}",
+    colors::bold("Compiler options:"),
+    serde_json::to_string_pretty(&config.compiler_options)?
+  );
+  Ok(())
+}
@@ -0,0 +1,141 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::error::JsError;
+use deno_core::parking_lot::Mutex;
+use deno_runtime::deno_io::Stdio;
+use deno_runtime::deno_io::StdioPipe;
+use deno_runtime::fmt_errors::format_js_error;
+
+use crate::args::Flags;
+
+#[derive(Clone, Copy)]
+enum TeeStream {
+  Stdout,
+  Stderr,
+}
+
+/// How long to wait for the output to be copied once the program is done.
+/// Subprocesses that outlive it keep the pipe open, so this doesn't wait for
+/// them to exit.
+const COPY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Copies everything written to a pipe to the terminal and to a file.
+struct Tee {
+  file: Arc<Mutex<File>>,
+  copy_done: mpsc::Receiver<()>,
+}
+
+impl Tee {
+  fn create(
+    path: &Path,
+    stream: TeeStream,
+  ) -> Result<(Self, StdioPipe), AnyError> {
+    let file = File::create(path)
+      .with_context(|| format!("Failed creating '{}'.", path.display()))?;
+    let file = Arc::new(Mutex::new(file));
+    let (mut reader, writer) = deno_runtime::deno_io::pipe()?;
+    let (copy_done_tx, copy_done) = mpsc::channel();
+    std::thread::spawn({
+      let file = file.clone();
+      move || {
+        let mut buf = [0; 16 * 1024];
+        loop {
+          let data = match reader.read(&mut buf) {
+            // every write end was closed
+            Ok(0) | Err(_) => break,
+            Ok(read) => &buf[..read],
+          };
+          // keep writing to the file even if the terminal went away
+          let _ = match stream {
+            TeeStream::Stdout => {
+              let mut stdout = std::io::stdout().lock();
+              stdout.write_all(data).and_then(|_| stdout.flush())
+            }
+            TeeStream::Stderr => std::io::stderr().write_all(data),
+          };
+          let _ = file.lock().write_all(data);
+        }
+        let _ = copy_done_tx.send(());
+      }
+    });
+    Ok((Self { file, copy_done }, StdioPipe::file(writer)))
+  }
+
+  /// Waits up to [`COPY_TIMEOUT`] for the output of the program to be
+  /// copied, then appends `trailer`. The write ends of the pipe held by the
+  /// program need to be closed first.
+  fn finish(self, trailer: Option<&str>) -> Result<(), AnyError> {
+    if self.copy_done.recv_timeout(COPY_TIMEOUT).is_err() {
+      log::debug!("Stopped waiting for the output copied to the --tee-* file");
+    }
+    let mut file = self.file.lock();
+    if let Some(trailer) = trailer {
+      writeln!(file, "{}", trailer)?;
+    }
+    file.flush()?;
+    Ok(())
+  }
+}
+
+/// The `--tee-stdout` and `--tee-stderr` files of `deno run`.
+#[derive(Default)]
+pub struct RunTee {
+  stdout: Option<Tee>,
+  stderr: Option<Tee>,
+}
+
+impl RunTee {
+  /// Creates the files and returns the stdio the main worker should use.
+  pub fn create(flags: &Flags) -> Result<(Self, Stdio), AnyError> {
+    let mut tee = RunTee::default();
+    let mut stdio = Stdio::default();
+    if let Some(path) = &flags.tee_stdout {
+      let (stdout, pipe) = Tee::create(Path::new(path), TeeStream::Stdout)?;
+      tee.stdout = Some(stdout);
+      stdio.stdout = pipe;
+    }
+    if let Some(path) = &flags.tee_stderr {
+      let (stderr, pipe) = Tee::create(Path::new(path), TeeStream::Stderr)?;
+      tee.stderr = Some(stderr);
+      stdio.stderr = pipe;
+    }
+    Ok((tee, stdio))
+  }
+
+  /// Adds a message Deno printed to stderr itself, like a warning, to the
+  /// stderr file.
+  pub fn write_stderr_message(&self, message: &str) {
+    if let Some(stderr) = &self.stderr {
+      let _ = writeln!(stderr.file.lock(), "{}", message);
+    }
+  }
+
+  /// Waits until all output was copied, adding the error the run failed with
+  /// to the stderr file. The main worker must have been dropped.
+  pub fn finish(self, result: &Result<i32, AnyError>) -> Result<(), AnyError> {
+    if let Some(stdout) = self.stdout {
+      stdout.finish(None)?;
+    }
+    if let Some(stderr) = self.stderr {
+      let error_message = result.as_ref().err().map(|err| {
+        let message = match err.downcast_ref::<JsError>() {
+          Some(js_error) => format_js_error(js_error),
+          None => format!("{err:?}"),
+        };
+        format!("error: {}", message.trim_start_matches("error: "))
+      });
+      stderr.finish(error_message.as_deref())?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,277 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use deno_ast::swc::ast::Decl;
+use deno_ast::swc::ast::ExportSpecifier;
+use deno_ast::swc::ast::ImportSpecifier;
+use deno_ast::swc::ast::ModuleDecl;
+use deno_ast::swc::ast::ModuleExportName;
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::ast::Pat;
+use deno_ast::swc::ast::TsModuleName;
+use deno_ast::swc::ast::TsModuleRef;
+use deno_ast::ModuleSpecifier;
+use deno_ast::SourcePos;
+use deno_ast::SourceRanged;
+use deno_core::error::AnyError;
+use deno_graph::GraphKind;
+
+use crate::colors;
+use crate::factory::CliFactory;
+
+/// The exports of a module that other modules import.
+enum UsedExports {
+  All,
+  Names(HashSet<String>),
+}
+
+impl UsedExports {
+  fn contains(&self, name: &str) -> bool {
+    match self {
+      UsedExports::All => true,
+      UsedExports::Names(names) => names.contains(name),
+    }
+  }
+}
+
+#[derive(Default)]
+struct ExportUsage(HashMap<ModuleSpecifier, UsedExports>);
+
+impl ExportUsage {
+  fn use_name(&mut self, specifier: &ModuleSpecifier, name: String) {
+    match self
+      .0
+      .entry(specifier.clone())
+      .or_insert_with(|| UsedExports::Names(HashSet::new()))
+    {
+      UsedExports::All => {}
+      UsedExports::Names(names) => {
+        names.insert(name);
+      }
+    }
+  }
+
+  fn use_all(&mut self, specifier: &ModuleSpecifier) {
+    self.0.insert(specifier.clone(), UsedExports::All);
+  }
+
+  fn is_used(&self, specifier: &ModuleSpecifier, name: &str) -> bool {
+    self
+      .0
+      .get(specifier)
+      .map(|used| used.contains(name))
+      .unwrap_or(false)
+  }
+}
+
+struct Export {
+  name: String,
+  start: SourcePos,
+}
+
+/// Logs the exports of the local modules in the graph of `main_module` that
+/// no module of the graph imports. Exports of the main module are its public
+/// API, so they are not reported.
+///
+/// This only looks at the import and export declarations, so modules that
+/// are imported dynamically, as a namespace or through `export *` count as
+/// having all of their exports used.
+pub async fn report_unused_exports(
+  factory: &CliFactory,
+  main_module: &ModuleSpecifier,
+) -> Result<(), AnyError> {
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let graph = module_graph_creator
+    .create_graph(GraphKind::All, vec![main_module.clone()])
+    .await?;
+  module_graph_creator.graph_valid(&graph)?;
+  let parsed_source_cache = factory.parsed_source_cache();
+
+  let mut usage = ExportUsage::default();
+  let mut modules = Vec::new();
+  for module in graph.modules() {
+    let Some(module) = module.js() else {
+      continue;
+    };
+    if module.specifier.scheme() != "file" {
+      continue;
+    }
+    let parsed_source =
+      parsed_source_cache.get_parsed_source_from_js_module(module)?;
+    let resolve = |src: &str| {
+      let dependency = module.dependencies.get(src)?;
+      let specifier =
+        dependency.get_code().or_else(|| dependency.get_type())?;
+      Some(graph.resolve(specifier))
+    };
+
+    for (src, dependency) in &module.dependencies {
+      if dependency.imports.iter().any(|import| import.is_dynamic) {
+        if let Some(specifier) = resolve(src) {
+          usage.use_all(&specifier);
+        }
+      }
+    }
+
+    let Some(program) = parsed_source.program_ref().as_module() else {
+      continue;
+    };
+    let mut exports = Vec::new();
+    for item in &program.body {
+      let ModuleItem::ModuleDecl(decl) = item else {
+        continue;
+      };
+      match decl {
+        ModuleDecl::Import(import) => {
+          let Some(target) = resolve(&import.src.value) else {
+            continue;
+          };
+          for specifier in &import.specifiers {
+            match specifier {
+              ImportSpecifier::Named(named) => {
+                let name = match &named.imported {
+                  Some(imported) => export_name(imported),
+                  None => named.local.sym.to_string(),
+                };
+                usage.use_name(&target, name);
+              }
+              ImportSpecifier::Default(_) => {
+                usage.use_name(&target, "default".to_string());
+              }
+              ImportSpecifier::Namespace(_) => usage.use_all(&target),
+            }
+          }
+        }
+        ModuleDecl::ExportAll(export_all) => {
+          if let Some(target) = resolve(&export_all.src.value) {
+            usage.use_all(&target);
+          }
+        }
+        ModuleDecl::ExportNamed(named) => {
+          let maybe_target =
+            named.src.as_ref().and_then(|src| resolve(&src.value));
+          for specifier in &named.specifiers {
+            match specifier {
+              ExportSpecifier::Named(specifier) => {
+                let exported =
+                  specifier.exported.as_ref().unwrap_or(&specifier.orig);
+                exports.push(Export {
+                  name: export_name(exported),
+                  start: exported.start(),
+                });
+                if let Some(target) = &maybe_target {
+                  usage.use_name(target, export_name(&specifier.orig));
+                }
+              }
+              ExportSpecifier::Namespace(specifier) => {
+                exports.push(Export {
+                  name: export_name(&specifier.name),
+                  start: specifier.name.start(),
+                });
+                if let Some(target) = &maybe_target {
+                  usage.use_all(target);
+                }
+              }
+              ExportSpecifier::Default(specifier) => {
+                exports.push(Export {
+                  name: specifier.exported.sym.to_string(),
+                  start: specifier.exported.start(),
+                });
+                if let Some(target) = &maybe_target {
+                  usage.use_name(target, "default".to_string());
+                }
+              }
+            }
+          }
+        }
+        ModuleDecl::ExportDecl(export_decl) => {
+          exports.extend(decl_exports(&export_decl.decl));
+        }
+        ModuleDecl::ExportDefaultDecl(export_default) => {
+          exports.push(Export {
+            name: "default".to_string(),
+            start: export_default.start(),
+          });
+        }
+        ModuleDecl::ExportDefaultExpr(export_default) => {
+          exports.push(Export {
+            name: "default".to_string(),
+            start: export_default.start(),
+          });
+        }
+        ModuleDecl::TsImportEquals(import_equals) => {
+          if let TsModuleRef::TsExternalModuleRef(module_ref) =
+            &import_equals.module_ref
+          {
+            if let Some(target) = resolve(&module_ref.expr.value) {
+              usage.use_all(&target);
+            }
+          }
+        }
+        ModuleDecl::TsExportAssignment(_)
+        | ModuleDecl::TsNamespaceExport(_) => {}
+      }
+    }
+    modules.push((module.specifier.clone(), parsed_source, exports));
+  }
+
+  for (specifier, parsed_source, exports) in modules {
+    if graph.roots.contains(&specifier) {
+      continue;
+    }
+    for export in exports {
+      if usage.is_used(&specifier, &export.name) {
+        continue;
+      }
+      let position = parsed_source
+        .text_info_lazy()
+        .line_and_column_display(export.start);
+      log::warn!(
+        "{} '{}' is never imported\n    at {}:{}:{}",
+        colors::yellow("Unused export"),
+        export.name,
+        specifier,
+        position.line_number,
+        position.column_number,
+      );
+    }
+  }
+  Ok(())
+}
+
+fn export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
+
+fn decl_exports(decl: &Decl) -> Vec<Export> {
+  let ident_export = |ident: &deno_ast::swc::ast::Ident| Export {
+    name: ident.sym.to_string(),
+    start: ident.start(),
+  };
+  match decl {
+    Decl::Class(decl) => vec![ident_export(&decl.ident)],
+    Decl::Fn(decl) => vec![ident_export(&decl.ident)],
+    Decl::Var(decl) => decl
+      .decls
+      .iter()
+      .filter_map(|declarator| match &declarator.name {
+        Pat::Ident(binding) => Some(ident_export(&binding.id)),
+        // destructured bindings are not tracked
+        _ => None,
+      })
+      .collect(),
+    Decl::TsInterface(decl) => vec![ident_export(&decl.id)],
+    Decl::TsTypeAlias(decl) => vec![ident_export(&decl.id)],
+    Decl::TsEnum(decl) => vec![ident_export(&decl.id)],
+    Decl::TsModule(decl) => match &decl.id {
+      TsModuleName::Ident(ident) => vec![ident_export(ident)],
+      TsModuleName::Str(_) => vec![],
+    },
+    Decl::Using(_) => vec![],
+  }
+}
@@ -0,0 +1,98 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_npm::registry::NpmPackageVersionDistInfo;
+use deno_semver::package::PackageNv;
+use deno_semver::Version;
+use serde::Deserialize;
+
+use crate::factory::CliFactory;
+
+/// A file listing the exact npm packages to put in the cache, for example:
+///
+/// ```json
+/// {
+///   "packages": [
+///     { "name": "chalk", "version": "5.0.1", "integrity": "sha512-..." }
+///   ]
+/// }
+/// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WarmNpmManifest {
+  packages: Vec<WarmNpmPackage>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WarmNpmPackage {
+  name: String,
+  version: String,
+  integrity: String,
+  /// Defaults to the tarball url the registry for this package uses.
+  tarball: Option<String>,
+}
+
+/// Downloads and extracts the packages listed in the manifest into the npm
+/// cache, along with their registry information so that a later run with
+/// `--cached-only` can resolve them. No module graph is built.
+pub async fn warm_npm_cache(
+  factory: &CliFactory,
+  manifest_path: &Path,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options()?;
+  let manifest_text =
+    std::fs::read_to_string(manifest_path).with_context(|| {
+      format!("Failed reading '{}'.", manifest_path.display())
+    })?;
+  let manifest: WarmNpmManifest = serde_json::from_str(&manifest_text)
+    .with_context(|| {
+      format!("Failed parsing '{}'.", manifest_path.display())
+    })?;
+  let Some(npm_resolver) = factory.npm_resolver().await?.as_managed() else {
+    bail!("--warm-npm cannot be used with a node_modules directory that is managed by another package manager.");
+  };
+
+  let mut packages = Vec::with_capacity(manifest.packages.len());
+  for package in manifest.packages {
+    let version =
+      Version::parse_standard(&package.version).with_context(|| {
+        format!("Invalid version for '{}' in --warm-npm.", package.name)
+      })?;
+    let tarball = match package.tarball {
+      Some(tarball) => tarball,
+      None => {
+        let registry_url = cli_options.npmrc().get_registry_url(&package.name);
+        let base_name = package
+          .name
+          .rsplit_once('/')
+          .map(|(_, name)| name)
+          .unwrap_or(&package.name);
+        registry_url
+          .join(&format!("{}/-/{}-{}.tgz", package.name, base_name, version))?
+          .to_string()
+      }
+    };
+    let dist: NpmPackageVersionDistInfo = serde_json::from_value(json!({
+      "tarball": tarball,
+      "shasum": "",
+      "integrity": package.integrity,
+    }))?;
+    npm_resolver.cache_package_info(&package.name).await?;
+    packages.push((
+      PackageNv {
+        name: package.name,
+        version,
+      },
+      dist,
+    ));
+  }
+
+  npm_resolver.cache_tarballs(&packages).await
+}
@@ -21,7 +21,7 @@ pub async fn serve(
   flags: Arc<Flags>,
   serve_flags: ServeFlags,
 ) -> Result<i32, AnyError> {
-  check_permission_before_script(&flags);
+  check_permission_before_script(&flags)?;
 
   if let Some(watch_flags) = serve_flags.watch {
     return serve_with_watch(flags, watch_flags, serve_flags.worker_count)
@@ -940,6 +940,7 @@ delete Object.prototype.__proto__;
    * @property {boolean} debug
    * @property {string[]} rootNames
    * @property {boolean} localOnly
+   * @property {string[] | null} checkNames
    */
 
   /**
@@ -994,7 +995,9 @@ delete Object.prototype.__proto__;
   /** The API that is called by Rust when executing a request.
    * @param {Request} request
    */
-  function exec({ config, debug: debugFlag, rootNames, localOnly }) {
+  function exec(
+    { config, debug: debugFlag, rootNames, localOnly, checkNames },
+  ) {
     setLogDebug(debugFlag, "TS");
     performanceStart();
 
@@ -1021,9 +1024,10 @@ delete Object.prototype.__proto__;
       configFileParsingDiagnostics,
     });
 
-    const checkFiles = localOnly
-      ? rootNames
-        .filter((n) => !n.startsWith("http"))
+    // only check the given roots, like the ones affected by a change
+    const checkFiles = localOnly || checkNames != null
+      ? (checkNames ?? rootNames)
+        .filter((n) => !localOnly || !n.startsWith("http"))
         .map((checkName) => {
           const sourceFile = program.getSourceFile(checkName);
           if (sourceFile == null) {
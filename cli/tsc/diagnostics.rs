@@ -280,6 +280,26 @@ impl Diagnostics {
     Diagnostics(diagnostics)
   }
 
+  /// Combines the diagnostics of several checks of the same program into one
+  /// set sorted by file name and position. Diagnostics that were reported by
+  /// more than one check, like the ones about the compiler options, are only
+  /// included once.
+  pub fn merge_sorted(all: Vec<Diagnostics>) -> Self {
+    let mut diagnostics = all.into_iter().flat_map(|d| d.0).collect::<Vec<_>>();
+    diagnostics.sort_by(|a, b| {
+      let position = |d: &Diagnostic| {
+        d.start.as_ref().map(|start| (start.line, start.character))
+      };
+      a.file_name
+        .cmp(&b.file_name)
+        .then_with(|| position(a).cmp(&position(b)))
+        .then_with(|| a.code.cmp(&b.code))
+        .then_with(|| a.message_text.cmp(&b.message_text))
+    });
+    diagnostics.dedup();
+    Self(diagnostics)
+  }
+
   /// Return a set of diagnostics where only the values where the predicate
   /// returns `true` are included.
   pub fn filter<P>(self, predicate: P) -> Self
@@ -294,6 +314,10 @@ impl Diagnostics {
     self.0.is_empty()
   }
 
+  pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+    self.0.iter()
+  }
+
   /// Modifies all the diagnostics to have their display positions
   /// modified to point at the original source.
   pub fn apply_fast_check_source_maps(&mut self, graph: &ModuleGraph) {
@@ -583,4 +607,42 @@ mod tests {
     let actual = diagnostics.to_string();
     assert_eq!(strip_ansi_codes(&actual), "TS2552 [ERROR]: Cannot find name \'foo_Bar\'. Did you mean \'foo_bar\'?\nfoo_Bar();\n~~~~~~~\n    at test.ts:8:1\n\n    \'foo_bar\' is declared here.\n    function foo_bar() {\n             ~~~~~~~\n        at test.ts:4:10");
   }
+
+  #[test]
+  fn test_diagnostics_merge_sorted() {
+    fn diagnostic(file_name: Option<&str>, line: u64, code: u64) -> Diagnostic {
+      serde_json::from_value(json!({
+        "start": file_name.map(|_| json!({ "line": line, "character": 0 })),
+        "end": file_name.map(|_| json!({ "line": line, "character": 1 })),
+        "fileName": file_name,
+        "messageText": "message",
+        "sourceLine": null,
+        "category": 1,
+        "code": code
+      }))
+      .unwrap()
+    }
+
+    let options = diagnostic(None, 0, 5023);
+    let merged = Diagnostics::merge_sorted(vec![
+      Diagnostics::new(vec![
+        options.clone(),
+        diagnostic(Some("file:///b.ts"), 3, 2322),
+        diagnostic(Some("file:///a.ts"), 5, 2322),
+      ]),
+      Diagnostics::new(vec![
+        diagnostic(Some("file:///a.ts"), 1, 2304),
+        options.clone(),
+      ]),
+    ]);
+    assert_eq!(
+      merged,
+      Diagnostics::new(vec![
+        options,
+        diagnostic(Some("file:///a.ts"), 1, 2304),
+        diagnostic(Some("file:///a.ts"), 5, 2322),
+        diagnostic(Some("file:///b.ts"), 3, 2322),
+      ])
+    );
+  }
 }
@@ -981,6 +981,13 @@ declare namespace Deno {
      * @default {false}
      */
     allowHost?: boolean;
+    /** Tune the client for many small requests by disabling Nagle's
+     * algorithm (`TCP_NODELAY`), sending HTTP/1 requests with vectored writes
+     * and keeping HTTP/2 connections alive for multiplexing.
+     *
+     * @default {false}
+     */
+    lowLatencyMode?: boolean;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
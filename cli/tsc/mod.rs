@@ -321,6 +321,9 @@ pub struct Request {
   /// program.
   pub root_names: Vec<(ModuleSpecifier, MediaType)>,
   pub check_mode: TypeCheckMode,
+  /// When set, only these root modules are checked and the other roots are
+  /// only loaded for their types.
+  pub check_only: Option<Vec<ModuleSpecifier>>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -909,6 +912,15 @@ pub fn exec(request: Request) -> Result<Response, AnyError> {
       }
     })
     .collect();
+  let check_names = request.check_only.as_ref().map(|check_only| {
+    request
+      .root_names
+      .iter()
+      .zip(&root_names)
+      .filter(|((specifier, _), _)| check_only.contains(specifier))
+      .map(|(_, name)| name.clone())
+      .collect::<Vec<_>>()
+  });
 
   deno_core::extension!(deno_cli_tsc,
     ops = [
@@ -944,6 +956,7 @@ pub fn exec(request: Request) -> Result<Response, AnyError> {
     "debug": request.debug,
     "rootNames": root_names,
     "localOnly": request.check_mode == TypeCheckMode::Local,
+    "checkNames": check_names,
   });
   let exec_source = format!("globalThis.exec({request_value})");
 
@@ -1085,6 +1098,7 @@ mod tests {
       maybe_tsbuildinfo: None,
       root_names: vec![(specifier.clone(), MediaType::TypeScript)],
       check_mode: TypeCheckMode::All,
+      check_only: None,
     };
     exec(request)
   }
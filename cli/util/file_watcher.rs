@@ -15,6 +15,7 @@ use log::info;
 use notify::event::Event as NotifyEvent;
 use notify::event::EventKind;
 use notify::Error as NotifyError;
+use notify::PollWatcher;
 use notify::RecommendedWatcher;
 use notify::RecursiveMode;
 use notify::Watcher;
@@ -23,6 +24,8 @@ use std::collections::HashSet;
 use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::select;
@@ -158,6 +161,9 @@ pub struct WatcherCommunicator {
 
   restart_mode: Mutex<WatcherRestartMode>,
 
+  /// Set when the watcher should stop once the current operation finishes.
+  stop_requested: AtomicBool,
+
   banner: String,
 }
 
@@ -183,6 +189,12 @@ impl WatcherCommunicator {
     rx.recv().await.map_err(AnyError::from)
   }
 
+  /// Makes `watch_recv` return once the current run of the operation
+  /// finishes, instead of waiting for a file change.
+  pub fn stop(&self) {
+    self.stop_requested.store(true, Ordering::Relaxed);
+  }
+
   pub fn change_restart_mode(&self, restart_mode: WatcherRestartMode) {
     *self.restart_mode.lock() = restart_mode;
   }
@@ -251,6 +263,7 @@ where
   F: Future<Output = Result<(), AnyError>>,
 {
   let exclude_set = flags.resolve_watch_exclude_set()?;
+  let poll_interval = flags.watch_poll_interval();
   let (paths_to_watch_tx, mut paths_to_watch_rx) =
     tokio::sync::mpsc::unbounded_channel();
   let (restart_tx, mut restart_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -270,6 +283,7 @@ where
     changed_paths_rx: changed_paths_rx.resubscribe(),
     restart_tx: restart_tx.clone(),
     restart_mode: Mutex::new(restart_mode),
+    stop_requested: AtomicBool::new(false),
     banner: colors::intense_blue(banner).to_string(),
   });
   info!("{} {} started.", colors::intense_blue(banner), job_name);
@@ -305,7 +319,7 @@ where
       tokio::task::yield_now().await;
     }
 
-    let mut watcher = new_watcher(watcher_sender.clone())?;
+    let mut watcher = new_watcher(watcher_sender.clone(), poll_interval)?;
     consume_paths_to_watch(&mut watcher, &mut paths_to_watch_rx, &exclude_set);
 
     let receiver_future = async {
@@ -336,6 +350,9 @@ where
       },
       success = operation_future => {
         consume_paths_to_watch(&mut watcher, &mut paths_to_watch_rx, &exclude_set);
+        if watcher_communicator.stop_requested.load(Ordering::Relaxed) {
+          return Ok(());
+        }
         // TODO(bartlomieju): print exit code here?
         info!(
           "{} {} {}. Restarting on file change...",
@@ -369,36 +386,45 @@ where
   }
 }
 
+/// Creates a watcher using the file system events of the platform, or one
+/// that compares the watched files at `poll_interval` when it's set.
 fn new_watcher(
   sender: Arc<mpsc::UnboundedSender<Vec<PathBuf>>>,
-) -> Result<RecommendedWatcher, AnyError> {
-  Ok(Watcher::new(
-    move |res: Result<NotifyEvent, NotifyError>| {
-      let Ok(event) = res else {
-        return;
-      };
-
-      if !matches!(
-        event.kind,
-        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
-      ) {
-        return;
-      }
+  poll_interval: Option<Duration>,
+) -> Result<Box<dyn Watcher>, AnyError> {
+  let event_handler = move |res: Result<NotifyEvent, NotifyError>| {
+    let Ok(event) = res else {
+      return;
+    };
 
-      let paths = event
-        .paths
-        .iter()
-        .filter_map(|path| canonicalize_path(path).ok())
-        .collect();
+    if !matches!(
+      event.kind,
+      EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+      return;
+    }
 
-      sender.send(paths).unwrap();
-    },
-    Default::default(),
-  )?)
+    let paths = event
+      .paths
+      .iter()
+      .filter_map(|path| canonicalize_path(path).ok())
+      .collect();
+
+    sender.send(paths).unwrap();
+  };
+  Ok(match poll_interval {
+    Some(poll_interval) => Box::new(PollWatcher::new(
+      event_handler,
+      notify::Config::default().with_poll_interval(poll_interval),
+    )?),
+    None => {
+      Box::new(RecommendedWatcher::new(event_handler, Default::default())?)
+    }
+  })
 }
 
 fn add_paths_to_watcher(
-  watcher: &mut RecommendedWatcher,
+  watcher: &mut dyn Watcher,
   paths: &[PathBuf],
   paths_to_exclude: &PathOrPatternSet,
 ) {
@@ -417,7 +443,7 @@ fn add_paths_to_watcher(
 }
 
 fn consume_paths_to_watch(
-  watcher: &mut RecommendedWatcher,
+  watcher: &mut dyn Watcher,
   receiver: &mut UnboundedReceiver<Vec<PathBuf>>,
   exclude_set: &PathOrPatternSet,
 ) {
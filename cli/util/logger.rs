@@ -3,22 +3,33 @@
 use std::io::Write;
 
 use super::draw_thread::DrawThread;
+use super::progress_bar::PROGRESS_LOG_TARGET;
 
-struct CliLogger(env_logger::Logger);
+struct CliLogger {
+  logger: env_logger::Logger,
+  /// Drops progress output (`--quiet=progress`).
+  hide_progress: bool,
+}
 
 impl CliLogger {
-  pub fn new(logger: env_logger::Logger) -> Self {
-    Self(logger)
+  pub fn new(logger: env_logger::Logger, hide_progress: bool) -> Self {
+    Self {
+      logger,
+      hide_progress,
+    }
   }
 
   pub fn filter(&self) -> log::LevelFilter {
-    self.0.filter()
+    self.logger.filter()
   }
 }
 
 impl log::Log for CliLogger {
   fn enabled(&self, metadata: &log::Metadata) -> bool {
-    self.0.enabled(metadata)
+    if self.hide_progress && metadata.target() == PROGRESS_LOG_TARGET {
+      return false;
+    }
+    self.logger.enabled(metadata)
   }
 
   fn log(&self, record: &log::Record) {
@@ -28,17 +39,17 @@ impl log::Log for CliLogger {
       // could potentially block other threads that access the draw
       // thread's state
       DrawThread::hide();
-      self.0.log(record);
+      self.logger.log(record);
       DrawThread::show();
     }
   }
 
   fn flush(&self) {
-    self.0.flush();
+    self.logger.flush();
   }
 }
 
-pub fn init(maybe_level: Option<log::Level>) {
+pub fn init(maybe_level: Option<log::Level>, hide_progress: bool) {
   let log_level = maybe_level.unwrap_or(log::Level::Info);
   let logger = env_logger::Builder::from_env(
     env_logger::Env::default()
@@ -88,7 +99,7 @@ pub fn init(maybe_level: Option<log::Level>) {
   })
   .build();
 
-  let cli_logger = CliLogger::new(logger);
+  let cli_logger = CliLogger::new(logger, hide_progress);
   let max_level = cli_logger.filter();
   let r = log::set_boxed_logger(Box::new(cli_logger));
   if r.is_ok() {
@@ -10,11 +10,14 @@ pub mod draw_thread;
 pub mod file_watcher;
 pub mod fs;
 pub mod logger;
+pub mod overlay_fs;
 pub mod path;
 pub mod progress_bar;
 pub mod result;
 pub mod sync;
 pub mod text_encoding;
+pub mod trace;
 pub mod unix;
 pub mod v8;
+pub mod warning;
 pub mod windows;
@@ -0,0 +1,1548 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::normalize_path;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::BufMutView;
+use deno_core::BufView;
+use deno_core::ResourceHandleFd;
+use deno_core::WriteOutcome;
+use deno_runtime::deno_fs::AccessCheckCb;
+use deno_runtime::deno_fs::FileSystem;
+use deno_runtime::deno_fs::FsDirEntry;
+use deno_runtime::deno_fs::FsFileType;
+use deno_runtime::deno_fs::OpenOptions;
+use deno_runtime::deno_fs::RealFs;
+use deno_runtime::deno_io::fs::File;
+use deno_runtime::deno_io::fs::FsError;
+use deno_runtime::deno_io::fs::FsResult;
+use deno_runtime::deno_io::fs::FsStat;
+
+const S_IFREG: u32 = 0o100000;
+const S_IFDIR: u32 = 0o040000;
+const S_IFLNK: u32 = 0o120000;
+const MAX_SYMLINKS: usize = 40;
+
+type Entries = HashMap<PathBuf, OverlayEntry>;
+
+/// An in-memory, copy-on-write layer over the real file system used by
+/// `--vfs-overlay`.
+///
+/// The overlay covers the directory containing the overlay file. Inside of
+/// it, reads consult the overlay before the disk and every change, including
+/// metadata changes, is made to a copy in the overlay so the disk is never
+/// modified. Paths outside of it are passed through to the real file system.
+#[derive(Debug)]
+pub struct OverlayFileSystem {
+  root: PathBuf,
+  entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OverlayMetadata {
+  mode: u32,
+  uid: u32,
+  gid: u32,
+  atime: Option<u64>,
+  mtime: Option<u64>,
+}
+
+impl OverlayMetadata {
+  fn new(mode: u32) -> Self {
+    let now = now_ms();
+    Self {
+      mode,
+      uid: 0,
+      gid: 0,
+      atime: now,
+      mtime: now,
+    }
+  }
+
+  fn from_stat(stat: &FsStat) -> Self {
+    Self {
+      mode: stat.mode,
+      uid: stat.uid,
+      gid: stat.gid,
+      atime: stat.atime,
+      mtime: stat.mtime,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+enum OverlayEntry {
+  File {
+    data: Vec<u8>,
+    metadata: OverlayMetadata,
+  },
+  Dir {
+    metadata: OverlayMetadata,
+    /// Directories created in the overlay hide whatever is on disk below
+    /// them, while copies of directories on disk don't.
+    opaque: bool,
+  },
+  Symlink {
+    target: PathBuf,
+    metadata: OverlayMetadata,
+  },
+  /// The path was removed in the overlay.
+  Removed,
+}
+
+impl OverlayEntry {
+  fn new_dir(mode: u32) -> Self {
+    Self::Dir {
+      metadata: OverlayMetadata::new(S_IFDIR | (mode & 0o7777)),
+      opaque: true,
+    }
+  }
+
+  fn metadata_mut(&mut self) -> FsResult<&mut OverlayMetadata> {
+    match self {
+      Self::File { metadata, .. }
+      | Self::Dir { metadata, .. }
+      | Self::Symlink { metadata, .. } => Ok(metadata),
+      Self::Removed => Err(not_found()),
+    }
+  }
+
+  fn stat(&self) -> FsResult<FsStat> {
+    let (size, metadata) = match self {
+      Self::File { data, metadata } => (data.len() as u64, metadata),
+      Self::Dir { metadata, .. } => (0, metadata),
+      Self::Symlink { target, metadata } => {
+        (target.as_os_str().len() as u64, metadata)
+      }
+      Self::Removed => return Err(not_found()),
+    };
+    Ok(FsStat {
+      is_file: matches!(self, Self::File { .. }),
+      is_directory: matches!(self, Self::Dir { .. }),
+      is_symlink: matches!(self, Self::Symlink { .. }),
+      size,
+      mtime: metadata.mtime,
+      atime: metadata.atime,
+      birthtime: None,
+      dev: 0,
+      ino: 0,
+      mode: metadata.mode,
+      nlink: 1,
+      uid: metadata.uid,
+      gid: metadata.gid,
+      rdev: 0,
+      blksize: 0,
+      blocks: 0,
+      is_block_device: false,
+      is_char_device: false,
+      is_fifo: false,
+      is_socket: false,
+    })
+  }
+}
+
+/// What the overlay knows about a path inside of it.
+enum Lookup<'a> {
+  Entry(&'a OverlayEntry),
+  /// The path was removed in the overlay or is below a path that was.
+  Missing,
+  /// The overlay doesn't change the path, so it comes from the disk.
+  Disk,
+}
+
+fn lookup<'a>(entries: &'a Entries, path: &Path) -> Lookup<'a> {
+  match entries.get(path) {
+    Some(OverlayEntry::Removed) => return Lookup::Missing,
+    Some(entry) => return Lookup::Entry(entry),
+    None => {}
+  }
+  for ancestor in path.ancestors().skip(1) {
+    match entries.get(ancestor) {
+      None | Some(OverlayEntry::Dir { opaque: false, .. }) => {}
+      Some(_) => return Lookup::Missing,
+    }
+  }
+  Lookup::Disk
+}
+
+fn lstat(entries: &Entries, path: &Path) -> FsResult<FsStat> {
+  match lookup(entries, path) {
+    Lookup::Entry(entry) => entry.stat(),
+    Lookup::Missing => Err(not_found()),
+    Lookup::Disk => RealFs.lstat_sync(path),
+  }
+}
+
+/// Resolves the symlinks created in the overlay along `path`.
+fn resolve_symlinks(entries: &Entries, path: &Path) -> FsResult<PathBuf> {
+  let mut path = path.to_path_buf();
+  let mut count = 0;
+  'outer: loop {
+    for ancestor in path.ancestors() {
+      if let Some(OverlayEntry::Symlink { target, .. }) = entries.get(ancestor)
+      {
+        count += 1;
+        if count > MAX_SYMLINKS {
+          return Err(other_error("Too many levels of symbolic links"));
+        }
+        let rest = path.strip_prefix(ancestor).unwrap();
+        let parent = ancestor.parent().unwrap_or(ancestor);
+        path = normalize_path(parent.join(target).join(rest));
+        continue 'outer;
+      }
+    }
+    return Ok(path);
+  }
+}
+
+/// Copies the entry for a path from the disk into the overlay, so that it can
+/// be changed there. With `follow`, a symlink on disk is copied as the entry
+/// it points to.
+fn copy_up<'a>(
+  entries: &'a mut Entries,
+  path: &Path,
+  follow: bool,
+) -> FsResult<&'a mut OverlayEntry> {
+  let is_on_disk = match lookup(entries, path) {
+    Lookup::Entry(_) => false,
+    Lookup::Missing => return Err(not_found()),
+    Lookup::Disk => true,
+  };
+  if is_on_disk {
+    let stat = if follow {
+      RealFs.stat_sync(path)?
+    } else {
+      RealFs.lstat_sync(path)?
+    };
+    let metadata = OverlayMetadata::from_stat(&stat);
+    let entry = if stat.is_symlink {
+      OverlayEntry::Symlink {
+        target: RealFs.read_link_sync(path)?,
+        metadata,
+      }
+    } else if stat.is_directory {
+      OverlayEntry::Dir {
+        metadata,
+        opaque: false,
+      }
+    } else {
+      OverlayEntry::File {
+        data: std::fs::read(path)?,
+        metadata,
+      }
+    };
+    entries.insert(path.to_path_buf(), entry);
+  }
+  Ok(entries.get_mut(path).unwrap())
+}
+
+fn ensure_parent_dir(entries: &Entries, path: &Path) -> FsResult<()> {
+  let Some(parent) = path.parent() else {
+    return Ok(());
+  };
+  let parent = resolve_symlinks(entries, parent)?;
+  let is_dir = match lookup(entries, &parent) {
+    Lookup::Entry(entry) => matches!(entry, OverlayEntry::Dir { .. }),
+    Lookup::Missing => false,
+    Lookup::Disk => RealFs.stat_sync(&parent).is_ok_and(|s| s.is_directory),
+  };
+  if is_dir {
+    Ok(())
+  } else {
+    Err(not_found())
+  }
+}
+
+fn read_dir(entries: &Entries, dir: &Path) -> FsResult<Vec<FsDirEntry>> {
+  let mut dir_entries = match lookup(entries, dir) {
+    Lookup::Entry(OverlayEntry::Dir { opaque: true, .. }) => Vec::new(),
+    Lookup::Entry(OverlayEntry::Dir { opaque: false, .. }) | Lookup::Disk => {
+      RealFs.read_dir_sync(dir)?
+    }
+    Lookup::Entry(_) => return Err(other_error("Not a directory")),
+    Lookup::Missing => return Err(not_found()),
+  };
+  // entries changed in the overlay are added back below
+  dir_entries.retain(|entry| !entries.contains_key(&dir.join(&entry.name)));
+  for (path, entry) in entries.iter() {
+    if path.parent() != Some(dir) {
+      continue;
+    }
+    let Some(name) = path.file_name() else {
+      continue;
+    };
+    if matches!(entry, OverlayEntry::Removed) {
+      continue;
+    }
+    dir_entries.push(FsDirEntry {
+      name: name.to_string_lossy().to_string(),
+      is_file: matches!(entry, OverlayEntry::File { .. }),
+      is_directory: matches!(entry, OverlayEntry::Dir { .. }),
+      is_symlink: matches!(entry, OverlayEntry::Symlink { .. }),
+    });
+  }
+  Ok(dir_entries)
+}
+
+/// Where an operation on a path is carried out.
+enum Target {
+  Overlay(PathBuf),
+  Real(PathBuf),
+}
+
+impl OverlayFileSystem {
+  /// Loads an overlay file, which is a JSON object mapping paths, relative
+  /// to the overlay file, to the text content of the virtual file.
+  pub fn load(overlay_path: &Path) -> Result<Self, AnyError> {
+    let text = std::fs::read_to_string(overlay_path).with_context(|| {
+      format!("Failed reading VFS overlay '{}'", overlay_path.display())
+    })?;
+    let files: BTreeMap<String, String> = serde_json::from_str(&text)
+      .with_context(|| {
+        format!(
+          "Failed parsing VFS overlay '{}', expected an object mapping paths to file contents",
+          overlay_path.display()
+        )
+      })?;
+    let root = normalize_path(overlay_path.parent().unwrap_or(overlay_path));
+    let mut entries = Entries::new();
+    for (path, text) in files {
+      let path = normalize_path(root.join(&path));
+      if !path.starts_with(&root) || path == root {
+        bail!(
+          "VFS overlay path '{}' must be inside '{}'",
+          path.display(),
+          root.display()
+        );
+      }
+      for ancestor in path.ancestors().skip(1) {
+        if ancestor == root.as_path() || ancestor.exists() {
+          break;
+        }
+        entries.insert(ancestor.to_path_buf(), OverlayEntry::new_dir(0o755));
+      }
+      entries.insert(
+        path,
+        OverlayEntry::File {
+          data: text.into_bytes(),
+          metadata: OverlayMetadata::new(S_IFREG | 0o644),
+        },
+      );
+    }
+    Ok(Self {
+      root,
+      entries: Arc::new(Mutex::new(entries)),
+    })
+  }
+
+  /// The paths and contents of the files currently in the overlay.
+  pub fn files(&self) -> Vec<(PathBuf, Vec<u8>)> {
+    self
+      .entries
+      .lock()
+      .iter()
+      .filter_map(|(path, entry)| match entry {
+        OverlayEntry::File { data, .. } => Some((path.clone(), data.clone())),
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn resolve(path: &Path) -> FsResult<PathBuf> {
+    if path.is_absolute() {
+      Ok(normalize_path(path))
+    } else {
+      Ok(normalize_path(RealFs.cwd()?.join(path)))
+    }
+  }
+
+  /// Resolves a path, following the symlinks created in the overlay, and
+  /// decides whether the overlay handles it.
+  fn target(&self, path: &Path, follow_last: bool) -> FsResult<Target> {
+    let path_abs = Self::resolve(path)?;
+    if !path_abs.starts_with(&self.root) {
+      return Ok(Target::Real(path.to_path_buf()));
+    }
+    let entries = self.entries.lock();
+    let resolved = match (follow_last, path_abs.parent(), path_abs.file_name())
+    {
+      (false, Some(parent), Some(name)) => {
+        resolve_symlinks(&entries, parent)?.join(name)
+      }
+      _ => resolve_symlinks(&entries, &path_abs)?,
+    };
+    if resolved.starts_with(&self.root) {
+      Ok(Target::Overlay(resolved))
+    } else {
+      Ok(Target::Real(resolved))
+    }
+  }
+
+  fn stat(&self, path: &Path, follow_last: bool) -> FsResult<FsStat> {
+    match self.target(path, follow_last)? {
+      Target::Overlay(path) => {
+        let entries = self.entries.lock();
+        match lookup(&entries, &path) {
+          Lookup::Entry(entry) => entry.stat(),
+          Lookup::Missing => Err(not_found()),
+          Lookup::Disk if follow_last => RealFs.stat_sync(&path),
+          Lookup::Disk => RealFs.lstat_sync(&path),
+        }
+      }
+      Target::Real(path) if follow_last => RealFs.stat_sync(&path),
+      Target::Real(path) => RealFs.lstat_sync(&path),
+    }
+  }
+
+  fn open(
+    &self,
+    path: &Path,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb>,
+  ) -> FsResult<Rc<dyn File>> {
+    let path = match self.target(path, true)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => {
+        return RealFs.open_sync(&path, options, access_check)
+      }
+    };
+    let mut entries = self.entries.lock();
+    let is_disk_read = !is_write(&options)
+      && matches!(
+        lookup(&entries, &path),
+        Lookup::Disk | Lookup::Entry(OverlayEntry::Dir { .. })
+      );
+    if is_disk_read {
+      // nothing changes, so there is nothing to copy
+      drop(entries);
+      return RealFs.open_sync(&path, options, access_check);
+    }
+    let exists = match lookup(&entries, &path) {
+      Lookup::Entry(OverlayEntry::File { .. }) => true,
+      Lookup::Entry(_) => return Err(other_error("Is a directory")),
+      Lookup::Missing => false,
+      Lookup::Disk => RealFs.stat_sync(&path).is_ok(),
+    };
+    if let Some(access_check) = access_check {
+      (*access_check)(false, &path, &options)?;
+    }
+    if options.create_new && exists {
+      return Err(FsError::Io(Error::from(ErrorKind::AlreadyExists)));
+    }
+    if exists {
+      let entry = copy_up(&mut entries, &path, true)?;
+      if let OverlayEntry::File { data, metadata } = entry {
+        if options.truncate && !options.append {
+          data.clear();
+          metadata.mtime = now_ms();
+        }
+      } else {
+        return Err(other_error("Is a directory"));
+      }
+    } else if options.create || options.create_new {
+      ensure_parent_dir(&entries, &path)?;
+      let mode = options.mode.unwrap_or(0o666) & 0o7777;
+      entries.insert(
+        path.clone(),
+        OverlayEntry::File {
+          data: Vec::new(),
+          metadata: OverlayMetadata::new(S_IFREG | mode),
+        },
+      );
+    } else {
+      return Err(not_found());
+    }
+    Ok(Rc::new(OverlayFile {
+      entries: self.entries.clone(),
+      path,
+      pos: Cell::new(0),
+      append: options.append,
+      writable: options.write || options.append,
+    }))
+  }
+
+  fn mkdir(&self, path: &Path, recursive: bool, mode: u32) -> FsResult<()> {
+    let path = match self.target(path, true)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => return RealFs.mkdir_sync(&path, recursive, mode),
+    };
+    let mut entries = self.entries.lock();
+    let mut dirs = vec![path.as_path()];
+    if recursive {
+      dirs.extend(
+        path
+          .ancestors()
+          .skip(1)
+          .take_while(|dir| *dir != self.root.as_path()),
+      );
+    }
+    for dir in dirs.into_iter().rev() {
+      match lstat(&entries, dir) {
+        Ok(stat) if recursive && stat.is_directory => continue,
+        Ok(_) => {
+          return Err(FsError::Io(Error::from(ErrorKind::AlreadyExists)))
+        }
+        Err(_) => {}
+      }
+      ensure_parent_dir(&entries, dir)?;
+      entries.insert(dir.to_path_buf(), OverlayEntry::new_dir(mode));
+    }
+    Ok(())
+  }
+
+  fn update_metadata(
+    &self,
+    path: &Path,
+    follow_last: bool,
+    update: impl FnOnce(&mut OverlayMetadata),
+    real: impl FnOnce(&Path) -> FsResult<()>,
+  ) -> FsResult<()> {
+    match self.target(path, follow_last)? {
+      Target::Overlay(path) => {
+        let mut entries = self.entries.lock();
+        update(copy_up(&mut entries, &path, follow_last)?.metadata_mut()?);
+        Ok(())
+      }
+      Target::Real(path) => real(&path),
+    }
+  }
+
+  fn chmod(&self, path: &Path, mode: u32) -> FsResult<()> {
+    self.update_metadata(
+      path,
+      true,
+      |metadata| metadata.mode = (metadata.mode & !0o7777) | (mode & 0o7777),
+      |path| RealFs.chmod_sync(path, mode),
+    )
+  }
+
+  fn chown(
+    &self,
+    path: &Path,
+    follow_last: bool,
+    uid: Option<u32>,
+    gid: Option<u32>,
+  ) -> FsResult<()> {
+    self.update_metadata(
+      path,
+      follow_last,
+      |metadata| {
+        metadata.uid = uid.unwrap_or(metadata.uid);
+        metadata.gid = gid.unwrap_or(metadata.gid);
+      },
+      |path| {
+        if follow_last {
+          RealFs.chown_sync(path, uid, gid)
+        } else {
+          RealFs.lchown_sync(path, uid, gid)
+        }
+      },
+    )
+  }
+
+  fn utime(
+    &self,
+    path: &Path,
+    follow_last: bool,
+    atime: (i64, u32),
+    mtime: (i64, u32),
+  ) -> FsResult<()> {
+    self.update_metadata(
+      path,
+      follow_last,
+      |metadata| {
+        metadata.atime = Some(to_ms(atime));
+        metadata.mtime = Some(to_ms(mtime));
+      },
+      |path| {
+        if follow_last {
+          RealFs.utime_sync(path, atime.0, atime.1, mtime.0, mtime.1)
+        } else {
+          RealFs.lutime_sync(path, atime.0, atime.1, mtime.0, mtime.1)
+        }
+      },
+    )
+  }
+
+  fn remove(&self, path: &Path, recursive: bool) -> FsResult<()> {
+    let path = match self.target(path, false)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => return RealFs.remove_sync(&path, recursive),
+    };
+    let mut entries = self.entries.lock();
+    let stat = lstat(&entries, &path)?;
+    if stat.is_directory && !recursive && !read_dir(&entries, &path)?.is_empty()
+    {
+      return Err(other_error("Directory not empty"));
+    }
+    entries.retain(|entry, _| !entry.starts_with(&path));
+    entries.insert(path, OverlayEntry::Removed);
+    Ok(())
+  }
+
+  fn truncate(&self, path: &Path, len: u64) -> FsResult<()> {
+    let path = match self.target(path, true)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => return RealFs.truncate_sync(&path, len),
+    };
+    let mut entries = self.entries.lock();
+    match copy_up(&mut entries, &path, true)? {
+      OverlayEntry::File { data, metadata } => {
+        data.resize(len as usize, 0);
+        metadata.mtime = now_ms();
+        Ok(())
+      }
+      _ => Err(other_error("Is a directory")),
+    }
+  }
+
+  fn copy_file(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    let data = self.read_file_sync(oldpath, None)?;
+    self.write_file_sync(
+      newpath,
+      OpenOptions::write(true, false, false, None),
+      None,
+      &data,
+    )
+  }
+
+  fn cp(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    let stat = self.stat(oldpath, false)?;
+    if stat.is_symlink {
+      self.symlink(&self.read_link(oldpath)?, newpath)
+    } else if stat.is_directory {
+      self.mkdir(newpath, true, stat.mode & 0o7777)?;
+      for entry in self.read_dir_sync(oldpath)? {
+        self.cp(&oldpath.join(&entry.name), &newpath.join(&entry.name))?;
+      }
+      Ok(())
+    } else {
+      self.copy_file(oldpath, newpath)
+    }
+  }
+
+  fn rename(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    match (self.target(oldpath, false)?, self.target(newpath, false)?) {
+      (Target::Real(oldpath), Target::Real(newpath)) => {
+        RealFs.rename_sync(&oldpath, &newpath)
+      }
+      (Target::Overlay(old), Target::Overlay(new)) if old == new => Ok(()),
+      _ => {
+        self.cp(oldpath, newpath)?;
+        self.remove(oldpath, true)
+      }
+    }
+  }
+
+  fn link(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    match (self.target(oldpath, true)?, self.target(newpath, false)?) {
+      (Target::Real(oldpath), Target::Real(newpath)) => {
+        RealFs.link_sync(&oldpath, &newpath)
+      }
+      // files in memory can't share their contents, so the link is a copy
+      _ => {
+        if self.exists_sync(newpath) {
+          return Err(FsError::Io(Error::from(ErrorKind::AlreadyExists)));
+        }
+        self.copy_file(oldpath, newpath)
+      }
+    }
+  }
+
+  fn symlink(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    let newpath = match self.target(newpath, false)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => return RealFs.symlink_sync(oldpath, &path, None),
+    };
+    let mut entries = self.entries.lock();
+    if lstat(&entries, &newpath).is_ok() {
+      return Err(FsError::Io(Error::from(ErrorKind::AlreadyExists)));
+    }
+    ensure_parent_dir(&entries, &newpath)?;
+    entries.insert(
+      newpath,
+      OverlayEntry::Symlink {
+        target: oldpath.to_path_buf(),
+        metadata: OverlayMetadata::new(S_IFLNK | 0o777),
+      },
+    );
+    Ok(())
+  }
+
+  fn read_link(&self, path: &Path) -> FsResult<PathBuf> {
+    let path = match self.target(path, false)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => return RealFs.read_link_sync(&path),
+    };
+    let entries = self.entries.lock();
+    match lookup(&entries, &path) {
+      Lookup::Entry(OverlayEntry::Symlink { target, .. }) => Ok(target.clone()),
+      Lookup::Entry(_) => Err(FsError::Io(Error::new(
+        ErrorKind::InvalidInput,
+        "Not a symbolic link",
+      ))),
+      Lookup::Missing => Err(not_found()),
+      Lookup::Disk => RealFs.read_link_sync(&path),
+    }
+  }
+
+  fn realpath(&self, path: &Path) -> FsResult<PathBuf> {
+    let path = match self.target(path, true)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => return RealFs.realpath_sync(&path),
+    };
+    let entries = self.entries.lock();
+    match lookup(&entries, &path) {
+      Lookup::Entry(_) => Ok(path),
+      Lookup::Missing => Err(not_found()),
+      Lookup::Disk => RealFs.realpath_sync(&path),
+    }
+  }
+
+  fn read_dir(&self, path: &Path) -> FsResult<Vec<FsDirEntry>> {
+    match self.target(path, true)? {
+      Target::Overlay(path) => read_dir(&self.entries.lock(), &path),
+      Target::Real(path) => RealFs.read_dir_sync(&path),
+    }
+  }
+
+  /// Returns the resolved path and contents of an overlay file, or the path
+  /// to read from disk instead.
+  fn read_file(
+    &self,
+    path: &Path,
+  ) -> FsResult<Result<(PathBuf, Vec<u8>), PathBuf>> {
+    let path = match self.target(path, true)? {
+      Target::Overlay(path) => path,
+      Target::Real(path) => return Ok(Err(path)),
+    };
+    let entries = self.entries.lock();
+    match lookup(&entries, &path) {
+      Lookup::Entry(OverlayEntry::File { data, .. }) => {
+        Ok(Ok((path.clone(), data.clone())))
+      }
+      Lookup::Entry(_) => Err(other_error("Is a directory")),
+      Lookup::Missing => Err(not_found()),
+      Lookup::Disk => Ok(Err(path)),
+    }
+  }
+
+  fn write_file(
+    &self,
+    path: &Path,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb>,
+    data: &[u8],
+  ) -> FsResult<()> {
+    let file = self.open(path, options, access_check)?;
+    if let Some(mode) = options.mode {
+      file.clone().chmod_sync(mode)?;
+    }
+    file.write_all_sync(data)
+  }
+
+  fn is_in_overlay(&self, path: &Path) -> FsResult<bool> {
+    Ok(Self::resolve(path)?.starts_with(&self.root))
+  }
+}
+
+/// A handle to a file in the overlay. Reads and writes go straight to the
+/// overlay, so they are visible to every other handle of the file.
+struct OverlayFile {
+  entries: Arc<Mutex<Entries>>,
+  path: PathBuf,
+  pos: Cell<u64>,
+  append: bool,
+  writable: bool,
+}
+
+impl OverlayFile {
+  fn with_file<R>(
+    &self,
+    f: impl FnOnce(&mut Vec<u8>, &mut OverlayMetadata) -> FsResult<R>,
+  ) -> FsResult<R> {
+    match self.entries.lock().get_mut(&self.path) {
+      Some(OverlayEntry::File { data, metadata }) => f(data, metadata),
+      _ => Err(not_found()),
+    }
+  }
+
+  fn read_to_buf(&self, buf: &mut [u8]) -> FsResult<usize> {
+    self.with_file(|data, _| {
+      let pos = (self.pos.get() as usize).min(data.len());
+      let nread = buf.len().min(data.len() - pos);
+      buf[..nread].copy_from_slice(&data[pos..pos + nread]);
+      self.pos.set((pos + nread) as u64);
+      Ok(nread)
+    })
+  }
+
+  fn read_to_end(&self) -> FsResult<Vec<u8>> {
+    self.with_file(|data, _| {
+      let pos = (self.pos.get() as usize).min(data.len());
+      self.pos.set(data.len() as u64);
+      Ok(data[pos..].to_vec())
+    })
+  }
+
+  fn write_from_buf(&self, buf: &[u8]) -> FsResult<usize> {
+    if !self.writable {
+      return Err(FsError::Io(Error::new(
+        ErrorKind::PermissionDenied,
+        "File was not opened for writing",
+      )));
+    }
+    self.with_file(|data, metadata| {
+      let pos = if self.append {
+        data.len()
+      } else {
+        self.pos.get() as usize
+      };
+      let end = pos + buf.len();
+      if data.len() < end {
+        data.resize(end, 0);
+      }
+      data[pos..end].copy_from_slice(buf);
+      metadata.mtime = now_ms();
+      self.pos.set(end as u64);
+      Ok(buf.len())
+    })
+  }
+
+  fn seek(&self, pos: SeekFrom) -> FsResult<u64> {
+    let new_pos = match pos {
+      SeekFrom::Start(offset) => Some(offset),
+      SeekFrom::End(offset) => {
+        let len = self.with_file(|data, _| Ok(data.len() as u64))?;
+        len.checked_add_signed(offset)
+      }
+      SeekFrom::Current(offset) => self.pos.get().checked_add_signed(offset),
+    };
+    let new_pos = new_pos.ok_or_else(|| {
+      FsError::Io(Error::new(
+        ErrorKind::InvalidInput,
+        "An attempt was made to move the file pointer before the beginning of the file.",
+      ))
+    })?;
+    self.pos.set(new_pos);
+    Ok(new_pos)
+  }
+
+  fn truncate(&self, len: u64) -> FsResult<()> {
+    self.with_file(|data, metadata| {
+      data.resize(len as usize, 0);
+      metadata.mtime = now_ms();
+      Ok(())
+    })
+  }
+
+  fn update_metadata(
+    &self,
+    update: impl FnOnce(&mut OverlayMetadata),
+  ) -> FsResult<()> {
+    self.with_file(|_, metadata| {
+      update(metadata);
+      Ok(())
+    })
+  }
+
+  fn stat(&self) -> FsResult<FsStat> {
+    match self.entries.lock().get(&self.path) {
+      Some(entry) => entry.stat(),
+      None => Err(not_found()),
+    }
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl File for OverlayFile {
+  fn read_sync(self: Rc<Self>, buf: &mut [u8]) -> FsResult<usize> {
+    self.read_to_buf(buf)
+  }
+  async fn read_byob(
+    self: Rc<Self>,
+    mut buf: BufMutView,
+  ) -> FsResult<(usize, BufMutView)> {
+    let nread = self.read_to_buf(&mut buf)?;
+    Ok((nread, buf))
+  }
+
+  fn write_sync(self: Rc<Self>, buf: &[u8]) -> FsResult<usize> {
+    self.write_from_buf(buf)
+  }
+  async fn write(self: Rc<Self>, buf: BufView) -> FsResult<WriteOutcome> {
+    let nwritten = self.write_from_buf(&buf)?;
+    Ok(WriteOutcome::Full { nwritten })
+  }
+
+  fn write_all_sync(self: Rc<Self>, buf: &[u8]) -> FsResult<()> {
+    self.write_from_buf(buf)?;
+    Ok(())
+  }
+  async fn write_all(self: Rc<Self>, buf: BufView) -> FsResult<()> {
+    self.write_from_buf(&buf)?;
+    Ok(())
+  }
+
+  fn read_all_sync(self: Rc<Self>) -> FsResult<Vec<u8>> {
+    self.read_to_end()
+  }
+  async fn read_all_async(self: Rc<Self>) -> FsResult<Vec<u8>> {
+    self.read_to_end()
+  }
+
+  fn chmod_sync(self: Rc<Self>, mode: u32) -> FsResult<()> {
+    self.update_metadata(|metadata| {
+      metadata.mode = (metadata.mode & !0o7777) | (mode & 0o7777)
+    })
+  }
+  async fn chmod_async(self: Rc<Self>, mode: u32) -> FsResult<()> {
+    self.chmod_sync(mode)
+  }
+
+  fn seek_sync(self: Rc<Self>, pos: SeekFrom) -> FsResult<u64> {
+    self.seek(pos)
+  }
+  async fn seek_async(self: Rc<Self>, pos: SeekFrom) -> FsResult<u64> {
+    self.seek(pos)
+  }
+
+  // there is nothing to flush to disk
+  fn datasync_sync(self: Rc<Self>) -> FsResult<()> {
+    Ok(())
+  }
+  async fn datasync_async(self: Rc<Self>) -> FsResult<()> {
+    Ok(())
+  }
+
+  fn sync_sync(self: Rc<Self>) -> FsResult<()> {
+    Ok(())
+  }
+  async fn sync_async(self: Rc<Self>) -> FsResult<()> {
+    Ok(())
+  }
+
+  fn stat_sync(self: Rc<Self>) -> FsResult<FsStat> {
+    self.stat()
+  }
+  async fn stat_async(self: Rc<Self>) -> FsResult<FsStat> {
+    self.stat()
+  }
+
+  // other processes can't see the file, so there is nobody to lock out
+  fn lock_sync(self: Rc<Self>, _exclusive: bool) -> FsResult<()> {
+    Ok(())
+  }
+  async fn lock_async(self: Rc<Self>, _exclusive: bool) -> FsResult<()> {
+    Ok(())
+  }
+
+  fn unlock_sync(self: Rc<Self>) -> FsResult<()> {
+    Ok(())
+  }
+  async fn unlock_async(self: Rc<Self>) -> FsResult<()> {
+    Ok(())
+  }
+
+  fn truncate_sync(self: Rc<Self>, len: u64) -> FsResult<()> {
+    self.truncate(len)
+  }
+  async fn truncate_async(self: Rc<Self>, len: u64) -> FsResult<()> {
+    self.truncate(len)
+  }
+
+  fn utime_sync(
+    self: Rc<Self>,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self.update_metadata(|metadata| {
+      metadata.atime = Some(to_ms((atime_secs, atime_nanos)));
+      metadata.mtime = Some(to_ms((mtime_secs, mtime_nanos)));
+    })
+  }
+  async fn utime_async(
+    self: Rc<Self>,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self.utime_sync(atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+  }
+
+  // lower level functionality
+  fn as_stdio(self: Rc<Self>) -> FsResult<std::process::Stdio> {
+    Err(FsError::NotSupported)
+  }
+  fn backing_fd(self: Rc<Self>) -> Option<ResourceHandleFd> {
+    None
+  }
+  fn try_clone_inner(self: Rc<Self>) -> FsResult<Rc<dyn File>> {
+    Ok(self)
+  }
+}
+
+fn not_found() -> FsError {
+  FsError::Io(Error::from(ErrorKind::NotFound))
+}
+
+fn other_error(message: &'static str) -> FsError {
+  FsError::Io(Error::new(ErrorKind::Other, message))
+}
+
+fn now_ms() -> Option<u64> {
+  SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .ok()
+    .map(|duration| duration.as_millis() as u64)
+}
+
+fn to_ms((secs, nanos): (i64, u32)) -> u64 {
+  (secs.max(0) as u64)
+    .saturating_mul(1000)
+    .saturating_add((nanos / 1_000_000) as u64)
+}
+
+fn is_write(options: &OpenOptions) -> bool {
+  options.write
+    || options.append
+    || options.create
+    || options.create_new
+    || options.truncate
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileSystem for OverlayFileSystem {
+  fn cwd(&self) -> FsResult<PathBuf> {
+    RealFs.cwd()
+  }
+
+  fn tmp_dir(&self) -> FsResult<PathBuf> {
+    RealFs.tmp_dir()
+  }
+
+  fn chdir(&self, path: &Path) -> FsResult<()> {
+    RealFs.chdir(path)
+  }
+
+  fn umask(&self, mask: Option<u32>) -> FsResult<u32> {
+    RealFs.umask(mask)
+  }
+
+  fn open_sync(
+    &self,
+    path: &Path,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb>,
+  ) -> FsResult<Rc<dyn File>> {
+    self.open(path, options, access_check)
+  }
+  async fn open_async<'a>(
+    &'a self,
+    path: PathBuf,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb<'a>>,
+  ) -> FsResult<Rc<dyn File>> {
+    if self.is_in_overlay(&path)? {
+      self.open(&path, options, access_check)
+    } else {
+      RealFs.open_async(path, options, access_check).await
+    }
+  }
+
+  fn mkdir_sync(
+    &self,
+    path: &Path,
+    recursive: bool,
+    mode: u32,
+  ) -> FsResult<()> {
+    self.mkdir(path, recursive, mode)
+  }
+  async fn mkdir_async(
+    &self,
+    path: PathBuf,
+    recursive: bool,
+    mode: u32,
+  ) -> FsResult<()> {
+    if self.is_in_overlay(&path)? {
+      self.mkdir(&path, recursive, mode)
+    } else {
+      RealFs.mkdir_async(path, recursive, mode).await
+    }
+  }
+
+  fn chmod_sync(&self, path: &Path, mode: u32) -> FsResult<()> {
+    self.chmod(path, mode)
+  }
+  async fn chmod_async(&self, path: PathBuf, mode: u32) -> FsResult<()> {
+    if self.is_in_overlay(&path)? {
+      self.chmod(&path, mode)
+    } else {
+      RealFs.chmod_async(path, mode).await
+    }
+  }
+
+  fn chown_sync(
+    &self,
+    path: &Path,
+    uid: Option<u32>,
+    gid: Option<u32>,
+  ) -> FsResult<()> {
+    self.chown(path, true, uid, gid)
+  }
+  async fn chown_async(
+    &self,
+    path: PathBuf,
+    uid: Option<u32>,
+    gid: Option<u32>,
+  ) -> FsResult<()> {
+    if self.is_in_overlay(&path)? {
+      self.chown(&path, true, uid, gid)
+    } else {
+      RealFs.chown_async(path, uid, gid).await
+    }
+  }
+
+  fn lchown_sync(
+    &self,
+    path: &Path,
+    uid: Option<u32>,
+    gid: Option<u32>,
+  ) -> FsResult<()> {
+    self.chown(path, false, uid, gid)
+  }
+  async fn lchown_async(
+    &self,
+    path: PathBuf,
+    uid: Option<u32>,
+    gid: Option<u32>,
+  ) -> FsResult<()> {
+    if self.is_in_overlay(&path)? {
+      self.chown(&path, false, uid, gid)
+    } else {
+      RealFs.lchown_async(path, uid, gid).await
+    }
+  }
+
+  fn remove_sync(&self, path: &Path, recursive: bool) -> FsResult<()> {
+    self.remove(path, recursive)
+  }
+  async fn remove_async(&self, path: PathBuf, recursive: bool) -> FsResult<()> {
+    if self.is_in_overlay(&path)? {
+      self.remove(&path, recursive)
+    } else {
+      RealFs.remove_async(path, recursive).await
+    }
+  }
+
+  fn copy_file_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    if self.is_in_overlay(oldpath)? || self.is_in_overlay(newpath)? {
+      self.copy_file(oldpath, newpath)
+    } else {
+      RealFs.copy_file_sync(oldpath, newpath)
+    }
+  }
+  async fn copy_file_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+  ) -> FsResult<()> {
+    if self.is_in_overlay(&oldpath)? || self.is_in_overlay(&newpath)? {
+      self.copy_file(&oldpath, &newpath)
+    } else {
+      RealFs.copy_file_async(oldpath, newpath).await
+    }
+  }
+
+  fn cp_sync(&self, path: &Path, new_path: &Path) -> FsResult<()> {
+    if self.is_in_overlay(path)? || self.is_in_overlay(new_path)? {
+      self.cp(path, new_path)
+    } else {
+      RealFs.cp_sync(path, new_path)
+    }
+  }
+  async fn cp_async(&self, path: PathBuf, new_path: PathBuf) -> FsResult<()> {
+    if self.is_in_overlay(&path)? || self.is_in_overlay(&new_path)? {
+      self.cp(&path, &new_path)
+    } else {
+      RealFs.cp_async(path, new_path).await
+    }
+  }
+
+  fn stat_sync(&self, path: &Path) -> FsResult<FsStat> {
+    self.stat(path, true)
+  }
+  async fn stat_async(&self, path: PathBuf) -> FsResult<FsStat> {
+    if self.is_in_overlay(&path)? {
+      self.stat(&path, true)
+    } else {
+      RealFs.stat_async(path).await
+    }
+  }
+
+  fn lstat_sync(&self, path: &Path) -> FsResult<FsStat> {
+    self.stat(path, false)
+  }
+  async fn lstat_async(&self, path: PathBuf) -> FsResult<FsStat> {
+    if self.is_in_overlay(&path)? {
+      self.stat(&path, false)
+    } else {
+      RealFs.lstat_async(path).await
+    }
+  }
+
+  fn realpath_sync(&self, path: &Path) -> FsResult<PathBuf> {
+    self.realpath(path)
+  }
+  async fn realpath_async(&self, path: PathBuf) -> FsResult<PathBuf> {
+    if self.is_in_overlay(&path)? {
+      self.realpath(&path)
+    } else {
+      RealFs.realpath_async(path).await
+    }
+  }
+
+  fn read_dir_sync(&self, path: &Path) -> FsResult<Vec<FsDirEntry>> {
+    self.read_dir(path)
+  }
+  async fn read_dir_async(&self, path: PathBuf) -> FsResult<Vec<FsDirEntry>> {
+    if self.is_in_overlay(&path)? {
+      self.read_dir(&path)
+    } else {
+      RealFs.read_dir_async(path).await
+    }
+  }
+
+  fn rename_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    self.rename(oldpath, newpath)
+  }
+  async fn rename_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+  ) -> FsResult<()> {
+    self.rename(&oldpath, &newpath)
+  }
+
+  fn link_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    self.link(oldpath, newpath)
+  }
+  async fn link_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+  ) -> FsResult<()> {
+    self.link(&oldpath, &newpath)
+  }
+
+  fn symlink_sync(
+    &self,
+    oldpath: &Path,
+    newpath: &Path,
+    file_type: Option<FsFileType>,
+  ) -> FsResult<()> {
+    if self.is_in_overlay(newpath)? {
+      self.symlink(oldpath, newpath)
+    } else {
+      RealFs.symlink_sync(oldpath, newpath, file_type)
+    }
+  }
+  async fn symlink_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+    file_type: Option<FsFileType>,
+  ) -> FsResult<()> {
+    if self.is_in_overlay(&newpath)? {
+      self.symlink(&oldpath, &newpath)
+    } else {
+      RealFs.symlink_async(oldpath, newpath, file_type).await
+    }
+  }
+
+  fn read_link_sync(&self, path: &Path) -> FsResult<PathBuf> {
+    self.read_link(path)
+  }
+  async fn read_link_async(&self, path: PathBuf) -> FsResult<PathBuf> {
+    if self.is_in_overlay(&path)? {
+      self.read_link(&path)
+    } else {
+      RealFs.read_link_async(path).await
+    }
+  }
+
+  fn truncate_sync(&self, path: &Path, len: u64) -> FsResult<()> {
+    self.truncate(path, len)
+  }
+  async fn truncate_async(&self, path: PathBuf, len: u64) -> FsResult<()> {
+    if self.is_in_overlay(&path)? {
+      self.truncate(&path, len)
+    } else {
+      RealFs.truncate_async(path, len).await
+    }
+  }
+
+  fn utime_sync(
+    &self,
+    path: &Path,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self.utime(
+      path,
+      true,
+      (atime_secs, atime_nanos),
+      (mtime_secs, mtime_nanos),
+    )
+  }
+  async fn utime_async(
+    &self,
+    path: PathBuf,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self.utime_sync(&path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+  }
+
+  fn lutime_sync(
+    &self,
+    path: &Path,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self.utime(
+      path,
+      false,
+      (atime_secs, atime_nanos),
+      (mtime_secs, mtime_nanos),
+    )
+  }
+  async fn lutime_async(
+    &self,
+    path: PathBuf,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self.lutime_sync(&path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+  }
+
+  fn write_file_sync(
+    &self,
+    path: &Path,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb>,
+    data: &[u8],
+  ) -> FsResult<()> {
+    if self.is_in_overlay(path)? {
+      self.write_file(path, options, access_check, data)
+    } else {
+      RealFs.write_file_sync(path, options, access_check, data)
+    }
+  }
+  async fn write_file_async<'a>(
+    &'a self,
+    path: PathBuf,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb<'a>>,
+    data: Vec<u8>,
+  ) -> FsResult<()> {
+    if self.is_in_overlay(&path)? {
+      self.write_file(&path, options, access_check, &data)
+    } else {
+      RealFs
+        .write_file_async(path, options, access_check, data)
+        .await
+    }
+  }
+
+  fn read_file_sync(
+    &self,
+    path: &Path,
+    access_check: Option<AccessCheckCb>,
+  ) -> FsResult<Vec<u8>> {
+    match self.read_file(path)? {
+      Ok((path, data)) => {
+        if let Some(access_check) = access_check {
+          (*access_check)(false, &path, &OpenOptions::read())?;
+        }
+        Ok(data)
+      }
+      Err(path) => RealFs.read_file_sync(&path, access_check),
+    }
+  }
+  async fn read_file_async<'a>(
+    &'a self,
+    path: PathBuf,
+    access_check: Option<AccessCheckCb<'a>>,
+  ) -> FsResult<Vec<u8>> {
+    match self.read_file(&path)? {
+      Ok((path, data)) => {
+        if let Some(access_check) = access_check {
+          (*access_check)(false, &path, &OpenOptions::read())?;
+        }
+        Ok(data)
+      }
+      Err(path) => RealFs.read_file_async(path, access_check).await,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use test_util::TempDir;
+
+  use super::*;
+
+  #[test]
+  fn reads_overlay_before_disk_and_writes_to_overlay() {
+    let temp_dir = TempDir::new();
+    temp_dir.write("data.txt", "from disk");
+    temp_dir.write(
+      "overlay.json",
+      r#"{ "data.txt": "from overlay", "virtual/new.txt": "new" }"#,
+    );
+    let fs =
+      OverlayFileSystem::load(temp_dir.path().join("overlay.json").as_path())
+        .unwrap();
+    let data_path = temp_dir.path().join("data.txt");
+    let data_path = data_path.as_path();
+
+    assert_eq!(fs.read_file_sync(data_path, None).unwrap(), b"from overlay");
+    assert!(fs.is_dir_sync(temp_dir.path().join("virtual").as_path()));
+
+    fs.write_file_sync(
+      data_path,
+      OpenOptions::write(false, true, false, None),
+      None,
+      b"!",
+    )
+    .unwrap();
+    assert_eq!(
+      fs.read_file_sync(data_path, None).unwrap(),
+      b"from overlay!"
+    );
+    // the disk is left untouched
+    assert_eq!(temp_dir.read_to_string("data.txt"), "from disk");
+
+    fs.remove_sync(data_path, false).unwrap();
+    assert!(!fs.exists_sync(data_path));
+    assert!(temp_dir.path().join("data.txt").exists());
+  }
+
+  #[test]
+  fn passes_paths_outside_the_overlay_to_disk() {
+    let temp_dir = TempDir::new();
+    temp_dir.create_dir_all("sandbox");
+    temp_dir.write("sandbox/overlay.json", "{}");
+    let fs = OverlayFileSystem::load(
+      temp_dir.path().join("sandbox/overlay.json").as_path(),
+    )
+    .unwrap();
+
+    let outside_path = temp_dir.path().join("outside");
+    fs.mkdir_sync(outside_path.as_path(), false, 0o755).unwrap();
+    fs.write_file_sync(
+      outside_path.join("file.txt").as_path(),
+      OpenOptions::write(true, false, false, None),
+      None,
+      b"on disk",
+    )
+    .unwrap();
+    assert_eq!(temp_dir.read_to_string("outside/file.txt"), "on disk");
+  }
+
+  #[test]
+  fn changes_copies_inside_the_overlay() {
+    let temp_dir = TempDir::new();
+    temp_dir.write("overlay.json", "{}");
+    temp_dir.write("disk.txt", "from disk");
+    temp_dir.create_dir_all("dir");
+    temp_dir.write("dir/child.txt", "child");
+    let fs =
+      OverlayFileSystem::load(temp_dir.path().join("overlay.json").as_path())
+        .unwrap();
+    let path = |name: &str| temp_dir.path().join(name);
+
+    // directories and files created through handles
+    fs.mkdir_sync(path("new/nested").as_path(), true, 0o755)
+      .unwrap();
+    let file = fs
+      .open_sync(
+        path("new/nested/file.txt").as_path(),
+        OpenOptions::write(true, false, false, None),
+        None,
+      )
+      .unwrap();
+    file.clone().write_all_sync(b"hello").unwrap();
+    file.seek_sync(SeekFrom::Start(0)).unwrap();
+    assert_eq!(
+      fs.read_file_sync(path("new/nested/file.txt").as_path(), None)
+        .unwrap(),
+      b"hello"
+    );
+    assert!(!path("new").exists());
+
+    // metadata changes are made to a copy
+    fs.chmod_sync(path("disk.txt").as_path(), 0o600).unwrap();
+    fs.utime_sync(path("disk.txt").as_path(), 1, 0, 2, 0)
+      .unwrap();
+    let stat = fs.stat_sync(path("disk.txt").as_path()).unwrap();
+    assert_eq!(stat.mode & 0o777, 0o600);
+    assert_eq!(stat.mtime, Some(2000));
+    assert_eq!(
+      fs.read_file_sync(path("disk.txt").as_path(), None).unwrap(),
+      b"from disk"
+    );
+
+    // renaming and removing directories only hide them
+    fs.rename_sync(path("dir").as_path(), path("moved").as_path())
+      .unwrap();
+    assert_eq!(
+      fs.read_file_sync(path("moved/child.txt").as_path(), None)
+        .unwrap(),
+      b"child"
+    );
+    assert!(!fs.exists_sync(path("dir/child.txt").as_path()));
+    fs.mkdir_sync(path("dir").as_path(), false, 0o755).unwrap();
+    assert!(fs.read_dir_sync(path("dir").as_path()).unwrap().is_empty());
+
+    fs.symlink_sync(path("moved").as_path(), path("link").as_path(), None)
+      .unwrap();
+    assert_eq!(
+      fs.read_file_sync(path("link/child.txt").as_path(), None)
+        .unwrap(),
+      b"child"
+    );
+
+    assert!(path("dir/child.txt").exists());
+    assert!(!path("moved").exists());
+    assert!(!path("link").exists());
+    assert_eq!(temp_dir.read_to_string("disk.txt"), "from disk");
+  }
+}
@@ -20,6 +20,10 @@ use super::draw_thread::DrawThreadRenderer;
 
 mod renderer;
 
+/// Log target of download, initialization and other progress output, so that
+/// it can be hidden on its own with `--quiet=progress`.
+pub const PROGRESS_LOG_TARGET: &str = "deno::progress";
+
 // Inspired by Indicatif, but this custom implementation allows
 // for more control over what's going on under the hood.
 
@@ -272,6 +276,7 @@ impl ProgressBar {
   /// Checks if progress bars are supported
   pub fn are_supported() -> bool {
     DrawThread::is_supported()
+      && log::log_enabled!(target: PROGRESS_LOG_TARGET, log::Level::Info)
   }
 
   pub fn new(style: ProgressBarStyle) -> Self {
@@ -313,7 +318,13 @@ impl ProgressBar {
     } else {
       // if we're not running in TTY, fallback to using logger crate
       if !msg.is_empty() {
-        log::log!(log::Level::Info, "{} {}", kind.as_text(), msg);
+        log::log!(
+          target: PROGRESS_LOG_TARGET,
+          log::Level::Info,
+          "{} {}",
+          kind.as_text(),
+          msg
+        );
       }
       UpdateGuard { maybe_entry: None }
     }
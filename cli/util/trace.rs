@@ -0,0 +1,295 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Records a timeline of runtime events in the Chrome trace event format,
+//! which can be viewed in `chrome://tracing` or https://ui.perfetto.dev.
+//!
+//! Recording is process wide and disabled unless [`init`] is called, in which
+//! case creating a span is a single atomic load.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::v8;
+use deno_core::OpMetricsEvent;
+use deno_core::OpMetricsFactoryFn;
+
+static RECORDER: OnceLock<TraceRecorder> = OnceLock::new();
+
+const TRACE_HEADER: &[u8] = b"{\"traceEvents\":[";
+const TRACE_TRAILER: &[u8] = b"],\"displayTimeUnit\":\"ms\"}";
+
+struct TraceRecorder {
+  path: PathBuf,
+  start: Instant,
+  writer: Mutex<TraceWriter>,
+}
+
+/// Streams events to the trace file as they are recorded, so they aren't
+/// kept in memory.
+struct TraceWriter {
+  file: BufWriter<File>,
+  has_events: bool,
+  /// The first error hit while writing an event, reported by [`flush`].
+  error: Option<std::io::Error>,
+}
+
+impl TraceWriter {
+  fn write_event(&mut self, event: &serde_json::Value) {
+    if self.error.is_some() {
+      return;
+    }
+    let result = (|| {
+      if self.has_events {
+        self.file.write_all(b",")?;
+      }
+      serde_json::to_writer(&mut self.file, event)?;
+      self.has_events = true;
+      Ok::<_, std::io::Error>(())
+    })();
+    self.error = result.err();
+  }
+
+  /// Terminates the events written so far so the file is valid JSON, then
+  /// moves back before the trailer so later events overwrite it.
+  fn flush(&mut self) -> std::io::Result<()> {
+    if let Some(err) = self.error.take() {
+      return Err(err);
+    }
+    self.file.write_all(TRACE_TRAILER)?;
+    self.file.flush()?;
+    self
+      .file
+      .seek(SeekFrom::Current(-(TRACE_TRAILER.len() as i64)))?;
+    Ok(())
+  }
+}
+
+impl TraceRecorder {
+  fn record(
+    &self,
+    category: &'static str,
+    name: &str,
+    detail: &str,
+    start: Instant,
+  ) {
+    let ts = start.duration_since(self.start).as_micros();
+    let dur = start.elapsed().as_micros();
+    self.writer.lock().write_event(&json!({
+      "name": name,
+      "cat": category,
+      "ph": "X",
+      "ts": ts as u64,
+      "dur": dur as u64,
+      "pid": std::process::id(),
+      "tid": current_tid(),
+      "args": { "detail": detail },
+    }));
+  }
+}
+
+fn current_tid() -> u64 {
+  static NEXT_TID: AtomicU64 = AtomicU64::new(1);
+  thread_local! {
+    static TID: Cell<u64> = const { Cell::new(0) };
+  }
+  TID.with(|tid| {
+    if tid.get() == 0 {
+      tid.set(NEXT_TID.fetch_add(1, Ordering::Relaxed));
+    }
+    tid.get()
+  })
+}
+
+/// Starts recording trace events to `path`. Events are written as they
+/// are recorded, but the file is only valid JSON after [`flush`] is called.
+pub fn init(path: PathBuf) -> Result<(), AnyError> {
+  if RECORDER.get().is_some() {
+    return Ok(());
+  }
+  let mut file = File::create(&path).with_context(|| {
+    format!("Failed creating trace file '{}'", path.display())
+  })?;
+  file.write_all(TRACE_HEADER)?;
+  let _ = RECORDER.set(TraceRecorder {
+    path,
+    start: Instant::now(),
+    writer: Mutex::new(TraceWriter {
+      file: BufWriter::new(file),
+      has_events: false,
+      error: None,
+    }),
+  });
+  Ok(())
+}
+
+/// Starts a span that is recorded as a complete event once dropped. Returns
+/// `None` when tracing is disabled.
+pub fn span(
+  category: &'static str,
+  name: &'static str,
+  detail: impl FnOnce() -> String,
+) -> Option<TraceSpan> {
+  RECORDER.get().map(|recorder| TraceSpan {
+    recorder,
+    category,
+    name,
+    detail: detail(),
+    start: Instant::now(),
+  })
+}
+
+pub struct TraceSpan {
+  recorder: &'static TraceRecorder,
+  category: &'static str,
+  name: &'static str,
+  detail: String,
+  start: Instant,
+}
+
+impl Drop for TraceSpan {
+  fn drop(&mut self) {
+    self
+      .recorder
+      .record(self.category, self.name, &self.detail, self.start);
+  }
+}
+
+/// Returns an op metrics factory that records a span for each op call, or
+/// `None` when tracing is disabled.
+///
+/// Op metrics don't identify the call that completed, so when several calls
+/// of an async op are in flight their spans are matched up in dispatch order.
+pub fn op_metrics_factory_fn() -> Option<OpMetricsFactoryFn> {
+  let recorder = RECORDER.get()?;
+  Some(Box::new(move |_, _, _| {
+    let dispatched: Rc<RefCell<Vec<Instant>>> = Default::default();
+    Some(Rc::new(move |op: &deno_core::_ops::OpCtx, event, _| {
+      let mut dispatched = dispatched.borrow_mut();
+      let start = match event {
+        OpMetricsEvent::Dispatched => {
+          dispatched.push(Instant::now());
+          return;
+        }
+        // a sync completion belongs to the call dispatched last
+        OpMetricsEvent::Completed | OpMetricsEvent::Error => dispatched.pop(),
+        OpMetricsEvent::CompletedAsync | OpMetricsEvent::ErrorAsync => {
+          (!dispatched.is_empty()).then(|| dispatched.remove(0))
+        }
+      };
+      if let Some(start) = start {
+        recorder.record("op", op.decl().name, "", start);
+      }
+    }))
+  }))
+}
+
+thread_local! {
+  static GC_START: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Records a span for each garbage collection in `isolate`, if tracing is
+/// enabled.
+pub fn install_gc_callbacks(isolate: &mut v8::Isolate) {
+  if RECORDER.get().is_none() {
+    return;
+  }
+  isolate.add_gc_prologue_callback(
+    gc_prologue,
+    std::ptr::null_mut(),
+    v8::GCType::kGCTypeAll,
+  );
+  isolate.add_gc_epilogue_callback(
+    gc_epilogue,
+    std::ptr::null_mut(),
+    v8::GCType::kGCTypeAll,
+  );
+}
+
+extern "C" fn gc_prologue(
+  _isolate: *mut v8::Isolate,
+  _gc_type: v8::GCType,
+  _flags: v8::GCCallbackFlags,
+  _data: *mut c_void,
+) {
+  GC_START.with(|start| start.set(Some(Instant::now())));
+}
+
+extern "C" fn gc_epilogue(
+  _isolate: *mut v8::Isolate,
+  gc_type: v8::GCType,
+  _flags: v8::GCCallbackFlags,
+  _data: *mut c_void,
+) {
+  let (Some(recorder), Some(start)) =
+    (RECORDER.get(), GC_START.with(|start| start.take()))
+  else {
+    return;
+  };
+  let name = match gc_type {
+    v8::GCType::kGCTypeScavenge => "scavenge",
+    v8::GCType::kGCTypeMinorMarkSweep => "minor_mark_sweep",
+    v8::GCType::kGCTypeMarkSweepCompact => "mark_sweep_compact",
+    v8::GCType::kGCTypeIncrementalMarking => "incremental_marking",
+    v8::GCType::kGCTypeProcessWeakCallbacks => "process_weak_callbacks",
+    _ => "gc",
+  };
+  recorder.record("gc", name, "", start);
+}
+
+/// Completes the trace file with the events recorded so far, if tracing is
+/// enabled. Events recorded after a flush are appended to the file, so a
+/// later flush writes every event recorded since [`init`].
+pub fn flush() -> Result<(), AnyError> {
+  let Some(recorder) = RECORDER.get() else {
+    return Ok(());
+  };
+  recorder.writer.lock().flush().with_context(|| {
+    format!("Failed writing trace file '{}'", recorder.path.display())
+  })
+}
+
+pub fn flush_or_log() {
+  if let Err(err) = flush() {
+    log::error!("{:#}", err);
+  }
+}
+
+/// Flushes the trace when dropped without [`FlushGuard::finish`], so that
+/// it's written when the run fails too. `Deno.exit()` skips this, so workers
+/// flush from an exit hook.
+#[derive(Default)]
+pub struct FlushGuard {
+  finished: bool,
+}
+
+impl FlushGuard {
+  pub fn finish(mut self) -> Result<(), AnyError> {
+    self.finished = true;
+    flush()
+  }
+}
+
+impl Drop for FlushGuard {
+  fn drop(&mut self) {
+    if !self.finished {
+      flush_or_log();
+    }
+  }
+}
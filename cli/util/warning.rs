@@ -0,0 +1,64 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use regex::Regex;
+
+/// Logs a warning. When the warning matches the `--abort-on-warning`
+/// pattern this errors after logging it, so the caller
+/// can stop with `?` instead of carrying on.
+pub fn warn(
+  message: impl std::fmt::Display,
+  maybe_abort_on_warning: Option<&str>,
+) -> Result<(), AnyError> {
+  let message = message.to_string();
+  log::warn!("{}", message);
+  let Some(pattern) = maybe_abort_on_warning else {
+    return Ok(());
+  };
+  // validated when parsing the flag
+  let regex = Regex::new(pattern)?;
+  if regex.is_match(&console_static_text::ansi::strip_ansi_codes(&message)) {
+    bail!(
+      "Aborting because a warning matched --abort-on-warning={}",
+      pattern
+    );
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn warn_without_pattern() {
+    assert!(warn("Warning something happened", None).is_ok());
+  }
+
+  #[test]
+  fn warn_matching_pattern() {
+    let err =
+      warn("Warning something happened", Some("something")).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Aborting because a warning matched --abort-on-warning=something"
+    );
+  }
+
+  #[test]
+  fn warn_matches_case_sensitively() {
+    assert!(warn("Warning something happened", Some("Something")).is_ok());
+  }
+
+  #[test]
+  fn warn_other_pattern() {
+    assert!(warn("Warning something happened", Some("else")).is_ok());
+  }
+
+  #[test]
+  fn warn_matches_without_colors() {
+    let message = format!("{} happened", crate::colors::yellow("Warning"));
+    assert!(warn(message, Some("^Warning happened$")).is_err());
+  }
+}
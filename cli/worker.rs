@@ -4,16 +4,26 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::generic_error;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::futures::FutureExt;
+use deno_core::serde_json;
+use deno_core::serde_v8;
+use deno_core::stats::RuntimeActivity;
+use deno_core::stats::RuntimeActivityStats;
+use deno_core::stats::RuntimeActivityStatsFilter;
 use deno_core::url::Url;
 use deno_core::v8;
 use deno_core::CompiledWasmModuleStore;
 use deno_core::Extension;
 use deno_core::FeatureChecker;
+use deno_core::LocalInspectorSession;
 use deno_core::ModuleId;
 use deno_core::ModuleLoader;
 use deno_core::PollEventLoopOptions;
@@ -51,6 +61,7 @@ use crate::npm::CliNpmResolver;
 use crate::util::checksum;
 use crate::util::file_watcher::WatcherCommunicator;
 use crate::util::file_watcher::WatcherRestartMode;
+use crate::util::trace;
 use crate::version;
 
 pub struct ModuleLoaderAndSourceMapGetter {
@@ -97,6 +108,10 @@ pub type CreateCoverageCollectorCb = Box<
 pub struct CliMainWorkerOptions {
   pub argv: Vec<String>,
   pub log_level: WorkerLogLevel,
+  pub check_resource_leaks: bool,
+  /// How long to keep the event loop running after `Deno.exit()`.
+  pub exit_drain: Option<Duration>,
+  pub max_redirect_bytes: Option<u64>,
   pub enable_op_summary_metrics: bool,
   pub enable_testing_features: bool,
   pub has_node_modules_dir: bool,
@@ -116,6 +131,14 @@ pub struct CliMainWorkerOptions {
   pub skip_op_registration: bool,
   pub create_hmr_runner: Option<CreateHmrRunnerCb>,
   pub create_coverage_collector: Option<CreateCoverageCollectorCb>,
+  /// Where to write a V8 CPU profile of the main module (`--cpu-prof`).
+  pub cpu_prof: Option<PathBuf>,
+  /// Evaluate CommonJS modules in strict mode (`--force-strict`).
+  pub force_strict: bool,
+  /// Throw when a deprecated Deno API is used (`--deprecation=error`).
+  pub deprecated_api_error: bool,
+  /// Throw when a warning matches this pattern (`--abort-on-warning`).
+  pub abort_on_warning: Option<String>,
 }
 
 struct SharedWorkerState {
@@ -158,6 +181,30 @@ impl SharedWorkerState {
   }
 }
 
+fn resource_leak_filter() -> RuntimeActivityStatsFilter {
+  RuntimeActivityStatsFilter::default()
+    .with_resources()
+    .with_timers()
+}
+
+fn format_resource_leaks(leaks: &[RuntimeActivity]) -> String {
+  let mut message =
+    String::from("Resources were still open when the program finished:");
+  for leak in leaks {
+    let item = match leak {
+      RuntimeActivity::Resource(rid, _, name) => format!("{name} (rid {rid})"),
+      RuntimeActivity::Timer(id, _) => format!("timer (id {id})"),
+      RuntimeActivity::Interval(id, _) => format!("interval (id {id})"),
+      RuntimeActivity::AsyncOp(id, _, name) => {
+        format!("{name} (promise id {id})")
+      }
+    };
+    message.push_str("\n    - ");
+    message.push_str(&item);
+  }
+  message
+}
+
 pub struct CliMainWorker {
   main_module: ModuleSpecifier,
   is_main_cjs: bool,
@@ -179,9 +226,93 @@ impl CliMainWorker {
     let mut maybe_coverage_collector =
       self.maybe_setup_coverage_collector().await?;
     let mut maybe_hmr_runner = self.maybe_setup_hmr_runner().await?;
+    let maybe_cpu_profiler = self.maybe_start_cpu_profiler().await?;
+    // Anything open before the main module runs, like the stdio resources,
+    // belongs to the runtime rather than the program.
+    let maybe_leak_check =
+      self.shared.options.check_resource_leaks.then(|| {
+        let stats = self.worker.js_runtime.runtime_activity_stats_factory();
+        let before = stats.clone().capture(&resource_leak_filter());
+        (stats, before)
+      });
 
     log::debug!("main_module {}", self.main_module);
 
+    let result = self
+      .execute_and_run_event_loop(
+        &mut maybe_hmr_runner,
+        maybe_coverage_collector.is_none(),
+      )
+      .await;
+    // The profile is written even if the program threw, as that's often when
+    // it is most interesting.
+    if let Some(session) = maybe_cpu_profiler {
+      if let Err(profile_err) = self.stop_cpu_profiler(session).await {
+        if result.is_ok() {
+          return Err(profile_err);
+        }
+        log::warn!("Failed writing CPU profile: {:#}", profile_err);
+      }
+    }
+    if let Err(err) = result {
+      // `Deno.exit()` stopped the script, give its cleanup a chance to run
+      match self.shared.options.exit_drain {
+        Some(grace) if self.worker.exit_drain_requested() => {
+          self.worker.drain_after_exit(grace).await;
+          if let Some(coverage_collector) = maybe_coverage_collector.as_mut() {
+            self.stop_coverage_collector(coverage_collector).await?;
+          }
+          return Ok(self.worker.exit_code());
+        }
+        _ => {
+          // Keep the coverage of the code that ran up to the error.
+          if let Some(coverage_collector) = maybe_coverage_collector.as_mut() {
+            if let Err(coverage_err) =
+              self.stop_coverage_collector(coverage_collector).await
+            {
+              log::warn!("Failed writing coverage: {:#}", coverage_err);
+            }
+          }
+          return Err(err);
+        }
+      }
+    }
+
+    self.worker.dispatch_unload_event()?;
+    self.worker.dispatch_process_exit_event()?;
+
+    if let Some(coverage_collector) = maybe_coverage_collector.as_mut() {
+      self.stop_coverage_collector(coverage_collector).await?;
+    }
+    if let Some(hmr_runner) = maybe_hmr_runner.as_mut() {
+      self
+        .worker
+        .js_runtime
+        .with_event_loop_future(
+          hmr_runner.stop().boxed_local(),
+          PollEventLoopOptions::default(),
+        )
+        .await?;
+    }
+    if let Some((stats, before)) = maybe_leak_check {
+      let after = stats.capture(&resource_leak_filter());
+      let diff = RuntimeActivityStats::diff(&before, &after);
+      if !diff.appeared.is_empty() {
+        return Err(generic_error(format_resource_leaks(&diff.appeared)));
+      }
+    }
+
+    Ok(self.worker.exit_code())
+  }
+
+  async fn execute_and_run_event_loop(
+    &mut self,
+    maybe_hmr_runner: &mut Option<Box<dyn HmrRunner>>,
+    wait_for_inspector: bool,
+  ) -> Result<(), AnyError> {
+    let trace_span = trace::span("worker", "execute_main_module", || {
+      self.main_module.to_string()
+    });
     if self.is_main_cjs {
       deno_node::load_cjs_module(
         &mut self.worker.js_runtime,
@@ -194,7 +325,9 @@ impl CliMainWorker {
     }
 
     self.worker.dispatch_load_event()?;
+    drop(trace_span);
 
+    let trace_span = trace::span("worker", "event_loop", String::new);
     loop {
       if let Some(hmr_runner) = maybe_hmr_runner.as_mut() {
         let watcher_communicator =
@@ -218,10 +351,7 @@ impl CliMainWorker {
           return Err(e);
         }
       } else {
-        self
-          .worker
-          .run_event_loop(maybe_coverage_collector.is_none())
-          .await?;
+        self.worker.run_event_loop(wait_for_inspector).await?;
       }
 
       let web_continue = self.worker.dispatch_beforeunload_event()?;
@@ -233,34 +363,32 @@ impl CliMainWorker {
       }
     }
 
-    self.worker.dispatch_unload_event()?;
-    self.worker.dispatch_process_exit_event()?;
+    drop(trace_span);
+    Ok(())
+  }
 
-    if let Some(coverage_collector) = maybe_coverage_collector.as_mut() {
-      self
-        .worker
-        .js_runtime
-        .with_event_loop_future(
-          coverage_collector.stop_collecting().boxed_local(),
-          PollEventLoopOptions::default(),
-        )
-        .await?;
-    }
-    if let Some(hmr_runner) = maybe_hmr_runner.as_mut() {
-      self
-        .worker
-        .js_runtime
-        .with_event_loop_future(
-          hmr_runner.stop().boxed_local(),
-          PollEventLoopOptions::default(),
-        )
-        .await?;
+  /// Runs the main module until the event loop is idle and hands out its
+  /// exports. Unlike [`Self::run`], the isolate is kept alive so that the
+  /// exported functions can still be called.
+  pub async fn run_for_namespace(
+    mut self,
+  ) -> Result<ModuleNamespace, AnyError> {
+    if self.is_main_cjs {
+      bail!(
+        "Cannot get the exports of '{}', because it is a CommonJS module.",
+        self.main_module
+      );
     }
-
-    Ok(self.worker.exit_code())
+    let id = self.worker.preload_main_module(&self.main_module).await?;
+    self.evaluate_module_possibly_with_npm(id).await?;
+    self.worker.dispatch_load_event()?;
+    self.worker.run_event_loop(false).await?;
+    ModuleNamespace::new(self.worker, id)
   }
 
-  pub async fn run_for_watcher(self) -> Result<(), AnyError> {
+  /// Runs the main module for the file watcher, returning the exit code set
+  /// by the program.
+  pub async fn run_for_watcher(self) -> Result<i32, AnyError> {
     /// The FileWatcherModuleExecutor provides module execution with safe dispatching of life-cycle events by tracking the
     /// state of any pending events and emitting accordingly on drop in the case of a future
     /// cancellation.
@@ -333,7 +461,8 @@ impl CliMainWorker {
     }
 
     let mut executor = FileWatcherModuleExecutor::new(self);
-    executor.execute().await
+    executor.execute().await?;
+    Ok(executor.inner.worker.exit_code())
   }
 
   pub async fn execute_main_module_possibly_with_npm(
@@ -343,6 +472,31 @@ impl CliMainWorker {
     self.evaluate_module_possibly_with_npm(id).await
   }
 
+  /// Loads the main module and its static imports without evaluating them.
+  /// This type checks them with `--check` and fills the transpile and V8
+  /// code caches, so that the next run starts warm.
+  pub async fn warmup(&mut self) -> Result<(), AnyError> {
+    if self.is_main_cjs {
+      // CommonJS modules are only compiled as they are evaluated
+      return Ok(());
+    }
+    self.worker.preload_main_module(&self.main_module).await?;
+    Ok(())
+  }
+
+  /// Loads and evaluates `specifiers` in order, before the main module, for
+  /// `--preload`.
+  pub async fn execute_preload_modules(
+    &mut self,
+    specifiers: &[ModuleSpecifier],
+  ) -> Result<(), AnyError> {
+    for specifier in specifiers {
+      let id = self.worker.preload_side_module(specifier).await?;
+      self.evaluate_module_possibly_with_npm(id).await?;
+    }
+    Ok(())
+  }
+
   pub async fn execute_side_module_possibly_with_npm(
     &mut self,
   ) -> Result<(), AnyError> {
@@ -405,6 +559,88 @@ impl CliMainWorker {
     Ok(Some(coverage_collector))
   }
 
+  async fn stop_coverage_collector(
+    &mut self,
+    coverage_collector: &mut dyn CoverageCollector,
+  ) -> Result<(), AnyError> {
+    self
+      .worker
+      .js_runtime
+      .with_event_loop_future(
+        coverage_collector.stop_collecting().boxed_local(),
+        PollEventLoopOptions::default(),
+      )
+      .await
+  }
+
+  async fn maybe_start_cpu_profiler(
+    &mut self,
+  ) -> Result<Option<LocalInspectorSession>, AnyError> {
+    if self.shared.options.cpu_prof.is_none() {
+      return Ok(None);
+    }
+
+    let mut session = self.worker.create_inspector_session();
+    self
+      .worker
+      .js_runtime
+      .with_event_loop_future(
+        async {
+          session.post_message::<()>("Profiler.enable", None).await?;
+          session.post_message::<()>("Profiler.start", None).await?;
+          Ok::<_, AnyError>(())
+        }
+        .boxed_local(),
+        PollEventLoopOptions::default(),
+      )
+      .await?;
+    Ok(Some(session))
+  }
+
+  /// Stops the profiler started by `maybe_start_cpu_profiler` and writes the
+  /// profile as a `.cpuprofile` file, which Chrome DevTools can load.
+  async fn stop_cpu_profiler(
+    &mut self,
+    mut session: LocalInspectorSession,
+  ) -> Result<(), AnyError> {
+    let Some(path) = self.shared.options.cpu_prof.clone() else {
+      return Ok(());
+    };
+    let mut result = self
+      .worker
+      .js_runtime
+      .with_event_loop_future(
+        session
+          .post_message::<()>("Profiler.stop", None)
+          .boxed_local(),
+        PollEventLoopOptions::default(),
+      )
+      .await?;
+    let profile = result
+      .get_mut("profile")
+      .map(serde_json::Value::take)
+      .ok_or_else(|| generic_error("The inspector returned no CPU profile."))?;
+    std::fs::write(&path, serde_json::to_vec(&profile)?).with_context(
+      || format!("Failed writing CPU profile to '{}'.", path.display()),
+    )?;
+    Ok(())
+  }
+
+  /// Routes randomness, clock reads and `fetch()` responses through the
+  /// `deno_nondeterminism` extension, which has to be one of the custom
+  /// extensions of this worker.
+  pub fn install_nondeterminism_hooks(&mut self) -> Result<(), AnyError> {
+    self.worker.js_runtime.lazy_load_es_module_with_code(
+      "ext:cli/40_nondeterminism.js",
+      deno_core::ascii_str_include!("js/40_nondeterminism.js"),
+    )?;
+    Ok(())
+  }
+
+  pub fn add_exit_hook(&mut self, hook: impl FnOnce() + 'static) {
+    self.worker.add_exit_hook(hook);
+  }
+
   pub fn execute_script_static(
     &mut self,
     name: &'static str,
@@ -414,6 +650,99 @@ impl CliMainWorker {
   }
 }
 
+/// The exports of an evaluated module, along with the worker that keeps its
+/// isolate alive.
+pub struct ModuleNamespace {
+  worker: MainWorker,
+  namespace: v8::Global<v8::Object>,
+}
+
+// only used by embedders of the cli crate and tests
+#[allow(dead_code)]
+impl ModuleNamespace {
+  pub fn new(worker: MainWorker, id: ModuleId) -> Result<Self, AnyError> {
+    let namespace = worker.js_runtime.get_module_namespace(id)?;
+    Ok(Self { worker, namespace })
+  }
+
+  pub fn into_main_worker(self) -> MainWorker {
+    self.worker
+  }
+
+  /// Names of the exports, including `default`.
+  pub fn export_names(&mut self) -> Vec<String> {
+    let scope = &mut self.worker.js_runtime.handle_scope();
+    let namespace = v8::Local::new(scope, &self.namespace);
+    let Some(names) =
+      namespace.get_own_property_names(scope, Default::default())
+    else {
+      return Vec::new();
+    };
+    (0..names.length())
+      .filter_map(|index| names.get_index(scope, index))
+      .map(|name| name.to_rust_string_lossy(scope))
+      .collect()
+  }
+
+  /// Reads an export that can be serialized, like a string or a plain object.
+  pub fn get(&mut self, name: &str) -> Result<serde_json::Value, AnyError> {
+    let scope = &mut self.worker.js_runtime.handle_scope();
+    let namespace = v8::Local::new(scope, &self.namespace);
+    let value = get_export(scope, namespace, name)?;
+    Ok(serde_v8::from_v8(scope, value)?)
+  }
+
+  /// Calls an exported function and waits for the promise it returns, if
+  /// any. The arguments and the result are passed as serialized values.
+  pub async fn call(
+    &mut self,
+    name: &str,
+    args: &[serde_json::Value],
+  ) -> Result<serde_json::Value, AnyError> {
+    let (function, args) = {
+      let scope = &mut self.worker.js_runtime.handle_scope();
+      let namespace = v8::Local::new(scope, &self.namespace);
+      let value = get_export(scope, namespace, name)?;
+      let function =
+        v8::Local::<v8::Function>::try_from(value).map_err(|_| {
+          type_error(format!("Export '{name}' is not a function"))
+        })?;
+      let args = args
+        .iter()
+        .map(|arg| {
+          let arg = serde_v8::to_v8(scope, arg)?;
+          Ok(v8::Global::new(scope, arg))
+        })
+        .collect::<Result<Vec<_>, AnyError>>()?;
+      (v8::Global::new(scope, function), args)
+    };
+    let call = self.worker.js_runtime.call_with_args(&function, &args);
+    let result = self
+      .worker
+      .js_runtime
+      .with_event_loop_promise(call, PollEventLoopOptions::default())
+      .await?;
+    let scope = &mut self.worker.js_runtime.handle_scope();
+    let result = v8::Local::new(scope, result);
+    Ok(serde_v8::from_v8(scope, result)?)
+  }
+}
+
+fn get_export<'s>(
+  scope: &mut v8::HandleScope<'s>,
+  namespace: v8::Local<'s, v8::Object>,
+  name: &str,
+) -> Result<v8::Local<'s, v8::Value>, AnyError> {
+  let key = v8::String::new(scope, name).unwrap();
+  if !namespace
+    .has_own_property(scope, key.into())
+    .unwrap_or(false)
+  {
+    return Err(type_error(format!("Module has no export named '{name}'")));
+  }
+  Ok(namespace.get(scope, key.into()).unwrap())
+}
+
 #[derive(Clone)]
 pub struct CliMainWorkerFactory {
   shared: Arc<SharedWorkerState>,
@@ -501,34 +830,9 @@ impl CliMainWorkerFactory {
     let (main_module, is_main_cjs) = if let Ok(package_ref) =
       NpmPackageReqReference::from_specifier(&main_module)
     {
-      if let Some(npm_resolver) = shared.npm_resolver.as_managed() {
-        npm_resolver
-          .add_package_reqs(&[package_ref.req().clone()])
-          .await?;
-      }
-
-      // use a fake referrer that can be used to discover the package.json if necessary
-      let referrer =
-        ModuleSpecifier::from_directory_path(self.shared.fs.cwd()?)
-          .unwrap()
-          .join("package.json")?;
-      let package_folder = shared
-        .npm_resolver
-        .resolve_pkg_folder_from_deno_module_req(
-          package_ref.req(),
-          &referrer,
-        )?;
-      let node_resolution = self
-        .resolve_binary_entrypoint(&package_folder, package_ref.sub_path())?;
+      let node_resolution =
+        self.resolve_npm_binary_entrypoint(&package_ref).await?;
       let is_main_cjs = matches!(node_resolution, NodeResolution::CommonJs(_));
-
-      if let Some(lockfile) = &shared.maybe_lockfile {
-        // For npm binary commands, ensure that the lockfile gets updated
-        // so that we can re-use the npm resolution the next time it runs
-        // for better performance
-        lockfile.write_if_changed()?;
-      }
-
       (node_resolution.into_url(), is_main_cjs)
     } else if shared.options.is_npm_main
       || shared.node_resolver.in_npm_package(&main_module)
@@ -609,6 +913,9 @@ impl CliMainWorkerFactory {
         mode,
         serve_port: shared.serve_port,
         serve_host: shared.serve_host.clone(),
+        force_strict: shared.options.force_strict,
+        deprecated_api_error: shared.options.deprecated_api_error,
+        abort_on_warning: shared.options.abort_on_warning.clone(),
       },
       extensions: custom_extensions,
       startup_snapshot: crate::js::deno_isolate_init(),
@@ -625,6 +932,7 @@ impl CliMainWorkerFactory {
       should_break_on_first_statement: shared.options.inspect_brk,
       should_wait_for_inspector_session: shared.options.inspect_wait,
       strace_ops: shared.options.strace_ops.clone(),
+      op_metrics_factory_fn: trace::op_metrics_factory_fn(),
       module_loader,
       fs: shared.fs.clone(),
       node_services: Some(shared.create_node_init_services()),
@@ -641,6 +949,8 @@ impl CliMainWorkerFactory {
       feature_checker,
       skip_op_registration: shared.options.skip_op_registration,
       v8_code_cache: shared.code_cache.clone(),
+      exit_drain: shared.options.exit_drain.is_some(),
+      max_redirect_bytes: shared.options.max_redirect_bytes,
     };
 
     let mut worker = MainWorker::bootstrap_from_options(
@@ -648,6 +958,7 @@ impl CliMainWorkerFactory {
       permissions,
       options,
     );
+    trace::install_gc_callbacks(worker.js_runtime.v8_isolate());
 
     if self.shared.subcommand.needs_test() {
       macro_rules! test_file {
@@ -674,6 +985,39 @@ impl CliMainWorkerFactory {
     })
   }
 
+  /// Installs the package of an `npm:` specifier and resolves the bin
+  /// script it refers to.
+  pub async fn resolve_npm_binary_entrypoint(
+    &self,
+    package_ref: &NpmPackageReqReference,
+  ) -> Result<NodeResolution, AnyError> {
+    let shared = &self.shared;
+    if let Some(npm_resolver) = shared.npm_resolver.as_managed() {
+      npm_resolver
+        .add_package_reqs(&[package_ref.req().clone()])
+        .await?;
+    }
+
+    // use a fake referrer that can be used to discover the package.json if necessary
+    let referrer = ModuleSpecifier::from_directory_path(self.shared.fs.cwd()?)
+      .unwrap()
+      .join("package.json")?;
+    let package_folder = shared
+      .npm_resolver
+      .resolve_pkg_folder_from_deno_module_req(package_ref.req(), &referrer)?;
+    let node_resolution = self
+      .resolve_binary_entrypoint(&package_folder, package_ref.sub_path())?;
+
+    if let Some(lockfile) = &shared.maybe_lockfile {
+      // For npm binary commands, ensure that the lockfile gets updated
+      // so that we can re-use the npm resolution the next time it runs
+      // for better performance
+      lockfile.write_if_changed()?;
+    }
+
+    Ok(node_resolution)
+  }
+
   fn resolve_binary_entrypoint(
     &self,
     package_folder: &Path,
@@ -807,6 +1151,9 @@ fn create_web_worker_callback(
         mode: WorkerExecutionMode::Worker,
         serve_port: shared.serve_port,
         serve_host: shared.serve_host.clone(),
+        force_strict: shared.options.force_strict,
+        deprecated_api_error: shared.options.deprecated_api_error,
+        abort_on_warning: shared.options.abort_on_warning.clone(),
       },
       extensions: vec![],
       startup_snapshot: crate::js::deno_isolate_init(),
@@ -834,17 +1181,21 @@ fn create_web_worker_callback(
       cache_storage_dir,
       feature_checker,
       strace_ops: shared.options.strace_ops.clone(),
+      op_metrics_factory_fn: trace::op_metrics_factory_fn(),
       close_on_idle: args.close_on_idle,
       maybe_worker_metadata: args.maybe_worker_metadata,
+      max_redirect_bytes: shared.options.max_redirect_bytes,
     };
 
-    WebWorker::bootstrap_from_options(
+    let (mut worker, handle) = WebWorker::bootstrap_from_options(
       args.name,
       args.permissions,
       args.main_module,
       args.worker_id,
       options,
-    )
+    );
+    trace::install_gc_callbacks(worker.js_runtime.v8_isolate());
+    (worker, handle)
   })
 }
 
@@ -912,6 +1263,41 @@ mod tests {
     assert!(result.is_err());
   }
 
+  #[tokio::test]
+  async fn module_namespace_call_export() {
+    let p = test_util::testdata_path().join("runtime/exports_fn.js");
+    let module_specifier = ModuleSpecifier::from_file_path(&p).unwrap();
+    let mut worker = create_test_worker();
+    let id = worker.preload_main_module(&module_specifier).await.unwrap();
+    worker.evaluate_module(id).await.unwrap();
+    let mut namespace = ModuleNamespace::new(worker, id).unwrap();
+
+    let mut names = namespace.export_names();
+    names.sort();
+    assert_eq!(names, vec!["add", "greet", "greeting"]);
+    assert_eq!(
+      namespace.get("greeting").unwrap(),
+      serde_json::json!("hello")
+    );
+    assert_eq!(
+      namespace
+        .call("add", &[serde_json::json!(1), serde_json::json!(2)])
+        .await
+        .unwrap(),
+      serde_json::json!(3)
+    );
+    assert_eq!(
+      namespace
+        .call("greet", &[serde_json::json!("deno")])
+        .await
+        .unwrap(),
+      serde_json::json!("hello deno")
+    );
+    let err = namespace.call("greeting", &[]).await.unwrap_err();
+    assert_eq!(err.to_string(), "Export 'greeting' is not a function");
+    assert!(namespace.get("missing").is_err());
+  }
+
   #[tokio::test]
   async fn execute_mod_002_hello() {
     // This assumes cwd is project root (an assumption made throughout the
@@ -157,6 +157,7 @@ async function mainFetch(req, recursive, terminator) {
     reqBody !== null || reqRid !== null,
     reqBody,
     reqRid,
+    req.redirectCount,
   );
 
   function onAbort() {
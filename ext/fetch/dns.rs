@@ -0,0 +1,133 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use hyper_util::client::legacy::connect::dns::GaiResolver;
+use hyper_util::client::legacy::connect::dns::Name;
+use tower_service::Service;
+
+/// Callback resolving a host name to the addresses to connect to, for
+/// [`crate::CreateHttpClientOptions::dns_resolver`].
+///
+/// Addresses with port `0` are connected to on the port of the request URL.
+/// Returning no address falls back to the system resolver.
+#[derive(Clone)]
+pub struct DnsResolver(pub Arc<dyn Fn(&str) -> Vec<SocketAddr> + Send + Sync>);
+
+impl DnsResolver {
+  pub fn new(
+    resolve: impl Fn(&str) -> Vec<SocketAddr> + Send + Sync + 'static,
+  ) -> Self {
+    Self(Arc::new(resolve))
+  }
+}
+
+impl std::fmt::Debug for DnsResolver {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_tuple("DnsResolver").finish_non_exhaustive()
+  }
+}
+
+/// Which IP versions the fetch client connects over, for
+/// [`crate::CreateHttpClientOptions::address_family_preference`]. Applies to
+/// resolved host names; IP addresses in a URL are connected to as they are.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AddressFamily {
+  /// Connect over whichever version the host resolves to first, falling
+  /// back to the other one ("happy eyeballs").
+  #[default]
+  Any,
+  Ipv4Only,
+  Ipv6Only,
+  /// Try the IPv4 addresses first and fall back to IPv6.
+  PreferIpv4,
+}
+
+impl AddressFamily {
+  fn apply(
+    self,
+    host: &str,
+    mut addrs: Vec<SocketAddr>,
+  ) -> Result<Vec<SocketAddr>, std::io::Error> {
+    match self {
+      AddressFamily::Any => return Ok(addrs),
+      AddressFamily::Ipv4Only => addrs.retain(SocketAddr::is_ipv4),
+      AddressFamily::Ipv6Only => addrs.retain(SocketAddr::is_ipv6),
+      // stable, so the resolver's order is kept within each version
+      AddressFamily::PreferIpv4 => addrs.sort_by_key(SocketAddr::is_ipv6),
+    }
+    if addrs.is_empty() {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::AddrNotAvailable,
+        format!("{host} has no address of the allowed IP version ({self:?})"),
+      ));
+    }
+    Ok(addrs)
+  }
+}
+
+/// Resolver of the fetch client's connector: asks the [`DnsResolver`] if
+/// there is one and the system resolver otherwise.
+#[derive(Clone, Debug)]
+pub(crate) struct Resolver {
+  custom: Option<DnsResolver>,
+  system: GaiResolver,
+  family: AddressFamily,
+}
+
+impl Resolver {
+  pub(crate) fn new(
+    custom: Option<DnsResolver>,
+    family: AddressFamily,
+  ) -> Self {
+    Self {
+      custom,
+      system: GaiResolver::new(),
+      family,
+    }
+  }
+}
+
+type ResolveFuture = Pin<
+  Box<
+    dyn Future<Output = Result<std::vec::IntoIter<SocketAddr>, std::io::Error>>
+      + Send,
+  >,
+>;
+
+impl Service<Name> for Resolver {
+  type Response = std::vec::IntoIter<SocketAddr>;
+  type Error = std::io::Error;
+  type Future = ResolveFuture;
+
+  fn poll_ready(
+    &mut self,
+    cx: &mut Context<'_>,
+  ) -> Poll<Result<(), Self::Error>> {
+    self.system.poll_ready(cx)
+  }
+
+  fn call(&mut self, name: Name) -> Self::Future {
+    let family = self.family;
+    if let Some(DnsResolver(resolve)) = &self.custom {
+      let addrs = resolve(name.as_str());
+      if !addrs.is_empty() {
+        let result = family
+          .apply(name.as_str(), addrs)
+          .map(|addrs| addrs.into_iter());
+        return Box::pin(std::future::ready(result));
+      }
+    }
+    let host = name.as_str().to_string();
+    let resolving = self.system.call(name);
+    Box::pin(async move {
+      let addrs = resolving.await?.collect::<Vec<_>>();
+      Ok(family.apply(&host, addrs)?.into_iter())
+    })
+  }
+}
@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+mod dns;
 mod fs_fetch_handler;
 mod proxy;
 #[cfg(test)]
@@ -8,12 +9,16 @@ mod tests;
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cmp::min;
+use std::collections::HashMap;
 use std::convert::From;
 use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::rc::Rc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::task::Context;
 use std::task::Poll;
 
@@ -43,6 +48,7 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_permissions::NetByteMeter;
 use deno_tls::rustls::RootCertStore;
 use deno_tls::Proxy;
 use deno_tls::RootCertStoreProvider;
@@ -61,9 +67,12 @@ use http::header::CONTENT_LENGTH;
 use http::header::HOST;
 use http::header::PROXY_AUTHORIZATION;
 use http::header::RANGE;
+use http::header::RETRY_AFTER;
 use http::header::USER_AGENT;
 use http::Extensions;
+use http::HeaderMap;
 use http::Method;
+use http::StatusCode;
 use http::Uri;
 use http_body_util::BodyExt;
 use hyper::body::Frame;
@@ -81,6 +90,9 @@ use tower_http::decompression::Decompression;
 
 // Re-export data_url
 pub use data_url;
+pub use deno_tls::TlsVersion;
+pub use dns::AddressFamily;
+pub use dns::DnsResolver;
 pub use proxy::basic_auth;
 
 pub use fs_fetch_handler::FsFetchHandler;
@@ -96,6 +108,8 @@ pub struct Options {
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub client_cert_chain_and_key: TlsKeys,
   pub file_fetch_handler: Rc<dyn FetchHandler>,
+  /// See [`CreateHttpClientOptions::max_redirect_bytes`].
+  pub max_redirect_bytes: Option<u64>,
 }
 
 impl Options {
@@ -117,6 +131,7 @@ impl Default for Options {
       unsafely_ignore_certificate_errors: None,
       client_cert_chain_and_key: TlsKeys::Null,
       file_fetch_handler: Rc::new(DefaultFileFetchHandler),
+      max_redirect_bytes: None,
     }
   }
 }
@@ -224,10 +239,30 @@ pub fn create_client_from_options(
         .clone()
         .try_into()
         .unwrap_or_default(),
+      tls_client_certs_per_host: HashMap::new(),
+      client_certs_by_issuer: vec![],
       pool_max_idle_per_host: None,
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      low_latency_mode: false,
+      connect_retries: 0,
+      connect_retry_backoff: std::time::Duration::ZERO,
+      retry: None,
+      max_redirect_bytes: options.max_redirect_bytes,
+      max_response_body_bytes: None,
+      http2_initial_stream_window: None,
+      http2_initial_connection_window: None,
+      http2_prior_knowledge: false,
+      connect_timeout: None,
+      request_timeout: None,
+      dns_resolver: None,
+      force_new_connection_per_request: false,
+      address_family_preference: AddressFamily::Any,
+      tls_resumption: true,
+      min_tls_version: None,
+      max_tls_version: None,
+      trust_env_proxy: true,
     },
   )
 }
@@ -236,12 +271,19 @@ pub fn create_client_from_options(
 pub struct ResourceToBodyAdapter(
   Rc<dyn Resource>,
   Option<Pin<Box<dyn Future<Output = Result<BufView, Error>>>>>,
+  // Charges each chunk against the `--allow-net` byte cap of the host.
+  Option<NetByteMeter>,
 );
 
 impl ResourceToBodyAdapter {
   pub fn new(resource: Rc<dyn Resource>) -> Self {
     let future = resource.clone().read(64 * 1024);
-    Self(resource, Some(future))
+    Self(resource, Some(future), None)
+  }
+
+  pub fn with_egress_meter(mut self, meter: Option<NetByteMeter>) -> Self {
+    self.2 = meter;
+    self
   }
 }
 
@@ -267,6 +309,11 @@ impl Stream for ResourceToBodyAdapter {
         Poll::Ready(res) => match res {
           Ok(buf) if buf.is_empty() => Poll::Ready(None),
           Ok(buf) => {
+            if let Some(meter) = &this.2 {
+              if let Err(err) = meter.charge(buf.len() as u64, "fetch()") {
+                return Poll::Ready(Some(Err(err)));
+              }
+            }
             this.1 = Some(this.0.clone().read(64 * 1024));
             Poll::Ready(Some(Ok(buf.to_vec().into())))
           }
@@ -307,6 +354,16 @@ pub trait FetchPermissions {
     _url: &Url,
     api_name: &str,
   ) -> Result<(), AnyError>;
+  fn check_net_url_bytes(
+    &mut self,
+    _url: &Url,
+    _bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError>;
+  fn net_url_byte_meter(
+    &mut self,
+    _url: &Url,
+  ) -> Result<Option<NetByteMeter>, AnyError>;
   fn check_read(&mut self, _p: &Path, api_name: &str) -> Result<(), AnyError>;
 }
 
@@ -320,6 +377,26 @@ impl FetchPermissions for deno_permissions::PermissionsContainer {
     deno_permissions::PermissionsContainer::check_net_url(self, url, api_name)
   }
 
+  #[inline(always)]
+  fn check_net_url_bytes(
+    &mut self,
+    url: &Url,
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    deno_permissions::PermissionsContainer::check_net_url_bytes(
+      self, url, bytes, api_name,
+    )
+  }
+
+  #[inline(always)]
+  fn net_url_byte_meter(
+    &mut self,
+    url: &Url,
+  ) -> Result<Option<NetByteMeter>, AnyError> {
+    deno_permissions::PermissionsContainer::net_url_byte_meter(self, url)
+  }
+
   #[inline(always)]
   fn check_read(
     &mut self,
@@ -342,6 +419,7 @@ pub fn op_fetch<FP>(
   has_body: bool,
   #[buffer] data: Option<JsBuffer>,
   #[smi] resource: Option<ResourceId>,
+  #[smi] redirect_count: u32,
 ) -> Result<FetchReturn, AnyError>
 where
   FP: FetchPermissions + 'static,
@@ -389,6 +467,7 @@ where
     "http" | "https" => {
       let permissions = state.borrow_mut::<FP>();
       permissions.check_net_url(&url, "fetch()")?;
+      permissions.check_net_url_bytes(&url, 0, "fetch()")?;
 
       let maybe_authority = extract_authority(&mut url);
       let uri = url
@@ -402,6 +481,11 @@ where
           (Some(data), _) => {
             // If a body is passed, we use it, and don't return a body for streaming.
             con_len = Some(data.len() as u64);
+            state.borrow_mut::<FP>().check_net_url_bytes(
+              &url,
+              data.len() as u64,
+              "fetch()",
+            )?;
 
             http_body_util::Full::new(data.to_vec().into())
               .map_err(|never| match never {})
@@ -409,13 +493,22 @@ where
           }
           (_, Some(resource)) => {
             let resource = state.resource_table.take_any(resource)?;
-            match resource.size_hint() {
+            // a body of known length is charged in full up front, a
+            // streamed one chunk by chunk while it is sent
+            let egress_meter = match resource.size_hint() {
               (body_size, Some(n)) if body_size == n && body_size > 0 => {
                 con_len = Some(body_size);
+                state
+                  .borrow_mut::<FP>()
+                  .check_net_url_bytes(&url, body_size, "fetch()")?;
+                None
               }
-              _ => {}
-            }
-            ReqBody::new(ResourceToBodyAdapter::new(resource))
+              _ => state.borrow_mut::<FP>().net_url_byte_meter(&url)?,
+            };
+            ReqBody::new(
+              ResourceToBodyAdapter::new(resource)
+                .with_egress_meter(egress_meter),
+            )
           }
           (None, None) => unreachable!(),
         }
@@ -433,6 +526,11 @@ where
       let mut request = http::Request::new(body);
       *request.method_mut() = method.clone();
       *request.uri_mut() = uri.clone();
+      if redirect_count > 0 {
+        // the bodies of the redirect responses are never read, so the chain
+        // only needs to cover the response that was redirected to
+        request.extensions_mut().insert(RedirectChain::default());
+      }
 
       if let Some((username, password)) = maybe_authority {
         request.headers_mut().insert(
@@ -895,6 +993,8 @@ pub struct CreateHttpClientArgs {
   http2: bool,
   #[serde(default)]
   allow_host: bool,
+  #[serde(default)]
+  low_latency_mode: bool,
 }
 
 fn default_true() -> bool {
@@ -934,6 +1034,8 @@ where
         .unsafely_ignore_certificate_errors
         .clone(),
       client_cert_chain_and_key: tls_keys.take().try_into().unwrap(),
+      tls_client_certs_per_host: HashMap::new(),
+      client_certs_by_issuer: vec![],
       pool_max_idle_per_host: args.pool_max_idle_per_host,
       pool_idle_timeout: args.pool_idle_timeout.and_then(
         |timeout| match timeout {
@@ -947,6 +1049,24 @@ where
       ),
       http1: args.http1,
       http2: args.http2,
+      low_latency_mode: args.low_latency_mode,
+      connect_retries: 0,
+      connect_retry_backoff: std::time::Duration::ZERO,
+      retry: None,
+      max_redirect_bytes: options.max_redirect_bytes,
+      max_response_body_bytes: None,
+      http2_initial_stream_window: None,
+      http2_initial_connection_window: None,
+      http2_prior_knowledge: false,
+      connect_timeout: None,
+      request_timeout: None,
+      dns_resolver: None,
+      force_new_connection_per_request: false,
+      address_family_preference: AddressFamily::Any,
+      tls_resumption: true,
+      min_tls_version: None,
+      max_tls_version: None,
+      trust_env_proxy: true,
     },
   )?;
 
@@ -963,10 +1083,79 @@ pub struct CreateHttpClientOptions {
   pub proxy: Option<Proxy>,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub client_cert_chain_and_key: Option<TlsKey>,
+  /// Client certificates to present to specific hosts, keyed by host name.
+  /// Other hosts get `client_cert_chain_and_key`.
+  pub tls_client_certs_per_host: HashMap<String, TlsKey>,
+  /// Client certificates to choose from by the certificate authorities a
+  /// server accepts, as listed in its certificate request. The one whose
+  /// chain was issued by an accepted authority is presented, otherwise
+  /// `client_cert_chain_and_key`.
+  pub client_certs_by_issuer: Vec<TlsKey>,
   pub pool_max_idle_per_host: Option<usize>,
   pub pool_idle_timeout: Option<Option<u64>>,
   pub http1: bool,
   pub http2: bool,
+  /// Tunes the client for many small requests: sets `TCP_NODELAY`, sends the
+  /// head and body chunks of HTTP/1 requests with vectored writes and keeps
+  /// HTTP/2 connections warm so requests are multiplexed over them. Request
+  /// bodies are buffered the same as without it.
+  pub low_latency_mode: bool,
+  /// How many times to retry establishing a connection (TCP connect and TLS
+  /// handshake) that failed because the server refused or dropped it. The
+  /// request itself is never retried.
+  pub connect_retries: u32,
+  /// Delay before the first connection retry, doubled for every later one
+  /// up to 30 seconds.
+  pub connect_retry_backoff: std::time::Duration,
+  /// Retries requests with an idempotent method that failed to connect or
+  /// got one of the configured response statuses. Requests with other
+  /// methods are only retried when marked with [`RetryNonIdempotent`].
+  pub retry: Option<RetryConfig>,
+  /// How many body bytes may be read in total from the responses of a
+  /// redirect chain, made of the requests marked with [`RedirectChain`].
+  /// Reading more fails the body.
+  pub max_redirect_bytes: Option<u64>,
+  /// How many body bytes may be read from a single response. Responses
+  /// announcing a larger `Content-Length` fail before their body is read,
+  /// others once the limit is crossed.
+  pub max_response_body_bytes: Option<u64>,
+  /// Initial HTTP/2 flow control window of each stream, in bytes. Larger
+  /// windows help throughput on links with a high bandwidth-delay product.
+  pub http2_initial_stream_window: Option<u32>,
+  /// Initial HTTP/2 flow control window of the whole connection, in bytes.
+  pub http2_initial_connection_window: Option<u32>,
+  /// Speak HTTP/2 right away on cleartext connections instead of starting
+  /// with HTTP/1.1 (h2c with prior knowledge), and only offer `h2` over TLS.
+  /// Can't be combined with `http1`.
+  pub http2_prior_knowledge: bool,
+  /// How long establishing a connection, including the TLS handshake and
+  /// connecting through a proxy, may take.
+  pub connect_timeout: Option<std::time::Duration>,
+  /// How long to wait for the response headers of a request, counted from
+  /// sending it and including the time to connect.
+  pub request_timeout: Option<std::time::Duration>,
+  /// Resolves host names instead of the system resolver, for example to pin
+  /// a host to a fixed address.
+  pub dns_resolver: Option<DnsResolver>,
+  /// Opens a new connection for every request instead of reusing one from
+  /// the pool, for example to measure requests on a cold connection. Takes
+  /// precedence over `pool_max_idle_per_host`.
+  pub force_new_connection_per_request: bool,
+  /// Restricts or orders the IP versions connected over, for networks where
+  /// one of them is broken.
+  pub address_family_preference: AddressFamily,
+  /// Resume TLS sessions with the session tickets or IDs of earlier
+  /// connections to the same server, which saves round trips. Disabling it
+  /// keeps servers from linking the connections of a client together.
+  pub tls_resumption: bool,
+  /// Oldest TLS version the client negotiates, TLS 1.2 if not set.
+  pub min_tls_version: Option<TlsVersion>,
+  /// Newest TLS version the client negotiates, TLS 1.3 if not set.
+  pub max_tls_version: Option<TlsVersion>,
+  /// Route requests through the proxies configured with the `HTTP_PROXY`,
+  /// `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables. When
+  /// false, only `proxy` is used.
+  pub trust_env_proxy: bool,
 }
 
 impl Default for CreateHttpClientOptions {
@@ -977,10 +1166,30 @@ impl Default for CreateHttpClientOptions {
       proxy: None,
       unsafely_ignore_certificate_errors: None,
       client_cert_chain_and_key: None,
+      tls_client_certs_per_host: HashMap::new(),
+      client_certs_by_issuer: vec![],
       pool_max_idle_per_host: None,
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      low_latency_mode: false,
+      connect_retries: 0,
+      connect_retry_backoff: std::time::Duration::from_millis(100),
+      retry: None,
+      max_redirect_bytes: None,
+      max_response_body_bytes: None,
+      http2_initial_stream_window: None,
+      http2_initial_connection_window: None,
+      http2_prior_knowledge: false,
+      connect_timeout: None,
+      request_timeout: None,
+      dns_resolver: None,
+      force_new_connection_per_request: false,
+      address_family_preference: AddressFamily::Any,
+      tls_resumption: true,
+      min_tls_version: None,
+      max_tls_version: None,
+      trust_env_proxy: true,
     }
   }
 }
@@ -991,30 +1200,80 @@ pub fn create_http_client(
   user_agent: &str,
   options: CreateHttpClientOptions,
 ) -> Result<Client, AnyError> {
-  let mut tls_config = deno_tls::create_client_config(
-    options.root_cert_store,
-    options.ca_certs,
-    options.unsafely_ignore_certificate_errors,
+  let maybe_issuer_cert_resolver = if options.client_certs_by_issuer.is_empty()
+  {
+    None
+  } else {
+    Some(Arc::new(deno_tls::IssuerClientCertResolver::new(
+      options.client_certs_by_issuer,
+      options.client_cert_chain_and_key.clone(),
+    )?))
+  };
+  let tls_versions = deno_tls::tls_versions_in_range(
+    options.min_tls_version,
+    options.max_tls_version,
+  )
+  .map_err(|err| type_error(err.to_string()))?;
+  let mut tls_config = deno_tls::create_client_config_with_versions(
+    options.root_cert_store.clone(),
+    options.ca_certs.clone(),
+    options.unsafely_ignore_certificate_errors.clone(),
     options.client_cert_chain_and_key.into(),
     deno_tls::SocketUse::Http,
+    &tls_versions,
   )?;
+  if let Some(resolver) = maybe_issuer_cert_resolver {
+    tls_config.client_auth_cert_resolver = resolver;
+  }
+  if !options.tls_resumption {
+    tls_config.resumption = deno_tls::rustls::client::Resumption::disabled();
+  }
 
   // Proxy TLS should not send ALPN
   tls_config.alpn_protocols.clear();
   let proxy_tls_config = Arc::from(tls_config.clone());
 
-  let mut alpn_protocols = vec![];
-  if options.http2 {
+  if options.http2_prior_knowledge && options.http1 {
+    return Err(type_error(
+      "`http2_prior_knowledge` and `http1` can't both be true",
+    ));
+  }
+  let http2 = options.http2 || options.http2_prior_knowledge;
+
+  let mut alpn_protocols: Vec<Vec<u8>> = vec![];
+  if http2 {
     alpn_protocols.push("h2".into());
   }
   if options.http1 {
     alpn_protocols.push("http/1.1".into());
   }
-  tls_config.alpn_protocols = alpn_protocols;
+  tls_config.alpn_protocols = alpn_protocols.clone();
   let tls_config = Arc::from(tls_config);
 
-  let mut http_connector = HttpConnector::new();
-  http_connector.enforce_http(false);
+  let mut tls_configs_per_host =
+    HashMap::with_capacity(options.tls_client_certs_per_host.len());
+  for (host, client_cert) in options.tls_client_certs_per_host {
+    let mut host_tls_config = deno_tls::create_client_config_with_versions(
+      options.root_cert_store.clone(),
+      options.ca_certs.clone(),
+      options.unsafely_ignore_certificate_errors.clone(),
+      TlsKeys::Static(client_cert),
+      deno_tls::SocketUse::Http,
+      &tls_versions,
+    )?;
+    host_tls_config.alpn_protocols = alpn_protocols.clone();
+    if !options.tls_resumption {
+      host_tls_config.resumption =
+        deno_tls::rustls::client::Resumption::disabled();
+    }
+    tls_configs_per_host
+      .insert(host.to_ascii_lowercase(), Arc::from(host_tls_config));
+  }
+
+  let http_connector = create_http_connector(
+    dns::Resolver::new(options.dns_resolver, options.address_family_preference),
+    options.low_latency_mode,
+  );
 
   let user_agent = user_agent
     .parse::<HeaderValue>()
@@ -1025,7 +1284,11 @@ pub fn create_http_client(
   builder.timer(TokioTimer::new());
   builder.pool_timer(TokioTimer::new());
 
-  let mut proxies = proxy::from_env();
+  let mut proxies = if options.trust_env_proxy {
+    proxy::from_env()
+  } else {
+    proxy::Proxies::default()
+  };
   if let Some(proxy) = options.proxy {
     let mut intercept = proxy::Intercept::all(&proxy.url)
       .ok_or_else(|| type_error("invalid proxy url"))?;
@@ -1039,11 +1302,18 @@ pub fn create_http_client(
     http: http_connector,
     proxies: proxies.clone(),
     tls: tls_config,
+    tls_per_host: Arc::new(tls_configs_per_host),
     tls_proxy: proxy_tls_config,
     user_agent: Some(user_agent.clone()),
+    connect_retries: options.connect_retries,
+    connect_retry_backoff: options.connect_retry_backoff,
+    connect_timeout: options.connect_timeout,
   };
 
-  if let Some(pool_max_idle_per_host) = options.pool_max_idle_per_host {
+  if options.force_new_connection_per_request {
+    // connections that can't be kept idle are closed after their request
+    builder.pool_max_idle_per_host(0);
+  } else if let Some(pool_max_idle_per_host) = options.pool_max_idle_per_host {
     builder.pool_max_idle_per_host(pool_max_idle_per_host);
   }
 
@@ -1053,7 +1323,20 @@ pub fn create_http_client(
     );
   }
 
-  match (options.http1, options.http2) {
+  if options.low_latency_mode {
+    builder.http1_writev(true);
+    builder.http2_keep_alive_interval(Some(std::time::Duration::from_secs(30)));
+    builder.http2_keep_alive_while_idle(true);
+  }
+
+  if let Some(window) = options.http2_initial_stream_window {
+    builder.http2_initial_stream_window_size(window);
+  }
+  if let Some(window) = options.http2_initial_connection_window {
+    builder.http2_initial_connection_window_size(window);
+  }
+
+  match (options.http1, http2) {
     (true, false) => {} // noop, handled by ALPN above
     (false, true) => {
       builder.http2_only(true);
@@ -1071,6 +1354,10 @@ pub fn create_http_client(
     inner: decompress,
     proxies,
     user_agent,
+    retry: options.retry,
+    max_redirect_bytes: options.max_redirect_bytes,
+    max_response_body_bytes: options.max_response_body_bytes,
+    request_timeout: options.request_timeout,
   })
 }
 
@@ -1088,9 +1375,114 @@ pub struct Client {
   // Used to check whether to include a proxy-authorization header
   proxies: Arc<proxy::Proxies>,
   user_agent: HeaderValue,
+  retry: Option<RetryConfig>,
+  max_redirect_bytes: Option<u64>,
+  max_response_body_bytes: Option<u64>,
+  request_timeout: Option<std::time::Duration>,
 }
 
-type Connector = proxy::ProxyConnector<HttpConnector>;
+/// Request extension that marks a request as a hop of a redirect chain. The
+/// body bytes read from the responses of every request sharing the same
+/// chain count towards [`CreateHttpClientOptions::max_redirect_bytes`].
+#[derive(Clone, Debug, Default)]
+pub struct RedirectChain(Arc<AtomicU64>);
+
+/// How [`Client::send`] retries requests, see
+/// [`CreateHttpClientOptions::retry`].
+#[derive(Clone, Debug)]
+pub struct RetryConfig {
+  /// How many times a request is sent at most, including the first attempt.
+  pub max_attempts: u32,
+  /// Delay before the first retry, doubled for every later one. Up to half
+  /// of the delay is added at random so that clients which failed together
+  /// don't retry together. A `Retry-After` header with a number of seconds
+  /// takes precedence.
+  pub base_delay: std::time::Duration,
+  /// The longest delay between two attempts, which also bounds the delay a
+  /// server asks for with `Retry-After`.
+  pub max_delay: std::time::Duration,
+  /// The response statuses that are retried. Requests that failed to
+  /// connect are always retried.
+  pub retry_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryConfig {
+  fn default() -> Self {
+    Self {
+      max_attempts: 3,
+      base_delay: std::time::Duration::from_millis(100),
+      max_delay: std::time::Duration::from_secs(30),
+      retry_statuses: vec![
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+      ],
+    }
+  }
+}
+
+fn with_jitter(delay: std::time::Duration) -> std::time::Duration {
+  delay.saturating_add(delay.mul_f64(rand::random::<f64>() / 2.0))
+}
+
+/// Request extension that opts a request with a non-idempotent method, like
+/// `POST`, into the retries configured with
+/// [`CreateHttpClientOptions::retry`].
+#[derive(Clone, Copy, Debug)]
+pub struct RetryNonIdempotent;
+
+/// Response extension holding the trailers the server sent after the body.
+/// [`Client::send`] adds it to every response; the trailers are available
+/// once the body was read to the end.
+#[derive(Clone, Debug, Default)]
+pub struct ResponseTrailers(Arc<Mutex<Option<HeaderMap>>>);
+
+impl ResponseTrailers {
+  /// Returns the trailers of the response, or `None` if the body hasn't been
+  /// read to the end yet or the server didn't send any.
+  pub fn get(&self) -> Option<HeaderMap> {
+    self.0.lock().unwrap().clone()
+  }
+}
+
+/// Response extension describing the TLS session of the connection the
+/// response arrived on, for example to find out why HTTP/2 wasn't chosen.
+/// Responses received over cleartext connections don't have it.
+#[derive(Clone, Debug)]
+pub struct TlsInfo {
+  pub protocol_version: Option<deno_tls::rustls::ProtocolVersion>,
+  pub cipher_suite: Option<deno_tls::rustls::CipherSuite>,
+  /// The protocol agreed on with ALPN, like `h2` or `http/1.1`.
+  pub alpn_protocol: Option<Vec<u8>>,
+}
+
+impl TlsInfo {
+  pub(crate) fn new(conn: &deno_tls::rustls::ClientConnection) -> Self {
+    Self {
+      protocol_version: conn.protocol_version(),
+      cipher_suite: conn.negotiated_cipher_suite().map(|suite| suite.suite()),
+      alpn_protocol: conn.alpn_protocol().map(|protocol| protocol.to_vec()),
+    }
+  }
+}
+
+/// Creates the connector that opens the TCP connections of a client.
+fn create_http_connector(
+  resolver: dns::Resolver,
+  low_latency_mode: bool,
+) -> HttpConnector<dns::Resolver> {
+  let mut http_connector = HttpConnector::new_with_resolver(resolver);
+  http_connector.enforce_http(false);
+  if low_latency_mode {
+    // small writes, like the ones of RPC calls, go out without waiting
+    // for the acks of the previous ones
+    http_connector.set_nodelay(true);
+  }
+  http_connector
+}
+
+type Connector = proxy::ProxyConnector<HttpConnector<dns::Resolver>>;
 
 // clippy is wrong here
 #[allow(clippy::declare_interior_mutable_const)]
@@ -1099,25 +1491,120 @@ const STAR_STAR: HeaderValue = HeaderValue::from_static("*/*");
 #[derive(Debug)]
 pub struct ClientSendError {
   uri: Uri,
-  source: hyper_util::client::legacy::Error,
+  kind: ClientSendErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ClientSendErrorKind {
+  /// Sending the request failed, for example because the connection was
+  /// refused or reset.
+  Send(hyper_util::client::legacy::Error),
+  /// Establishing the connection took longer than
+  /// [`CreateHttpClientOptions::connect_timeout`].
+  ConnectTimeout(std::time::Duration),
+  /// The response headers didn't arrive within
+  /// [`CreateHttpClientOptions::request_timeout`].
+  RequestTimeout(std::time::Duration),
+  /// The TLS handshake failed because the server doesn't support any of the
+  /// versions between [`CreateHttpClientOptions::min_tls_version`] and
+  /// [`CreateHttpClientOptions::max_tls_version`].
+  TlsVersion(hyper_util::client::legacy::Error),
 }
 
 impl ClientSendError {
+  fn new(uri: Uri, source: hyper_util::client::legacy::Error) -> Self {
+    let mut err: Option<&(dyn std::error::Error + 'static)> = Some(&source);
+    while let Some(inner) = err {
+      if let Some(timeout) = inner.downcast_ref::<proxy::ConnectTimeout>() {
+        return Self {
+          uri,
+          kind: ClientSendErrorKind::ConnectTimeout(timeout.0),
+        };
+      }
+      // rustls errors are wrapped in the I/O error of the TLS stream
+      let tls_err =
+        inner.downcast_ref::<deno_tls::rustls::Error>().or_else(|| {
+          inner
+            .downcast_ref::<std::io::Error>()
+            .and_then(|err| err.get_ref())
+            .and_then(|err| err.downcast_ref::<deno_tls::rustls::Error>())
+        });
+      if tls_err.is_some_and(is_tls_version_error) {
+        return Self {
+          uri,
+          kind: ClientSendErrorKind::TlsVersion(source),
+        };
+      }
+      err = inner.source();
+    }
+    Self {
+      uri,
+      kind: ClientSendErrorKind::Send(source),
+    }
+  }
+
+  pub fn kind(&self) -> &ClientSendErrorKind {
+    &self.kind
+  }
+
   pub fn is_connect_error(&self) -> bool {
-    self.source.is_connect()
+    match &self.kind {
+      ClientSendErrorKind::Send(source) => source.is_connect(),
+      ClientSendErrorKind::ConnectTimeout(_)
+      | ClientSendErrorKind::TlsVersion(_) => true,
+      ClientSendErrorKind::RequestTimeout(_) => false,
+    }
+  }
+
+  pub fn is_timeout(&self) -> bool {
+    matches!(
+      self.kind,
+      ClientSendErrorKind::ConnectTimeout(_)
+        | ClientSendErrorKind::RequestTimeout(_)
+    )
   }
 
   fn http_info(&self) -> Option<HttpInfo> {
+    let (ClientSendErrorKind::Send(source)
+    | ClientSendErrorKind::TlsVersion(source)) = &self.kind
+    else {
+      return None;
+    };
     let mut exts = Extensions::new();
-    self.source.connect_info()?.get_extras(&mut exts);
+    source.connect_info()?.get_extras(&mut exts);
     exts.remove::<HttpInfo>()
   }
 }
 
 impl std::fmt::Display for ClientSendError {
   fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    let source = match &self.kind {
+      ClientSendErrorKind::Send(source) => source,
+      ClientSendErrorKind::TlsVersion(source) => {
+        return write!(
+          f,
+          "error sending request for url ({uri}): the server doesn't support any of the TLS versions the client allows: {detail}",
+          uri = self.uri,
+          detail = error_reporter::Report::new(source),
+        );
+      }
+      ClientSendErrorKind::ConnectTimeout(timeout) => {
+        return write!(
+          f,
+          "error sending request for url ({uri}): connection timed out after {timeout:?}",
+          uri = self.uri,
+        );
+      }
+      ClientSendErrorKind::RequestTimeout(timeout) => {
+        return write!(
+          f,
+          "error sending request for url ({uri}): no response within {timeout:?}",
+          uri = self.uri,
+        );
+      }
+    };
     // NOTE: we can use `std::error::Report` instead once it's stabilized.
-    let detail = error_reporter::Report::new(&self.source);
+    let detail = error_reporter::Report::new(source);
 
     match self.http_info() {
       Some(http_info) => {
@@ -1144,10 +1631,32 @@ impl std::fmt::Display for ClientSendError {
 
 impl std::error::Error for ClientSendError {
   fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-    Some(&self.source)
+    match &self.kind {
+      ClientSendErrorKind::Send(source)
+      | ClientSendErrorKind::TlsVersion(source) => Some(source),
+      ClientSendErrorKind::ConnectTimeout(_)
+      | ClientSendErrorKind::RequestTimeout(_) => None,
+    }
   }
 }
 
+/// Whether a TLS handshake failed because the client and the server have no
+/// protocol version in common.
+fn is_tls_version_error(err: &deno_tls::rustls::Error) -> bool {
+  use deno_tls::rustls::AlertDescription;
+  use deno_tls::rustls::Error;
+  use deno_tls::rustls::PeerIncompatible;
+  matches!(
+    err,
+    Error::AlertReceived(AlertDescription::ProtocolVersion)
+      | Error::PeerIncompatible(
+        PeerIncompatible::ServerTlsVersionIsDisabledByOurConfig
+          | PeerIncompatible::ServerDoesNotSupportTls12Or13
+          | PeerIncompatible::SupportedVersionsExtensionRequired
+      )
+  )
+}
+
 impl Client {
   pub async fn send(
     self,
@@ -1164,14 +1673,302 @@ impl Client {
       req.headers_mut().insert(PROXY_AUTHORIZATION, auth.clone());
     }
 
+    let is_retryable =
+      matches!(
+        *req.method(),
+        Method::GET
+          | Method::HEAD
+          | Method::PUT
+          | Method::DELETE
+          | Method::OPTIONS
+          | Method::TRACE
+      ) || req.extensions().get::<RetryNonIdempotent>().is_some();
+    let retry = match &self.retry {
+      Some(retry) if retry.max_attempts > 1 && is_retryable => retry.clone(),
+      _ => return self.send_once(req).await,
+    };
+
+    // Retrying means sending the body again, so it has to be buffered. Only
+    // bodies of a known size are buffered, streams are sent just once.
+    let (parts, body) = req.into_parts();
+    let body = if hyper::body::Body::is_end_stream(&body) {
+      Bytes::new()
+    } else if hyper::body::Body::size_hint(&body).exact().is_some() {
+      match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(err) => {
+          // surface the body error the same way a failed send would
+          let body =
+            http_body_util::StreamBody::new(deno_core::futures::stream::once(
+              async move { Err::<Frame<Bytes>, _>(err) },
+            ))
+            .boxed();
+          return self.send_once(http::Request::from_parts(parts, body)).await;
+        }
+      }
+    } else {
+      return self.send_once(http::Request::from_parts(parts, body)).await;
+    };
+
+    let mut backoff = retry.base_delay;
+    let mut attempt = 1;
+    loop {
+      let mut req = http::Request::new(
+        http_body_util::Full::new(body.clone())
+          .map_err(|never| match never {})
+          .boxed(),
+      );
+      *req.method_mut() = parts.method.clone();
+      *req.uri_mut() = parts.uri.clone();
+      *req.version_mut() = parts.version;
+      *req.headers_mut() = parts.headers.clone();
+      *req.extensions_mut() = parts.extensions.clone();
+
+      let can_retry = attempt < retry.max_attempts;
+      let delay = match self.send_once(req).await {
+        Ok(resp)
+          if can_retry && retry.retry_statuses.contains(&resp.status()) =>
+        {
+          resp
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(std::time::Duration::from_secs)
+            .unwrap_or_else(|| with_jitter(backoff))
+        }
+        Err(err) if can_retry && err.is_connect_error() => with_jitter(backoff),
+        result => return result,
+      };
+      tokio::time::sleep(delay.min(retry.max_delay)).await;
+      backoff = backoff.saturating_mul(2).min(retry.max_delay);
+      attempt += 1;
+    }
+  }
+
+  async fn send_once(
+    &self,
+    req: http::Request<ReqBody>,
+  ) -> Result<http::Response<ResBody>, ClientSendError> {
     let uri = req.uri().clone();
+    let maybe_redirect_chain = req.extensions().get::<RedirectChain>().cloned();
+
+    let send = self.inner.clone().oneshot(req);
+    let result = match self.request_timeout {
+      Some(timeout) => match tokio::time::timeout(timeout, send).await {
+        Ok(result) => result,
+        Err(_) => {
+          return Err(ClientSendError {
+            uri,
+            kind: ClientSendErrorKind::RequestTimeout(timeout),
+          })
+        }
+      },
+      None => send.await,
+    };
+    let resp = result.map_err(|e| ClientSendError::new(uri, e))?;
+    let trailers = ResponseTrailers::default();
+    let mut resp = resp.map(|b| {
+      TrailersBody {
+        inner: b.map_err(|e| anyhow!(e)).boxed(),
+        trailers: trailers.clone(),
+      }
+      .boxed()
+    });
+    resp.extensions_mut().insert(trailers);
+    let resp = match self.max_response_body_bytes {
+      Some(max_bytes) => {
+        let content_length = resp
+          .headers()
+          .get(CONTENT_LENGTH)
+          .and_then(|value| value.to_str().ok())
+          .and_then(|value| value.parse::<u64>().ok());
+        resp.map(|body| {
+          MaxSizeBody {
+            inner: body,
+            read: 0,
+            content_length,
+            max_bytes,
+          }
+          .boxed()
+        })
+      }
+      None => resp,
+    };
+    match (self.max_redirect_bytes, maybe_redirect_chain) {
+      (Some(max_bytes), Some(chain)) => Ok(resp.map(|body| {
+        RedirectChainBody {
+          inner: body,
+          chain,
+          max_bytes,
+        }
+        .boxed()
+      })),
+      _ => Ok(resp),
+    }
+  }
+}
+
+/// Response body that stores the trailers frame in [`ResponseTrailers`], so
+/// that they are still available to readers that only consume data frames.
+struct TrailersBody {
+  inner: ResBody,
+  trailers: ResponseTrailers,
+}
+
+impl hyper::body::Body for TrailersBody {
+  type Data = Bytes;
+  type Error = Error;
+
+  fn poll_frame(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Result<Frame<Bytes>, Error>>> {
+    let frame = std::task::ready!(hyper::body::Body::poll_frame(
+      Pin::new(&mut self.inner),
+      cx
+    ));
+    if let Some(trailers) = frame
+      .as_ref()
+      .and_then(|frame| frame.as_ref().ok())
+      .and_then(|frame| frame.trailers_ref())
+    {
+      let mut stored = self.trailers.0.lock().unwrap();
+      match &mut *stored {
+        Some(stored) => stored.extend(trailers.clone()),
+        None => *stored = Some(trailers.clone()),
+      }
+    }
+    Poll::Ready(frame)
+  }
+
+  fn is_end_stream(&self) -> bool {
+    hyper::body::Body::is_end_stream(&self.inner)
+  }
+
+  fn size_hint(&self) -> hyper::body::SizeHint {
+    hyper::body::Body::size_hint(&self.inner)
+  }
+}
+
+/// Response body that fails once the bodies read across its redirect chain
+/// exceed `max_bytes`.
+struct RedirectChainBody {
+  inner: ResBody,
+  chain: RedirectChain,
+  max_bytes: u64,
+}
+
+impl RedirectChainBody {
+  fn exceeds_limit(&self, read: u64) -> Option<Error> {
+    (read > self.max_bytes).then(|| {
+      type_error(format!(
+        "Response bodies of the redirect chain exceeded the limit of {} bytes",
+        self.max_bytes
+      ))
+    })
+  }
+}
+
+impl hyper::body::Body for RedirectChainBody {
+  type Data = Bytes;
+  type Error = Error;
+
+  fn poll_frame(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Result<Frame<Bytes>, Error>>> {
+    // fail early when the announced length already exceeds the limit
+    let announced = self.chain.0.load(Ordering::Relaxed)
+      + hyper::body::Body::size_hint(&self.inner).lower();
+    if let Some(err) = self.exceeds_limit(announced) {
+      return Poll::Ready(Some(Err(err)));
+    }
+    let frame = std::task::ready!(hyper::body::Body::poll_frame(
+      Pin::new(&mut self.inner),
+      cx
+    ));
+    if let Some(data) = frame
+      .as_ref()
+      .and_then(|frame| frame.as_ref().ok())
+      .and_then(|frame| frame.data_ref())
+    {
+      let len = data.len() as u64;
+      let read = self.chain.0.fetch_add(len, Ordering::Relaxed) + len;
+      if let Some(err) = self.exceeds_limit(read) {
+        return Poll::Ready(Some(Err(err)));
+      }
+    }
+    Poll::Ready(frame)
+  }
+
+  fn is_end_stream(&self) -> bool {
+    hyper::body::Body::is_end_stream(&self.inner)
+  }
+
+  fn size_hint(&self) -> hyper::body::SizeHint {
+    hyper::body::Body::size_hint(&self.inner)
+  }
+}
+
+/// Response body that fails once more than `max_bytes` were read from it.
+struct MaxSizeBody {
+  inner: ResBody,
+  read: u64,
+  content_length: Option<u64>,
+  max_bytes: u64,
+}
+
+impl MaxSizeBody {
+  fn exceeds_limit(&self, read: u64) -> Option<Error> {
+    (read > self.max_bytes).then(|| {
+      type_error(format!(
+        "Response body exceeded the limit of {} bytes",
+        self.max_bytes
+      ))
+    })
+  }
+}
+
+impl hyper::body::Body for MaxSizeBody {
+  type Data = Bytes;
+  type Error = Error;
+
+  fn poll_frame(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Result<Frame<Bytes>, Error>>> {
+    // fail without reading when the announced length exceeds the limit
+    let announced = self
+      .content_length
+      .unwrap_or(0)
+      .max(hyper::body::Body::size_hint(&self.inner).lower());
+    if let Some(err) = self.exceeds_limit(announced) {
+      return Poll::Ready(Some(Err(err)));
+    }
+    let frame = std::task::ready!(hyper::body::Body::poll_frame(
+      Pin::new(&mut self.inner),
+      cx
+    ));
+    if let Some(data) = frame
+      .as_ref()
+      .and_then(|frame| frame.as_ref().ok())
+      .and_then(|frame| frame.data_ref())
+    {
+      self.read += data.len() as u64;
+      if let Some(err) = self.exceeds_limit(self.read) {
+        return Poll::Ready(Some(Err(err)));
+      }
+    }
+    Poll::Ready(frame)
+  }
+
+  fn is_end_stream(&self) -> bool {
+    hyper::body::Body::is_end_stream(&self.inner)
+  }
 
-    let resp = self
-      .inner
-      .oneshot(req)
-      .await
-      .map_err(|e| ClientSendError { uri, source: e })?;
-    Ok(resp.map(|b| b.map_err(|e| anyhow!(e)).boxed()))
+  fn size_hint(&self) -> hyper::body::SizeHint {
+    hyper::body::Body::size_hint(&self.inner)
   }
 }
 
@@ -1,21 +1,41 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use bytes::Bytes;
+use deno_tls::rustls;
+use deno_tls::TlsKey;
 use fast_socks5::server::Config as Socks5Config;
 use fast_socks5::server::Socks5Socket;
 use http_body_util::BodyExt;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
+use tower::ServiceExt;
 
 use super::create_http_client;
+use super::create_http_connector;
+use super::dns;
+use super::AddressFamily;
+use super::ClientSendErrorKind;
 use super::CreateHttpClientOptions;
+use super::DnsResolver;
+use super::ResponseTrailers;
+use super::RetryConfig;
+use super::RetryNonIdempotent;
+use super::TlsInfo;
+use super::TlsVersion;
 
 static EXAMPLE_CRT: &[u8] = include_bytes!("../tls/testdata/example1_cert.der");
 static EXAMPLE_KEY: &[u8] =
   include_bytes!("../tls/testdata/example1_prikey.der");
+static EXAMPLE2_CRT: &[u8] =
+  include_bytes!("../tls/testdata/example2_cert.der");
+static EXAMPLE2_KEY: &[u8] =
+  include_bytes!("../tls/testdata/example2_prikey.der");
 
 #[tokio::test]
 async fn test_https_proxy_http11() {
@@ -52,6 +72,898 @@ async fn test_socks_proxy_h2() {
   run_test_client(prx_addr, src_addr, "socks5", http::Version::HTTP_2).await;
 }
 
+#[tokio::test]
+async fn test_low_latency_mode_reuses_connection() {
+  let connections = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_http_echo_server(connections.clone()).await;
+
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      low_latency_mode: true,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+
+  for i in 0..50u8 {
+    let req = http::Request::builder()
+      .method(http::Method::POST)
+      .uri(format!("http://{}/rpc", src_addr))
+      .body(
+        http_body_util::Full::new(Bytes::from(vec![i; 16]))
+          .map_err(|never| match never {})
+          .boxed(),
+      )
+      .unwrap();
+    let resp = client.send(req).await.unwrap();
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let body = resp.collect().await.unwrap().to_bytes();
+    assert_eq!(body, vec![i; 16]);
+  }
+
+  // every request went over the same connection
+  assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_low_latency_mode_sets_nodelay() {
+  let src_addr = create_http_echo_server(Default::default()).await;
+  let uri = format!("http://{}/", src_addr)
+    .parse::<http::Uri>()
+    .unwrap();
+
+  for low_latency_mode in [false, true] {
+    let connector = create_http_connector(
+      dns::Resolver::new(None, AddressFamily::default()),
+      low_latency_mode,
+    );
+    let io = connector.oneshot(uri.clone()).await.unwrap();
+    // the default client leaves Nagle's algorithm on
+    assert_eq!(io.inner().nodelay().unwrap(), low_latency_mode);
+  }
+}
+
+#[tokio::test]
+async fn test_force_new_connection_per_request() {
+  let connections = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_http_echo_server(connections.clone()).await;
+
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      force_new_connection_per_request: true,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+
+  for _ in 0..3 {
+    let req = http::Request::builder()
+      .uri(format!("http://{}/", src_addr))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap();
+    let resp = client.clone().send(req).await.unwrap();
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    resp.collect().await.unwrap();
+  }
+
+  assert_eq!(connections.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_tls_resumption() {
+  let resumptions = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_session_counting_server(resumptions.clone()).await;
+
+  for tls_resumption in [false, true] {
+    let client = create_http_client(
+      "fetch/test",
+      CreateHttpClientOptions {
+        unsafely_ignore_certificate_errors: Some(vec![]),
+        force_new_connection_per_request: true,
+        tls_resumption,
+        ..Default::default()
+      },
+    )
+    .unwrap();
+    for _ in 0..3 {
+      let req = http::Request::builder()
+        .uri(format!("https://localhost:{}/", src_addr.port()))
+        .body(
+          http_body_util::Empty::new()
+            .map_err(|err| match err {})
+            .boxed(),
+        )
+        .unwrap();
+      let resp = client.clone().send(req).await.unwrap();
+      assert_eq!(resp.status(), http::StatusCode::OK);
+      resp.collect().await.unwrap();
+    }
+    if tls_resumption {
+      assert!(resumptions.load(Ordering::SeqCst) > 0);
+    } else {
+      // every connection did a full handshake
+      assert_eq!(resumptions.load(Ordering::SeqCst), 0);
+    }
+  }
+}
+
+#[tokio::test]
+async fn test_tls_version_range() {
+  let src_addr = create_tls12_server().await;
+  let request = || {
+    http::Request::builder()
+      .uri(format!("https://localhost:{}/", src_addr.port()))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+  let client_with_versions =
+    |min_tls_version: Option<TlsVersion>,
+     max_tls_version: Option<TlsVersion>| {
+      create_http_client(
+        "fetch/test",
+        CreateHttpClientOptions {
+          unsafely_ignore_certificate_errors: Some(vec![]),
+          min_tls_version,
+          max_tls_version,
+          ..Default::default()
+        },
+      )
+    };
+
+  let client = client_with_versions(None, Some(TlsVersion::Tls12)).unwrap();
+  let resp = client.send(request()).await.unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+
+  let client = client_with_versions(Some(TlsVersion::Tls13), None).unwrap();
+  let err = client.send(request()).await.unwrap_err();
+  assert!(
+    matches!(err.kind(), ClientSendErrorKind::TlsVersion(_)),
+    "{err}"
+  );
+  assert!(err.to_string().contains("TLS versions the client allows"));
+
+  assert!(client_with_versions(
+    Some(TlsVersion::Tls13),
+    Some(TlsVersion::Tls12)
+  )
+  .is_err());
+}
+
+#[tokio::test]
+async fn test_tls_info() {
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      unsafely_ignore_certificate_errors: Some(vec![]),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let request = |uri: String| {
+    http::Request::builder()
+      .uri(uri)
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+
+  for (allow_h2, alpn_protocol) in
+    [(true, b"h2".as_slice()), (false, b"http/1.1".as_slice())]
+  {
+    let src_addr = create_https_server(allow_h2).await;
+    let resp = client
+      .clone()
+      .send(request(format!("https://localhost:{}/", src_addr.port())))
+      .await
+      .unwrap();
+    let tls_info = resp.extensions().get::<TlsInfo>().unwrap();
+    assert_eq!(tls_info.alpn_protocol.as_deref(), Some(alpn_protocol));
+    assert_eq!(
+      tls_info.protocol_version,
+      Some(rustls::ProtocolVersion::TLSv1_3)
+    );
+    assert!(tls_info.cipher_suite.is_some());
+  }
+
+  let connections = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_http_echo_server(connections).await;
+  let resp = client
+    .send(request(format!("http://{}/", src_addr)))
+    .await
+    .unwrap();
+  assert!(resp.extensions().get::<TlsInfo>().is_none());
+}
+
+#[tokio::test]
+async fn test_trust_env_proxy() {
+  let src_addr = create_https_server(false).await;
+  // nothing listens on the proxy's port, so proxied requests fail
+  let proxy_addr = tokio::net::TcpListener::bind("127.0.0.1:0")
+    .await
+    .unwrap()
+    .local_addr()
+    .unwrap();
+  let create_client = |trust_env_proxy| {
+    create_http_client(
+      "fetch/test",
+      CreateHttpClientOptions {
+        unsafely_ignore_certificate_errors: Some(vec![]),
+        trust_env_proxy,
+        ..Default::default()
+      },
+    )
+    .unwrap()
+  };
+  // the environment is only read when a client is created
+  std::env::set_var("HTTPS_PROXY", format!("http://{}", proxy_addr));
+  let direct_client = create_client(false);
+  let proxied_client = create_client(true);
+  std::env::remove_var("HTTPS_PROXY");
+
+  let request = || {
+    http::Request::builder()
+      .uri(format!("https://localhost:{}/", src_addr.port()))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+  let resp = direct_client.send(request()).await.unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  let err = proxied_client.send(request()).await.unwrap_err();
+  assert!(err.is_connect_error(), "{err}");
+}
+
+#[tokio::test]
+async fn test_dns_resolver() {
+  let connections = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_http_echo_server(connections.clone()).await;
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      dns_resolver: Some(DnsResolver::new(|host| match host {
+        "example.com" => vec![SocketAddr::from(([127, 0, 0, 1], 0))],
+        _ => vec![],
+      })),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+
+  // `example.com` is pinned to the local server, with the port of the URL,
+  // while `localhost` is left to the system resolver.
+  for host in ["example.com", "localhost"] {
+    let req = http::Request::builder()
+      .method(http::Method::POST)
+      .uri(format!("http://{}:{}/", host, src_addr.port()))
+      .body(
+        http_body_util::Full::new(Bytes::from_static(b"hello"))
+          .map_err(|never| match never {})
+          .boxed(),
+      )
+      .unwrap();
+    let resp = client.clone().send(req).await.unwrap();
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let body = resp.collect().await.unwrap().to_bytes();
+    assert_eq!(body, "hello");
+  }
+  assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_address_family_preference() {
+  let connections = Arc::new(AtomicUsize::new(0));
+  let Ok(v6_listener) = tokio::net::TcpListener::bind("[::1]:0").await else {
+    // no IPv6 loopback to test with
+    return;
+  };
+  let port = v6_listener.local_addr().unwrap().port();
+  serve_http_echo(v6_listener, connections.clone());
+  let resolve_to_both = || {
+    Some(DnsResolver::new(|_| {
+      vec![
+        SocketAddr::from(([127, 0, 0, 1], 0)),
+        SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], 0)),
+      ]
+    }))
+  };
+  let request = || {
+    http::Request::builder()
+      .uri(format!("http://dual-stack.test:{}/", port))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      dns_resolver: resolve_to_both(),
+      address_family_preference: AddressFamily::Ipv4Only,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let err = client.send(request()).await.unwrap_err();
+  assert!(err.is_connect_error(), "{err}");
+  assert_eq!(connections.load(Ordering::SeqCst), 0);
+
+  for family in [AddressFamily::Ipv6Only, AddressFamily::PreferIpv4] {
+    let client = create_http_client(
+      "fetch/test",
+      CreateHttpClientOptions {
+        dns_resolver: resolve_to_both(),
+        address_family_preference: family,
+        ..Default::default()
+      },
+    )
+    .unwrap();
+    let resp = client.send(request()).await.unwrap();
+    assert_eq!(resp.status(), http::StatusCode::OK);
+  }
+  assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_connect_retries_wait_for_server() {
+  // Reserve a port that nothing is listening on yet.
+  let src_addr = tokio::net::TcpListener::bind("127.0.0.1:0")
+    .await
+    .unwrap()
+    .local_addr()
+    .unwrap();
+  let request = || {
+    http::Request::builder()
+      .uri(format!("http://{}/", src_addr))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+
+  let client =
+    create_http_client("fetch/test", CreateHttpClientOptions::default())
+      .unwrap();
+  assert!(client.send(request()).await.is_err());
+
+  // The server comes up while the client is retrying.
+  let connections = Arc::new(AtomicUsize::new(0));
+  tokio::spawn({
+    let connections = connections.clone();
+    async move {
+      tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+      let src_tcp = tokio::net::TcpListener::bind(src_addr).await.unwrap();
+      serve_http_echo(src_tcp, connections);
+    }
+  });
+
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      connect_retries: 10,
+      connect_retry_backoff: std::time::Duration::from_millis(20),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let resp = client.send(request()).await.unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_request_retries_idempotent_methods() {
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      retry: Some(RetryConfig {
+        base_delay: std::time::Duration::from_millis(10),
+        ..Default::default()
+      }),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let request = |method: http::Method, src_addr: SocketAddr| {
+    http::Request::builder()
+      .method(method)
+      .uri(format!("http://{}/", src_addr))
+      .body(
+        http_body_util::Full::new(Bytes::from_static(b"payload"))
+          .map_err(|never| match never {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_flaky_server(requests.clone(), 1).await;
+  let resp = client
+    .clone()
+    .send(request(http::Method::GET, src_addr))
+    .await
+    .unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  assert_eq!(resp.collect().await.unwrap().to_bytes(), "payload");
+  assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+  // POST is not retried by default
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_flaky_server(requests.clone(), 1).await;
+  let resp = client
+    .clone()
+    .send(request(http::Method::POST, src_addr))
+    .await
+    .unwrap();
+  assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+  assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+  // unless the request opts in
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_flaky_server(requests.clone(), 1).await;
+  let mut req = request(http::Method::POST, src_addr);
+  req.extensions_mut().insert(RetryNonIdempotent);
+  let resp = client.send(req).await.unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_retry_config() {
+  let request = |src_addr: SocketAddr| {
+    http::Request::builder()
+      .uri(format!("http://{}/", src_addr))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+  let client = |retry: RetryConfig| {
+    create_http_client(
+      "fetch/test",
+      CreateHttpClientOptions {
+        retry: Some(retry),
+        ..Default::default()
+      },
+    )
+    .unwrap()
+  };
+
+  // two 503s, then a 200
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_flaky_server(requests.clone(), 2).await;
+  let resp = client(RetryConfig {
+    max_attempts: 3,
+    base_delay: std::time::Duration::from_millis(10),
+    ..Default::default()
+  })
+  .send(request(src_addr))
+  .await
+  .unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+  // the last response is returned once the attempts are used up
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_flaky_server(requests.clone(), 2).await;
+  let resp = client(RetryConfig {
+    max_attempts: 2,
+    base_delay: std::time::Duration::from_millis(10),
+    ..Default::default()
+  })
+  .send(request(src_addr))
+  .await
+  .unwrap();
+  assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+  assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+  // statuses that aren't configured aren't retried
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_flaky_server(requests.clone(), 2).await;
+  let resp = client(RetryConfig {
+    max_attempts: 3,
+    base_delay: std::time::Duration::from_millis(10),
+    retry_statuses: vec![http::StatusCode::TOO_MANY_REQUESTS],
+    ..Default::default()
+  })
+  .send(request(src_addr))
+  .await
+  .unwrap();
+  assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+  assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+  // a long Retry-After is capped at the max delay
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr =
+    create_flaky_server_with_retry_after(requests.clone(), 1, "3600").await;
+  let resp = tokio::time::timeout(
+    std::time::Duration::from_secs(10),
+    client(RetryConfig {
+      max_attempts: 2,
+      max_delay: std::time::Duration::from_millis(10),
+      ..Default::default()
+    })
+    .send(request(src_addr)),
+  )
+  .await
+  .expect("Retry-After should be capped")
+  .unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+  // doubling a huge backoff doesn't overflow
+  let requests = Arc::new(AtomicUsize::new(0));
+  let src_addr = create_flaky_server(requests.clone(), 3).await;
+  let resp = client(RetryConfig {
+    max_attempts: u32::MAX,
+    base_delay: std::time::Duration::MAX,
+    max_delay: std::time::Duration::from_millis(10),
+    ..Default::default()
+  })
+  .send(request(src_addr))
+  .await
+  .unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  assert_eq!(requests.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn test_tls_client_certs_per_host() {
+  let example_key = |cert: &'static [u8], key: &'static [u8]| {
+    TlsKey(
+      vec![cert.into()],
+      webpki::types::PrivateKeyDer::try_from(key).unwrap(),
+    )
+  };
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      unsafely_ignore_certificate_errors: Some(vec![]),
+      client_cert_chain_and_key: Some(example_key(EXAMPLE_CRT, EXAMPLE_KEY)),
+      tls_client_certs_per_host: HashMap::from([(
+        "LOCALHOST".to_string(),
+        example_key(EXAMPLE2_CRT, EXAMPLE2_KEY),
+      )]),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let request = |uri: String| {
+    http::Request::builder()
+      .uri(uri)
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+
+  let a_addr = create_client_auth_server(vec![]).await;
+  let b_addr = create_client_auth_server(vec![]).await;
+  let resp = client
+    .clone()
+    .send(request(format!("https://localhost:{}/", a_addr.port())))
+    .await
+    .unwrap();
+  assert_eq!(resp.collect().await.unwrap().to_bytes(), "example2");
+  // hosts without an entry get the default certificate
+  let resp = client
+    .send(request(format!("https://{}/", b_addr)))
+    .await
+    .unwrap();
+  assert_eq!(resp.collect().await.unwrap().to_bytes(), "example1");
+}
+
+#[tokio::test]
+async fn test_client_certs_by_issuer() {
+  let example_key = |cert: &'static [u8], key: &'static [u8]| {
+    TlsKey(
+      vec![cert.into()],
+      webpki::types::PrivateKeyDer::try_from(key).unwrap(),
+    )
+  };
+  let issuer_of = |cert: &'static [u8]| {
+    let cert = webpki::types::CertificateDer::from(cert);
+    let cert = webpki::EndEntityCert::try_from(&cert).unwrap();
+    rustls::DistinguishedName::in_sequence(cert.issuer())
+  };
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      unsafely_ignore_certificate_errors: Some(vec![]),
+      client_certs_by_issuer: vec![
+        example_key(EXAMPLE_CRT, EXAMPLE_KEY),
+        example_key(EXAMPLE2_CRT, EXAMPLE2_KEY),
+      ],
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let request = |addr: SocketAddr| {
+    http::Request::builder()
+      .uri(format!("https://{}/", addr))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+
+  let example1_addr =
+    create_client_auth_server(vec![issuer_of(EXAMPLE_CRT)]).await;
+  let example2_addr =
+    create_client_auth_server(vec![issuer_of(EXAMPLE2_CRT)]).await;
+  let resp = client.clone().send(request(example2_addr)).await.unwrap();
+  assert_eq!(resp.collect().await.unwrap().to_bytes(), "example2");
+  let resp = client.send(request(example1_addr)).await.unwrap();
+  assert_eq!(resp.collect().await.unwrap().to_bytes(), "example1");
+}
+
+#[tokio::test]
+async fn test_max_response_body_bytes() {
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      max_response_body_bytes: Some(4096),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let request = |uri: String, body: Bytes| {
+    http::Request::builder()
+      .method(http::Method::POST)
+      .uri(uri)
+      .body(
+        http_body_util::Full::new(body)
+          .map_err(|never| match never {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+
+  // chunked responses are counted while streaming
+  let src_addr = create_chunked_server().await;
+  let resp = client
+    .clone()
+    .send(request(format!("http://{}/4096", src_addr), Bytes::new()))
+    .await
+    .unwrap();
+  assert_eq!(resp.collect().await.unwrap().to_bytes().len(), 4096);
+  let resp = client
+    .clone()
+    .send(request(format!("http://{}/4097", src_addr), Bytes::new()))
+    .await
+    .unwrap();
+  assert!(resp.headers().get(http::header::CONTENT_LENGTH).is_none());
+  let err = resp.collect().await.unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "Response body exceeded the limit of 4096 bytes"
+  );
+
+  // responses with a content length fail upfront
+  let src_addr = create_http_echo_server(Arc::new(AtomicUsize::new(0))).await;
+  let resp = client
+    .clone()
+    .send(request(
+      format!("http://{}/", src_addr),
+      Bytes::from(vec![b'a'; 16]),
+    ))
+    .await
+    .unwrap();
+  assert_eq!(resp.collect().await.unwrap().to_bytes().len(), 16);
+  let mut resp = client
+    .send(request(
+      format!("http://{}/", src_addr),
+      Bytes::from(vec![b'a'; 4097]),
+    ))
+    .await
+    .unwrap();
+  let err = resp.frame().await.unwrap().unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "Response body exceeded the limit of 4096 bytes"
+  );
+}
+
+#[tokio::test]
+async fn test_http2_initial_window_sizes() {
+  let (src_addr, windows_rx) = create_h2_settings_server().await;
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      http1: false,
+      http2: true,
+      http2_initial_stream_window: Some(4 * 1024 * 1024),
+      http2_initial_connection_window: Some(8 * 1024 * 1024),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let req = http::Request::builder()
+    .uri(format!("http://{}/", src_addr))
+    .body(
+      http_body_util::Empty::new()
+        .map_err(|err| match err {})
+        .boxed(),
+    )
+    .unwrap();
+  // the server never answers, it only reads the client's settings
+  let request_task = tokio::spawn(client.send(req));
+
+  let (stream_window, connection_window) = windows_rx.await.unwrap();
+  request_task.abort();
+  assert_eq!(stream_window, 4 * 1024 * 1024);
+  assert_eq!(connection_window, 8 * 1024 * 1024);
+}
+
+#[tokio::test]
+async fn test_http2_prior_knowledge() {
+  let src_addr = create_h2c_server().await;
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      http1: false,
+      http2: false,
+      http2_prior_knowledge: true,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let req = http::Request::builder()
+    .uri(format!("http://{}/foo", src_addr))
+    .body(
+      http_body_util::Empty::new()
+        .map_err(|err| match err {})
+        .boxed(),
+    )
+    .unwrap();
+  let resp = client.send(req).await.unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  assert_eq!(resp.version(), http::Version::HTTP_2);
+  let hello = resp.collect().await.unwrap().to_bytes();
+  assert_eq!(hello, "hello from server");
+
+  let err = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      http1: true,
+      http2_prior_knowledge: true,
+      ..Default::default()
+    },
+  )
+  .unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "`http2_prior_knowledge` and `http1` can't both be true"
+  );
+}
+
+#[tokio::test]
+async fn test_response_trailers() {
+  let src_addr = create_h2_trailers_server().await;
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      http1: false,
+      http2: true,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let req = http::Request::builder()
+    .uri(format!("http://{}/", src_addr))
+    .body(
+      http_body_util::Empty::new()
+        .map_err(|err| match err {})
+        .boxed(),
+    )
+    .unwrap();
+  let resp = client.send(req).await.unwrap();
+  assert_eq!(resp.version(), http::Version::HTTP_2);
+  let trailers = resp
+    .extensions()
+    .get::<ResponseTrailers>()
+    .cloned()
+    .unwrap();
+  assert!(trailers.get().is_none());
+
+  // reading only the data still records the trailers
+  let body = resp.into_body().into_data_stream();
+  let chunks = deno_core::futures::TryStreamExt::try_collect::<Vec<_>>(body)
+    .await
+    .unwrap();
+  assert_eq!(chunks.concat(), b"hello");
+  let trailers = trailers.get().unwrap();
+  assert_eq!(trailers.get("grpc-status").unwrap(), "0");
+}
+
+#[tokio::test]
+async fn test_connect_and_request_timeouts() {
+  let src_addr = create_silent_server().await;
+  let request = |scheme: &str| {
+    http::Request::builder()
+      .uri(format!("{}://{}/", scheme, src_addr))
+      .body(
+        http_body_util::Empty::new()
+          .map_err(|err| match err {})
+          .boxed(),
+      )
+      .unwrap()
+  };
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      connect_timeout: Some(std::time::Duration::from_millis(100)),
+      request_timeout: Some(std::time::Duration::from_millis(500)),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+
+  // The TLS handshake never completes, so the connection isn't established.
+  let err = client.clone().send(request("https")).await.unwrap_err();
+  assert!(
+    matches!(err.kind(), ClientSendErrorKind::ConnectTimeout(_)),
+    "{err}"
+  );
+  assert!(err.is_timeout());
+  assert!(err.is_connect_error());
+
+  // The TCP connection is all there is to a cleartext connection, then the
+  // response headers never arrive.
+  let err = client.clone().send(request("http")).await.unwrap_err();
+  assert!(
+    matches!(err.kind(), ClientSendErrorKind::RequestTimeout(_)),
+    "{err}"
+  );
+  assert!(err.is_timeout());
+  assert!(!err.is_connect_error());
+
+  // A refused connection is neither.
+  let closed_addr = tokio::net::TcpListener::bind("127.0.0.1:0")
+    .await
+    .unwrap()
+    .local_addr()
+    .unwrap();
+  let req = http::Request::builder()
+    .uri(format!("http://{}/", closed_addr))
+    .body(
+      http_body_util::Empty::new()
+        .map_err(|err| match err {})
+        .boxed(),
+    )
+    .unwrap();
+  let err = client.send(req).await.unwrap_err();
+  assert!(matches!(err.kind(), ClientSendErrorKind::Send(_)), "{err}");
+  assert!(!err.is_timeout());
+  assert!(err.is_connect_error());
+}
+
 async fn run_test_client(
   prx_addr: SocketAddr,
   src_addr: SocketAddr,
@@ -69,10 +981,30 @@ async fn run_test_client(
       }),
       unsafely_ignore_certificate_errors: Some(vec![]),
       client_cert_chain_and_key: None,
+      tls_client_certs_per_host: HashMap::new(),
+      client_certs_by_issuer: vec![],
       pool_max_idle_per_host: None,
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      low_latency_mode: false,
+      connect_retries: 0,
+      connect_retry_backoff: std::time::Duration::ZERO,
+      retry: None,
+      max_redirect_bytes: None,
+      max_response_body_bytes: None,
+      http2_initial_stream_window: None,
+      http2_initial_connection_window: None,
+      http2_prior_knowledge: false,
+      connect_timeout: None,
+      request_timeout: None,
+      dns_resolver: None,
+      force_new_connection_per_request: false,
+      address_family_preference: AddressFamily::Any,
+      tls_resumption: true,
+      min_tls_version: None,
+      max_tls_version: None,
+      trust_env_proxy: true,
     },
   )
   .unwrap();
@@ -141,6 +1073,463 @@ async fn create_https_server(allow_h2: bool) -> SocketAddr {
   src_addr
 }
 
+/// Creates an HTTPS server that only speaks TLS 1.2.
+async fn create_tls12_server() -> SocketAddr {
+  let tls_config =
+    rustls::server::ServerConfig::builder_with_protocol_versions(&[
+      &rustls::version::TLS12,
+    ])
+    .with_no_client_auth()
+    .with_single_cert(
+      vec![EXAMPLE_CRT.into()],
+      webpki::types::PrivateKeyDer::try_from(EXAMPLE_KEY).unwrap(),
+    )
+    .unwrap();
+  let tls_acceptor = tokio_rustls::TlsAcceptor::from(Arc::from(tls_config));
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = src_tcp.accept().await {
+      let Ok(conn) = tls_acceptor.accept(sock).await else {
+        continue;
+      };
+      let fut = hyper::server::conn::http1::Builder::new().serve_connection(
+        hyper_util::rt::TokioIo::new(conn),
+        hyper::service::service_fn(|_req| async {
+          Ok::<_, std::convert::Infallible>(http::Response::new(
+            http_body_util::Full::<Bytes>::new("hello from server".into()),
+          ))
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+
+  src_addr
+}
+
+/// Server session store that counts the sessions clients resumed.
+#[derive(Debug)]
+struct CountingSessionStore {
+  inner: Arc<dyn rustls::server::StoresServerSessions>,
+  resumptions: Arc<AtomicUsize>,
+}
+
+impl CountingSessionStore {
+  fn count(&self, value: Option<Vec<u8>>) -> Option<Vec<u8>> {
+    if value.is_some() {
+      self.resumptions.fetch_add(1, Ordering::SeqCst);
+    }
+    value
+  }
+}
+
+impl rustls::server::StoresServerSessions for CountingSessionStore {
+  fn put(&self, key: Vec<u8>, value: Vec<u8>) -> bool {
+    self.inner.put(key, value)
+  }
+
+  fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+    self.count(self.inner.get(key))
+  }
+
+  fn take(&self, key: &[u8]) -> Option<Vec<u8>> {
+    self.count(self.inner.take(key))
+  }
+
+  fn can_cache(&self) -> bool {
+    self.inner.can_cache()
+  }
+}
+
+/// Creates an HTTPS server that issues session tickets and counts how many
+/// connections resumed a session in `resumptions`.
+async fn create_session_counting_server(
+  resumptions: Arc<AtomicUsize>,
+) -> SocketAddr {
+  let mut tls_config = rustls::server::ServerConfig::builder()
+    .with_no_client_auth()
+    .with_single_cert(
+      vec![EXAMPLE_CRT.into()],
+      webpki::types::PrivateKeyDer::try_from(EXAMPLE_KEY).unwrap(),
+    )
+    .unwrap();
+  tls_config.session_storage = Arc::new(CountingSessionStore {
+    inner: rustls::server::ServerSessionMemoryCache::new(32),
+    resumptions,
+  });
+  tls_config.alpn_protocols.push("http/1.1".into());
+  let tls_acceptor = tokio_rustls::TlsAcceptor::from(Arc::from(tls_config));
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = src_tcp.accept().await {
+      let Ok(conn) = tls_acceptor.accept(sock).await else {
+        continue;
+      };
+      let fut = hyper::server::conn::http1::Builder::new().serve_connection(
+        hyper_util::rt::TokioIo::new(conn),
+        hyper::service::service_fn(|_req| async {
+          Ok::<_, std::convert::Infallible>(http::Response::new(
+            http_body_util::Full::<Bytes>::new("hello from server".into()),
+          ))
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+
+  src_addr
+}
+
+/// Accepts any client certificate, so that tests can tell which one the
+/// client presented.
+#[derive(Debug)]
+struct AcceptAnyClientCert(
+  rustls::crypto::CryptoProvider,
+  Vec<rustls::DistinguishedName>,
+);
+
+impl rustls::server::danger::ClientCertVerifier for AcceptAnyClientCert {
+  fn root_hint_subjects(&self) -> &[rustls::DistinguishedName] {
+    &self.1
+  }
+
+  fn verify_client_cert(
+    &self,
+    _end_entity: &webpki::types::CertificateDer<'_>,
+    _intermediates: &[webpki::types::CertificateDer<'_>],
+    _now: webpki::types::UnixTime,
+  ) -> Result<rustls::server::danger::ClientCertVerified, rustls::Error> {
+    Ok(rustls::server::danger::ClientCertVerified::assertion())
+  }
+
+  fn verify_tls12_signature(
+    &self,
+    message: &[u8],
+    cert: &webpki::types::CertificateDer<'_>,
+    dss: &rustls::DigitallySignedStruct,
+  ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error>
+  {
+    rustls::crypto::verify_tls12_signature(
+      message,
+      cert,
+      dss,
+      &self.0.signature_verification_algorithms,
+    )
+  }
+
+  fn verify_tls13_signature(
+    &self,
+    message: &[u8],
+    cert: &webpki::types::CertificateDer<'_>,
+    dss: &rustls::DigitallySignedStruct,
+  ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error>
+  {
+    rustls::crypto::verify_tls13_signature(
+      message,
+      cert,
+      dss,
+      &self.0.signature_verification_algorithms,
+    )
+  }
+
+  fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+    self.0.signature_verification_algorithms.supported_schemes()
+  }
+}
+
+/// Creates an HTTPS server that requires a client certificate and responds
+/// with the name of the example certificate the client presented. The
+/// server's certificate request lists `accepted_issuers`.
+async fn create_client_auth_server(
+  accepted_issuers: Vec<rustls::DistinguishedName>,
+) -> SocketAddr {
+  let mut tls_config = rustls::server::ServerConfig::builder()
+    .with_client_cert_verifier(Arc::new(AcceptAnyClientCert(
+      rustls::crypto::ring::default_provider(),
+      accepted_issuers,
+    )))
+    .with_single_cert(
+      vec![EXAMPLE_CRT.into()],
+      webpki::types::PrivateKeyDer::try_from(EXAMPLE_KEY).unwrap(),
+    )
+    .unwrap();
+  tls_config.alpn_protocols.push("http/1.1".into());
+  let tls_acceptor = tokio_rustls::TlsAcceptor::from(Arc::from(tls_config));
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = src_tcp.accept().await {
+      let Ok(conn) = tls_acceptor.accept(sock).await else {
+        continue;
+      };
+      let client_cert = match conn.get_ref().1.peer_certificates() {
+        Some([cert, ..]) if cert.as_ref() == EXAMPLE_CRT => "example1",
+        Some([cert, ..]) if cert.as_ref() == EXAMPLE2_CRT => "example2",
+        _ => "unknown",
+      };
+      let fut = hyper::server::conn::http1::Builder::new().serve_connection(
+        hyper_util::rt::TokioIo::new(conn),
+        hyper::service::service_fn(move |_req| async move {
+          Ok::<_, std::convert::Infallible>(http::Response::new(
+            http_body_util::Full::<Bytes>::new(client_cert.into()),
+          ))
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+
+  src_addr
+}
+
+async fn create_http_echo_server(connections: Arc<AtomicUsize>) -> SocketAddr {
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+  serve_http_echo(src_tcp, connections);
+  src_addr
+}
+
+fn serve_http_echo(
+  src_tcp: tokio::net::TcpListener,
+  connections: Arc<AtomicUsize>,
+) {
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = src_tcp.accept().await {
+      connections.fetch_add(1, Ordering::SeqCst);
+      let fut = hyper::server::conn::http1::Builder::new().serve_connection(
+        hyper_util::rt::TokioIo::new(sock),
+        hyper::service::service_fn(|req: http::Request<_>| async {
+          let body = req.into_body().collect().await?.to_bytes();
+          Ok::<_, hyper::Error>(http::Response::new(http_body_util::Full::<
+            Bytes,
+          >::new(body)))
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+}
+
+/// Responds to `/<n>` with `n` bytes, sent in chunks of 1024 bytes without
+/// a content length.
+async fn create_chunked_server() -> SocketAddr {
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = src_tcp.accept().await {
+      let fut = hyper::server::conn::http1::Builder::new().serve_connection(
+        hyper_util::rt::TokioIo::new(sock),
+        hyper::service::service_fn(|req: http::Request<_>| async move {
+          let len = req.uri().path()[1..].parse::<usize>().unwrap();
+          let chunks = vec![b'a'; len]
+            .chunks(1024)
+            .map(|chunk| {
+              Ok::<_, hyper::Error>(hyper::body::Frame::data(
+                Bytes::copy_from_slice(chunk),
+              ))
+            })
+            .collect::<Vec<_>>();
+          Ok::<_, hyper::Error>(http::Response::new(
+            http_body_util::StreamBody::new(deno_core::futures::stream::iter(
+              chunks,
+            )),
+          ))
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+
+  src_addr
+}
+
+/// Reads the HTTP/2 connection preface of the first connection and reports
+/// the initial stream window from the client's SETTINGS frame and the
+/// connection window, which starts at 65535 bytes and is grown by the
+/// client's WINDOW_UPDATE frame for stream 0.
+async fn create_h2_settings_server(
+) -> (SocketAddr, tokio::sync::oneshot::Receiver<(u32, u32)>) {
+  const FRAME_TYPE_SETTINGS: u8 = 0x4;
+  const FRAME_TYPE_WINDOW_UPDATE: u8 = 0x8;
+  const SETTINGS_INITIAL_WINDOW_SIZE: u16 = 0x4;
+  const DEFAULT_WINDOW_SIZE: u32 = 65_535;
+
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+  let (tx, rx) = tokio::sync::oneshot::channel();
+
+  tokio::spawn(async move {
+    let (mut sock, _) = src_tcp.accept().await.unwrap();
+    let mut preface = [0; 24];
+    sock.read_exact(&mut preface).await.unwrap();
+    assert_eq!(&preface, b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n");
+
+    let mut stream_window = None;
+    let mut connection_window = None;
+    while stream_window.is_none() || connection_window.is_none() {
+      let mut header = [0; 9];
+      sock.read_exact(&mut header).await.unwrap();
+      let len = u32::from_be_bytes([0, header[0], header[1], header[2]]);
+      let frame_type = header[3];
+      let stream_id =
+        u32::from_be_bytes([header[5], header[6], header[7], header[8]]);
+      let mut payload = vec![0; len as usize];
+      sock.read_exact(&mut payload).await.unwrap();
+      match frame_type {
+        FRAME_TYPE_SETTINGS => {
+          for setting in payload.chunks_exact(6) {
+            let id = u16::from_be_bytes([setting[0], setting[1]]);
+            if id == SETTINGS_INITIAL_WINDOW_SIZE {
+              stream_window = Some(u32::from_be_bytes([
+                setting[2], setting[3], setting[4], setting[5],
+              ]));
+            }
+          }
+        }
+        FRAME_TYPE_WINDOW_UPDATE if stream_id == 0 => {
+          let increment = u32::from_be_bytes([
+            payload[0] & 0x7f,
+            payload[1],
+            payload[2],
+            payload[3],
+          ]);
+          connection_window = Some(DEFAULT_WINDOW_SIZE + increment);
+        }
+        _ => {}
+      }
+    }
+    tx.send((stream_window.unwrap(), connection_window.unwrap()))
+      .unwrap();
+  });
+
+  (src_addr, rx)
+}
+
+/// Answers the first request with a 503 and echoes the body of every later
+/// one.
+async fn create_h2c_server() -> SocketAddr {
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = listener.accept().await {
+      let fut = hyper::server::conn::http2::Builder::new(
+        hyper_util::rt::TokioExecutor::new(),
+      )
+      .serve_connection(
+        hyper_util::rt::TokioIo::new(sock),
+        hyper::service::service_fn(|_req| async {
+          Ok::<_, std::convert::Infallible>(http::Response::new(
+            http_body_util::Full::<Bytes>::new("hello from server".into()),
+          ))
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+  addr
+}
+
+async fn create_h2_trailers_server() -> SocketAddr {
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = listener.accept().await {
+      let fut = hyper::server::conn::http2::Builder::new(
+        hyper_util::rt::TokioExecutor::new(),
+      )
+      .serve_connection(
+        hyper_util::rt::TokioIo::new(sock),
+        hyper::service::service_fn(|_req| async {
+          let mut trailers = http::HeaderMap::new();
+          trailers.insert("grpc-status", "0".parse().unwrap());
+          let frames = vec![
+            Ok::<_, std::convert::Infallible>(hyper::body::Frame::data(
+              Bytes::from_static(b"hello"),
+            )),
+            Ok(hyper::body::Frame::trailers(trailers)),
+          ];
+          Ok::<_, std::convert::Infallible>(http::Response::new(
+            http_body_util::StreamBody::new(deno_core::futures::stream::iter(
+              frames,
+            )),
+          ))
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+  addr
+}
+
+/// Responds with a 503 to the first `failures` requests, and echoes the
+/// request body after that.
+async fn create_flaky_server(
+  requests: Arc<AtomicUsize>,
+  failures: usize,
+) -> SocketAddr {
+  create_flaky_server_with_retry_after(requests, failures, "0").await
+}
+
+/// Like [`create_flaky_server`], with the given `Retry-After` header on the
+/// failed responses.
+async fn create_flaky_server_with_retry_after(
+  requests: Arc<AtomicUsize>,
+  failures: usize,
+  retry_after: &'static str,
+) -> SocketAddr {
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+
+  tokio::spawn(async move {
+    while let Ok((sock, _)) = src_tcp.accept().await {
+      let requests = requests.clone();
+      let fut = hyper::server::conn::http1::Builder::new().serve_connection(
+        hyper_util::rt::TokioIo::new(sock),
+        hyper::service::service_fn(move |req: http::Request<_>| {
+          let requests = requests.clone();
+          async move {
+            let body = req.into_body().collect().await?.to_bytes();
+            let mut resp =
+              http::Response::new(http_body_util::Full::<Bytes>::new(body));
+            if requests.fetch_add(1, Ordering::SeqCst) < failures {
+              *resp.status_mut() = http::StatusCode::SERVICE_UNAVAILABLE;
+              resp.headers_mut().insert(
+                http::header::RETRY_AFTER,
+                retry_after.parse().unwrap(),
+              );
+            }
+            Ok::<_, hyper::Error>(resp)
+          }
+        }),
+      );
+      tokio::spawn(fut);
+    }
+  });
+
+  src_addr
+}
+
+/// Accepts TCP connections but never reads from or writes to them.
+async fn create_silent_server() -> SocketAddr {
+  let src_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let src_addr = src_tcp.local_addr().unwrap();
+
+  tokio::spawn(async move {
+    let mut connections = vec![];
+    while let Ok((sock, _)) = src_tcp.accept().await {
+      connections.push(sock);
+    }
+  });
+
+  src_addr
+}
+
 async fn create_http_proxy(src_addr: SocketAddr) -> SocketAddr {
   let prx_tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
   let prx_addr = prx_tcp.local_addr().unwrap();
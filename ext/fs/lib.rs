@@ -61,6 +61,10 @@ pub trait FsPermissions {
     display: &str,
     api_name: &str,
   ) -> Result<(), AnyError>;
+  fn write_byte_meter(
+    &self,
+    path: &Path,
+  ) -> Result<Option<deno_permissions::WriteByteMeter>, AnyError>;
 
   fn check<'a>(
     &mut self,
@@ -156,6 +160,13 @@ impl FsPermissions for deno_permissions::PermissionsContainer {
     )
   }
 
+  fn write_byte_meter(
+    &self,
+    path: &Path,
+  ) -> Result<Option<deno_permissions::WriteByteMeter>, AnyError> {
+    deno_permissions::PermissionsContainer::write_byte_meter(self, path)
+  }
+
   fn check_read_all(&mut self, api_name: &str) -> Result<(), AnyError> {
     deno_permissions::PermissionsContainer::check_read_all(self, api_name)
   }
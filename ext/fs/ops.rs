@@ -21,6 +21,7 @@ use deno_core::ToJsBuffer;
 use deno_io::fs::FileResource;
 use deno_io::fs::FsError;
 use deno_io::fs::FsStat;
+use deno_permissions::WriteByteMeter;
 use rand::rngs::ThreadRng;
 use rand::thread_rng;
 use rand::Rng;
@@ -54,6 +55,34 @@ fn async_permission_check<P: FsPermissions + 'static>(
   }
 }
 
+/// Charges `bytes` written to `path` against its `--allow-write` quota. The
+/// write permission is checked first, so that a denied write doesn't use up
+/// the quota. Returns the meter, to refund the bytes if the write fails.
+fn charge_write_quota<P: FsPermissions + 'static>(
+  permissions: &mut P,
+  path: &Path,
+  bytes: u64,
+  api_name: &str,
+) -> Result<Option<WriteByteMeter>, AnyError> {
+  let Some(meter) = permissions.write_byte_meter(path)? else {
+    return Ok(None);
+  };
+  permissions.check_write(path, api_name)?;
+  meter.charge(bytes, api_name)?;
+  Ok(Some(meter))
+}
+
+fn refund_if_failed<T, E>(
+  result: Result<T, E>,
+  meter: Option<WriteByteMeter>,
+  bytes: u64,
+) -> Result<T, E> {
+  if let (Err(_), Some(meter)) = (&result, meter) {
+    meter.refund(bytes);
+  }
+  result
+}
+
 fn map_permission_error(
   operation: &'static str,
   error: FsError,
@@ -146,9 +175,14 @@ where
     .open_sync(&path, options, Some(&mut access_check))
     .map_err(|error| map_permission_error("open", error, &path))?;
   drop(access_check);
-  let rid = state
-    .resource_table
-    .add(FileResource::new(file, "fsFile".to_string()));
+  let write_meter = if options.write || options.append {
+    state.borrow::<P>().write_byte_meter(&path)?
+  } else {
+    None
+  };
+  let rid = state.resource_table.add(
+    FileResource::new(file, "fsFile".to_string()).with_write_meter(write_meter),
+  );
   Ok(rid)
 }
 
@@ -173,10 +207,15 @@ where
     .await
     .map_err(|error| map_permission_error("open", error, &path))?;
 
-  let rid = state
-    .borrow_mut()
-    .resource_table
-    .add(FileResource::new(file, "fsFile".to_string()));
+  let mut state = state.borrow_mut();
+  let write_meter = if options.write || options.append {
+    state.borrow::<P>().write_byte_meter(&path)?
+  } else {
+    None
+  };
+  let rid = state.resource_table.add(
+    FileResource::new(file, "fsFile".to_string()).with_write_meter(write_meter),
+  );
   Ok(rid)
 }
 
@@ -380,12 +419,20 @@ where
   let from = PathBuf::from(from);
   let to = PathBuf::from(to);
 
+  let fs = state.borrow::<FileSystemRc>().clone();
   let permissions = state.borrow_mut::<P>();
   permissions.check_read(&from, "Deno.copyFileSync()")?;
   permissions.check_write(&to, "Deno.copyFileSync()")?;
+  let (write_meter, size) = match permissions.write_byte_meter(&to)? {
+    Some(meter) => {
+      let size = fs.stat_sync(&from).context_path("stat", &from)?.size;
+      meter.charge(size, "Deno.copyFileSync()")?;
+      (Some(meter), size)
+    }
+    None => (None, 0),
+  };
 
-  let fs = state.borrow::<FileSystemRc>();
-  fs.copy_file_sync(&from, &to)
+  refund_if_failed(fs.copy_file_sync(&from, &to), write_meter, size)
     .context_two_path("copy", &from, &to)?;
 
   Ok(())
@@ -403,16 +450,30 @@ where
   let from = PathBuf::from(from);
   let to = PathBuf::from(to);
 
-  let fs = {
+  let (fs, write_meter) = {
     let mut state = state.borrow_mut();
     let permissions = state.borrow_mut::<P>();
     permissions.check_read(&from, "Deno.copyFile()")?;
     permissions.check_write(&to, "Deno.copyFile()")?;
-    state.borrow::<FileSystemRc>().clone()
+    let write_meter = permissions.write_byte_meter(&to)?;
+    (state.borrow::<FileSystemRc>().clone(), write_meter)
   };
 
-  fs.copy_file_async(from.clone(), to.clone())
-    .await
+  let size = match &write_meter {
+    Some(meter) => {
+      let size = fs
+        .stat_async(from.clone())
+        .await
+        .context_path("stat", &from)?
+        .size;
+      meter.charge(size, "Deno.copyFile()")?;
+      size
+    }
+    None => 0,
+  };
+
+  let result = fs.copy_file_async(from.clone(), to.clone()).await;
+  refund_if_failed(result, write_meter, size)
     .context_two_path("copy", &from, &to)?;
 
   Ok(())
@@ -825,12 +886,21 @@ where
 {
   let path = PathBuf::from(path);
 
-  state
-    .borrow_mut::<P>()
-    .check_write(&path, "Deno.truncateSync()")?;
+  let fs = state.borrow::<FileSystemRc>().clone();
+  let permissions = state.borrow_mut::<P>();
+  permissions.check_write(&path, "Deno.truncateSync()")?;
+  // only growing a file is charged against its quota
+  let (write_meter, grown) = match permissions.write_byte_meter(&path)? {
+    Some(meter) => {
+      let size = fs.stat_sync(&path).map_or(0, |stat| stat.size);
+      let grown = len.saturating_sub(size);
+      meter.charge(grown, "Deno.truncateSync()")?;
+      (Some(meter), grown)
+    }
+    None => (None, 0),
+  };
 
-  let fs = state.borrow::<FileSystemRc>();
-  fs.truncate_sync(&path, len)
+  refund_if_failed(fs.truncate_sync(&path, len), write_meter, grown)
     .context_path("truncate", &path)?;
 
   Ok(())
@@ -847,16 +917,30 @@ where
 {
   let path = PathBuf::from(path);
 
-  let fs = {
+  let (fs, write_meter) = {
     let mut state = state.borrow_mut();
-    state
-      .borrow_mut::<P>()
-      .check_write(&path, "Deno.truncate()")?;
-    state.borrow::<FileSystemRc>().clone()
+    let permissions = state.borrow_mut::<P>();
+    permissions.check_write(&path, "Deno.truncate()")?;
+    let write_meter = permissions.write_byte_meter(&path)?;
+    (state.borrow::<FileSystemRc>().clone(), write_meter)
   };
 
-  fs.truncate_async(path.clone(), len)
-    .await
+  // only growing a file is charged against its quota
+  let grown = match &write_meter {
+    Some(meter) => {
+      let size = fs
+        .stat_async(path.clone())
+        .await
+        .map_or(0, |stat| stat.size);
+      let grown = len.saturating_sub(size);
+      meter.charge(grown, "Deno.truncate()")?;
+      grown
+    }
+    None => 0,
+  };
+
+  let result = fs.truncate_async(path.clone(), len).await;
+  refund_if_failed(result, write_meter, grown)
     .context_path("truncate", &path)?;
 
   Ok(())
@@ -1197,12 +1281,21 @@ where
 {
   let path = PathBuf::from(path);
 
+  let write_meter = charge_write_quota(
+    state.borrow_mut::<P>(),
+    &path,
+    data.len() as u64,
+    "Deno.writeFileSync()",
+  )?;
+
   let options = OpenOptions::write(create, append, create_new, mode);
   let fs = state.borrow::<FileSystemRc>().clone();
   let mut access_check =
     sync_permission_check::<P>(state.borrow_mut(), "Deno.writeFileSync()");
 
-  fs.write_file_sync(&path, options, Some(&mut access_check), &data)
+  let result =
+    fs.write_file_sync(&path, options, Some(&mut access_check), &data);
+  refund_if_failed(result, write_meter, data.len() as u64)
     .map_err(|error| map_permission_error("writefile", error, &path))?;
 
   Ok(())
@@ -1225,6 +1318,14 @@ where
 {
   let path = PathBuf::from(path);
 
+  let write_meter = charge_write_quota(
+    state.borrow_mut().borrow_mut::<P>(),
+    &path,
+    data.len() as u64,
+    "Deno.writeFile()",
+  )?;
+  let len = data.len() as u64;
+
   let options = OpenOptions::write(create, append, create_new, mode);
 
   let mut access_check =
@@ -1252,10 +1353,19 @@ where
       }
     };
 
-    res?.map_err(|error| map_permission_error("writefile", error, &path))?;
+    let res = match res {
+      Ok(res) => res,
+      Err(err) => {
+        if let Some(meter) = write_meter {
+          meter.refund(len);
+        }
+        return Err(err.into());
+      }
+    };
+    refund_if_failed(res, write_meter, len)
+      .map_err(|error| map_permission_error("writefile", error, &path))?;
   } else {
-    fut
-      .await
+    refund_if_failed(fut.await, write_meter, len)
       .map_err(|error| map_permission_error("writefile", error, &path))?;
   }
 
@@ -1588,7 +1698,17 @@ pub fn op_fs_ftruncate_sync(
   #[number] len: u64,
 ) -> Result<(), AnyError> {
   let file = FileResource::get_file(state, rid)?;
-  file.truncate_sync(len)?;
+  let write_meter = FileResource::get_write_meter(state, rid)?;
+  // only growing a file is charged against its quota
+  let grown = match &write_meter {
+    Some(meter) => {
+      let grown = len.saturating_sub(file.clone().stat_sync()?.size);
+      meter.charge(grown, "Deno.FsFile.truncateSync()")?;
+      grown
+    }
+    None => 0,
+  };
+  refund_if_failed(file.truncate_sync(len), write_meter, grown)?;
   Ok(())
 }
 
@@ -1598,8 +1718,23 @@ pub async fn op_fs_ftruncate_async(
   #[smi] rid: ResourceId,
   #[number] len: u64,
 ) -> Result<(), AnyError> {
-  let file = FileResource::get_file(&state.borrow(), rid)?;
-  file.truncate_async(len).await?;
+  let (file, write_meter) = {
+    let state = state.borrow();
+    (
+      FileResource::get_file(&state, rid)?,
+      FileResource::get_write_meter(&state, rid)?,
+    )
+  };
+  // only growing a file is charged against its quota
+  let grown = match &write_meter {
+    Some(meter) => {
+      let grown = len.saturating_sub(file.clone().stat_async().await?.size);
+      meter.charge(grown, "Deno.FsFile.truncate()")?;
+      grown
+    }
+    None => 0,
+  };
+  refund_if_failed(file.truncate_async(len).await, write_meter, grown)?;
   Ok(())
 }
 
@@ -15,6 +15,7 @@ use deno_core::BufView;
 use deno_core::OpState;
 use deno_core::ResourceHandleFd;
 use deno_core::ResourceId;
+use deno_permissions::WriteByteMeter;
 use tokio::task::JoinError;
 
 #[derive(Debug)]
@@ -255,11 +256,22 @@ pub trait File {
 pub struct FileResource {
   name: String,
   file: Rc<dyn File>,
+  // Charges writes against the `--allow-write` quota of the file's path.
+  write_meter: Option<WriteByteMeter>,
 }
 
 impl FileResource {
   pub fn new(file: Rc<dyn File>, name: String) -> Self {
-    Self { name, file }
+    Self {
+      name,
+      file,
+      write_meter: None,
+    }
+  }
+
+  pub fn with_write_meter(mut self, meter: Option<WriteByteMeter>) -> Self {
+    self.write_meter = meter;
+    self
   }
 
   fn with_resource<F, R>(
@@ -296,6 +308,27 @@ impl FileResource {
   pub fn file(&self) -> Rc<dyn File> {
     self.file.clone()
   }
+
+  pub fn get_write_meter(
+    state: &OpState,
+    rid: ResourceId,
+  ) -> Result<Option<WriteByteMeter>, AnyError> {
+    let resource = state.resource_table.get::<FileResource>(rid)?;
+    Ok(resource.write_meter.clone())
+  }
+
+  fn charge_write(&self, len: usize, api_name: &str) -> Result<(), AnyError> {
+    match &self.write_meter {
+      Some(meter) => meter.charge(len as u64, api_name),
+      None => Ok(()),
+    }
+  }
+
+  fn refund_write(&self, len: usize) {
+    if let Some(meter) = &self.write_meter {
+      meter.refund(len as u64);
+    }
+  }
 }
 
 impl deno_core::Resource for FileResource {
@@ -336,7 +369,16 @@ impl deno_core::Resource for FileResource {
     buf: deno_core::BufView,
   ) -> deno_core::AsyncResult<deno_core::WriteOutcome> {
     Box::pin(async move {
-      self.file.clone().write(buf).await.map_err(|err| err.into())
+      let len = buf.len();
+      self.charge_write(len, "Deno.FsFile.write()")?;
+      let result = self.file.clone().write(buf).await;
+      let nwritten = match &result {
+        Ok(deno_core::WriteOutcome::Full { nwritten }) => *nwritten,
+        Ok(deno_core::WriteOutcome::Partial { nwritten, .. }) => *nwritten,
+        Err(_) => 0,
+      };
+      self.refund_write(len.saturating_sub(nwritten));
+      result.map_err(|err| err.into())
     })
   }
 
@@ -345,12 +387,13 @@ impl deno_core::Resource for FileResource {
     buf: deno_core::BufView,
   ) -> deno_core::AsyncResult<()> {
     Box::pin(async move {
-      self
-        .file
-        .clone()
-        .write_all(buf)
-        .await
-        .map_err(|err| err.into())
+      let len = buf.len();
+      self.charge_write(len, "Deno.FsFile.write()")?;
+      let result = self.file.clone().write_all(buf).await;
+      if result.is_err() {
+        self.refund_write(len);
+      }
+      result.map_err(|err| err.into())
     })
   }
 
@@ -365,7 +408,11 @@ impl deno_core::Resource for FileResource {
     self: Rc<Self>,
     data: &[u8],
   ) -> Result<usize, deno_core::anyhow::Error> {
-    self.file.clone().write_sync(data).map_err(|err| err.into())
+    self.charge_write(data.len(), "Deno.FsFile.writeSync()")?;
+    let result = self.file.clone().write_sync(data);
+    let nwritten = result.as_ref().map_or(0, |nwritten| *nwritten);
+    self.refund_write(data.len().saturating_sub(nwritten));
+    result.map_err(|err| err.into())
   }
 
   fn backing_fd(self: Rc<Self>) -> Option<ResourceHandleFd> {
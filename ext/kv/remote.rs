@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -14,6 +15,7 @@ use deno_core::error::AnyError;
 use deno_core::futures::Stream;
 use deno_core::OpState;
 use deno_fetch::create_http_client;
+use deno_fetch::AddressFamily;
 use deno_fetch::CreateHttpClientOptions;
 use deno_tls::rustls::RootCertStore;
 use deno_tls::Proxy;
@@ -202,10 +204,30 @@ impl<P: RemoteDbHandlerPermissions + 'static> DatabaseHandler
           .clone()
           .try_into()
           .unwrap(),
+        tls_client_certs_per_host: HashMap::new(),
+        client_certs_by_issuer: vec![],
         pool_max_idle_per_host: None,
         pool_idle_timeout: None,
         http1: false,
         http2: true,
+        low_latency_mode: false,
+        connect_retries: 0,
+        connect_retry_backoff: std::time::Duration::ZERO,
+        retry: None,
+        max_redirect_bytes: None,
+        max_response_body_bytes: None,
+        http2_initial_stream_window: None,
+        http2_initial_connection_window: None,
+        http2_prior_knowledge: false,
+        connect_timeout: None,
+        request_timeout: None,
+        dns_resolver: None,
+        force_new_connection_per_request: false,
+        address_family_preference: AddressFamily::Any,
+        tls_resumption: true,
+        min_tls_version: None,
+        max_tls_version: None,
+        trust_env_proxy: true,
       },
     )?;
     let fetch_client = FetchClient(client);
@@ -9,8 +9,10 @@ use deno_core::CancelHandle;
 use deno_core::CancelTryFuture;
 use deno_core::RcRef;
 use deno_core::Resource;
+use deno_permissions::NetByteMeter;
 use socket2::SockRef;
 use std::borrow::Cow;
+use std::future::Future;
 use std::rc::Rc;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
@@ -31,6 +33,8 @@ pub struct FullDuplexResource<R, W> {
   // canceled, while 'write' ops are allowed to complete. Therefore only
   // 'read' futures should be attached to this cancel handle.
   cancel_handle: CancelHandle,
+  // Charges writes against the `--allow-net` byte cap of the remote host.
+  egress_meter: Option<NetByteMeter>,
 }
 
 impl<R, W> FullDuplexResource<R, W>
@@ -43,9 +47,19 @@ where
       rd: rd.into(),
       wr: wr.into(),
       cancel_handle: Default::default(),
+      egress_meter: None,
     }
   }
 
+  pub fn with_egress_meter(mut self, meter: Option<NetByteMeter>) -> Self {
+    self.egress_meter = meter;
+    self
+  }
+
+  pub(crate) fn egress_meter(&self) -> Option<NetByteMeter> {
+    self.egress_meter.clone()
+  }
+
   pub fn into_inner(self) -> (R, W) {
     (self.rd.into_inner(), self.wr.into_inner())
   }
@@ -77,8 +91,14 @@ where
 
   pub async fn write(self: Rc<Self>, data: &[u8]) -> Result<usize, AnyError> {
     let mut wr = self.wr_borrow_mut().await;
-    let nwritten = wr.write(data).await?;
-    Ok(nwritten)
+    let write = async { wr.write(data).await.map_err(AnyError::from) };
+    metered_write(
+      self.egress_meter.as_ref(),
+      data.len(),
+      "Deno.Conn.write()",
+      write,
+    )
+    .await
   }
 
   pub async fn shutdown(self: Rc<Self>) -> Result<(), AnyError> {
@@ -88,6 +108,24 @@ where
   }
 }
 
+/// Awaits `write` after charging `len` bytes against `meter`, if there is
+/// one. The bytes that didn't get written are refunded.
+pub(crate) async fn metered_write(
+  meter: Option<&NetByteMeter>,
+  len: usize,
+  api_name: &str,
+  write: impl Future<Output = Result<usize, AnyError>>,
+) -> Result<usize, AnyError> {
+  let Some(meter) = meter else {
+    return write.await;
+  };
+  meter.charge(len as u64, api_name)?;
+  let result = write.await;
+  let nwritten = result.as_ref().map_or(0, |nwritten| *nwritten);
+  meter.refund(len.saturating_sub(nwritten) as u64);
+  result
+}
+
 pub type TcpStreamResource =
   FullDuplexResource<tcp::OwnedReadHalf, tcp::OwnedWriteHalf>;
 
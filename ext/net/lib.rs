@@ -11,6 +11,7 @@ mod tcp;
 
 use deno_core::error::AnyError;
 use deno_core::OpState;
+use deno_permissions::NetByteMeter;
 use deno_tls::rustls::RootCertStore;
 use deno_tls::RootCertStoreProvider;
 use std::path::Path;
@@ -25,6 +26,21 @@ pub trait NetPermissions {
     _host: &(T, Option<u16>),
     _api_name: &str,
   ) -> Result<(), AnyError>;
+  fn check_net_bytes<T: AsRef<str>>(
+    &mut self,
+    _host: &(T, Option<u16>),
+    _bytes: u64,
+    _api_name: &str,
+  ) -> Result<(), AnyError>;
+  fn net_byte_meter<T: AsRef<str>>(
+    &mut self,
+    _host: &(T, Option<u16>),
+  ) -> Result<Option<NetByteMeter>, AnyError>;
+  fn check_net_unix(
+    &mut self,
+    _p: &Path,
+    _api_name: &str,
+  ) -> Result<(), AnyError>;
   fn check_read(&mut self, _p: &Path, _api_name: &str) -> Result<(), AnyError>;
   fn check_write(&mut self, _p: &Path, _api_name: &str)
     -> Result<(), AnyError>;
@@ -40,6 +56,35 @@ impl NetPermissions for deno_permissions::PermissionsContainer {
     deno_permissions::PermissionsContainer::check_net(self, host, api_name)
   }
 
+  #[inline(always)]
+  fn check_net_bytes<T: AsRef<str>>(
+    &mut self,
+    host: &(T, Option<u16>),
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    deno_permissions::PermissionsContainer::check_net_bytes(
+      self, host, bytes, api_name,
+    )
+  }
+
+  #[inline(always)]
+  fn net_byte_meter<T: AsRef<str>>(
+    &mut self,
+    host: &(T, Option<u16>),
+  ) -> Result<Option<NetByteMeter>, AnyError> {
+    deno_permissions::PermissionsContainer::net_byte_meter(self, host)
+  }
+
+  #[inline(always)]
+  fn check_net_unix(
+    &mut self,
+    path: &Path,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    deno_permissions::PermissionsContainer::check_net_unix(self, path, api_name)
+  }
+
   #[inline(always)]
   fn check_read(
     &mut self,
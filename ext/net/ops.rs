@@ -145,6 +145,11 @@ where
       &(&addr.hostname, Some(addr.port)),
       "Deno.DatagramConn.send()",
     )?;
+    s.borrow_mut::<NP>().check_net_bytes(
+      &(&addr.hostname, Some(addr.port)),
+      zero_copy.len() as u64,
+      "Deno.DatagramConn.send()",
+    )?;
   }
   let addr = resolve_addr(&addr.hostname, addr.port)
     .await?
@@ -309,12 +314,18 @@ pub async fn op_net_connect_tcp_inner<NP>(
 where
   NP: NetPermissions + 'static,
 {
-  {
+  let egress_meter = {
     let mut state_ = state.borrow_mut();
-    state_
-      .borrow_mut::<NP>()
+    let permissions = state_.borrow_mut::<NP>();
+    permissions
       .check_net(&(&addr.hostname, Some(addr.port)), "Deno.connect()")?;
-  }
+    permissions.check_net_bytes(
+      &(&addr.hostname, Some(addr.port)),
+      0,
+      "Deno.connect()",
+    )?;
+    permissions.net_byte_meter(&(&addr.hostname, Some(addr.port)))?
+  };
 
   let addr = resolve_addr(&addr.hostname, addr.port)
     .await?
@@ -325,9 +336,10 @@ where
   let remote_addr = tcp_stream.peer_addr()?;
 
   let mut state_ = state.borrow_mut();
-  let rid = state_
-    .resource_table
-    .add(TcpStreamResource::new(tcp_stream.into_split()));
+  let rid = state_.resource_table.add(
+    TcpStreamResource::new(tcp_stream.into_split())
+      .with_egress_meter(egress_meter),
+  );
 
   Ok((rid, IpAddr::from(local_addr), IpAddr::from(remote_addr)))
 }
@@ -779,6 +791,7 @@ mod tests {
   use deno_core::futures::FutureExt;
   use deno_core::JsRuntime;
   use deno_core::RuntimeOptions;
+  use deno_permissions::NetByteMeter;
   use socket2::SockRef;
   use std::net::Ipv4Addr;
   use std::net::Ipv6Addr;
@@ -989,6 +1002,30 @@ mod tests {
       Ok(())
     }
 
+    fn check_net_bytes<T: AsRef<str>>(
+      &mut self,
+      _host: &(T, Option<u16>),
+      _bytes: u64,
+      _api_name: &str,
+    ) -> Result<(), AnyError> {
+      Ok(())
+    }
+
+    fn net_byte_meter<T: AsRef<str>>(
+      &mut self,
+      _host: &(T, Option<u16>),
+    ) -> Result<Option<NetByteMeter>, AnyError> {
+      Ok(None)
+    }
+
+    fn check_net_unix(
+      &mut self,
+      _p: &Path,
+      _api_name: &str,
+    ) -> Result<(), AnyError> {
+      Ok(())
+    }
+
     fn check_read(
       &mut self,
       _p: &Path,
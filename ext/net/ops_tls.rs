@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::io::metered_write;
 use crate::io::TcpStreamResource;
 use crate::ops::IpAddr;
 use crate::ops::TlsHandshakeInfo;
@@ -27,6 +28,7 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_permissions::NetByteMeter;
 use deno_tls::create_client_config;
 use deno_tls::load_certs;
 use deno_tls::load_private_keys;
@@ -102,6 +104,8 @@ pub struct TlsStreamResource {
   // `None` when a TLS handshake hasn't been done.
   handshake_info: RefCell<Option<TlsHandshakeInfo>>,
   cancel_handle: CancelHandle, // Only read and handshake ops get canceled.
+  // Charges writes against the `--allow-net` byte cap of the remote host.
+  egress_meter: Option<NetByteMeter>,
 }
 
 impl TlsStreamResource {
@@ -111,9 +115,15 @@ impl TlsStreamResource {
       wr: wr.into(),
       handshake_info: RefCell::new(None),
       cancel_handle: Default::default(),
+      egress_meter: None,
     }
   }
 
+  pub fn with_egress_meter(mut self, meter: Option<NetByteMeter>) -> Self {
+    self.egress_meter = meter;
+    self
+  }
+
   pub fn into_inner(self) -> (TlsStreamRead, TlsStreamWrite) {
     (self.rd.into_inner(), self.wr.into_inner())
   }
@@ -128,10 +138,19 @@ impl TlsStreamResource {
   }
 
   pub async fn write(self: Rc<Self>, data: &[u8]) -> Result<usize, AnyError> {
-    let mut wr = RcRef::map(self, |r| &r.wr).borrow_mut().await;
-    let nwritten = wr.write(data).await?;
-    wr.flush().await?;
-    Ok(nwritten)
+    let mut wr = RcRef::map(&self, |r| &r.wr).borrow_mut().await;
+    let write = async {
+      let nwritten = wr.write(data).await?;
+      wr.flush().await?;
+      Ok(nwritten)
+    };
+    metered_write(
+      self.egress_meter.as_ref(),
+      data.len(),
+      "Deno.TlsConn.write()",
+      write,
+    )
+    .await
   }
 
   pub async fn shutdown(self: Rc<Self>) -> Result<(), AnyError> {
@@ -331,6 +350,7 @@ where
   // resource error. See also: https://github.com/denoland/deno/pull/16242
   let resource = Rc::try_unwrap(resource_rc)
     .map_err(|_| bad_resource("TCP stream is currently in use"))?;
+  let egress_meter = resource.egress_meter();
   let (read_half, write_half) = resource.into_inner();
   let tcp_stream = read_half.reunite(write_half)?;
 
@@ -359,9 +379,10 @@ where
 
   let rid = {
     let mut state_ = state.borrow_mut();
-    state_
-      .resource_table
-      .add(TlsStreamResource::new(tls_stream.into_split()))
+    state_.resource_table.add(
+      TlsStreamResource::new(tls_stream.into_split())
+        .with_egress_meter(egress_meter),
+    )
   };
 
   Ok((rid, IpAddr::from(local_addr), IpAddr::from(remote_addr)))
@@ -384,15 +405,21 @@ where
     .try_borrow::<UnsafelyIgnoreCertificateErrors>()
     .and_then(|it| it.0.clone());
 
-  {
+  let egress_meter = {
     let mut s = state.borrow_mut();
     let permissions = s.borrow_mut::<NP>();
     permissions
       .check_net(&(&addr.hostname, Some(addr.port)), "Deno.connectTls()")?;
+    permissions.check_net_bytes(
+      &(&addr.hostname, Some(addr.port)),
+      0,
+      "Deno.connectTls()",
+    )?;
     if let Some(path) = cert_file {
       permissions.check_read(Path::new(path), "Deno.connectTls()")?;
     }
-  }
+    permissions.net_byte_meter(&(&addr.hostname, Some(addr.port)))?
+  };
 
   let mut ca_certs = args
     .ca_certs
@@ -447,9 +474,10 @@ where
 
   let rid = {
     let mut state_ = state.borrow_mut();
-    state_
-      .resource_table
-      .add(TlsStreamResource::new(tls_stream.into_split()))
+    state_.resource_table.add(
+      TlsStreamResource::new(tls_stream.into_split())
+        .with_egress_meter(egress_meter),
+    )
   };
 
   Ok((rid, IpAddr::from(local_addr), IpAddr::from(remote_addr)))
@@ -104,10 +104,7 @@ where
     let mut state_ = state.borrow_mut();
     state_
       .borrow_mut::<NP>()
-      .check_read(address_path, "Deno.connect()")?;
-    state_
-      .borrow_mut::<NP>()
-      .check_write(address_path, "Deno.connect()")?;
+      .check_net_unix(address_path, "Deno.connect()")?;
   }
   let unix_stream = UnixStream::connect(Path::new(&path)).await?;
   let local_addr = unix_stream.local_addr()?;
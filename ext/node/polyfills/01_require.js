@@ -288,6 +288,8 @@ let hasBrokenOnInspectBrk = false;
 let hasInspectBrk = false;
 // Are we running with --node-modules-dir flag or byonm?
 let usesLocalNodeModulesDir = false;
+// Are CommonJS modules evaluated in strict mode (--force-strict)?
+let forceStrict = false;
 
 function stat(filename) {
   // TODO: required only on windows
@@ -979,11 +981,24 @@ function wrapSafe(
   format,
 ) {
   const wrapper = Module.wrap(content);
-  const [f, err] = core.evalContext(
-    wrapper,
-    url.pathToFileURL(filename).toString(),
+  const fileUrl = url.pathToFileURL(filename).toString();
+  let [f, err] = core.evalContext(
+    forceStrict ? wrapStrict(wrapper) : wrapper,
+    fileUrl,
     [format !== "module"],
   );
+  if (
+    err && forceStrict && err.thrown instanceof SyntaxError &&
+    !isEsmSyntaxError(err.thrown)
+  ) {
+    // Code like `with` statements or legacy octal literals can't even be
+    // parsed in strict mode, so such modules keep running in sloppy mode.
+    process.emitWarning(
+      `"${filename}" can't be evaluated in strict mode ` +
+        `(${err.thrown.message}), falling back to sloppy mode`,
+    );
+    [f, err] = core.evalContext(wrapper, fileUrl, [format !== "module"]);
+  }
   if (err) {
     if (process.mainModule === cjsModuleInstance) {
       enrichCJSError(err.thrown);
@@ -1000,6 +1015,21 @@ function wrapSafe(
   return f;
 }
 
+/**
+ * Puts a "use strict" directive at the start of the module body.
+ * @param {string} wrapper
+ * @returns {string}
+ */
+function wrapStrict(wrapper) {
+  const prefix = Module.wrapper[0];
+  if (!StringPrototypeStartsWith(wrapper, prefix)) {
+    return wrapper;
+  }
+  return `${prefix}"use strict";${
+    StringPrototypeSlice(wrapper, prefix.length)
+  }`;
+}
+
 Module.prototype._compile = function (content, filename, format) {
   const compiledWrapper = wrapSafe(filename, content, this, format);
 
@@ -1277,6 +1307,9 @@ internals.requireImpl = {
   setUsesLocalNodeModulesDir() {
     usesLocalNodeModulesDir = true;
   },
+  setForceStrict() {
+    forceStrict = true;
+  },
   setInspectBrk() {
     hasInspectBrk = true;
   },
@@ -18,6 +18,7 @@ function initialize(args) {
     workerId,
     maybeWorkerMetadata,
     nodeDebug,
+    forceStrict = false,
     warmup = false,
   } = args;
   if (!warmup) {
@@ -28,6 +29,9 @@ function initialize(args) {
     if (usesLocalNodeModulesDir) {
       requireImpl.setUsesLocalNodeModulesDir();
     }
+    if (forceStrict) {
+      requireImpl.setForceStrict();
+    }
 
     // FIXME(bartlomieju): not nice to depend on `Deno` namespace here
     // but it's the only way to get `args` and `version` and this point.
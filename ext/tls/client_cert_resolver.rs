@@ -0,0 +1,133 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Selection of the client certificate to present in mutual TLS, based on the
+//! certificate authorities a server says it accepts.
+
+use std::sync::Arc;
+
+use deno_core::anyhow::anyhow;
+use deno_core::error::AnyError;
+use rustls::client::ResolvesClientCert;
+use rustls::sign::CertifiedKey;
+use rustls::SignatureScheme;
+
+use crate::TlsKey;
+
+/// Presents the client certificate whose chain was issued by one of the
+/// certificate authorities listed in the server's certificate request.
+///
+/// When none of the chains match, or the server doesn't list any authority,
+/// the default certificate is presented if there is one.
+#[derive(Debug)]
+pub struct IssuerClientCertResolver {
+  /// The issuer names of every certificate in a chain, with its key.
+  certs: Vec<(Vec<Vec<u8>>, Arc<CertifiedKey>)>,
+  default: Option<Arc<CertifiedKey>>,
+}
+
+impl IssuerClientCertResolver {
+  pub fn new(
+    keys: Vec<TlsKey>,
+    default: Option<TlsKey>,
+  ) -> Result<Self, AnyError> {
+    let mut certs = Vec::with_capacity(keys.len());
+    for key in keys {
+      let issuers = key
+        .0
+        .iter()
+        .map(|cert| {
+          let cert = webpki::EndEntityCert::try_from(cert)
+            .map_err(|err| anyhow!("Invalid client certificate: {:?}", err))?;
+          Ok(cert.issuer().to_vec())
+        })
+        .collect::<Result<Vec<_>, AnyError>>()?;
+      certs.push((issuers, certified_key(key)?));
+    }
+    let default = default.map(certified_key).transpose()?;
+    Ok(Self { certs, default })
+  }
+}
+
+impl ResolvesClientCert for IssuerClientCertResolver {
+  fn resolve(
+    &self,
+    root_hint_subjects: &[&[u8]],
+    sigschemes: &[SignatureScheme],
+  ) -> Option<Arc<CertifiedKey>> {
+    let usable =
+      |key: &Arc<CertifiedKey>| key.key.choose_scheme(sigschemes).is_some();
+    self
+      .certs
+      .iter()
+      .find(|(issuers, key)| {
+        usable(key)
+          && issuers.iter().any(|issuer| {
+            root_hint_subjects
+              .iter()
+              .any(|hint| name_contents(hint) == name_contents(issuer))
+          })
+      })
+      .map(|(_, key)| key.clone())
+      .or_else(|| self.default.clone().filter(usable))
+  }
+
+  fn has_certs(&self) -> bool {
+    !self.certs.is_empty() || self.default.is_some()
+  }
+}
+
+fn certified_key(key: TlsKey) -> Result<Arc<CertifiedKey>, AnyError> {
+  let TlsKey(cert_chain, private_key) = key;
+  let signing_key =
+    rustls::crypto::ring::sign::any_supported_type(&private_key)
+      .map_err(|err| anyhow!("Invalid client certificate key: {}", err))?;
+  Ok(Arc::new(CertifiedKey::new(cert_chain, signing_key)))
+}
+
+/// Returns the contents of a DER encoded distinguished name. Servers send
+/// the names of acceptable authorities either with or without the outer
+/// `SEQUENCE`, while certificates store their issuer without it.
+fn name_contents(name: &[u8]) -> &[u8] {
+  const SEQUENCE: u8 = 0x30;
+  let (Some(&SEQUENCE), Some(&first_len_byte)) = (name.first(), name.get(1))
+  else {
+    return name;
+  };
+  let (header_len, len) = if first_len_byte < 0x80 {
+    (2, first_len_byte as usize)
+  } else {
+    let len_bytes = (first_len_byte & 0x7f) as usize;
+    let Some(bytes) = name.get(2..2 + len_bytes) else {
+      return name;
+    };
+    let len = bytes
+      .iter()
+      .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+    (2 + len_bytes, len)
+  };
+  if header_len.checked_add(len) == Some(name.len()) {
+    &name[header_len..]
+  } else {
+    name
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_name_contents() {
+    let contents = [0x31, 0x03, 0x30, 0x01, 0x00];
+    assert_eq!(name_contents(&contents), &contents);
+    let mut wrapped = vec![0x30, contents.len() as u8];
+    wrapped.extend_from_slice(&contents);
+    assert_eq!(name_contents(&wrapped), &contents);
+    let mut long_form = vec![0x30, 0x81, contents.len() as u8];
+    long_form.extend_from_slice(&contents);
+    assert_eq!(name_contents(&long_form), &contents);
+    // a length that doesn't cover the whole name isn't an outer sequence
+    let truncated = [0x30, 0x01, 0x00, 0x00];
+    assert_eq!(name_contents(&truncated), &truncated);
+  }
+}
@@ -21,6 +21,7 @@ use rustls::ClientConfig;
 use rustls::DigitallySignedStruct;
 use rustls::Error;
 use rustls::RootCertStore;
+use rustls::SupportedProtocolVersion;
 use rustls_pemfile::certs;
 use rustls_pemfile::ec_private_keys;
 use rustls_pemfile::pkcs8_private_keys;
@@ -32,7 +33,9 @@ use std::io::Cursor;
 use std::net::IpAddr;
 use std::sync::Arc;
 
+mod client_cert_resolver;
 mod tls_key;
+pub use client_cert_resolver::IssuerClientCertResolver;
 pub use tls_key::*;
 
 /// Lazily resolves the root cert store.
@@ -172,15 +175,75 @@ pub enum SocketUse {
   Http2Only,
 }
 
+/// A TLS protocol version a client may negotiate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsVersion {
+  Tls12,
+  Tls13,
+}
+
+impl TlsVersion {
+  fn supported_version(self) -> &'static SupportedProtocolVersion {
+    match self {
+      TlsVersion::Tls12 => &rustls::version::TLS12,
+      TlsVersion::Tls13 => &rustls::version::TLS13,
+    }
+  }
+}
+
+/// Returns the protocol versions from `min` to `max`, both included and
+/// defaulting to the oldest and newest supported version.
+pub fn tls_versions_in_range(
+  min: Option<TlsVersion>,
+  max: Option<TlsVersion>,
+) -> Result<Vec<&'static SupportedProtocolVersion>, AnyError> {
+  let min = min.unwrap_or(TlsVersion::Tls12);
+  let max = max.unwrap_or(TlsVersion::Tls13);
+  if min > max {
+    return Err(anyhow!(
+      "The minimum TLS version ({:?}) is newer than the maximum ({:?})",
+      min,
+      max
+    ));
+  }
+  Ok(
+    [TlsVersion::Tls12, TlsVersion::Tls13]
+      .into_iter()
+      .filter(|version| (min..=max).contains(version))
+      .map(TlsVersion::supported_version)
+      .collect(),
+  )
+}
+
 pub fn create_client_config(
   root_cert_store: Option<RootCertStore>,
   ca_certs: Vec<Vec<u8>>,
   unsafely_ignore_certificate_errors: Option<Vec<String>>,
   maybe_cert_chain_and_key: TlsKeys,
   socket_use: SocketUse,
+) -> Result<ClientConfig, AnyError> {
+  create_client_config_with_versions(
+    root_cert_store,
+    ca_certs,
+    unsafely_ignore_certificate_errors,
+    maybe_cert_chain_and_key,
+    socket_use,
+    rustls::DEFAULT_VERSIONS,
+  )
+}
+
+/// Like [`create_client_config`], but only negotiates the given protocol
+/// versions.
+pub fn create_client_config_with_versions(
+  root_cert_store: Option<RootCertStore>,
+  ca_certs: Vec<Vec<u8>>,
+  unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  maybe_cert_chain_and_key: TlsKeys,
+  socket_use: SocketUse,
+  versions: &[&'static SupportedProtocolVersion],
 ) -> Result<ClientConfig, AnyError> {
   if let Some(ic_allowlist) = unsafely_ignore_certificate_errors {
-    let client_config = ClientConfig::builder()
+    let client_config = ClientConfig::builder_with_protocol_versions(versions)
       .dangerous()
       .with_custom_certificate_verifier(Arc::new(
         NoCertificateVerification::new(ic_allowlist),
@@ -223,8 +286,8 @@ pub fn create_client_config(
     }
   }
 
-  let client_config =
-    ClientConfig::builder().with_root_certificates(root_cert_store);
+  let client_config = ClientConfig::builder_with_protocol_versions(versions)
+    .with_root_certificates(root_cert_store);
 
   let mut client = match maybe_cert_chain_and_key {
     TlsKeys::Static(TlsKey(cert_chain, private_key)) => client_config
@@ -21,6 +21,7 @@ use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ToJsBuffer;
 use deno_net::raw::NetworkStream;
+use deno_permissions::NetByteMeter;
 use deno_tls::create_client_config;
 use deno_tls::rustls::ClientConfig;
 use deno_tls::rustls::ClientConnection;
@@ -96,6 +97,16 @@ pub trait WebSocketPermissions {
     _url: &url::Url,
     _api_name: &str,
   ) -> Result<(), AnyError>;
+  fn check_net_url_bytes(
+    &mut self,
+    _url: &url::Url,
+    _bytes: u64,
+    _api_name: &str,
+  ) -> Result<(), AnyError>;
+  fn net_url_byte_meter(
+    &mut self,
+    _url: &url::Url,
+  ) -> Result<Option<NetByteMeter>, AnyError>;
 }
 
 impl WebSocketPermissions for deno_permissions::PermissionsContainer {
@@ -107,6 +118,26 @@ impl WebSocketPermissions for deno_permissions::PermissionsContainer {
   ) -> Result<(), AnyError> {
     deno_permissions::PermissionsContainer::check_net_url(self, url, api_name)
   }
+
+  #[inline(always)]
+  fn check_net_url_bytes(
+    &mut self,
+    url: &url::Url,
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    deno_permissions::PermissionsContainer::check_net_url_bytes(
+      self, url, bytes, api_name,
+    )
+  }
+
+  #[inline(always)]
+  fn net_url_byte_meter(
+    &mut self,
+    url: &url::Url,
+  ) -> Result<Option<NetByteMeter>, AnyError> {
+    deno_permissions::PermissionsContainer::net_url_byte_meter(self, url)
+  }
 }
 
 /// `UnsafelyIgnoreCertificateErrors` is a wrapper struct so it can be placed inside `GothamState`;
@@ -141,9 +172,10 @@ pub fn op_ws_check_permission_and_cancel_handle<WP>(
 where
   WP: WebSocketPermissions + 'static,
 {
-  state
-    .borrow_mut::<WP>()
-    .check_net_url(&url::Url::parse(&url)?, &api_name)?;
+  let url = url::Url::parse(&url)?;
+  let permissions = state.borrow_mut::<WP>();
+  permissions.check_net_url(&url, &api_name)?;
+  permissions.check_net_url_bytes(&url, 0, &api_name)?;
 
   if cancel_handle {
     let rid = state
@@ -405,14 +437,15 @@ pub async fn op_ws_create<WP>(
 where
   WP: WebSocketPermissions + 'static,
 {
-  {
+  let egress_meter = {
     let mut s = state.borrow_mut();
-    s.borrow_mut::<WP>()
-      .check_net_url(&url::Url::parse(&url)?, &api_name)
-      .expect(
-        "Permission check should have been done in op_ws_check_permission",
-      );
-  }
+    let permissions = s.borrow_mut::<WP>();
+    let url = url::Url::parse(&url)?;
+    permissions.check_net_url(&url, &api_name).expect(
+      "Permission check should have been done in op_ws_check_permission",
+    );
+    permissions.net_url_byte_meter(&url)?
+  };
 
   let cancel_resource = if let Some(cancel_rid) = cancel_handle {
     let r = state
@@ -444,7 +477,9 @@ where
   }
 
   let mut state = state.borrow_mut();
-  let rid = state.resource_table.add(ServerWebSocket::new(stream));
+  let rid = state
+    .resource_table
+    .add(ServerWebSocket::new(stream).with_egress_meter(egress_meter));
 
   let protocol = match response.get("Sec-WebSocket-Protocol") {
     Some(header) => header.to_str().unwrap(),
@@ -481,6 +516,8 @@ pub struct ServerWebSocket {
   string: Cell<Option<String>>,
   ws_read: AsyncRefCell<FragmentCollectorRead<ReadHalf<WebSocketStream>>>,
   ws_write: AsyncRefCell<WebSocketWrite<WriteHalf<WebSocketStream>>>,
+  // Charges sent frames against the `--allow-net` byte cap of the host.
+  egress_meter: Option<NetByteMeter>,
 }
 
 impl ServerWebSocket {
@@ -495,9 +532,15 @@ impl ServerWebSocket {
       string: Cell::new(None),
       ws_read: AsyncRefCell::new(FragmentCollectorRead::new(ws_read)),
       ws_write: AsyncRefCell::new(ws_write),
+      egress_meter: None,
     }
   }
 
+  fn with_egress_meter(mut self, meter: Option<NetByteMeter>) -> Self {
+    self.egress_meter = meter;
+    self
+  }
+
   fn set_error(&self, error: Option<String>) {
     if let Some(error) = error {
       self.error.set(Some(error));
@@ -525,6 +568,9 @@ impl ServerWebSocket {
     if ws.is_closed() {
       return Ok(());
     }
+    if let Some(meter) = &self.egress_meter {
+      meter.charge(frame.payload.len() as u64, "WebSocket.send()")?;
+    }
     ws.write_frame(frame)
       .await
       .map_err(|err| type_error(err.to_string()))?;
@@ -7,6 +7,7 @@ delete Intl.v8BreakIterator;
 import { core, internals, primordials } from "ext:core/mod.js";
 const ops = core.ops;
 import {
+  op_abort_on_warning,
   op_bootstrap_args,
   op_bootstrap_is_stderr_tty,
   op_bootstrap_is_stdout_tty,
@@ -43,6 +44,8 @@ const {
   ObjectValues,
   PromisePrototypeThen,
   PromiseResolve,
+  RegExpPrototypeTest,
+  SafeRegExp,
   SafeSet,
   StringPrototypeIncludes,
   StringPrototypePadEnd,
@@ -118,9 +121,33 @@ let globalThis_;
 
 let verboseDeprecatedApiWarning = false;
 let deprecatedApiWarningDisabled = false;
+let deprecatedApiError = false;
+let abortOnWarningPattern = null;
 const ALREADY_WARNED_DEPRECATED = new SafeSet();
 
+function maybeAbortOnWarning(message) {
+  if (
+    abortOnWarningPattern !== null &&
+    RegExpPrototypeTest(abortOnWarningPattern, message)
+  ) {
+    // deno-lint-ignore no-console
+    console.error(
+      `%cerror%c: Aborting because a warning matched --abort-on-warning=${abortOnWarningPattern.source}`,
+      "color: red; font-weight: bold;",
+      "",
+    );
+    // exit rather than throw, so the warning can't be caught
+    op_abort_on_warning();
+    throw new Error("Code not reachable");
+  }
+}
+
 function warnOnDeprecatedApi(apiName, stack, ...suggestions) {
+  if (deprecatedApiError) {
+    throw new Error(
+      `Use of deprecated "${apiName}" API is not allowed with --deprecation=error.`,
+    );
+  }
   if (deprecatedApiWarningDisabled) {
     return;
   }
@@ -138,6 +165,9 @@ function warnOnDeprecatedApi(apiName, stack, ...suggestions) {
       "color: yellow;",
       "font-weight: bold;",
     );
+    maybeAbortOnWarning(
+      `warning: Use of deprecated "${apiName}" API. This API will be removed in Deno 2. Run again with DENO_VERBOSE_WARNINGS=1 to get more details.`,
+    );
     return;
   }
 
@@ -213,6 +243,9 @@ function warnOnDeprecatedApi(apiName, stack, ...suggestions) {
     );
   }
   logError();
+  maybeAbortOnWarning(
+    `warning: Use of deprecated "${apiName}" API. This API will be removed in Deno 2.`,
+  );
 }
 
 function windowClose() {
@@ -715,6 +748,9 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
       14: serveHost,
       15: serveIsMain,
       16: serveWorkerCount,
+      17: forceStrict,
+      18: shouldThrowOnDeprecatedApi,
+      19: abortOnWarning,
     } = runtimeOptions;
 
     if (mode === executionModes.serve) {
@@ -807,6 +843,10 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
 
     deprecatedApiWarningDisabled = shouldDisableDeprecatedApiWarning;
     verboseDeprecatedApiWarning = shouldUseVerboseDeprecatedApiWarning;
+    deprecatedApiError = shouldThrowOnDeprecatedApi;
+    if (abortOnWarning !== null) {
+      abortOnWarningPattern = new SafeRegExp(abortOnWarning);
+    }
     performance.setTimeOrigin(DateNow());
     globalThis_ = globalThis;
 
@@ -931,6 +971,7 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
         runningOnMainThread: true,
         argv0,
         nodeDebug,
+        forceStrict,
       });
     }
     if (future) {
@@ -994,6 +1035,9 @@ function bootstrapWorkerRuntime(
       9: shouldDisableDeprecatedApiWarning,
       10: shouldUseVerboseDeprecatedApiWarning,
       11: future,
+      17: forceStrict,
+      18: shouldThrowOnDeprecatedApi,
+      19: abortOnWarning,
     } = runtimeOptions;
 
     // TODO(iuioiua): remove in Deno v2. This allows us to dynamically delete
@@ -1003,6 +1047,10 @@ function bootstrapWorkerRuntime(
 
     deprecatedApiWarningDisabled = shouldDisableDeprecatedApiWarning;
     verboseDeprecatedApiWarning = shouldUseVerboseDeprecatedApiWarning;
+    deprecatedApiError = shouldThrowOnDeprecatedApi;
+    if (abortOnWarning !== null) {
+      abortOnWarningPattern = new SafeRegExp(abortOnWarning);
+    }
     performance.setTimeOrigin(DateNow());
     globalThis_ = globalThis;
 
@@ -1121,6 +1169,7 @@ function bootstrapWorkerRuntime(
         workerId,
         maybeWorkerMetadata: workerMetadata,
         nodeDebug,
+        forceStrict,
       });
     }
 
@@ -2,6 +2,8 @@
 
 use super::utils::into_string;
 use crate::worker::ExitCode;
+use crate::worker::ExitDrain;
+use crate::worker::ExitHooks;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::normalize_path;
@@ -19,6 +21,7 @@ mod sys_info;
 deno_core::extension!(
   deno_os,
   ops = [
+    op_abort_on_warning,
     op_env,
     op_exec_path,
     op_exit,
@@ -48,6 +51,7 @@ deno_core::extension!(
 deno_core::extension!(
   deno_os_worker,
   ops = [
+    op_abort_on_warning,
     op_env,
     op_exec_path,
     op_exit,
@@ -170,9 +174,35 @@ fn op_get_exit_code(state: &mut OpState) -> i32 {
   state.borrow_mut::<ExitCode>().get()
 }
 
+/// Exits with code 1 when a warning matches `--abort-on-warning`. Unlike
+/// `op_exit`, this also ends the process from workers.
+#[op2(fast)]
+fn op_abort_on_warning(state: &mut OpState) {
+  if let Some(exit_code) = state.try_borrow_mut::<ExitCode>() {
+    exit_code.set(1);
+  }
+  if let Some(exit_drain) = state.try_borrow::<ExitDrain>() {
+    if exit_drain.request() {
+      return;
+    }
+  }
+  if let Some(exit_hooks) = state.try_take::<ExitHooks>() {
+    exit_hooks.run();
+  }
+  std::process::exit(1)
+}
+
 #[op2(fast)]
 fn op_exit(state: &mut OpState) {
+  if let Some(exit_drain) = state.try_borrow::<ExitDrain>() {
+    if exit_drain.request() {
+      return;
+    }
+  }
   let code = state.borrow::<ExitCode>().get();
+  if let Some(exit_hooks) = state.try_take::<ExitHooks>() {
+    exit_hooks.run();
+  }
   std::process::exit(code)
 }
 
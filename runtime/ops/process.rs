@@ -231,7 +231,7 @@ fn create_command(
 ) -> Result<CreateCommand, AnyError> {
   state
     .borrow_mut::<PermissionsContainer>()
-    .check_run(&args.cmd, api_name)?;
+    .check_run(&args.cmd, &args.args, api_name)?;
 
   let mut command = std::process::Command::new(args.cmd);
 
@@ -635,9 +635,11 @@ mod deprecated {
     #[serde] run_args: RunArgs,
   ) -> Result<RunInfo, AnyError> {
     let args = run_args.cmd;
-    state
-      .borrow_mut::<PermissionsContainer>()
-      .check_run(&args[0], "Deno.run()")?;
+    state.borrow_mut::<PermissionsContainer>().check_run(
+      &args[0],
+      &args[1..],
+      "Deno.run()",
+    )?;
     let env = run_args.env;
     let cwd = run_args.cwd;
 
@@ -20,6 +20,7 @@ use deno_terminal::colors;
 use fqdn::FQDN;
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fmt;
@@ -31,6 +32,8 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::string::ToString;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use which::which;
 
@@ -40,6 +43,7 @@ use prompter::PromptResponse;
 use prompter::PERMISSION_EMOJI;
 
 pub use prompter::set_prompt_callbacks;
+pub use prompter::set_prompt_timeout;
 pub use prompter::PromptCallback;
 
 /// Fast exit from permission check routines if this permission
@@ -662,10 +666,35 @@ impl Descriptor for ReadDescriptor {
   }
 
   fn stronger_than(&self, other: &Self) -> bool {
-    other.0.starts_with(&self.0)
+    match path_glob(&self.0) {
+      // A glob only covers the paths it matches, not the directories they
+      // are in, and no other glob than itself.
+      Some(glob) => {
+        self == other || glob.matches_path_with(&other.0, GLOB_MATCH_OPTIONS)
+      }
+      None => other.0.starts_with(&self.0),
+    }
   }
 }
 
+const GLOB_MATCH_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+  case_sensitive: !cfg!(windows),
+  require_literal_separator: true,
+  require_literal_leading_dot: false,
+};
+
+/// Returns the glob pattern of a read permission path with a `*` or `?` in
+/// it, like `--allow-read=**/*.json`. Relative globs are resolved against the
+/// current directory like plain paths, so `*.json` only matches the JSON
+/// files directly in it and `**/*.json` those in any of its subdirectories.
+fn path_glob(path: &Path) -> Option<glob::Pattern> {
+  let path = path.to_str()?;
+  if !path.contains(['*', '?']) {
+    return None;
+  }
+  glob::Pattern::new(path).ok()
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct WriteDescriptor(pub PathBuf);
 
@@ -682,7 +711,7 @@ impl Descriptor for WriteDescriptor {
   }
 
   fn parse(args: &Option<Vec<Self::Arg>>) -> Result<HashSet<Self>, AnyError> {
-    parse_path_list(args, WriteDescriptor)
+    parse_path_list(&strip_write_quotas(args)?, WriteDescriptor)
   }
 
   fn flag_name() -> &'static str {
@@ -702,6 +731,8 @@ impl Descriptor for WriteDescriptor {
 pub enum Host {
   Fqdn(FQDN),
   Ip(IpAddr),
+  /// A unix socket, allowed with `--allow-net=unix:<path>`.
+  Unix(PathBuf),
 }
 
 impl FromStr for Host {
@@ -775,6 +806,16 @@ impl FromStr for NetDescriptor {
   type Err = AnyError;
 
   fn from_str(hostname: &str) -> Result<Self, Self::Err> {
+    if let Some(path) = hostname.strip_prefix("unix:") {
+      if path.is_empty() {
+        return Err(uri_error(format!(
+          "invalid empty unix socket path in '{hostname}'"
+        )));
+      }
+      let path = resolve_from_cwd(Path::new(path))?;
+      return Ok(NetDescriptor(Host::Unix(path), None));
+    }
+
     // If this is a IPv6 address enclosed in square brackets, parse it as such.
     if hostname.starts_with('[') {
       if let Some((ip, after)) = hostname.split_once(']') {
@@ -835,6 +876,7 @@ impl fmt::Display for NetDescriptor {
       Host::Fqdn(fqdn) => write!(f, "{fqdn}"),
       Host::Ip(IpAddr::V4(ip)) => write!(f, "{ip}"),
       Host::Ip(IpAddr::V6(ip)) => write!(f, "[{ip}]"),
+      Host::Unix(path) => write!(f, "unix:{}", path.display()),
     }?;
     if let Some(port) = self.1 {
       write!(f, ":{}", port)?;
@@ -843,6 +885,323 @@ impl fmt::Display for NetDescriptor {
   }
 }
 
+/// Parses a decimal size like `512KB`, `10MB` or `1GB` into bytes.
+fn parse_byte_size(size: &str) -> Option<u64> {
+  let digits_end = size
+    .find(|c: char| !c.is_ascii_digit())
+    .unwrap_or(size.len());
+  let (amount, unit) = size.split_at(digits_end);
+  let multiplier = match unit.to_ascii_uppercase().as_str() {
+    "" | "B" => 1,
+    "KB" => 1_000,
+    "MB" => 1_000_000,
+    "GB" => 1_000_000_000,
+    _ => return None,
+  };
+  amount.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+/// Splits an `--allow-net` entry like `api.example.com:bytes=10MB` into the
+/// host and the optional egress byte cap.
+pub fn split_net_byte_cap(
+  entry: &str,
+) -> Result<(&str, Option<u64>), AnyError> {
+  let Some((host, cap)) = entry.rsplit_once(":bytes=") else {
+    return Ok((entry, None));
+  };
+  let bytes = parse_byte_size(cap).ok_or_else(|| {
+    type_error(format!(
+      "Invalid byte cap in --allow-net entry \"{entry}\", expected a size like 512KB, 10MB or 1GB"
+    ))
+  })?;
+  Ok((host, Some(bytes)))
+}
+
+#[derive(Debug, Default)]
+struct NetByteUsage {
+  sent: u64,
+  exceeded: bool,
+}
+
+/// Egress byte caps for hosts allowed with `--allow-net=<host>:bytes=<size>`.
+/// Usage is shared between clones, so workers draw from the same budget as
+/// the main worker.
+#[derive(Clone, Debug, Default)]
+pub struct NetByteQuotas {
+  caps: HashMap<NetDescriptor, u64>,
+  usage: Arc<Mutex<HashMap<NetDescriptor, NetByteUsage>>>,
+}
+
+impl PartialEq for NetByteQuotas {
+  fn eq(&self, other: &Self) -> bool {
+    self.caps == other.caps
+  }
+}
+
+impl Eq for NetByteQuotas {}
+
+impl NetByteQuotas {
+  pub fn parse(allow_list: &Option<Vec<String>>) -> Result<Self, AnyError> {
+    let mut caps = HashMap::new();
+    for entry in allow_list.iter().flatten() {
+      if let (host, Some(cap)) = split_net_byte_cap(entry)? {
+        caps.insert(NetDescriptor::from_str(host)?, cap);
+      }
+    }
+    Ok(Self {
+      caps,
+      usage: Default::default(),
+    })
+  }
+
+  /// Records `bytes` sent to `host`. Fails without recording anything when
+  /// that would go over the host's cap, after which every further charge for
+  /// the host, including new connections (zero bytes), fails as well.
+  pub fn charge(
+    &self,
+    host: &NetDescriptor,
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    let Some(key) = self.cap_key(host) else {
+      return Ok(());
+    };
+    let cap = self.caps[&key];
+    let mut usage = self.usage.lock();
+    let usage = usage.entry(key.clone()).or_default();
+    if !usage.exceeded && usage.sent.saturating_add(bytes) <= cap {
+      usage.sent += bytes;
+      return Ok(());
+    }
+    usage.exceeded = true;
+    Err(custom_error(
+      "DOMExceptionQuotaExceededError",
+      format!(
+        "{api_name} exceeded the egress cap of {cap} bytes for \"{key}\" ({} bytes already sent)",
+        usage.sent
+      ),
+    ))
+  }
+
+  /// Returns a meter for the connections to `host`, or `None` when the host
+  /// has no cap.
+  pub fn meter(&self, host: &NetDescriptor) -> Option<NetByteMeter> {
+    let host = self.cap_key(host)?;
+    Some(NetByteMeter {
+      quotas: self.clone(),
+      host,
+    })
+  }
+
+  /// The entry in `caps` that applies to `host`, preferring one with a
+  /// matching port over one for the whole host.
+  fn cap_key(&self, host: &NetDescriptor) -> Option<NetDescriptor> {
+    if self.caps.is_empty() {
+      return None;
+    }
+    if self.caps.contains_key(host) {
+      return Some(host.clone());
+    }
+    let key = NetDescriptor(host.0.clone(), None);
+    self.caps.contains_key(&key).then_some(key)
+  }
+}
+
+/// Charges the bytes written to an open connection against the egress cap
+/// of its host. Created when the connection is opened, so that resources can
+/// charge writes without access to the permissions.
+#[derive(Clone, Debug)]
+pub struct NetByteMeter {
+  quotas: NetByteQuotas,
+  host: NetDescriptor,
+}
+
+impl NetByteMeter {
+  pub fn charge(&self, bytes: u64, api_name: &str) -> Result<(), AnyError> {
+    self.quotas.charge(&self.host, bytes, api_name)
+  }
+
+  /// Gives back bytes that were charged but not sent, like the rest of a
+  /// partial write.
+  pub fn refund(&self, bytes: u64) {
+    if let Some(usage) = self.quotas.usage.lock().get_mut(&self.host) {
+      usage.sent = usage.sent.saturating_sub(bytes);
+    }
+  }
+}
+
+/// Splits an `--allow-write` entry like `/tmp:quota=50MB` into the path and
+/// the optional size quota.
+pub fn split_write_quota(
+  entry: &Path,
+) -> Result<(PathBuf, Option<u64>), AnyError> {
+  let Some((path, quota)) = entry
+    .to_str()
+    .and_then(|entry| entry.rsplit_once(":quota="))
+  else {
+    return Ok((entry.to_path_buf(), None));
+  };
+  let bytes = parse_byte_size(quota).ok_or_else(|| {
+    type_error(format!(
+      "Invalid quota in --allow-write entry \"{}\", expected a size like 512KB, 10MB or 1GB",
+      entry.display()
+    ))
+  })?;
+  Ok((PathBuf::from(path), Some(bytes)))
+}
+
+fn strip_write_quotas(
+  list: &Option<Vec<PathBuf>>,
+) -> Result<Option<Vec<PathBuf>>, AnyError> {
+  list
+    .as_ref()
+    .map(|list| {
+      list
+        .iter()
+        .map(|entry| split_write_quota(entry).map(|(path, _)| path))
+        .collect()
+    })
+    .transpose()
+}
+
+/// Size quotas for paths allowed with `--allow-write=<path>:quota=<size>`.
+/// Writes are charged to the most specific quota that contains the path and,
+/// like [`NetByteQuotas`], usage is shared between clones.
+#[derive(Clone, Debug, Default)]
+pub struct WriteByteQuotas {
+  quotas: Vec<(PathBuf, u64)>,
+  written: Arc<Mutex<HashMap<PathBuf, u64>>>,
+}
+
+impl PartialEq for WriteByteQuotas {
+  fn eq(&self, other: &Self) -> bool {
+    self.quotas == other.quotas
+  }
+}
+
+impl Eq for WriteByteQuotas {}
+
+impl WriteByteQuotas {
+  pub fn parse(allow_list: &Option<Vec<PathBuf>>) -> Result<Self, AnyError> {
+    let mut quotas = Vec::new();
+    for entry in allow_list.iter().flatten() {
+      if let (path, Some(quota)) = split_write_quota(entry)? {
+        quotas.push((resolve_from_cwd(&path)?, quota));
+      }
+    }
+    Ok(Self {
+      quotas,
+      written: Default::default(),
+    })
+  }
+
+  /// Records `bytes` written to `path`. Fails without recording anything
+  /// when that would go over the quota of the enclosing scope.
+  pub fn charge(
+    &self,
+    path: &Path,
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    match self.meter(path)? {
+      Some(meter) => meter.charge(bytes, api_name),
+      None => Ok(()),
+    }
+  }
+
+  /// Returns a meter for the writes to `path`, or `None` when the path isn't
+  /// in a scope with a quota.
+  pub fn meter(&self, path: &Path) -> Result<Option<WriteByteMeter>, AnyError> {
+    if self.quotas.is_empty() {
+      return Ok(None);
+    }
+    let path = resolve_from_cwd(path)?;
+    Ok(
+      self
+        .quotas
+        .iter()
+        .filter(|(scope, _)| path.starts_with(scope))
+        .max_by_key(|(scope, _)| scope.components().count())
+        .map(|(scope, quota)| WriteByteMeter {
+          quotas: self.clone(),
+          scope: scope.clone(),
+          quota: *quota,
+        }),
+    )
+  }
+}
+
+/// Charges the bytes written to an open file against the write quota of its
+/// scope. Created when the file is opened, like [`NetByteMeter`].
+#[derive(Clone, Debug)]
+pub struct WriteByteMeter {
+  quotas: WriteByteQuotas,
+  scope: PathBuf,
+  quota: u64,
+}
+
+impl WriteByteMeter {
+  pub fn charge(&self, bytes: u64, api_name: &str) -> Result<(), AnyError> {
+    let mut written = self.quotas.written.lock();
+    let written = written.entry(self.scope.clone()).or_default();
+    if written.saturating_add(bytes) <= self.quota {
+      *written += bytes;
+      return Ok(());
+    }
+    Err(custom_error(
+      "DOMExceptionQuotaExceededError",
+      format!(
+        "{api_name} exceeded the write quota of {} bytes for \"{}\" ({written} bytes already written)",
+        self.quota,
+        self.scope.display()
+      ),
+    ))
+  }
+
+  /// Gives back bytes that were charged but not written, like the rest of a
+  /// partial write.
+  pub fn refund(&self, bytes: u64) {
+    if let Some(written) = self.quotas.written.lock().get_mut(&self.scope) {
+      *written = written.saturating_sub(bytes);
+    }
+  }
+}
+
+/// Counts reads of the high resolution clock, split by whether they were
+/// served at full precision. Counts are shared between clones, so reads in
+/// workers add up with the ones in the main worker.
+#[derive(Clone, Debug, Default)]
+pub struct HrtimeUsage {
+  precise: Arc<AtomicU64>,
+  coarse: Arc<AtomicU64>,
+}
+
+impl PartialEq for HrtimeUsage {
+  fn eq(&self, _other: &Self) -> bool {
+    // usage is not part of the configuration
+    true
+  }
+}
+
+impl Eq for HrtimeUsage {}
+
+impl HrtimeUsage {
+  fn record(&self, precise: bool) {
+    let counter = if precise { &self.precise } else { &self.coarse };
+    counter.fetch_add(1, Ordering::Relaxed);
+  }
+
+  /// Reads served at full precision.
+  pub fn precise_reads(&self) -> u64 {
+    self.precise.load(Ordering::Relaxed)
+  }
+
+  /// Reads rounded to 2ms because hrtime was not granted.
+  pub fn coarse_reads(&self) -> u64 {
+    self.coarse.load(Ordering::Relaxed)
+  }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct EnvDescriptor(EnvVarName);
 
@@ -973,6 +1332,135 @@ impl AsRef<Path> for RunDescriptor {
   }
 }
 
+/// Splits an `--allow-run` entry like `git:status` into the command and the
+/// optional argument pattern.
+fn split_run_arg_pattern(entry: &str) -> (&str, Option<&str>) {
+  let is_separator = |c: char| c == '/' || c == '\\';
+  let Some(index) = entry.find(':') else {
+    return (entry, None);
+  };
+  let bytes = entry.as_bytes();
+  // a Windows drive letter (ex. `C:\git.exe`) is part of a path
+  let is_drive_letter = index == 1
+    && bytes[0].is_ascii_alphabetic()
+    && bytes.get(2).is_some_and(|b| matches!(b, b'\\' | b'/'));
+  if !is_drive_letter && !entry[..index].contains(is_separator) {
+    return (&entry[..index], Some(&entry[index + 1..]));
+  }
+  // The command is a path, which may contain colons in its directories
+  // (ex. `/opt/a:b/git:status`), so only look for the separator in its
+  // file name. The arguments start after the first whitespace at the latest.
+  let head_end = entry.find(char::is_whitespace).unwrap_or(entry.len());
+  let file_name_start = entry[..head_end]
+    .rfind(is_separator)
+    .map(|index| index + 1)
+    .unwrap_or(0);
+  match entry[file_name_start..head_end].find(':') {
+    Some(index) => {
+      let index = file_name_start + index;
+      (&entry[..index], Some(&entry[index + 1..]))
+    }
+    None => (entry, None),
+  }
+}
+
+/// `--allow-run=git:status,log` reads like `git log` is allowed, but could
+/// also mean a program called `log` is. Rather than guess, require entries
+/// without a pattern to come before the patterned ones
+/// (ex. `--allow-run=deno,git:status,git:log`).
+fn check_run_arg_pattern_order(
+  allow_list: &Option<Vec<String>>,
+) -> Result<(), AnyError> {
+  let mut last_patterned: Option<&str> = None;
+  for entry in allow_list.iter().flatten() {
+    match (split_run_arg_pattern(entry), last_patterned) {
+      ((_, Some(_)), _) => last_patterned = Some(entry),
+      ((cmd, None), Some(patterned)) => {
+        let (patterned_cmd, _) = split_run_arg_pattern(patterned);
+        return Err(type_error(format!(
+          "Ambiguous --allow-run entry \"{entry}\" after \"{patterned}\". Write \"{patterned_cmd}:{cmd}\" to allow it as arguments of \"{patterned_cmd}\", or list it before the entries with argument patterns to allow the program"
+        )));
+      }
+      ((_, None), None) => {}
+    }
+  }
+  Ok(())
+}
+
+/// Argument prefixes that commands allowed with `--allow-run=<cmd>:<args>`
+/// are restricted to. For example `--allow-run=git:status,git:log` only allows
+/// `git status ...` and `git log ...`. Commands that were also allowed
+/// without a pattern are not restricted.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RunArgsAllowlist(HashMap<RunDescriptor, Vec<Vec<String>>>);
+
+impl RunArgsAllowlist {
+  pub fn parse(allow_list: &Option<Vec<String>>) -> Result<Self, AnyError> {
+    let mut patterns: HashMap<RunDescriptor, Vec<Vec<String>>> = HashMap::new();
+    let mut unrestricted = HashSet::new();
+    for entry in allow_list.iter().flatten() {
+      let (cmd, maybe_pattern) = split_run_arg_pattern(entry);
+      if cmd.is_empty() {
+        return Err(AnyError::msg("Empty path is not allowed"));
+      }
+      let desc = RunDescriptor::from(cmd.to_string());
+      let mut descs = desc.aliases();
+      descs.push(desc);
+      match maybe_pattern {
+        Some(pattern) => {
+          let args = pattern
+            .split_whitespace()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>();
+          if args.is_empty() {
+            return Err(type_error(format!(
+              "Empty argument pattern in --allow-run entry \"{entry}\""
+            )));
+          }
+          for desc in descs {
+            patterns.entry(desc).or_default().push(args.clone());
+          }
+        }
+        None => unrestricted.extend(descs),
+      }
+    }
+    patterns.retain(|desc, _| !unrestricted.contains(desc));
+    Ok(Self(patterns))
+  }
+
+  pub fn check(&self, cmd: &str, args: &[String]) -> Result<(), AnyError> {
+    if self.0.is_empty() {
+      return Ok(());
+    }
+    let Some(patterns) = self.0.get(&RunDescriptor::from(cmd.to_string()))
+    else {
+      return Ok(());
+    };
+    let is_allowed = patterns.iter().any(|pattern| {
+      pattern.len() <= args.len()
+        && pattern.iter().zip(args).all(|(a, b)| a == b)
+    });
+    if is_allowed {
+      return Ok(());
+    }
+    let command_line = std::iter::once(cmd)
+      .chain(args.iter().map(|arg| arg.as_str()))
+      .collect::<Vec<_>>()
+      .join(" ");
+    let allowed = patterns
+      .iter()
+      .map(|pattern| pattern.join(" "))
+      .collect::<Vec<_>>()
+      .join(", ");
+    Err(custom_error(
+      "PermissionDenied",
+      format!(
+        "Requires run access to \"{command_line}\", but \"{cmd}\" is only allowed with arguments starting with: {allowed}"
+      ),
+    ))
+  }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct SysDescriptor(pub String);
 
@@ -1414,6 +1902,10 @@ pub struct Permissions {
   pub env: UnaryPermission<EnvDescriptor>,
   pub sys: UnaryPermission<SysDescriptor>,
   pub run: UnaryPermission<RunDescriptor>,
+  pub run_args: RunArgsAllowlist,
+  pub net_quotas: NetByteQuotas,
+  pub write_quotas: WriteByteQuotas,
+  pub hrtime_usage: HrtimeUsage,
   pub ffi: UnaryPermission<FfiDescriptor>,
   pub all: UnitPermission,
   pub hrtime: UnitPermission,
@@ -1484,6 +1976,37 @@ impl Permissions {
   }
 
   pub fn from_options(opts: &PermissionsOptions) -> Result<Self, AnyError> {
+    if let Some(entry) = opts
+      .deny_run
+      .iter()
+      .flatten()
+      .find(|entry| split_run_arg_pattern(entry).1.is_some())
+    {
+      return Err(type_error(format!(
+        "Argument patterns are only supported by --allow-run, found \"{entry}\" in --deny-run"
+      )));
+    }
+    if let Some(entry) = opts
+      .deny_net
+      .iter()
+      .flatten()
+      .find(|entry| entry.contains(":bytes="))
+    {
+      return Err(type_error(format!(
+        "Byte caps are only supported by --allow-net, found \"{entry}\" in --deny-net"
+      )));
+    }
+    if let Some(entry) = opts.deny_write.iter().flatten().find(|entry| {
+      entry
+        .to_str()
+        .is_some_and(|entry| entry.contains(":quota="))
+    }) {
+      return Err(type_error(format!(
+        "Quotas are only supported by --allow-write, found \"{}\" in --deny-write",
+        entry.display()
+      )));
+    }
+    check_run_arg_pattern_order(&opts.allow_run)?;
     Ok(Self {
       read: Permissions::new_unary(
         &opts.allow_read,
@@ -1515,6 +2038,10 @@ impl Permissions {
         &opts.deny_run,
         opts.prompt,
       )?,
+      run_args: RunArgsAllowlist::parse(&opts.allow_run)?,
+      net_quotas: NetByteQuotas::parse(&opts.allow_net)?,
+      write_quotas: WriteByteQuotas::parse(&opts.allow_write)?,
+      hrtime_usage: Default::default(),
       ffi: Permissions::new_unary(
         &opts.allow_ffi,
         &opts.deny_ffi,
@@ -1534,6 +2061,10 @@ impl Permissions {
       env: UnaryPermission::allow_all(),
       sys: UnaryPermission::allow_all(),
       run: UnaryPermission::allow_all(),
+      run_args: Default::default(),
+      net_quotas: Default::default(),
+      write_quotas: Default::default(),
+      hrtime_usage: Default::default(),
       ffi: UnaryPermission::allow_all(),
       all: Permissions::new_all(true),
       hrtime: Permissions::new_hrtime(true, false),
@@ -1558,6 +2089,10 @@ impl Permissions {
       env: Permissions::new_unary(&None, &None, prompt).unwrap(),
       sys: Permissions::new_unary(&None, &None, prompt).unwrap(),
       run: Permissions::new_unary(&None, &None, prompt).unwrap(),
+      run_args: Default::default(),
+      net_quotas: Default::default(),
+      write_quotas: Default::default(),
+      hrtime_usage: Default::default(),
       ffi: Permissions::new_unary(&None, &None, prompt).unwrap(),
       all: Permissions::new_all(false),
       hrtime: Permissions::new_hrtime(false, false),
@@ -1600,7 +2135,18 @@ impl PermissionsContainer {
 
   #[inline(always)]
   pub fn allow_hrtime(&mut self) -> bool {
-    self.0.lock().hrtime.check().is_ok()
+    let mut inner = self.0.lock();
+    let allowed = inner.hrtime.check().is_ok();
+    inner.hrtime_usage.record(allowed);
+    allowed
+  }
+
+  pub fn hrtime_state(&self) -> PermissionState {
+    self.0.lock().hrtime.query()
+  }
+
+  pub fn hrtime_usage(&self) -> HrtimeUsage {
+    self.0.lock().hrtime_usage.clone()
   }
 
   pub fn allow_all() -> Self {
@@ -1657,6 +2203,26 @@ impl PermissionsContainer {
     self.0.lock().write.check(path, Some(api_name))
   }
 
+  /// Charges `bytes` written to `path` against the `--allow-write` quota of
+  /// the enclosing path, if there is one.
+  pub fn check_write_bytes(
+    &mut self,
+    path: &Path,
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    self.0.lock().write_quotas.charge(path, bytes, api_name)
+  }
+
+  /// Returns a meter that charges the bytes later written to an open file at
+  /// `path`, or `None` when the path has no quota.
+  pub fn write_byte_meter(
+    &self,
+    path: &Path,
+  ) -> Result<Option<WriteByteMeter>, AnyError> {
+    self.0.lock().write_quotas.meter(path)
+  }
+
   #[inline(always)]
   pub fn check_write_with_api_name(
     &self,
@@ -1694,9 +2260,12 @@ impl PermissionsContainer {
   pub fn check_run(
     &mut self,
     cmd: &str,
+    args: &[String],
     api_name: &str,
   ) -> Result<(), AnyError> {
-    self.0.lock().run.check(cmd, Some(api_name))
+    let mut perms = self.0.lock();
+    perms.run.check(cmd, Some(api_name))?;
+    perms.run_args.check(cmd, args)
   }
 
   #[inline(always)]
@@ -1860,6 +2429,79 @@ impl PermissionsContainer {
     self.0.lock().net.check(&descriptor, Some(api_name))
   }
 
+  /// Checks access to the unix socket at `path`. Sockets denied with
+  /// `--deny-net` are always rejected. When `--allow-net` lists unix
+  /// sockets, only those may be used. Otherwise the socket needs read and
+  /// write access.
+  pub fn check_net_unix(
+    &mut self,
+    path: &Path,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    let mut inner = self.0.lock();
+    let descriptor = NetDescriptor(Host::Unix(resolve_from_cwd(path)?), None);
+    let is_scoped = inner
+      .net
+      .granted_list
+      .iter()
+      .any(|descriptor| matches!(descriptor.0, Host::Unix(_)));
+    if is_scoped || inner.net.is_flag_denied(Some(&descriptor)) {
+      return inner.net.check(&descriptor, Some(api_name));
+    }
+    inner.read.check(path, Some(api_name))?;
+    inner.write.check(path, Some(api_name))
+  }
+
+  /// Charges `bytes` of egress against the `--allow-net` byte cap of the
+  /// host, if it has one.
+  pub fn check_net_bytes<T: AsRef<str>>(
+    &mut self,
+    host: &(T, Option<u16>),
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    let hostname = host.0.as_ref().parse::<Host>()?;
+    let descriptor = NetDescriptor(hostname, host.1);
+    self
+      .0
+      .lock()
+      .net_quotas
+      .charge(&descriptor, bytes, api_name)
+  }
+
+  pub fn check_net_url_bytes(
+    &mut self,
+    url: &Url,
+    bytes: u64,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    let Some(host) = url.host_str() else {
+      return Ok(());
+    };
+    self.check_net_bytes(&(host, url.port_or_known_default()), bytes, api_name)
+  }
+
+  /// Returns a meter that charges the bytes later written to a connection
+  /// to `host`, or `None` when the host has no byte cap.
+  pub fn net_byte_meter<T: AsRef<str>>(
+    &self,
+    host: &(T, Option<u16>),
+  ) -> Result<Option<NetByteMeter>, AnyError> {
+    let hostname = host.0.as_ref().parse::<Host>()?;
+    let descriptor = NetDescriptor(hostname, host.1);
+    Ok(self.0.lock().net_quotas.meter(&descriptor))
+  }
+
+  pub fn net_url_byte_meter(
+    &self,
+    url: &Url,
+  ) -> Result<Option<NetByteMeter>, AnyError> {
+    let Some(host) = url.host_str() else {
+      return Ok(None);
+    };
+    self.net_byte_meter(&(host, url.port_or_known_default()))
+  }
+
   #[inline(always)]
   pub fn check_ffi(&mut self, path: Option<&Path>) -> Result<(), AnyError> {
     self.0.lock().ffi.check(path.unwrap(), None)
@@ -1904,7 +2546,7 @@ fn parse_net_list(
 ) -> Result<HashSet<NetDescriptor>, AnyError> {
   if let Some(v) = list {
     v.iter()
-      .map(|x| NetDescriptor::from_str(x))
+      .map(|x| NetDescriptor::from_str(split_net_byte_cap(x)?.0))
       .collect::<Result<HashSet<NetDescriptor>, AnyError>>()
   } else {
     Ok(HashSet::new())
@@ -1975,7 +2617,8 @@ fn parse_run_list(
       if s.is_empty() {
         return Err(AnyError::msg("Empty path is not allowed"));
       } else {
-        let desc = RunDescriptor::from(s.to_string());
+        let (cmd, _) = split_run_arg_pattern(s);
+        let desc = RunDescriptor::from(cmd.to_string());
         let aliases = desc.aliases();
         result.insert(desc);
         result.extend(aliases);
@@ -2310,6 +2953,11 @@ pub fn create_child_permissions(
   worker_perms.run = main_perms
     .run
     .create_child_permissions(child_permissions_arg.run)?;
+  // argument restrictions can only narrow access, so always inherit them
+  worker_perms.run_args = main_perms.run_args.clone();
+  worker_perms.net_quotas = main_perms.net_quotas.clone();
+  worker_perms.write_quotas = main_perms.write_quotas.clone();
+  worker_perms.hrtime_usage = main_perms.hrtime_usage.clone();
   worker_perms.ffi = main_perms
     .ffi
     .create_child_permissions(child_permissions_arg.ffi)?;
@@ -2445,6 +3093,39 @@ mod tests {
     assert!(perms.ffi.check(Path::new("/a/b"), None).is_err());
   }
 
+  #[test]
+  fn check_read_globs() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_read: Some(vec![
+        PathBuf::from("/data/**/*.json"),
+        PathBuf::from("/logs/*.log"),
+      ]),
+      deny_read: Some(vec![PathBuf::from("/data/secret/*.json")]),
+      ..Default::default()
+    })
+    .unwrap();
+
+    assert!(perms.read.check(Path::new("/data/a.json"), None).is_ok());
+    assert!(perms.read.check(Path::new("/data/b/c.json"), None).is_ok());
+    assert!(perms.read.check(Path::new("/logs/today.log"), None).is_ok());
+
+    // not matched by the globs
+    assert!(perms.read.check(Path::new("/data/a.txt"), None).is_err());
+    assert!(perms
+      .read
+      .check(Path::new("/logs/old/a.log"), None)
+      .is_err());
+    // the directories the matched files are in aren't granted
+    assert!(perms.read.check(Path::new("/data"), None).is_err());
+    assert!(perms.read.check(Path::new("/data/b"), None).is_err());
+    // denying takes precedence over a matching glob
+    assert!(perms
+      .read
+      .check(Path::new("/data/secret/key.json"), None)
+      .is_err());
+  }
+
   #[test]
   fn test_check_net_with_values() {
     set_prompter(Box::new(TestPrompter));
@@ -2746,6 +3427,10 @@ mod tests {
       sys: Permissions::new_unary(&Some(svec!["hostname"]), &None, false)
         .unwrap(),
       run: Permissions::new_unary(&Some(svec!["deno"]), &None, false).unwrap(),
+      run_args: Default::default(),
+      net_quotas: Default::default(),
+      write_quotas: Default::default(),
+      hrtime_usage: Default::default(),
       all: Permissions::new_all(false),
       hrtime: Permissions::new_hrtime(false, false),
     };
@@ -2774,6 +3459,10 @@ mod tests {
       sys: Permissions::new_unary(&None, &Some(svec!["hostname"]), false)
         .unwrap(),
       run: Permissions::new_unary(&None, &Some(svec!["deno"]), false).unwrap(),
+      run_args: Default::default(),
+      net_quotas: Default::default(),
+      write_quotas: Default::default(),
+      hrtime_usage: Default::default(),
       all: Permissions::new_all(false),
       hrtime: Permissions::new_hrtime(false, true),
     };
@@ -2812,6 +3501,10 @@ mod tests {
       .unwrap(),
       run: Permissions::new_unary(&Some(vec![]), &Some(svec!["deno"]), false)
         .unwrap(),
+      run_args: Default::default(),
+      net_quotas: Default::default(),
+      write_quotas: Default::default(),
+      hrtime_usage: Default::default(),
       all: Permissions::new_all(false),
       hrtime: Permissions::new_hrtime(true, true),
     };
@@ -2976,6 +3669,10 @@ mod tests {
       sys: Permissions::new_unary(&Some(svec!["hostname"]), &None, false)
         .unwrap(),
       run: Permissions::new_unary(&Some(svec!["deno"]), &None, false).unwrap(),
+      run_args: Default::default(),
+      net_quotas: Default::default(),
+      write_quotas: Default::default(),
+      hrtime_usage: Default::default(),
       all: Permissions::new_all(false),
       hrtime: Permissions::new_hrtime(false, true),
     };
@@ -3426,6 +4123,346 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_check_run_with_arg_patterns() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_run: Some(svec![
+          "deno",
+          "git:status",
+          "git:log",
+          "git:remote show"
+        ]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms.check_run("git", &svec!["status"], "api").is_ok());
+    assert!(perms
+      .check_run("git", &svec!["status", "--short"], "api")
+      .is_ok());
+    assert!(perms
+      .check_run("git", &svec!["remote", "show", "origin"], "api")
+      .is_ok());
+    assert!(perms.check_run("git", &svec!["log", "-1"], "api").is_ok());
+    assert!(perms.check_run("deno", &svec!["eval", "1"], "api").is_ok());
+
+    let err = perms
+      .check_run("git", &svec!["push", "origin"], "api")
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Requires run access to \"git push origin\", but \"git\" is only allowed with arguments starting with: status, log, remote show"
+    );
+    assert!(perms.check_run("git", &svec!["remote"], "api").is_err());
+    assert!(perms.check_run("git", &[], "api").is_err());
+
+    // an unrestricted entry for the same command takes precedence
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_run: Some(svec!["git", "git:status"]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms.check_run("git", &svec!["push"], "api").is_ok());
+
+    // a program after a patterned entry could be meant either way
+    let err = Permissions::from_options(&PermissionsOptions {
+      allow_run: Some(svec!["git:status", "log"]),
+      ..Default::default()
+    })
+    .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Ambiguous --allow-run entry \"log\" after \"git:status\". Write \"git:log\" to allow it as arguments of \"git\", or list it before the entries with argument patterns to allow the program"
+    );
+
+    assert!(Permissions::from_options(&PermissionsOptions {
+      deny_run: Some(svec!["git:push"]),
+      ..Default::default()
+    })
+    .is_err());
+  }
+
+  #[test]
+  fn test_split_run_arg_pattern() {
+    assert_eq!(split_run_arg_pattern("git"), ("git", None));
+    assert_eq!(split_run_arg_pattern("git:status"), ("git", Some("status")));
+    assert_eq!(
+      split_run_arg_pattern("git:log src/main.rs"),
+      ("git", Some("log src/main.rs"))
+    );
+    assert_eq!(
+      split_run_arg_pattern("/opt/a:b/bin/git"),
+      ("/opt/a:b/bin/git", None)
+    );
+    assert_eq!(
+      split_run_arg_pattern("/opt/a:b/bin/git:log src/"),
+      ("/opt/a:b/bin/git", Some("log src/"))
+    );
+    assert_eq!(
+      split_run_arg_pattern("C:\\Git\\git.exe"),
+      ("C:\\Git\\git.exe", None)
+    );
+    assert_eq!(
+      split_run_arg_pattern("C:\\Git\\git.exe:status"),
+      ("C:\\Git\\git.exe", Some("status"))
+    );
+  }
+
+  #[test]
+  fn test_check_net_bytes() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_net: Some(svec!["api.example.com:bytes=1KB", "deno.land"]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms
+      .check_net(&("api.example.com", Some(443)), "api")
+      .is_ok());
+    assert!(perms
+      .check_net_bytes(&("api.example.com", Some(443)), 999, "api")
+      .is_ok());
+    // reaching the cap exactly is allowed
+    assert!(perms
+      .check_net_bytes(&("api.example.com", Some(80)), 1, "api")
+      .is_ok());
+    let err = perms
+      .check_net_bytes(&("api.example.com", Some(443)), 1, "api")
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "api exceeded the egress cap of 1000 bytes for \"api.example.com\" (1000 bytes already sent)"
+    );
+    // once exceeded, new connections are refused too
+    assert!(perms
+      .check_net_bytes(&("api.example.com", Some(443)), 0, "api")
+      .is_err());
+    assert!(perms
+      .check_net_bytes(&("deno.land", Some(443)), 1_000_000, "api")
+      .is_ok());
+
+    assert!(Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(svec!["api.example.com:bytes=10XB"]),
+      ..Default::default()
+    })
+    .is_err());
+  }
+
+  #[test]
+  fn test_net_byte_meter() {
+    set_prompter(Box::new(TestPrompter));
+    let perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_net: Some(svec!["api.example.com:bytes=1KB", "deno.land"]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms
+      .net_byte_meter(&("deno.land", Some(443)))
+      .unwrap()
+      .is_none());
+    let meter = perms
+      .net_byte_meter(&("api.example.com", Some(443)))
+      .unwrap()
+      .unwrap();
+    assert!(meter.charge(800, "api").is_ok());
+    // only 500 of the 800 bytes were written
+    meter.refund(300);
+    assert!(meter.charge(500, "api").is_ok());
+    let err = meter.charge(1, "api").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "api exceeded the egress cap of 1000 bytes for \"api.example.com\" (1000 bytes already sent)"
+    );
+    // meters share their usage with the permissions
+    let url = Url::parse("https://api.example.com/upload").unwrap();
+    let meter = perms.net_url_byte_meter(&url).unwrap().unwrap();
+    assert!(meter.charge(0, "api").is_err());
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn test_check_net_unix() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_net: Some(svec!["unix:/var/run/app.sock", "deno.land"]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms
+      .check_net_unix(Path::new("/var/run/app.sock"), "api")
+      .is_ok());
+    let err = perms
+      .check_net_unix(Path::new("/var/run/other.sock"), "api")
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Requires net access to \"unix:/var/run/other.sock\", run again with the --allow-net flag"
+    );
+    assert!(perms.check_net(&("deno.land", None), "api").is_ok());
+
+    // without unix entries, sockets need read and write access
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_net: Some(svec!["deno.land"]),
+        allow_read: Some(vec![PathBuf::from("/var/run")]),
+        allow_write: Some(vec![PathBuf::from("/var/run")]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms
+      .check_net_unix(Path::new("/var/run/other.sock"), "api")
+      .is_ok());
+    assert!(perms
+      .check_net_unix(Path::new("/tmp/app.sock"), "api")
+      .is_err());
+
+    // denied sockets are rejected even with read and write access
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_read: Some(vec![PathBuf::from("/var/run")]),
+        allow_write: Some(vec![PathBuf::from("/var/run")]),
+        deny_net: Some(svec!["unix:/var/run/app.sock"]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    let err = perms
+      .check_net_unix(Path::new("/var/run/app.sock"), "api")
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Requires net access to \"unix:/var/run/app.sock\", run again with the --allow-net flag"
+    );
+    assert!(perms
+      .check_net_unix(Path::new("/var/run/other.sock"), "api")
+      .is_ok());
+  }
+
+  #[test]
+  fn test_check_write_bytes() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_write: Some(vec![
+          PathBuf::from("/a:quota=1KB"),
+          PathBuf::from("/a/b:quota=100"),
+          PathBuf::from("/c"),
+        ]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms.check_write(Path::new("/a/file"), "api").is_ok());
+    assert!(perms
+      .check_write_bytes(Path::new("/a/file"), 999, "api")
+      .is_ok());
+    // reaching the quota exactly is allowed
+    assert!(perms
+      .check_write_bytes(Path::new("/a/other"), 1, "api")
+      .is_ok());
+    let err = perms
+      .check_write_bytes(Path::new("/a/file"), 1, "api")
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      format!(
+        "api exceeded the write quota of 1000 bytes for \"{}\" (1000 bytes already written)",
+        resolve_from_cwd(Path::new("/a")).unwrap().display()
+      )
+    );
+    // the most specific quota applies
+    assert!(perms
+      .check_write_bytes(Path::new("/a/b/file"), 100, "api")
+      .is_ok());
+    assert!(perms
+      .check_write_bytes(Path::new("/a/b/file"), 1, "api")
+      .is_err());
+    assert!(perms
+      .check_write_bytes(Path::new("/c/file"), 1_000_000, "api")
+      .is_ok());
+
+    assert!(Permissions::from_options(&PermissionsOptions {
+      allow_write: Some(vec![PathBuf::from("/a:quota=10XB")]),
+      ..Default::default()
+    })
+    .is_err());
+    assert!(Permissions::from_options(&PermissionsOptions {
+      deny_write: Some(vec![PathBuf::from("/a:quota=10MB")]),
+      ..Default::default()
+    })
+    .is_err());
+  }
+
+  #[test]
+  fn test_write_byte_meter() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_write: Some(vec![
+          PathBuf::from("/a:quota=100"),
+          PathBuf::from("/c"),
+        ]),
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(perms
+      .write_byte_meter(Path::new("/c/file"))
+      .unwrap()
+      .is_none());
+    let meter = perms
+      .write_byte_meter(Path::new("/a/file"))
+      .unwrap()
+      .unwrap();
+    assert!(meter.charge(60, "api").is_ok());
+    // the meter shares its usage with the other writes to the scope
+    assert!(perms
+      .check_write_bytes(Path::new("/a/other"), 41, "api")
+      .is_err());
+    // refunded bytes can be written again
+    meter.refund(10);
+    assert!(perms
+      .check_write_bytes(Path::new("/a/other"), 50, "api")
+      .is_ok());
+    assert!(meter.charge(1, "api").is_err());
+  }
+
+  #[test]
+  fn test_hrtime_usage() {
+    set_prompter(Box::new(TestPrompter));
+    // --deny-hrtime wins over -A
+    let mut perms = PermissionsContainer::new(
+      Permissions::from_options(&PermissionsOptions {
+        allow_all: true,
+        allow_hrtime: true,
+        deny_hrtime: true,
+        ..Default::default()
+      })
+      .unwrap(),
+    );
+    assert!(!perms.allow_hrtime());
+    assert!(!perms.allow_hrtime());
+    assert_eq!(perms.hrtime_state(), PermissionState::Denied);
+    let usage = perms.hrtime_usage();
+    assert_eq!(usage.coarse_reads(), 2);
+    assert_eq!(usage.precise_reads(), 0);
+
+    let mut perms = PermissionsContainer::allow_all();
+    assert!(perms.allow_hrtime());
+    assert_eq!(perms.hrtime_usage().precise_reads(), 1);
+  }
+
   #[test]
   fn test_create_child_permissions() {
     set_prompter(Box::new(TestPrompter));
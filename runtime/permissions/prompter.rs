@@ -10,6 +10,8 @@ use std::io::IsTerminal;
 use std::io::StderrLock;
 use std::io::StdinLock;
 use std::io::Write as IoWrite;
+use std::time::Duration;
+use std::time::Instant;
 
 use crate::is_standalone;
 
@@ -54,6 +56,9 @@ static MAYBE_BEFORE_PROMPT_CALLBACK: Lazy<Mutex<Option<PromptCallback>>> =
 static MAYBE_AFTER_PROMPT_CALLBACK: Lazy<Mutex<Option<PromptCallback>>> =
   Lazy::new(|| Mutex::new(None));
 
+static MAYBE_PROMPT_TIMEOUT: Lazy<Mutex<Option<Duration>>> =
+  Lazy::new(|| Mutex::new(None));
+
 pub fn permission_prompt(
   message: &str,
   flag: &str,
@@ -80,6 +85,12 @@ pub fn set_prompt_callbacks(
   *MAYBE_AFTER_PROMPT_CALLBACK.lock() = Some(after_callback);
 }
 
+/// Denies prompts that aren't answered within `timeout`, so that a run
+/// doesn't hang on a terminal nobody is looking at.
+pub fn set_prompt_timeout(timeout: Option<Duration>) {
+  *MAYBE_PROMPT_TIMEOUT.lock() = timeout;
+}
+
 pub type PromptCallback = Box<dyn FnMut() + Send + Sync>;
 
 pub trait PermissionPrompter: Send + Sync {
@@ -250,6 +261,40 @@ fn clear_stdin(
   }
 }
 
+/// Waits until stdin has input to read. Returns `false` if there was none
+/// within `timeout`.
+#[cfg(unix)]
+fn wait_for_stdin(timeout: Duration) -> bool {
+  let mut poll_fd = libc::pollfd {
+    fd: 0,
+    events: libc::POLLIN,
+    revents: 0,
+  };
+  let timeout_ms = timeout.as_millis().min(libc::c_int::MAX as u128);
+  // SAFETY: `poll_fd` is a valid pollfd for the duration of the call
+  let result =
+    unsafe { libc::poll(&mut poll_fd, 1, timeout_ms as libc::c_int) };
+  // on error let the read fail instead
+  result != 0
+}
+
+/// Waits until stdin has input to read. Returns `false` if there was none
+/// within `timeout`.
+#[cfg(not(unix))]
+fn wait_for_stdin(timeout: Duration) -> bool {
+  use winapi::shared::winerror::WAIT_TIMEOUT;
+  use winapi::um::processenv::GetStdHandle;
+  use winapi::um::synchapi::WaitForSingleObject;
+  use winapi::um::winbase::STD_INPUT_HANDLE;
+
+  let timeout_ms = timeout.as_millis().min(u32::MAX as u128 - 1);
+  // SAFETY: winapi calls
+  unsafe {
+    let stdin = GetStdHandle(STD_INPUT_HANDLE);
+    WaitForSingleObject(stdin, timeout_ms as u32) != WAIT_TIMEOUT
+  }
+}
+
 // Clear n-lines in terminal and move cursor to the beginning of the line.
 fn clear_n_lines(stderr_lock: &mut StderrLock, n: usize) {
   write!(stderr_lock, "\x1B[{n}A\x1B[0J").unwrap();
@@ -353,6 +398,9 @@ impl PermissionPrompter for TtyPrompter {
       stderr_lock.write_all(output.as_bytes()).unwrap();
     }
 
+    let deadline = MAYBE_PROMPT_TIMEOUT
+      .lock()
+      .map(|timeout| Instant::now() + timeout);
     let value = loop {
       // Clear stdin each time we loop around in case the user accidentally pasted
       // multiple lines or otherwise did something silly to generate a torrent of
@@ -364,6 +412,20 @@ impl PermissionPrompter for TtyPrompter {
         return PromptResponse::Deny; // don't grant permission if this fails
       }
 
+      if let Some(deadline) = deadline {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if !wait_for_stdin(remaining) {
+          clear_n_lines(
+            &mut stderr_lock,
+            if api_name.is_some() { 5 } else { 4 },
+          );
+          let msg =
+            format!("Denied {message}, the prompt was not answered in time.");
+          writeln!(stderr_lock, "❌ {}", colors::bold(&msg)).unwrap();
+          break PromptResponse::Deny;
+        }
+      }
+
       let mut input = String::new();
       let result = stdin_lock.read_line(&mut input);
       let input = input.trim_end_matches(|c| c == '\r' || c == '\n');
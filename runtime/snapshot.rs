@@ -28,6 +28,23 @@ impl deno_websocket::WebSocketPermissions for Permissions {
   ) -> Result<(), deno_core::error::AnyError> {
     unreachable!("snapshotting!")
   }
+
+  fn check_net_url_bytes(
+    &mut self,
+    _url: &deno_core::url::Url,
+    _bytes: u64,
+    _api_name: &str,
+  ) -> Result<(), deno_core::error::AnyError> {
+    unreachable!("snapshotting!")
+  }
+
+  fn net_url_byte_meter(
+    &mut self,
+    _url: &deno_core::url::Url,
+  ) -> Result<Option<deno_permissions::NetByteMeter>, deno_core::error::AnyError>
+  {
+    unreachable!("snapshotting!")
+  }
 }
 
 impl deno_web::TimersPermission for Permissions {
@@ -45,6 +62,23 @@ impl deno_fetch::FetchPermissions for Permissions {
     unreachable!("snapshotting!")
   }
 
+  fn check_net_url_bytes(
+    &mut self,
+    _url: &deno_core::url::Url,
+    _bytes: u64,
+    _api_name: &str,
+  ) -> Result<(), deno_core::error::AnyError> {
+    unreachable!("snapshotting!")
+  }
+
+  fn net_url_byte_meter(
+    &mut self,
+    _url: &deno_core::url::Url,
+  ) -> Result<Option<deno_permissions::NetByteMeter>, deno_core::error::AnyError>
+  {
+    unreachable!("snapshotting!")
+  }
+
   fn check_read(
     &mut self,
     _p: &Path,
@@ -112,6 +146,31 @@ impl deno_net::NetPermissions for Permissions {
     unreachable!("snapshotting!")
   }
 
+  fn check_net_bytes<T: AsRef<str>>(
+    &mut self,
+    _host: &(T, Option<u16>),
+    _bytes: u64,
+    _api_name: &str,
+  ) -> Result<(), deno_core::error::AnyError> {
+    unreachable!("snapshotting!")
+  }
+
+  fn net_byte_meter<T: AsRef<str>>(
+    &mut self,
+    _host: &(T, Option<u16>),
+  ) -> Result<Option<deno_permissions::NetByteMeter>, deno_core::error::AnyError>
+  {
+    unreachable!("snapshotting!")
+  }
+
+  fn check_net_unix(
+    &mut self,
+    _p: &Path,
+    _api_name: &str,
+  ) -> Result<(), deno_core::error::AnyError> {
+    unreachable!("snapshotting!")
+  }
+
   fn check_read(
     &mut self,
     _p: &Path,
@@ -190,6 +249,13 @@ impl deno_fs::FsPermissions for Permissions {
   ) -> Result<(), AnyError> {
     unreachable!("snapshotting!")
   }
+
+  fn write_byte_meter(
+    &self,
+    _path: &Path,
+  ) -> Result<Option<deno_permissions::WriteByteMeter>, AnyError> {
+    unreachable!("snapshotting!")
+  }
 }
 
 impl deno_kv::sqlite::SqliteDbHandlerPermissions for Permissions {
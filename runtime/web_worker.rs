@@ -35,6 +35,7 @@ use deno_core::ModuleCodeString;
 use deno_core::ModuleId;
 use deno_core::ModuleLoader;
 use deno_core::ModuleSpecifier;
+use deno_core::OpMetricsFactoryFn;
 use deno_core::PollEventLoopOptions;
 use deno_core::RuntimeOptions;
 use deno_core::SharedArrayBufferStore;
@@ -378,8 +379,12 @@ pub struct WebWorkerOptions {
   pub stdio: Stdio,
   pub feature_checker: Arc<FeatureChecker>,
   pub strace_ops: Option<Vec<String>>,
+  pub op_metrics_factory_fn: Option<OpMetricsFactoryFn>,
   pub close_on_idle: bool,
   pub maybe_worker_metadata: Option<WorkerMetadata>,
+  /// Limit of the body bytes `fetch()` reads from the responses of a
+  /// redirect chain.
+  pub max_redirect_bytes: Option<u64>,
 }
 
 impl WebWorker {
@@ -444,6 +449,7 @@ impl WebWorker {
             .unsafely_ignore_certificate_errors
             .clone(),
           file_fetch_handler: Rc::new(deno_fetch::FsFetchHandler),
+          max_redirect_bytes: options.max_redirect_bytes,
           ..Default::default()
         },
       ),
@@ -537,6 +543,7 @@ impl WebWorker {
     let (op_summary_metrics, op_metrics_factory_fn) = create_op_metrics(
       options.bootstrap.enable_op_summary_metrics,
       options.strace_ops,
+      options.op_metrics_factory_fn,
     );
     let import_assertions_support = if options.bootstrap.future {
       deno_core::ImportAssertionsSupport::Error
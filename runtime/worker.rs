@@ -106,6 +106,43 @@ impl ExitCode {
   }
 }
 
+/// Put in the op state when `Deno.exit()` should stop the running script
+/// rather than end the process, so that pending cleanup can still finish.
+#[derive(Clone)]
+pub struct ExitDrain {
+  isolate_handle: v8::IsolateHandle,
+  requested: Arc<AtomicBool>,
+}
+
+impl ExitDrain {
+  /// Stops the running script. Returns `false` if an exit was already
+  /// requested, in which case the process should exit right away.
+  pub fn request(&self) -> bool {
+    if self.requested.swap(true, Relaxed) {
+      return false;
+    }
+    self.isolate_handle.terminate_execution();
+    true
+  }
+
+  pub fn is_requested(&self) -> bool {
+    self.requested.load(Relaxed)
+  }
+}
+
+/// Put in the op state to run callbacks before `Deno.exit()` ends the
+/// process, which skips destructors.
+#[derive(Default)]
+pub struct ExitHooks(Vec<Box<dyn FnOnce()>>);
+
+impl ExitHooks {
+  pub fn run(self) {
+    for hook in self.0 {
+      hook();
+    }
+  }
+}
+
 /// This worker is created and used by almost all
 /// subcommands in Deno executable.
 ///
@@ -171,6 +208,8 @@ pub struct WorkerOptions {
   pub should_wait_for_inspector_session: bool,
   /// If Some, print a low-level trace output for ops matching the given patterns.
   pub strace_ops: Option<Vec<String>>,
+  /// Op metrics to collect in addition to the ones for `strace_ops`.
+  pub op_metrics_factory_fn: Option<OpMetricsFactoryFn>,
 
   /// Allows to map error type to a string "class" used to represent
   /// error in JavaScript.
@@ -199,6 +238,14 @@ pub struct WorkerOptions {
 
   /// V8 code cache for module and script source code.
   pub v8_code_cache: Option<Arc<dyn CodeCache>>,
+
+  /// If true, `Deno.exit()` stops the script instead of ending the process.
+  /// See [`MainWorker::drain_after_exit`].
+  pub exit_drain: bool,
+
+  /// Limit of the body bytes `fetch()` reads from the responses of a
+  /// redirect chain.
+  pub max_redirect_bytes: Option<u64>,
 }
 
 impl Default for WorkerOptions {
@@ -215,6 +262,7 @@ impl Default for WorkerOptions {
       should_break_on_first_statement: Default::default(),
       should_wait_for_inspector_session: Default::default(),
       strace_ops: Default::default(),
+      op_metrics_factory_fn: Default::default(),
       compiled_wasm_module_store: Default::default(),
       shared_array_buffer_store: Default::default(),
       maybe_inspector_server: Default::default(),
@@ -233,6 +281,8 @@ impl Default for WorkerOptions {
       stdio: Default::default(),
       feature_checker: Default::default(),
       v8_code_cache: Default::default(),
+      exit_drain: false,
+      max_redirect_bytes: None,
     }
   }
 }
@@ -240,6 +290,7 @@ impl Default for WorkerOptions {
 pub fn create_op_metrics(
   enable_op_summary_metrics: bool,
   strace_ops: Option<Vec<String>>,
+  extra_op_metrics_factory_fn: Option<OpMetricsFactoryFn>,
 ) -> (
   Option<Rc<OpMetricsSummaryTracker>>,
   Option<OpMetricsFactoryFn>,
@@ -290,6 +341,13 @@ pub fn create_op_metrics(
     }));
   }
 
+  if let Some(extra) = extra_op_metrics_factory_fn {
+    op_metrics_factory_fn = Some(match op_metrics_factory_fn {
+      Some(f) => merge_op_metrics(f, extra),
+      None => extra,
+    });
+  }
+
   if enable_op_summary_metrics {
     let summary = Rc::new(OpMetricsSummaryTracker::default());
     let summary_metrics = summary.clone().op_metrics_factory_fn(|_| true);
@@ -335,6 +393,7 @@ impl MainWorker {
     let (op_summary_metrics, op_metrics_factory_fn) = create_op_metrics(
       options.bootstrap.enable_op_summary_metrics,
       options.strace_ops,
+      options.op_metrics_factory_fn,
     );
 
     // Permissions: many ops depend on this
@@ -366,6 +425,7 @@ impl MainWorker {
             .unsafely_ignore_certificate_errors
             .clone(),
           file_fetch_handler: Rc::new(deno_fetch::FsFetchHandler),
+          max_redirect_bytes: options.max_redirect_bytes,
           ..Default::default()
         },
       ),
@@ -657,6 +717,14 @@ impl MainWorker {
       )
     };
 
+    if options.exit_drain {
+      let exit_drain = ExitDrain {
+        isolate_handle: js_runtime.v8_isolate().thread_safe_handle(),
+        requested: Default::default(),
+      };
+      js_runtime.op_state().borrow_mut().put(exit_drain);
+    }
+
     Self {
       js_runtime,
       should_break_on_first_statement: options.should_break_on_first_statement,
@@ -817,6 +885,40 @@ impl MainWorker {
       .await
   }
 
+  /// Returns whether `Deno.exit()` stopped the script so that the event
+  /// loop can be drained.
+  pub fn exit_drain_requested(&self) -> bool {
+    self
+      .js_runtime
+      .op_state()
+      .borrow()
+      .try_borrow::<ExitDrain>()
+      .is_some_and(|exit_drain| exit_drain.is_requested())
+  }
+
+  /// Resumes the event loop after `Deno.exit()` stopped the script, so that
+  /// cleanup started by the program (for example from an "unload" listener)
+  /// can finish. Gives up after `grace`.
+  pub async fn drain_after_exit(&mut self, grace: Duration) {
+    self.js_runtime.v8_isolate().cancel_terminate_execution();
+    match tokio::time::timeout(grace, self.run_event_loop(false)).await {
+      Ok(Ok(())) => {}
+      Ok(Err(err)) => debug!("Error while draining after exit: {err:#}"),
+      Err(_) => debug!("Exit drain did not finish within {grace:?}"),
+    }
+  }
+
+  /// Adds a callback that runs when `Deno.exit()` ends the process, so that
+  /// state which is otherwise saved by a destructor is saved then too.
+  pub fn add_exit_hook(&mut self, hook: impl FnOnce() + 'static) {
+    let op_state = self.js_runtime.op_state();
+    let mut op_state = op_state.borrow_mut();
+    if !op_state.has::<ExitHooks>() {
+      op_state.put(ExitHooks::default());
+    }
+    op_state.borrow_mut::<ExitHooks>().0.push(Box::new(hook));
+  }
+
   /// Return exit code set by the executed code (either in main worker
   /// or one of child web workers).
   pub fn exit_code(&self) -> i32 {
@@ -123,6 +123,12 @@ pub struct BootstrapOptions {
   // Used by `deno serve`
   pub serve_port: Option<u16>,
   pub serve_host: Option<String>,
+  /// Evaluate CommonJS modules in strict mode.
+  pub force_strict: bool,
+  /// Throw when a deprecated API is used instead of warning.
+  pub deprecated_api_error: bool,
+  /// Throw when a warning matches this case-insensitive pattern.
+  pub abort_on_warning: Option<String>,
 }
 
 impl Default for BootstrapOptions {
@@ -161,6 +167,9 @@ impl Default for BootstrapOptions {
       mode: WorkerExecutionMode::None,
       serve_port: Default::default(),
       serve_host: Default::default(),
+      force_strict: false,
+      deprecated_api_error: false,
+      abort_on_warning: None,
     }
   }
 }
@@ -210,6 +219,12 @@ struct BootstrapV8<'a>(
   Option<bool>,
   // serve worker count
   Option<usize>,
+  // force strict
+  bool,
+  // deprecated api error
+  bool,
+  // abort on warning
+  Option<&'a str>,
 );
 
 impl BootstrapOptions {
@@ -240,6 +255,9 @@ impl BootstrapOptions {
       self.serve_host.as_deref(),
       serve_is_main,
       serve_worker_count,
+      self.force_strict,
+      self.deprecated_api_error,
+      self.abort_on_warning.as_deref(),
     );
 
     bootstrap.serialize(ser).unwrap()
@@ -20,6 +20,27 @@ fn eval_p() {
   assert_eq!("3", stdout_str);
 }
 
+#[test]
+fn eval_p_completion_value() {
+  let eval_print = |code: &str| {
+    let output = util::deno_cmd()
+      .arg("eval")
+      .arg("-p")
+      .arg(code)
+      .stdout_piped()
+      .spawn()
+      .unwrap()
+      .wait_with_output()
+      .unwrap();
+    assert!(output.status.success());
+    util::strip_ansi_codes(std::str::from_utf8(&output.stdout).unwrap().trim())
+      .to_string()
+  };
+  assert_eq!(eval_print("const a = 1; a + 2"), "3");
+  assert_eq!(eval_print("const a = 1;\na + 2;\n// done"), "3");
+  assert_eq!(eval_print("let x = 1"), "undefined");
+}
+
 // Make sure that snapshot flags don't affect runtime.
 #[test]
 fn eval_randomness() {
@@ -770,6 +770,77 @@ fn deno_run_bin_lockfile() {
   assert!(temp_dir.path().join("deno.lock").exists());
 }
 
+#[test]
+fn deno_run_print_bin() {
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+  let output = context
+    .new_command()
+    .args("run --quiet --print-bin npm:@denotest/bin/cli-esm this is a test")
+    .run();
+  output.assert_exit_code(0);
+  // the bin script is not executed
+  let bin_path = std::path::PathBuf::from(output.combined_output().trim());
+  assert!(bin_path.is_absolute());
+  assert!(bin_path.exists());
+  assert!(bin_path.ends_with("@denotest/bin/1.0.0/cli.mjs"));
+}
+
+#[test]
+fn deno_run_print_entrypoint() {
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "throw new Error('should not run');");
+  temp_dir.write(
+    "package.json",
+    r#"{ "dependencies": { "@denotest/bin": "1.0.0" } }"#,
+  );
+
+  // relative file
+  let output = context
+    .new_command()
+    .args("run --quiet --print-entrypoint ./main.ts")
+    .run();
+  output.assert_exit_code(0);
+  assert_eq!(
+    output.combined_output().trim(),
+    temp_dir.path().join("main.ts").url_file().as_str()
+  );
+
+  // npm: bin
+  let output = context
+    .new_command()
+    .args("run --quiet --print-entrypoint npm:@denotest/bin/cli-esm")
+    .run();
+  output.assert_exit_code(0);
+  let url = output.combined_output().trim().to_string();
+  assert!(url.starts_with("file:///"), "{}", url);
+  assert!(url.ends_with("/@denotest/bin/1.0.0/cli.mjs"), "{}", url);
+
+  // bare specifier resolved via the package.json
+  let output = context
+    .new_command()
+    .args("run --quiet --print-entrypoint @denotest/bin/cli-esm")
+    .run();
+  output.assert_exit_code(0);
+  let url = output.combined_output().trim().to_string();
+  assert!(url.starts_with("file:///"), "{}", url);
+  assert!(url.ends_with("/@denotest/bin/1.0.0/cli.mjs"), "{}", url);
+}
+
+itest!(deno_run_print_bin_no_bin_entry {
+  args: "run --quiet --print-bin npm:@denotest/bin/non-existent",
+  output_str: Some(concat!(
+    "error: Failed resolving binary export. '[WILDCARD]package.json' did not have a bin entry for 'non-existent'\n",
+    "\n",
+    "Possibilities:\n",
+    "[WILDCARD] * npm:@denotest/bin@1.0.0/cli-esm\n",
+    "[WILDCARD]",
+  )),
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+  exit_code: 1,
+});
+
 itest!(deno_run_non_existent {
   args: "run npm:mkdirp@0.5.125",
   output: "npm/deno_run_non_existent.out",
@@ -1197,7 +1268,7 @@ fn lock_file_missing_top_level_package() {
   assert_eq!(
     stderr,
     concat!(
-      "error: failed reading lockfile 'deno.lock'\n",
+      "error: failed reading lockfile 'deno.lock'. You can recreate it with `deno cache --lock=deno.lock --lock-write main.ts`\n",
       "\n",
       "Caused by:\n",
       "    0: The lockfile is corrupt. Remove the lockfile to regenerate it.\n",
@@ -1574,6 +1645,118 @@ fn peer_deps_with_copied_folders_and_lockfile() {
   );
 }
 
+#[test]
+fn node_modules_deterministic_layout() {
+  fn collect_tree(
+    dir: &std::path::Path,
+    root: &std::path::Path,
+    entries: &mut Vec<String>,
+  ) {
+    let mut dir_entries = std::fs::read_dir(dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().path())
+      .collect::<Vec<_>>();
+    dir_entries.sort();
+    for path in dir_entries {
+      let relative_path = path
+        .strip_prefix(root)
+        .unwrap()
+        .to_string_lossy()
+        .replace('\\', "/");
+      let file_type = std::fs::symlink_metadata(&path).unwrap().file_type();
+      if file_type.is_symlink() {
+        let target = std::fs::read_link(&path).unwrap();
+        entries.push(format!(
+          "{} -> {}",
+          relative_path,
+          target.to_string_lossy().replace('\\', "/")
+        ));
+      } else if file_type.is_dir() {
+        entries.push(format!("{}/", relative_path));
+        collect_tree(&path, root, entries);
+      } else {
+        entries.push(relative_path);
+      }
+    }
+  }
+
+  let context = TestContextBuilder::for_npm()
+    .use_copy_temp_dir("npm/peer_deps_with_copied_folders")
+    .cwd("npm/peer_deps_with_copied_folders")
+    .build();
+  let temp_dir = context.temp_dir();
+  let temp_dir_sub_path =
+    temp_dir.path().join("npm/peer_deps_with_copied_folders");
+  temp_dir.write("npm/peer_deps_with_copied_folders/deno.json", "{}");
+  let node_modules_path = temp_dir_sub_path.join("node_modules");
+  let read_tree = || {
+    let mut entries = Vec::new();
+    collect_tree(
+      node_modules_path.as_path(),
+      node_modules_path.as_path(),
+      &mut entries,
+    );
+    entries
+  };
+
+  // resolve from the registry
+  let output = context
+    .new_command()
+    .args("run -A --node-modules-dir --nm-layout=deterministic main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("[WILDCARD]1\n2\n");
+  let first_tree = read_tree();
+  // the copy folder is named after its peer dependencies
+  assert!(!node_modules_path
+    .join(".deno/@denotest+peer-dep-test-grandchild@1.0.0_1")
+    .exists());
+  assert!(first_tree.iter().any(|entry| entry
+    .starts_with(".deno/@denotest+peer-dep-test-grandchild@1.0.0_")));
+
+  // resolve from the lockfile this time
+  node_modules_path.remove_dir_all();
+  let output = context
+    .new_command()
+    .args("run -A --node-modules-dir --nm-layout=deterministic main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("[WILDCARD]1\n2\n");
+  assert_eq!(read_tree(), first_tree);
+
+  // later runs without the flag keep using the recorded layout
+  let output = context
+    .new_command()
+    .args("run -A --node-modules-dir main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("[WILDCARD]1\n2\n");
+  assert_eq!(read_tree(), first_tree);
+  assert_eq!(
+    node_modules_path.join(".deno/.layout").read_to_string(),
+    "deterministic"
+  );
+
+  // switching the layout replaces the tree instead of adding a second one
+  let output = context
+    .new_command()
+    .args("run -A --node-modules-dir --nm-layout=default main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("[WILDCARD]1\n2\n");
+  let default_tree = read_tree();
+  assert!(node_modules_path
+    .join(".deno/@denotest+peer-dep-test-grandchild@1.0.0_1")
+    .exists());
+  assert!(!default_tree.iter().any(|entry| entry
+    .starts_with(".deno/@denotest+peer-dep-test-grandchild@1.0.0_")
+    && !entry.starts_with(".deno/@denotest+peer-dep-test-grandchild@1.0.0_1")));
+  assert_eq!(
+    node_modules_path.join(".deno/.layout").read_to_string(),
+    "default"
+  );
+}
+
 itest!(info_peer_deps {
   args: "info --quiet npm/peer_deps_with_copied_folders/main.ts",
   output: "npm/peer_deps_with_copied_folders/main_info.out",
@@ -1994,6 +2177,58 @@ fn binary_package_with_optional_dependencies() {
   }
 }
 
+#[test]
+fn binary_package_with_npm_target_os() {
+  let context = TestContextBuilder::for_npm()
+    .use_copy_temp_dir("npm/binary_package")
+    .cwd("npm/binary_package")
+    .build();
+
+  let temp_dir = context.temp_dir();
+  let project_path = temp_dir.path().join("npm/binary_package");
+
+  // resolve for an operating system other than the host's
+  let (target_os, target_package, host_package) = if cfg!(windows) {
+    ("linux", "binary-package-linux", "binary-package-windows")
+  } else {
+    ("win32", "binary-package-windows", "binary-package-linux")
+  };
+
+  context
+    .new_command()
+    .args_vec([
+      "cache",
+      "--node-modules-dir",
+      &format!("--npm-target-os={}", target_os),
+      "main.js",
+    ])
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  assert!(project_path
+    .join(format!(
+      "node_modules/.deno/@denotest+{}@1.0.0",
+      target_package
+    ))
+    .exists());
+  assert!(!project_path
+    .join(format!(
+      "node_modules/.deno/@denotest+{}@1.0.0",
+      host_package
+    ))
+    .exists());
+  assert!(!project_path
+    .join("node_modules/.deno/@denotest+binary-package-mac@1.0.0")
+    .exists());
+  assert!(project_path
+    .join(format!(
+      "node_modules/.deno/@denotest+binary-package@1.0.0/node_modules/@denotest/{}",
+      target_package
+    ))
+    .exists());
+}
+
 #[test]
 fn node_modules_dir_config_file() {
   let test_context = TestContextBuilder::for_npm().use_temp_cwd().build();
@@ -2930,3 +3165,69 @@ async fn test_private_npm_registry() {
   let resp = client.execute(req).await.unwrap();
   assert_eq!(resp.status(), reqwest::StatusCode::OK);
 }
+
+#[test]
+fn run_with_npm_snapshot() {
+  // create the snapshot from the test registry
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path();
+  temp_dir.join("make_snapshot.ts").write(
+    r#"const registry = Deno.env.get("NPM_CONFIG_REGISTRY")!;
+const name = "@denotest/esm-basic";
+const info = await (await fetch(new URL(name, registry))).json();
+const tarball = new Uint8Array(
+  await (await fetch(info.versions["1.0.0"].dist.tarball)).arrayBuffer(),
+);
+let binary = "";
+for (const byte of tarball) {
+  binary += String.fromCharCode(byte);
+}
+Deno.writeTextFileSync(
+  "snapshot.json",
+  JSON.stringify({
+    packages: { [name]: info },
+    tarballs: { [`${name}@1.0.0`]: btoa(binary) },
+  }),
+);
+"#,
+  );
+  context
+    .new_command()
+    .args("run -A make_snapshot.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  let snapshot = temp_dir.join("snapshot.json").read_to_string();
+
+  // then run with a registry that can't be reached and an empty cache
+  let context = TestContextBuilder::new()
+    .use_temp_cwd()
+    .env("NPM_CONFIG_REGISTRY", "http://localhost:9/")
+    .build();
+  let temp_dir = context.temp_dir().path();
+  temp_dir.join("snapshot.json").write(snapshot);
+  temp_dir.join("main.ts").write(
+    r#"import { getValue, setValue } from "npm:@denotest/esm-basic";
+setValue(5);
+console.log(getValue());
+"#,
+  );
+  context
+    .new_command()
+    .args("run --quiet --npm-snapshot=snapshot.json main.ts")
+    .run()
+    .assert_matches_text("5\n")
+    .assert_exit_code(0);
+
+  temp_dir
+    .join("missing.ts")
+    .write(r#"import "npm:@denotest/add";"#);
+  context
+    .new_command()
+    .args("run --quiet --npm-snapshot=snapshot.json missing.ts")
+    .run()
+    .assert_matches_text(
+      "[WILDCARD]npm package '@denotest/add' is not in the npm snapshot '[WILDLINE]snapshot.json'.[WILDCARD]",
+    )
+    .assert_exit_code(1);
+}
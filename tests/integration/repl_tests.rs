@@ -719,6 +719,28 @@ fn eval_flag_runtime_error() {
   assert!(err.is_empty());
 }
 
+#[test]
+fn eval_repl_flag_valid_input() {
+  util::with_pty(&["eval", "--repl", "const t = 10;"], |mut console| {
+    console.write_line("t * 500");
+    console.expect("5000");
+  });
+}
+
+#[test]
+fn eval_repl_flag_runtime_error() {
+  let (out, err) = util::run_and_collect_output_with_args(
+    true,
+    vec!["eval", "--repl", "throw new Error('Testing')"],
+    Some(vec!["250 * 10"]),
+    None,
+    false,
+  );
+  assert_contains!(out, "Error in --eval flag: Uncaught Error: Testing");
+  assert_contains!(out, "2500"); // should not prevent input
+  assert!(err.is_empty());
+}
+
 #[test]
 fn eval_file_flag_valid_input() {
   let (out, err) = util::run_and_collect_output_with_args(
@@ -730,6 +730,33 @@ fn permission_request_long() {
     });
 }
 
+#[test]
+fn permissions_prompt_timeout() {
+  let context = TestContextBuilder::default().use_temp_cwd().build();
+  context.temp_dir().path().join("main.ts").write(
+    r#"const status = await Deno.permissions.request({ name: "env", variable: "FOO" });
+console.log(status.state);
+"#,
+  );
+  context
+    .new_command()
+    .args_vec(["run", "--quiet", "--permissions-prompt-timeout=1", "main.ts"])
+    .with_pty(|mut console| {
+      console.expect(concat!(
+        "┏ ⚠️  Deno requests env access to \"FOO\".\r\n",
+        "┠─ Requested by `Deno.permissions.request()` API.\r\n",
+        "┠─ Learn more at: https://docs.deno.com/go/--allow-env\r\n",
+        "┠─ Run again with --allow-env to bypass this prompt.\r\n",
+        "┗ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all env permissions)",
+      ));
+      // don't answer
+      console.expect(
+        "Denied env access to \"FOO\", the prompt was not answered in time.",
+      );
+      console.expect("denied");
+    });
+}
+
 itest!(deny_all_permission_args {
   args: "run --deny-env --deny-read --deny-write --deny-ffi --deny-run --deny-sys --deny-net --deny-hrtime run/deny_all_permission_args.js",
   output: "run/deny_all_permission_args.out",
@@ -2408,6 +2435,38 @@ fn no_validate_asm() {
   assert!(output.stdout.is_empty());
 }
 
+#[test]
+fn run_color_flag() {
+  let t = TempDir::new();
+  t.write("a.ts", "export class A {}");
+  t.write("main.ts", "import { A } from './a.js';\nconsole.log(A);");
+
+  let run_with_color = |color: &str| {
+    let output = util::deno_cmd()
+      .current_dir(t.path())
+      .arg("run")
+      .arg(format!("--color={color}"))
+      .arg("main.ts")
+      .env("NO_COLOR", "1")
+      .piped_output()
+      .spawn()
+      .unwrap()
+      .wait_with_output()
+      .unwrap();
+    assert!(!output.status.success());
+    String::from_utf8(output.stderr).unwrap()
+  };
+
+  // the sloppy imports hint is colored even though stderr is piped
+  let stderr = run_with_color("always");
+  assert_contains!(stderr, "\u{1b}[");
+  assert_contains!(stderr, "--unstable-sloppy-imports");
+
+  let stderr = run_with_color("never");
+  assert_not_contains!(stderr, "\u{1b}[");
+  assert_contains!(stderr, "--unstable-sloppy-imports");
+}
+
 #[test]
 fn exec_path() {
   let output = util::deno_cmd()
@@ -2474,6 +2533,27 @@ console.log("executing javascript");
   assert_eq!(stdout_str, "executing javascript");
 }
 
+#[test]
+fn run_from_stdin_ext_tsx() {
+  let source_code = r#"
+const React = { createElement: (tag: string) => `<${tag}>` };
+console.log(<div />);
+"#;
+
+  let mut p = util::deno_cmd()
+    .args("run --ext tsx -")
+    .stdin(std::process::Stdio::piped())
+    .stdout_piped()
+    .spawn()
+    .unwrap();
+  let stdin = p.stdin.as_mut().unwrap();
+  stdin.write_all(source_code.as_bytes()).unwrap();
+  let result = p.wait_with_output().unwrap();
+  assert!(result.status.success());
+  let stdout_str = std::str::from_utf8(&result.stdout).unwrap().trim();
+  assert_eq!(stdout_str, "<div>");
+}
+
 #[cfg(windows)]
 // Clippy suggests to remove the `NoStd` prefix from all variants. I disagree.
 #[allow(clippy::enum_variant_names)]
@@ -4045,6 +4125,32 @@ fn basic_auth_tokens() {
   assert_eq!(util::strip_ansi_codes(stdout_str), "Hello World");
 }
 
+#[test]
+fn remote_auth_flag() {
+  let _g = util::http_server();
+
+  // the flag takes precedence over a mismatching DENO_AUTH_TOKENS entry
+  let output = util::deno_cmd()
+    .current_dir(util::root_path())
+    .arg("run")
+    .arg("--remote-auth=127.0.0.1:4551=Bearer abcdef123456789")
+    .arg("http://127.0.0.1:4551/run/001_hello.js")
+    .env("DENO_AUTH_TOKENS", "wrongtoken@127.0.0.1:4551")
+    .piped_output()
+    .spawn()
+    .unwrap()
+    .wait_with_output()
+    .unwrap();
+
+  let stderr_str = std::str::from_utf8(&output.stderr).unwrap().trim();
+  eprintln!("{stderr_str}");
+
+  assert!(output.status.success());
+
+  let stdout_str = std::str::from_utf8(&output.stdout).unwrap().trim();
+  assert_eq!(util::strip_ansi_codes(stdout_str), "Hello World");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_resolve_dns() {
   use std::net::SocketAddr;
@@ -5116,6 +5222,86 @@ fn code_cache_test() {
   }
 }
 
+#[test]
+fn code_cache_custom_path_test() {
+  let test_context = TestContextBuilder::new().use_temp_cwd().build();
+  let deno_dir = test_context.deno_dir();
+  let temp_dir = test_context.temp_dir();
+  temp_dir.write("main.js", "console.log('Hello World - A');");
+
+  // First run populates the cache at the given path.
+  {
+    let output = test_context
+      .new_command()
+      .args("run -Ldebug --code-cache=cache/code_cache main.js")
+      .split_output()
+      .run();
+
+    output
+      .assert_stdout_matches_text("Hello World - A[WILDCARD]")
+      .assert_stderr_matches_text("[WILDCARD]Updating V8 code cache for ES module: file:///[WILDCARD]/main.js[WILDCARD]");
+    assert_not_contains!(output.stderr(), "V8 code cache hit");
+
+    assert!(temp_dir.path().join("cache/code_cache").exists());
+    assert!(!deno_dir.path().join(CODE_CACHE_DB_FILE_NAME).exists());
+  }
+
+  // A run with a fresh DENO_DIR, like a new container, reuses the persisted
+  // cache instead of compiling again.
+  {
+    let output = test_context
+      .new_command()
+      .env("DENO_DIR", temp_dir.path().join("other_deno_dir"))
+      .args("run -Ldebug --code-cache=cache/code_cache main.js")
+      .split_output()
+      .run();
+
+    output
+      .assert_stdout_matches_text("Hello World - A[WILDCARD]")
+      .assert_stderr_matches_text("[WILDCARD]V8 code cache hit for ES module: file:///[WILDCARD]/main.js[WILDCARD]");
+    assert_not_contains!(output.stderr(), "Updating V8 code cache");
+  }
+}
+
+#[test]
+fn code_cache_warmup_test() {
+  let test_context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = test_context.temp_dir();
+  temp_dir.write("mod.ts", "export const letter: string = 'A';");
+  temp_dir.write(
+    "main.ts",
+    "import { letter } from './mod.ts';console.log(`Hello World - ${letter}`);",
+  );
+
+  // Warming up compiles everything without running the program.
+  {
+    let output = test_context
+      .new_command()
+      .args("run -Ldebug --check --warmup main.ts")
+      .split_output()
+      .run();
+
+    output.assert_exit_code(0);
+    assert_not_contains!(output.stdout(), "Hello World");
+    output.assert_stderr_matches_text("[WILDCARD]Updating V8 code cache for ES module: file:///[WILDCARD]/main.ts[WILDCARD]");
+  }
+
+  // The real run afterwards doesn't compile anything.
+  {
+    let output = test_context
+      .new_command()
+      .args("run -Ldebug --check main.ts")
+      .split_output()
+      .run();
+
+    output
+      .assert_stdout_matches_text("Hello World - A[WILDCARD]")
+      .assert_stderr_matches_text("[WILDCARD]V8 code cache hit for ES module: file:///[WILDCARD]/main.ts[WILDCARD]");
+    assert_not_contains!(output.stderr(), "Updating V8 code cache");
+    assert_not_contains!(output.stderr(), "Check file:///");
+  }
+}
+
 #[test]
 fn code_cache_npm_test() {
   let test_context = TestContextBuilder::for_npm().use_temp_cwd().build();
@@ -5409,3 +5595,150 @@ fn emit_failed_readonly_file_system() {
     .run();
   output.assert_matches_text("[WILDCARD]Error saving emit data ([WILDLINE]main.ts)[WILDCARD]Skipped emit cache save of [WILDLINE]other.ts[WILDCARD]hi[WILDCARD]");
 }
+
+#[test]
+fn record_and_replay_nondeterminism() {
+  let context = TestContextBuilder::default().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path();
+  temp_dir.join("main.ts").write(
+    r#"console.log(Math.random(), Math.random());
+console.log(Date.now(), new Date().toISOString());
+console.log(crypto.randomUUID());
+"#,
+  );
+
+  let recorded = context
+    .new_command()
+    .args("run --record-nondeterminism=trace.json main.ts")
+    .run();
+  recorded.assert_exit_code(0);
+  assert!(temp_dir.join("trace.json").exists());
+
+  let replayed = context
+    .new_command()
+    .args("run --replay-nondeterminism=trace.json main.ts")
+    .run();
+  replayed.assert_exit_code(0);
+  assert_eq!(replayed.combined_output(), recorded.combined_output());
+
+  // a program that reads more inputs than were recorded fails
+  temp_dir
+    .join("main.ts")
+    .write("console.log(Math.random(), Math.random(), Math.random());");
+  context
+    .new_command()
+    .args("run --replay-nondeterminism=trace.json main.ts")
+    .run()
+    .assert_matches_text(
+      "[WILDCARD]The replay trace has no more 'random' inputs.[WILDCARD]",
+    )
+    .assert_exit_code(1);
+}
+
+#[test]
+fn record_nondeterminism_on_every_exit() {
+  let context = TestContextBuilder::default().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path();
+  temp_dir
+    .join("exit.ts")
+    .write("console.log(Math.random());\nDeno.exit(3);\n");
+  temp_dir
+    .join("throw.ts")
+    .write("console.log(Math.random());\nthrow new Error(\"boom\");\n");
+
+  for (script, exit_code) in [("exit.ts", 3), ("throw.ts", 1)] {
+    let recorded = context
+      .new_command()
+      .args_vec(["run", "--record-nondeterminism=trace.json", script])
+      .run();
+    recorded.assert_exit_code(exit_code);
+    let replayed = context
+      .new_command()
+      .args_vec(["run", "--replay-nondeterminism=trace.json", script])
+      .run();
+    replayed.assert_exit_code(exit_code);
+    assert_eq!(
+      replayed.combined_output().lines().next(),
+      recorded.combined_output().lines().next()
+    );
+    temp_dir.join("trace.json").remove_file();
+  }
+}
+
+// uses a stub package manager, so that the test doesn't depend on npm
+#[cfg(unix)]
+#[test]
+fn byonm_on_stale_auto_install() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let context = TestContextBuilder::default().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path().canonicalize();
+  temp_dir
+    .join("deno.json")
+    .write(r#"{ "unstable": ["byonm"] }"#);
+  temp_dir
+    .join("package.json")
+    .write(r#"{ "dependencies": { "stub-pkg": "1.0.0" } }"#);
+  temp_dir
+    .join("main.ts")
+    .write("import { value } from \"stub-pkg\";\nconsole.log(value);\n");
+  let bin_dir = temp_dir.join("bin");
+  bin_dir.create_dir_all();
+  let npm = bin_dir.join("npm");
+  npm.write(
+    r#"#!/bin/sh
+mkdir -p node_modules/stub-pkg
+echo '{ "name": "stub-pkg", "version": "1.0.0", "type": "module", "main": "index.js" }' > node_modules/stub-pkg/package.json
+echo 'export const value = 5;' > node_modules/stub-pkg/index.js
+touch node_modules/.package-lock.json
+echo "stub npm $@"
+"#,
+  );
+  std::fs::set_permissions(
+    npm.as_path(),
+    std::fs::Permissions::from_mode(0o755),
+  )
+  .unwrap();
+  let path = format!(
+    "{}:{}",
+    bin_dir.as_path().display(),
+    std::env::var("PATH").unwrap_or_default()
+  );
+  let run = |on_stale: &str| {
+    context
+      .new_command()
+      .args_vec(["run", &format!("--byonm-on-stale={on_stale}"), "main.ts"])
+      .env("PATH", &path)
+      .run()
+  };
+
+  // the dependency is missing
+  run("auto-install")
+    .assert_matches_text(
+      "[WILDCARD]Running npm install in [WILDCARD]stub npm install\n5\n",
+    )
+    .assert_exit_code(0);
+  run("error").assert_matches_text("5\n").assert_exit_code(0);
+
+  // the package.json is newer than the last install
+  let marker = temp_dir.join("node_modules/.package-lock.json");
+  std::fs::File::options()
+    .write(true)
+    .open(marker.as_path())
+    .unwrap()
+    .set_modified(
+      std::time::SystemTime::now() - std::time::Duration::from_secs(60),
+    )
+    .unwrap();
+  run("error")
+    .assert_matches_text(
+      "error: The node_modules directory is out of date:\n  [WILDCARD]package.json was modified after the last install\n[WILDCARD]",
+    )
+    .assert_exit_code(1);
+  run("auto-install")
+    .assert_matches_text(
+      "[WILDCARD]Running npm install in [WILDCARD]stub npm install\n5\n",
+    )
+    .assert_exit_code(0);
+  run("error").assert_matches_text("5\n").assert_exit_code(0);
+}
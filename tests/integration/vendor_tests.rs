@@ -678,6 +678,99 @@ fn vendor_only_npm_specifiers() {
   ));
 }
 
+#[test]
+fn run_vendor_out() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    "import {Logger} from 'http://localhost:4545/vendor/logger.ts'; new Logger().log('outputted');",
+  );
+
+  let output = context
+    .new_command()
+    .args("run --quiet --vendor-out=vendor/ main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("outputted\n");
+  assert!(temp_dir
+    .path()
+    .join("vendor/localhost_4545/vendor/logger.ts")
+    .exists());
+  let import_map: serde_json::Value =
+    serde_json::from_str(&temp_dir.read_to_string("vendor/import_map.json"))
+      .unwrap();
+  assert_eq!(
+    import_map,
+    json!({
+      "imports": {
+        "http://localhost:4545/": "./localhost_4545/",
+      },
+    }),
+  );
+
+  // the vendored modules work without the remote module cache
+  let deno_dir = TempDir::new();
+  let output = context
+    .new_command()
+    .env("DENO_DIR", deno_dir.path())
+    .args("run --quiet --no-remote --import-map=vendor/import_map.json main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("outputted\n");
+}
+
+#[test]
+fn run_vendor_out_npm() {
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  // the directory is written when the program calls `Deno.exit()` too
+  temp_dir.write(
+    "main.ts",
+    concat!(
+      "import version from 'npm:@denotest/different-nested-dep@1.0.0';\n",
+      "console.log(version);\n",
+      "Deno.exit(0);\n",
+    ),
+  );
+
+  let output = context
+    .new_command()
+    .args("run --quiet --vendor-out=vendor/ main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("1\n");
+  assert!(temp_dir
+    .path()
+    .join("vendor/node_modules/@denotest/different-nested-dep-child/index.js")
+    .exists());
+  let import_map: serde_json::Value =
+    serde_json::from_str(&temp_dir.read_to_string("vendor/import_map.json"))
+      .unwrap();
+  assert_eq!(
+    import_map,
+    json!({
+      "imports": {
+        "npm:@denotest/different-nested-dep@1.0.0":
+          "./node_modules/@denotest/different-nested-dep/index.js",
+      },
+    }),
+  );
+
+  // the package resolves its dependency from the vendored node_modules
+  let deno_dir = TempDir::new();
+  let output = context
+    .new_command()
+    .env("DENO_DIR", deno_dir.path())
+    .args("run --quiet --unstable-byonm --import-map=vendor/import_map.json main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("1\n");
+}
+
 fn success_text(module_count: &str, dir: &str, has_import_map: bool) -> String {
   let mut text = format!("Vendored {module_count} into {dir} directory.");
   if has_import_map {
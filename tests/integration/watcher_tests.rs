@@ -706,6 +706,68 @@ async fn run_watch_no_dynamic() {
   check_alive_then_kill(child);
 }
 
+#[flaky_test(tokio)]
+async fn run_watch_check_only_changed_modules() {
+  let t = TempDir::new();
+  let main_file = t.path().join("main.ts");
+  let a_file = t.path().join("a.ts");
+  let b_file = t.path().join("b.ts");
+  a_file.write("export const a: number = 1;");
+  b_file.write("export const b: string = 'b';");
+  main_file.write(
+    "import { a } from './a.ts'; import { b } from './b.ts'; console.log(a, b);",
+  );
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch")
+    .arg("--check")
+    .arg(&main_file)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+
+  wait_contains("Process started", &mut stderr_lines).await;
+  assert_contains!(next_line(&mut stderr_lines).await.unwrap(), "main.ts");
+  wait_contains("1 b", &mut stdout_lines).await;
+  wait_contains("Process finished", &mut stderr_lines).await;
+
+  // only b.ts and the module importing it are checked again
+  b_file.write("export const b: string = 'changed';");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  let line = next_line(&mut stderr_lines).await.unwrap();
+  assert_contains!(line, "Check");
+  assert_contains!(line, "main.ts");
+  let line = next_line(&mut stderr_lines).await.unwrap();
+  assert_contains!(line, "Check");
+  assert_contains!(line, "b.ts");
+  wait_contains("1 changed", &mut stdout_lines).await;
+  assert_contains!(
+    next_line(&mut stderr_lines).await.unwrap(),
+    "Process finished"
+  );
+
+  // a type error keeps its module checked until it's fixed
+  a_file.write("export const a: number = 'a';");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  wait_contains("TS2322", &mut stderr_lines).await;
+  wait_contains("Process failed", &mut stderr_lines).await;
+  b_file.write("export const b: string = 'b';");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  let line = next_line(&mut stderr_lines).await.unwrap();
+  assert_contains!(line, "main.ts");
+  let line = next_line(&mut stderr_lines).await.unwrap();
+  assert_contains!(line, "a.ts");
+  let line = next_line(&mut stderr_lines).await.unwrap();
+  assert_contains!(line, "b.ts");
+  wait_contains("TS2322", &mut stderr_lines).await;
+
+  check_alive_then_kill(child);
+}
+
 #[flaky_test(tokio)]
 async fn run_watch_npm_specifier() {
   let _g = util::http_server();
@@ -1004,6 +1066,83 @@ async fn run_watch_error_messages() {
   check_alive_then_kill(child);
 }
 
+#[flaky_test(tokio)]
+async fn run_watch_exit_on_error() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write("console.log('done'); Deno.exitCode = 3;");
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch")
+    .arg("--watch-exit-on-error")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+
+  wait_contains("Process started", &mut stderr_lines).await;
+  wait_contains("done", &mut stdout_lines).await;
+  let exit_status = child.wait().unwrap();
+  assert_eq!(exit_status.code(), Some(3));
+
+  // uncaught errors stop the watcher with exit code 1
+  file_to_watch.write("throw new Error('boom');");
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch-hmr")
+    .arg("--watch-exit-on-error")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (_, mut stderr_lines) = child_lines(&mut child);
+
+  wait_contains("error: Uncaught", &mut stderr_lines).await;
+  let exit_status = child.wait().unwrap();
+  assert_eq!(exit_status.code(), Some(1));
+}
+
+#[flaky_test(tokio)]
+async fn run_watch_poll() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write("console.log('Hello world');");
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch")
+    .arg("--watch-poll=200")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+
+  wait_contains("Hello world", &mut stdout_lines).await;
+  wait_for_watcher("file_to_watch.js", &mut stderr_lines).await;
+
+  // the polling watcher picks up the change on its next scan
+  let changed_at = std::time::Instant::now();
+  file_to_watch.write("console.log('Hello world2');");
+
+  wait_contains("Restarting", &mut stderr_lines).await;
+  wait_contains("Hello world2", &mut stdout_lines).await;
+  // poll interval plus the debounce interval, with slack for slow machines
+  assert!(changed_at.elapsed() < std::time::Duration::from_secs(5));
+
+  check_alive_then_kill(child);
+}
+
 #[flaky_test(tokio)]
 async fn test_watch_basic() {
   let t = TempDir::new();
@@ -1812,6 +1951,102 @@ export function foo() {
   check_alive_then_kill(child);
 }
 
+#[flaky_test(tokio)]
+async fn run_watch_reevaluate_leaf_module() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write(
+    r#"
+import "./leaf.js";
+
+globalThis.mainRuns = (globalThis.mainRuns ?? 0) + 1;
+console.log("main runs", globalThis.mainRuns);
+setInterval(() => {}, 1000);
+"#,
+  );
+  let leaf = t.path().join("leaf.js");
+  leaf.write(r#"console.log("leaf v1");"#);
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch-reevaluate")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  wait_contains("Process started", &mut stderr_lines).await;
+  wait_contains("Finished config loading.", &mut stderr_lines).await;
+
+  wait_for_watcher("file_to_watch.js", &mut stderr_lines).await;
+  wait_contains("leaf v1", &mut stdout_lines).await;
+  wait_contains("main runs 1", &mut stdout_lines).await;
+
+  leaf.write(r#"console.log("leaf v2, main runs", globalThis.mainRuns);"#);
+
+  wait_contains("Re-evaluated module", &mut stderr_lines).await;
+  wait_contains("leaf v2, main runs 1", &mut stdout_lines).await;
+
+  check_alive_then_kill(child);
+}
+
+#[flaky_test(tokio)]
+async fn run_watch_reevaluate_intermediate_dependent() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write(
+    r#"
+import "./middle.js";
+
+setInterval(() => {}, 1000);
+"#,
+  );
+  let middle = t.path().join("middle.js");
+  middle.write(
+    r#"
+import { value } from "./leaf.js";
+
+console.log("middle sees", value);
+"#,
+  );
+  let leaf = t.path().join("leaf.js");
+  leaf.write(r#"export const value = "v1";"#);
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch-reevaluate")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  wait_contains("Process started", &mut stderr_lines).await;
+  wait_contains("Finished config loading.", &mut stderr_lines).await;
+
+  wait_for_watcher("file_to_watch.js", &mut stderr_lines).await;
+  wait_contains("middle sees v1", &mut stdout_lines).await;
+
+  leaf.write(r#"export const value = "v2";"#);
+
+  // the re-run middle.js imports the fresh leaf.js, not the original one
+  wait_contains("Re-evaluated module", &mut stderr_lines).await;
+  wait_contains("middle sees v2", &mut stdout_lines).await;
+
+  leaf.write(r#"export const value = "v3";"#);
+
+  wait_contains("middle sees v3", &mut stdout_lines).await;
+
+  check_alive_then_kill(child);
+}
+
 #[flaky_test(tokio)]
 async fn run_hmr_uncaught_error() {
   let t = TempDir::new();
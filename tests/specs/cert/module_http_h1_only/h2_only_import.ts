@@ -0,0 +1,3 @@
+import { printHello } from "https://localhost:5547/subdir/print_hello.ts";
+
+printHello();
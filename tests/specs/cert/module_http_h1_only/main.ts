@@ -0,0 +1,5 @@
+import { printHello } from "https://localhost:5546/subdir/print_hello.ts";
+
+printHello();
+const res = await fetch("https://localhost:5545/http_version");
+console.log(await res.text());
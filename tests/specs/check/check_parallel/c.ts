@@ -0,0 +1,2 @@
+export const c: number = 1;
+export const unused: boolean = "c";
@@ -0,0 +1 @@
+export const d: string = 1;
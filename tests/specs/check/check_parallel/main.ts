@@ -0,0 +1,5 @@
+import { a } from "./a.ts";
+import { b } from "./b.ts";
+
+const value: number = b;
+console.log(a, value);
@@ -0,0 +1,4 @@
+import { d } from "./d.ts";
+
+const other: boolean = d;
+console.log(other);
@@ -0,0 +1 @@
+import "npm:@denotest/add@1";
@@ -0,0 +1 @@
+import "npm:@denotest/subtract@1";
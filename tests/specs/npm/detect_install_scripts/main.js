@@ -0,0 +1,3 @@
+import "npm:@denotest/has-postinstall";
+
+console.log("ran");
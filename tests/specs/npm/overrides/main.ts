@@ -0,0 +1,3 @@
+import version from "@denotest/different-nested-dep";
+
+console.log(version);
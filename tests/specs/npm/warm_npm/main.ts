@@ -0,0 +1,3 @@
+import chalk from "npm:chalk@5.0.1";
+
+console.log(chalk.green("warm"));
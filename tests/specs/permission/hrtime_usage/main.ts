@@ -0,0 +1,9 @@
+const samples = [];
+for (let i = 0; i < 5; i++) {
+  samples.push(performance.now());
+}
+// without hrtime, times are rounded to 2ms
+console.log(
+  "coarse:",
+  samples.every((time) => Number.isInteger(time) && time % 2 === 0),
+);
@@ -0,0 +1,25 @@
+const url = "http://localhost:4545/echo_server";
+
+async function post(size: number) {
+  try {
+    const res = await fetch(url, {
+      method: "POST",
+      body: new Uint8Array(size),
+    });
+    console.log("sent", (await res.arrayBuffer()).byteLength);
+  } catch (err) {
+    console.log(`${err.name}: ${err.message}`);
+  }
+}
+
+await post(600);
+// exactly reaches the cap
+await post(400);
+// one byte over the cap
+await post(1);
+// once the cap is exceeded even requests without a body are refused
+try {
+  await fetch(url);
+} catch (err) {
+  console.log(`${err.name}: ${err.message}`);
+}
@@ -0,0 +1,21 @@
+const url = "http://localhost:4545/echo_server";
+
+// a stream has no known length, so it is charged while it is sent
+const body = new ReadableStream({
+  start(controller) {
+    controller.enqueue(new Uint8Array(600));
+    controller.enqueue(new Uint8Array(600));
+    controller.close();
+  },
+});
+try {
+  await fetch(url, { method: "POST", body });
+  console.log("sent");
+} catch (err) {
+  console.log(`${err.name}: ${err.cause?.message}`);
+}
+try {
+  await fetch(url);
+} catch (err) {
+  console.log(`${err.name}: ${err.message}`);
+}
@@ -0,0 +1,35 @@
+const listener = Deno.listen({ hostname: "localhost", port: 4611 });
+const received = (async () => {
+  const conn = await listener.accept();
+  let size = 0;
+  for await (const chunk of conn.readable) {
+    size += chunk.byteLength;
+  }
+  return size;
+})();
+
+const conn = await Deno.connect({ hostname: "localhost", port: 4611 });
+
+async function write(size: number) {
+  try {
+    console.log("wrote", await conn.write(new Uint8Array(size)));
+  } catch (err) {
+    console.log(`${err.name}: ${err.message}`);
+  }
+}
+
+await write(600);
+// exactly reaches the cap
+await write(400);
+// one byte over the cap
+await write(1);
+conn.close();
+console.log("received", await received);
+listener.close();
+
+// once the cap is exceeded new connections are refused too
+try {
+  await Deno.connect({ hostname: "localhost", port: 4611 });
+} catch (err) {
+  console.log(`${err.name}: ${err.message}`);
+}
@@ -0,0 +1,38 @@
+const tlsDir = new URL("../../../testdata/tls/", import.meta.url);
+const listener = Deno.listenTls({
+  hostname: "localhost",
+  port: 4612,
+  cert: Deno.readTextFileSync(new URL("localhost.crt", tlsDir)),
+  key: Deno.readTextFileSync(new URL("localhost.key", tlsDir)),
+});
+const received = (async () => {
+  const conn = await listener.accept();
+  let size = 0;
+  for await (const chunk of conn.readable) {
+    size += chunk.byteLength;
+  }
+  return size;
+})();
+
+const conn = await Deno.connectTls({
+  hostname: "localhost",
+  port: 4612,
+  caCerts: [Deno.readTextFileSync(new URL("RootCA.pem", tlsDir))],
+});
+
+async function write(size: number) {
+  try {
+    console.log("wrote", await conn.write(new Uint8Array(size)));
+  } catch (err) {
+    console.log(`${err.name}: ${err.message}`);
+  }
+}
+
+await write(600);
+// exactly reaches the cap
+await write(400);
+// one byte over the cap
+await write(1);
+conn.close();
+console.log("received", await received);
+listener.close();
@@ -0,0 +1,18 @@
+const ws = new WebSocket("ws://localhost:4242");
+await new Promise((resolve) => ws.onopen = resolve);
+
+const closed = new Promise((resolve) => ws.onclose = resolve);
+ws.onmessage = (event) => console.log("echoed", event.data.size);
+ws.onerror = (event) => console.log("error:", (event as ErrorEvent).message);
+ws.binaryType = "blob";
+ws.send(new Uint8Array(600));
+// goes over the cap
+ws.send(new Uint8Array(600));
+await closed;
+
+// once the cap is exceeded new connections are refused too
+try {
+  new WebSocket("ws://localhost:4242");
+} catch (err) {
+  console.log(`${err.name}: ${err.message}`);
+}
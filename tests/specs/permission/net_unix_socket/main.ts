@@ -0,0 +1,11 @@
+const listener = Deno.listen({ transport: "unix", path: "app.sock" });
+const accepted = listener.accept();
+
+const conn = await Deno.connect({ transport: "unix", path: "app.sock" });
+console.log("connected to app.sock");
+conn.close();
+(await accepted).close();
+listener.close();
+
+// only the sockets listed in --allow-net may be used
+await Deno.connect({ transport: "unix", path: "other.sock" });
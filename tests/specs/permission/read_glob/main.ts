@@ -0,0 +1,8 @@
+for (const path of Deno.args) {
+  try {
+    Deno.readTextFileSync(path);
+    console.log(`${path}: read`);
+  } catch (err) {
+    console.log(`${path}: ${err.name}`);
+  }
+}
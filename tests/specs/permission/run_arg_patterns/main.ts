@@ -0,0 +1,22 @@
+// Only the permission check is under test, so a missing or failing `git` on
+// the host doesn't matter as long as the spawn isn't denied.
+async function tryRun(args: string[]) {
+  try {
+    await new Deno.Command("git", {
+      args,
+      stdout: "null",
+      stderr: "null",
+    }).output();
+    console.log(`git ${args.join(" ")}: allowed`);
+  } catch (err) {
+    if (err instanceof Deno.errors.PermissionDenied) {
+      console.log(err.name, err.message);
+    } else {
+      console.log(`git ${args.join(" ")}: allowed`);
+    }
+  }
+}
+
+await tryRun(["status"]);
+await tryRun(["log", "-1"]);
+await tryRun(["push", "origin"]);
@@ -0,0 +1,16 @@
+Deno.mkdirSync("out");
+// the source is outside of the quota
+Deno.writeFileSync("source.bin", new Uint8Array(600));
+
+Deno.copyFileSync("source.bin", "out/a.bin");
+console.log("copied 600");
+try {
+  await Deno.copyFile("source.bin", "out/b.bin");
+} catch (err) {
+  console.log(`${err.name}: ${err.message}`);
+}
+try {
+  Deno.statSync("out/b.bin");
+} catch (err) {
+  console.log(err.name);
+}
@@ -0,0 +1,10 @@
+Deno.mkdirSync("out");
+
+try {
+  Deno.writeFileSync("out/secret.bin", new Uint8Array(600));
+} catch (err) {
+  console.log(err.name);
+}
+// the denied write didn't use up any of the quota
+Deno.writeFileSync("out/a.bin", new Uint8Array(1000));
+console.log("wrote 1000");
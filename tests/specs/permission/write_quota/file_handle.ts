@@ -0,0 +1,28 @@
+Deno.mkdirSync("out");
+
+function attempt(label: string, fn: () => void) {
+  try {
+    fn();
+    console.log(label);
+  } catch (err) {
+    console.log(`${err.name}: ${err.message}`);
+  }
+}
+
+const file = Deno.openSync("out/a.bin", { write: true, create: true });
+attempt("wrote 600", () => file.writeSync(new Uint8Array(600)));
+await file.write(new Uint8Array(400));
+console.log("wrote 400");
+// one byte over the quota
+attempt("wrote 1", () => file.writeSync(new Uint8Array(1)));
+try {
+  await file.write(new Uint8Array(1));
+} catch (err) {
+  console.log(`${err.name}: ${err.message}`);
+}
+
+// files opened for reading only are not metered
+const reader = Deno.openSync("out/a.bin");
+console.log("size", reader.statSync().size);
+file.close();
+reader.close();
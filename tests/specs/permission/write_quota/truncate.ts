@@ -0,0 +1,28 @@
+Deno.mkdirSync("out");
+
+function attempt(label: string, fn: () => void) {
+  try {
+    fn();
+    console.log(label);
+  } catch (err) {
+    console.log(`${err.name}: ${err.message}`);
+  }
+}
+
+Deno.writeFileSync("out/a.bin", new Uint8Array(500));
+// shrinking a file isn't charged
+attempt("truncated to 100", () => Deno.truncateSync("out/a.bin", 100));
+// growing it is charged by the bytes it grows by
+await Deno.truncate("out/a.bin", 400);
+console.log("truncated to 400");
+const file = Deno.openSync("out/a.bin", { write: true });
+attempt("grew to 600", () => file.truncateSync(600));
+// one byte over the quota
+attempt("grew to 601", () => file.truncateSync(601));
+try {
+  await file.truncate(601);
+} catch (err) {
+  console.log(`${err.name}: ${err.message}`);
+}
+file.close();
+console.log("size", Deno.statSync("out/a.bin").size);
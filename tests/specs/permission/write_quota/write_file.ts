@@ -0,0 +1,21 @@
+Deno.mkdirSync("out");
+
+function write(path: string, size: number) {
+  try {
+    Deno.writeFileSync(path, new Uint8Array(size));
+    console.log("wrote", size);
+  } catch (err) {
+    console.log(`${err.name}: ${err.message}`);
+  }
+}
+
+write("out/a.bin", 600);
+// exactly reaches the quota
+await Deno.writeFile("out/b.bin", new Uint8Array(400));
+console.log("wrote", 400);
+// one byte over the quota
+write("out/c.bin", 1);
+
+// reads are not affected by the quota
+console.log("read", Deno.readFileSync("out/a.bin").byteLength);
+console.log("read", (await Deno.readFile("out/b.bin")).byteLength);
@@ -0,0 +1,6 @@
+try {
+  Deno.resources();
+} catch {
+  console.log("caught");
+}
+console.log("ran");
@@ -0,0 +1,3 @@
+const file = Deno.openSync("closed.ts");
+console.log("opened", file.statSync().isFile);
+file.close();
@@ -0,0 +1,2 @@
+const file = Deno.openSync("leak.ts");
+console.log("opened", file.statSync().isFile);
@@ -0,0 +1,10 @@
+function branch(condition: boolean): string {
+  if (condition) {
+    return "taken";
+  } else {
+    return "not taken";
+  }
+}
+
+console.log(branch(true));
+throw new Error("boom");
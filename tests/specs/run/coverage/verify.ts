@@ -0,0 +1,11 @@
+// Prints whether the taken (line 3) and the other (line 5) branch of the
+// script are recorded as executed.
+const hits = new Map<number, number>();
+for (const line of Deno.readTextFileSync(Deno.args[0]).split("\n")) {
+  const match = line.match(/^DA:(\d+),(\d+)$/);
+  if (match) {
+    hits.set(Number(match[1]), Number(match[2]));
+  }
+}
+console.log("taken branch executed:", (hits.get(3) ?? 0) > 0);
+console.log("other branch executed:", (hits.get(5) ?? 0) > 0);
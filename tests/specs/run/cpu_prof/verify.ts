@@ -0,0 +1,3 @@
+const profile = JSON.parse(Deno.readTextFileSync(Deno.args[0]));
+console.log(Array.isArray(profile.nodes) && profile.nodes.length > 0);
+console.log(typeof profile.startTime, typeof profile.endTime);
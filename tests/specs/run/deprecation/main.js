@@ -0,0 +1,6 @@
+try {
+  Deno.resources();
+  console.log("called");
+} catch (err) {
+  console.log("threw:", err.message);
+}
@@ -0,0 +1,4 @@
+import { getValue } from "npm:@denotest/esm-basic@1.0.0";
+import { message } from "./message.js";
+
+console.log(message, getValue());
@@ -0,0 +1,3 @@
+import { add } from "./add.ts";
+
+console.log(add(1, 2));
@@ -0,0 +1,9 @@
+const start = Date.now();
+try {
+  // this server responds only after 10 seconds
+  await import("http://localhost:4545/sleep/10000");
+  console.log("unexpectedly loaded");
+} catch (err) {
+  console.log(err.name, err.message);
+  console.log("rejected within window:", Date.now() - start < 5000);
+}
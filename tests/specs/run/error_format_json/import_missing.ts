@@ -0,0 +1 @@
+import "./missing.ts";
@@ -0,0 +1 @@
+throw new TypeError("boom");
@@ -0,0 +1,8 @@
+addEventListener("unload", () => {
+  console.log("cleanup started");
+  setTimeout(() => console.log("cleanup finished"), 100);
+});
+
+setTimeout(() => {
+  Deno.exit(3);
+}, 10);
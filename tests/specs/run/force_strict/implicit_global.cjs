@@ -0,0 +1,2 @@
+undeclaredVariable = 1;
+console.log("assigned");
@@ -0,0 +1,4 @@
+import { createRequire } from "node:module";
+
+const require = createRequire(import.meta.url);
+require("./implicit_global.cjs");
@@ -0,0 +1,3 @@
+with ({ value: 42 }) {
+  module.exports = value;
+}
@@ -0,0 +1,4 @@
+import { createRequire } from "node:module";
+
+const require = createRequire(import.meta.url);
+console.log(require("./with_statement.cjs"));
@@ -0,0 +1,3 @@
+import { Test } from "jsr:@denotest/module-graph@1.4.0";
+
+console.log(typeof new Test().other);
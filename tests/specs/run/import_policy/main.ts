@@ -0,0 +1,3 @@
+import { a } from "./a.ts";
+
+console.log(a);
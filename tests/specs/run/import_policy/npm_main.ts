@@ -0,0 +1,3 @@
+import version from "npm:@denotest/different-nested-dep@1.0.0";
+
+console.log(version);
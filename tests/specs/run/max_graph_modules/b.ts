@@ -0,0 +1,3 @@
+import "./c.ts";
+import "./d.ts";
+import "./e.ts";
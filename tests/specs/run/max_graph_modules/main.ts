@@ -0,0 +1,4 @@
+import "./a.ts";
+import "./b.ts";
+
+console.log("main");
@@ -0,0 +1,10 @@
+// responses that were not redirected to are not limited
+const direct = await fetch("http://localhost:4545/run/001_hello.js");
+console.log((await direct.text()).length);
+
+const redirected = await fetch("http://localhost:4546/run/001_hello.js");
+try {
+  await redirected.text();
+} catch (err) {
+  console.log(err.message);
+}
@@ -0,0 +1,2 @@
+// redirects twice before reaching http://localhost:4545/run/001_hello.js
+import "http://localhost:4548/run/001_hello.js";
@@ -0,0 +1,2 @@
+console.log("first");
+(globalThis as any).preloaded = ["first"];
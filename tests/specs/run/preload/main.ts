@@ -0,0 +1 @@
+console.log("main", (globalThis as any).preloaded);
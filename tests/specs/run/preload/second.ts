@@ -0,0 +1,2 @@
+console.log("second");
+(globalThis as any).preloaded.push("second");
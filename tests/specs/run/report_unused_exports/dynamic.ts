@@ -0,0 +1,2 @@
+// dynamically imported modules count as fully used
+export const neverReferenced = 1;
@@ -0,0 +1,9 @@
+import { add } from "./math.ts";
+import type { Point } from "./types.ts";
+
+const point: Point = { x: 1, y: 2 };
+console.log(add(point.x, point.y));
+await import("./dynamic.ts");
+
+// exports of the main module are not reported
+export const result = add(1, 2);
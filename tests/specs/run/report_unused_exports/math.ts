@@ -0,0 +1,7 @@
+export function add(a: number, b: number) {
+  return a + b;
+}
+
+export function unusedSubtract(a: number, b: number) {
+  return a - b;
+}
@@ -0,0 +1,6 @@
+export interface Point {
+  x: number;
+  y: number;
+}
+
+export type UnusedSize = { width: number; height: number };
@@ -0,0 +1,2 @@
+console.log("stdout line");
+Deno.exit(3);
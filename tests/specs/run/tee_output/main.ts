@@ -0,0 +1,3 @@
+console.log("stdout line");
+console.error("stderr line");
+throw new Error("failed");
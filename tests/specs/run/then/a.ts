@@ -0,0 +1,2 @@
+globalThis.shared = "from a";
+console.log("a");
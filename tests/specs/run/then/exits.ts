@@ -0,0 +1,2 @@
+console.log("exits");
+Deno.exit(0);
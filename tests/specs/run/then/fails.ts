@@ -0,0 +1,2 @@
+console.log("fails");
+Deno.exit(3);
@@ -0,0 +1,3 @@
+import { value } from "./generated.ts";
+
+console.log(value);
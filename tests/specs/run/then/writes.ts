@@ -0,0 +1,7 @@
+import { value } from "./generated.ts";
+
+console.log(value);
+Deno.writeTextFileSync(
+  new URL("./generated.ts", import.meta.url),
+  'export const value = "new";\n',
+);
@@ -0,0 +1,4 @@
+import { greeting } from "./greeting.ts";
+
+console.log(greeting);
+throw new Error("boom");
@@ -0,0 +1,4 @@
+import { greeting } from "./greeting.ts";
+
+console.log(greeting);
+Deno.exit(3);
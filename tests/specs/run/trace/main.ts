@@ -0,0 +1,5 @@
+import { greeting } from "./greeting.ts";
+
+console.log(greeting);
+// deno-lint-ignore no-explicit-any
+(globalThis as any).gc();
@@ -0,0 +1,16 @@
+const trace = JSON.parse(Deno.readTextFileSync("trace.json"));
+const names = new Set(trace.traceEvents.map((e: { name: string }) => e.name));
+const categories = new Set(
+  trace.traceEvents.map((e: { cat: string }) => e.cat),
+);
+console.log(Array.isArray(trace.traceEvents));
+console.log(names.has("load_module"));
+console.log(names.has("execute_main_module"));
+console.log(
+  trace.traceEvents.every((e: { ph: string; ts: number }) =>
+    e.ph === "X" && typeof e.ts === "number"
+  ),
+);
+// console.log goes through an op
+console.log(names.has("op_print"));
+console.log(categories.has("gc"));
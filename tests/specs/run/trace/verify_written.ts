@@ -0,0 +1,3 @@
+const trace = JSON.parse(Deno.readTextFileSync("trace.json"));
+const names = new Set(trace.traceEvents.map((e: { name: string }) => e.name));
+console.log(names.has("create_main_worker"));
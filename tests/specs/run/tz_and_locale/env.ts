@@ -0,0 +1 @@
+console.log(Deno.env.get("TZ"));
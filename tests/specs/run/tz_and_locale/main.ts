@@ -0,0 +1,6 @@
+const date = new Date(Date.UTC(2024, 0, 2, 3, 4, 5));
+console.log(date.toString());
+console.log(new Intl.DateTimeFormat(undefined, { dateStyle: "full" }).format(date));
+console.log(new Intl.NumberFormat().format(1234567.891));
+console.log(Intl.DateTimeFormat().resolvedOptions().timeZone);
+console.log(navigator.language);
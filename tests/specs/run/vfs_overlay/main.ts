@@ -0,0 +1,9 @@
+import { source } from "./mod.ts";
+
+console.log("import:", source);
+console.log("read:", Deno.readTextFileSync("data.txt"));
+
+Deno.writeTextFileSync("data.txt", "written");
+Deno.writeTextFileSync("new.txt", "created");
+console.log("read after write:", await Deno.readTextFile("data.txt"));
+console.log("new file:", Deno.readTextFileSync("new.txt"));
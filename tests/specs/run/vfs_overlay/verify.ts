@@ -0,0 +1,7 @@
+console.log("read:", Deno.readTextFileSync("data.txt"));
+try {
+  Deno.statSync("new.txt");
+  console.log("new.txt exists");
+} catch (err) {
+  console.log(err.name);
+}
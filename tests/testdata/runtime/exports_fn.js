@@ -0,0 +1,10 @@
+export const greeting = "hello";
+
+export function add(a, b) {
+  return a + b;
+}
+
+export async function greet(name) {
+  await Promise.resolve();
+  return `${greeting} ${name}`;
+}
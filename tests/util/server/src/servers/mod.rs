@@ -573,6 +573,32 @@ async fn main_server(
       *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
       Ok(res)
     }
+    (_, "/server_error_with_body.js") => {
+      let mut res = Response::new(string_body(
+        "<html>\n  <body>Database   unavailable</body>\n</html>\n",
+      ));
+      *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+      Ok(res)
+    }
+    (_, "/server_error_twice_then_ok.js") => {
+      // every third request succeeds, so a client has to retry twice
+      static REQUESTS: std::sync::atomic::AtomicUsize =
+        std::sync::atomic::AtomicUsize::new(0);
+      let count =
+        REQUESTS.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+      if count % 3 != 0 {
+        let mut res = Response::new(string_body("Database unavailable"));
+        *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        return Ok(res);
+      }
+      let mut res =
+        Response::new(string_body("console.log(\"loaded after retries\");"));
+      res.headers_mut().insert(
+        "content-type",
+        HeaderValue::from_static("application/javascript"),
+      );
+      Ok(res)
+    }
     (_, "/x_deno_warning.js") => {
       let mut res = Response::new(empty_body());
       *res.status_mut() = StatusCode::MOVED_PERMANENTLY;